@@ -176,9 +176,15 @@ impl CharacterCreation {
                     character.spellcasting = Some(SpellcastingData {
                         ability,
                         spells_known: self.selected_spells.clone(),
+                        spellbook: if class == CharacterClass::Wizard {
+                            self.selected_spells.clone()
+                        } else {
+                            Vec::new()
+                        },
                         spells_prepared: self.selected_spells.clone(), // For simplicity, prepared = known at level 1
                         cantrips_known: self.selected_cantrips.clone(),
                         spell_slots: create_level_1_spell_slots(class),
+                        pact_slots: class.pact_slots_at_level(1),
                     });
                 }
             }
@@ -265,9 +271,15 @@ impl CharacterCreation {
                     character.spellcasting = Some(SpellcastingData {
                         ability,
                         spells_known: self.selected_spells.clone(),
+                        spellbook: if class == CharacterClass::Wizard {
+                            self.selected_spells.clone()
+                        } else {
+                            Vec::new()
+                        },
                         spells_prepared: self.selected_spells.clone(),
                         cantrips_known: self.selected_cantrips.clone(),
                         spell_slots: create_level_1_spell_slots(class),
+                        pact_slots: class.pact_slots_at_level(1),
                     });
                 }
             }
@@ -285,7 +297,7 @@ fn create_level_1_spell_slots(class: CharacterClass) -> SpellSlots {
         | CharacterClass::Druid
         | CharacterClass::Sorcerer
         | CharacterClass::Wizard => 2,
-        CharacterClass::Warlock => 1, // Pact Magic
+        // Warlocks use Pact Magic slots instead
         _ => 0,
     };
 
@@ -21,7 +21,7 @@ pub struct NarrativeOutput {
 pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
     match effect {
         Effect::DiceRolled { roll, purpose } => Some(NarrativeOutput {
-            text: format!("{}: {} = {}", purpose, roll.expression, roll.total),
+            text: format!("{}: {} {}", purpose, roll.expression, roll.breakdown()),
             narrative_type: NarrativeType::System,
             status: None,
         }),
@@ -106,6 +106,30 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: None,
         }),
 
+        Effect::AcBonusApplied { source, bonus, .. } => Some(NarrativeOutput {
+            text: format!("AC +{bonus} from {source}."),
+            narrative_type: NarrativeType::Combat,
+            status: None,
+        }),
+
+        Effect::AcBonusEnded { source, .. } => Some(NarrativeOutput {
+            text: format!("{source} fades."),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
+        Effect::SpeedBonusApplied { source, feet, .. } => Some(NarrativeOutput {
+            text: format!("Speed +{feet} ft from {source}."),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
+        Effect::SpeedBonusEnded { source, .. } => Some(NarrativeOutput {
+            text: format!("{source} fades."),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
         Effect::ConditionRemoved { condition, .. } => Some(NarrativeOutput {
             text: format!("No longer {condition}."),
             narrative_type: NarrativeType::System,
@@ -118,8 +142,11 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: Some("Roll for initiative!".to_string()),
         }),
 
-        Effect::CombatEnded => Some(NarrativeOutput {
-            text: "Combat ends.".to_string(),
+        Effect::CombatEnded { summary } => Some(NarrativeOutput {
+            text: match summary {
+                Some(summary) => format!("Combat ends. {}", summary.describe()),
+                None => "Combat ends.".to_string(),
+            },
             narrative_type: NarrativeType::System,
             status: None,
         }),
@@ -180,6 +207,32 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: Some(format!("Level up! Now level {new_level}!")),
         }),
 
+        Effect::AsiAvailable { level } => Some(NarrativeOutput {
+            text: format!("Level {level}: Ability Score Improvement available!"),
+            narrative_type: NarrativeType::System,
+            status: Some("Ability Score Improvement available".to_string()),
+        }),
+
+        Effect::AbilityScoreImproved {
+            ability, new_score, ..
+        } => Some(NarrativeOutput {
+            text: format!("{} increased to {new_score}.", ability.name()),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
+        Effect::FeatChosen { feat_name, .. } => Some(NarrativeOutput {
+            text: format!("Gained the {feat_name} feat."),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
+        Effect::FightingStyleChosen { style } => Some(NarrativeOutput {
+            text: format!("Adopted the {style} fighting style."),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
         Effect::FeatureUsed {
             feature_name,
             uses_remaining,
@@ -189,12 +242,27 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: None,
         }),
 
+        Effect::FeatureRecharged {
+            feature_name,
+            uses_remaining,
+        } => Some(NarrativeOutput {
+            text: format!("{feature_name} recharged! ({uses_remaining} uses remaining)"),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
         Effect::SpellSlotUsed { level, remaining } => Some(NarrativeOutput {
             text: format!("Used a level {level} spell slot. ({remaining} remaining)"),
             narrative_type: NarrativeType::System,
             status: None,
         }),
 
+        Effect::PactSlotUsed { level, remaining } => Some(NarrativeOutput {
+            text: format!("Used a level {level} Pact Magic slot. ({remaining} remaining)"),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
         Effect::RestCompleted { rest_type } => {
             let rest_name = match rest_type {
                 chronicler_core::rules::RestType::Short => "short",
@@ -227,7 +295,36 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: None,
         }),
 
-        Effect::FactRemembered { .. } | Effect::ConsequenceRegistered { .. } => {
+        Effect::HelpGiven {
+            helper,
+            target_name,
+        } => Some(NarrativeOutput {
+            text: format!("{helper} is helping {target_name} (advantage on next check)."),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
+        Effect::GroupCheckResolved {
+            check_type,
+            successes,
+            participants,
+            dc,
+            success,
+        } => Some(NarrativeOutput {
+            text: format!(
+                "Group {check_type} check {}! ({successes}/{participants} passed DC {dc})",
+                if *success { "succeeded" } else { "failed" }
+            ),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
+        Effect::FactRemembered { .. }
+        | Effect::ConsequenceRegistered { .. }
+        | Effect::HelpUsed { .. }
+        | Effect::ActionUsed { .. }
+        | Effect::AttackMade { .. }
+        | Effect::ExtraActionGained { .. } => {
             // Internal effects - no UI output
             None
         }
@@ -245,6 +342,7 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             item_name,
             quantity,
             new_total,
+            ..
         } => {
             let qty_str = if *quantity > 1 {
                 format!("{quantity} x ")
@@ -258,6 +356,16 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             })
         }
 
+        Effect::AmmunitionSpent {
+            ammunition,
+            remaining,
+            ..
+        } => (*remaining == 0).then(|| NarrativeOutput {
+            text: format!("That was your last of the {}!", ammunition.to_lowercase()),
+            narrative_type: NarrativeType::System,
+            status: Some(format!("Out of {}", ammunition.to_lowercase())),
+        }),
+
         Effect::ItemRemoved {
             item_name,
             quantity,
@@ -365,6 +473,16 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: None,
         }),
 
+        Effect::DeathSavesSet {
+            successes,
+            failures,
+            ..
+        } => Some(NarrativeOutput {
+            text: format!("Death saves: {successes}/3 successes, {failures}/3 failures"),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
         Effect::CharacterDied { cause, .. } => Some(NarrativeOutput {
             text: format!("YOU HAVE DIED! Cause: {cause}"),
             narrative_type: NarrativeType::Combat,
@@ -401,6 +519,12 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: Some(format!("Lost concentration on {spell_name}!")),
         }),
 
+        Effect::ConcentrationStarted { spell_name, .. } => Some(NarrativeOutput {
+            text: format!("Concentrating on {spell_name}."),
+            narrative_type: NarrativeType::System,
+            status: Some(format!("Concentrating: {spell_name}")),
+        }),
+
         Effect::ConcentrationMaintained {
             spell_name,
             roll,
@@ -447,6 +571,58 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: None,
         }),
 
+        Effect::WildShapeStarted { stats, .. } => Some(NarrativeOutput {
+            text: format!(
+                "WILD SHAPE! Now a {} (AC {}, {} HP)",
+                stats.name, stats.armor_class, stats.hit_points.maximum
+            ),
+            narrative_type: NarrativeType::System,
+            status: Some(format!("Wild Shape: {}", stats.name)),
+        }),
+
+        Effect::WildShapeHpChanged {
+            amount,
+            new_current,
+            ..
+        } => {
+            let text = if *amount < 0 {
+                format!("Beast form takes {} damage ({new_current} HP left)", -amount)
+            } else {
+                format!("Beast form heals {amount} HP ({new_current} HP)")
+            };
+            Some(NarrativeOutput {
+                text,
+                narrative_type: NarrativeType::Combat,
+                status: None,
+            })
+        }
+
+        Effect::CombatantHpChanged {
+            name,
+            amount,
+            new_current,
+            ..
+        } => {
+            let text = if *amount < 0 {
+                format!("{name} takes {} damage ({new_current} HP left)", -amount)
+            } else if *amount > 0 {
+                format!("{name} heals {amount} HP ({new_current} HP)")
+            } else {
+                return None;
+            };
+            Some(NarrativeOutput {
+                text,
+                narrative_type: NarrativeType::Combat,
+                status: None,
+            })
+        }
+
+        Effect::WildShapeEnded { reason, .. } => Some(NarrativeOutput {
+            text: format!("Wild Shape ended: {reason}"),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
         // Quest effects
         Effect::QuestCreated { name, giver, .. } => {
             let giver_text = giver
@@ -575,6 +751,15 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: None,
         }),
 
+        Effect::LightLevelChanged { light_level } => Some(NarrativeOutput {
+            text: match light_level {
+                Some(level) => format!("The area is now in {level}."),
+                None => "Lighting returns to normal.".to_string(),
+            },
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
         Effect::AbilityScoreModified {
             ability,
             modifier,
@@ -588,6 +773,17 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             })
         }
 
+        Effect::ReputationChanged {
+            faction,
+            amount,
+            new_reputation,
+            ..
+        } => Some(NarrativeOutput {
+            text: format!("Reputation with {faction}: {amount:+} (now {new_reputation})"),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
         Effect::SpellSlotRestored {
             level,
             new_remaining,
@@ -597,6 +793,36 @@ pub fn narrative_for_effect(effect: &Effect) -> Option<NarrativeOutput> {
             status: None,
         }),
 
+        Effect::SpellPrepared { spell_name } => Some(NarrativeOutput {
+            text: format!("Prepared {spell_name}"),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
+        Effect::SpellUnprepared { spell_name } => Some(NarrativeOutput {
+            text: format!("{spell_name} is no longer prepared"),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
+        Effect::SpellCopied { spell_name, .. } => Some(NarrativeOutput {
+            text: format!("Copied {spell_name} into the spellbook"),
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
+        Effect::SpellLearned {
+            spell_name,
+            replaced,
+        } => Some(NarrativeOutput {
+            text: match replaced {
+                Some(replaced) => format!("Learned {spell_name} in place of {replaced}"),
+                None => format!("Learned {spell_name}"),
+            },
+            narrative_type: NarrativeType::System,
+            status: None,
+        }),
+
         Effect::StateAsserted {
             entity_name,
             state_type,
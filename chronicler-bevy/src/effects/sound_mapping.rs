@@ -21,7 +21,9 @@ pub fn sound_for_effect(effect: &Effect) -> Option<SoundEffect> {
 
         Effect::AttackMissed { .. } => Some(SoundEffect::Miss),
 
-        Effect::HpChanged { amount, .. } => {
+        Effect::HpChanged { amount, .. }
+        | Effect::WildShapeHpChanged { amount, .. }
+        | Effect::CombatantHpChanged { amount, .. } => {
             if *amount > 0 {
                 Some(SoundEffect::Heal)
             } else {
@@ -33,28 +35,41 @@ pub fn sound_for_effect(effect: &Effect) -> Option<SoundEffect> {
 
         Effect::LevelUp { .. } => Some(SoundEffect::LevelUp),
 
-        Effect::SpellSlotUsed { .. } => Some(SoundEffect::SpellCast),
+        Effect::SpellSlotUsed { .. } | Effect::PactSlotUsed { .. } => Some(SoundEffect::SpellCast),
 
         Effect::CharacterDied { .. } => Some(SoundEffect::Death),
 
         // Effects with no associated sound
         Effect::ConditionApplied { .. }
         | Effect::ConditionRemoved { .. }
-        | Effect::CombatEnded
+        | Effect::AcBonusApplied { .. }
+        | Effect::AcBonusEnded { .. }
+        | Effect::SpeedBonusApplied { .. }
+        | Effect::SpeedBonusEnded { .. }
+        | Effect::CombatEnded { .. }
         | Effect::TurnAdvanced { .. }
         | Effect::InitiativeRolled { .. }
         | Effect::CombatantAdded { .. }
         | Effect::TimeAdvanced { .. }
         | Effect::ExperienceGained { .. }
+        | Effect::AsiAvailable { .. }
+        | Effect::AbilityScoreImproved { .. }
+        | Effect::FeatChosen { .. }
+        | Effect::FightingStyleChosen { .. }
         | Effect::FeatureUsed { .. }
+        | Effect::FeatureRecharged { .. }
         | Effect::RestCompleted { .. }
         | Effect::CheckSucceeded { .. }
         | Effect::CheckFailed { .. }
+        | Effect::HelpGiven { .. }
+        | Effect::HelpUsed { .. }
+        | Effect::GroupCheckResolved { .. }
         | Effect::FactRemembered { .. }
         | Effect::ConsequenceRegistered { .. }
         | Effect::ConsequenceTriggered { .. }
         | Effect::ItemAdded { .. }
         | Effect::ItemRemoved { .. }
+        | Effect::AmmunitionSpent { .. }
         | Effect::ItemEquipped { .. }
         | Effect::ItemUnequipped { .. }
         | Effect::ItemUsed { .. }
@@ -63,14 +78,18 @@ pub fn sound_for_effect(effect: &Effect) -> Option<SoundEffect> {
         | Effect::AcChanged { .. }
         | Effect::DeathSaveFailure { .. }
         | Effect::DeathSavesReset { .. }
+        | Effect::DeathSavesSet { .. }
         | Effect::DeathSaveSuccess { .. }
         | Effect::Stabilized { .. }
         | Effect::ConcentrationBroken { .. }
+        | Effect::ConcentrationStarted { .. }
         | Effect::ConcentrationMaintained { .. }
         | Effect::LocationChanged { .. }
         | Effect::ClassResourceUsed { .. }
         | Effect::RageStarted { .. }
         | Effect::RageEnded { .. }
+        | Effect::WildShapeStarted { .. }
+        | Effect::WildShapeEnded { .. }
         | Effect::QuestCreated { .. }
         | Effect::QuestObjectiveAdded { .. }
         | Effect::QuestObjectiveCompleted { .. }
@@ -78,6 +97,9 @@ pub fn sound_for_effect(effect: &Effect) -> Option<SoundEffect> {
         | Effect::QuestFailed { .. }
         | Effect::QuestUpdated { .. }
         | Effect::SneakAttackUsed { .. }
+        | Effect::ActionUsed { .. }
+        | Effect::AttackMade { .. }
+        | Effect::ExtraActionGained { .. }
         // World-building effects - no sounds
         | Effect::NpcCreated { .. }
         | Effect::NpcUpdated { .. }
@@ -86,8 +108,14 @@ pub fn sound_for_effect(effect: &Effect) -> Option<SoundEffect> {
         | Effect::LocationCreated { .. }
         | Effect::LocationsConnected { .. }
         | Effect::LocationUpdated { .. }
+        | Effect::LightLevelChanged { .. }
         | Effect::AbilityScoreModified { .. }
         | Effect::SpellSlotRestored { .. }
+        | Effect::SpellPrepared { .. }
+        | Effect::SpellUnprepared { .. }
+        | Effect::SpellLearned { .. }
+        | Effect::SpellCopied { .. }
+        | Effect::ReputationChanged { .. }
         | Effect::StateAsserted { .. }
         | Effect::KnowledgeShared { .. }
         | Effect::EventScheduled { .. }
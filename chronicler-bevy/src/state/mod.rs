@@ -15,7 +15,7 @@ mod world_update;
 // Re-export all public types
 pub use app_state::{ActiveOverlay, AppState};
 pub use game_phase::GamePhase;
-pub use onboarding::OnboardingState;
+pub use onboarding::{HeroChoices, OnboardingState};
 pub use save_lists::{
     CharacterSaveList, GameSaveInfo, GameSaveList, PendingCharacterList, PendingGameList,
     PendingGameLoad, PendingSession,
@@ -1,18 +1,110 @@
 //! Onboarding state tracking.
 
 use bevy::prelude::*;
+use chronicler_core::character_builder::STANDARD_ARRAY;
+use chronicler_core::world::{Ability, AbilityScores, Background, CharacterClass, RaceType, Skill};
+use chronicler_core::{AbilityMethod, CharacterBuilder};
 
 /// Tracks whether the user has seen the onboarding modal.
 #[derive(Resource, Default)]
 pub struct OnboardingState {
     /// Whether the user has completed onboarding.
     pub has_seen: bool,
-    /// Current page of the onboarding flow (0-2).
+    /// Current page of the onboarding flow (0-3).
     pub current_page: usize,
+    /// Choices made on the quick-start hero page.
+    pub hero: HeroChoices,
     /// Saves path for persistence.
     saves_path: String,
 }
 
+/// Selections on the onboarding hero page, turned into a [`CharacterBuilder`].
+#[derive(Debug, Clone, Default)]
+pub struct HeroChoices {
+    pub name: String,
+    pub race: Option<RaceType>,
+    pub class: Option<CharacterClass>,
+    pub background: Option<Background>,
+    pub ability_method: AbilityMethod,
+    /// Scores for point buy (starting at 8 each).
+    pub point_buy: [u8; 6],
+    /// Rolled values, highest first, once the player has rolled.
+    pub rolled: Option<[u8; 6]>,
+    pub skills: Vec<Skill>,
+    pub half_elf_bonuses: [Ability; 2],
+}
+
+impl HeroChoices {
+    pub fn new() -> Self {
+        Self {
+            point_buy: [8; 6],
+            half_elf_bonuses: [Ability::Strength, Ability::Constitution],
+            ..Default::default()
+        }
+    }
+
+    /// Abilities in the order the class wants its highest values: saving
+    /// throw abilities first, then the rest.
+    pub fn ability_priority(&self) -> Vec<Ability> {
+        let mut order: Vec<Ability> = self
+            .class
+            .map(|class| class.data().saving_throws.to_vec())
+            .unwrap_or_default();
+        for ability in Ability::all() {
+            if !order.contains(&ability) {
+                order.push(ability);
+            }
+        }
+        order
+    }
+
+    /// The builder for the current selections; its `validate()` drives the
+    /// feedback shown on the page.
+    pub fn builder(&self) -> CharacterBuilder {
+        let mut builder = CharacterBuilder::new()
+            .name(self.name.trim())
+            .skills(self.skills.clone());
+        if let Some(race) = self.race {
+            builder = builder.race(race);
+            if race == RaceType::HalfElf {
+                builder = builder.half_elf_bonuses(self.half_elf_bonuses);
+            }
+        }
+        if let Some(class) = self.class {
+            builder = builder.class(class);
+        }
+        if let Some(background) = self.background {
+            builder = builder.background(background);
+        }
+
+        let abilities = Ability::all();
+        match self.ability_method {
+            AbilityMethod::StandardArray => {
+                let priority = self.ability_priority();
+                let assignment = std::array::from_fn(|i| (STANDARD_ARRAY[i], priority[i]));
+                builder.standard_array(assignment)
+            }
+            AbilityMethod::PointBuy => {
+                let mut scores = AbilityScores::default();
+                for (ability, value) in abilities.into_iter().zip(self.point_buy) {
+                    scores.set(ability, value);
+                }
+                builder.point_buy(scores)
+            }
+            AbilityMethod::Rolled => match self.rolled {
+                Some(rolled) => {
+                    let mut scores = AbilityScores::default();
+                    for (ability, value) in self.ability_priority().into_iter().zip(rolled) {
+                        scores.set(ability, value);
+                    }
+                    builder.rolled(scores)
+                }
+                None => builder.ability_method(AbilityMethod::Rolled),
+            },
+        }
+    }
+}
+
 impl OnboardingState {
     /// Load onboarding state from disk.
     pub fn load(saves_path: &str) -> Self {
@@ -25,6 +117,7 @@ impl OnboardingState {
                         return Self {
                             has_seen,
                             current_page: 0,
+                            hero: HeroChoices::new(),
                             saves_path: saves_path.to_string(),
                         };
                     }
@@ -34,6 +127,7 @@ impl OnboardingState {
         Self {
             has_seen: false,
             current_page: 0,
+            hero: HeroChoices::new(),
             saves_path: saves_path.to_string(),
         }
     }
@@ -25,7 +25,9 @@ pub struct GameSaveInfo {
     pub campaign_name: String,
     pub character_name: String,
     pub character_level: u8,
+    pub location: String,
     pub saved_at: String,
+    pub play_time: String,
 }
 
 /// List of saved games for the load game overlay.
@@ -59,7 +59,8 @@ pub fn handle_worker_responses(
             WorkerResponse::Complete {
                 narrative,
                 effects: _,
-                world_update,
+                diff,
+                mut world_update,
                 in_combat,
                 is_player_turn,
             } => {
@@ -72,6 +73,16 @@ pub fn handle_worker_responses(
                     );
                 }
                 app_state.streaming_text.clear();
+                // Carry the HUD forward through the turn's diff so enemy
+                // conditions and concentration, which the world doesn't
+                // track, stay on screen.
+                if let (Some(mut hud), Some(combat)) = (
+                    app_state.world.combat_hud.take(),
+                    world_update.combat.as_ref(),
+                ) {
+                    hud.update(combat, &diff);
+                    world_update.combat_hud = Some(hud);
+                }
                 app_state.world = world_update;
                 app_state.in_combat = in_combat;
                 app_state.is_player_turn = is_player_turn;
@@ -1,6 +1,6 @@
 //! Worker thread communication types.
 
-use chronicler_core::rules::Effect;
+use chronicler_core::rules::{Effect, WorldDiff};
 use chronicler_core::GameSession;
 use tokio::sync::mpsc;
 
@@ -36,6 +36,8 @@ pub enum WorkerResponse {
         narrative: String,
         /// All effects that were applied.
         effects: Vec<Effect>,
+        /// What those effects changed.
+        diff: WorldDiff,
         /// Updated world state for rendering.
         world_update: WorldUpdate,
         /// Whether combat is currently active.
@@ -157,6 +159,7 @@ async fn process_player_action(
                 .send(WorkerResponse::Complete {
                     narrative: response.narrative,
                     effects: response.effects,
+                    diff: response.diff,
                     world_update,
                     in_combat: response.in_combat,
                     is_player_turn: response.is_player_turn,
@@ -1,8 +1,8 @@
 //! World state snapshot for UI rendering.
 
 use chronicler_core::world::{
-    AbilityScores, CombatState, Condition, DeathSaves, GameMode, GameTime, HitPoints, Item, Quest,
-    Skill,
+    AbilityScores, CombatHud, CombatState, Condition, DeathSaves, EncumbranceLevel, GameMode,
+    GameTime, HitPoints, Item, Quest, Skill,
 };
 use chronicler_core::GameSession;
 use std::collections::HashMap;
@@ -15,6 +15,8 @@ pub struct WorldUpdate {
     pub player_hp: HitPoints,
     /// Current combat state if any.
     pub combat: Option<CombatState>,
+    /// Per-combatant HP, conditions, and concentration for the combat HUD.
+    pub combat_hud: Option<CombatHud>,
     /// Current game mode.
     pub mode: GameMode,
     /// Current game time.
@@ -51,6 +53,12 @@ pub struct WorldUpdate {
     pub equipped_armor: Option<String>,
     /// Inventory items.
     pub inventory_items: Vec<Item>,
+    /// Total weight of inventory items, in pounds.
+    pub inventory_weight: f32,
+    /// Total value of inventory items, in gold pieces.
+    pub inventory_value: f32,
+    /// How burdened the player is by their inventory.
+    pub encumbrance: EncumbranceLevel,
     /// Ability scores.
     pub ability_scores: AbilityScores,
     /// Skill proficiencies (skill -> proficiency level string).
@@ -78,6 +86,7 @@ impl Default for WorldUpdate {
         Self {
             player_hp: HitPoints::new(10),
             combat: None,
+            combat_hud: None,
             mode: GameMode::Exploration,
             game_time: GameTime::default(),
             player_name: "???".to_string(),
@@ -96,6 +105,9 @@ impl Default for WorldUpdate {
             equipped_weapon: None,
             equipped_armor: None,
             inventory_items: Vec::new(),
+            inventory_weight: 0.0,
+            inventory_value: 0.0,
+            encumbrance: EncumbranceLevel::Unencumbered,
             ability_scores: AbilityScores::default(),
             skill_proficiencies: HashMap::new(),
             proficiency_bonus: 2,
@@ -118,6 +130,7 @@ impl WorldUpdate {
         Self {
             player_hp: character.hit_points.clone(),
             combat: world.combat.clone(),
+            combat_hud: CombatHud::from_world(world),
             mode: world.mode,
             game_time: world.game_time.clone(),
             player_name: character.name.clone(),
@@ -151,6 +164,11 @@ impl WorldUpdate {
                 .as_ref()
                 .map(|a| a.base.name.clone()),
             inventory_items: character.inventory.items.clone(),
+            inventory_weight: character.inventory.total_weight(),
+            inventory_value: character.inventory.total_value(),
+            encumbrance: character
+                .inventory
+                .encumbrance(character.ability_scores.strength),
             ability_scores: character.ability_scores.clone(),
             skill_proficiencies: character
                 .skill_proficiencies
@@ -163,11 +181,20 @@ impl WorldUpdate {
                 .spellcasting
                 .as_ref()
                 .map(|sc| {
-                    sc.spell_slots
+                    let mut slots: Vec<(u8, u8)> = sc
+                        .spell_slots
                         .slots
                         .iter()
                         .map(|slot| (slot.available(), slot.total))
-                        .collect()
+                        .collect();
+                    // Pact Magic slots are shown alongside regular slots of their level
+                    if let Some(pact) = &sc.pact_slots {
+                        if let Some(slot) = slots.get_mut(pact.level as usize - 1) {
+                            slot.0 += pact.available();
+                            slot.1 += pact.total;
+                        }
+                    }
+                    slots
                 })
                 .unwrap_or_default(),
             known_spells: character
@@ -50,7 +50,16 @@ pub fn main_ui_system(
             // Render overlays (Settings and LoadCharacter can be accessed from main menu)
             match app_state.overlay {
                 ActiveOverlay::Onboarding => {
-                    overlays::render_onboarding(ctx, &mut onboarding, &mut app_state);
+                    if let Some(character) =
+                        overlays::render_onboarding(ctx, &mut onboarding, &mut app_state)
+                    {
+                        // Start game with the hero built during onboarding
+                        commands.insert_resource(ReadyToStart {
+                            character,
+                            campaign_name: "The Dragon's Lair".to_string(),
+                        });
+                        next_phase.set(GamePhase::Playing);
+                    }
                 }
                 ActiveOverlay::Settings => {
                     overlays::render_settings(
@@ -162,7 +171,7 @@ pub fn main_ui_system(
             // CentralPanel must come after side/top/bottom panels
             panels::render_narrative_panel(ctx, &app_state, time.elapsed_secs_f64());
             // Windows can be rendered anytime (they float)
-            panels::render_combat_panel(ctx, &app_state);
+            overlays::render_combat_hud(ctx, &app_state);
 
             // Render overlays if active
             match app_state.overlay {
@@ -355,48 +364,25 @@ pub fn handle_keyboard_input(
 
 /// List all game saves in the saves directory.
 async fn list_game_saves(dir: &str) -> Result<Vec<GameSaveInfo>, String> {
-    use tokio::fs;
-
-    let mut saves = Vec::new();
-    let dir_path = std::path::Path::new(dir);
-
-    if !dir_path.exists() {
-        return Ok(saves);
-    }
-
-    let mut entries = fs::read_dir(dir_path).await.map_err(|e| e.to_string())?;
-
-    while let Ok(Some(entry)) = entries.next_entry().await {
-        let path = entry.path();
-
-        // Skip directories and non-json files
-        if path.is_dir() || path.extension().map(|e| e != "json").unwrap_or(true) {
-            continue;
-        }
-
-        // Skip character saves (they're in saves/characters/)
-        if path
-            .file_name()
-            .map(|n| n.to_string_lossy().contains("character"))
-            .unwrap_or(false)
-        {
-            continue;
-        }
-
-        // Try to peek at the save metadata
-        if let Ok(metadata) = chronicler_core::persist::SavedCampaign::peek_metadata(&path).await {
-            saves.push(GameSaveInfo {
-                path: path.to_string_lossy().to_string(),
-                campaign_name: metadata.campaign_name,
-                character_name: metadata.character_name,
-                character_level: metadata.level,
-                saved_at: metadata.saved_at,
-            });
-        }
+    if !std::path::Path::new(dir).exists() {
+        return Ok(Vec::new());
     }
 
-    // Sort by save time (most recent first)
-    saves.sort_by(|a, b| b.saved_at.cmp(&a.saved_at));
-
-    Ok(saves)
+    // Reads only each save's metadata header, most recent first
+    let saves = chronicler_core::persist::list_saves(dir)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(saves
+        .into_iter()
+        .map(|save| GameSaveInfo {
+            play_time: save.metadata.play_time_display(),
+            path: save.path,
+            campaign_name: save.metadata.campaign_name,
+            character_name: save.metadata.character_name,
+            character_level: save.metadata.level,
+            location: save.metadata.location,
+            saved_at: save.metadata.saved_at,
+        })
+        .collect())
 }
@@ -0,0 +1,95 @@
+//! Combat HUD overlay: initiative order with HP bars and status.
+
+use bevy_egui::egui;
+
+use crate::state::AppState;
+
+/// Render the combat HUD while in combat.
+pub fn render_combat_hud(ctx: &egui::Context, app_state: &AppState) {
+    if !app_state.in_combat {
+        return;
+    }
+    let Some(ref hud) = app_state.world.combat_hud else {
+        return;
+    };
+
+    // Position combat window below top bar, use responsive width
+    let screen = ctx.screen_rect();
+    let max_width = (screen.width() * 0.3).clamp(180.0, 280.0);
+
+    egui::Window::new("Combat")
+        .collapsible(true)
+        .resizable(true)
+        .default_pos([10.0, 50.0])
+        .default_width(max_width)
+        .show(ctx, |ui| {
+            ui.label(format!("Round {}", hud.round));
+            ui.separator();
+
+            for (i, combatant) in hud.combatants.iter().enumerate() {
+                let is_current = i == hud.turn_index;
+
+                let name_color = if combatant.is_player {
+                    egui::Color32::from_rgb(100, 180, 255)
+                } else if combatant.is_ally {
+                    egui::Color32::GREEN
+                } else {
+                    egui::Color32::RED
+                };
+
+                ui.horizontal(|ui| {
+                    if is_current {
+                        ui.label(
+                            egui::RichText::new(">")
+                                .color(egui::Color32::YELLOW)
+                                .strong(),
+                        );
+                    } else {
+                        ui.label(" ");
+                    }
+                    ui.label(egui::RichText::new(&combatant.name).color(name_color));
+                    if let Some(ref spell) = combatant.concentrating_on {
+                        ui.label(
+                            egui::RichText::new(format!("◆ {spell}"))
+                                .color(egui::Color32::from_rgb(180, 140, 255))
+                                .small(),
+                        )
+                        .on_hover_text(format!("Concentrating on {spell}"));
+                    }
+                });
+
+                let fraction = combatant.hp_fraction();
+                let bar_color = if fraction > 0.5 {
+                    egui::Color32::from_rgb(60, 160, 60)
+                } else if combatant.current_hp > 0 {
+                    egui::Color32::from_rgb(200, 170, 40)
+                } else {
+                    egui::Color32::from_rgb(180, 40, 40)
+                };
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .fill(bar_color)
+                        .text(format!("{}/{}", combatant.current_hp, combatant.max_hp)),
+                );
+
+                if !combatant.conditions.is_empty() {
+                    let names: Vec<_> = combatant.conditions.iter().map(|c| c.name()).collect();
+                    ui.label(
+                        egui::RichText::new(names.join(", "))
+                            .color(egui::Color32::from_rgb(255, 150, 100))
+                            .small(),
+                    );
+                }
+                ui.add_space(4.0);
+            }
+
+            if app_state.is_player_turn {
+                ui.separator();
+                ui.label(
+                    egui::RichText::new("Your turn!")
+                        .color(egui::Color32::YELLOW)
+                        .strong(),
+                );
+            }
+        });
+}
@@ -1,6 +1,7 @@
 //! Inventory overlay.
 
 use bevy_egui::egui;
+use chronicler_core::world::EncumbranceLevel;
 
 use crate::state::AppState;
 
@@ -33,6 +34,21 @@ pub fn render_inventory(ctx: &egui::Context, app_state: &AppState) {
                 );
             });
 
+            // Carried weight, value, and encumbrance
+            ui.horizontal(|ui| {
+                ui.label(format!(
+                    "Weight: {:.1} lb",
+                    app_state.world.inventory_weight
+                ));
+                ui.label(format!("Value: {:.0} gp", app_state.world.inventory_value));
+                let encumbrance = app_state.world.encumbrance;
+                ui.label(
+                    egui::RichText::new(encumbrance.name())
+                        .color(encumbrance_color(encumbrance))
+                        .strong(),
+                );
+            });
+
             ui.separator();
 
             // Equipped items
@@ -108,3 +124,13 @@ pub fn render_inventory(ctx: &egui::Context, app_state: &AppState) {
             );
         });
 }
+
+/// Green when unburdened, shading to red as the load grows.
+fn encumbrance_color(level: EncumbranceLevel) -> egui::Color32 {
+    match level {
+        EncumbranceLevel::Unencumbered => egui::Color32::from_rgb(50, 205, 50),
+        EncumbranceLevel::Encumbered => egui::Color32::from_rgb(255, 215, 0),
+        EncumbranceLevel::HeavilyEncumbered => egui::Color32::from_rgb(255, 140, 0),
+        EncumbranceLevel::OverCapacity => egui::Color32::from_rgb(220, 20, 60),
+    }
+}
@@ -66,7 +66,7 @@ pub fn render_load_character(
                             let meta = &save.metadata;
 
                             let text = format!(
-                                "{} - Level {} {} {}{}",
+                                "{} - Level {} {} {}{}\nSaved {}",
                                 meta.name,
                                 meta.level,
                                 meta.race,
@@ -75,7 +75,8 @@ pub fn render_load_character(
                                     " (has backstory)"
                                 } else {
                                     ""
-                                }
+                                },
+                                super::load_game::last_played(&meta.saved_at)
                             );
 
                             if ui.selectable_label(is_selected, text).clicked() {
@@ -64,11 +64,13 @@ pub fn render_load_game(
                             let is_selected = save_list.selected == Some(i);
 
                             let text = format!(
-                                "{} - {} (Level {})\nSaved: {}",
+                                "{} - {} (Level {})\n{} | Last played {} | Played {}",
                                 save.campaign_name,
                                 save.character_name,
                                 save.character_level,
-                                save.saved_at
+                                save.location,
+                                last_played(&save.saved_at),
+                                save.play_time
                             );
 
                             if ui.selectable_label(is_selected, text).clicked() {
@@ -109,3 +111,21 @@ pub fn render_load_game(
 
     selected_path
 }
+
+/// How long ago a save was written, from its Unix-seconds timestamp.
+pub(crate) fn last_played(saved_at: &str) -> String {
+    let Ok(saved) = saved_at.parse::<u64>() else {
+        return "unknown".to_string();
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(saved);
+    let minutes = now.saturating_sub(saved) / 60;
+    match minutes {
+        0 => "just now".to_string(),
+        1..=59 => format!("{minutes}m ago"),
+        60..=1439 => format!("{}h ago", minutes / 60),
+        _ => format!("{}d ago", minutes / 1440),
+    }
+}
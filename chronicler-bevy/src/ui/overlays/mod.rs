@@ -1,6 +1,7 @@
 //! Overlay windows for inventory, character sheet, etc.
 
 mod character_sheet;
+mod combat_hud;
 mod help;
 mod inventory;
 mod load_character;
@@ -11,6 +12,7 @@ mod settings;
 mod spell_detail;
 
 pub use character_sheet::render_character_sheet;
+pub use combat_hud::render_combat_hud;
 pub use help::render_help;
 pub use inventory::render_inventory;
 pub use load_character::render_load_character;
@@ -1,15 +1,23 @@
 //! Onboarding overlay.
 
 use bevy_egui::egui;
+use chronicler_core::character_builder::{point_buy_cost, roll_ability_scores, STANDARD_ARRAY};
+use chronicler_core::world::{Ability, Background, Character, CharacterClass, RaceType};
+use chronicler_core::AbilityMethod;
 
-use crate::state::{ActiveOverlay, AppState, OnboardingState};
+use crate::state::{ActiveOverlay, AppState, HeroChoices, OnboardingState};
 
-/// Render the onboarding modal. Returns true when onboarding is complete.
+/// Tutorial pages followed by the hero page.
+const PAGE_COUNT: usize = 4;
+
+/// Render the onboarding modal. Returns the hero built on the last page when
+/// the player starts an adventure with it.
 pub fn render_onboarding(
     ctx: &egui::Context,
     onboarding: &mut OnboardingState,
     app_state: &mut AppState,
-) -> bool {
+) -> Option<Character> {
+    let mut hero = None;
     let mut completed = false;
 
     let screen = ctx.screen_rect();
@@ -24,7 +32,7 @@ pub fn render_onboarding(
         .show(ctx, |ui| {
             // Page indicator
             ui.horizontal(|ui| {
-                for i in 0..3 {
+                for i in 0..PAGE_COUNT {
                     let color = if i == onboarding.current_page {
                         egui::Color32::from_rgb(218, 165, 32) // Gold for current
                     } else {
@@ -41,6 +49,7 @@ pub fn render_onboarding(
                 0 => render_onboarding_page_welcome(ui),
                 1 => render_onboarding_page_how_to_play(ui),
                 2 => render_onboarding_page_good_to_know(ui),
+                3 => render_onboarding_page_hero(ui, &mut onboarding.hero, app_state),
                 _ => {}
             }
 
@@ -56,7 +65,7 @@ pub fn render_onboarding(
                 }
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if onboarding.current_page < 2 {
+                    if onboarding.current_page < PAGE_COUNT - 1 {
                         if ui
                             .button(
                                 egui::RichText::new("Next >")
@@ -67,18 +76,31 @@ pub fn render_onboarding(
                             app_state.play_click();
                             onboarding.current_page += 1;
                         }
-                    } else if ui
-                        .button(
-                            egui::RichText::new("Begin Adventure!")
-                                .color(egui::Color32::from_rgb(100, 200, 100))
-                                .strong(),
-                        )
-                        .clicked()
-                    {
-                        app_state.play_click();
-                        onboarding.complete();
-                        app_state.overlay = ActiveOverlay::None;
-                        completed = true;
+                    } else {
+                        let builder = onboarding.hero.builder();
+                        let ready = builder.validate().is_empty();
+                        if ui
+                            .add_enabled(
+                                ready,
+                                egui::Button::new(
+                                    egui::RichText::new("Begin Adventure!")
+                                        .color(egui::Color32::from_rgb(100, 200, 100))
+                                        .strong(),
+                                ),
+                            )
+                            .clicked()
+                        {
+                            app_state.play_click();
+                            match builder.build() {
+                                Ok(character) => {
+                                    onboarding.complete();
+                                    app_state.overlay = ActiveOverlay::None;
+                                    hero = Some(character);
+                                    completed = true;
+                                }
+                                Err(e) => app_state.error_message = Some(e.to_string()),
+                            }
+                        }
                     }
 
                     // Skip link on any page
@@ -100,7 +122,7 @@ pub fn render_onboarding(
             });
         });
 
-    completed
+    hero
 }
 
 /// Page 1: Welcome
@@ -268,3 +290,213 @@ fn render_onboarding_page_good_to_know(ui: &mut egui::Ui) {
         );
     });
 }
+
+/// Page 4: Create Your Hero
+fn render_onboarding_page_hero(
+    ui: &mut egui::Ui,
+    hero: &mut HeroChoices,
+    app_state: &mut AppState,
+) {
+    ui.vertical_centered(|ui| {
+        ui.heading(
+            egui::RichText::new("Create Your Hero")
+                .color(egui::Color32::from_rgb(218, 165, 32))
+                .size(26.0),
+        );
+    });
+
+    ui.add_space(10.0);
+
+    egui::ScrollArea::vertical()
+        .max_height(260.0)
+        .show(ui, |ui| {
+            egui::Grid::new("onboarding_hero_choices")
+                .num_columns(2)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut hero.name);
+                    ui.end_row();
+
+                    ui.label("Race");
+                    egui::ComboBox::from_id_salt("onboarding_race")
+                        .selected_text(hero.race.map_or("Choose...", |r| r.name()))
+                        .show_ui(ui, |ui| {
+                            for race in RaceType::all() {
+                                ui.selectable_value(&mut hero.race, Some(*race), race.name());
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Class");
+                    let previous_class = hero.class;
+                    egui::ComboBox::from_id_salt("onboarding_class")
+                        .selected_text(hero.class.map_or("Choose...", |c| c.name()))
+                        .show_ui(ui, |ui| {
+                            for class in CharacterClass::all() {
+                                ui.selectable_value(&mut hero.class, Some(*class), class.name());
+                            }
+                        });
+                    if hero.class != previous_class {
+                        // Skill options depend on the class
+                        hero.skills.clear();
+                    }
+                    ui.end_row();
+
+                    ui.label("Background");
+                    egui::ComboBox::from_id_salt("onboarding_background")
+                        .selected_text(hero.background.map_or("Choose...", |b| b.name()))
+                        .show_ui(ui, |ui| {
+                            for background in Background::all() {
+                                ui.selectable_value(
+                                    &mut hero.background,
+                                    Some(*background),
+                                    background.name(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label("Abilities");
+                    egui::ComboBox::from_id_salt("onboarding_ability_method")
+                        .selected_text(hero.ability_method.name())
+                        .show_ui(ui, |ui| {
+                            for method in AbilityMethod::all() {
+                                ui.selectable_value(
+                                    &mut hero.ability_method,
+                                    *method,
+                                    method.name(),
+                                )
+                                .on_hover_text(method.description());
+                            }
+                        });
+                    ui.end_row();
+                });
+
+            ui.add_space(6.0);
+            render_hero_abilities(ui, hero, app_state);
+
+            if hero.race == Some(RaceType::HalfElf) {
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.label("Half-Elf +1 to:");
+                    for (slot, bonus) in hero.half_elf_bonuses.iter_mut().enumerate() {
+                        egui::ComboBox::from_id_salt(("onboarding_half_elf", slot))
+                            .selected_text(bonus.abbreviation())
+                            .show_ui(ui, |ui| {
+                                for ability in Ability::all() {
+                                    ui.selectable_value(bonus, ability, ability.abbreviation());
+                                }
+                            });
+                    }
+                });
+            }
+
+            if let Some(class) = hero.class {
+                let data = class.data();
+                ui.add_space(6.0);
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Skills ({}/{} chosen)",
+                        hero.skills.len(),
+                        data.skill_count
+                    ))
+                    .strong(),
+                );
+                ui.horizontal_wrapped(|ui| {
+                    for skill in data.skill_options {
+                        let mut chosen = hero.skills.contains(skill);
+                        if ui.checkbox(&mut chosen, skill.name()).changed() {
+                            if chosen {
+                                hero.skills.push(*skill);
+                            } else {
+                                hero.skills.retain(|s| s != skill);
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+    // Inline validation from the builder
+    ui.add_space(6.0);
+    let problems = hero.builder().validate();
+    if problems.is_empty() {
+        ui.label(
+            egui::RichText::new("Ready to adventure!")
+                .color(egui::Color32::from_rgb(100, 200, 100)),
+        );
+    } else {
+        for problem in problems {
+            ui.label(
+                egui::RichText::new(format!("- {problem}"))
+                    .color(egui::Color32::from_rgb(220, 120, 100))
+                    .small(),
+            );
+        }
+    }
+}
+
+/// Ability scores for the chosen method, with point-buy controls and budget.
+fn render_hero_abilities(ui: &mut egui::Ui, hero: &mut HeroChoices, app_state: &mut AppState) {
+    match hero.ability_method {
+        AbilityMethod::PointBuy => {
+            let remaining = hero.builder().point_buy_remaining().unwrap_or(0);
+            let color = if remaining < 0 {
+                egui::Color32::from_rgb(220, 120, 100)
+            } else {
+                egui::Color32::from_rgb(218, 165, 32)
+            };
+            ui.label(egui::RichText::new(format!("Points remaining: {remaining}")).color(color));
+            ui.horizontal_wrapped(|ui| {
+                for (ability, score) in Ability::all().into_iter().zip(hero.point_buy.iter_mut()) {
+                    ui.label(ability.abbreviation());
+                    if ui.small_button("-").clicked() && *score > 8 {
+                        *score -= 1;
+                    }
+                    ui.label(egui::RichText::new(score.to_string()).strong());
+                    let next_cost = point_buy_cost(*score + 1).zip(point_buy_cost(*score));
+                    let affordable = next_cost
+                        .is_some_and(|(next, current)| (next - current) as i32 <= remaining);
+                    if ui
+                        .add_enabled(affordable, egui::Button::new("+").small())
+                        .clicked()
+                    {
+                        *score += 1;
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+        }
+        AbilityMethod::StandardArray | AbilityMethod::Rolled => {
+            let values = match hero.ability_method {
+                AbilityMethod::Rolled => hero.rolled,
+                _ => Some(STANDARD_ARRAY),
+            };
+            ui.horizontal_wrapped(|ui| {
+                if let Some(values) = values {
+                    for (ability, value) in hero.ability_priority().into_iter().zip(values) {
+                        ui.label(format!("{} {}", ability.abbreviation(), value));
+                        ui.add_space(6.0);
+                    }
+                }
+                if hero.ability_method == AbilityMethod::Rolled {
+                    let label = if hero.rolled.is_some() {
+                        "Reroll"
+                    } else {
+                        "Roll 4d6"
+                    };
+                    if ui.button(label).clicked() {
+                        app_state.play_click();
+                        hero.rolled = Some(roll_ability_scores());
+                    }
+                }
+            });
+            ui.label(
+                egui::RichText::new("Highest values go to your class's saving throw abilities.")
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+        }
+    }
+}
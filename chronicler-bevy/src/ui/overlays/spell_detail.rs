@@ -1,7 +1,7 @@
 //! Spell detail overlay.
 
 use bevy_egui::egui;
-use chronicler_core::spells::get_spell;
+use chronicler_core::spells::find_spell;
 
 use crate::state::AppState;
 
@@ -12,7 +12,7 @@ pub fn render_spell_detail(ctx: &egui::Context, app_state: &mut AppState) {
         None => return,
     };
 
-    let spell_data = get_spell(&spell_name);
+    let spell_data = find_spell(&spell_name);
 
     egui::Window::new("Spell Details")
         .collapsible(false)
@@ -29,20 +29,8 @@ pub fn render_spell_detail(ctx: &egui::Context, app_state: &mut AppState) {
                             .color(egui::Color32::from_rgb(100, 180, 255)),
                     );
 
-                    let level_text = if spell.level == 0 {
-                        format!("{} cantrip", spell.school.name())
-                    } else {
-                        let suffix = match spell.level {
-                            1 => "st",
-                            2 => "nd",
-                            3 => "rd",
-                            _ => "th",
-                        };
-                        format!("{}{}-level {}", spell.level, suffix, spell.school.name())
-                    };
-
                     ui.label(
-                        egui::RichText::new(level_text)
+                        egui::RichText::new(spell.level_and_school())
                             .italics()
                             .color(egui::Color32::LIGHT_GRAY),
                     );
@@ -75,12 +63,11 @@ pub fn render_spell_detail(ctx: &egui::Context, app_state: &mut AppState) {
                             ui.end_row();
 
                             ui.label(egui::RichText::new("Duration:").strong());
-                            let duration_text = if spell.concentration {
-                                format!("Concentration, {}", spell.duration.description())
-                            } else {
-                                spell.duration.description().to_string()
-                            };
-                            ui.label(duration_text);
+                            ui.label(spell.duration.description());
+                            ui.end_row();
+
+                            ui.label(egui::RichText::new("Concentration:").strong());
+                            ui.label(if spell.concentration { "Yes" } else { "No" });
                             ui.end_row();
                         });
 
@@ -333,6 +333,7 @@ pub fn render_narrative_panel(ctx: &egui::Context, app_state: &AppState, _curren
                         NarrativeType::NpcDialogue => egui::Color32::from_rgb(200, 200, 150), // Tan
                         NarrativeType::Combat => egui::Color32::from_rgb(255, 100, 100),      // Red
                         NarrativeType::System => egui::Color32::from_rgb(180, 180, 180), // Gray
+                        NarrativeType::Mechanics => egui::Color32::from_rgb(150, 200, 150), // Green
                     };
 
                     let prefix = match entry.entry_type {
@@ -341,6 +342,7 @@ pub fn render_narrative_panel(ctx: &egui::Context, app_state: &AppState, _curren
                         NarrativeType::NpcDialogue => "\"",
                         NarrativeType::Combat => "[Combat] ",
                         NarrativeType::System => "[System] ",
+                        NarrativeType::Mechanics => "[Rules] ",
                     };
 
                     // Split by paragraph breaks (double newlines first, then single)
@@ -709,77 +711,6 @@ pub fn render_character_panel(ctx: &egui::Context, app_state: &mut AppState) {
         });
 }
 
-/// Render the combat panel (shows when in combat).
-pub fn render_combat_panel(ctx: &egui::Context, app_state: &AppState) {
-    if !app_state.in_combat {
-        return;
-    }
-
-    if let Some(ref combat) = app_state.world.combat {
-        // Position combat window below top bar, use responsive width
-        let screen = ctx.screen_rect();
-        let max_width = (screen.width() * 0.3).clamp(150.0, 250.0);
-
-        egui::Window::new("Combat")
-            .collapsible(true)
-            .resizable(true)
-            .default_pos([10.0, 50.0])
-            .default_width(max_width)
-            .show(ctx, |ui| {
-                ui.label(format!("Round {}", combat.round));
-                ui.separator();
-
-                // Initiative order
-                for (i, combatant) in combat.combatants.iter().enumerate() {
-                    let is_current = i == combat.turn_index;
-                    let prefix = if is_current { "> " } else { "  " };
-
-                    let hp_text = format!("{}/{}", combatant.current_hp, combatant.max_hp);
-                    let hp_color = if combatant.current_hp as f32 / combatant.max_hp as f32 > 0.5 {
-                        egui::Color32::GREEN
-                    } else if combatant.current_hp > 0 {
-                        egui::Color32::YELLOW
-                    } else {
-                        egui::Color32::RED
-                    };
-
-                    ui.horizontal(|ui| {
-                        if is_current {
-                            ui.label(
-                                egui::RichText::new(prefix)
-                                    .color(egui::Color32::YELLOW)
-                                    .strong(),
-                            );
-                        } else {
-                            ui.label(prefix);
-                        }
-
-                        let name_color = if combatant.is_player {
-                            egui::Color32::from_rgb(100, 180, 255)
-                        } else if combatant.is_ally {
-                            egui::Color32::GREEN
-                        } else {
-                            egui::Color32::RED
-                        };
-
-                        ui.label(egui::RichText::new(&combatant.name).color(name_color));
-                        ui.label(format!("({})", combatant.initiative));
-                        ui.label(egui::RichText::new(hp_text).color(hp_color));
-                    });
-                }
-
-                if app_state.is_player_turn {
-                    ui.separator();
-                    ui.label(
-                        egui::RichText::new("Your turn!")
-                            .color(egui::Color32::YELLOW)
-                            .strong(),
-                    );
-                }
-            });
-    }
-}
-
 /// Render the game over screen.
 pub fn render_game_over(
     ctx: &egui::Context,
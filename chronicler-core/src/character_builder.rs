@@ -3,10 +3,11 @@
 //! Provides a step-by-step builder for creating new characters with
 //! proper D&D 5e rules for ability scores, class features, and proficiencies.
 
+use crate::rules::Resistance;
 use crate::world::{
     Ability, AbilityScores, Background, Character, CharacterClass, ClassLevel, ClassResources,
-    HitDice, HitPoints, ProficiencyLevel, Race, RaceType, Skill, SlotInfo, Speed, SpellSlots,
-    SpellcastingData,
+    HitDice, HitPoints, Item, ItemType, ProficiencyLevel, Race, RaceType, Skill, SlotInfo, Speed,
+    SpellSlots, SpellcastingData, MULTICLASS_MINIMUM,
 };
 use std::collections::{HashMap, HashSet};
 
@@ -86,16 +87,22 @@ pub struct CharacterBuilder {
 }
 
 /// Error from character building.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BuilderError {
     MissingName,
     MissingRace,
     MissingClass,
     MissingBackground,
     MissingAbilityScores,
-    InvalidSkillCount { expected: usize, got: usize },
+    InvalidPointBuy(String),
+    InvalidSkillCount {
+        expected: usize,
+        got: usize,
+    },
     SkillNotAvailable(Skill),
     HalfElfNeedsBonusAbilities,
+    /// Ability scores too low to multiclass into or out of `class`.
+    MulticlassPrerequisite(CharacterClass),
 }
 
 impl std::fmt::Display for BuilderError {
@@ -106,6 +113,7 @@ impl std::fmt::Display for BuilderError {
             BuilderError::MissingClass => write!(f, "Class selection is required"),
             BuilderError::MissingBackground => write!(f, "Background selection is required"),
             BuilderError::MissingAbilityScores => write!(f, "Ability scores are required"),
+            BuilderError::InvalidPointBuy(reason) => write!(f, "Invalid point buy: {reason}"),
             BuilderError::InvalidSkillCount { expected, got } => {
                 write!(f, "Expected {expected} skills, got {got}")
             }
@@ -115,6 +123,24 @@ impl std::fmt::Display for BuilderError {
             BuilderError::HalfElfNeedsBonusAbilities => {
                 write!(f, "Half-Elf requires two additional ability bonuses")
             }
+            BuilderError::MulticlassPrerequisite(class) => {
+                let abilities: Vec<_> = class
+                    .multiclass_abilities()
+                    .iter()
+                    .map(|a| a.name())
+                    .collect();
+                let joiner = if *class == CharacterClass::Fighter {
+                    " or "
+                } else {
+                    " and "
+                };
+                write!(
+                    f,
+                    "Multiclassing with {} requires {} {MULTICLASS_MINIMUM}",
+                    class.name(),
+                    abilities.join(joiner)
+                )
+            }
         }
     }
 }
@@ -208,8 +234,76 @@ impl CharacterBuilder {
         self
     }
 
-    /// Build the character, returning an error if any required field is missing.
+    /// Points left to spend, when scores are being set by point buy.
+    ///
+    /// Negative when the scores cost more than the budget, and `None` for
+    /// other methods or while a score is outside the 8-15 range.
+    pub fn point_buy_remaining(&self) -> Option<i32> {
+        if self.ability_method != AbilityMethod::PointBuy {
+            return None;
+        }
+        let scores = self.ability_scores.as_ref()?;
+        let mut spent = 0i32;
+        for ability in Ability::all() {
+            spent += point_buy_cost(scores.get(ability))? as i32;
+        }
+        Some(POINT_BUY_TOTAL as i32 - spent)
+    }
+
+    /// Every problem that would stop [`build`](Self::build), in the order
+    /// `build` reports them. Empty once the character is ready.
+    pub fn validate(&self) -> Vec<BuilderError> {
+        let mut errors = Vec::new();
+        if self
+            .name
+            .as_deref()
+            .is_none_or(|name| name.trim().is_empty())
+        {
+            errors.push(BuilderError::MissingName);
+        }
+        if self.race.is_none() {
+            errors.push(BuilderError::MissingRace);
+        }
+        if self.class.is_none() {
+            errors.push(BuilderError::MissingClass);
+        }
+        if self.background.is_none() {
+            errors.push(BuilderError::MissingBackground);
+        }
+        match &self.ability_scores {
+            None => errors.push(BuilderError::MissingAbilityScores),
+            Some(scores) if self.ability_method == AbilityMethod::PointBuy => {
+                if let Err(reason) = validate_point_buy(scores) {
+                    errors.push(BuilderError::InvalidPointBuy(reason));
+                }
+            }
+            Some(_) => {}
+        }
+        if self.race == Some(RaceType::HalfElf) && self.half_elf_bonus_abilities.is_none() {
+            errors.push(BuilderError::HalfElfNeedsBonusAbilities);
+        }
+        if let Some(class) = self.class {
+            let class_data = class.data();
+            if self.selected_skills.len() != class_data.skill_count {
+                errors.push(BuilderError::InvalidSkillCount {
+                    expected: class_data.skill_count,
+                    got: self.selected_skills.len(),
+                });
+            }
+            for skill in &self.selected_skills {
+                if !class_data.skill_options.contains(skill) {
+                    errors.push(BuilderError::SkillNotAvailable(*skill));
+                }
+            }
+        }
+        errors
+    }
+
+    /// Build the character, returning the first problem [`validate`](Self::validate) finds.
     pub fn build(self) -> Result<Character, BuilderError> {
+        if let Some(error) = self.validate().into_iter().next() {
+            return Err(error);
+        }
         let name = self.name.ok_or(BuilderError::MissingName)?;
         let race = self.race.ok_or(BuilderError::MissingRace)?;
         let class = self.class.ok_or(BuilderError::MissingClass)?;
@@ -219,6 +313,7 @@ impl CharacterBuilder {
             .ok_or(BuilderError::MissingAbilityScores)?;
 
         // Apply racial ability bonuses
+        let traits = race.traits();
         race.apply_ability_bonuses(&mut ability_scores);
 
         // Handle Half-Elf's extra +1 to two abilities
@@ -235,22 +330,6 @@ impl CharacterBuilder {
         // Get class data
         let class_data = class.data();
 
-        // Validate skill count
-        let expected_skills = class_data.skill_count;
-        if self.selected_skills.len() != expected_skills {
-            return Err(BuilderError::InvalidSkillCount {
-                expected: expected_skills,
-                got: self.selected_skills.len(),
-            });
-        }
-
-        // Validate skill availability
-        for skill in &self.selected_skills {
-            if !class_data.skill_options.contains(skill) {
-                return Err(BuilderError::SkillNotAvailable(*skill));
-            }
-        }
-
         // Calculate HP
         let con_mod = ability_scores.modifier(Ability::Constitution);
         let hp = (class_data.base_hp + con_mod as i32).max(1);
@@ -302,20 +381,40 @@ impl CharacterBuilder {
         // Set features
         character.features = class_data.level_1_features;
 
-        // Set race
+        // Set race and apply racial traits
         character.race = Race {
             name: race.name().to_string(),
             subrace: None,
             race_type: Some(race),
         };
         character.race_type = race;
+        character.darkvision_range = traits.darkvision_range;
+        character.resistances = traits
+            .resistances
+            .iter()
+            .map(|&damage_type| Resistance::Type(damage_type))
+            .collect();
+        for &skill in traits.skill_proficiencies {
+            character
+                .skill_proficiencies
+                .entry(skill)
+                .or_insert(ProficiencyLevel::Proficient);
+        }
+        for language in traits.languages {
+            if !character.languages.iter().any(|l| l == language) {
+                character.languages.push(language.to_string());
+            }
+        }
+        character
+            .features
+            .extend(traits.features.iter().map(|f| f.to_feature(race)));
 
         // Set background
         character.background = background;
         character.background_name = background.name().to_string();
 
         // Set speed
-        character.speed = Speed::new(race.base_speed());
+        character.speed = Speed::new(traits.speed);
 
         // Set backstory
         character.backstory = self.backstory;
@@ -327,10 +426,6 @@ impl CharacterBuilder {
 
                 // Set up level 1 spell slots based on class
                 match class {
-                    CharacterClass::Warlock => {
-                        // Pact Magic: 1 first-level slot at level 1
-                        spell_slots.slots[0] = SlotInfo { total: 1, used: 0 };
-                    }
                     CharacterClass::Bard
                     | CharacterClass::Cleric
                     | CharacterClass::Druid
@@ -378,18 +473,22 @@ impl CharacterBuilder {
                     _ => vec![],
                 };
 
+                // A Wizard starts with six spells in their spellbook
+                let spellbook = if class == CharacterClass::Wizard {
+                    vec![
+                        "Magic Missile".to_string(),
+                        "Shield".to_string(),
+                        "Burning Hands".to_string(),
+                        "Detect Magic".to_string(),
+                        "Sleep".to_string(),
+                        "Mage Armor".to_string(),
+                    ]
+                } else {
+                    vec![]
+                };
+
                 // Default spells known based on class
                 let spells_known = match class {
-                    CharacterClass::Wizard => {
-                        vec![
-                            "Magic Missile".to_string(),
-                            "Shield".to_string(),
-                            "Burning Hands".to_string(),
-                            "Detect Magic".to_string(),
-                            "Sleep".to_string(),
-                            "Mage Armor".to_string(),
-                        ]
-                    }
                     CharacterClass::Sorcerer => {
                         vec!["Magic Missile".to_string(), "Shield".to_string()]
                     }
@@ -405,7 +504,7 @@ impl CharacterBuilder {
                             "Dissonant Whispers".to_string(),
                         ]
                     }
-                    // Clerics and Druids prepare spells, so no spells_known
+                    // Clerics, Druids, and Wizards prepare spells, so no spells_known
                     _ => vec![],
                 };
 
@@ -416,9 +515,12 @@ impl CharacterBuilder {
                 character.spellcasting = Some(SpellcastingData {
                     ability,
                     spells_known,
+                    spellbook,
                     spells_prepared,
                     cantrips_known,
                     spell_slots,
+                    // Pact Magic: 1 first-level slot at level 1
+                    pact_slots: class.pact_slots_at_level(1),
                 });
             }
         }
@@ -437,10 +539,48 @@ impl CharacterBuilder {
 
         character.class_resources = class_resources;
 
+        // Hand out starting equipment, wearing any armor and shield
+        let background_equipment = background.starting_equipment();
+        for name in class_data
+            .starting_equipment
+            .iter()
+            .chain(&background_equipment)
+        {
+            give_starting_item(&mut character, name);
+        }
+
         Ok(character)
     }
 }
 
+/// Give `character` a starting item by name: armor and a shield go straight
+/// into their equipment slots, everything else into the pack.
+fn give_starting_item(character: &mut Character, name: &str) {
+    if let Some(armor) = crate::items::get_armor(name) {
+        if character.equipment.armor.is_none() {
+            character.equipment.armor = Some(armor);
+            return;
+        }
+    }
+    let item = match crate::items::find_item(name) {
+        Some(standard) => standard.as_item(),
+        None => Item {
+            name: name.to_string(),
+            quantity: 1,
+            weight: 0.0,
+            value_gp: 0.0,
+            description: None,
+            item_type: ItemType::Adventuring,
+            magical: false,
+        },
+    };
+    if item.item_type == ItemType::Shield && character.equipment.shield.is_none() {
+        character.equipment.shield = Some(item);
+    } else {
+        character.inventory.add_item(item);
+    }
+}
+
 /// Roll 4d6, drop lowest, for ability score generation.
 pub fn roll_4d6_drop_lowest() -> u8 {
     use rand::Rng;
@@ -459,6 +599,28 @@ pub fn roll_ability_scores() -> [u8; 6] {
     ]
 }
 
+/// Check that `character` may take a first level in `class`.
+///
+/// Multiclassing needs the prerequisite scores of both the new class and
+/// every class the character already has. Another level in a class they
+/// already have needs nothing.
+pub fn validate_multiclass(
+    character: &Character,
+    class: CharacterClass,
+) -> Result<(), BuilderError> {
+    if character.classes.iter().any(|c| c.class == class) {
+        return Ok(());
+    }
+    let scores = &character.ability_scores;
+    character
+        .classes
+        .iter()
+        .map(|c| c.class)
+        .chain(std::iter::once(class))
+        .find(|c| !c.meets_multiclass_prerequisites(scores))
+        .map_or(Ok(()), |c| Err(BuilderError::MulticlassPrerequisite(c)))
+}
+
 /// Validate point buy scores.
 pub fn validate_point_buy(scores: &AbilityScores) -> Result<(), String> {
     let mut total_cost = 0u8;
@@ -517,6 +679,105 @@ mod tests {
         assert_eq!(character.hit_points.maximum, 13);
     }
 
+    #[test]
+    fn test_fighter_starts_armed_and_armored() {
+        let character = CharacterBuilder::new()
+            .name("Thorin")
+            .race(RaceType::Human)
+            .class(CharacterClass::Fighter)
+            .background(Background::Soldier)
+            .standard_array([
+                (15, Ability::Strength),
+                (14, Ability::Constitution),
+                (13, Ability::Dexterity),
+                (12, Ability::Wisdom),
+                (10, Ability::Intelligence),
+                (8, Ability::Charisma),
+            ])
+            .skills(vec![Skill::Athletics, Skill::Perception])
+            .build()
+            .unwrap();
+
+        let armor = character.equipment.armor.as_ref().expect("wears armor");
+        assert_eq!(armor.base.name, "Chain Mail");
+        assert!(character.equipment.shield.is_some());
+        assert!(character.inventory.find_item("Longsword").is_some());
+        assert!(character.inventory.find_item("Chain Mail").is_none());
+        // Chain mail (16) + shield (2)
+        assert_eq!(character.current_ac(), 18);
+        // Background gear is carried too
+        assert!(character.inventory.find_item("Insignia of Rank").is_some());
+    }
+
+    #[test]
+    fn test_dwarf_racial_traits_applied() {
+        use crate::rules::DamageType;
+
+        let character = CharacterBuilder::new()
+            .name("Thorin")
+            .race(RaceType::Dwarf)
+            .class(CharacterClass::Fighter)
+            .background(Background::Soldier)
+            .standard_array([
+                (15, Ability::Strength),
+                (14, Ability::Constitution),
+                (13, Ability::Dexterity),
+                (12, Ability::Wisdom),
+                (10, Ability::Intelligence),
+                (8, Ability::Charisma),
+            ])
+            .skills(vec![Skill::Athletics, Skill::Perception])
+            .build()
+            .unwrap();
+
+        assert_eq!(character.ability_scores.constitution, 16);
+        assert_eq!(character.speed.walk, 25);
+        assert_eq!(character.darkvision_range, 60);
+        assert!(character
+            .resistances
+            .contains(&Resistance::Type(DamageType::Poison)));
+        assert!(character.languages.contains(&"Dwarvish".to_string()));
+        assert!(character
+            .features
+            .iter()
+            .any(|f| f.name == "Dwarven Resilience" && f.source == "Dwarf"));
+    }
+
+    #[test]
+    fn test_half_orc_racial_traits_applied() {
+        let character = CharacterBuilder::new()
+            .name("Grusk")
+            .race(RaceType::HalfOrc)
+            .class(CharacterClass::Barbarian)
+            .background(Background::Soldier)
+            .standard_array([
+                (15, Ability::Strength),
+                (14, Ability::Constitution),
+                (13, Ability::Dexterity),
+                (12, Ability::Wisdom),
+                (10, Ability::Intelligence),
+                (8, Ability::Charisma),
+            ])
+            .skills(vec![Skill::Athletics, Skill::Survival])
+            .build()
+            .unwrap();
+
+        assert_eq!(character.ability_scores.strength, 17);
+        assert_eq!(character.ability_scores.constitution, 15);
+        assert_eq!(character.speed.walk, 30);
+        assert!(character.resistances.is_empty());
+        assert_eq!(
+            character.skill_proficiencies.get(&Skill::Intimidation),
+            Some(&ProficiencyLevel::Proficient)
+        );
+        let endurance = character
+            .features
+            .iter()
+            .find(|f| f.name == "Relentless Endurance")
+            .expect("half-orcs have Relentless Endurance");
+        assert_eq!(endurance.uses.as_ref().map(|u| u.maximum), Some(1));
+    }
+
     #[test]
     fn test_build_wizard() {
         let character = CharacterBuilder::new()
@@ -606,6 +867,85 @@ mod tests {
         assert!(validate_point_buy(&out_of_range).is_err());
     }
 
+    #[test]
+    fn test_validate_lists_every_missing_choice() {
+        let errors = CharacterBuilder::new().validate();
+        assert_eq!(
+            errors,
+            vec![
+                BuilderError::MissingName,
+                BuilderError::MissingRace,
+                BuilderError::MissingClass,
+                BuilderError::MissingBackground,
+                BuilderError::MissingAbilityScores,
+            ]
+        );
+
+        let half_elf_rogue = CharacterBuilder::new()
+            .name("Vex")
+            .race(RaceType::HalfElf)
+            .class(CharacterClass::Rogue)
+            .background(Background::Criminal)
+            .rolled(AbilityScores::new(12, 16, 13, 10, 11, 14))
+            .skills(vec![Skill::Stealth, Skill::Arcana]);
+        let errors = half_elf_rogue.validate();
+        assert_eq!(
+            errors,
+            vec![
+                BuilderError::HalfElfNeedsBonusAbilities,
+                BuilderError::InvalidSkillCount {
+                    expected: 4,
+                    got: 2
+                },
+                BuilderError::SkillNotAvailable(Skill::Arcana),
+            ]
+        );
+        assert!(matches!(
+            half_elf_rogue.build(),
+            Err(BuilderError::HalfElfNeedsBonusAbilities)
+        ));
+    }
+
+    #[test]
+    fn test_complete_point_buy_build_validates_and_builds() {
+        let builder = CharacterBuilder::new()
+            .name("Mira")
+            .race(RaceType::Human)
+            .class(CharacterClass::Fighter)
+            .background(Background::Soldier)
+            .point_buy(AbilityScores::new(15, 14, 13, 12, 10, 8))
+            .skills(vec![Skill::Athletics, Skill::Perception]);
+
+        assert_eq!(builder.point_buy_remaining(), Some(0));
+        assert!(builder.validate().is_empty());
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn test_point_buy_over_budget_is_reported() {
+        let builder = CharacterBuilder::new()
+            .name("Mira")
+            .race(RaceType::Human)
+            .class(CharacterClass::Fighter)
+            .background(Background::Soldier)
+            .point_buy(AbilityScores::new(15, 15, 15, 15, 8, 8))
+            .skills(vec![Skill::Athletics, Skill::Perception]);
+
+        assert_eq!(builder.point_buy_remaining(), Some(-9));
+        assert!(matches!(
+            builder.validate().as_slice(),
+            [BuilderError::InvalidPointBuy(_)]
+        ));
+        assert!(matches!(
+            builder.build(),
+            Err(BuilderError::InvalidPointBuy(_))
+        ));
+
+        // Other methods have no budget to report.
+        let rolled = CharacterBuilder::new().rolled(AbilityScores::new(18, 8, 8, 8, 8, 8));
+        assert_eq!(rolled.point_buy_remaining(), None);
+    }
+
     #[test]
     fn test_roll_4d6_drop_lowest() {
         for _ in 0..100 {
@@ -696,11 +1036,12 @@ mod tests {
             .cantrips_known
             .contains(&"Fire Bolt".to_string()));
 
-        // Wizard gets 6 spells at level 1
-        assert_eq!(spellcasting.spells_known.len(), 6);
+        // Wizard gets 6 spells in their spellbook at level 1
+        assert_eq!(spellcasting.spellbook.len(), 6);
         assert!(spellcasting
-            .spells_known
+            .spellbook
             .contains(&"Magic Missile".to_string()));
+        assert!(spellcasting.spells_known.is_empty());
 
         // Wizard gets 2 first-level slots at level 1
         assert_eq!(spellcasting.spell_slots.slots[0].total, 2);
@@ -742,8 +1083,12 @@ mod tests {
         // Warlock knows 2 spells at level 1
         assert_eq!(spellcasting.spells_known.len(), 2);
 
-        // Warlock gets only 1 first-level slot (Pact Magic)
-        assert_eq!(spellcasting.spell_slots.slots[0].total, 1);
+        // Warlock gets only 1 first-level Pact Magic slot
+        assert_eq!(spellcasting.spell_slots.slots[0].total, 0);
+        assert_eq!(
+            spellcasting.pact_slots,
+            Some(crate::world::PactSlots::new(1, 1))
+        );
     }
 
     #[test]
@@ -768,4 +1113,37 @@ mod tests {
         // Fighter is not a spellcaster at level 1
         assert!(character.spellcasting.is_none());
     }
+
+    #[test]
+    fn test_multiclass_requires_prerequisite_scores() {
+        let mut fighter = crate::world::create_sample_fighter("Roland");
+        fighter.ability_scores = AbilityScores::new(16, 14, 14, 10, 12, 8);
+        assert_eq!(
+            validate_multiclass(&fighter, CharacterClass::Wizard),
+            Err(BuilderError::MulticlassPrerequisite(CharacterClass::Wizard))
+        );
+        assert_eq!(
+            BuilderError::MulticlassPrerequisite(CharacterClass::Wizard).to_string(),
+            "Multiclassing with Wizard requires Intelligence 13"
+        );
+
+        fighter.ability_scores = AbilityScores::new(16, 14, 14, 13, 12, 8);
+        assert_eq!(
+            validate_multiclass(&fighter, CharacterClass::Wizard),
+            Ok(())
+        );
+
+        // The class being left behind has prerequisites too
+        fighter.ability_scores = AbilityScores::new(10, 10, 14, 13, 12, 8);
+        assert_eq!(
+            validate_multiclass(&fighter, CharacterClass::Wizard),
+            Err(BuilderError::MulticlassPrerequisite(
+                CharacterClass::Fighter
+            ))
+        );
+        assert_eq!(
+            validate_multiclass(&fighter, CharacterClass::Fighter),
+            Ok(())
+        );
+    }
 }
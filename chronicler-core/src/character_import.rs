@@ -0,0 +1,940 @@
+//! Character import from external character sheet exports.
+//!
+//! Players often already have a character built elsewhere. This module maps a
+//! subset of a character sheet into a [`Character`]: ability scores, classes
+//! and levels, hit points, armor class, proficiencies, inventory, and spells.
+//! Anything that can't be mapped is skipped and reported as an
+//! [`ImportWarning`] rather than failing the import.
+//!
+//! Two formats are supported:
+//!
+//! - [`ImportFormat::DndBeyond`]: the character JSON served by D&D Beyond's
+//!   character service (either the full response or its `data` object).
+//! - [`ImportFormat::Generic`]: a small documented format that other tools
+//!   (e.g. a Foundry VTT actor export run through a script) can target:
+//!
+//! ```json
+//! {
+//!   "name": "Vex",
+//!   "race": "Elf",
+//!   "subrace": "Wood Elf",
+//!   "background": "Criminal",
+//!   "classes": [{ "class": "Rogue", "level": 3, "subclass": "Thief" }],
+//!   "abilities": { "str": 10, "dex": 17, "con": 12, "int": 13, "wis": 11, "cha": 14 },
+//!   "max_hp": 20,
+//!   "current_hp": 18,
+//!   "temp_hp": 0,
+//!   "armor_class": 14,
+//!   "saving_throws": ["dex", "int"],
+//!   "skills": ["stealth", "acrobatics", "perception"],
+//!   "expertise": ["stealth"],
+//!   "tools": ["Thieves' Tools"],
+//!   "languages": ["Common", "Elvish"],
+//!   "inventory": [{ "name": "Rapier", "quantity": 1, "weight": 2.0, "type": "weapon" }],
+//!   "gold": 25,
+//!   "silver": 3,
+//!   "spells": { "cantrips": [], "known": [], "prepared": [] },
+//!   "backstory": "..."
+//! }
+//! ```
+//!
+//! Only `name` is required. `armor_class` is taken as the final AC.
+
+use crate::character_builder::CharacterBuilder;
+use crate::rules::Resistance;
+use crate::world::{
+    Ability, AbilityScores, Background, Character, CharacterClass, ClassLevel, HitDice, HitPoints,
+    Item, ItemType, ProficiencyLevel, Race, RaceType, Skill, SlotInfo, Speed, SpellSlots,
+    SpellcastingData, Subclass,
+};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// Source format of a character import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// D&D Beyond character service JSON.
+    DndBeyond,
+    /// The generic format documented in this module.
+    Generic,
+}
+
+/// Errors that prevent an import entirely.
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Missing required field: {0}")]
+    MissingField(&'static str),
+}
+
+/// A part of the export that was skipped or approximated during import.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportWarning {
+    /// The field (or value) that couldn't be mapped.
+    pub field: String,
+    pub message: String,
+}
+
+impl ImportWarning {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ImportWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// An imported character along with anything that couldn't be mapped.
+#[derive(Debug, Clone)]
+pub struct ImportedCharacter {
+    pub character: Character,
+    pub warnings: Vec<ImportWarning>,
+}
+
+impl CharacterBuilder {
+    /// Import a character from an external JSON export.
+    ///
+    /// Unknown or unsupported fields are ignored and collected as warnings.
+    pub fn from_import(json: &str, format: ImportFormat) -> Result<ImportedCharacter, ImportError> {
+        let mut warnings = Vec::new();
+        let sheet = match format {
+            ImportFormat::Generic => {
+                let sheet: Sheet = serde_json::from_str(json)?;
+                for key in sheet.unknown.keys() {
+                    warnings.push(ImportWarning::new(key, "unknown field ignored"));
+                }
+                sheet
+            }
+            ImportFormat::DndBeyond => {
+                let value: Value = serde_json::from_str(json)?;
+                dndbeyond_sheet(&value, &mut warnings)?
+            }
+        };
+        let character = sheet.into_character(&mut warnings);
+        Ok(ImportedCharacter {
+            character,
+            warnings,
+        })
+    }
+}
+
+// ============================================================================
+// Generic format
+// ============================================================================
+
+/// The generic import format. D&D Beyond exports are converted into this
+/// before being mapped onto a character.
+#[derive(Debug, Default, Deserialize)]
+struct Sheet {
+    name: String,
+    #[serde(default)]
+    race: Option<String>,
+    #[serde(default)]
+    subrace: Option<String>,
+    #[serde(default)]
+    background: Option<String>,
+    #[serde(default)]
+    classes: Vec<SheetClass>,
+    #[serde(default)]
+    abilities: Map<String, Value>,
+    #[serde(default)]
+    max_hp: Option<i32>,
+    #[serde(default)]
+    current_hp: Option<i32>,
+    #[serde(default)]
+    temp_hp: Option<i32>,
+    #[serde(default)]
+    armor_class: Option<u8>,
+    #[serde(default)]
+    saving_throws: Vec<String>,
+    #[serde(default)]
+    skills: Vec<String>,
+    #[serde(default)]
+    expertise: Vec<String>,
+    #[serde(default)]
+    tools: Vec<String>,
+    #[serde(default)]
+    languages: Vec<String>,
+    #[serde(default)]
+    inventory: Vec<SheetItem>,
+    #[serde(default)]
+    gold: Option<i32>,
+    #[serde(default)]
+    silver: Option<i32>,
+    #[serde(default)]
+    spells: SheetSpells,
+    #[serde(default)]
+    backstory: Option<String>,
+    /// Anything not listed above, reported as a warning.
+    #[serde(flatten)]
+    unknown: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SheetClass {
+    class: String,
+    #[serde(default = "default_level")]
+    level: u8,
+    #[serde(default)]
+    subclass: Option<String>,
+}
+
+fn default_level() -> u8 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+struct SheetItem {
+    name: String,
+    #[serde(default = "default_quantity")]
+    quantity: u32,
+    #[serde(default)]
+    weight: f32,
+    #[serde(default)]
+    value_gp: f32,
+    #[serde(default, rename = "type")]
+    item_type: Option<String>,
+    #[serde(default)]
+    magical: bool,
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SheetSpells {
+    #[serde(default)]
+    cantrips: Vec<String>,
+    #[serde(default)]
+    known: Vec<String>,
+    #[serde(default)]
+    prepared: Vec<String>,
+}
+
+impl Sheet {
+    fn into_character(self, warnings: &mut Vec<ImportWarning>) -> Character {
+        let mut character = Character::new(&self.name);
+
+        // Abilities (missing scores stay at 10)
+        let mut scores = AbilityScores::default();
+        for (key, value) in &self.abilities {
+            match (parse_ability(key), value.as_u64()) {
+                (Some(ability), Some(score)) => scores.set(ability, score.clamp(1, 30) as u8),
+                _ => warnings.push(ImportWarning::new(
+                    format!("abilities.{key}"),
+                    "unrecognized ability score",
+                )),
+            }
+        }
+        character.ability_scores = scores;
+
+        // Race and background
+        if let Some(race_name) = &self.race {
+            match parse_race(race_name) {
+                Some(race) => {
+                    character.race_type = race;
+                    character.darkvision_range = race.darkvision_range();
+                    character.resistances = race
+                        .traits()
+                        .resistances
+                        .iter()
+                        .map(|&damage_type| Resistance::Type(damage_type))
+                        .collect();
+                    character.speed = Speed::new(race.base_speed());
+                    character.race = Race {
+                        name: race.name().to_string(),
+                        subrace: self
+                            .subrace
+                            .clone()
+                            .or_else(|| Some(race_name.clone()))
+                            .filter(|s| s != race.name()),
+                        race_type: Some(race),
+                    };
+                }
+                None => warnings.push(ImportWarning::new(
+                    "race",
+                    format!("unknown race '{race_name}', defaulting to Human"),
+                )),
+            }
+        }
+        if let Some(background_name) = &self.background {
+            match parse_background(background_name) {
+                Some(background) => {
+                    character.background = background;
+                    character.background_name = background.name().to_string();
+                }
+                None => {
+                    character.background_name = background_name.clone();
+                    warnings.push(ImportWarning::new(
+                        "background",
+                        format!("unknown background '{background_name}'"),
+                    ));
+                }
+            }
+        }
+
+        // Classes, levels, and hit dice
+        character.classes.clear();
+        character.hit_dice = HitDice::new();
+        for entry in &self.classes {
+            let Some(class) = parse_class(&entry.class) else {
+                warnings.push(ImportWarning::new(
+                    "classes",
+                    format!("unknown class '{}' skipped", entry.class),
+                ));
+                continue;
+            };
+            let subclass = entry.subclass.as_ref().and_then(|name| {
+                let subclass = parse_subclass(name);
+                if subclass.is_none() {
+                    warnings.push(ImportWarning::new(
+                        "classes",
+                        format!("unknown subclass '{name}' ignored"),
+                    ));
+                }
+                subclass
+            });
+            let level = entry.level.clamp(1, 20);
+            character.hit_dice.add(class.hit_die(), level);
+            character.classes.push(ClassLevel {
+                class,
+                level,
+                subclass,
+            });
+        }
+        character.level = character
+            .classes
+            .iter()
+            .map(|c| u32::from(c.level))
+            .sum::<u32>()
+            .clamp(1, 20) as u8;
+
+        // Hit points
+        let maximum = self.max_hp.unwrap_or_else(|| {
+            warnings.push(ImportWarning::new(
+                "max_hp",
+                "missing, estimated from class hit dice",
+            ));
+            estimate_max_hp(&character)
+        });
+        let mut hit_points = HitPoints::new(maximum.max(1));
+        if let Some(current) = self.current_hp {
+            hit_points.current = current.clamp(0, hit_points.maximum);
+        }
+        hit_points.temporary = self.temp_hp.unwrap_or(0).max(0);
+        character.hit_points = hit_points;
+
+        // Armor class is already final, so don't add DEX on top of it
+        character.ac_override = self.armor_class;
+
+        // Proficiencies
+        for name in &self.saving_throws {
+            match parse_ability(name) {
+                Some(ability) => {
+                    character.saving_throw_proficiencies.insert(ability);
+                }
+                None => warnings.push(ImportWarning::new(
+                    "saving_throws",
+                    format!("unknown saving throw '{name}'"),
+                )),
+            }
+        }
+        if self.saving_throws.is_empty() {
+            if let Some(class) = character.classes.first() {
+                character
+                    .saving_throw_proficiencies
+                    .extend(class.class.data().saving_throws);
+            }
+        }
+        for (names, level) in [
+            (&self.skills, ProficiencyLevel::Proficient),
+            (&self.expertise, ProficiencyLevel::Expertise),
+        ] {
+            for name in names {
+                match parse_skill(name) {
+                    Some(skill) => {
+                        character.skill_proficiencies.insert(skill, level);
+                    }
+                    None => warnings.push(ImportWarning::new(
+                        "skills",
+                        format!("unknown skill '{name}'"),
+                    )),
+                }
+            }
+        }
+        character.tool_proficiencies.extend(self.tools);
+        if !self.languages.is_empty() {
+            character.languages = self.languages;
+        }
+
+        // Inventory
+        for item in self.inventory {
+            character.inventory.items.push(Item {
+                name: item.name,
+                quantity: item.quantity,
+                weight: item.weight,
+                value_gp: item.value_gp,
+                description: None,
+                item_type: item
+                    .item_type
+                    .as_deref()
+                    .map(parse_item_type)
+                    .unwrap_or(ItemType::Other),
+                magical: item.magical,
+            });
+        }
+        if let Some(gold) = self.gold {
+            character.inventory.gold = gold;
+        }
+        if let Some(silver) = self.silver {
+            character.inventory.silver = silver;
+        }
+
+        // Spells
+        let spells = self.spells;
+        if !(spells.cantrips.is_empty() && spells.known.is_empty() && spells.prepared.is_empty()) {
+            match character
+                .classes
+                .iter()
+                .find_map(|c| c.class.spellcasting_ability().map(|a| (c, a)))
+            {
+                Some((class_level, ability)) => {
+                    let mut spell_slots = SpellSlots::new();
+                    let slots = class_level.class.spell_slots_at_level(class_level.level);
+                    for (slot, total) in spell_slots.slots.iter_mut().zip(slots) {
+                        *slot = SlotInfo { total, used: 0 };
+                    }
+                    // A wizard's known spells are the ones in their spellbook
+                    let (spells_known, spellbook) = if class_level.class == CharacterClass::Wizard {
+                        (Vec::new(), spells.known)
+                    } else {
+                        (spells.known, Vec::new())
+                    };
+                    character.spellcasting = Some(SpellcastingData {
+                        ability,
+                        spells_known,
+                        spellbook,
+                        spells_prepared: spells.prepared,
+                        cantrips_known: spells.cantrips,
+                        spell_slots,
+                        pact_slots: class_level.class.pact_slots_at_level(class_level.level),
+                    });
+                }
+                None => warnings.push(ImportWarning::new(
+                    "spells",
+                    "no spellcasting class, spells ignored",
+                )),
+            }
+        }
+
+        character.backstory = self.backstory;
+        character
+    }
+}
+
+/// Fixed HP per level: max hit die at first level, average after, plus CON.
+fn estimate_max_hp(character: &Character) -> i32 {
+    let con_mod = character.ability_scores.modifier(Ability::Constitution) as i32;
+    let mut total = 0;
+    for (i, class_level) in character.classes.iter().enumerate() {
+        let die = class_level.class.hit_die().sides() as i32;
+        let levels = class_level.level as i32;
+        total += if i == 0 {
+            die + (die / 2 + 1) * (levels - 1)
+        } else {
+            (die / 2 + 1) * levels
+        };
+    }
+    (total + con_mod * character.level as i32).max(1)
+}
+
+// ============================================================================
+// D&D Beyond
+// ============================================================================
+
+/// D&D Beyond stat ids, in order 1-6.
+const DDB_ABILITIES: [&str; 6] = ["str", "dex", "con", "int", "wis", "cha"];
+
+/// Convert a D&D Beyond character into the generic sheet.
+fn dndbeyond_sheet(root: &Value, warnings: &mut Vec<ImportWarning>) -> Result<Sheet, ImportError> {
+    let data = root.get("data").unwrap_or(root);
+    let name = data["name"]
+        .as_str()
+        .ok_or(ImportError::MissingField("name"))?
+        .to_string();
+
+    let modifiers: Vec<&Value> = data["modifiers"]
+        .as_object()
+        .into_iter()
+        .flat_map(|sources| sources.values())
+        .filter_map(Value::as_array)
+        .flatten()
+        .collect();
+
+    let mut sheet = Sheet {
+        name,
+        race: data["race"]["baseRaceName"].as_str().map(String::from),
+        subrace: data["race"]["fullName"].as_str().map(String::from),
+        background: data["background"]["definition"]["name"]
+            .as_str()
+            .map(String::from),
+        backstory: data["notes"]["backstory"].as_str().map(String::from),
+        ..Sheet::default()
+    };
+
+    // Ability scores: base + bonus + racial/feat bonuses, unless overridden
+    let stat = |list: &str, id: usize| {
+        data[list]
+            .as_array()
+            .and_then(|stats| stats.iter().find(|s| s["id"].as_u64() == Some(id as u64)))
+            .and_then(|s| s["value"].as_i64())
+    };
+    let mut scores = AbilityScores::default();
+    for (i, key) in DDB_ABILITIES.iter().enumerate() {
+        let id = i + 1;
+        let ability = parse_ability(key).expect("DDB ability keys are valid");
+        let score = match stat("overrideStats", id) {
+            Some(value) => value,
+            None => {
+                let score_bonus = format!("{}-score", ability.name().to_lowercase());
+                let modifier_bonus: i64 = modifiers
+                    .iter()
+                    .filter(|m| m["type"] == "bonus" && m["subType"] == score_bonus.as_str())
+                    .filter_map(|m| m["value"].as_i64())
+                    .sum();
+                stat("stats", id).unwrap_or(10)
+                    + stat("bonusStats", id).unwrap_or(0)
+                    + modifier_bonus
+            }
+        };
+        scores.set(ability, score.clamp(1, 30) as u8);
+        sheet.abilities.insert(key.to_string(), score.into());
+    }
+
+    // Classes
+    let mut total_level = 0;
+    for class in data["classes"].as_array().into_iter().flatten() {
+        let Some(class_name) = class["definition"]["name"].as_str() else {
+            continue;
+        };
+        let level = class["level"].as_u64().unwrap_or(1) as u8;
+        total_level += level as i32;
+        sheet.classes.push(SheetClass {
+            class: class_name.to_string(),
+            level,
+            subclass: class["subclassDefinition"]["name"]
+                .as_str()
+                .map(String::from),
+        });
+    }
+
+    // Hit points: D&D Beyond stores rolled/fixed HP without CON
+    let con_mod = scores.modifier(Ability::Constitution) as i32;
+    let max_hp = match data["overrideHitPoints"].as_i64() {
+        Some(hp) => hp as i32,
+        None => {
+            data["baseHitPoints"].as_i64().unwrap_or(0) as i32
+                + data["bonusHitPoints"].as_i64().unwrap_or(0) as i32
+                + con_mod * total_level.max(1)
+        }
+    };
+    sheet.max_hp = Some(max_hp);
+    sheet.current_hp = Some(max_hp - data["removedHitPoints"].as_i64().unwrap_or(0) as i32);
+    sheet.temp_hp = data["temporaryHitPoints"].as_i64().map(|hp| hp as i32);
+
+    // Proficiencies, expertise, and languages
+    for modifier in &modifiers {
+        let sub_type = modifier["subType"].as_str().unwrap_or_default();
+        let friendly = modifier["friendlySubtypeName"]
+            .as_str()
+            .unwrap_or(sub_type)
+            .to_string();
+        match modifier["type"].as_str().unwrap_or_default() {
+            "proficiency" => {
+                if let Some(ability) = sub_type.strip_suffix("-saving-throws") {
+                    sheet.saving_throws.push(ability.to_string());
+                } else if parse_skill(sub_type).is_some() {
+                    sheet.skills.push(sub_type.to_string());
+                } else if is_armor_or_weapon_proficiency(sub_type) {
+                    // Armor and weapon proficiencies aren't tracked
+                } else {
+                    sheet.tools.push(friendly);
+                }
+            }
+            "expertise" => {
+                if parse_skill(sub_type).is_some() {
+                    sheet.expertise.push(sub_type.to_string());
+                }
+            }
+            "language" => sheet.languages.push(friendly),
+            // Ability score bonuses were applied above
+            "bonus" if sub_type.ends_with("-score") => {}
+            other => warnings.push(ImportWarning::new(
+                "modifiers",
+                format!("{other} modifier '{friendly}' not imported"),
+            )),
+        }
+    }
+
+    // Inventory and armor class from equipped armor
+    let dex_mod = scores.modifier(Ability::Dexterity);
+    let mut armor_ac: Option<i8> = None;
+    let mut shield_bonus = 0;
+    for entry in data["inventory"].as_array().into_iter().flatten() {
+        let definition = &entry["definition"];
+        let Some(item_name) = definition["name"].as_str() else {
+            continue;
+        };
+        let filter_type = definition["filterType"].as_str().unwrap_or_default();
+        sheet.inventory.push(SheetItem {
+            name: item_name.to_string(),
+            quantity: entry["quantity"].as_u64().unwrap_or(1) as u32,
+            weight: definition["weight"].as_f64().unwrap_or(0.0) as f32,
+            value_gp: definition["cost"].as_f64().unwrap_or(0.0) as f32,
+            item_type: Some(filter_type.to_string()),
+            magical: definition["magic"].as_bool().unwrap_or(false),
+        });
+
+        if entry["equipped"].as_bool() == Some(true) {
+            let base = definition["armorClass"].as_i64().unwrap_or(0) as i8;
+            match definition["armorTypeId"].as_u64() {
+                Some(1) => armor_ac = Some(base + dex_mod),
+                Some(2) => armor_ac = Some(base + dex_mod.min(2)),
+                Some(3) => armor_ac = Some(base),
+                Some(4) => shield_bonus = base,
+                _ => {}
+            }
+        }
+    }
+    let ac = armor_ac.unwrap_or(10 + dex_mod) + shield_bonus;
+    sheet.armor_class = Some(ac.max(1) as u8);
+
+    // Currency, folding platinum into gold
+    let currencies = &data["currencies"];
+    let coin = |key: &str| currencies[key].as_i64().unwrap_or(0) as i32;
+    sheet.gold = Some(coin("gp") + coin("pp") * 10);
+    sheet.silver = Some(coin("sp"));
+    for key in ["cp", "ep"] {
+        if coin(key) > 0 {
+            warnings.push(ImportWarning::new(
+                format!("currencies.{key}"),
+                "currency not tracked, dropped",
+            ));
+        }
+    }
+
+    // Class spells
+    let class_spells = data["classSpells"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry["spells"].as_array())
+        .flatten();
+    for spell in class_spells {
+        let Some(spell_name) = spell["definition"]["name"].as_str() else {
+            continue;
+        };
+        if spell["definition"]["level"].as_u64().unwrap_or(0) == 0 {
+            sheet.spells.cantrips.push(spell_name.to_string());
+            continue;
+        }
+        sheet.spells.known.push(spell_name.to_string());
+        if spell["prepared"].as_bool() == Some(true)
+            || spell["alwaysPrepared"].as_bool() == Some(true)
+        {
+            sheet.spells.prepared.push(spell_name.to_string());
+        }
+    }
+
+    for feat in data["feats"].as_array().into_iter().flatten() {
+        if let Some(feat_name) = feat["definition"]["name"].as_str() {
+            warnings.push(ImportWarning::new(
+                "feats",
+                format!("feat '{feat_name}' not imported"),
+            ));
+        }
+    }
+
+    Ok(sheet)
+}
+
+fn is_armor_or_weapon_proficiency(sub_type: &str) -> bool {
+    sub_type.ends_with("-armor")
+        || sub_type.ends_with("-weapons")
+        || sub_type == "shields"
+        || crate::items::get_weapon(&sub_type.replace('-', " ")).is_some()
+}
+
+// ============================================================================
+// Name matching
+// ============================================================================
+
+/// Lowercase and drop everything but letters and digits, so "Sleight of Hand",
+/// "sleight-of-hand", and "sleight_of_hand" all compare equal.
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn parse_ability(s: &str) -> Option<Ability> {
+    let key = normalize(s);
+    Ability::all()
+        .into_iter()
+        .find(|a| normalize(a.name()) == key || normalize(a.abbreviation()) == key)
+}
+
+fn parse_skill(s: &str) -> Option<Skill> {
+    let key = normalize(s);
+    Skill::all()
+        .into_iter()
+        .find(|skill| normalize(skill.name()) == key)
+}
+
+fn parse_class(s: &str) -> Option<CharacterClass> {
+    let key = normalize(s);
+    CharacterClass::all()
+        .iter()
+        .copied()
+        .find(|class| normalize(class.name()) == key)
+}
+
+fn parse_subclass(s: &str) -> Option<Subclass> {
+    let key = normalize(s);
+    Subclass::all()
+        .iter()
+        .copied()
+        .find(|subclass| normalize(subclass.name()) == key)
+}
+
+/// Match a race by name, falling back to the last word so subraces like
+/// "Wood Elf" or "Hill Dwarf" map to their base race.
+fn parse_race(s: &str) -> Option<RaceType> {
+    let find = |key: String| {
+        RaceType::all()
+            .iter()
+            .copied()
+            .find(|race| normalize(race.name()) == key)
+    };
+    find(normalize(s)).or_else(|| find(normalize(s.split_whitespace().last()?)))
+}
+
+fn parse_background(s: &str) -> Option<Background> {
+    let key = normalize(s);
+    Background::all()
+        .iter()
+        .copied()
+        .find(|background| normalize(background.name()) == key)
+}
+
+fn parse_item_type(s: &str) -> ItemType {
+    match normalize(s).as_str() {
+        "weapon" => ItemType::Weapon,
+        "armor" => ItemType::Armor,
+        "shield" => ItemType::Shield,
+        "potion" => ItemType::Potion,
+        "scroll" => ItemType::Scroll,
+        "wand" | "rod" | "staff" => ItemType::Wand,
+        "ring" => ItemType::Ring,
+        "wondrous" | "wondrousitem" => ItemType::Wondrous,
+        "adventuring" | "adventuringgear" | "gear" => ItemType::Adventuring,
+        "tool" => ItemType::Tool,
+        _ => ItemType::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DDB_FIXTURE: &str = include_str!("../tests/fixtures/dndbeyond_character.json");
+
+    #[test]
+    fn test_dndbeyond_core_fields() {
+        let imported = CharacterBuilder::from_import(DDB_FIXTURE, ImportFormat::DndBeyond)
+            .expect("fixture should import");
+        let character = &imported.character;
+
+        assert_eq!(character.name, "Lyra Thistlewood");
+        assert_eq!(character.race_type, RaceType::HalfElf);
+        assert_eq!(character.background, Background::Entertainer);
+
+        // Base scores plus Half-Elf bonuses
+        let scores = &character.ability_scores;
+        assert_eq!(
+            [
+                scores.strength,
+                scores.dexterity,
+                scores.constitution,
+                scores.intelligence,
+                scores.wisdom,
+                scores.charisma
+            ],
+            [8, 15, 14, 10, 12, 17]
+        );
+
+        assert_eq!(character.level, 3);
+        assert_eq!(character.classes.len(), 1);
+        assert_eq!(character.classes[0].class, CharacterClass::Bard);
+        assert_eq!(character.classes[0].subclass, Some(Subclass::CollegeOfLore));
+
+        // 18 base + 3 levels of +2 CON, minus 4 removed
+        assert_eq!(character.hit_points.maximum, 24);
+        assert_eq!(character.hit_points.current, 20);
+
+        // Equipped leather (11) + DEX 2; unequipped chain mail is ignored
+        assert_eq!(character.current_ac(), 13);
+
+        assert!(character
+            .saving_throw_proficiencies
+            .contains(&Ability::Dexterity));
+        assert!(character
+            .saving_throw_proficiencies
+            .contains(&Ability::Charisma));
+        assert_eq!(
+            character.skill_proficiencies.get(&Skill::Insight),
+            Some(&ProficiencyLevel::Proficient)
+        );
+        assert_eq!(
+            character.skill_proficiencies.get(&Skill::Persuasion),
+            Some(&ProficiencyLevel::Expertise)
+        );
+        assert_eq!(
+            character.skill_proficiencies.get(&Skill::Performance),
+            Some(&ProficiencyLevel::Expertise)
+        );
+        assert!(character.tool_proficiencies.contains("Lute"));
+        assert!(character.tool_proficiencies.contains("Disguise Kit"));
+        assert!(!character.tool_proficiencies.contains("Rapier"));
+        assert!(character.languages.contains(&"Elvish".to_string()));
+
+        let potion = character
+            .inventory
+            .items
+            .iter()
+            .find(|i| i.name == "Potion of Healing")
+            .expect("potion imported");
+        assert_eq!(potion.quantity, 2);
+        assert_eq!(potion.item_type, ItemType::Potion);
+        assert_eq!(character.inventory.items.len(), 5);
+        assert_eq!(character.inventory.gold, 52);
+        assert_eq!(character.inventory.silver, 7);
+
+        let spellcasting = character.spellcasting.as_ref().expect("bard casts spells");
+        assert_eq!(spellcasting.ability, Ability::Charisma);
+        assert_eq!(spellcasting.cantrips_known.len(), 2);
+        assert_eq!(spellcasting.spells_known.len(), 6);
+        assert!(spellcasting
+            .spells_known
+            .contains(&"Healing Word".to_string()));
+        assert_eq!(spellcasting.spell_slots.slots[0].total, 4);
+        assert_eq!(spellcasting.spell_slots.slots[1].total, 2);
+
+        assert_eq!(
+            character.backstory.as_deref(),
+            Some("Ran away with a travelling troupe at twelve.")
+        );
+    }
+
+    #[test]
+    fn test_dndbeyond_collects_warnings() {
+        let imported = CharacterBuilder::from_import(DDB_FIXTURE, ImportFormat::DndBeyond).unwrap();
+        let warnings: Vec<String> = imported.warnings.iter().map(|w| w.to_string()).collect();
+
+        assert!(warnings.iter().any(|w| w.contains("Lucky")));
+        assert!(warnings.iter().any(|w| w.contains("Darkvision")));
+        assert!(warnings.iter().any(|w| w.starts_with("currencies.cp")));
+    }
+
+    #[test]
+    fn test_generic_format() {
+        let json = r#"{
+            "name": "Vex",
+            "race": "Wood Elf",
+            "classes": [{ "class": "rogue", "level": 3, "subclass": "Arcane Trickster" }],
+            "abilities": { "dex": 17, "int": 13 },
+            "max_hp": 20,
+            "armor_class": 14,
+            "skills": ["sleight_of_hand", "juggling"],
+            "expertise": ["Stealth"],
+            "inventory": [{ "name": "Rapier", "type": "weapon" }],
+            "gold": 25,
+            "favorite_color": "green"
+        }"#;
+        let imported = CharacterBuilder::from_import(json, ImportFormat::Generic).unwrap();
+        let character = &imported.character;
+
+        assert_eq!(character.race_type, RaceType::Elf);
+        assert_eq!(character.race.subrace.as_deref(), Some("Wood Elf"));
+        assert_eq!(character.ability_scores.dexterity, 17);
+        assert_eq!(character.ability_scores.strength, 10);
+        assert_eq!(character.classes[0].class, CharacterClass::Rogue);
+        assert_eq!(character.classes[0].subclass, None);
+        assert_eq!(character.hit_points.current, 20);
+        assert_eq!(character.current_ac(), 14);
+        assert!(character.armor_class.armor_type.is_none());
+        assert_eq!(
+            character.skill_proficiencies.get(&Skill::SleightOfHand),
+            Some(&ProficiencyLevel::Proficient)
+        );
+        assert_eq!(
+            character.skill_proficiencies.get(&Skill::Stealth),
+            Some(&ProficiencyLevel::Expertise)
+        );
+        // Saving throws default to the class's when not given
+        assert!(character
+            .saving_throw_proficiencies
+            .contains(&Ability::Intelligence));
+        assert_eq!(character.inventory.items[0].item_type, ItemType::Weapon);
+
+        // Changing armor works AC out from equipment again
+        let mut world = crate::world::GameWorld::new("Import", character.clone());
+        crate::rules::apply_effect(
+            &mut world,
+            &crate::rules::Effect::ItemUnequipped {
+                item_name: "Leather Armor".to_string(),
+                slot: "armor".to_string(),
+            },
+        );
+        assert_eq!(world.player_character.ac_override, None);
+        assert_eq!(world.player_character.current_ac(), 13);
+
+        let fields: Vec<&str> = imported.warnings.iter().map(|w| w.field.as_str()).collect();
+        assert!(fields.contains(&"favorite_color"));
+        assert!(fields.contains(&"skills"));
+        assert!(fields.contains(&"classes"));
+    }
+
+    #[test]
+    fn test_import_requires_name() {
+        let result = CharacterBuilder::from_import(r#"{"data": {}}"#, ImportFormat::DndBeyond);
+        assert!(matches!(result, Err(ImportError::MissingField("name"))));
+
+        let result = CharacterBuilder::from_import("{}", ImportFormat::Generic);
+        assert!(matches!(result, Err(ImportError::Json(_))));
+    }
+
+    #[test]
+    fn test_import_tolerates_oversized_class_levels() {
+        let classes: Vec<String> = (0..14)
+            .map(|_| r#"{ "class": "fighter", "level": 20 }"#.to_string())
+            .collect();
+        let json = format!(
+            r#"{{"name": "Overlevelled", "classes": [{}]}}"#,
+            classes.join(",")
+        );
+        let imported = CharacterBuilder::from_import(&json, ImportFormat::Generic).unwrap();
+        assert_eq!(imported.character.level, 20);
+    }
+}
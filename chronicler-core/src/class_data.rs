@@ -15,6 +15,9 @@ pub struct ClassData {
     pub skill_options: &'static [Skill],
     /// Starting HP at level 1 (hit die max, before CON modifier).
     pub base_hp: i32,
+    /// Items from the standard item database a new character starts with.
+    /// Armor and a shield among them start equipped.
+    pub starting_equipment: &'static [&'static str],
     /// Features gained at level 1.
     pub level_1_features: Vec<Feature>,
 }
@@ -35,6 +38,7 @@ impl CharacterClass {
                     Skill::Survival,
                 ],
                 base_hp: 12,
+                starting_equipment: &["Greataxe", "Handaxe", "Javelin", "Backpack"],
                 level_1_features: vec![
                     Feature {
                         name: "Rage".to_string(),
@@ -71,6 +75,7 @@ impl CharacterClass {
                     Skill::Stealth, Skill::Survival,
                 ],
                 base_hp: 8,
+                starting_equipment: &["Leather Armor", "Rapier", "Dagger", "Backpack"],
                 level_1_features: vec![
                     Feature {
                         name: "Bardic Inspiration".to_string(),
@@ -101,6 +106,7 @@ impl CharacterClass {
                     Skill::Religion,
                 ],
                 base_hp: 8,
+                starting_equipment: &["Scale Mail", "Shield", "Mace", "Light Crossbow", "Bolts (20)", "Holy Symbol"],
                 level_1_features: vec![
                     Feature {
                         name: "Spellcasting".to_string(),
@@ -130,6 +136,7 @@ impl CharacterClass {
                     Skill::Survival,
                 ],
                 base_hp: 8,
+                starting_equipment: &["Leather Armor", "Shield", "Scimitar", "Backpack"],
                 level_1_features: vec![
                     Feature {
                         name: "Druidic".to_string(),
@@ -159,6 +166,7 @@ impl CharacterClass {
                     Skill::Survival,
                 ],
                 base_hp: 10,
+                starting_equipment: &["Chain Mail", "Shield", "Longsword", "Light Crossbow", "Bolts (20)"],
                 level_1_features: vec![
                     Feature {
                         name: "Fighting Style".to_string(),
@@ -196,6 +204,7 @@ impl CharacterClass {
                     Skill::Stealth,
                 ],
                 base_hp: 8,
+                starting_equipment: &["Shortsword", "Dagger", "Backpack"],
                 level_1_features: vec![
                     Feature {
                         name: "Unarmored Defense".to_string(),
@@ -223,6 +232,7 @@ impl CharacterClass {
                     Skill::Religion,
                 ],
                 base_hp: 10,
+                starting_equipment: &["Chain Mail", "Shield", "Longsword", "Javelin", "Holy Symbol"],
                 level_1_features: vec![
                     Feature {
                         name: "Lay on Hands".to_string(),
@@ -262,6 +272,7 @@ impl CharacterClass {
                     Skill::Survival,
                 ],
                 base_hp: 10,
+                starting_equipment: &["Scale Mail", "Shortsword", "Longbow", "Arrows (20)"],
                 level_1_features: vec![
                     Feature {
                         name: "Spellcasting".to_string(),
@@ -305,6 +316,7 @@ impl CharacterClass {
                     Skill::Athletics,
                 ],
                 base_hp: 8,
+                starting_equipment: &["Leather Armor", "Rapier", "Shortbow", "Arrows (20)", "Thieves' Tools"],
                 level_1_features: vec![
                     Feature {
                         name: "Expertise".to_string(),
@@ -344,6 +356,7 @@ impl CharacterClass {
                     Skill::Religion,
                 ],
                 base_hp: 6,
+                starting_equipment: &["Light Crossbow", "Bolts (20)", "Component Pouch", "Dagger"],
                 level_1_features: vec![
                     Feature {
                         name: "Spellcasting".to_string(),
@@ -372,6 +385,7 @@ impl CharacterClass {
                     Skill::Religion,
                 ],
                 base_hp: 8,
+                starting_equipment: &["Leather Armor", "Light Crossbow", "Bolts (20)", "Component Pouch", "Dagger"],
                 level_1_features: vec![
                     Feature {
                         name: "Otherworldly Patron".to_string(),
@@ -399,6 +413,7 @@ impl CharacterClass {
                     Skill::Religion,
                 ],
                 base_hp: 6,
+                starting_equipment: &["Quarterstaff", "Component Pouch", "Dagger", "Backpack"],
                 level_1_features: vec![
                     Feature {
                         name: "Spellcasting".to_string(),
@@ -472,6 +487,12 @@ mod tests {
             assert!(data.skill_count > 0);
             assert!(!data.skill_options.is_empty());
             assert!(data.base_hp > 0);
+            for item in data.starting_equipment {
+                assert!(
+                    crate::items::find_item(item).is_some(),
+                    "{class} starts with unknown item {item}"
+                );
+            }
         }
     }
 
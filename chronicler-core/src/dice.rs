@@ -3,7 +3,8 @@
 //! Supports standard dice notation: XdY+Z, advantage/disadvantage,
 //! keep highest/lowest, and more.
 
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
@@ -37,17 +38,76 @@ pub enum Advantage {
 
 impl Advantage {
     /// Combine two advantage states (advantage + disadvantage = normal).
+    ///
+    /// Chaining this over three or more sources depends on the order:
+    /// advantage, advantage, then disadvantage ends up normal, but
+    /// disadvantage arriving second leaves the last advantage standing. Use
+    /// [`from_sources`](Self::from_sources) to merge more than two.
     pub fn combine(self, other: Advantage) -> Advantage {
-        match (self, other) {
-            (Advantage::Normal, x) | (x, Advantage::Normal) => x,
-            (Advantage::Advantage, Advantage::Disadvantage) => Advantage::Normal,
-            (Advantage::Disadvantage, Advantage::Advantage) => Advantage::Normal,
-            (Advantage::Advantage, Advantage::Advantage) => Advantage::Advantage,
-            (Advantage::Disadvantage, Advantage::Disadvantage) => Advantage::Disadvantage,
+        Self::from_sources([self, other])
+    }
+
+    /// Merge every source of advantage and disadvantage on one roll.
+    ///
+    /// Neither stacks, and any of each cancels to a straight roll however
+    /// many there are, so the order of `sources` doesn't matter.
+    pub fn from_sources(sources: impl IntoIterator<Item = Advantage>) -> Advantage {
+        sources.into_iter().collect::<AdvantageSources>().resolve()
+    }
+}
+
+/// Whether any advantage and any disadvantage apply to a roll.
+///
+/// Unlike [`Advantage`], which forgets a cancelled pair, this keeps both, so
+/// [`combine`](Self::combine) is associative and sources can be gathered in
+/// any grouping before [`resolve`](Self::resolve) settles the roll.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AdvantageSources {
+    pub advantage: bool,
+    pub disadvantage: bool,
+}
+
+impl AdvantageSources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Both sets of sources together.
+    pub fn combine(self, other: AdvantageSources) -> AdvantageSources {
+        AdvantageSources {
+            advantage: self.advantage || other.advantage,
+            disadvantage: self.disadvantage || other.disadvantage,
+        }
+    }
+
+    /// How the d20 is rolled.
+    pub fn resolve(self) -> Advantage {
+        match (self.advantage, self.disadvantage) {
+            (true, false) => Advantage::Advantage,
+            (false, true) => Advantage::Disadvantage,
+            _ => Advantage::Normal,
+        }
+    }
+}
+
+impl From<Advantage> for AdvantageSources {
+    fn from(advantage: Advantage) -> Self {
+        AdvantageSources {
+            advantage: advantage == Advantage::Advantage,
+            disadvantage: advantage == Advantage::Disadvantage,
         }
     }
 }
 
+impl FromIterator<Advantage> for AdvantageSources {
+    fn from_iter<I: IntoIterator<Item = Advantage>>(sources: I) -> Self {
+        sources
+            .into_iter()
+            .map(AdvantageSources::from)
+            .fold(AdvantageSources::new(), AdvantageSources::combine)
+    }
+}
+
 /// Standard D&D die types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DieType {
@@ -252,6 +312,7 @@ impl DiceExpression {
                 rolls: rolls.clone(),
                 kept: kept_rolls,
                 subtotal,
+                rerolled: Vec::new(),
             });
             all_rolls.extend(rolls);
         }
@@ -273,6 +334,7 @@ impl DiceExpression {
             total,
             natural_20: d20_roll == Some(20),
             natural_1: d20_roll == Some(1),
+            advantage: Advantage::Normal,
         }
     }
 
@@ -319,11 +381,13 @@ impl DiceExpression {
                         rolls: vec![roll1, roll2],
                         kept: vec![chosen],
                         subtotal: chosen,
+                        rerolled: Vec::new(),
                     }],
                     modifier: self.modifier,
                     total,
                     natural_20: chosen == 20,
                     natural_1: chosen == 1,
+                    advantage,
                 }
             }
         }
@@ -357,6 +421,57 @@ pub struct ComponentResult {
     pub rolls: Vec<u32>,
     pub kept: Vec<u32>,
     pub subtotal: u32,
+    /// Dice that were rerolled, as (index into `rolls`, original face).
+    #[serde(default)]
+    pub rerolled: Vec<(usize, u32)>,
+}
+
+/// One die face in a [`RollBreakdown`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DieResult {
+    pub die_type: DieType,
+    pub value: u32,
+    /// Whether the die counts toward the total (false when dropped by
+    /// advantage, disadvantage, or keep-highest/lowest).
+    pub kept: bool,
+    /// The face this die showed before it was rerolled, if it was.
+    pub rerolled_from: Option<u32>,
+}
+
+/// Every die face behind a roll, for rendering "[4, 6] + 3 = 13".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollBreakdown {
+    pub dice: Vec<DieResult>,
+    pub modifier: i32,
+    pub total: i32,
+    pub advantage: Advantage,
+}
+
+impl fmt::Display for RollBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let faces: Vec<String> = self
+            .dice
+            .iter()
+            .map(|die| {
+                let face = match die.rerolled_from {
+                    Some(original) => format!("{original}→{}", die.value),
+                    None => die.value.to_string(),
+                };
+                if die.kept {
+                    face
+                } else {
+                    format!("({face})")
+                }
+            })
+            .collect();
+        write!(f, "[{}]", faces.join(", "))?;
+        match self.modifier {
+            0 => {}
+            m if m > 0 => write!(f, " + {m}")?,
+            m => write!(f, " - {}", m.abs())?,
+        }
+        write!(f, " = {}", self.total)
+    }
 }
 
 /// Complete result of a dice roll.
@@ -368,9 +483,50 @@ pub struct RollResult {
     pub total: i32,
     pub natural_20: bool,
     pub natural_1: bool,
+    /// Advantage state the d20 was rolled with.
+    #[serde(default)]
+    pub advantage: Advantage,
 }
 
 impl RollResult {
+    /// Structured view of every die rolled, which were kept, and which
+    /// were rerolled.
+    pub fn breakdown(&self) -> RollBreakdown {
+        let mut dice = Vec::new();
+        for component in &self.component_results {
+            // Match kept faces against rolls one-for-one so duplicates are
+            // only counted once
+            let mut unmatched = component.kept.clone();
+            for (index, &value) in component.rolls.iter().enumerate() {
+                let kept = match unmatched.iter().position(|&k| k == value) {
+                    Some(i) => {
+                        unmatched.swap_remove(i);
+                        true
+                    }
+                    None => false,
+                };
+                let rerolled_from = component
+                    .rerolled
+                    .iter()
+                    .find(|(i, _)| *i == index)
+                    .map(|&(_, original)| original);
+                dice.push(DieResult {
+                    die_type: component.die_type,
+                    value,
+                    kept,
+                    rerolled_from,
+                });
+            }
+        }
+
+        RollBreakdown {
+            dice,
+            modifier: self.modifier,
+            total: self.total,
+            advantage: self.advantage,
+        }
+    }
+
     /// Format the individual dice results for display.
     pub fn dice_display(&self) -> String {
         let dice_parts: Vec<String> = self
@@ -433,6 +589,24 @@ impl RollResult {
         self.natural_20
     }
 
+    /// The kept natural d20 result, for a single d20 roll (with or without
+    /// advantage).
+    pub fn natural_d20(&self) -> Option<u32> {
+        self.component_results
+            .iter()
+            .find(|c| c.die_type == DieType::D20 && c.kept.len() == 1)
+            .map(|c| c.kept[0])
+    }
+
+    /// Check for a critical hit when the attacker crits on a natural
+    /// `crit_range` or higher (20 normally, lower with Improved Critical).
+    pub fn is_critical_on(&self, crit_range: u8) -> bool {
+        self.natural_20
+            || self
+                .natural_d20()
+                .is_some_and(|natural| natural >= crit_range as u32)
+    }
+
     /// Check if this was a critical failure (natural 1 on attack).
     pub fn is_fumble(&self) -> bool {
         self.natural_1
@@ -457,6 +631,104 @@ pub fn roll_with_advantage(notation: &str, advantage: Advantage) -> Result<RollR
     Ok(expr.roll_with_advantage(advantage))
 }
 
+/// A seeded dice roller whose position in the roll sequence can be saved.
+///
+/// It serializes as its seed and the number of 32-bit words drawn so far;
+/// deserializing reseeds and skips ahead, so a restored roller produces
+/// exactly the rolls the original would have made next.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "DiceRollerState", from = "DiceRollerState")]
+pub struct DiceRoller {
+    seed: u64,
+    draws: u64,
+    rng: StdRng,
+}
+
+/// On-disk form of a [`DiceRoller`].
+#[derive(Serialize, Deserialize)]
+struct DiceRollerState {
+    seed: u64,
+    draws: u64,
+}
+
+impl DiceRoller {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            draws: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Number of 32-bit words drawn since seeding.
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// Roll dice from a notation string.
+    pub fn roll(&mut self, notation: &str) -> Result<RollResult, DiceError> {
+        let expr = DiceExpression::parse(notation)?;
+        Ok(expr.roll_with_rng(self))
+    }
+
+    /// Roll with advantage/disadvantage.
+    pub fn roll_with_advantage(
+        &mut self,
+        notation: &str,
+        advantage: Advantage,
+    ) -> Result<RollResult, DiceError> {
+        let expr = DiceExpression::parse(notation)?;
+        Ok(expr.roll_with_advantage_rng(advantage, self))
+    }
+}
+
+impl From<DiceRoller> for DiceRollerState {
+    fn from(roller: DiceRoller) -> Self {
+        Self {
+            seed: roller.seed,
+            draws: roller.draws,
+        }
+    }
+}
+
+impl From<DiceRollerState> for DiceRoller {
+    fn from(state: DiceRollerState) -> Self {
+        let mut roller = DiceRoller::new(state.seed);
+        for _ in 0..state.draws {
+            roller.next_u32();
+        }
+        roller
+    }
+}
+
+// The underlying generator hands out 32-bit words: a u64 takes two and
+// bytes are drawn four at a time, so counting words pins down the position.
+impl RngCore for DiceRoller {
+    fn next_u32(&mut self) -> u32 {
+        self.draws += 1;
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.draws += 2;
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.draws += dest.len().div_ceil(4) as u64;
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -547,4 +819,118 @@ mod tests {
             Advantage::Advantage
         );
     }
+
+    const ADVANTAGES: [Advantage; 3] = [
+        Advantage::Normal,
+        Advantage::Advantage,
+        Advantage::Disadvantage,
+    ];
+
+    #[test]
+    fn test_advantage_combination_table() {
+        use Advantage::{Advantage as Adv, Disadvantage as Dis, Normal};
+        let table = [
+            (Normal, Normal, Normal),
+            (Normal, Adv, Adv),
+            (Normal, Dis, Dis),
+            (Adv, Normal, Adv),
+            (Adv, Adv, Adv),
+            (Adv, Dis, Normal),
+            (Dis, Normal, Dis),
+            (Dis, Adv, Normal),
+            (Dis, Dis, Dis),
+        ];
+        for (a, b, expected) in table {
+            assert_eq!(a.combine(b), expected, "{a:?} + {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_advantage_sources_merge_in_any_order() {
+        for a in ADVANTAGES {
+            for b in ADVANTAGES {
+                for c in ADVANTAGES {
+                    let merged = Advantage::from_sources([a, b, c]);
+                    let has = |x| [a, b, c].contains(&x);
+                    let expected = match (has(Advantage::Advantage), has(Advantage::Disadvantage)) {
+                        (true, false) => Advantage::Advantage,
+                        (false, true) => Advantage::Disadvantage,
+                        _ => Advantage::Normal,
+                    };
+                    assert_eq!(merged, expected, "{a:?}, {b:?}, {c:?}");
+                    assert_eq!(Advantage::from_sources([c, a, b]), merged);
+                }
+            }
+        }
+        assert_eq!(Advantage::from_sources([]), Advantage::Normal);
+    }
+
+    #[test]
+    fn test_advantage_sources_combine_associatively() {
+        let states: Vec<AdvantageSources> = ADVANTAGES
+            .iter()
+            .flat_map(|&a| ADVANTAGES.map(|b| [a, b].into_iter().collect()))
+            .collect();
+        for &x in &states {
+            assert_eq!(x.combine(AdvantageSources::new()), x);
+            for &y in &states {
+                assert_eq!(x.combine(y), y.combine(x));
+                for &z in &states {
+                    assert_eq!(x.combine(y).combine(z), x.combine(y.combine(z)));
+                }
+            }
+        }
+    }
+
+    fn kept_sum(breakdown: &RollBreakdown) -> i32 {
+        breakdown
+            .dice
+            .iter()
+            .filter(|d| d.kept)
+            .map(|d| d.value as i32)
+            .sum::<i32>()
+            + breakdown.modifier
+    }
+
+    #[test]
+    fn test_breakdown_matches_total() {
+        for notation in ["2d6+3", "4d6kh3", "1d8+1d4-1"] {
+            let result = roll(notation).unwrap();
+            let breakdown = result.breakdown();
+            assert_eq!(kept_sum(&breakdown), result.total, "{notation}");
+            assert_eq!(breakdown.total, result.total);
+        }
+
+        let result = DiceExpression::parse("1d20+3")
+            .unwrap()
+            .roll_with_advantage(Advantage::Advantage);
+        let breakdown = result.breakdown();
+        assert_eq!(breakdown.advantage, Advantage::Advantage);
+        assert_eq!(breakdown.dice.len(), 2);
+        assert_eq!(breakdown.dice.iter().filter(|d| d.kept).count(), 1);
+        assert_eq!(kept_sum(&breakdown), result.total);
+        assert!(breakdown
+            .to_string()
+            .ends_with(&format!(" + 3 = {}", result.total)));
+    }
+
+    #[test]
+    fn test_breakdown_survives_effect_serialization() {
+        let roll = DiceExpression::parse("1d20-1")
+            .unwrap()
+            .roll_with_advantage(Advantage::Disadvantage);
+        let effect = crate::rules::Effect::DiceRolled {
+            roll: roll.clone(),
+            purpose: "test".to_string(),
+        };
+
+        let json = serde_json::to_string(&effect).unwrap();
+        let crate::rules::Effect::DiceRolled { roll: restored, .. } =
+            serde_json::from_str(&json).unwrap()
+        else {
+            panic!("Expected DiceRolled");
+        };
+        assert_eq!(restored.breakdown(), roll.breakdown());
+        assert_eq!(restored.advantage, Advantage::Disadvantage);
+    }
 }
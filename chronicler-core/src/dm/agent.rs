@@ -4,18 +4,32 @@
 //! D&D gameplay. It uses the Claude API to generate narrative responses
 //! and tool calls that are resolved by the RulesEngine.
 
-use super::memory::{DmMemory, FactCategory};
+use super::memory::{DmMemory, FactCategory, DEFAULT_HISTORY_WINDOW};
+use super::metrics::ToolMetrics;
 use super::relevance::{InferredStateChange, RelevanceChecker, RelevanceResult, StateInferrer};
 use super::story_memory::{
-    ConsequenceSeverity, EntityType, FactCategory as StoryFactCategory, FactSource, StoryMemory,
+    ConsequenceSeverity, EntityType, FactCategory as StoryFactCategory, FactSource, PruneConfig,
+    StoryMemory,
 };
-use super::tools::{execute_info_tool_with_memory, parse_tool_call, DmTools};
-use crate::rules::{apply_effects, Effect, Intent, Resolution, RulesEngine, StateType};
-use crate::world::{GameMode, GameWorld, NarrativeType};
-use claude::{Claude, ContentBlock, Message, Request, StopReason, StreamEvent, ToolResult};
+use super::tools::{execute_info_tool_with_memory, DmTools, ToolRegistry};
+use crate::rules::{
+    apply_effects, narrate_outcomes, Effect, EffectOutcome, Intent, Locale, Resolution,
+    RulesEngine, StateType,
+};
+use crate::world::{
+    reputation_standing, Concentration, GameMode, GameWorld, LightLevel, NarrativeType,
+};
+use claude::{Claude, ContentBlock, Message, Request, StopReason, StreamEvent, Tool, ToolResult};
 use futures::StreamExt;
 use thiserror::Error;
 
+/// Default number of rounds of tool results fed back to the model in one turn.
+pub const DEFAULT_MAX_TOOL_ROUNDS: usize = 8;
+
+/// Default number of follow-up requests made to finish a reply cut off by
+/// the token limit.
+pub const DEFAULT_MAX_CONTINUATIONS: usize = 2;
+
 /// Errors from the DM agent.
 #[derive(Debug, Error)]
 pub enum DmError {
@@ -38,7 +52,10 @@ pub struct DmConfig {
     /// Maximum tokens for responses.
     pub max_tokens: usize,
 
-    /// Temperature for generation.
+    /// Temperature for the DM's narration.
+    ///
+    /// Relevance checks and state inference ignore this and always run at
+    /// [`HELPER_TEMPERATURE`](super::HELPER_TEMPERATURE).
     pub temperature: Option<f32>,
 
     /// System prompt customization.
@@ -68,6 +85,60 @@ pub struct DmConfig {
     /// Only changes with confidence >= this threshold are applied.
     /// Default: 0.8 (high confidence only).
     pub state_inference_confidence: f32,
+
+    /// Whether to take state inference off the critical path of a turn.
+    ///
+    /// When `true`, a turn returns as soon as the DM has narrated. Inference
+    /// on that narrative runs at the start of the next turn, concurrently
+    /// with the DM's first request. Its changes are applied (and reported in
+    /// that turn's response) once both finish, so the DM's first reply is
+    /// written without them but its tools act on the updated world.
+    pub deferred_inference: bool,
+
+    /// Whether to send the turn's first DM request alongside the relevance
+    /// check instead of after it.
+    ///
+    /// This hides the check's latency, but the request goes out with the
+    /// prompt as it stood before the check. When the check triggers a
+    /// consequence, that reply is discarded and the request sent again, so
+    /// those turns pay for two full DM calls.
+    pub speculative_dm_request: bool,
+
+    /// How many recent conversation messages are sent with each request.
+    ///
+    /// This is independent of how much history memory retains.
+    pub history_window: usize,
+
+    /// Whether to use a fast model to decide which consequences trigger.
+    ///
+    /// When `false`, or when the relevance call fails, consequences are
+    /// matched locally by keyword overlap instead.
+    pub enable_relevance_check: bool,
+
+    /// When to archive stale story facts out of the active set.
+    /// Pruning runs once per turn.
+    pub fact_pruning: PruneConfig,
+
+    /// Whether to send only the tools suited to the current game mode.
+    ///
+    /// When `true` (default), see [`DmTools::for_mode`]. When `false`, every
+    /// tool is sent on every request.
+    pub mode_aware_tools: bool,
+
+    /// How many times tool results are fed back to the model in one turn.
+    ///
+    /// Each round lets the DM see what its tools did (a roll, a rejected
+    /// call) and continue. When the bound is reached the turn ends with the
+    /// narrative gathered so far, so a model that keeps calling tools cannot
+    /// loop forever.
+    pub max_tool_rounds: usize,
+
+    /// How many times a reply cut off by `max_tokens` is continued.
+    ///
+    /// Each continuation sends the partial reply back as the start of the
+    /// assistant's message so the model picks up mid-sentence, and the pieces
+    /// are joined into one narrative. Zero leaves truncated replies as they are.
+    pub max_continuations: usize,
 }
 
 impl Default for DmConfig {
@@ -80,6 +151,14 @@ impl Default for DmConfig {
             deferred_effects: false,
             enable_state_inference: true, // Enable by default
             state_inference_confidence: 0.8,
+            deferred_inference: false,
+            speculative_dm_request: false,
+            history_window: DEFAULT_HISTORY_WINDOW,
+            enable_relevance_check: true,
+            fact_pruning: PruneConfig::default(),
+            mode_aware_tools: true,
+            max_tool_rounds: DEFAULT_MAX_TOOL_ROUNDS,
+            max_continuations: DEFAULT_MAX_CONTINUATIONS,
         }
     }
 }
@@ -96,12 +175,22 @@ pub struct DmResponse {
     /// Effects from resolving intents.
     pub effects: Vec<Effect>,
 
+    /// What applying each of `effects` did to the world, in the same order.
+    pub outcomes: Vec<EffectOutcome>,
+
     /// Resolution details for each intent.
     pub resolutions: Vec<Resolution>,
 
     /// State changes inferred from the narrative (if inference is enabled).
-    /// These are already applied to the world state.
+    /// These are already applied to the world state. With
+    /// [`DmConfig::deferred_inference`], they come from the previous turn's
+    /// narrative.
     pub inferred_state_changes: Vec<InferredStateChange>,
+
+    /// Whether the turn was cut short because the DM was still calling
+    /// tools after [`DmConfig::max_tool_rounds`] rounds. The narrative is
+    /// whatever had been written by then.
+    pub tool_round_limit_reached: bool,
 }
 
 /// The AI Dungeon Master.
@@ -111,30 +200,40 @@ pub struct DungeonMaster {
     memory: DmMemory,
     story_memory: StoryMemory,
     rules: RulesEngine,
+    tools: ToolRegistry,
+    tool_metrics: ToolMetrics,
+    /// Narrative whose state inference was deferred to the next turn.
+    pending_inference: Option<String>,
 }
 
 impl DungeonMaster {
     /// Create a new DungeonMaster with an API key.
     pub fn new(api_key: impl Into<String>) -> Self {
-        Self {
-            client: Claude::new(api_key),
-            config: DmConfig::default(),
-            memory: DmMemory::new(),
-            story_memory: StoryMemory::new(),
-            rules: RulesEngine::new(),
-        }
+        Self::with_client(Claude::new(api_key))
     }
 
     /// Create a DungeonMaster from the ANTHROPIC_API_KEY environment variable.
     pub fn from_env() -> Result<Self, DmError> {
-        let client = Claude::from_env()?;
-        Ok(Self {
+        match Claude::from_env() {
+            Ok(client) => Ok(Self::with_client(client)),
+            Err(claude::Error::NoApiKey) => Err(DmError::NoApiKey),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Create a DungeonMaster around an existing client, e.g. one using a
+    /// custom transport.
+    pub fn with_client(client: Claude) -> Self {
+        Self {
             client,
             config: DmConfig::default(),
             memory: DmMemory::new(),
             story_memory: StoryMemory::new(),
             rules: RulesEngine::new(),
-        })
+            tools: ToolRegistry::new(),
+            tool_metrics: ToolMetrics::new(),
+            pending_inference: None,
+        }
     }
 
     /// Get the story memory.
@@ -153,6 +252,18 @@ impl DungeonMaster {
         self
     }
 
+    /// Offer the custom tools in `tools` alongside the built-in ones.
+    pub fn with_tool_registry(mut self, tools: ToolRegistry) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    /// Write mechanical narration with the templates in `locale`.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.rules = self.rules.with_locale(locale);
+        self
+    }
+
     /// Get the current memory.
     pub fn memory(&self) -> &DmMemory {
         &self.memory
@@ -163,14 +274,42 @@ impl DungeonMaster {
         &mut self.memory
     }
 
+    /// The rules engine tool calls are resolved with.
+    pub fn rules(&self) -> &RulesEngine {
+        &self.rules
+    }
+
+    /// Get mutable access to the rules engine, e.g. to seed its dice.
+    pub fn rules_mut(&mut self) -> &mut RulesEngine {
+        &mut self.rules
+    }
+
+    /// Narrative from the last turn whose state inference was deferred to
+    /// the next one (see [`DmConfig::deferred_inference`]).
+    pub fn pending_inference(&self) -> Option<&str> {
+        self.pending_inference.as_deref()
+    }
+
+    /// Queue `narrative` for inference at the start of the next turn, e.g.
+    /// when resuming a saved session.
+    pub fn set_pending_inference(&mut self, narrative: Option<String>) {
+        self.pending_inference = narrative;
+    }
+
+    /// Counts of the tools called so far and how many failed.
+    pub fn tool_metrics(&self) -> &ToolMetrics {
+        &self.tool_metrics
+    }
+
     /// Process a player's action and generate a response.
     pub async fn process_input(
         &mut self,
         player_input: &str,
         world: &mut GameWorld,
     ) -> Result<DmResponse, DmError> {
-        // Advance story turn
+        // Advance story turn and archive stale facts
         self.story_memory.advance_turn();
+        self.story_memory.prune(&self.config.fact_pruning);
 
         // Add player input to memory
         self.memory.add_player_message(player_input);
@@ -178,58 +317,94 @@ impl DungeonMaster {
         // Add to game world narrative
         world.add_narrative(player_input.to_string(), NarrativeType::PlayerAction);
 
-        // Check for relevant consequences using fast model (Haiku)
-        let relevance_result = self.check_relevance(player_input, world).await?;
-
-        // Mark triggered consequences
-        self.apply_relevance_results(&relevance_result);
-
-        // Build system prompt with story context for this input
-        let mut system_prompt = self.build_system_prompt(world, player_input);
-
-        // Add triggered consequences to context
-        let triggered_context = self.build_triggered_consequences_context(&relevance_result);
-        if !triggered_context.is_empty() {
-            system_prompt.push_str(&triggered_context);
-        }
+        // Check for relevant consequences using fast model (Haiku); with a
+        // speculative DM request, the DM starts on a reply alongside it
+        let speculative_prompt = self
+            .config
+            .speculative_dm_request
+            .then(|| self.build_system_prompt(world, player_input));
+        let (relevance, early_response) = match &speculative_prompt {
+            Some(prompt) => {
+                let request = self.dm_request(self.initial_messages(), prompt, world);
+                let (relevance, response) = tokio::join!(
+                    self.check_relevance(player_input, world),
+                    self.client.complete(request)
+                );
+                (relevance, Some(response))
+            }
+            None => (self.check_relevance(player_input, world).await, None),
+        };
+        let system_prompt = self.consequence_prompt(&relevance?, player_input, world);
+        // A reply to a prompt the check changed is asked for again
+        let early_response = early_response
+            .filter(|_| speculative_prompt.as_deref() == Some(system_prompt.as_str()));
+
+        // Catch up on inference deferred from the last turn while the DM
+        // writes its first reply. Its changes land before any of the DM's
+        // tools run.
+        let pending = self.pending_inference.take();
+        let (inferred, first_response) =
+            tokio::join!(self.infer_pending(pending.as_deref(), world), async {
+                match early_response {
+                    Some(response) => response,
+                    None => {
+                        let request =
+                            self.dm_request(self.initial_messages(), &system_prompt, world);
+                        self.client.complete(request).await
+                    }
+                }
+            });
+        let mut inferred_state_changes = inferred?;
+        self.apply_inferred_state_changes(&inferred_state_changes, world);
+        let mut early_response = Some(first_response);
 
         // Track intents, effects, and resolutions
         let mut all_intents = Vec::new();
         let mut all_effects = Vec::new();
+        let mut all_outcomes = Vec::new();
         let mut all_resolutions = Vec::new();
         let mut narrative = String::new();
 
         // Build initial messages
-        let mut messages = self.memory.get_messages();
+        let mut messages = self.initial_messages();
 
         // Tool use loop
+        let mut tool_rounds = 0;
+        let mut tool_round_limit_reached = false;
+        let mut continuations = 0;
+        let mut nudged = false;
+        // The partial reply being continued, sent as the last message
+        let mut prefill: Option<Vec<ContentBlock>> = None;
         loop {
-            let tools = DmTools::all();
-
-            let mut request = Request::new(messages.clone())
-                .with_system(&system_prompt)
-                .with_max_tokens(self.config.max_tokens)
-                .with_tools(tools);
-
-            if let Some(ref model) = self.config.model {
-                request = request.with_model(model);
-            }
-
-            if let Some(temp) = self.config.temperature {
-                request = request.with_temperature(temp);
-            }
-
             // Make API call
-            let response = self.client.complete(request).await?;
+            let response = match early_response.take() {
+                Some(response) => response?,
+                None => {
+                    let request = self.dm_request(messages.clone(), &system_prompt, world);
+                    self.client.complete(request).await?
+                }
+            };
+
+            // A continuation finishes the prefilled message rather than
+            // starting a new one
+            let mut content = match prefill.take() {
+                Some(partial) => {
+                    messages.pop();
+                    partial
+                }
+                None => Vec::new(),
+            };
+            let mut joining = !content.is_empty();
 
             // Collect tool uses
             let mut tool_uses = Vec::new();
             for block in &response.content {
                 match block {
                     ContentBlock::Text { text } => {
-                        if !narrative.is_empty() {
+                        if !narrative.is_empty() && !joining {
                             narrative.push('\n');
                         }
+                        joining = false;
                         narrative.push_str(text);
                     }
                     ContentBlock::ToolUse { id, name, input } => {
@@ -238,33 +413,64 @@ impl DungeonMaster {
                     _ => {}
                 }
             }
+            content.extend(response.content.iter().cloned());
+
+            // Ask the model to finish a reply cut off by the token limit
+            if response.stop_reason == StopReason::MaxTokens
+                && tool_uses.is_empty()
+                && continuations < self.config.max_continuations
+            {
+                let partial = continuation_prefill(content.clone());
+                if !partial.is_empty() {
+                    continuations += 1;
+                    narrative.truncate(narrative.trim_end().len());
+                    messages.push(Message {
+                        role: claude::Role::Assistant,
+                        content: partial.clone(),
+                    });
+                    prefill = Some(partial);
+                    continue;
+                }
+            }
+
+            // A reply with nothing in it is retried once rather than shown
+            if tool_uses.is_empty() && narrative.trim().is_empty() && !nudged {
+                nudged = true;
+                narrative.clear();
+                nudge_after_empty_reply(&mut messages);
+                continue;
+            }
 
             // If no tool calls or stop reason isn't ToolUse, we're done
             if response.stop_reason != StopReason::ToolUse || tool_uses.is_empty() {
                 break;
             }
+            tool_rounds += 1;
+            if tool_rounds > self.config.max_tool_rounds {
+                tool_round_limit_reached = true;
+                break;
+            }
 
             // Add assistant response to messages
             messages.push(Message {
                 role: claude::Role::Assistant,
-                content: response.content.clone(),
+                content,
             });
 
             // Execute tools and collect results
             let mut tool_results = Vec::new();
             for (id, name, input) in tool_uses {
                 // First check if it's an informational tool
-                let result = if let Some(info_result) =
-                    execute_info_tool_with_memory(&name, &input, world, &self.story_memory)
+                let result = if let Some(info_result) = self.info_tool_result(&name, &input, world)
                 {
                     // Info tools just return data without changing state
                     ToolResult::success(&info_result)
-                } else if let Some(intent) = parse_tool_call(&name, &input, world) {
+                } else if let Some(intent) = self.tools.parse_tool_call(&name, &input, world) {
                     // Resolve the intent
                     let resolution = self.rules.resolve(world, intent.clone());
 
                     // Apply effects to world
-                    apply_effects(world, &resolution.effects);
+                    let outcomes = apply_effects(world, &resolution.effects);
 
                     // Handle FactRemembered and ConsequenceRegistered effects specially - store in story memory
                     for effect in &resolution.effects {
@@ -302,16 +508,21 @@ impl DungeonMaster {
                         }
                     }
 
+                    // Record the mechanical outcome for the audit trail
+                    world.add_narrative(resolution.narrative.clone(), NarrativeType::Mechanics);
+
                     // Store for response
                     all_intents.push(intent);
                     all_effects.extend(resolution.effects.clone());
+                    all_outcomes.extend(outcomes.iter().cloned());
                     all_resolutions.push(resolution.clone());
 
-                    // Return narrative as tool result
-                    ToolResult::success(&resolution.narrative)
+                    // Return narrative as tool result, noting what didn't apply
+                    ToolResult::success(narrate_outcomes(&resolution.narrative, &outcomes))
                 } else {
-                    ToolResult::error(format!("Unknown tool: {name}"))
+                    ToolResult::error(self.tools.invalid_tool_call_message(&name, &input, world))
                 };
+                self.tool_metrics.record(&name, !result.is_error);
 
                 tool_results.push(ContentBlock::ToolResult {
                     tool_use_id: id,
@@ -334,19 +545,16 @@ impl DungeonMaster {
         world.add_narrative(narrative.clone(), NarrativeType::DmNarration);
 
         // Run post-narrative state inference if enabled
-        let inferred_state_changes = if self.config.enable_state_inference {
-            self.infer_and_apply_state_changes(&narrative, world)
-                .await?
-        } else {
-            Vec::new()
-        };
+        inferred_state_changes.extend(self.infer_after_narration(&narrative, world).await?);
 
         Ok(DmResponse {
             narrative,
             intents: all_intents,
             effects: all_effects,
+            outcomes: all_outcomes,
             resolutions: all_resolutions,
             inferred_state_changes,
+            tool_round_limit_reached,
         })
     }
 
@@ -384,8 +592,9 @@ impl DungeonMaster {
         F: FnMut(&str) + Send,
         E: FnMut(&Effect) + Send,
     {
-        // Advance story turn
+        // Advance story turn and archive stale facts
         self.story_memory.advance_turn();
+        self.story_memory.prune(&self.config.fact_pruning);
 
         // Add player input to memory
         self.memory.add_player_message(player_input);
@@ -393,66 +602,106 @@ impl DungeonMaster {
         // Add to game world narrative
         world.add_narrative(player_input.to_string(), NarrativeType::PlayerAction);
 
-        // Check for relevant consequences using fast model (Haiku)
-        let relevance_result = self.check_relevance(player_input, world).await?;
-
-        // Mark triggered consequences
-        self.apply_relevance_results(&relevance_result);
-
-        // Build system prompt with story context for this input
-        let mut system_prompt = self.build_system_prompt(world, player_input);
-
-        // Add triggered consequences to context
-        let triggered_context = self.build_triggered_consequences_context(&relevance_result);
-        if !triggered_context.is_empty() {
-            system_prompt.push_str(&triggered_context);
-        }
+        // Check for relevant consequences using fast model (Haiku); with a
+        // speculative DM request, the DM starts on a reply alongside it
+        let speculative_prompt = self
+            .config
+            .speculative_dm_request
+            .then(|| self.build_system_prompt(world, player_input));
+        let (relevance, early_stream) = match &speculative_prompt {
+            Some(prompt) => {
+                let request = self.dm_request(self.initial_messages(), prompt, world);
+                let (relevance, stream) = tokio::join!(
+                    self.check_relevance(player_input, world),
+                    self.client.stream(request)
+                );
+                (relevance, Some(stream))
+            }
+            None => (self.check_relevance(player_input, world).await, None),
+        };
+        let system_prompt = self.consequence_prompt(&relevance?, player_input, world);
+        // A reply to a prompt the check changed is asked for again
+        let early_stream =
+            early_stream.filter(|_| speculative_prompt.as_deref() == Some(system_prompt.as_str()));
+
+        // Catch up on inference deferred from the last turn while the DM
+        // writes its first reply. Its changes land before any of the DM's
+        // tools run.
+        let pending = self.pending_inference.take();
+        let (inferred, first_stream) =
+            tokio::join!(self.infer_pending(pending.as_deref(), world), async {
+                match early_stream {
+                    Some(stream) => stream,
+                    None => {
+                        let request =
+                            self.dm_request(self.initial_messages(), &system_prompt, world);
+                        self.client.stream(request).await
+                    }
+                }
+            });
+        let mut inferred_state_changes = inferred?;
+        self.apply_inferred_state_changes(&inferred_state_changes, world);
+        let mut early_stream = Some(first_stream);
 
         // Track intents, effects, and resolutions
         let mut all_intents = Vec::new();
         let mut all_effects = Vec::new();
+        let mut all_outcomes = Vec::new();
         let mut all_resolutions = Vec::new();
         let mut narrative = String::new();
 
         // Build initial messages
-        let mut messages = self.memory.get_messages();
+        let mut messages = self.initial_messages();
 
         // Tool use loop
         let mut iteration = 0;
+        let mut tool_rounds = 0;
+        let mut tool_round_limit_reached = false;
+        let mut continuations = 0;
+        let mut nudged = false;
+        // Whether this request finishes a reply cut off by the token limit,
+        // and where in the narrative that reply began
+        let mut continuing = false;
+        let mut message_start = 0;
         loop {
             // Add paragraph break between narrative from different API calls
             // (e.g., when continuing after tool results)
-            if iteration > 0 && !narrative.is_empty() && !narrative.ends_with('\n') {
+            if iteration > 0 && !continuing && !narrative.is_empty() && !narrative.ends_with('\n') {
                 narrative.push_str("\n\n");
                 on_text("\n\n");
             }
-            iteration += 1;
-
-            let tools = DmTools::all();
-
-            let mut request = Request::new(messages.clone())
-                .with_system(&system_prompt)
-                .with_max_tokens(self.config.max_tokens)
-                .with_tools(tools);
-
-            if let Some(ref model) = self.config.model {
-                request = request.with_model(model);
-            }
-
-            if let Some(temp) = self.config.temperature {
-                request = request.with_temperature(temp);
+            if !continuing {
+                message_start = narrative.len();
             }
+            iteration += 1;
 
             // Use streaming API
-            let mut stream = self.client.stream(request).await?;
+            let mut stream = match early_stream.take() {
+                Some(stream) => stream?,
+                None => {
+                    let request = self.dm_request(messages.clone(), &system_prompt, world);
+                    self.client.stream(request).await?
+                }
+            };
 
             // Track tool uses being accumulated
             let mut tool_uses: Vec<PartialToolUse> = Vec::new();
             let mut current_tool_index: Option<usize> = None;
             let mut stop_reason = StopReason::EndTurn;
+            let mut connection_dropped = false;
 
             while let Some(event_result) = stream.next().await {
-                let event = event_result?;
+                // A connection dropped mid-response keeps the narrative that
+                // already reached the player instead of discarding the turn.
+                let event = match event_result {
+                    Ok(event) => event,
+                    Err(_) if !narrative.is_empty() => {
+                        stop_reason = StopReason::MaxTokens;
+                        connection_dropped = true;
+                        break;
+                    }
+                    Err(e) => return Err(e.into()),
+                };
                 match event {
                     StreamEvent::TextDelta { text, .. } => {
                         // Send text to callback immediately
@@ -496,6 +745,7 @@ impl DungeonMaster {
                     }
                     StreamEvent::MessageDelta {
                         stop_reason: Some(sr),
+                        ..
                     } => {
                         stop_reason = sr;
                     }
@@ -508,10 +758,47 @@ impl DungeonMaster {
                 }
             }
 
+            // The prefilled partial reply is replaced by the finished one
+            if std::mem::take(&mut continuing) {
+                messages.pop();
+            }
+
+            // Ask the model to finish a reply cut off by the token limit
+            if stop_reason == StopReason::MaxTokens
+                && !connection_dropped
+                && tool_uses.is_empty()
+                && continuations < self.config.max_continuations
+                && narrative.trim_end().len() > message_start
+            {
+                continuations += 1;
+                narrative.truncate(narrative.trim_end().len());
+                messages.push(Message {
+                    role: claude::Role::Assistant,
+                    content: vec![ContentBlock::Text {
+                        text: narrative[message_start..].to_string(),
+                    }],
+                });
+                continuing = true;
+                continue;
+            }
+
+            // A reply with nothing in it is retried once rather than shown
+            if tool_uses.is_empty() && narrative.trim().is_empty() && !nudged {
+                nudged = true;
+                narrative.clear();
+                nudge_after_empty_reply(&mut messages);
+                continue;
+            }
+
             // If no tool calls or stop reason isn't ToolUse, we're done
             if stop_reason != StopReason::ToolUse || tool_uses.is_empty() {
                 break;
             }
+            tool_rounds += 1;
+            if tool_rounds > self.config.max_tool_rounds {
+                tool_round_limit_reached = true;
+                break;
+            }
 
             // Build assistant message content from what we received
             let mut assistant_content: Vec<ContentBlock> = Vec::new();
@@ -552,18 +839,20 @@ impl DungeonMaster {
 
                 // First check if it's an informational tool
                 let result = if let Some(info_result) =
-                    execute_info_tool_with_memory(&tool.name, &input, world, &self.story_memory)
+                    self.info_tool_result(&tool.name, &input, world)
                 {
                     // Info tools just return data without changing state
                     ToolResult::success(&info_result)
-                } else if let Some(intent) = parse_tool_call(&tool.name, &input, world) {
+                } else if let Some(intent) = self.tools.parse_tool_call(&tool.name, &input, world) {
                     // Resolve the intent
                     let resolution = self.rules.resolve(world, intent.clone());
 
                     // Apply effects to world (unless deferred)
-                    if !self.config.deferred_effects {
-                        apply_effects(world, &resolution.effects);
-                    }
+                    let outcomes = if self.config.deferred_effects {
+                        Vec::new()
+                    } else {
+                        apply_effects(world, &resolution.effects)
+                    };
 
                     // Handle FactRemembered and ConsequenceRegistered effects specially - store in story memory
                     for effect in &resolution.effects {
@@ -608,16 +897,24 @@ impl DungeonMaster {
                         }
                     }
 
+                    // Record the mechanical outcome for the audit trail
+                    world.add_narrative(resolution.narrative.clone(), NarrativeType::Mechanics);
+
                     // Store for response
                     all_intents.push(intent);
                     all_effects.extend(resolution.effects.clone());
+                    all_outcomes.extend(outcomes.iter().cloned());
                     all_resolutions.push(resolution.clone());
 
-                    // Return narrative as tool result
-                    ToolResult::success(&resolution.narrative)
+                    // Return narrative as tool result, noting what didn't apply
+                    ToolResult::success(narrate_outcomes(&resolution.narrative, &outcomes))
                 } else {
-                    ToolResult::error(format!("Unknown tool: {}", tool.name))
+                    ToolResult::error(
+                        self.tools
+                            .invalid_tool_call_message(&tool.name, &input, world),
+                    )
                 };
+                self.tool_metrics.record(&tool.name, !result.is_error);
 
                 tool_results.push(ContentBlock::ToolResult {
                     tool_use_id: tool.id,
@@ -637,7 +934,7 @@ impl DungeonMaster {
 
         // If effects were deferred, apply them all atomically now that streaming succeeded
         if self.config.deferred_effects && !all_effects.is_empty() {
-            apply_effects(world, &all_effects);
+            all_outcomes = apply_effects(world, &all_effects);
             // Stream all effects at once at the end
             for effect in &all_effects {
                 on_effect(effect);
@@ -651,19 +948,16 @@ impl DungeonMaster {
         world.add_narrative(narrative.clone(), NarrativeType::DmNarration);
 
         // Run post-narrative state inference if enabled
-        let inferred_state_changes = if self.config.enable_state_inference {
-            self.infer_and_apply_state_changes(&narrative, world)
-                .await?
-        } else {
-            Vec::new()
-        };
+        inferred_state_changes.extend(self.infer_after_narration(&narrative, world).await?);
 
         Ok(DmResponse {
             narrative,
             intents: all_intents,
             effects: all_effects,
+            outcomes: all_outcomes,
             resolutions: all_resolutions,
             inferred_state_changes,
+            tool_round_limit_reached,
         })
     }
 
@@ -750,11 +1044,18 @@ impl DungeonMaster {
             pc.background.name(),
             pc.background.description()
         ));
-        prompt.push_str(&format!(
-            "**HP:** {}/{}\n",
-            pc.hit_points.current, pc.hit_points.maximum
-        ));
         prompt.push_str(&format!("**AC:** {}\n", pc.current_ac()));
+        prompt.push_str(&format!("**Speed:** {} ft\n", pc.effective_speed()));
+        if let Some(ref beast) = pc.class_resources.wild_shape_stats {
+            prompt.push_str(&format!(
+                "**Wild Shape:** {} (Beast HP: {}/{}, {} {})\n",
+                beast.name,
+                beast.hit_points.current,
+                beast.hit_points.maximum,
+                beast.attack_name,
+                beast.attack_damage
+            ));
+        }
 
         // Add backstory if present
         if let Some(ref backstory) = pc.backstory {
@@ -767,8 +1068,8 @@ impl DungeonMaster {
             prompt.push_str(&format!(
                 "- Ability: {} (DC {}, +{} to hit)\n",
                 spellcasting.ability.name(),
-                spellcasting.spell_save_dc(&pc.ability_scores, pc.proficiency_bonus()),
-                spellcasting.spell_attack_bonus(&pc.ability_scores, pc.proficiency_bonus())
+                pc.spell_save_dc().unwrap_or_default(),
+                pc.spell_attack_bonus().unwrap_or_default()
             ));
             if !spellcasting.cantrips_known.is_empty() {
                 prompt.push_str(&format!(
@@ -776,7 +1077,20 @@ impl DungeonMaster {
                     spellcasting.cantrips_known.join(", ")
                 ));
             }
-            if !spellcasting.spells_prepared.is_empty() {
+            if !spellcasting.spellbook.is_empty() {
+                prompt.push_str(&format!(
+                    "- Spellbook: {}\n",
+                    spellcasting.spellbook.join(", ")
+                ));
+            }
+            if let Some(limit) = pc.max_prepared_spells() {
+                prompt.push_str(&format!(
+                    "- Prepared Spells ({}/{}): {}\n",
+                    spellcasting.spells_prepared.len(),
+                    limit,
+                    spellcasting.spells_prepared.join(", ")
+                ));
+            } else if !spellcasting.spells_prepared.is_empty() {
                 prompt.push_str(&format!(
                     "- Prepared Spells: {}\n",
                     spellcasting.spells_prepared.join(", ")
@@ -787,6 +1101,18 @@ impl DungeonMaster {
                     spellcasting.spells_known.join(", ")
                 ));
             }
+            if pc.spell_swaps > 0 {
+                prompt.push_str(&format!(
+                    "- May replace {} known spell(s) from leveling up (learn_spell with replace)\n",
+                    pc.spell_swaps
+                ));
+            }
+            if pc.free_spellbook_spells > 0 {
+                prompt.push_str(&format!(
+                    "- May add {} spell(s) to the spellbook for free from leveling up (copy_spell_to_book with free)\n",
+                    pc.free_spellbook_spells
+                ));
+            }
             // Show available spell slots
             let slots: Vec<String> = spellcasting
                 .spell_slots
@@ -799,6 +1125,14 @@ impl DungeonMaster {
             if !slots.is_empty() {
                 prompt.push_str(&format!("- Spell Slots: {}\n", slots.join(", ")));
             }
+            if let Some(pact) = &spellcasting.pact_slots {
+                prompt.push_str(&format!(
+                    "- Pact Magic Slots (level {}, recharge on short rest): {}/{}\n",
+                    pact.level,
+                    pact.available(),
+                    pact.total
+                ));
+            }
         }
 
         // Add ability scores
@@ -831,6 +1165,14 @@ impl DungeonMaster {
                 ));
             }
 
+            // Widened critical range (Champion)
+            let crit_range = pc.crit_range();
+            if crit_range < 20 {
+                prompt.push_str(&format!(
+                    "**Critical Hits:** on a natural {crit_range}-20 (auto-applied by attack tool)\n"
+                ));
+            }
+
             // Sneak Attack (Rogues)
             if primary_class.class == crate::world::CharacterClass::Rogue {
                 let sneak_dice = primary_class.level.div_ceil(2);
@@ -843,17 +1185,34 @@ impl DungeonMaster {
 
         // Add current situation
         prompt.push_str("\n## Current Situation\n");
-        prompt.push_str(&format!("Location: {}\n", world.current_location.name));
-        prompt.push_str(&format!(
-            "Time: {} ({})\n",
-            world.game_time.time_of_day(),
-            if world.game_time.is_daytime() {
-                "day"
-            } else {
-                "night"
-            }
-        ));
-        prompt.push_str(&format!("Mode: {:?}\n", world.mode));
+        let scene = world.scene_summary();
+        prompt.push_str(&scene.to_string());
+        let seen = pc.perceived_light(scene.light);
+        if seen != scene.light {
+            prompt.push_str(&format!("{} sees it as {seen} with darkvision.\n", pc.name));
+        }
+        if scene.time_of_day.is_dark() {
+            prompt.push_str("It is night: most shops are closed.\n");
+        }
+        if seen != LightLevel::Bright {
+            prompt.push_str(
+                "Poor light: Perception checks have disadvantage, and in darkness attacks do too (applied automatically by skill_check and attack).\n",
+            );
+        }
+        if !world.factions.is_empty() {
+            let mut factions: Vec<_> = world.factions.iter().collect();
+            factions.sort_by(|a, b| a.0.cmp(b.0));
+            let standings: Vec<String> = factions
+                .into_iter()
+                .map(|(name, reputation)| {
+                    format!(
+                        "{name} {reputation:+} ({})",
+                        reputation_standing(*reputation)
+                    )
+                })
+                .collect();
+            prompt.push_str(&format!("Faction standing: {}\n", standings.join(", ")));
+        }
 
         // Combat info if in combat
         if world.mode == GameMode::Combat {
@@ -882,11 +1241,19 @@ impl DungeonMaster {
             }
         }
 
-        // Active conditions
-        if !pc.conditions.is_empty() {
-            prompt.push_str("\nActive conditions:\n");
-            for cond in &pc.conditions {
-                prompt.push_str(&format!("- {} (from {})\n", cond.condition, cond.source));
+        // Concentration saves owed for damage taken since the last check
+        if let Some(ref concentration) = pc.concentration {
+            if !concentration.pending_damage.is_empty() {
+                prompt.push('\n');
+            }
+            for damage in &concentration.pending_damage {
+                prompt.push_str(&format!(
+                    "{} is concentrating on {} and took {} damage \u{2014} CON save DC {} required (call concentration_check)\n",
+                    pc.name,
+                    concentration.spell_name,
+                    damage,
+                    Concentration::save_dc(*damage)
+                ));
             }
         }
 
@@ -907,6 +1274,81 @@ impl DungeonMaster {
         prompt
     }
 
+    /// Run `name` as a built-in informational tool, unless a custom tool
+    /// has replaced it.
+    fn info_tool_result(
+        &self,
+        name: &str,
+        input: &serde_json::Value,
+        world: &GameWorld,
+    ) -> Option<String> {
+        if self.tools.is_custom(name) {
+            return None;
+        }
+        execute_info_tool_with_memory(name, input, world, &self.story_memory)
+    }
+
+    /// The tool definitions sent with a request, filtered by game mode unless
+    /// mode-aware tools are disabled, followed by any custom tools.
+    fn tools_for(&self, world: &GameWorld) -> Vec<Tool> {
+        let mut tools = if self.config.mode_aware_tools {
+            DmTools::for_mode(world.mode)
+        } else {
+            DmTools::all()
+        };
+        let custom: Vec<Tool> = self.tools.custom_tools().cloned().collect();
+        tools.retain(|tool| !custom.iter().any(|c| c.name == tool.name));
+        tools.extend(custom);
+        tools
+    }
+
+    /// The request the next turn for `player_input` would send first, built
+    /// without sending it or changing any state.
+    ///
+    /// Consequences a relevance check would trigger are left out, since
+    /// finding them can take a model call.
+    pub fn build_turn_request(&self, player_input: &str, world: &GameWorld) -> Request {
+        let mut memory = self.memory.clone();
+        memory.add_player_message(player_input);
+        let messages = memory.get_recent_messages(self.history_window());
+        let system_prompt = self.build_system_prompt(world, player_input);
+        self.dm_request(messages, &system_prompt, world)
+    }
+
+    /// A request to the DM model with this turn's prompt and tools.
+    fn dm_request(
+        &self,
+        messages: Vec<Message>,
+        system_prompt: &str,
+        world: &GameWorld,
+    ) -> Request {
+        let mut request = Request::new(messages)
+            .with_system(system_prompt)
+            .with_max_tokens(self.config.max_tokens)
+            .with_tools(self.tools_for(world));
+
+        if let Some(ref model) = self.config.model {
+            request = request.with_model(model);
+        }
+
+        if let Some(temp) = self.config.temperature {
+            request = request.with_temperature(temp);
+        }
+        request
+    }
+
+    /// The conversation history sent at the start of each request, limited to
+    /// the configured history window.
+    fn initial_messages(&self) -> Vec<Message> {
+        self.memory.get_recent_messages(self.history_window())
+    }
+
+    /// The configured history window; never zero, since the API rejects a
+    /// request without messages.
+    fn history_window(&self) -> usize {
+        self.config.history_window.max(1)
+    }
+
     /// Add a campaign fact to memory.
     pub fn remember(&mut self, category: FactCategory, fact: impl Into<String>) {
         self.memory.add_fact(category, fact);
@@ -932,14 +1374,64 @@ impl DungeonMaster {
         }
     }
 
-    /// Infer state changes from narrative and apply them to the world.
+    /// Infer state changes from the previous turn's `pending` narrative,
+    /// when inference on it was deferred.
+    async fn infer_pending(
+        &self,
+        pending: Option<&str>,
+        world: &GameWorld,
+    ) -> Result<Vec<InferredStateChange>, DmError> {
+        match pending {
+            Some(narrative) => self.infer_state_changes(narrative, world).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Mark the consequences `relevance` triggered and return the system
+    /// prompt for the turn with them in it.
+    fn consequence_prompt(
+        &mut self,
+        relevance: &RelevanceResult,
+        player_input: &str,
+        world: &GameWorld,
+    ) -> String {
+        self.apply_relevance_results(relevance);
+
+        // Build system prompt with story context for this input
+        let mut system_prompt = self.build_system_prompt(world, player_input);
+
+        // Add triggered consequences to context
+        system_prompt.push_str(&self.build_triggered_consequences_context(relevance));
+        system_prompt
+    }
+
+    /// Infer state changes from this turn's narrative and apply them, or
+    /// queue the narrative for the next turn when inference is deferred.
+    async fn infer_after_narration(
+        &mut self,
+        narrative: &str,
+        world: &mut GameWorld,
+    ) -> Result<Vec<InferredStateChange>, DmError> {
+        if !self.config.enable_state_inference {
+            return Ok(Vec::new());
+        }
+        if self.config.deferred_inference {
+            self.pending_inference = Some(narrative.to_string());
+            return Ok(Vec::new());
+        }
+        let inferred = self.infer_state_changes(narrative, world).await?;
+        self.apply_inferred_state_changes(&inferred, world);
+        Ok(inferred)
+    }
+
+    /// Infer state changes from narrative.
     ///
     /// This uses a fast model (Haiku) to detect implied state changes
     /// that weren't explicitly recorded with tools.
-    async fn infer_and_apply_state_changes(
-        &mut self,
+    async fn infer_state_changes(
+        &self,
         narrative: &str,
-        world: &mut GameWorld,
+        world: &GameWorld,
     ) -> Result<Vec<InferredStateChange>, DmError> {
         // Collect known entity names from NPCs and story memory
         let mut known_entities: Vec<String> =
@@ -976,8 +1468,17 @@ impl DungeonMaster {
             .await
             .map_err(|e| DmError::ToolError(format!("State inference failed: {e}")))?;
 
+        Ok(inferred)
+    }
+
+    /// Apply inferred state changes to the world and record them as facts.
+    fn apply_inferred_state_changes(
+        &mut self,
+        inferred: &[InferredStateChange],
+        world: &mut GameWorld,
+    ) {
         // Apply inferred changes to the world
-        for change in &inferred {
+        for change in inferred {
             // Parse state type
             if let Some(state_type) = StateType::parse(&change.state_type) {
                 // Create and resolve the intent
@@ -990,7 +1491,11 @@ impl DungeonMaster {
                 };
 
                 let resolution = self.rules.resolve(world, intent);
-                apply_effects(world, &resolution.effects);
+                let outcomes = apply_effects(world, &resolution.effects);
+                // A change the world refused isn't a fact worth remembering
+                if !outcomes.is_empty() && outcomes.iter().all(EffectOutcome::is_skipped) {
+                    continue;
+                }
 
                 // Also record as a fact in story memory
                 self.store_fact(
@@ -1006,8 +1511,6 @@ impl DungeonMaster {
                 );
             }
         }
-
-        Ok(inferred)
     }
 
     /// Store a fact in story memory.
@@ -1099,7 +1602,8 @@ impl DungeonMaster {
     /// Check relevance of stored context against player input using a fast model.
     ///
     /// Returns triggered consequences and relevant entities that should be
-    /// included in the DM's context.
+    /// included in the DM's context. Falls back to local keyword matching if
+    /// the model is disabled or the call fails.
     pub async fn check_relevance(
         &self,
         player_input: &str,
@@ -1110,15 +1614,23 @@ impl DungeonMaster {
             return Ok(RelevanceResult::default());
         }
 
-        let checker = RelevanceChecker::new(self.client.clone());
-        let result = checker
-            .check_relevance(
+        let location = &world.current_location.name;
+        if !self.config.enable_relevance_check {
+            return Ok(RelevanceResult::from_lexical(
                 player_input,
-                &world.current_location.name,
+                location,
                 &self.story_memory,
-            )
+            ));
+        }
+
+        // Fall back to keyword matching rather than losing relevance entirely
+        let checker = RelevanceChecker::new(self.client.clone());
+        let result = checker
+            .check_relevance(player_input, location, &self.story_memory)
             .await
-            .map_err(|e| DmError::ToolError(format!("Relevance check failed: {e}")))?;
+            .unwrap_or_else(|_| {
+                RelevanceResult::from_lexical(player_input, location, &self.story_memory)
+            });
 
         Ok(result)
     }
@@ -1157,6 +1669,32 @@ impl DungeonMaster {
     }
 }
 
+/// The content of a reply cut off by the token limit, ready to send back as
+/// the start of the assistant's message. The API rejects a final assistant
+/// message that ends in whitespace, so the last text block is trimmed.
+fn continuation_prefill(mut content: Vec<ContentBlock>) -> Vec<ContentBlock> {
+    if let Some(ContentBlock::Text { text }) = content.last_mut() {
+        text.truncate(text.trim_end().len());
+        if text.is_empty() {
+            content.pop();
+        }
+    }
+    content
+}
+
+/// Sent when the DM's reply has neither narration nor tool calls.
+const EMPTY_REPLY_NUDGE: &str = "Please respond with narration or tool calls.";
+
+/// Ask again for a reply after an empty one, adding the nudge to the
+/// player's message (or tool results) the empty reply answered.
+fn nudge_after_empty_reply(messages: &mut [Message]) {
+    if let Some(last) = messages.last_mut() {
+        last.content.push(ContentBlock::Text {
+            text: EMPTY_REPLY_NUDGE.to_string(),
+        });
+    }
+}
+
 /// Helper for accumulating tool use data during streaming.
 struct PartialToolUse {
     /// Tool use ID from the API.
@@ -1193,6 +1731,8 @@ mod tests {
         assert_eq!(config.max_tokens, 4096);
         assert_eq!(config.temperature, Some(0.8));
         assert!(config.custom_system_prompt.is_none());
+        assert_eq!(config.max_tool_rounds, DEFAULT_MAX_TOOL_ROUNDS);
+        assert_eq!(config.max_continuations, DEFAULT_MAX_CONTINUATIONS);
     }
 
     #[test]
@@ -1201,8 +1741,10 @@ mod tests {
             narrative: "You enter the dark cave.".to_string(),
             intents: vec![],
             effects: vec![],
+            outcomes: vec![],
             resolutions: vec![],
             inferred_state_changes: vec![],
+            tool_round_limit_reached: false,
         };
         assert_eq!(response.narrative, "You enter the dark cave.");
         assert!(response.intents.is_empty());
@@ -1222,6 +1764,79 @@ mod tests {
         assert!(memory.get_messages().is_empty());
     }
 
+    #[test]
+    fn test_history_window_limits_messages_sent() {
+        let mut dm = DungeonMaster::new("test-key");
+        for i in 0..10 {
+            dm.memory_mut().add_player_message(&format!("Action {i}"));
+            dm.memory_mut().add_dm_message(&format!("Narration {i}"));
+        }
+        assert_eq!(dm.initial_messages().len(), 20);
+
+        let dm = dm.with_config(DmConfig {
+            history_window: 4,
+            ..Default::default()
+        });
+        let messages = dm.initial_messages();
+        assert_eq!(messages.len(), 4);
+        let first = messages[0].content.iter().find_map(|b| b.as_text());
+        assert_eq!(first, Some("Action 8"));
+    }
+
+    #[tokio::test]
+    async fn test_history_window_limits_messages_in_the_request() {
+        let transport = claude::MockTransport::new();
+        let mut dm =
+            DungeonMaster::with_client(Claude::new("test-key").with_transport(transport.clone()))
+                .with_config(DmConfig {
+                    enable_state_inference: false,
+                    history_window: 4,
+                    max_continuations: 0,
+                    ..Default::default()
+                });
+        for i in 0..10 {
+            dm.memory_mut().add_player_message(&format!("Action {i}"));
+            dm.memory_mut().add_dm_message(&format!("Narration {i}"));
+        }
+        let mut world = create_test_world();
+
+        transport.push_response(200, text_response("The road stretches on.", "end_turn"));
+        dm.process_input("Action 10", &mut world).await.unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        let messages = body["messages"].as_array().unwrap();
+        // The window's oldest message is the DM's, dropped so the request
+        // opens with the player
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[0]["content"][0]["text"], "Action 9");
+        let last = messages.last().unwrap();
+        assert_eq!(last["role"], "user");
+        assert_eq!(last["content"][0]["text"], "Action 10");
+    }
+
+    #[test]
+    fn test_tools_sent_differ_between_exploration_and_combat() {
+        let dm = DungeonMaster::new("test-key");
+        let mut world = GameWorld::new("Test", Character::new("Test Hero"));
+        let names =
+            |tools: Vec<Tool>| -> Vec<String> { tools.into_iter().map(|t| t.name).collect() };
+
+        let exploration = names(dm.tools_for(&world));
+        world.mode = GameMode::Combat;
+        let combat = names(dm.tools_for(&world));
+        assert_ne!(exploration, combat);
+        assert!(combat.contains(&"attack".to_string()));
+        assert!(!exploration.contains(&"attack".to_string()));
+
+        let dm = dm.with_config(DmConfig {
+            mode_aware_tools: false,
+            ..Default::default()
+        });
+        assert_eq!(names(dm.tools_for(&world)).len(), DmTools::all().len());
+    }
+
     #[test]
     fn test_with_config() {
         let config = DmConfig {
@@ -1247,6 +1862,44 @@ mod tests {
         assert!(prompt.contains("Test Location"));
     }
 
+    #[test]
+    fn test_build_system_prompt_reminds_concentration_save_after_damage() {
+        let dm = DungeonMaster::new("test-key");
+        let mut world = create_test_world();
+        world.player_character = crate::world::create_sample_cleric("Roland");
+        let engine = RulesEngine::new();
+
+        let cast = engine.resolve(
+            &world,
+            Intent::CastSpell {
+                caster_id: world.player_character.id,
+                spell_name: "Bless".to_string(),
+                targets: Vec::new(),
+                spell_level: 1,
+                target_names: Vec::new(),
+            },
+        );
+        apply_effects(&mut world, &cast.effects);
+        let prompt = dm.build_system_prompt(&world, "I hold the line");
+        assert!(prompt.contains("concentrating on Bless"));
+        assert!(!prompt.contains("CON save DC"));
+
+        let hit = engine.resolve(
+            &world,
+            Intent::Damage {
+                target_id: world.player_character.id,
+                amount: 12,
+                damage_type: crate::rules::DamageType::Slashing,
+                source: "Orc".to_string(),
+            },
+        );
+        apply_effects(&mut world, &hit.effects);
+        let prompt = dm.build_system_prompt(&world, "I hold the line");
+        assert!(prompt.contains(
+            "Roland is concentrating on Bless and took 12 damage \u{2014} CON save DC 10 required"
+        ));
+    }
+
     #[test]
     fn test_relevance_result_triggers() {
         let result = RelevanceResult {
@@ -1283,6 +1936,362 @@ mod tests {
         assert!(context.is_empty());
     }
 
+    fn text_response(text: &str, stop_reason: &str) -> String {
+        serde_json::json!({
+            "id": "msg",
+            "model": "m",
+            "content": [{"type": "text", "text": text}],
+            "stop_reason": stop_reason,
+            "usage": {"input_tokens": 1, "output_tokens": 1}
+        })
+        .to_string()
+    }
+
+    fn dm_with_transport(
+        transport: &claude::MockTransport,
+        max_continuations: usize,
+    ) -> DungeonMaster {
+        DungeonMaster::with_client(Claude::new("test-key").with_transport(transport.clone()))
+            .with_config(DmConfig {
+                enable_state_inference: false,
+                max_continuations,
+                ..Default::default()
+            })
+    }
+
+    #[tokio::test]
+    async fn test_custom_tool_replaces_builtin_info_tool() {
+        let transport = claude::MockTransport::new();
+        transport.push_response(
+            200,
+            serde_json::json!({
+                "id": "msg",
+                "model": "m",
+                "content": [{"type": "tool_use", "id": "t1", "name": "show_inventory", "input": {}}],
+                "stop_reason": "tool_use",
+                "usage": {"input_tokens": 1, "output_tokens": 1}
+            })
+            .to_string(),
+        );
+        transport.push_response(200, text_response("Your pack rattles.", "end_turn"));
+        let mut tools = ToolRegistry::new();
+        tools.register(
+            Tool {
+                name: "show_inventory".to_string(),
+                description: "Rummage through the pack.".to_string(),
+                input_schema: serde_json::json!({"type": "object", "properties": {}}),
+            },
+            |_input, _world| {
+                Some(Intent::RollDice {
+                    notation: "1d6".to_string(),
+                    purpose: "rummaging".to_string(),
+                })
+            },
+        );
+        let mut dm = dm_with_transport(&transport, 0).with_tool_registry(tools);
+        let mut world = create_test_world();
+
+        let response = dm
+            .process_input("I check my pack", &mut world)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            response.intents.as_slice(),
+            [Intent::RollDice { purpose, .. }] if purpose == "rummaging"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reply_cut_off_by_max_tokens_is_continued() {
+        let transport = claude::MockTransport::new();
+        transport.push_response(
+            200,
+            text_response("The dragon rears back and ", "max_tokens"),
+        );
+        transport.push_response(
+            200,
+            text_response(" exhales a torrent of flame.", "end_turn"),
+        );
+        let mut dm = dm_with_transport(&transport, DEFAULT_MAX_CONTINUATIONS);
+        let mut world = create_test_world();
+
+        let response = dm
+            .process_input("I draw my sword", &mut world)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.narrative,
+            "The dragon rears back and exhales a torrent of flame."
+        );
+        // The continuation resends the partial reply, trimmed, as the
+        // assistant's message to pick up from
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        let body: serde_json::Value = serde_json::from_str(&requests[1].body).unwrap();
+        let last = body["messages"].as_array().unwrap().last().unwrap().clone();
+        assert_eq!(last["role"], "assistant");
+        assert_eq!(last["content"][0]["text"], "The dragon rears back and");
+    }
+
+    #[tokio::test]
+    async fn test_max_tokens_without_continuations_keeps_partial_reply() {
+        let transport = claude::MockTransport::new();
+        transport.push_response(
+            200,
+            text_response("The dragon rears back and", "max_tokens"),
+        );
+        let mut dm = dm_with_transport(&transport, 0);
+        let mut world = create_test_world();
+
+        let response = dm
+            .process_input("I draw my sword", &mut world)
+            .await
+            .unwrap();
+
+        assert_eq!(response.narrative, "The dragon rears back and");
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_empty_reply_is_retried_with_a_nudge() {
+        let transport = claude::MockTransport::new();
+        transport.push_response(200, text_response("  \n", "end_turn"));
+        transport.push_response(
+            200,
+            text_response("The innkeeper looks up from the bar.", "end_turn"),
+        );
+        let mut dm = dm_with_transport(&transport, DEFAULT_MAX_CONTINUATIONS);
+        let mut world = create_test_world();
+
+        let response = dm
+            .process_input("I walk into the inn", &mut world)
+            .await
+            .unwrap();
+
+        assert_eq!(response.narrative, "The innkeeper looks up from the bar.");
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        let body: serde_json::Value = serde_json::from_str(&requests[1].body).unwrap();
+        let last = body["messages"].as_array().unwrap().last().unwrap().clone();
+        assert_eq!(last["role"], "user");
+        assert_eq!(
+            last["content"].as_array().unwrap().last().unwrap()["text"],
+            EMPTY_REPLY_NUDGE
+        );
+    }
+
+    fn attack_response(target: &str) -> String {
+        serde_json::json!({
+            "id": "msg",
+            "model": "m",
+            "content": [{
+                "type": "tool_use",
+                "id": format!("attack-{target}"),
+                "name": "attack",
+                "input": {"weapon": "Longsword", "target": target}
+            }],
+            "stop_reason": "tool_use",
+            "usage": {"input_tokens": 1, "output_tokens": 1}
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_invalid_attack_target_is_corrected_within_the_turn() {
+        let transport = claude::MockTransport::new();
+        transport.push_response(200, attack_response("Orc"));
+        transport.push_response(200, attack_response("goblin"));
+        transport.push_response(200, text_response("Steel rings out.", "end_turn"));
+        let mut dm = dm_with_transport(&transport, DEFAULT_MAX_CONTINUATIONS);
+        let mut world = create_test_world();
+        world.player_character = crate::world::create_sample_fighter("Roland");
+        let start = crate::dm::parse_tool_call(
+            "start_combat",
+            &serde_json::json!({"enemies": [{"name": "Goblin", "max_hp": 7}]}),
+            &world,
+        )
+        .unwrap();
+        let resolution = RulesEngine::new().resolve(&world, start);
+        apply_effects(&mut world, &resolution.effects);
+
+        let response = dm
+            .process_input("I swing at the orc", &mut world)
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        let body: serde_json::Value = serde_json::from_str(&requests[1].body).unwrap();
+        let result = body["messages"].as_array().unwrap().last().unwrap()["content"][0].clone();
+        assert_eq!(result["type"], "tool_result");
+        assert_eq!(result["is_error"], true);
+        let content = result["content"][0]["text"].as_str().unwrap();
+        assert!(content.contains("`Orc` is not in this combat"));
+        assert!(content.contains("Current combatants: "));
+        assert!(content.contains("Roland") && content.contains("Goblin"));
+
+        assert_eq!(response.intents.len(), 1);
+        assert!(matches!(response.intents[0], Intent::Attack { .. }));
+    }
+
+    fn speculative_dm(transport: &claude::MockTransport) -> DungeonMaster {
+        let mut dm =
+            DungeonMaster::with_client(Claude::new("test-key").with_transport(transport.clone()))
+                .with_config(DmConfig {
+                    enable_state_inference: false,
+                    speculative_dm_request: true,
+                    max_continuations: 0,
+                    ..Default::default()
+                });
+        dm.story_memory_mut().create_consequence(
+            "The player returns to the mill",
+            "The miller demands payment",
+            ConsequenceSeverity::Minor,
+        );
+        dm
+    }
+
+    #[tokio::test]
+    async fn test_speculative_dm_request_starts_alongside_the_relevance_check() {
+        let transport = claude::MockTransport::new();
+        let mut dm = speculative_dm(&transport);
+        let mut world = create_test_world();
+
+        let relevance = serde_json::json!({
+            "triggered_consequences": [],
+            "relevant_entities": [],
+            "explanation": "Nothing matches"
+        });
+        transport.push_response(200, text_response(&relevance.to_string(), "end_turn"));
+        transport.push_response(200, text_response("The road stretches on.", "end_turn"));
+        let response = dm
+            .process_input("I keep walking", &mut world)
+            .await
+            .unwrap();
+
+        // Nothing triggered, so the reply sent alongside the check is kept
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].body.contains("Pending Consequences"));
+        assert!(requests[1].body.contains("\"tools\""));
+        assert_eq!(response.narrative, "The road stretches on.");
+    }
+
+    #[tokio::test]
+    async fn test_speculative_dm_request_is_sent_again_when_a_consequence_triggers() {
+        let transport = claude::MockTransport::new();
+        let mut dm = speculative_dm(&transport);
+        let mut world = create_test_world();
+
+        let relevance = serde_json::json!({
+            "triggered_consequences": ["C1"],
+            "relevant_entities": [],
+            "explanation": "The player is at the mill"
+        });
+        transport.push_response(200, text_response(&relevance.to_string(), "end_turn"));
+        transport.push_response(200, text_response("The mill is quiet.", "end_turn"));
+        transport.push_response(
+            200,
+            text_response("The miller blocks the door.", "end_turn"),
+        );
+        let response = dm
+            .process_input("I head back to the mill", &mut world)
+            .await
+            .unwrap();
+
+        // The first reply predates the consequence and is discarded
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(!requests[1].body.contains("TRIGGERED CONSEQUENCES"));
+        assert!(requests[2].body.contains("TRIGGERED CONSEQUENCES"));
+        assert_eq!(response.narrative, "The miller blocks the door.");
+    }
+
+    #[tokio::test]
+    async fn test_dm_request_waits_for_the_relevance_check_by_default() {
+        let transport = claude::MockTransport::new();
+        let mut dm = dm_with_transport(&transport, 0);
+        dm.story_memory_mut().create_consequence(
+            "The player returns to the mill",
+            "The miller demands payment",
+            ConsequenceSeverity::Minor,
+        );
+        let mut world = create_test_world();
+
+        let relevance = serde_json::json!({
+            "triggered_consequences": ["C1"],
+            "relevant_entities": [],
+            "explanation": "The player is at the mill"
+        });
+        transport.push_response(200, text_response(&relevance.to_string(), "end_turn"));
+        transport.push_response(
+            200,
+            text_response("The miller blocks the door.", "end_turn"),
+        );
+        dm.process_input("I head back to the mill", &mut world)
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].body.contains("TRIGGERED CONSEQUENCES"));
+    }
+
+    #[tokio::test]
+    async fn test_deferred_inference_is_applied_on_the_next_turn() {
+        let transport = claude::MockTransport::new();
+        let mut dm =
+            DungeonMaster::with_client(Claude::new("test-key").with_transport(transport.clone()))
+                .with_config(DmConfig {
+                    deferred_inference: true,
+                    max_continuations: 0,
+                    ..Default::default()
+                });
+        let mut world = create_test_world();
+        let mira = crate::world::NPC::new("Mira");
+        let mira_id = mira.id;
+        world.npcs.insert(mira_id, mira);
+
+        // The first turn returns without waiting on inference
+        transport.push_response(
+            200,
+            text_response(
+                "Mira smiles warmly and thanks you for the help.",
+                "end_turn",
+            ),
+        );
+        let first = dm.process_input("I help Mira", &mut world).await.unwrap();
+        assert!(first.inferred_state_changes.is_empty());
+        assert_eq!(transport.requests().len(), 1);
+
+        // The next turn infers from the first turn's narrative while the DM replies
+        let inference = serde_json::json!({"inferred_changes": [{
+            "entity_name": "Mira",
+            "state_type": "disposition",
+            "new_value": "friendly",
+            "evidence": "Mira smiles warmly",
+            "confidence": 0.95,
+            "target_entity": null
+        }]});
+        transport.push_response(200, text_response(&inference.to_string(), "end_turn"));
+        transport.push_response(200, text_response("Mira waves you off.", "end_turn"));
+        let second = dm.process_input("I say goodbye", &mut world).await.unwrap();
+
+        // One DM call, its reply kept though it was written before the changes
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1].body.contains("implied state changes"));
+        assert!(requests[2].body.contains("\"tools\""));
+        assert_eq!(second.narrative, "Mira waves you off.");
+        assert_eq!(second.inferred_state_changes.len(), 1);
+        assert!(matches!(
+            world.npcs[&mira_id].disposition,
+            crate::world::Disposition::Friendly
+        ));
+    }
+
     #[test]
     fn test_partial_tool_use_struct() {
         let partial = PartialToolUse {
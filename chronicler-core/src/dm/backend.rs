@@ -0,0 +1,163 @@
+//! The interface a [`GameSession`](crate::GameSession) uses to run the DM.
+//!
+//! [`DungeonMaster`] implements [`DmBackend`] over Claude and
+//! [`MockDm`](crate::MockDm) over a script; a local model or a deterministic
+//! planner can implement it too without forking the session.
+
+use claude::Request;
+use futures::future::BoxFuture;
+
+use super::{DmError, DmMemory, DmResponse, DungeonMaster, StoryMemory, ToolMetrics};
+use crate::rules::{Effect, RulesEngine};
+use crate::world::GameWorld;
+
+/// Everything a backend needs for one player turn.
+pub struct TurnContext<'a> {
+    /// What the player typed.
+    pub input: &'a str,
+    /// The world the turn's effects are applied to.
+    pub world: &'a mut GameWorld,
+    /// Receives narrative text as it is produced; `None` when the caller
+    /// only wants the finished response.
+    pub on_text: Option<&'a mut (dyn FnMut(&str) + Send + 'a)>,
+    /// Receives each effect as soon as it is applied.
+    pub on_effect: Option<&'a mut (dyn FnMut(&Effect) + Send + 'a)>,
+}
+
+impl<'a> TurnContext<'a> {
+    /// A turn whose response is only wanted once it is complete.
+    pub fn new(input: &'a str, world: &'a mut GameWorld) -> Self {
+        Self {
+            input,
+            world,
+            on_text: None,
+            on_effect: None,
+        }
+    }
+
+    /// Whether the caller wants text and effects as they happen.
+    pub fn is_streaming(&self) -> bool {
+        self.on_text.is_some() || self.on_effect.is_some()
+    }
+}
+
+/// A Dungeon Master a session can run turns against.
+pub trait DmBackend: Send {
+    /// Run one turn: narrate, resolve the mechanics, and apply the effects
+    /// to `turn.world`.
+    fn respond<'a>(
+        &'a mut self,
+        turn: TurnContext<'a>,
+    ) -> BoxFuture<'a, Result<DmResponse, DmError>>;
+
+    /// Conversation memory (campaign facts and summary), saved with the session.
+    fn memory(&self) -> &DmMemory;
+
+    fn memory_mut(&mut self) -> &mut DmMemory;
+
+    /// Story memory (entities, facts, and consequences), saved with the session.
+    fn story_memory(&self) -> &StoryMemory;
+
+    fn story_memory_mut(&mut self) -> &mut StoryMemory;
+
+    /// Counts of the tools called so far.
+    fn tool_metrics(&self) -> &ToolMetrics;
+
+    /// The rules engine turns are resolved with, whose dice are saved with
+    /// the session. `None` for backends that don't roll dice.
+    fn rules(&self) -> Option<&RulesEngine> {
+        None
+    }
+
+    fn rules_mut(&mut self) -> Option<&mut RulesEngine> {
+        None
+    }
+
+    /// Narrative whose state inference was deferred to the next turn, saved
+    /// with the session so a reload still infers from it.
+    fn pending_inference(&self) -> Option<&str> {
+        None
+    }
+
+    fn set_pending_inference(&mut self, _narrative: Option<String>) {}
+
+    /// The first request a turn for `input` would send, for inspecting the
+    /// prompt. `None` for backends that don't send Claude requests.
+    fn build_turn_request(&self, _input: &str, _world: &GameWorld) -> Option<Request> {
+        None
+    }
+}
+
+impl DmBackend for DungeonMaster {
+    fn respond<'a>(
+        &'a mut self,
+        turn: TurnContext<'a>,
+    ) -> BoxFuture<'a, Result<DmResponse, DmError>> {
+        Box::pin(async move {
+            if !turn.is_streaming() {
+                return self.process_input(turn.input, turn.world).await;
+            }
+            let TurnContext {
+                input,
+                world,
+                mut on_text,
+                mut on_effect,
+            } = turn;
+            self.process_input_streaming_with_effects(
+                input,
+                world,
+                |text| {
+                    if let Some(on_text) = on_text.as_mut() {
+                        on_text(text);
+                    }
+                },
+                |effect| {
+                    if let Some(on_effect) = on_effect.as_mut() {
+                        on_effect(effect);
+                    }
+                },
+            )
+            .await
+        })
+    }
+
+    fn memory(&self) -> &DmMemory {
+        DungeonMaster::memory(self)
+    }
+
+    fn memory_mut(&mut self) -> &mut DmMemory {
+        DungeonMaster::memory_mut(self)
+    }
+
+    fn story_memory(&self) -> &StoryMemory {
+        DungeonMaster::story_memory(self)
+    }
+
+    fn story_memory_mut(&mut self) -> &mut StoryMemory {
+        DungeonMaster::story_memory_mut(self)
+    }
+
+    fn tool_metrics(&self) -> &ToolMetrics {
+        DungeonMaster::tool_metrics(self)
+    }
+
+    fn rules(&self) -> Option<&RulesEngine> {
+        Some(DungeonMaster::rules(self))
+    }
+
+    fn rules_mut(&mut self) -> Option<&mut RulesEngine> {
+        Some(DungeonMaster::rules_mut(self))
+    }
+
+    fn pending_inference(&self) -> Option<&str> {
+        DungeonMaster::pending_inference(self)
+    }
+
+    fn set_pending_inference(&mut self, narrative: Option<String>) {
+        DungeonMaster::set_pending_inference(self, narrative);
+    }
+
+    fn build_turn_request(&self, input: &str, world: &GameWorld) -> Option<Request> {
+        Some(DungeonMaster::build_turn_request(self, input, world))
+    }
+}
@@ -11,6 +11,9 @@ use serde::{Deserialize, Serialize};
 /// Increased from 20 to 30 for better narrative continuity in longer sessions.
 const MAX_RECENT_MESSAGES: usize = 30;
 
+/// Default number of recent messages sent to the DM with each request.
+pub const DEFAULT_HISTORY_WINDOW: usize = MAX_RECENT_MESSAGES;
+
 /// DM Memory manages context for the AI Dungeon Master.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DmMemory {
@@ -83,6 +86,22 @@ impl DmMemory {
             .collect()
     }
 
+    /// Get at most `window` of the most recent messages for an API call.
+    ///
+    /// The window never starts on a DM message, since the conversation sent
+    /// to the API must open with the player.
+    pub fn get_recent_messages(&self, window: usize) -> Vec<Message> {
+        let start = self.recent_messages.len().saturating_sub(window);
+        self.recent_messages[start..]
+            .iter()
+            .skip_while(|m| matches!(m.role, MessageRole::Assistant))
+            .map(|m| match m.role {
+                MessageRole::User => Message::user(&m.content),
+                MessageRole::Assistant => Message::assistant(&m.content),
+            })
+            .collect()
+    }
+
     /// Build context string with campaign facts.
     pub fn build_context(&self) -> String {
         let mut context = String::new();
@@ -324,6 +343,24 @@ mod tests {
         assert_eq!(messages.len(), 2);
     }
 
+    #[test]
+    fn test_get_recent_messages_window() {
+        let mut memory = DmMemory::new();
+        for i in 0..5 {
+            memory.add_player_message(&format!("Action {i}"));
+            memory.add_dm_message(&format!("Narration {i}"));
+        }
+
+        assert_eq!(memory.get_recent_messages(DEFAULT_HISTORY_WINDOW).len(), 10);
+        assert_eq!(memory.get_recent_messages(4).len(), 4);
+
+        // An odd window would start on a DM message, so that one is dropped
+        let messages = memory.get_recent_messages(3);
+        assert_eq!(messages.len(), 2);
+        let first = messages[0].content.iter().find_map(|b| b.as_text());
+        assert_eq!(first, Some("Action 4"));
+    }
+
     #[test]
     fn test_build_context_empty() {
         let memory = DmMemory::new();
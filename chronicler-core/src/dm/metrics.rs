@@ -0,0 +1,56 @@
+//! Tool usage counters.
+//!
+//! Records which tools the DM calls and how often those calls fail to parse
+//! or resolve, to spot tools the model ignores or keeps misusing.
+
+use std::collections::BTreeMap;
+
+/// Call counts for a single tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolStats {
+    pub calls: u32,
+    pub failures: u32,
+}
+
+impl ToolStats {
+    pub fn successes(&self) -> u32 {
+        self.calls - self.failures
+    }
+}
+
+/// Per-tool call counts, kept by name so reports list tools alphabetically.
+#[derive(Debug, Clone, Default)]
+pub struct ToolMetrics {
+    tools: BTreeMap<String, ToolStats>,
+}
+
+impl ToolMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one call to `tool`.
+    pub fn record(&mut self, tool: &str, success: bool) {
+        let stats = self.tools.entry(tool.to_string()).or_default();
+        stats.calls += 1;
+        if !success {
+            stats.failures += 1;
+        }
+    }
+
+    /// Counts for `tool` (all zero if it was never called).
+    pub fn get(&self, tool: &str) -> ToolStats {
+        self.tools.get(tool).copied().unwrap_or_default()
+    }
+
+    /// Every tool called so far with its counts.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ToolStats)> {
+        self.tools
+            .iter()
+            .map(|(name, stats)| (name.as_str(), *stats))
+    }
+
+    pub fn total_calls(&self) -> u32 {
+        self.tools.values().map(|stats| stats.calls).sum()
+    }
+}
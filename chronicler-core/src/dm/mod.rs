@@ -4,19 +4,28 @@
 //! running AI-powered D&D sessions.
 
 mod agent;
+mod backend;
 pub mod memory;
+mod metrics;
 pub mod relevance;
 pub mod story_memory;
 mod tools;
 
-pub use agent::{DmConfig, DmError, DmResponse, DungeonMaster};
+pub use agent::{
+    DmConfig, DmError, DmResponse, DungeonMaster, DEFAULT_MAX_CONTINUATIONS,
+    DEFAULT_MAX_TOOL_ROUNDS,
+};
+pub use backend::{DmBackend, TurnContext};
 pub use memory::{CampaignFact, DmMemory, FactCategory};
+pub use metrics::{ToolMetrics, ToolStats};
 pub use relevance::{
     InferredStateChange, RelevanceChecker, RelevanceError, RelevanceResult, StateInferrer,
+    HELPER_TEMPERATURE,
 };
 pub use story_memory::{
     Consequence, ConsequenceId, ConsequenceSeverity, ConsequenceStatus, Entity, EntityId,
-    EntityType, FactCategory as StoryFactCategory, FactSource, Relationship, RelationshipType,
-    StoryFact, StoryMemory, StoryMoment,
+    EntityType, FactCategory as StoryFactCategory, FactSource, PruneConfig, Relationship,
+    RelationshipType, StoryFact, StoryMemory, StoryMoment,
 };
-pub use tools::DmTools;
+pub(crate) use tools::{invalid_tool_call_message, parse_tool_call};
+pub use tools::{DmTools, ToolParser, ToolRegistry};
@@ -4,7 +4,7 @@
 //! and facts are relevant to the current player input, enabling semantic
 //! matching instead of just keyword matching.
 
-use super::story_memory::{ConsequenceId, EntityId, FactId, StoryMemory};
+use super::story_memory::{ConsequenceId, EntityId, FactId, IndexedConsequences, StoryMemory};
 use claude::{Claude, Message, Request};
 use serde::Deserialize;
 use thiserror::Error;
@@ -15,6 +15,10 @@ const RELEVANCE_MODEL: &str = "claude-3-5-haiku-20241022";
 /// Maximum tokens for relevance check response.
 const RELEVANCE_MAX_TOKENS: usize = 500;
 
+/// Temperature for relevance checks and state inference, kept at zero so
+/// mechanical reasoning stays deterministic however creative the DM is.
+pub const HELPER_TEMPERATURE: f32 = 0.0;
+
 /// Errors from relevance checking.
 #[derive(Debug, Error)]
 pub enum RelevanceError {
@@ -42,6 +46,27 @@ pub struct RelevanceResult {
 }
 
 impl RelevanceResult {
+    /// Build a result from local keyword matching alone, triggering only the
+    /// consequences with strong overlap. Used when the relevance model is
+    /// disabled or unavailable.
+    pub fn from_lexical(
+        player_input: &str,
+        current_location: &str,
+        story_memory: &StoryMemory,
+    ) -> Self {
+        Self {
+            triggered_consequences: story_memory
+                .lexical_relevance(player_input, current_location)
+                .into_iter()
+                .filter(|m| m.is_likely_trigger())
+                .map(|m| m.consequence_id)
+                .collect(),
+            relevant_entities: story_memory.extract_mentioned_entities(player_input),
+            explanation: Some("Keyword match (relevance model unavailable)".to_string()),
+            ..Default::default()
+        }
+    }
+
     /// Check if any consequences were triggered.
     pub fn has_triggered_consequences(&self) -> bool {
         !self.triggered_consequences.is_empty()
@@ -109,16 +134,15 @@ impl RelevanceChecker {
         current_location: &str,
         story_memory: &StoryMemory,
     ) -> Result<RelevanceResult, RelevanceError> {
-        // Get pending consequences
-        let consequences = story_memory.pending_consequences_by_importance();
+        // Build the labelled consequences list, pre-filtered by keyword overlap
+        let consequences =
+            story_memory.build_consequences_for_input(player_input, current_location);
 
         // If no consequences to check, return early
         if consequences.is_empty() {
             return Ok(RelevanceResult::default());
         }
-
-        // Build the consequences list for the prompt
-        let consequences_text = story_memory.build_consequences_for_relevance();
+        let consequences_text = &consequences.text;
 
         // Build the prompt
         let prompt = format!(
@@ -140,9 +164,11 @@ Analyze the player's action and determine:
 
 A consequence should trigger if the player's action matches or is closely related to its trigger condition. Be generous with semantic matching - "I enter the village" should trigger a consequence about "entering Riverside" if Riverside is a village.
 
+Refer to consequences by their label (C1, C2, ...).
+
 Respond with ONLY a JSON object (no markdown, no explanation outside the JSON):
 {{
-  "triggered_consequences": ["id1", "id2"],
+  "triggered_consequences": ["C1", "C3"],
   "relevant_entities": ["Baron Aldric", "Town Guards"],
   "explanation": "Brief explanation of matches"
 }}
@@ -154,19 +180,19 @@ If nothing is relevant, return empty arrays."#
         let request = Request::new(vec![Message::user(&prompt)])
             .with_model(&self.model)
             .with_max_tokens(RELEVANCE_MAX_TOKENS)
-            .with_temperature(0.0); // Deterministic for relevance checking
+            .with_temperature(HELPER_TEMPERATURE);
 
         let response = self.client.complete(request).await?;
         let response_text = response.text();
 
         // Parse the response
-        self.parse_response(&response_text, story_memory)
+        Self::parse_response(&response_text, &consequences, story_memory)
     }
 
     /// Parse the Haiku response into a RelevanceResult.
     fn parse_response(
-        &self,
         response: &str,
+        consequences: &IndexedConsequences,
         story_memory: &StoryMemory,
     ) -> Result<RelevanceResult, RelevanceError> {
         // Try to extract JSON from the response (handle potential markdown wrapping)
@@ -182,13 +208,11 @@ If nothing is relevant, return empty arrays."#
             ..Default::default()
         };
 
-        // Parse consequence IDs
-        for id_str in parsed.triggered_consequences {
-            // Try to find the consequence by ID string
-            for consequence in story_memory.pending_consequences() {
-                if consequence.id.to_string() == id_str {
-                    result.triggered_consequences.push(consequence.id);
-                    break;
+        // Map consequence labels back to IDs, skipping any the model invented
+        for label in parsed.triggered_consequences {
+            if let Some(id) = consequences.resolve(&label) {
+                if !result.triggered_consequences.contains(&id) {
+                    result.triggered_consequences.push(id);
                 }
             }
         }
@@ -332,7 +356,7 @@ If no state changes are implied, return: {{"inferred_changes": []}}"#
         let request = Request::new(vec![Message::user(&prompt)])
             .with_model(&self.model)
             .with_max_tokens(500)
-            .with_temperature(0.0);
+            .with_temperature(HELPER_TEMPERATURE);
 
         let response = self.client.complete(request).await?;
         let response_text = response.text();
@@ -701,4 +725,30 @@ Explanation: ..."#;
         let parsed: StateInferenceResponse = serde_json::from_str(&sanitized).unwrap();
         assert_eq!(parsed.inferred_changes[0].evidence, "single string");
     }
+
+    #[test]
+    fn test_parse_response_maps_labels_to_ids() {
+        use crate::dm::story_memory::ConsequenceSeverity;
+
+        let mut story_memory = StoryMemory::new();
+        let first = story_memory.create_consequence(
+            "Player enters Riverside",
+            "Guards attack",
+            ConsequenceSeverity::Major,
+        );
+        let second = story_memory.create_consequence(
+            "Player insults the baron",
+            "Bounty posted",
+            ConsequenceSeverity::Minor,
+        );
+        let consequences = story_memory.build_consequences_for_relevance();
+
+        let response =
+            r#"{"triggered_consequences": ["C2", "C9", "C1", "C2"], "relevant_entities": []}"#;
+        let result =
+            RelevanceChecker::parse_response(response, &consequences, &story_memory).unwrap();
+
+        // C9 doesn't exist and is ignored; duplicates collapse
+        assert_eq!(result.triggered_consequences, vec![second, first]);
+    }
 }
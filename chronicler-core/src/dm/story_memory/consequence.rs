@@ -20,7 +20,7 @@ pub struct ConsequenceId(Uuid);
 impl ConsequenceId {
     /// Create a new unique consequence ID.
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::world::new_uuid())
     }
 }
 
@@ -10,7 +10,7 @@ pub struct EntityId(Uuid);
 impl EntityId {
     /// Create a new unique entity ID.
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::world::new_uuid())
     }
 }
 
@@ -11,7 +11,7 @@ pub struct FactId(Uuid);
 impl FactId {
     /// Create a new unique fact ID.
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::world::new_uuid())
     }
 }
 
@@ -112,10 +112,19 @@ pub struct StoryFact {
     pub is_current: bool,
     /// Importance score (0.0 to 1.0).
     pub importance: f32,
+    /// Importance the fact was recorded with, before any decay.
+    #[serde(default = "default_recorded_importance")]
+    pub recorded_importance: f32,
     /// Where this fact came from.
     pub source: FactSource,
 }
 
+/// Facts saved before the recorded importance was tracked are treated as
+/// important, so they are never archived on load.
+fn default_recorded_importance() -> f32 {
+    1.0
+}
+
 impl StoryFact {
     /// Create a new story fact.
     pub fn new(
@@ -134,6 +143,7 @@ impl StoryFact {
             established: StoryMoment::new(current_turn),
             is_current: true,
             importance: 1.0,
+            recorded_importance: 1.0,
             source,
         }
     }
@@ -149,6 +159,7 @@ impl StoryFact {
     /// Set the importance level.
     pub fn with_importance(mut self, importance: f32) -> Self {
         self.importance = importance.clamp(0.0, 1.0);
+        self.recorded_importance = self.importance;
         self
     }
 
@@ -15,7 +15,7 @@ pub struct KnowledgeId(Uuid);
 impl KnowledgeId {
     /// Create a new unique knowledge ID.
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::world::new_uuid())
     }
 }
 
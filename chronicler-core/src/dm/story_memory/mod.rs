@@ -45,4 +45,4 @@ pub use relationship::{Relationship, RelationshipType};
 pub use scheduled_event::{
     EventStatus, EventTrigger, EventVisibility, ScheduledEvent, ScheduledEventId,
 };
-pub use store::StoryMemory;
+pub use store::{IndexedConsequences, LexicalMatch, PruneConfig, StoryMemory};
@@ -21,12 +21,46 @@ const MAX_CONTEXT_FACTS: usize = 30;
 /// Maximum consequences to include in relevance checking.
 const MAX_CONTEXT_CONSEQUENCES: usize = 20;
 
+/// Maximum consequences sent to the relevance model after lexical pre-filtering.
+const MAX_RELEVANCE_CANDIDATES: usize = 10;
+
+/// Lexical score at or above which a consequence is considered likely triggered.
+const LEXICAL_TRIGGER_THRESHOLD: f32 = 0.6;
+
+/// Words too common to count as keyword overlap.
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "into", "onto", "from", "that", "this", "they", "their", "them",
+    "then", "when", "what", "where", "there", "here", "have", "has", "had", "was", "were", "are",
+    "will", "would", "should", "could", "can", "not", "any", "all", "some", "his", "her", "its",
+    "you", "your", "our", "out", "about", "again", "player", "party", "someone",
+];
+
 /// Importance decay rate per turn.
 const IMPORTANCE_DECAY_PER_TURN: f32 = 0.02;
 
 /// Consequence decay rate per turn (slower than facts).
 const CONSEQUENCE_DECAY_PER_TURN: f32 = 0.01;
 
+/// Settings for archiving stale facts out of the active set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PruneConfig {
+    /// Facts recorded with importance below this are eligible for
+    /// archiving. Decay over time does not count, so an important fact
+    /// stays active however old it gets.
+    pub importance_threshold: f32,
+    /// Facts must be at least this many turns old to be archived.
+    pub min_age_turns: u32,
+}
+
+impl Default for PruneConfig {
+    fn default() -> Self {
+        Self {
+            importance_threshold: 0.3,
+            min_age_turns: 30,
+        }
+    }
+}
+
 /// The main story memory store.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StoryMemory {
@@ -36,6 +70,9 @@ pub struct StoryMemory {
     name_index: HashMap<String, EntityId>,
     /// All story facts.
     facts: Vec<StoryFact>,
+    /// Facts pruned out of the active set, kept for history.
+    #[serde(default)]
+    archived_facts: Vec<StoryFact>,
     /// All relationships.
     relationships: Vec<Relationship>,
     /// All pending consequences.
@@ -261,6 +298,27 @@ impl StoryMemory {
         self.add_fact(fact);
     }
 
+    /// Archive facts that are old and were recorded as unimportant so they
+    /// no longer appear in context or relevance prompts. Archived facts are
+    /// kept in the save. Returns the number of facts archived.
+    pub fn prune(&mut self, config: &PruneConfig) -> usize {
+        let current_turn = self.current_turn;
+        let (stale, active): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.facts).into_iter().partition(|f| {
+                f.recorded_importance < config.importance_threshold
+                    && current_turn.saturating_sub(f.established.turn) >= config.min_age_turns
+            });
+        self.facts = active;
+        let archived = stale.len();
+        self.archived_facts.extend(stale);
+        archived
+    }
+
+    /// Facts that have been pruned out of the active set.
+    pub fn archived_facts(&self) -> &[StoryFact] {
+        &self.archived_facts
+    }
+
     /// Get all facts about an entity.
     pub fn facts_about(&self, entity_id: EntityId) -> Vec<&StoryFact> {
         self.facts
@@ -406,6 +464,79 @@ impl StoryMemory {
         consequences
     }
 
+    /// Score pending consequences by keyword overlap between their trigger and
+    /// the player's input, with partial credit for trigger words that match the
+    /// current location and a bonus for consequences involving entities named
+    /// in the input.
+    ///
+    /// Used as a fallback when the relevance model is unavailable and to
+    /// pre-filter the consequences sent to it. Returns only non-zero matches,
+    /// highest score first.
+    pub fn lexical_relevance(&self, player_input: &str, location: &str) -> Vec<LexicalMatch> {
+        let input_words = keywords(player_input);
+        let location_words = keywords(location);
+        let mentioned = self.extract_mentioned_entities(player_input);
+
+        let mut matches: Vec<LexicalMatch> = self
+            .pending_consequences()
+            .into_iter()
+            .filter_map(|consequence| {
+                let trigger_words = keywords(&consequence.trigger_description);
+                if trigger_words.is_empty() {
+                    return None;
+                }
+
+                let overlap: f32 = trigger_words
+                    .iter()
+                    .map(|word| {
+                        if input_words.contains(word) {
+                            1.0
+                        } else if location_words.contains(word) {
+                            0.5
+                        } else {
+                            0.0
+                        }
+                    })
+                    .sum();
+                let entity_bonus = if mentioned.iter().any(|&id| consequence.involves(id)) {
+                    0.3
+                } else {
+                    0.0
+                };
+
+                let score = (overlap / trigger_words.len() as f32 + entity_bonus).min(1.0);
+                (score > 0.0).then_some(LexicalMatch {
+                    consequence_id: consequence.id,
+                    score,
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        matches
+    }
+
+    /// Build the consequences list for a relevance check against specific
+    /// input: lexical matches first, then the most important remaining
+    /// consequences, capped to keep the prompt small.
+    pub fn build_consequences_for_input(
+        &self,
+        player_input: &str,
+        location: &str,
+    ) -> IndexedConsequences {
+        let lexical = self.lexical_relevance(player_input, location);
+        let mut candidates: Vec<&Consequence> = lexical
+            .iter()
+            .filter_map(|m| self.get_consequence(m.consequence_id))
+            .collect();
+        for consequence in self.pending_consequences_by_importance() {
+            if !candidates.iter().any(|c| c.id == consequence.id) {
+                candidates.push(consequence);
+            }
+        }
+        IndexedConsequences::new(candidates.into_iter().take(MAX_RELEVANCE_CANDIDATES))
+    }
+
     /// Get consequences involving a specific entity.
     pub fn consequences_involving(&self, entity_id: EntityId) -> Vec<&Consequence> {
         self.consequences
@@ -447,25 +578,14 @@ impl StoryMemory {
             .count()
     }
 
-    /// Build context string for pending consequences.
+    /// Build the labelled list of pending consequences, most important first.
     /// This is used by the relevance checker.
-    pub fn build_consequences_for_relevance(&self) -> String {
-        let pending = self.pending_consequences_by_importance();
-        if pending.is_empty() {
-            return String::new();
-        }
-
-        let mut context = String::new();
-        for (i, consequence) in pending.iter().take(MAX_CONTEXT_CONSEQUENCES).enumerate() {
-            context.push_str(&format!(
-                "{}. [{}] TRIGGER: {} -> EFFECT: {}\n",
-                i + 1,
-                consequence.id,
-                consequence.trigger_description,
-                consequence.consequence_description
-            ));
-        }
-        context
+    pub fn build_consequences_for_relevance(&self) -> IndexedConsequences {
+        IndexedConsequences::new(
+            self.pending_consequences_by_importance()
+                .into_iter()
+                .take(MAX_CONTEXT_CONSEQUENCES),
+        )
     }
 
     // =========================================================================
@@ -619,13 +739,7 @@ impl StoryMemory {
     /// Update the current minute count from game time.
     /// Call this when time advances in the game.
     pub fn sync_time(&mut self, game_time: &GameTime) {
-        // Calculate total minutes from game time
-        // Days since year 0, then hours, then minutes
-        let days = (game_time.year as u64 * 12 * 30)  // 12 months, 30 days each
-            + (game_time.month as u64 - 1) * 30
-            + (game_time.day as u64 - 1);
-        let hours = days * 24 + game_time.hour as u64;
-        self.current_minute = hours * 60 + game_time.minute as u64;
+        self.current_minute = game_time.to_minutes().max(0) as u64;
     }
 
     /// Advance time by a number of minutes.
@@ -762,19 +876,7 @@ impl StoryMemory {
                     month,
                     day,
                     hour,
-                } => {
-                    game_time.year >= *year
-                        && (game_time.year > *year || game_time.month >= *month)
-                        && (game_time.year > *year
-                            || game_time.month > *month
-                            || game_time.day >= *day)
-                        && hour.is_none_or(|h| {
-                            game_time.year > *year
-                                || game_time.month > *month
-                                || game_time.day > *day
-                                || game_time.hour >= h
-                        })
-                }
+                } => *game_time >= GameTime::new(*year, *month, *day, hour.unwrap_or(0), 0),
                 EventTrigger::TimeOfDay { hour, minute } => {
                     game_time.hour == *hour && game_time.minute >= *minute
                 }
@@ -936,6 +1038,114 @@ impl StoryMemory {
     }
 }
 
+/// Consequences listed in a relevance prompt under short labels (`C1`, `C2`,
+/// ...) so the model can refer to them without copying UUIDs. Labels are
+/// assigned fresh for each prompt.
+#[derive(Debug, Clone, Default)]
+pub struct IndexedConsequences {
+    /// The prompt text, one labelled consequence per line.
+    pub text: String,
+    ids: Vec<ConsequenceId>,
+}
+
+impl IndexedConsequences {
+    fn new<'a>(consequences: impl IntoIterator<Item = &'a Consequence>) -> Self {
+        let mut indexed = Self::default();
+        for consequence in consequences {
+            indexed.ids.push(consequence.id);
+            indexed.text.push_str(&format!(
+                "[C{}] TRIGGER: {} -> EFFECT: {}\n",
+                indexed.ids.len(),
+                consequence.trigger_description,
+                consequence.consequence_description
+            ));
+        }
+        indexed
+    }
+
+    /// Number of consequences listed.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether no consequences are listed.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Map a label returned by the model (`C3`, `c3`, or `[C3]`) back to its
+    /// consequence. Unknown or malformed labels return `None`.
+    pub fn resolve(&self, label: &str) -> Option<ConsequenceId> {
+        let label = label.trim().trim_start_matches('[').trim_end_matches(']');
+        let index: usize = label
+            .strip_prefix(['C', 'c'])
+            .unwrap_or(label)
+            .parse()
+            .ok()?;
+        self.ids.get(index.checked_sub(1)?).copied()
+    }
+}
+
+/// A consequence's keyword-overlap score against player input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LexicalMatch {
+    pub consequence_id: ConsequenceId,
+    /// Overlap score from 0.0 to 1.0.
+    pub score: f32,
+}
+
+impl LexicalMatch {
+    /// Whether the overlap is strong enough to treat as a trigger on its own.
+    pub fn is_likely_trigger(&self) -> bool {
+        self.score >= LEXICAL_TRIGGER_THRESHOLD
+    }
+}
+
+/// Split text into lowercase, lightly stemmed keywords, skipping short and
+/// common words so "I enter Riverside" and "enters Riverside" overlap.
+fn keywords(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.len() >= 3 && !STOP_WORDS.contains(&w.as_str()))
+    {
+        let stem = stem(&word).to_string();
+        if !words.contains(&stem) {
+            words.push(stem);
+        }
+    }
+    words
+}
+
+/// Strip common English suffixes from longer words.
+///
+/// Plurals lose "es" only after a sibilant ("churches", "glasses") and "s"
+/// otherwise, so "shrines" and "shrine" share the stem "shrine".
+fn stem(word: &str) -> &str {
+    if word.len() > 5 {
+        for suffix in ["ing", "ed"] {
+            if let Some(stem) = word.strip_suffix(suffix) {
+                return stem;
+            }
+        }
+    }
+    if word.len() > 3 && !word.ends_with("ss") {
+        if let Some(stem) = word.strip_suffix("es") {
+            if ["ss", "x", "z", "ch", "sh"]
+                .iter()
+                .any(|s| stem.ends_with(s))
+            {
+                return stem;
+            }
+        }
+        if let Some(stem) = word.strip_suffix('s') {
+            return stem;
+        }
+    }
+    word
+}
+
 /// Check if `text` contains `word` at word boundaries.
 ///
 /// A word boundary is the start/end of string or a non-alphanumeric character.
@@ -1173,7 +1383,7 @@ impl StoryMemory {
         self.entities.len()
     }
 
-    /// Get the total number of facts.
+    /// Get the number of active (non-archived) facts.
     pub fn fact_count(&self) -> usize {
         self.facts.len()
     }
@@ -1467,4 +1677,166 @@ mod tests {
         let involving = store.consequences_involving(npc_id);
         assert_eq!(involving.len(), 1);
     }
+
+    #[test]
+    fn test_prune_archives_old_unimportant_facts() {
+        let mut store = StoryMemory::new();
+        let mira = store.create_entity(EntityType::Npc, "Mira");
+
+        store.record_fact_full(
+            mira,
+            "Mira hums while she works",
+            FactCategory::Personality,
+            FactSource::DmNarration,
+            &[],
+            0.2,
+        );
+        store.record_fact(
+            mira,
+            "Mira is the lost heir to the throne",
+            FactCategory::Secret,
+            FactSource::DmNarration,
+        );
+
+        let config = PruneConfig::default();
+
+        // Too recent to prune, even though unimportant
+        assert_eq!(store.prune(&config), 0);
+        assert_eq!(store.facts_about(mira).len(), 2);
+
+        for _ in 0..config.min_age_turns {
+            store.advance_turn();
+        }
+        assert_eq!(store.prune(&config), 1);
+
+        let active = store.facts_about(mira);
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].content, "Mira is the lost heir to the throne");
+        assert!(!store
+            .build_relevant_context(&[mira])
+            .contains("hums while she works"));
+
+        // Decay alone never archives the heir secret
+        for _ in 0..50 {
+            store.advance_turn();
+        }
+        assert_eq!(store.prune(&config), 0);
+        assert_eq!(store.facts_about(mira).len(), 1);
+
+        // Still in the save for history
+        assert_eq!(store.archived_facts().len(), 1);
+        let json = serde_json::to_string(&store).unwrap();
+        let restored: StoryMemory = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.archived_facts()[0].content,
+            "Mira hums while she works"
+        );
+    }
+
+    #[test]
+    fn test_stem_matches_plural_and_singular() {
+        for (plural, singular) in [
+            ("shrines", "shrine"),
+            ("houses", "house"),
+            ("churches", "church"),
+            ("glasses", "glass"),
+            ("boxes", "box"),
+            ("guards", "guard"),
+        ] {
+            assert_eq!(stem(plural), stem(singular), "{plural} / {singular}");
+        }
+    }
+
+    #[test]
+    fn test_lexical_relevance_matches_location_name() {
+        let mut store = StoryMemory::new();
+        let riverside = store.create_consequence(
+            "Player enters Riverside",
+            "The town guard recognizes them and raises the alarm",
+            ConsequenceSeverity::Major,
+        );
+        let bakery = store.create_consequence(
+            "Player steals from the bakery",
+            "The baker sends thugs after them",
+            ConsequenceSeverity::Minor,
+        );
+
+        let matches = store.lexical_relevance("I enter Riverside", "Forest Road");
+        assert_eq!(matches[0].consequence_id, riverside);
+        assert!(matches[0].is_likely_trigger());
+        assert!(!matches.iter().any(|m| m.consequence_id == bakery));
+
+        // Unrelated input produces no matches
+        assert!(store
+            .lexical_relevance("I sharpen my sword", "Forest Road")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_lexical_relevance_location_and_entities() {
+        let mut store = StoryMemory::new();
+        let baron = store.create_entity(EntityType::Npc, "Baron Aldric");
+        let consequence = Consequence::new(
+            "Player returns to Riverside",
+            "Bounty hunters are waiting",
+            ConsequenceSeverity::Major,
+            0,
+        )
+        .with_related(baron);
+        let id = store.add_consequence(consequence);
+
+        // Being in Riverside gives partial credit even if the input doesn't name it
+        let at_location = store.lexical_relevance("I look around", "Riverside");
+        assert_eq!(at_location[0].consequence_id, id);
+        assert!(!at_location[0].is_likely_trigger());
+
+        // Naming a related entity adds to the score
+        let with_entity = store.lexical_relevance("I ask about Baron Aldric", "Riverside");
+        assert!(with_entity[0].score > at_location[0].score);
+    }
+
+    #[test]
+    fn test_build_consequences_for_input_prefers_lexical_matches() {
+        let mut store = StoryMemory::new();
+        for i in 0..12 {
+            store.create_consequence(
+                format!("Player visits shrine {i}"),
+                "Nothing much",
+                ConsequenceSeverity::Critical,
+            );
+        }
+        store.create_consequence(
+            "Player enters Riverside",
+            "Alarm bells ring",
+            ConsequenceSeverity::Minor,
+        );
+
+        let prompt = store.build_consequences_for_input("I enter Riverside", "Forest Road");
+        assert!(prompt
+            .text
+            .starts_with("[C1] TRIGGER: Player enters Riverside"));
+        assert_eq!(prompt.len(), MAX_RELEVANCE_CANDIDATES);
+        assert_eq!(prompt.text.lines().count(), MAX_RELEVANCE_CANDIDATES);
+    }
+
+    #[test]
+    fn test_indexed_consequences_round_trip() {
+        let mut store = StoryMemory::new();
+        let first = store.create_consequence("Trigger A", "Effect A", ConsequenceSeverity::Major);
+        let second = store.create_consequence("Trigger B", "Effect B", ConsequenceSeverity::Minor);
+
+        let indexed = store.build_consequences_for_relevance();
+        assert_eq!(indexed.len(), 2);
+        assert!(indexed.text.contains("[C1] TRIGGER: Trigger A"));
+        assert!(!indexed.text.contains(&first.to_string()));
+
+        assert_eq!(indexed.resolve("C1"), Some(first));
+        assert_eq!(indexed.resolve("[c2]"), Some(second));
+        assert_eq!(indexed.resolve(" 2 "), Some(second));
+
+        // Unknown or malformed labels are ignored
+        assert_eq!(indexed.resolve("C3"), None);
+        assert_eq!(indexed.resolve("C0"), None);
+        assert_eq!(indexed.resolve("banana"), None);
+    }
 }
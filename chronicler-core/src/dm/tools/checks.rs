@@ -1,5 +1,6 @@
 //! Dice rolling and check tools.
 
+use super::converters::{ability_names, skill_names};
 use claude::Tool;
 use serde_json::json;
 
@@ -36,10 +37,7 @@ pub fn skill_check() -> Tool {
             "properties": {
                 "skill": {
                     "type": "string",
-                    "enum": ["athletics", "acrobatics", "sleight_of_hand", "stealth",
-                            "arcana", "history", "investigation", "nature", "religion",
-                            "animal_handling", "insight", "medicine", "perception", "survival",
-                            "deception", "intimidation", "performance", "persuasion"],
+                    "enum": skill_names(),
                     "description": "The skill to check"
                 },
                 "dc": {
@@ -71,7 +69,7 @@ pub fn ability_check() -> Tool {
             "properties": {
                 "ability": {
                     "type": "string",
-                    "enum": ["strength", "dexterity", "constitution", "intelligence", "wisdom", "charisma"],
+                    "enum": ability_names(),
                     "description": "The ability to check"
                 },
                 "dc": {
@@ -103,7 +101,7 @@ pub fn saving_throw() -> Tool {
             "properties": {
                 "ability": {
                     "type": "string",
-                    "enum": ["strength", "dexterity", "constitution", "intelligence", "wisdom", "charisma"],
+                    "enum": ability_names(),
                     "description": "The ability for the save"
                 },
                 "dc": {
@@ -112,7 +110,7 @@ pub fn saving_throw() -> Tool {
                 },
                 "source": {
                     "type": "string",
-                    "description": "What is causing the saving throw"
+                    "description": "What is causing the saving throw (e.g., 'poison', 'charm spell'). Advantage from traits like Dwarven Resilience is applied automatically."
                 },
                 "advantage": {
                     "type": "string",
@@ -124,3 +122,86 @@ pub fn saving_throw() -> Tool {
         }),
     }
 }
+
+/// Have an ally help the player with their next ability check.
+pub fn help() -> Tool {
+    Tool {
+        name: "help".to_string(),
+        description: "An ally takes the Help action, giving the player advantage on their next skill or ability check. The advantage is used up by that check.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "helper": {
+                    "type": "string",
+                    "description": "Name of the ally who is helping"
+                },
+                "task": {
+                    "type": "string",
+                    "description": "What the ally is helping with"
+                }
+            },
+            "required": ["helper"]
+        }),
+    }
+}
+
+/// Have the party make a check together.
+pub fn group_check() -> Tool {
+    Tool {
+        name: "group_check".to_string(),
+        description: "The player and companions attempt a check together (sneaking as a group, climbing a cliff). The group succeeds if at least half its members succeed.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "skill": {
+                    "type": "string",
+                    "enum": skill_names(),
+                    "description": "The skill to check"
+                },
+                "dc": {
+                    "type": "integer",
+                    "description": "Difficulty Class for the check"
+                },
+                "companions": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "modifier": {
+                                "type": "integer",
+                                "description": "The companion's bonus for this skill"
+                            }
+                        },
+                        "required": ["name"]
+                    },
+                    "description": "Companions joining the player in the check"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "What the group is attempting"
+                }
+            },
+            "required": ["skill", "dc", "companions", "description"]
+        }),
+    }
+}
+
+/// Reroll the player's most recent d20 with a luck feature.
+pub fn reroll_d20() -> Tool {
+    Tool {
+        name: "reroll_d20".to_string(),
+        description: "Reroll the player's most recent d20 (attack roll, ability check, or saving throw) with a luck feature. Halfling Luck rerolls a natural 1 for free and must use the new die; the Lucky feat spends one of 3 luck points (recovered on long rest) and keeps the better die. Each feature can reroll a given roll only once. The earlier result is not undone: narrate the outcome from the new total.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "feature": {
+                    "type": "string",
+                    "enum": ["lucky", "halfling_luck"],
+                    "description": "Feature to reroll with; omit to use whichever applies"
+                }
+            },
+            "required": []
+        }),
+    }
+}
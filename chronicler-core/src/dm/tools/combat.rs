@@ -1,5 +1,6 @@
 //! Combat-related tools: damage, healing, conditions, and combat flow.
 
+use super::converters::ability_names;
 use claude::Tool;
 use serde_json::json;
 
@@ -37,6 +38,62 @@ pub fn apply_damage() -> Tool {
     }
 }
 
+/// Damage several creatures in an area, each saving for half.
+pub fn aoe_damage() -> Tool {
+    Tool {
+        name: "aoe_damage".to_string(),
+        description: "Deal area damage (e.g., Fireball, a dragon's breath) to several combatants at once. Damage is rolled once; each target makes its own saving throw and takes half on a success. Evasion and resistances are applied automatically.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "targets": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": {
+                                "type": "string",
+                                "description": "Name of the target (a combatant in the current combat, or the player)"
+                            },
+                            "save_modifier": {
+                                "type": "integer",
+                                "description": "The target's modifier for this saving throw (e.g., Goblin DEX: +2). Ignored for the player."
+                            }
+                        },
+                        "required": ["name"]
+                    },
+                    "description": "Every creature caught in the area"
+                },
+                "damage_dice": {
+                    "type": "string",
+                    "description": "Damage dice rolled once for all targets (e.g., '8d6')"
+                },
+                "damage_type": {
+                    "type": "string",
+                    "enum": ["slashing", "piercing", "bludgeoning", "fire", "cold",
+                            "lightning", "thunder", "acid", "poison", "necrotic",
+                            "radiant", "force", "psychic"],
+                    "description": "Type of damage"
+                },
+                "save_ability": {
+                    "type": "string",
+                    "enum": ability_names(),
+                    "description": "Ability used for the saving throw (default: dexterity)"
+                },
+                "dc": {
+                    "type": "integer",
+                    "description": "Saving throw DC"
+                },
+                "source": {
+                    "type": "string",
+                    "description": "Source of the damage (e.g., 'Fireball')"
+                }
+            },
+            "required": ["targets", "damage_dice", "damage_type", "dc", "source"]
+        }),
+    }
+}
+
 /// Heal a character.
 pub fn apply_healing() -> Tool {
     Tool {
@@ -80,7 +137,12 @@ pub fn apply_condition() -> Tool {
                 },
                 "duration_rounds": {
                     "type": "integer",
-                    "description": "How many rounds the condition lasts (omit for indefinite)"
+                    "description": "How many of the character's turns the condition lasts (omit for indefinite). 'Until the end of your next turn' is 1."
+                },
+                "expires_at": {
+                    "type": "string",
+                    "enum": ["end_of_turn", "start_of_turn"],
+                    "description": "Whether the duration counts down at the end (default) or the start of the character's turn"
                 }
             },
             "required": ["condition", "source"]
@@ -138,11 +200,16 @@ pub fn start_combat() -> Tool {
                             "initiative_modifier": {
                                 "type": "integer",
                                 "description": "Initiative modifier based on DEX (e.g., Goblin: +2, Orc: +1, Wolf: +2)"
+                            },
+                            "resistances": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Damage resistances: a damage type (e.g., 'fire'), 'nonmagical' (nonmagical bludgeoning/piercing/slashing), or 'nonmagical_unsilvered' (e.g., Werewolf)"
                             }
                         },
                         "required": ["name"]
                     },
-                    "description": "List of enemy combatants with their stats"
+                    "description": "List of combatants other than the player, with their stats. NPCs already in the world join by disposition: friendly or helpful ones fight alongside the player, the rest against them."
                 }
             },
             "required": ["enemies"]
@@ -237,3 +304,26 @@ pub fn attack() -> Tool {
         }),
     }
 }
+
+/// Bonus-action attack with an off-hand light weapon.
+pub fn offhand_attack() -> Tool {
+    Tool {
+        name: "offhand_attack".to_string(),
+        description: "Make a bonus-action attack with the light weapon held in the player's off hand (two-weapon fighting), after attacking with a light weapon in the main hand. Damage adds no ability modifier unless the player has the Two-Weapon Fighting style.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "target": {
+                    "type": "string",
+                    "description": "Name of the target (must be a combatant in the current combat)"
+                },
+                "advantage": {
+                    "type": "string",
+                    "enum": ["normal", "advantage", "disadvantage"],
+                    "description": "Advantage state for the attack roll"
+                }
+            },
+            "required": ["target"]
+        }),
+    }
+}
@@ -4,8 +4,24 @@
 //! into their corresponding D&D enum types.
 
 use crate::dice::Advantage;
-use crate::rules::DamageType;
-use crate::world::{Ability, Condition, Skill};
+use crate::rules::{DamageType, Resistance};
+use crate::world::{Ability, Condition, FightingStyle, LightLevel, Skill};
+
+/// Every skill as tool inputs name it, e.g. "sleight_of_hand".
+pub fn skill_names() -> Vec<String> {
+    Skill::all()
+        .iter()
+        .map(|skill| skill.name().to_lowercase().replace(' ', "_"))
+        .collect()
+}
+
+/// Every ability as tool inputs name it, e.g. "strength".
+pub fn ability_names() -> Vec<String> {
+    Ability::all()
+        .iter()
+        .map(|ability| ability.name().to_lowercase())
+        .collect()
+}
 
 /// Parse a skill name string into a Skill enum.
 pub fn parse_skill(s: &str) -> Option<Skill> {
@@ -74,6 +90,42 @@ pub fn parse_damage_type(s: &str) -> Option<DamageType> {
     }
 }
 
+/// Parse a resistance string ("fire", "nonmagical", "nonmagical_unsilvered")
+/// into a Resistance.
+pub fn parse_resistance(s: &str) -> Option<Resistance> {
+    match s.to_lowercase().replace([' ', '-'], "_").as_str() {
+        "nonmagical" | "nonmagical_physical" => Some(Resistance::NonmagicalPhysical),
+        "nonmagical_unsilvered" | "nonmagical_unsilvered_physical" => {
+            Some(Resistance::NonmagicalUnsilveredPhysical)
+        }
+        other => parse_damage_type(other).map(Resistance::Type),
+    }
+}
+
+/// Parse a fighting style string ("great_weapon_fighting", "Two-Weapon Fighting")
+/// into a FightingStyle.
+pub fn parse_fighting_style(s: &str) -> Option<FightingStyle> {
+    match s.to_lowercase().replace([' ', '-'], "_").as_str() {
+        "archery" => Some(FightingStyle::Archery),
+        "defense" | "defence" => Some(FightingStyle::Defense),
+        "dueling" | "duelling" => Some(FightingStyle::Dueling),
+        "great_weapon_fighting" | "great_weapon" => Some(FightingStyle::GreatWeaponFighting),
+        "protection" => Some(FightingStyle::Protection),
+        "two_weapon_fighting" | "two_weapon" => Some(FightingStyle::TwoWeaponFighting),
+        _ => None,
+    }
+}
+
+/// Parse a light level string ("bright", "dim", "darkness") into a LightLevel.
+pub fn parse_light_level(s: &str) -> Option<LightLevel> {
+    match s.to_lowercase().as_str() {
+        "bright" | "bright_light" => Some(LightLevel::Bright),
+        "dim" | "dim_light" => Some(LightLevel::Dim),
+        "dark" | "darkness" => Some(LightLevel::Darkness),
+        _ => None,
+    }
+}
+
 /// Parse a condition string into a Condition enum.
 pub fn parse_condition(s: &str) -> Option<Condition> {
     match s.to_lowercase().as_str() {
@@ -81,6 +81,25 @@ pub fn connect_locations() -> Tool {
 }
 
 /// Update an existing location's state.
+pub fn set_light_level() -> Tool {
+    Tool {
+        name: "set_light_level".to_string(),
+        description: "Set how well lit the current area is (or the battlefield, during combat). Use this when the party enters an unlit cave, a torch goes out, magical darkness falls, or a lantern is lit. Dim light gives disadvantage on Perception; darkness also gives disadvantage on attacks, unless darkvision covers it.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "light_level": {
+                    "type": "string",
+                    "enum": ["bright", "dim", "darkness", "default"],
+                    "description": "The new light level, or 'default' to follow the time of day again"
+                }
+            },
+            "required": ["light_level"]
+        }),
+    }
+}
+
+/// Update an existing location.
 pub fn update_location() -> Tool {
     Tool {
         name: "update_location".to_string(),
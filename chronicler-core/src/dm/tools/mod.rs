@@ -13,6 +13,8 @@
 //! - `npc` - NPC creation, updates, movement, and removal
 //! - `quests` - Quest creation, objectives, and completion tracking
 //! - `state` - Declarative state assertions (disposition, location, status, relationships)
+//!
+//! Games can add their own tools through a [`ToolRegistry`].
 
 mod checks;
 mod class_features;
@@ -25,19 +27,48 @@ mod locations;
 mod npc;
 mod parsing;
 mod quests;
+mod registry;
 mod schedule;
 mod state;
 mod world;
 
 pub use info::execute_info_tool_with_memory;
-pub use parsing::parse_tool_call;
+pub use parsing::{invalid_tool_call_message, parse_tool_call};
+pub use registry::{ToolParser, ToolRegistry};
 
+use crate::world::GameMode;
 use claude::Tool;
 
+/// Combat tools that only make sense once combat has started.
+const COMBAT_ONLY_TOOLS: &[&str] = &["attack", "offhand_attack", "end_combat", "next_turn"];
+
 /// Collection of D&D tools for the DM.
 pub struct DmTools;
 
 impl DmTools {
+    /// Get the tool definitions suited to the current game mode.
+    ///
+    /// - `Combat`: every tool, with combat tools listed first
+    /// - Any other mode: everything except tools that need an active combat
+    ///   (attacks, turn order, ending combat)
+    pub fn for_mode(mode: GameMode) -> Vec<Tool> {
+        let needs_combat = |tool: &Tool| COMBAT_ONLY_TOOLS.contains(&tool.name.as_str());
+
+        let tools = Self::all();
+        match mode {
+            GameMode::Combat => {
+                let (mut combat, other): (Vec<_>, Vec<_>) = tools
+                    .into_iter()
+                    .partition(|tool| parsing::is_combat_tool(&tool.name));
+                combat.extend(other);
+                combat
+            }
+            GameMode::Exploration | GameMode::Dialogue | GameMode::Rest => {
+                tools.into_iter().filter(|t| !needs_combat(t)).collect()
+            }
+        }
+    }
+
     /// Get all tool definitions for the Claude API.
     pub fn all() -> Vec<Tool> {
         vec![
@@ -46,9 +77,14 @@ impl DmTools {
             checks::skill_check(),
             checks::ability_check(),
             checks::saving_throw(),
+            checks::help(),
+            checks::group_check(),
+            checks::reroll_d20(),
             // Combat
             combat::attack(),
+            combat::offhand_attack(),
             combat::apply_damage(),
+            combat::aoe_damage(),
             combat::apply_healing(),
             combat::apply_condition(),
             combat::remove_condition(),
@@ -86,14 +122,23 @@ impl DmTools {
             world::remember_fact(),
             world::register_consequence(),
             world::cast_spell(),
+            world::prepare_spell(),
+            world::unprepare_spell(),
+            world::learn_spell(),
+            world::copy_spell_to_book(),
             world::award_experience(),
+            world::apply_asi(),
+            world::choose_feat(),
+            world::choose_fighting_style(),
             world::modify_ability_score(),
             world::advance_time(),
             world::restore_spell_slot(),
+            world::adjust_reputation(),
             // Locations
             locations::create_location(),
             locations::connect_locations(),
             locations::update_location(),
+            locations::set_light_level(),
             // NPCs
             npc::create_npc(),
             npc::update_npc(),
@@ -124,6 +169,36 @@ impl DmTools {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_combat_tool_lists_name_real_tools() {
+        let names: Vec<String> = DmTools::all().into_iter().map(|t| t.name).collect();
+        for name in COMBAT_ONLY_TOOLS {
+            assert!(names.iter().any(|n| n == name), "unknown tool {name}");
+            assert!(parsing::is_combat_tool(name), "{name} is not a combat tool");
+        }
+    }
+
+    #[test]
+    fn test_tools_for_mode() {
+        let names = |mode| -> Vec<String> {
+            DmTools::for_mode(mode)
+                .into_iter()
+                .map(|t| t.name)
+                .collect()
+        };
+
+        let combat = names(GameMode::Combat);
+        assert_eq!(combat.len(), DmTools::all().len());
+        assert_eq!(combat[0], "attack");
+
+        let exploration = names(GameMode::Exploration);
+        assert!(exploration.contains(&"start_combat".to_string()));
+        assert!(exploration.contains(&"apply_damage".to_string()));
+        assert!(!exploration.contains(&"attack".to_string()));
+        assert!(!exploration.contains(&"next_turn".to_string()));
+        assert_eq!(names(GameMode::Dialogue), exploration);
+    }
+
     #[test]
     fn test_all_tools_have_valid_schemas() {
         let tools = DmTools::all();
@@ -142,6 +217,12 @@ mod tests {
                 tool.name
             );
         }
+
+        // The API rejects the whole request over one bad tool
+        claude::Request::new(Vec::new())
+            .with_tools(tools)
+            .validate()
+            .expect("DM tools should pass request validation");
     }
 
     #[test]
@@ -214,7 +295,15 @@ mod tests {
         let tools = DmTools::all();
 
         // Check that we have tools from each category
-        let check_tools = ["roll_dice", "skill_check", "ability_check", "saving_throw"];
+        let check_tools = [
+            "roll_dice",
+            "skill_check",
+            "ability_check",
+            "saving_throw",
+            "help",
+            "group_check",
+            "reroll_d20",
+        ];
         for name in check_tools {
             assert!(
                 tools.iter().any(|t| t.name == name),
@@ -1,11 +1,12 @@
 //! Parsing for dice rolls and check-related tools.
 
 use super::super::converters::{parse_ability, parse_advantage, parse_skill};
-use crate::rules::Intent;
+use crate::rules::{GroupCheckMember, Intent, RerollFeature};
 use crate::world::GameWorld;
 use serde_json::Value;
 
-/// Parse check-related tool calls: roll_dice, skill_check, ability_check, saving_throw.
+/// Parse check-related tool calls: roll_dice, skill_check, ability_check, saving_throw,
+/// help, group_check, reroll_d20.
 pub fn parse_checks_tool(name: &str, input: &Value, world: &GameWorld) -> Option<Intent> {
     match name {
         "roll_dice" => {
@@ -55,6 +56,48 @@ pub fn parse_checks_tool(name: &str, input: &Value, world: &GameWorld) -> Option
                 source,
             })
         }
+        "help" => {
+            let helper = input["helper"].as_str()?.to_string();
+            let task = input["task"].as_str().unwrap_or("").to_string();
+            Some(Intent::Help {
+                helper,
+                target_id: world.player_character.id,
+                task,
+            })
+        }
+        "reroll_d20" => {
+            let feature = match input["feature"].as_str() {
+                Some("lucky") => Some(RerollFeature::Lucky),
+                Some("halfling_luck") => Some(RerollFeature::HalflingLuck),
+                Some(_) => return None,
+                None => None,
+            };
+            Some(Intent::RerollD20 {
+                character_id: world.player_character.id,
+                feature,
+            })
+        }
+        "group_check" => {
+            let skill = parse_skill(input["skill"].as_str()?)?;
+            let dc = input["dc"].as_i64()? as i32;
+            let companions = input["companions"]
+                .as_array()?
+                .iter()
+                .map(|c| {
+                    Some(GroupCheckMember {
+                        name: c["name"].as_str()?.to_string(),
+                        modifier: c["modifier"].as_i64().unwrap_or(0) as i32,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let description = input["description"].as_str().unwrap_or("").to_string();
+            Some(Intent::GroupCheck {
+                skill,
+                dc,
+                companions,
+                description,
+            })
+        }
         _ => None,
     }
 }
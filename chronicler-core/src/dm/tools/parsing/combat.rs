@@ -1,8 +1,10 @@
 //! Parsing for combat-related tools.
 
-use super::super::converters::{parse_advantage, parse_condition, parse_damage_type};
-use crate::rules::{CombatantInit, Intent};
-use crate::world::{CharacterId, GameWorld};
+use super::super::converters::{
+    parse_ability, parse_advantage, parse_condition, parse_damage_type, parse_resistance,
+};
+use crate::rules::{AreaTarget, CombatantInit, Intent};
+use crate::world::{Ability, CharacterId, DurationTiming, GameWorld};
 use serde_json::Value;
 
 /// Parse combat-related tool calls.
@@ -23,6 +25,42 @@ pub fn parse_combat_tool(name: &str, input: &Value, world: &GameWorld) -> Option
                 source,
             })
         }
+        "aoe_damage" => {
+            let damage_dice = input["damage_dice"].as_str()?.to_string();
+            let damage_type = parse_damage_type(input["damage_type"].as_str()?)?;
+            let save_ability = match input["save_ability"].as_str() {
+                Some(ability) => parse_ability(ability)?,
+                None => Ability::Dexterity,
+            };
+            let dc = input["dc"].as_i64()? as i32;
+            let source = input["source"].as_str().unwrap_or("unknown").to_string();
+
+            // Every named target must be the player or a combatant in the fight
+            let targets = input["targets"]
+                .as_array()?
+                .iter()
+                .map(|target| {
+                    let name = target["name"].as_str()?;
+                    let id = find_combatant(world, name).or_else(|| {
+                        is_player_name(world, name).then_some(world.player_character.id)
+                    })?;
+                    let save_modifier = target["save_modifier"].as_i64().unwrap_or(0) as i8;
+                    Some(AreaTarget { id, save_modifier })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            if targets.is_empty() {
+                return None;
+            }
+
+            Some(Intent::AreaDamage {
+                targets,
+                damage_dice,
+                damage_type,
+                save_ability,
+                dc,
+                source,
+            })
+        }
         "apply_healing" => {
             let amount = input["amount"].as_i64()? as i32;
             // Validate healing is positive
@@ -40,11 +78,16 @@ pub fn parse_combat_tool(name: &str, input: &Value, world: &GameWorld) -> Option
             let condition = parse_condition(input["condition"].as_str()?)?;
             let source = input["source"].as_str().unwrap_or("unknown").to_string();
             let duration_rounds = input["duration_rounds"].as_i64().map(|d| d as u32);
+            let timing = match input["expires_at"].as_str() {
+                Some("start_of_turn") => DurationTiming::StartOfTurn,
+                _ => DurationTiming::EndOfTurn,
+            };
             Some(Intent::ApplyCondition {
                 target_id: world.player_character.id,
                 condition,
                 source,
                 duration_rounds,
+                timing,
             })
         }
         "remove_condition" => {
@@ -66,23 +109,38 @@ pub fn parse_combat_tool(name: &str, input: &Value, world: &GameWorld) -> Option
                 max_hp: player_hp.maximum,
                 armor_class: world.player_character.current_ac(),
                 initiative_modifier: world.player_character.initiative_modifier(),
+                resistances: Vec::new(),
             }];
 
             for enemy in enemies {
                 let name = enemy["name"].as_str().unwrap_or("Enemy").to_string();
+                // A known NPC keeps its id and takes the side its disposition puts it on
+                let npc = world
+                    .npcs
+                    .values()
+                    .find(|npc| npc.name.eq_ignore_ascii_case(&name));
                 let max_hp = enemy["max_hp"].as_i64().unwrap_or(10) as i32;
                 let current_hp = enemy["current_hp"].as_i64().unwrap_or(max_hp as i64) as i32;
                 let armor_class = enemy["armor_class"].as_u64().unwrap_or(10) as u8;
                 let initiative_modifier = enemy["initiative_modifier"].as_i64().unwrap_or(0) as i8;
+                let resistances = enemy["resistances"]
+                    .as_array()
+                    .map(|list| {
+                        list.iter()
+                            .filter_map(|r| r.as_str().and_then(parse_resistance))
+                            .collect()
+                    })
+                    .unwrap_or_default();
                 combatants.push(CombatantInit {
-                    id: CharacterId::new(),
+                    id: npc.map_or_else(CharacterId::new, |npc| npc.id),
                     name,
                     is_player: false,
-                    is_ally: false,
+                    is_ally: npc.is_some_and(|npc| npc.disposition.is_ally()),
                     current_hp,
                     max_hp,
                     armor_class,
                     initiative_modifier,
+                    resistances,
                 });
             }
 
@@ -107,19 +165,7 @@ pub fn parse_combat_tool(name: &str, input: &Value, world: &GameWorld) -> Option
             let target_name = input["target"].as_str()?;
             let advantage = parse_advantage(input["advantage"].as_str());
 
-            // Find the target in combat state by name
-            let target_id = if let Some(ref combat) = world.combat {
-                combat
-                    .combatants
-                    .iter()
-                    .find(|c| c.name.eq_ignore_ascii_case(target_name))
-                    .map(|c| c.id)
-            } else {
-                None
-            };
-
-            // If no combat or target not found, we can't attack
-            let target_id = target_id?;
+            let target_id = find_combatant(world, target_name)?;
 
             Some(Intent::Attack {
                 attacker_id: world.player_character.id,
@@ -128,6 +174,108 @@ pub fn parse_combat_tool(name: &str, input: &Value, world: &GameWorld) -> Option
                 advantage,
             })
         }
+        "offhand_attack" => {
+            let target_name = input["target"].as_str()?;
+            let advantage = parse_advantage(input["advantage"].as_str());
+            let target_id = find_combatant(world, target_name)?;
+
+            Some(Intent::OffhandAttack {
+                attacker_id: world.player_character.id,
+                target_id,
+                advantage,
+            })
+        }
         _ => None,
     }
 }
+
+/// The id of the combatant called `name`, if a fight is on and they're in it.
+fn find_combatant(world: &GameWorld, name: &str) -> Option<CharacterId> {
+    world
+        .combat
+        .as_ref()?
+        .combatants
+        .iter()
+        .find(|c| c.name.eq_ignore_ascii_case(name))
+        .map(|c| c.id)
+}
+
+fn is_player_name(world: &GameWorld, name: &str) -> bool {
+    name.eq_ignore_ascii_case("player") || name.eq_ignore_ascii_case(&world.player_character.name)
+}
+
+/// Explain why a combat tool's target couldn't be found, naming who can be
+/// targeted instead.
+pub(super) fn invalid_target(name: &str, input: &Value, world: &GameWorld) -> Option<String> {
+    let missing = match name {
+        "attack" | "offhand_attack" => input["target"]
+            .as_str()
+            .filter(|target| find_combatant(world, target).is_none())?,
+        "aoe_damage" => input["targets"]
+            .as_array()?
+            .iter()
+            .filter_map(|target| target["name"].as_str())
+            .find(|target| {
+                find_combatant(world, target).is_none() && !is_player_name(world, target)
+            })?,
+        _ => return None,
+    };
+    Some(match world.combat {
+        Some(ref combat) => {
+            let combatants: Vec<&str> = combat.combatants.iter().map(|c| c.name.as_str()).collect();
+            format!(
+                "`{missing}` is not in this combat. Current combatants: {}. \
+                 The call was not applied; call `{name}` again targeting one of them.",
+                combatants.join(", ")
+            )
+        }
+        None => format!(
+            "`{missing}` can't be targeted because no combat is in progress. \
+             The call was not applied; call `start_combat` first."
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{create_sample_fighter, Disposition, NPC};
+    use serde_json::json;
+
+    fn add_npc(world: &mut GameWorld, name: &str, disposition: Disposition) -> CharacterId {
+        let mut npc = NPC::new(name);
+        npc.disposition = disposition;
+        let id = npc.id;
+        world.npcs.insert(id, npc);
+        id
+    }
+
+    #[test]
+    fn test_start_combat_sides_follow_npc_disposition() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let bandit = add_npc(&mut world, "Bandit Captain", Disposition::Hostile);
+        let guard = add_npc(&mut world, "Sergeant Vell", Disposition::Friendly);
+        let input = json!({
+            "enemies": [
+                {"name": "bandit captain", "max_hp": 65},
+                {"name": "Sergeant Vell", "max_hp": 22},
+                {"name": "Wolf", "max_hp": 11}
+            ]
+        });
+
+        let Some(Intent::StartCombat { combatants }) =
+            parse_combat_tool("start_combat", &input, &world)
+        else {
+            panic!("Expected StartCombat intent");
+        };
+
+        let side = |name: &str| {
+            let combatant = combatants.iter().find(|c| c.name == name).unwrap();
+            (combatant.id, combatant.is_ally)
+        };
+        assert_eq!(side("bandit captain"), (bandit, false));
+        assert_eq!(side("Sergeant Vell"), (guard, true));
+        // Creatures the world doesn't know about are enemies
+        assert!(!side("Wolf").1);
+    }
+}
@@ -43,6 +43,25 @@ pub fn parse_gameplay_tool(name: &str, input: &Value, _world: &GameWorld) -> Opt
             Some(Intent::RestoreSpellSlot { slot_level, source })
         }
 
+        "adjust_reputation" => {
+            let faction = input.get("faction")?.as_str()?.trim().to_string();
+            let amount = input.get("amount")?.as_i64()? as i32;
+            if faction.is_empty() || amount == 0 {
+                return None;
+            }
+            let reason = input
+                .get("reason")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unspecified")
+                .to_string();
+
+            Some(Intent::AdjustReputation {
+                faction,
+                amount,
+                reason,
+            })
+        }
+
         _ => None,
     }
 }
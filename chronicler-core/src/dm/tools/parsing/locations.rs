@@ -1,5 +1,6 @@
 //! Location tool parsing - converts location tool calls into game Intents.
 
+use super::super::converters::parse_light_level;
 use crate::rules::Intent;
 use serde_json::Value;
 
@@ -121,6 +122,14 @@ pub fn parse_locations_tool(name: &str, input: &Value) -> Option<Intent> {
             })
         }
 
+        "set_light_level" => {
+            let light_level = match input.get("light_level")?.as_str()? {
+                "default" | "normal" => None,
+                other => Some(parse_light_level(other)?),
+            };
+            Some(Intent::SetLightLevel { light_level })
+        }
+
         _ => None,
     }
 }
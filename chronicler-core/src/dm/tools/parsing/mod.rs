@@ -40,8 +40,11 @@ pub use schedule::parse_schedule_tool;
 pub use state::parse_state_tool;
 pub use world::parse_world_tool;
 
+use super::converters::{parse_ability, parse_skill};
+use super::DmTools;
 use crate::rules::Intent;
 use crate::world::GameWorld;
+use combat::invalid_target;
 use serde_json::Value;
 use std::sync::LazyLock;
 
@@ -72,9 +75,13 @@ static TOOL_DOMAINS: LazyLock<std::collections::HashMap<&'static str, ToolDomain
         m.insert("skill_check", ToolDomain::Checks);
         m.insert("ability_check", ToolDomain::Checks);
         m.insert("saving_throw", ToolDomain::Checks);
+        m.insert("help", ToolDomain::Checks);
+        m.insert("group_check", ToolDomain::Checks);
+        m.insert("reroll_d20", ToolDomain::Checks);
 
         // Combat domain
         m.insert("apply_damage", ToolDomain::Combat);
+        m.insert("aoe_damage", ToolDomain::Combat);
         m.insert("apply_healing", ToolDomain::Combat);
         m.insert("apply_condition", ToolDomain::Combat);
         m.insert("remove_condition", ToolDomain::Combat);
@@ -84,6 +91,7 @@ static TOOL_DOMAINS: LazyLock<std::collections::HashMap<&'static str, ToolDomain
         m.insert("death_save", ToolDomain::Combat);
         m.insert("concentration_check", ToolDomain::Combat);
         m.insert("attack", ToolDomain::Combat);
+        m.insert("offhand_attack", ToolDomain::Combat);
 
         // Inventory domain
         m.insert("give_item", ToolDomain::Inventory);
@@ -115,7 +123,14 @@ static TOOL_DOMAINS: LazyLock<std::collections::HashMap<&'static str, ToolDomain
         m.insert("remember_fact", ToolDomain::World);
         m.insert("register_consequence", ToolDomain::World);
         m.insert("cast_spell", ToolDomain::World);
+        m.insert("prepare_spell", ToolDomain::World);
+        m.insert("unprepare_spell", ToolDomain::World);
+        m.insert("learn_spell", ToolDomain::World);
+        m.insert("copy_spell_to_book", ToolDomain::World);
         m.insert("award_experience", ToolDomain::World);
+        m.insert("apply_asi", ToolDomain::World);
+        m.insert("choose_feat", ToolDomain::World);
+        m.insert("choose_fighting_style", ToolDomain::World);
 
         // Quests domain
         m.insert("create_quest", ToolDomain::Quests);
@@ -135,11 +150,13 @@ static TOOL_DOMAINS: LazyLock<std::collections::HashMap<&'static str, ToolDomain
         m.insert("create_location", ToolDomain::Locations);
         m.insert("connect_locations", ToolDomain::Locations);
         m.insert("update_location", ToolDomain::Locations);
+        m.insert("set_light_level", ToolDomain::Locations);
 
         // Gameplay domain
         m.insert("modify_ability_score", ToolDomain::Gameplay);
         m.insert("advance_time", ToolDomain::Gameplay);
         m.insert("restore_spell_slot", ToolDomain::Gameplay);
+        m.insert("adjust_reputation", ToolDomain::Gameplay);
 
         // State domain
         m.insert("assert_state", ToolDomain::State);
@@ -178,6 +195,70 @@ pub fn parse_tool_call(name: &str, input: &Value, world: &GameWorld) -> Option<I
     }
 }
 
+/// Whether `name` is one of the built-in combat tools.
+pub(crate) fn is_combat_tool(name: &str) -> bool {
+    matches!(TOOL_DOMAINS.get(name), Some(ToolDomain::Combat))
+}
+
+/// Explain why a tool call could not be turned into an [`Intent`].
+///
+/// The message is returned to the model as an error `tool_result` so it can
+/// correct the call and retry within the same turn.
+pub fn invalid_tool_call_message(name: &str, input: &Value, world: &GameWorld) -> String {
+    if TOOL_DOMAINS.contains_key(name) {
+        invalid_input_message(name, input, world)
+    } else {
+        format!("Unknown tool: {name}. Only call tools from the provided list.")
+    }
+}
+
+/// The message for a call to a known tool whose input didn't parse.
+pub(super) fn invalid_input_message(name: &str, input: &Value, world: &GameWorld) -> String {
+    if let Some(problem) = invalid_target(name, input, world) {
+        return format!("Invalid input for `{name}`: {problem}");
+    }
+    if let Some(problem) = invalid_choice(name, input) {
+        return format!(
+            "Invalid input for `{name}`: {problem} The call was not applied; \
+             call `{name}` again with one of the valid options."
+        );
+    }
+    format!(
+        "Invalid input for `{name}`: {input}. The call was not applied. \
+         Check that all required fields are present, names refer to existing \
+         characters or items, and amounts are positive, then call `{name}` again \
+         with corrected input."
+    )
+}
+
+/// Name the first input field whose value isn't one of the choices the
+/// tool's schema lists, along with those choices.
+fn invalid_choice(name: &str, input: &Value) -> Option<String> {
+    let tool = DmTools::all().into_iter().find(|tool| tool.name == name)?;
+    let properties = tool.input_schema["properties"].as_object()?;
+    properties.iter().find_map(|(field, schema)| {
+        let choices = schema["enum"].as_array()?;
+        let value = input[field.as_str()].as_str()?;
+        let accepted = choices
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|choice| choice.eq_ignore_ascii_case(value))
+            || match field.as_str() {
+                "skill" => parse_skill(value).is_some(),
+                "ability" => parse_ability(value).is_some(),
+                _ => false,
+            };
+        if accepted {
+            return None;
+        }
+        let choices: Vec<&str> = choices.iter().filter_map(Value::as_str).collect();
+        Some(format!(
+            "`{value}` is not a valid {field}. Valid options: {}.",
+            choices.join(", ")
+        ))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,6 +371,59 @@ mod tests {
         assert!(intent.is_none(), "Should reject negative damage");
     }
 
+    #[test]
+    fn test_invalid_tool_call_message() {
+        let world = create_test_world();
+        let input = json!({"amount": 0, "damage_type": "slashing"});
+        let message = invalid_tool_call_message("apply_damage", &input, &world);
+        assert!(message.starts_with("Invalid input for `apply_damage`"));
+        assert!(message.contains("\"amount\":0"));
+
+        let message = invalid_tool_call_message("cast_fireball", &input, &world);
+        assert!(message.starts_with("Unknown tool: cast_fireball"));
+    }
+
+    #[test]
+    fn test_unknown_skill_names_the_valid_skills() {
+        let world = create_test_world();
+        let input = json!({"skill": "climbing", "dc": 12, "description": "Scaling the wall"});
+
+        assert!(parse_tool_call("skill_check", &input, &world).is_none());
+        let message = invalid_tool_call_message("skill_check", &input, &world);
+        assert!(message.contains("`climbing` is not a valid skill"));
+        assert!(message.contains("athletics"));
+        assert!(message.contains("sleight_of_hand"));
+
+        // Abbreviations the parser accepts aren't blamed for a failed call
+        let input = json!({"ability": "dex", "source": "trap"});
+        let message = invalid_tool_call_message("saving_throw", &input, &world);
+        assert!(message.contains("\"ability\":\"dex\""));
+    }
+
+    #[test]
+    fn test_parse_tool_call_group_check() {
+        let world = create_test_world();
+        let input = json!({
+            "skill": "stealth",
+            "dc": 12,
+            "companions": [{"name": "Kira", "modifier": 5}, {"name": "Bram"}],
+            "description": "Sneaking past the camp"
+        });
+
+        let intent = parse_tool_call("group_check", &input, &world);
+        if let Some(Intent::GroupCheck {
+            skill, companions, ..
+        }) = intent
+        {
+            assert_eq!(skill, Skill::Stealth);
+            assert_eq!(companions.len(), 2);
+            assert_eq!(companions[0].modifier, 5);
+            assert_eq!(companions[1].modifier, 0);
+        } else {
+            panic!("Expected GroupCheck intent");
+        }
+    }
+
     #[test]
     fn test_parse_tool_call_apply_healing() {
         let world = create_test_world();
@@ -1,5 +1,6 @@
 //! Parsing for world/session-related tools.
 
+use super::super::converters::{parse_ability, parse_fighting_style};
 use crate::rules::Intent;
 use crate::world::GameWorld;
 use serde_json::Value;
@@ -94,10 +95,48 @@ pub fn parse_world_tool(name: &str, input: &Value, world: &GameWorld) -> Option<
                 target_names: targets,
             })
         }
+        "prepare_spell" => {
+            let spell_name = input["spell_name"].as_str()?.to_string();
+            Some(Intent::PrepareSpell { spell_name })
+        }
+        "unprepare_spell" => {
+            let spell_name = input["spell_name"].as_str()?.to_string();
+            Some(Intent::UnprepareSpell { spell_name })
+        }
+        "copy_spell_to_book" => {
+            let spell_name = input["spell_name"].as_str()?.to_string();
+            let free = input["free"].as_bool().unwrap_or(false);
+            Some(Intent::CopySpellToBook { spell_name, free })
+        }
+        "learn_spell" => {
+            let spell_name = input["spell_name"].as_str()?.to_string();
+            let replace = input["replace"].as_str().map(|s| s.to_string());
+            Some(Intent::LearnSpell {
+                spell_name,
+                replace,
+            })
+        }
         "award_experience" => {
             let amount = input["amount"].as_u64()? as u32;
             Some(Intent::GainExperience { amount })
         }
+        "apply_asi" => {
+            let ability = parse_ability(input["ability"].as_str()?)?;
+            let amount = input["amount"].as_u64()? as u8;
+            Some(Intent::ApplyAsi { ability, amount })
+        }
+        "choose_feat" => {
+            let feat_name = input["feat_name"].as_str()?.to_string();
+            let description = input["description"].as_str().unwrap_or("").to_string();
+            Some(Intent::ChooseFeat {
+                feat_name,
+                description,
+            })
+        }
+        "choose_fighting_style" => {
+            let style = parse_fighting_style(input["style"].as_str()?)?;
+            Some(Intent::ChooseFightingStyle { style })
+        }
         _ => None,
     }
 }
@@ -0,0 +1,151 @@
+//! Custom DM tools registered alongside the built-in ones.
+//!
+//! A [`ToolRegistry`] lets a game add homebrew tools (say, an
+//! `invoke_patron` for a warlock campaign) without editing this crate. Each
+//! custom tool pairs the definition sent to the model with a parser that
+//! turns its input into an [`Intent`] for the rules engine.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use claude::Tool;
+use serde_json::Value;
+
+use super::parsing::{self, parse_tool_call};
+use crate::rules::Intent;
+use crate::world::GameWorld;
+
+/// Turns a custom tool's input into an intent, or `None` if it is invalid.
+pub type ToolParser = Arc<dyn Fn(&Value, &GameWorld) -> Option<Intent> + Send + Sync>;
+
+#[derive(Clone)]
+struct CustomTool {
+    definition: Tool,
+    parser: ToolParser,
+}
+
+/// The built-in tools plus any registered custom ones.
+///
+/// A custom tool with the same name as a built-in replaces it.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    custom: BTreeMap<String, CustomTool>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `definition`, parsing its calls with `parser`.
+    pub fn register(
+        &mut self,
+        definition: Tool,
+        parser: impl Fn(&Value, &GameWorld) -> Option<Intent> + Send + Sync + 'static,
+    ) {
+        self.custom.insert(
+            definition.name.clone(),
+            CustomTool {
+                definition,
+                parser: Arc::new(parser),
+            },
+        );
+    }
+
+    /// Whether `name` is a custom tool, possibly replacing a built-in one.
+    pub fn is_custom(&self, name: &str) -> bool {
+        self.custom.contains_key(name)
+    }
+
+    /// Definitions of the custom tools, in name order.
+    pub fn custom_tools(&self) -> impl Iterator<Item = &Tool> {
+        self.custom.values().map(|tool| &tool.definition)
+    }
+
+    /// Parse a call to a custom or built-in tool into an intent.
+    pub fn parse_tool_call(&self, name: &str, input: &Value, world: &GameWorld) -> Option<Intent> {
+        match self.custom.get(name) {
+            Some(tool) => (tool.parser)(input, world),
+            None => parse_tool_call(name, input, world),
+        }
+    }
+
+    /// Explain why a call could not be turned into an intent; see
+    /// [`invalid_tool_call_message`](super::invalid_tool_call_message).
+    pub fn invalid_tool_call_message(
+        &self,
+        name: &str,
+        input: &Value,
+        world: &GameWorld,
+    ) -> String {
+        if self.custom.contains_key(name) {
+            parsing::invalid_input_message(name, input, world)
+        } else {
+            parsing::invalid_tool_call_message(name, input, world)
+        }
+    }
+}
+
+impl fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("custom", &self.custom.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::create_sample_fighter;
+    use serde_json::json;
+
+    fn invoke_patron() -> Tool {
+        Tool {
+            name: "invoke_patron".to_string(),
+            description: "Call on the warlock's patron for a boon.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {"boon": {"type": "string"}},
+                "required": ["boon"]
+            }),
+        }
+    }
+
+    #[test]
+    fn test_custom_tool_dispatches_to_its_parser() {
+        let world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let mut registry = ToolRegistry::new();
+        registry.register(invoke_patron(), |input, _world| {
+            Some(Intent::RollDice {
+                notation: "1d20".to_string(),
+                purpose: input["boon"].as_str()?.to_string(),
+            })
+        });
+
+        let intent = registry.parse_tool_call("invoke_patron", &json!({"boon": "favor"}), &world);
+        assert!(matches!(
+            intent,
+            Some(Intent::RollDice { purpose, .. }) if purpose == "favor"
+        ));
+        assert!(registry
+            .invalid_tool_call_message("invoke_patron", &json!({}), &world)
+            .starts_with("Invalid input for `invoke_patron`"));
+        assert_eq!(
+            registry
+                .custom_tools()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["invoke_patron"]
+        );
+
+        // Built-in tools still parse through the registry
+        let builtin = registry.parse_tool_call(
+            "roll_dice",
+            &json!({"notation": "2d6", "purpose": "damage"}),
+            &world,
+        );
+        assert!(matches!(builtin, Some(Intent::RollDice { .. })));
+    }
+}
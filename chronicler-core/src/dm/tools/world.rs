@@ -1,5 +1,6 @@
 //! World state tools: rest, location, story memory, spells, and progression.
 
+use super::converters::ability_names;
 use claude::Tool;
 use serde_json::json;
 
@@ -146,7 +147,7 @@ pub fn register_consequence() -> Tool {
 pub fn cast_spell() -> Tool {
     Tool {
         name: "cast_spell".to_string(),
-        description: "Cast a spell. Handles spell slot consumption, attack rolls, saving throws, and damage/healing. For cantrips (level 0), no spell slot is consumed. For leveled spells, a spell slot of the appropriate level or higher must be available.".to_string(),
+        description: "Cast a spell. Handles spell slot consumption, attack rolls, saving throws, and damage/healing. For cantrips (level 0), no spell slot is consumed. For leveled spells, a spell slot of the appropriate level or higher must be available. Spells with costly material components (e.g., Revivify's 300 gp diamond) require the component in inventory and use it up if the spell consumes it - give it with give_item first.".to_string(),
         input_schema: json!({
             "type": "object",
             "properties": {
@@ -171,6 +172,88 @@ pub fn cast_spell() -> Tool {
     }
 }
 
+/// Prepare a spell for the day.
+pub fn prepare_spell() -> Tool {
+    Tool {
+        name: "prepare_spell".to_string(),
+        description: "Add a spell to the player's prepared spells. Clerics, Druids, Paladins, and Wizards (only spells in their spellbook) can have at most their spellcasting ability modifier + class level prepared (half the level for Paladins), and only spells on their class list of a level they can cast. Usually done after a long rest. If the limit is reached, unprepare a spell first.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "spell_name": {
+                    "type": "string",
+                    "description": "Name of the spell to prepare"
+                }
+            },
+            "required": ["spell_name"]
+        }),
+    }
+}
+
+/// Stop having a spell prepared.
+pub fn unprepare_spell() -> Tool {
+    Tool {
+        name: "unprepare_spell".to_string(),
+        description:
+            "Remove a spell from the player's prepared spells, freeing room to prepare another."
+                .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "spell_name": {
+                    "type": "string",
+                    "description": "Name of the prepared spell to drop"
+                }
+            },
+            "required": ["spell_name"]
+        }),
+    }
+}
+
+/// Copy a spell into a Wizard's spellbook.
+pub fn copy_spell_to_book() -> Tool {
+    Tool {
+        name: "copy_spell_to_book".to_string(),
+        description: "Copy a wizard spell the player has found (on a scroll or in another spellbook) into their own spellbook, so they can prepare it. Costs 50 gp and 2 hours per spell level, which are deducted automatically. The spell must be on the Wizard list and of a level they can cast. When a Wizard gains a level they add two spells of their choice for free: set 'free' for those.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "spell_name": {
+                    "type": "string",
+                    "description": "Name of the spell to copy"
+                },
+                "free": {
+                    "type": "boolean",
+                    "description": "Add the spell as one of the free spells gained at level-up, without gold or time"
+                }
+            },
+            "required": ["spell_name"]
+        }),
+    }
+}
+
+/// Learn a spell as a caster with a fixed list of known spells.
+pub fn learn_spell() -> Tool {
+    Tool {
+        name: "learn_spell".to_string(),
+        description: "Add a spell to the player's known spells. For Bards, Sorcerers, Warlocks, and Rangers, who know a fixed number of spells for their level; use it when they level up and pick new spells. The spell must be on their class list and of a level they can cast. On gaining a level they may also replace one known spell: set 'replace' to the spell they give up.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "spell_name": {
+                    "type": "string",
+                    "description": "Name of the spell to learn"
+                },
+                "replace": {
+                    "type": "string",
+                    "description": "A known spell to give up in exchange (only when leveling up)"
+                }
+            },
+            "required": ["spell_name"]
+        }),
+    }
+}
+
 /// Award experience points.
 pub fn award_experience() -> Tool {
     Tool {
@@ -193,6 +276,72 @@ pub fn award_experience() -> Tool {
     }
 }
 
+/// Spend Ability Score Improvement points on an ability score.
+pub fn apply_asi() -> Tool {
+    Tool {
+        name: "apply_asi".to_string(),
+        description: "Spend the player's Ability Score Improvement on an ability score. Use this when the player has an unspent Ability Score Improvement (from leveling up) and chooses which ability to raise. Each improvement is 2 points: +2 to one ability, or +1 to two abilities (call twice). Scores cannot exceed 20.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "ability": {
+                    "type": "string",
+                    "enum": ability_names(),
+                    "description": "The ability score to raise"
+                },
+                "amount": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": 2,
+                    "description": "How many points to add (1 or 2)"
+                }
+            },
+            "required": ["ability", "amount"]
+        }),
+    }
+}
+
+/// Take a feat in place of an Ability Score Improvement.
+pub fn choose_feat() -> Tool {
+    Tool {
+        name: "choose_feat".to_string(),
+        description: "Spend a full unspent Ability Score Improvement on a feat instead of raising ability scores. Use this when the player chooses a feat (e.g., Alert, Lucky, Tough) after leveling up. For Resilient, include the ability: 'Resilient (Constitution)'.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "feat_name": {
+                    "type": "string",
+                    "description": "Name of the feat"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "Short summary of what the feat does"
+                }
+            },
+            "required": ["feat_name"]
+        }),
+    }
+}
+
+/// Choose a Fighting Style granted by a class feature.
+pub fn choose_fighting_style() -> Tool {
+    Tool {
+        name: "choose_fighting_style".to_string(),
+        description: "Record the Fighting Style the player picks from their class feature (Fighter at level 1, Paladin and Ranger at level 2). The style then applies automatically: Defense +1 AC in armor, Archery +2 to hit with ranged weapons, Dueling +2 damage with a one-handed melee weapon, Great Weapon Fighting rerolls 1s and 2s on two-handed damage, Two-Weapon Fighting adds the modifier to off-hand damage.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "style": {
+                    "type": "string",
+                    "enum": ["archery", "defense", "dueling", "great_weapon_fighting", "protection", "two_weapon_fighting"],
+                    "description": "The fighting style to adopt"
+                }
+            },
+            "required": ["style"]
+        }),
+    }
+}
+
 /// Temporarily modify an ability score.
 pub fn modify_ability_score() -> Tool {
     Tool {
@@ -203,7 +352,7 @@ pub fn modify_ability_score() -> Tool {
             "properties": {
                 "ability": {
                     "type": "string",
-                    "enum": ["strength", "dexterity", "constitution", "intelligence", "wisdom", "charisma"],
+                    "enum": ability_names(),
                     "description": "The ability score to modify"
                 },
                 "modifier": {
@@ -250,6 +399,32 @@ pub fn advance_time() -> Tool {
     }
 }
 
+/// Adjust the party's reputation with a faction.
+pub fn adjust_reputation() -> Tool {
+    Tool {
+        name: "adjust_reputation".to_string(),
+        description: "Raise or lower the party's reputation with a faction (e.g., the Thieves' Guild, the City Watch) after deeds that faction would hear about. Reputation runs from -100 to 100 and starts at 0; current standings are listed in your context. Use it to decide how members of the faction react and what they offer.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "faction": {
+                    "type": "string",
+                    "description": "Name of the faction"
+                },
+                "amount": {
+                    "type": "integer",
+                    "description": "Change in reputation: about 5 for a small favor or slight, 15-25 for a significant deed, 50 for a defining act (negative to lower)"
+                },
+                "reason": {
+                    "type": "string",
+                    "description": "What the party did"
+                }
+            },
+            "required": ["faction", "amount", "reason"]
+        }),
+    }
+}
+
 /// Restore a specific spell slot.
 pub fn restore_spell_slot() -> Tool {
     Tool {
@@ -237,12 +237,14 @@ static WEAPONS: LazyLock<Vec<WeaponItem>> = LazyLock::new(|| {
                 WeaponProperty::Loading,
                 WeaponProperty::TwoHanded,
             ])
-            .with_range(80, 320),
+            .with_range(80, 320)
+            .with_ammunition("Bolts"),
         WeaponItem::new("Shortbow", "1d6", WeaponDamageType::Piercing)
             .with_weight(2.0)
             .with_value(25.0)
             .with_properties(vec![WeaponProperty::Ammunition, WeaponProperty::TwoHanded])
-            .with_range(80, 320),
+            .with_range(80, 320)
+            .with_ammunition("Arrows"),
         // Martial Ranged Weapons
         WeaponItem::new("Hand Crossbow", "1d6", WeaponDamageType::Piercing)
             .with_weight(3.0)
@@ -252,7 +254,8 @@ static WEAPONS: LazyLock<Vec<WeaponItem>> = LazyLock::new(|| {
                 WeaponProperty::Light,
                 WeaponProperty::Loading,
             ])
-            .with_range(30, 120),
+            .with_range(30, 120)
+            .with_ammunition("Bolts"),
         WeaponItem::new("Heavy Crossbow", "1d10", WeaponDamageType::Piercing)
             .with_weight(18.0)
             .with_value(50.0)
@@ -262,7 +265,8 @@ static WEAPONS: LazyLock<Vec<WeaponItem>> = LazyLock::new(|| {
                 WeaponProperty::Loading,
                 WeaponProperty::TwoHanded,
             ])
-            .with_range(100, 400),
+            .with_range(100, 400)
+            .with_ammunition("Bolts"),
         WeaponItem::new("Longbow", "1d8", WeaponDamageType::Piercing)
             .with_weight(2.0)
             .with_value(50.0)
@@ -271,7 +275,8 @@ static WEAPONS: LazyLock<Vec<WeaponItem>> = LazyLock::new(|| {
                 WeaponProperty::Heavy,
                 WeaponProperty::TwoHanded,
             ])
-            .with_range(150, 600),
+            .with_range(150, 600)
+            .with_ammunition("Arrows"),
     ]
 });
 
@@ -27,11 +27,14 @@
 //! ```
 
 pub mod character_builder;
+pub mod character_import;
 pub mod class_data;
 pub mod dice;
 pub mod dm;
 pub mod headless;
 pub mod items;
+pub mod narrative_log;
+pub mod offline;
 pub mod persist;
 pub mod rules;
 pub mod session;
@@ -41,7 +44,9 @@ pub mod world;
 
 // Primary public API
 pub use character_builder::{AbilityMethod, CharacterBuilder};
+pub use character_import::{ImportFormat, ImportWarning, ImportedCharacter};
 pub use headless::{HeadlessConfig, HeadlessGame};
+pub use offline::{OfflineTransport, ScriptedReply};
 pub use persist::{CharacterMetadata, CharacterSaveInfo, SavedCharacter};
 pub use session::{GameSession, Response, SessionConfig, SessionError};
 pub use testing::{MockDm, MockResponse, TestHarness};
@@ -0,0 +1,137 @@
+//! Spilling old narrative history to disk.
+//!
+//! A long campaign's narrative history can outgrow what is worth keeping in
+//! memory and writing into every save. A [`GameSession`](crate::GameSession)
+//! configured with [`SessionConfig::with_narrative_log`](crate::SessionConfig::with_narrative_log)
+//! keeps only the most recent entries on the world and appends older ones,
+//! one JSON line each, to a [`NarrativeSpill`] log that
+//! [`GameSession::narrative_page`](crate::GameSession::narrative_page) reads
+//! back. The log belongs to the session rather than the
+//! [`GameWorld`](crate::world::GameWorld), which does no file I/O of its own.
+//!
+//! The spill is saved with the session and records how long the log was at
+//! that point, so loading an earlier save reads only the entries that belong
+//! to it, and the first spill after the load overwrites the rest.
+
+use serde::{Deserialize, Serialize};
+use std::io::{self, SeekFrom};
+use std::ops::Range;
+use std::path::PathBuf;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader};
+
+use crate::world::NarrativeEntry;
+
+/// Where older narrative entries go once the in-memory history is full.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NarrativeSpill {
+    /// Append-only log of spilled entries, oldest first.
+    pub path: PathBuf,
+    /// How many of the most recent entries stay in memory.
+    pub keep_in_memory: usize,
+    /// How many entries have been written to the log.
+    #[serde(default)]
+    pub spilled: usize,
+    /// Length of the log in bytes after the last spill. Anything past it was
+    /// written by a later, unsaved sitting.
+    #[serde(default)]
+    pub spilled_bytes: u64,
+}
+
+impl NarrativeSpill {
+    pub fn new(path: impl Into<PathBuf>, keep_in_memory: usize) -> Self {
+        Self {
+            path: path.into(),
+            keep_in_memory,
+            spilled: 0,
+            spilled_bytes: 0,
+        }
+    }
+
+    /// Move the entries of `history` beyond the in-memory limit to the log.
+    ///
+    /// Entries stay in `history` if the log can't be written, so none are
+    /// lost.
+    pub async fn spill(&mut self, history: &mut Vec<NarrativeEntry>) -> io::Result<()> {
+        let excess = history.len().saturating_sub(self.keep_in_memory);
+        if excess == 0 {
+            return Ok(());
+        }
+        self.append(&history[..excess]).await?;
+        history.drain(..excess);
+        Ok(())
+    }
+
+    /// Number of narrative entries over the whole campaign, spilled or not.
+    pub fn len(&self, history: &[NarrativeEntry]) -> usize {
+        self.spilled + history.len()
+    }
+
+    /// The narrative entries at positions `range`, counted from the first
+    /// entry of the campaign, read back from the log where needed.
+    ///
+    /// The range is clamped to [`len`](Self::len).
+    pub async fn page(
+        &self,
+        history: &[NarrativeEntry],
+        range: Range<usize>,
+    ) -> io::Result<Vec<NarrativeEntry>> {
+        let end = range.end.min(self.len(history));
+        let start = range.start.min(end);
+
+        let mut page = self.read(start..end).await?;
+        // Whatever wasn't spilled comes from memory
+        let memory_start = start.saturating_sub(self.spilled);
+        let memory_end = end.saturating_sub(self.spilled);
+        page.extend_from_slice(&history[memory_start..memory_end]);
+        Ok(page)
+    }
+
+    /// Append `entries` to the log, dropping whatever follows the last
+    /// spill this state knows of.
+    async fn append(&mut self, entries: &[NarrativeEntry]) -> io::Result<()> {
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry).map_err(io::Error::other)?);
+            lines.push('\n');
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&self.path)
+            .await?;
+        file.set_len(self.spilled_bytes).await?;
+        file.seek(SeekFrom::End(0)).await?;
+        file.write_all(lines.as_bytes()).await?;
+        file.flush().await?;
+        self.spilled += entries.len();
+        self.spilled_bytes += lines.len() as u64;
+        Ok(())
+    }
+
+    /// Read the spilled entries at positions `range` (clamped to what was
+    /// spilled).
+    async fn read(&self, range: Range<usize>) -> io::Result<Vec<NarrativeEntry>> {
+        let end = range.end.min(self.spilled);
+        if range.start >= end {
+            return Ok(Vec::new());
+        }
+        let file = File::open(&self.path).await?;
+        let mut lines = BufReader::new(file.take(self.spilled_bytes)).lines();
+        let mut entries = Vec::with_capacity(end - range.start);
+        let mut index = 0;
+        while let Some(line) = lines.next_line().await? {
+            if index >= end {
+                break;
+            }
+            if index >= range.start {
+                let entry = serde_json::from_str(&line)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                entries.push(entry);
+            }
+            index += 1;
+        }
+        Ok(entries)
+    }
+}
@@ -0,0 +1,191 @@
+//! Offline play without an API key.
+//!
+//! [`OfflineTransport`] stands in for the Anthropic API. It answers the DM's
+//! requests with scripted replies built locally, so a
+//! [`GameSession`](crate::GameSession) runs the real tool parsing, rules, and
+//! effects pipeline with no network access. Useful for demos and tests.
+
+use claude::{Error, HttpRequest, RawResponse, Transport};
+use futures::future::BoxFuture;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Narration returned once the script runs out.
+pub const OFFLINE_NARRATION: &str =
+    "The world holds its breath, waiting for what you do next. (Offline mode)";
+
+/// One scripted DM reply: narration plus the tools the DM calls.
+#[derive(Debug, Clone)]
+pub struct ScriptedReply {
+    pub narrative: String,
+    /// Tool calls as (tool name, JSON input), parsed as real DM calls are.
+    pub tool_calls: Vec<(String, Value)>,
+}
+
+impl ScriptedReply {
+    /// A reply that only narrates, ending the turn.
+    pub fn narration(text: impl Into<String>) -> Self {
+        Self {
+            narrative: text.into(),
+            tool_calls: Vec::new(),
+        }
+    }
+
+    /// A reply that calls tools; the turn continues with the next reply.
+    pub fn with_tool_calls(text: impl Into<String>, tool_calls: Vec<(&str, Value)>) -> Self {
+        Self {
+            narrative: text.into(),
+            tool_calls: tool_calls
+                .into_iter()
+                .map(|(name, input)| (name.to_string(), input))
+                .collect(),
+        }
+    }
+
+    fn stop_reason(&self) -> &'static str {
+        if self.tool_calls.is_empty() {
+            "end_turn"
+        } else {
+            "tool_use"
+        }
+    }
+
+    fn tool_use_id(id: &str, index: usize) -> String {
+        format!("{id}_tool_{index}")
+    }
+
+    /// The reply as a Messages API response body.
+    fn to_message(&self, id: &str) -> String {
+        let mut content = Vec::new();
+        if !self.narrative.is_empty() {
+            content.push(json!({"type": "text", "text": self.narrative}));
+        }
+        for (i, (name, input)) in self.tool_calls.iter().enumerate() {
+            content.push(json!({
+                "type": "tool_use",
+                "id": Self::tool_use_id(id, i),
+                "name": name,
+                "input": input,
+            }));
+        }
+        json!({
+            "id": id,
+            "model": "offline",
+            "content": content,
+            "stop_reason": self.stop_reason(),
+            "usage": {"input_tokens": 0, "output_tokens": 0},
+        })
+        .to_string()
+    }
+
+    /// The reply as a server-sent event stream.
+    fn to_event_stream(&self, id: &str) -> String {
+        let mut events = vec![json!({
+            "type": "message_start",
+            "message": {"id": id, "model": "offline"},
+        })];
+        let mut index = 0;
+        if !self.narrative.is_empty() {
+            events.push(json!({
+                "type": "content_block_start",
+                "index": index,
+                "content_block": {"type": "text"},
+            }));
+            events.push(json!({
+                "type": "content_block_delta",
+                "index": index,
+                "delta": {"type": "text_delta", "text": self.narrative},
+            }));
+            events.push(json!({"type": "content_block_stop", "index": index}));
+            index += 1;
+        }
+        for (i, (name, input)) in self.tool_calls.iter().enumerate() {
+            events.push(json!({
+                "type": "content_block_start",
+                "index": index,
+                "content_block": {
+                    "type": "tool_use",
+                    "id": Self::tool_use_id(id, i),
+                    "name": name,
+                },
+            }));
+            events.push(json!({
+                "type": "content_block_delta",
+                "index": index,
+                "delta": {"type": "input_json_delta", "partial_json": input.to_string()},
+            }));
+            events.push(json!({"type": "content_block_stop", "index": index}));
+            index += 1;
+        }
+        events.push(json!({
+            "type": "message_delta",
+            "delta": {"stop_reason": self.stop_reason()},
+        }));
+        events.push(json!({"type": "message_stop"}));
+
+        events
+            .iter()
+            .map(|event| format!("data: {event}\n\n"))
+            .collect()
+    }
+}
+
+/// A transport that answers every request with the next scripted reply.
+///
+/// Clones share the same script, so a caller can keep one handle to queue
+/// replies while the session owns another. Once the script is used up, each
+/// request gets [`OFFLINE_NARRATION`].
+#[derive(Debug, Clone, Default)]
+pub struct OfflineTransport {
+    replies: Arc<Mutex<VecDeque<ScriptedReply>>>,
+    sent: Arc<Mutex<usize>>,
+}
+
+impl OfflineTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue the next reply.
+    pub fn push_reply(&self, reply: ScriptedReply) {
+        self.replies.lock().unwrap().push_back(reply);
+    }
+
+    /// Queue a reply that only narrates.
+    pub fn push_narration(&self, text: impl Into<String>) {
+        self.push_reply(ScriptedReply::narration(text));
+    }
+
+    /// Number of scripted replies not yet used.
+    pub fn remaining(&self) -> usize {
+        self.replies.lock().unwrap().len()
+    }
+}
+
+impl Transport for OfflineTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<RawResponse, Error>> {
+        let reply = self
+            .replies
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| ScriptedReply::narration(OFFLINE_NARRATION));
+        let id = {
+            let mut sent = self.sent.lock().unwrap();
+            *sent += 1;
+            format!("offline_{sent}")
+        };
+
+        let streaming = serde_json::from_str::<Value>(&request.body)
+            .ok()
+            .and_then(|body| body.get("stream")?.as_bool())
+            .unwrap_or(false);
+        let body = if streaming {
+            reply.to_event_stream(&id)
+        } else {
+            reply.to_message(&id)
+        };
+        Box::pin(async move { Ok(RawResponse::from_body(200, body)) })
+    }
+}
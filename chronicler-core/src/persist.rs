@@ -3,12 +3,17 @@
 //! Provides robust serialization of game state and DM memory,
 //! supporting both JSON (human-readable) and bincode (compact) formats.
 
+use crate::dice::DiceRoller;
 use crate::dm::memory::{CampaignFact, FactCategory};
-use crate::world::{Character, GameWorld};
+use crate::dm::StoryMemory;
+use crate::world::{Character, GameWorld, ValidationIssue};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::path::Path;
 use thiserror::Error;
 use tokio::fs;
+use tokio::io::AsyncWriteExt;
 
 /// Errors from persistence operations.
 #[derive(Debug, Error)]
@@ -24,6 +29,83 @@ pub enum PersistError {
 
     #[error("Version mismatch: expected {expected}, found {found}")]
     VersionMismatch { expected: u32, found: u32 },
+
+    #[error("Save file is corrupted: {0}")]
+    Corrupted(String),
+}
+
+/// Field holding the checksum of the rest of a save file.
+const CHECKSUM_FIELD: &str = "checksum";
+
+/// FNV-1a hash of `value`'s compact JSON, as hex.
+///
+/// This catches partial writes and hand edits; it is not meant to stop
+/// deliberate tampering.
+fn checksum(value: &Value) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in value.to_string().bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+/// Write `data` to `path` as pretty JSON with a checksum of its contents.
+///
+/// The file is written next to `path` first and then renamed over it, so a
+/// crash mid-save leaves the previous save intact.
+pub(crate) async fn write_checked<T: Serialize>(
+    path: impl AsRef<Path>,
+    data: &T,
+) -> Result<(), PersistError> {
+    let mut value = serde_json::to_value(data)?;
+    let sum = checksum(&value);
+    match value.as_object_mut() {
+        Some(object) => object.insert(CHECKSUM_FIELD.to_string(), Value::String(sum)),
+        None => return Err(PersistError::InvalidFormat),
+    };
+    let content = serde_json::to_string_pretty(&value)?;
+
+    let path = path.as_ref();
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut file = fs::File::create(&temp_path).await?;
+    file.write_all(content.as_bytes()).await?;
+    file.sync_all().await?;
+    drop(file);
+    fs::rename(&temp_path, path).await?;
+    Ok(())
+}
+
+/// Read a file written by [`write_checked`], verifying its checksum.
+///
+/// Files saved before checksums were added carry none and load unverified.
+pub(crate) async fn read_checked<T: DeserializeOwned>(
+    path: impl AsRef<Path>,
+) -> Result<T, PersistError> {
+    let content = fs::read_to_string(path).await?;
+    let mut value: Value = serde_json::from_str(&content).map_err(|e| {
+        if e.is_eof() {
+            PersistError::Corrupted("the file ends early, likely from an interrupted save".into())
+        } else {
+            PersistError::Json(e)
+        }
+    })?;
+
+    let stored = value
+        .as_object_mut()
+        .and_then(|object| object.remove(CHECKSUM_FIELD));
+    if let Some(stored) = stored {
+        if stored.as_str() != Some(checksum(&value).as_str()) {
+            return Err(PersistError::Corrupted(
+                "its checksum does not match; it was edited or only partly written".into(),
+            ));
+        }
+    }
+
+    Ok(serde_json::from_value(value)?)
 }
 
 /// Current save file version.
@@ -49,6 +131,15 @@ pub struct SavedCampaign {
 
     /// Metadata about the save.
     pub metadata: SaveMetadata,
+
+    /// The player's dice roller, saved mid-sequence so loading resumes the
+    /// same rolls.
+    #[serde(default, alias = "dice_roller")]
+    pub player_roller: Option<DiceRoller>,
+
+    /// The DM's dice roller, when it isn't the player's.
+    #[serde(default)]
+    pub dm_roller: Option<DiceRoller>,
 }
 
 /// Metadata about the save file.
@@ -72,6 +163,35 @@ pub struct SaveMetadata {
     /// When the save was created (duplicated from parent for peek access).
     #[serde(default)]
     pub saved_at: String,
+
+    /// Real time spent playing the campaign, in seconds.
+    #[serde(default)]
+    pub play_time_secs: u64,
+}
+
+impl SaveMetadata {
+    /// Metadata describing `world`, saved at `saved_at`.
+    pub fn for_world(world: &GameWorld, saved_at: String) -> Self {
+        Self {
+            character_name: world.player_character.name.clone(),
+            campaign_name: world.campaign_name.clone(),
+            level: world.player_character.level,
+            location: world.current_location.name.clone(),
+            days_elapsed: world.game_time.day as u32,
+            saved_at,
+            play_time_secs: 0,
+        }
+    }
+
+    /// Play time as hours and minutes, e.g. "2h 05m" or "12m".
+    pub fn play_time_display(&self) -> String {
+        let minutes = self.play_time_secs / 60;
+        if minutes < 60 {
+            format!("{minutes}m")
+        } else {
+            format!("{}h {:02}m", minutes / 60, minutes % 60)
+        }
+    }
 }
 
 impl SavedCampaign {
@@ -82,14 +202,7 @@ impl SavedCampaign {
         conversation_summary: Option<String>,
     ) -> Self {
         let saved_at = chrono_now();
-        let metadata = SaveMetadata {
-            character_name: world.player_character.name.clone(),
-            campaign_name: world.campaign_name.clone(),
-            level: world.player_character.level,
-            location: world.current_location.name.clone(),
-            days_elapsed: world.game_time.day as u32,
-            saved_at: saved_at.clone(),
-        };
+        let metadata = SaveMetadata::for_world(&world, saved_at.clone());
 
         Self {
             version: SAVE_VERSION,
@@ -98,20 +211,27 @@ impl SavedCampaign {
             campaign_facts,
             conversation_summary,
             metadata,
+            player_roller: None,
+            dm_roller: None,
         }
     }
 
+    /// Save the state of the dice rollers with the campaign. A `dm` of
+    /// `None` means the DM rolls on the player's roller.
+    pub fn with_dice_rollers(mut self, player: DiceRoller, dm: Option<DiceRoller>) -> Self {
+        self.player_roller = Some(player);
+        self.dm_roller = dm;
+        self
+    }
+
     /// Save to a JSON file.
     pub async fn save_json(&self, path: impl AsRef<Path>) -> Result<(), PersistError> {
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(path, content).await?;
-        Ok(())
+        write_checked(path, self).await
     }
 
     /// Load from a JSON file.
     pub async fn load_json(path: impl AsRef<Path>) -> Result<Self, PersistError> {
-        let content = fs::read_to_string(path).await?;
-        let saved: Self = serde_json::from_str(&content)?;
+        let saved: Self = read_checked(path).await?;
 
         if saved.version != SAVE_VERSION {
             return Err(PersistError::VersionMismatch {
@@ -123,6 +243,17 @@ impl SavedCampaign {
         Ok(saved)
     }
 
+    /// Load from a JSON file, repairing any impossible state in the world.
+    ///
+    /// Returns the loaded campaign along with the issues that were repaired.
+    pub async fn load_json_repaired(
+        path: impl AsRef<Path>,
+    ) -> Result<(Self, Vec<ValidationIssue>), PersistError> {
+        let mut saved = Self::load_json(path).await?;
+        let issues = saved.world.repair();
+        Ok((saved, issues))
+    }
+
     /// Check if a save file exists and get its metadata without loading the full state.
     pub async fn peek_metadata(path: impl AsRef<Path>) -> Result<SaveMetadata, PersistError> {
         let content = fs::read_to_string(path).await?;
@@ -147,6 +278,107 @@ impl SavedCampaign {
     }
 }
 
+/// Current campaign bundle version.
+const BUNDLE_VERSION: u32 = 1;
+
+/// A portable campaign bundle for moving a campaign between machines.
+///
+/// Unlike [`SavedCharacter`], this carries the whole campaign: the world,
+/// the player character, and the DM's story memory (entities, facts,
+/// consequences, and scheduled events) in a single versioned file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignBundle {
+    /// Bundle format version for compatibility checking.
+    pub version: u32,
+
+    /// When the bundle was exported.
+    pub exported_at: String,
+
+    /// The complete game world state, including the player character.
+    pub world: GameWorld,
+
+    /// The DM's story memory.
+    pub story_memory: StoryMemory,
+
+    /// Campaign facts from the DM's conversation memory.
+    pub campaign_facts: Vec<CampaignFact>,
+
+    /// Summary of the conversation for context restoration.
+    pub conversation_summary: Option<String>,
+
+    /// Metadata about the bundle.
+    pub metadata: SaveMetadata,
+}
+
+impl CampaignBundle {
+    /// Create a new bundle from game state.
+    pub fn new(
+        world: GameWorld,
+        story_memory: StoryMemory,
+        campaign_facts: Vec<CampaignFact>,
+        conversation_summary: Option<String>,
+    ) -> Self {
+        let exported_at = chrono_now();
+        let metadata = SaveMetadata::for_world(&world, exported_at.clone());
+
+        Self {
+            version: BUNDLE_VERSION,
+            exported_at,
+            world,
+            story_memory,
+            campaign_facts,
+            conversation_summary,
+            metadata,
+        }
+    }
+
+    /// Save to a JSON file.
+    pub async fn save_json(&self, path: impl AsRef<Path>) -> Result<(), PersistError> {
+        write_checked(path, self).await
+    }
+
+    /// Load from a JSON file.
+    pub async fn load_json(path: impl AsRef<Path>) -> Result<Self, PersistError> {
+        let bundle: Self = read_checked(path).await?;
+
+        if bundle.version != BUNDLE_VERSION {
+            return Err(PersistError::VersionMismatch {
+                expected: BUNDLE_VERSION,
+                found: bundle.version,
+            });
+        }
+
+        Ok(bundle)
+    }
+}
+
+/// Read just the metadata header of a campaign save, bundle, or session save.
+///
+/// The rest of the file (including the world) is skipped over rather than
+/// deserialized, so listing many saves stays cheap.
+pub async fn peek_save_metadata(path: impl AsRef<Path>) -> Result<SaveMetadata, PersistError> {
+    let content = fs::read_to_string(path).await?;
+
+    // Session saves carry no version; campaign saves and bundles do
+    #[derive(Deserialize)]
+    struct Partial {
+        #[serde(default)]
+        version: Option<u32>,
+        metadata: SaveMetadata,
+    }
+
+    let partial: Partial = serde_json::from_str(&content)?;
+
+    if let Some(found) = partial.version.filter(|&v| v != SAVE_VERSION) {
+        return Err(PersistError::VersionMismatch {
+            expected: SAVE_VERSION,
+            found,
+        });
+    }
+
+    Ok(partial.metadata)
+}
+
 /// List all save files in a directory.
 pub async fn list_saves(dir: impl AsRef<Path>) -> Result<Vec<SaveInfo>, PersistError> {
     let mut saves = Vec::new();
@@ -155,7 +387,7 @@ pub async fn list_saves(dir: impl AsRef<Path>) -> Result<Vec<SaveInfo>, PersistE
     while let Some(entry) = entries.next_entry().await? {
         let path = entry.path();
         if path.extension().map(|e| e == "json").unwrap_or(false) {
-            if let Ok(metadata) = SavedCampaign::peek_metadata(&path).await {
+            if let Ok(metadata) = peek_save_metadata(&path).await {
                 saves.push(SaveInfo {
                     path: path.to_string_lossy().to_string(),
                     metadata,
@@ -164,8 +396,13 @@ pub async fn list_saves(dir: impl AsRef<Path>) -> Result<Vec<SaveInfo>, PersistE
         }
     }
 
-    // Sort by modification time (most recent first)
-    saves.sort_by(|a, b| b.path.cmp(&a.path));
+    // Most recently saved first
+    saves.sort_by(|a, b| {
+        let saved_at = |info: &SaveInfo| info.metadata.saved_at.parse::<u64>().unwrap_or(0);
+        saved_at(b)
+            .cmp(&saved_at(a))
+            .then_with(|| b.path.cmp(&a.path))
+    });
     Ok(saves)
 }
 
@@ -201,7 +438,7 @@ pub fn manual_save_path(base_dir: impl AsRef<Path>, campaign_name: &str) -> std:
 }
 
 /// Get current timestamp as ISO 8601 string.
-fn chrono_now() -> String {
+pub(crate) fn chrono_now() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
 
     let now = SystemTime::now()
@@ -351,11 +588,16 @@ pub struct CharacterMetadata {
 
     /// Whether the character has a backstory.
     pub has_backstory: bool,
+
+    /// When the character was saved (duplicated from parent for peek access).
+    #[serde(default)]
+    pub saved_at: String,
 }
 
 impl SavedCharacter {
     /// Create a new saved character from a Character.
     pub fn new(character: Character) -> Self {
+        let saved_at = chrono_now();
         let metadata = CharacterMetadata {
             name: character.name.clone(),
             race: character.race.name.clone(),
@@ -366,11 +608,12 @@ impl SavedCharacter {
                 .unwrap_or_else(|| "Unknown".to_string()),
             level: character.level,
             has_backstory: character.backstory.is_some(),
+            saved_at: saved_at.clone(),
         };
 
         Self {
             version: CHARACTER_SAVE_VERSION,
-            saved_at: chrono_now(),
+            saved_at,
             character,
             metadata,
         }
@@ -378,15 +621,12 @@ impl SavedCharacter {
 
     /// Save to a JSON file.
     pub async fn save_json(&self, path: impl AsRef<Path>) -> Result<(), PersistError> {
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(path, content).await?;
-        Ok(())
+        write_checked(path, self).await
     }
 
     /// Load from a JSON file.
     pub async fn load_json(path: impl AsRef<Path>) -> Result<Self, PersistError> {
-        let content = fs::read_to_string(path).await?;
-        let saved: Self = serde_json::from_str(&content)?;
+        let saved: Self = read_checked(path).await?;
 
         if saved.version != CHARACTER_SAVE_VERSION {
             return Err(PersistError::VersionMismatch {
@@ -599,6 +839,84 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_dice_roller_resumes_sequence_after_load() {
+        use crate::dice::Advantage;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let save_path = temp_dir.path().join("seeded.json");
+
+        let roll_some = |roller: &mut DiceRoller| -> Vec<i32> {
+            vec![
+                roller.roll("4d6kh3").unwrap().total,
+                roller
+                    .roll_with_advantage("1d20+5", Advantage::Disadvantage)
+                    .unwrap()
+                    .total,
+                roller.roll("8d6").unwrap().total,
+            ]
+        };
+
+        let mut continuous = DiceRoller::new(1234);
+        let before = roll_some(&mut continuous);
+        let after = roll_some(&mut continuous);
+
+        let mut roller = DiceRoller::new(1234);
+        assert_eq!(roll_some(&mut roller), before);
+        let world = GameWorld::new("Seeded", create_sample_fighter("Roland"));
+        SavedCampaign::new(world, Vec::new(), None)
+            .with_dice_rollers(roller, None)
+            .save_json(&save_path)
+            .await
+            .expect("Save should succeed");
+
+        let loaded = SavedCampaign::load_json(&save_path)
+            .await
+            .expect("Load should succeed");
+        assert!(loaded.dm_roller.is_none());
+        let mut restored = loaded
+            .player_roller
+            .clone()
+            .expect("roller should be saved");
+        assert_eq!(restored.seed(), 1234);
+        assert_eq!(roll_some(&mut restored), after);
+
+        // Saves from before the DM had its own roller name it dice_roller
+        let mut old = serde_json::to_value(&loaded).unwrap();
+        let object = old.as_object_mut().unwrap();
+        let roller = object.remove("player_roller").unwrap();
+        object.remove("dm_roller");
+        object.insert("dice_roller".to_string(), roller);
+        let old: SavedCampaign = serde_json::from_value(old).unwrap();
+        assert_eq!(old.player_roller.unwrap().seed(), 1234);
+    }
+
+    #[tokio::test]
+    async fn test_load_json_repaired_clamps_invalid_state() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let save_path = temp_dir.path().join("drifted.json");
+
+        let mut character = create_sample_fighter("Drifted");
+        character.hit_points.current = character.hit_points.maximum + 10;
+        let maximum = character.hit_points.maximum;
+        let world = GameWorld::new("Test Campaign", character);
+        SavedCampaign::new(world, Vec::new(), None)
+            .save_json(&save_path)
+            .await
+            .expect("Save should succeed");
+
+        let (loaded, issues) = SavedCampaign::load_json_repaired(&save_path)
+            .await
+            .expect("Load should succeed");
+
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(issues[0], ValidationIssue::HpAboveMaximum { .. }));
+        assert_eq!(loaded.world.player_character.hit_points.current, maximum);
+    }
+
     #[tokio::test]
     async fn test_peek_character_metadata() {
         use tempfile::TempDir;
@@ -670,4 +988,85 @@ mod tests {
         assert!(saves.is_empty());
         assert!(char_dir.exists()); // Directory should be created
     }
+
+    #[tokio::test]
+    async fn test_list_saves_reads_metadata_without_loading_worlds() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let world = GameWorld::new("Dragon's Lair", create_sample_fighter("Roland"));
+        let mut saved = SavedCampaign::new(world, Vec::new(), None);
+        saved.metadata.saved_at = "100".to_string();
+        saved
+            .save_json(temp_dir.path().join("campaign.json"))
+            .await
+            .unwrap();
+
+        // A world this broken would fail to load, so listing must not load it.
+        let header_only = serde_json::json!({
+            "metadata": {
+                "character_name": "Vex",
+                "campaign_name": "Sunken Keep",
+                "level": 4,
+                "location": "Flooded Crypt",
+                "days_elapsed": 3,
+                "saved_at": "200",
+                "play_time_secs": 7500
+            },
+            "world": {"not": "a world"}
+        });
+        std::fs::write(
+            temp_dir.path().join("session.json"),
+            header_only.to_string(),
+        )
+        .unwrap();
+
+        // Character saves have a different header and are left out.
+        SavedCharacter::new(create_sample_fighter("Lone Hero"))
+            .save_json(temp_dir.path().join("character.json"))
+            .await
+            .unwrap();
+
+        let saves = list_saves(temp_dir.path()).await.unwrap();
+        assert_eq!(saves.len(), 2);
+
+        let newest = &saves[0].metadata;
+        assert_eq!(newest.campaign_name, "Sunken Keep");
+        assert_eq!(newest.character_name, "Vex");
+        assert_eq!(newest.level, 4);
+        assert_eq!(newest.play_time_display(), "2h 05m");
+
+        let older = &saves[1].metadata;
+        assert_eq!(older.campaign_name, "Dragon's Lair");
+        assert_eq!(older.play_time_secs, 0);
+        assert_eq!(older.play_time_display(), "0m");
+    }
+
+    #[tokio::test]
+    async fn test_tampered_save_is_detected() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("campaign.json");
+        let world = GameWorld::new("Dragon's Lair", create_sample_fighter("Roland"));
+        SavedCampaign::new(world, Vec::new(), None)
+            .save_json(&path)
+            .await
+            .unwrap();
+        // Written through a temp file that is renamed into place
+        assert!(!temp_dir.path().join("campaign.json.tmp").exists());
+        assert!(SavedCampaign::load_json(&path).await.is_ok());
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, content.replacen("Roland", "Rolund", 1)).unwrap();
+        let err = SavedCampaign::load_json(&path).await.unwrap_err();
+        assert!(matches!(err, PersistError::Corrupted(_)));
+        assert!(err.to_string().contains("checksum does not match"));
+
+        std::fs::write(&path, &content[..content.len() / 2]).unwrap();
+        assert!(matches!(
+            SavedCampaign::load_json(&path).await,
+            Err(PersistError::Corrupted(_))
+        ));
+    }
 }
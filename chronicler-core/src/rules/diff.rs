@@ -0,0 +1,216 @@
+//! Building a [`WorldDiff`] from the effects a turn applied.
+
+use crate::rules::effects::EffectOutcome;
+use crate::rules::types::Effect;
+use crate::world::{CharacterId, HpDelta, InventoryDelta, WorldDiff};
+
+impl WorldDiff {
+    /// Summarize `effects`, in the order they were applied.
+    pub fn from_effects(effects: &[Effect]) -> Self {
+        let mut diff = Self::default();
+        for effect in effects {
+            diff.record(effect);
+        }
+        diff
+    }
+
+    /// Summarize the `effects` that took hold, given the outcome of applying
+    /// each. Effects that were skipped changed nothing and are left out.
+    pub fn from_outcomes(effects: &[Effect], outcomes: &[EffectOutcome]) -> Self {
+        let mut diff = Self::default();
+        for (i, effect) in effects.iter().enumerate() {
+            if !outcomes.get(i).is_some_and(EffectOutcome::is_skipped) {
+                diff.record(effect);
+            }
+        }
+        diff
+    }
+
+    fn record(&mut self, effect: &Effect) {
+        match effect {
+            Effect::HpChanged {
+                target_id,
+                amount,
+                new_current,
+                ..
+            } => self.record_hp(*target_id, *amount, *new_current),
+            Effect::CombatantHpChanged {
+                id,
+                amount,
+                new_current,
+                ..
+            } => self.record_hp(*id, *amount, *new_current),
+            Effect::ConditionApplied {
+                target_id,
+                condition,
+                ..
+            } => {
+                let change = (*target_id, *condition);
+                if !self.conditions_added.contains(&change) {
+                    self.conditions_added.push(change);
+                }
+            }
+            Effect::ConditionRemoved {
+                target_id,
+                condition,
+            } => {
+                let change = (*target_id, *condition);
+                if !self.conditions_removed.contains(&change) {
+                    self.conditions_removed.push(change);
+                }
+            }
+            Effect::ConcentrationStarted {
+                character_id,
+                spell_name,
+            } => {
+                self.concentration_started
+                    .retain(|(id, _)| id != character_id);
+                self.concentration_started
+                    .push((*character_id, spell_name.clone()));
+            }
+            Effect::ConcentrationBroken { character_id, .. } => {
+                self.concentration_started
+                    .retain(|(id, _)| id != character_id);
+                if !self.concentration_ended.contains(character_id) {
+                    self.concentration_ended.push(*character_id);
+                }
+            }
+            Effect::ItemAdded {
+                item_name,
+                quantity,
+                ..
+            } => self.record_item(item_name, *quantity as i32),
+            Effect::ItemRemoved {
+                item_name,
+                quantity,
+                ..
+            } => self.record_item(item_name, -(*quantity as i32)),
+            Effect::AmmunitionSpent { ammunition, .. } => self.record_item(ammunition, -1),
+            Effect::LocationChanged { new_location, .. } => {
+                self.location = Some(new_location.clone());
+            }
+            Effect::QuestCreated { name, .. } => self.new_quests.push(name.clone()),
+            _ => {}
+        }
+    }
+
+    fn record_hp(&mut self, target_id: CharacterId, amount: i32, current: i32) {
+        match self
+            .hp
+            .iter_mut()
+            .find(|delta| delta.target_id == target_id)
+        {
+            Some(delta) => {
+                delta.amount += amount;
+                delta.current = current;
+            }
+            None => self.hp.push(HpDelta {
+                target_id,
+                amount,
+                current,
+            }),
+        }
+    }
+
+    fn record_item(&mut self, item_name: &str, quantity: i32) {
+        match self
+            .inventory
+            .iter_mut()
+            .find(|delta| delta.item_name.eq_ignore_ascii_case(item_name))
+        {
+            Some(delta) => delta.quantity += quantity,
+            None => self.inventory.push(InventoryDelta {
+                item_name: item_name.to_string(),
+                quantity,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damage_and_item_turn_diff() {
+        let hero = CharacterId::new();
+        let effects = vec![
+            Effect::HpChanged {
+                target_id: hero,
+                amount: -7,
+                new_current: 5,
+                new_max: 12,
+                dropped_to_zero: false,
+            },
+            Effect::TimeAdvanced { minutes: 1 },
+            Effect::ItemAdded {
+                item_name: "Rope".to_string(),
+                quantity: 1,
+                new_total: 1,
+                item: None,
+            },
+        ];
+
+        let diff = WorldDiff::from_effects(&effects);
+
+        assert_eq!(
+            diff,
+            WorldDiff {
+                hp: vec![HpDelta {
+                    target_id: hero,
+                    amount: -7,
+                    current: 5,
+                }],
+                inventory: vec![InventoryDelta {
+                    item_name: "Rope".to_string(),
+                    quantity: 1,
+                }],
+                ..WorldDiff::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_repeated_changes_are_netted() {
+        let hero = CharacterId::new();
+        let hit = |amount, new_current| Effect::HpChanged {
+            target_id: hero,
+            amount,
+            new_current,
+            new_max: 12,
+            dropped_to_zero: false,
+        };
+
+        let diff = WorldDiff::from_effects(&[hit(-4, 8), hit(2, 10)]);
+
+        assert_eq!(diff.hp.len(), 1);
+        assert_eq!(diff.hp[0].amount, -2);
+        assert_eq!(diff.hp[0].current, 10);
+        assert!(WorldDiff::from_effects(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_skipped_effects_are_left_out() {
+        let rope = |quantity| Effect::ItemRemoved {
+            item_name: "Rope".to_string(),
+            quantity,
+            remaining: 0,
+        };
+        let outcomes = [
+            EffectOutcome::Skipped {
+                reason: "not carrying 5 Rope".to_string(),
+            },
+            EffectOutcome::Applied,
+        ];
+
+        let diff = WorldDiff::from_outcomes(&[rope(5), rope(1)], &outcomes);
+
+        assert_eq!(
+            diff.inventory,
+            vec![InventoryDelta {
+                item_name: "Rope".to_string(),
+                quantity: -1,
+            }]
+        );
+    }
+}
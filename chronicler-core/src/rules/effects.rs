@@ -2,19 +2,72 @@
 
 use crate::rules::types::{Effect, RestType, StateType};
 use crate::world::{
-    Ability, CharacterClass, Combatant, Condition, GameWorld, Item, ItemType, SlotInfo, SpellSlots,
-    SpellcastingData,
+    Ability, AcBonus, ActiveCondition, ClassResources, Combatant, Concentration, Condition,
+    Feature, FeatureUses, GameWorld, Item, ItemType, NarrativeType, RechargeType, SlotInfo,
+    SpeedBonus, SpellSlots, SpellcastingData,
 };
 
-/// Apply effects to the game world.
-pub fn apply_effects(world: &mut GameWorld, effects: &[Effect]) {
-    for effect in effects {
-        apply_effect(world, effect);
+/// What applying an effect actually did to the world.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EffectOutcome {
+    /// The effect changed the world as described.
+    Applied,
+    /// The effect was rejected and the world is unchanged.
+    Skipped { reason: String },
+    /// The effect was applied, but limited to what the world allowed.
+    Clamped { reason: String },
+}
+
+impl EffectOutcome {
+    fn skipped(reason: impl Into<String>) -> Self {
+        EffectOutcome::Skipped {
+            reason: reason.into(),
+        }
+    }
+
+    fn clamped(reason: impl Into<String>) -> Self {
+        EffectOutcome::Clamped {
+            reason: reason.into(),
+        }
+    }
+
+    pub fn is_applied(&self) -> bool {
+        matches!(self, EffectOutcome::Applied)
+    }
+
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, EffectOutcome::Skipped { .. })
     }
 }
 
+/// `narrative` followed by a line for each effect that didn't apply as
+/// resolved, so the DM narrates what actually happened.
+pub fn narrate_outcomes(narrative: &str, outcomes: &[EffectOutcome]) -> String {
+    let mut text = narrative.to_string();
+    for outcome in outcomes {
+        match outcome {
+            EffectOutcome::Applied => {}
+            EffectOutcome::Skipped { reason } => {
+                text.push_str(&format!("\n(Not applied: {reason})"));
+            }
+            EffectOutcome::Clamped { reason } => {
+                text.push_str(&format!("\n(Only partly applied: {reason})"));
+            }
+        }
+    }
+    text
+}
+
+/// Apply effects to the game world, returning one outcome per effect.
+pub fn apply_effects(world: &mut GameWorld, effects: &[Effect]) -> Vec<EffectOutcome> {
+    effects
+        .iter()
+        .map(|effect| apply_effect(world, effect))
+        .collect()
+}
+
 /// Apply a single effect to the game world.
-pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
+pub fn apply_effect(world: &mut GameWorld, effect: &Effect) -> EffectOutcome {
     match effect {
         Effect::HpChanged {
             amount,
@@ -34,6 +87,13 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                 world
                     .player_character
                     .add_condition(Condition::Unconscious, "Dropped to 0 HP");
+                // Falling unconscious ends concentration outright
+                world.player_character.end_concentration();
+            } else if *amount < 0 {
+                // Each hit calls for its own concentration save
+                if let Some(ref mut concentration) = world.player_character.concentration {
+                    concentration.pending_damage.push(-*amount);
+                }
             }
 
             // Remove Unconscious condition and reset death saves if healed above 0
@@ -50,32 +110,111 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
             if let Some(ref mut combat) = world.combat {
                 let player_id = world.player_character.id;
                 combat.update_combatant_hp(player_id, world.player_character.hit_points.current);
+                if *amount < 0 {
+                    combat.damage_taken += -*amount;
+                }
             }
         }
         Effect::ConditionApplied {
             condition,
             source,
             duration_rounds,
+            timing,
             ..
         } => {
-            world.player_character.add_condition_with_duration(
-                *condition,
-                source.clone(),
-                *duration_rounds,
-            );
+            let character = &mut world.player_character;
+            if let Some(reason) = character.condition_immunity(*condition, source) {
+                return EffectOutcome::skipped(format!("{} is {reason}", character.name));
+            }
+            let mut active = ActiveCondition::new(*condition, source.clone()).with_timing(*timing);
+            if let Some(duration) = duration_rounds {
+                active = active.with_duration(*duration);
+            }
+            if !character.add_active_condition(active) {
+                return EffectOutcome::skipped(format!(
+                    "{} is already {}",
+                    character.name,
+                    condition.name()
+                ));
+            }
         }
         Effect::ConditionRemoved { condition, .. } => {
+            if !world.player_character.has_condition(*condition) {
+                return EffectOutcome::skipped(format!(
+                    "{} is not {}",
+                    world.player_character.name,
+                    condition.name()
+                ));
+            }
             world
                 .player_character
                 .conditions
                 .retain(|c| c.condition != *condition);
         }
+        Effect::AcBonusApplied {
+            source,
+            bonus,
+            duration_rounds,
+            timing,
+            ..
+        } => {
+            let mut ac_bonus = AcBonus::new(source.clone(), *bonus);
+            if let Some(duration) = duration_rounds {
+                ac_bonus = ac_bonus.with_duration(*duration, *timing);
+            }
+            // The same source doesn't stack; recasting refreshes it
+            let bonuses = &mut world.player_character.ac_bonuses;
+            bonuses.retain(|b| b.source != *source);
+            bonuses.push(ac_bonus);
+        }
+        Effect::AcBonusEnded { source, .. } => {
+            world
+                .player_character
+                .ac_bonuses
+                .retain(|b| b.source != *source);
+        }
+        Effect::SpeedBonusApplied { source, feet, .. } => {
+            // The same source doesn't stack; recasting refreshes it
+            let bonuses = &mut world.player_character.speed_bonuses;
+            bonuses.retain(|b| b.source != *source);
+            bonuses.push(SpeedBonus {
+                source: source.clone(),
+                feet: *feet,
+            });
+        }
+        Effect::SpeedBonusEnded { source, .. } => {
+            world
+                .player_character
+                .speed_bonuses
+                .retain(|b| b.source != *source);
+        }
         Effect::CombatStarted => {
             world.start_combat();
         }
-        Effect::CombatEnded => {
+        Effect::CombatEnded { summary } => {
+            if let Some(summary) = summary {
+                world.add_narrative(summary.describe(), NarrativeType::System);
+            }
             world.end_combat();
         }
+        Effect::AmmunitionSpent {
+            ammunition,
+            recoverable,
+            ..
+        } => {
+            world
+                .player_character
+                .inventory
+                .spend_ammunition(ammunition);
+            if *recoverable {
+                if let Some(ref mut combat) = world.combat {
+                    *combat
+                        .recoverable_ammunition
+                        .entry(ammunition.clone())
+                        .or_insert(0) += 1;
+                }
+            }
+        }
         Effect::CombatantAdded {
             id,
             name,
@@ -84,6 +223,7 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
             current_hp,
             max_hp,
             armor_class,
+            resistances,
         } => {
             if let Some(ref mut combat) = world.combat {
                 combat.add_combatant(Combatant {
@@ -95,33 +235,47 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                     current_hp: *current_hp,
                     max_hp: *max_hp,
                     armor_class: *armor_class,
+                    resistances: resistances.clone(),
                 });
             }
         }
+        Effect::CombatantHpChanged {
+            id, new_current, ..
+        } => {
+            if let Some(ref mut combat) = world.combat {
+                combat.record_combatant_hp(*id, *new_current);
+            }
+        }
         Effect::TurnAdvanced { .. } => {
             if let Some(ref mut combat) = world.combat {
+                // Timed conditions count down at the end or start of the
+                // player's own turn, whichever they are tied to
+                for moment in combat.player_turn_moments() {
+                    let player = &mut world.player_character;
+                    player.conditions.retain_mut(|c| c.tick(moment));
+                    player.ac_bonuses.retain_mut(|b| b.tick(moment));
+                }
                 combat.next_turn();
             }
-
-            // Decrement condition durations and remove expired conditions
-            world.player_character.conditions.retain_mut(|c| {
-                if let Some(ref mut duration) = c.duration_rounds {
-                    if *duration > 0 {
-                        *duration -= 1;
-                    }
-                    *duration > 0 // Keep only if duration remaining
-                } else {
-                    true // Keep permanent conditions
-                }
-            });
         }
         Effect::TimeAdvanced { minutes } => {
-            world.game_time.advance_minutes(*minutes);
+            // Bonuses measured in rounds are long gone after a minute
+            if *minutes > 0 {
+                world
+                    .player_character
+                    .ac_bonuses
+                    .retain(|b| b.duration_rounds.is_none());
+            }
+            world.advance_time(*minutes);
+            world.follow_npc_schedules();
+        }
+        Effect::RestCompleted { rest_type } => {
+            match rest_type {
+                RestType::Short => world.short_rest(),
+                RestType::Long => world.long_rest(),
+            }
+            world.follow_npc_schedules();
         }
-        Effect::RestCompleted { rest_type } => match rest_type {
-            RestType::Short => world.short_rest(),
-            RestType::Long => world.long_rest(),
-        },
         Effect::ExperienceGained { amount, .. } => {
             world.player_character.experience += amount;
         }
@@ -133,6 +287,7 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
             // Get the primary class for level-up calculations
             if let Some(class_level) = character.classes.first_mut() {
                 let class = class_level.class;
+                let subclass = class_level.subclass;
                 let hit_die = class.hit_die();
 
                 // Update class level
@@ -152,6 +307,7 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                 // Update spell slots for spellcasters
                 if let Some(spellcasting_ability) = class.spellcasting_ability() {
                     let new_slots = class.spell_slots_at_level(*new_level);
+                    let new_pact = class.pact_slots_at_level(*new_level);
 
                     if let Some(ref mut spellcasting) = character.spellcasting {
                         for (i, &total) in new_slots.iter().enumerate() {
@@ -166,11 +322,21 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                                         .saturating_sub(gained);
                             }
                         }
-                    } else if new_slots.iter().any(|&s| s > 0) {
+                        if let Some(mut pact) = new_pact {
+                            // Slots gained on level up are fresh
+                            if let Some(old) = spellcasting.pact_slots {
+                                pact.used = old
+                                    .used
+                                    .saturating_sub(pact.total.saturating_sub(old.total));
+                            }
+                            spellcasting.pact_slots = Some(pact);
+                        }
+                    } else if new_slots.iter().any(|&s| s > 0) || new_pact.is_some() {
                         // Class just gained spellcasting (e.g., Paladin/Ranger at level 2)
                         character.spellcasting = Some(SpellcastingData {
                             ability: spellcasting_ability,
                             spells_known: Vec::new(),
+                            spellbook: Vec::new(),
                             spells_prepared: Vec::new(),
                             cantrips_known: Vec::new(),
                             spell_slots: SpellSlots {
@@ -179,6 +345,7 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                                     used: 0,
                                 }),
                             },
+                            pact_slots: new_pact,
                         });
                     }
 
@@ -188,78 +355,103 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                     let new_cantrips = class.cantrips_known_at_level(*new_level);
                     let _cantrips_gained = new_cantrips.saturating_sub(old_cantrips);
 
-                    // For "known" casters, track new spell capacity
-                    if let Some(old_known) = class.spells_known_at_level(old_level) {
-                        if let Some(new_known) = class.spells_known_at_level(*new_level) {
-                            let _spells_gained = new_known.saturating_sub(old_known);
-                            // Player can now learn more spells up to new_known total
-                        }
+                    // "Known" casters learn new spells up to spells_known_at_level
+                    // with learn_spell, and may swap one known spell when they
+                    // gain a level; an unused swap doesn't carry over
+                    if class.spells_known_at_level(*new_level).is_some() && *new_level > old_level {
+                        character.spell_swaps = 1;
                     }
 
-                    // For Wizard, track spellbook expansion
-                    let _wizard_spells_added = class.wizard_spellbook_spells_at_level(*new_level);
+                    // Wizards add spells of their choice to the spellbook
+                    // for each level gained, free of cost
+                    let free_spells: usize = (old_level + 1..=*new_level)
+                        .map(|level| class.wizard_spellbook_spells_at_level(level))
+                        .sum();
+                    character.free_spellbook_spells = character
+                        .free_spellbook_spells
+                        .saturating_add(free_spells.min(u8::MAX as usize) as u8);
                 }
 
                 // Update class resources based on class and level
-                match class {
-                    CharacterClass::Monk => {
-                        // Ki points = Monk level
-                        character.class_resources.max_ki_points = *new_level;
-                        character.class_resources.ki_points = *new_level;
-                    }
-                    CharacterClass::Sorcerer => {
-                        // Sorcery points = Sorcerer level (gained at level 2)
-                        if *new_level >= 2 {
-                            character.class_resources.max_sorcery_points = *new_level;
-                            // Give the new points
-                            let gained = *new_level - old_level;
-                            character.class_resources.sorcery_points =
-                                (character.class_resources.sorcery_points + gained)
-                                    .min(character.class_resources.max_sorcery_points);
-                        }
-                    }
-                    CharacterClass::Paladin => {
-                        // Lay on Hands pool = 5 × Paladin level
-                        character.class_resources.lay_on_hands_max = (*new_level as u32) * 5;
-                        // Restore to full on level up
-                        character.class_resources.lay_on_hands_pool =
-                            character.class_resources.lay_on_hands_max;
+                character
+                    .class_resources
+                    .recompute_for(class, old_level, *new_level);
+
+                // Limited-use features whose use count scales with level
+                if let Some((feature_name, maximum)) =
+                    ClassResources::feature_uses_at(class, *new_level)
+                {
+                    if let Some(uses) = character
+                        .features
+                        .iter_mut()
+                        .find(|f| f.name == feature_name)
+                        .and_then(|f| f.uses.as_mut())
+                    {
+                        let gained = maximum.saturating_sub(uses.maximum);
+                        uses.maximum = maximum;
+                        uses.current = uses.current.saturating_add(gained).min(maximum);
                     }
-                    CharacterClass::Barbarian => {
-                        // Rage uses increase at certain levels
-                        let rage_uses = match *new_level {
-                            1..=2 => 2,
-                            3..=5 => 3,
-                            6..=11 => 4,
-                            12..=16 => 5,
-                            17..=19 => 6,
-                            20 => u8::MAX, // Unlimited at 20
-                            _ => 2,
-                        };
-                        // Update rage feature uses
-                        if let Some(rage_feature) =
-                            character.features.iter_mut().find(|f| f.name == "Rage")
-                        {
-                            if let Some(ref mut uses) = rage_feature.uses {
-                                uses.maximum = rage_uses;
-                                uses.current = rage_uses;
+                }
+
+                // Features from the chosen subclass for each level gained
+                if let Some(subclass) = subclass {
+                    for level in (old_level + 1)..=*new_level {
+                        for feature in subclass.features_at_level(level) {
+                            if !character.features.iter().any(|f| f.name == feature.name) {
+                                character.features.push(feature.to_feature(subclass));
                             }
                         }
-                        // Rage damage bonus increases at levels 9 and 16
-                        character.class_resources.rage_damage_bonus = match *new_level {
-                            1..=8 => 2,
-                            9..=15 => 3,
-                            16..=20 => 4,
-                            _ => 2,
-                        };
                     }
-                    _ => {}
                 }
             }
         }
+        Effect::AsiAvailable { .. } => {
+            world.player_character.asi_points = world.player_character.asi_points.saturating_add(2);
+        }
+        Effect::AbilityScoreImproved {
+            ability,
+            amount,
+            new_score,
+        } => {
+            let character = &mut world.player_character;
+            character.ability_scores.set(*ability, *new_score);
+            character.asi_points = character.asi_points.saturating_sub(*amount);
+        }
+        Effect::FeatChosen {
+            feat_name,
+            description,
+        } => {
+            let character = &mut world.player_character;
+            character.asi_points = character.asi_points.saturating_sub(2);
+            // Resilient (Ability): +1 to the ability and proficiency in its saves
+            if let Some(ability) = resilient_ability(feat_name) {
+                let score = character.ability_scores.get(ability);
+                character.ability_scores.set(ability, (score + 1).min(20));
+                character.saving_throw_proficiencies.insert(ability);
+            }
+            character.features.push(Feature {
+                name: feat_name.clone(),
+                description: description.clone(),
+                source: "Feat".to_string(),
+                uses: feat_uses(feat_name),
+            });
+        }
+        Effect::HelpGiven { helper, .. } => {
+            world.player_character.helped_by = Some(helper.clone());
+        }
+        Effect::HelpUsed { .. } => {
+            world.player_character.helped_by = None;
+        }
+        Effect::FightingStyleChosen { style } => {
+            world.player_character.fighting_styles.insert(*style);
+        }
         Effect::FeatureUsed {
             feature_name,
             uses_remaining,
+        }
+        | Effect::FeatureRecharged {
+            feature_name,
+            uses_remaining,
         } => {
             if let Some(feature) = world
                 .player_character
@@ -273,14 +465,34 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
             }
         }
         Effect::SpellSlotUsed { level, .. } => {
-            if let Some(ref mut spellcasting) = world.player_character.spellcasting {
-                spellcasting.spell_slots.use_slot(*level);
+            let used = world
+                .player_character
+                .spellcasting
+                .as_mut()
+                .is_some_and(|sc| sc.spell_slots.use_slot(*level));
+            if !used {
+                return EffectOutcome::skipped(format!("no level {level} spell slot left"));
+            }
+        }
+        Effect::PactSlotUsed { .. } => {
+            if let Some(pact) = world
+                .player_character
+                .spellcasting
+                .as_mut()
+                .and_then(|sc| sc.pact_slots.as_mut())
+            {
+                if !pact.use_slot() {
+                    return EffectOutcome::skipped("no pact slot left");
+                }
+            } else {
+                return EffectOutcome::skipped("no pact slots");
             }
         }
         // Effects that don't modify state (informational)
         Effect::DiceRolled { .. } => {}
         Effect::CheckSucceeded { .. } => {}
         Effect::CheckFailed { .. } => {}
+        Effect::GroupCheckResolved { .. } => {}
         Effect::AttackHit { .. } => {}
         Effect::AttackMissed { .. } => {}
         Effect::InitiativeRolled { .. } => {}
@@ -290,6 +502,31 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                 combat.sneak_attack_used.insert(*character_id);
             }
         }
+        Effect::ActionUsed {
+            character_id,
+            action,
+            spell_level,
+        } => {
+            if let Some(ref mut combat) = world.combat {
+                combat
+                    .actions_this_turn
+                    .entry(*character_id)
+                    .or_default()
+                    .spend(*action, *spell_level);
+            }
+        }
+        Effect::AttackMade { character_id } => {
+            if let Some(ref mut combat) = world.combat {
+                let made = combat.attacks_this_turn.entry(*character_id).or_insert(0);
+                *made = made.saturating_add(1);
+            }
+        }
+        Effect::ExtraActionGained { character_id } => {
+            if let Some(ref mut combat) = world.combat {
+                let turn = combat.actions_this_turn.entry(*character_id).or_default();
+                turn.extra_actions = turn.extra_actions.saturating_add(1);
+            }
+        }
         // FactRemembered is handled by the DM agent's memory system, not world state
         Effect::FactRemembered { .. } => {}
 
@@ -297,10 +534,15 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
         Effect::ItemAdded {
             item_name,
             quantity,
+            item,
             ..
         } => {
-            // Try to look up item from standard database first
-            let item = if let Some(standard_item) = crate::items::find_item(item_name) {
+            // Use the stats carried by the effect, then the standard database
+            let item = if let Some(item) = item {
+                let mut item = item.clone();
+                item.quantity = *quantity;
+                item
+            } else if let Some(standard_item) = crate::items::find_item(item_name) {
                 let mut item = standard_item.as_item();
                 item.quantity = *quantity;
                 item
@@ -323,12 +565,18 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
             quantity,
             ..
         } => {
-            world
+            let removed = world
                 .player_character
                 .inventory
                 .remove_item(item_name, *quantity);
+            if !removed {
+                return EffectOutcome::skipped(format!("not carrying {quantity} {item_name}"));
+            }
         }
         Effect::ItemEquipped { item_name, slot } => {
+            if matches!(slot.as_str(), "armor" | "shield") {
+                world.player_character.ac_override = None;
+            }
             // Look up item from database for proper stats, fall back to defaults
             match slot.as_str() {
                 "armor" => {
@@ -394,11 +642,13 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
         }
         Effect::ItemUnequipped { slot, .. } => match slot.as_str() {
             "armor" => {
+                world.player_character.ac_override = None;
                 if let Some(armor) = world.player_character.equipment.armor.take() {
                     world.player_character.inventory.add_item(armor.base);
                 }
             }
             "shield" => {
+                world.player_character.ac_override = None;
                 if let Some(shield) = world.player_character.equipment.shield.take() {
                     world.player_character.inventory.add_item(shield);
                 }
@@ -436,10 +686,20 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
             world.player_character.death_saves.reset();
         }
 
+        Effect::DeathSavesSet {
+            successes,
+            failures,
+            ..
+        } => {
+            world.player_character.death_saves.successes = *successes;
+            world.player_character.death_saves.failures = *failures;
+        }
+
         Effect::CharacterDied { .. } => {
             // Character death is tracked via the effect itself
             // The UI/game can check for this effect and handle appropriately
             // For now, we don't modify world state further (could add a `dead: bool` flag)
+            world.player_character.end_concentration();
         }
 
         Effect::DeathSaveSuccess {
@@ -454,16 +714,38 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
             // Note: Character remains Unconscious until healed
         }
 
+        Effect::ConcentrationStarted { spell_name, .. } => {
+            // Starting a new concentration spell ends any previous one
+            world.player_character.end_concentration();
+            world.player_character.concentration = Some(Concentration::new(spell_name.clone()));
+        }
+
         Effect::ConcentrationBroken { .. } => {
-            // Concentration tracking would be handled here if we had it
-            // For now, this is informational for the UI/narrative
+            world.player_character.end_concentration();
         }
 
         Effect::ConcentrationMaintained { .. } => {
-            // Informational - concentration continues
+            // One pending save has been made
+            if let Some(ref mut concentration) = world.player_character.concentration {
+                if !concentration.pending_damage.is_empty() {
+                    concentration.pending_damage.remove(0);
+                }
+            }
         }
         Effect::LocationChanged { new_location, .. } => {
             world.current_location.name = new_location.clone();
+            // A new place has its own lighting
+            world.current_location.light_level = None;
+        }
+        Effect::LightLevelChanged { light_level } => {
+            if let Some(ref mut combat) = world.combat {
+                combat.light_level = *light_level;
+            } else {
+                world.current_location.light_level = *light_level;
+                if let Some(known) = world.known_locations.get_mut(&world.current_location.id) {
+                    known.light_level = *light_level;
+                }
+            }
         }
         Effect::ConsequenceRegistered { .. } => {
             // Consequence storage is handled by the DM agent in story_memory
@@ -489,6 +771,40 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
             world.player_character.class_resources.rage_damage_bonus = 0;
             world.player_character.class_resources.rage_rounds_remaining = None;
         }
+        Effect::WildShapeStarted { stats, .. } => {
+            let resources = &mut world.player_character.class_resources;
+            resources.wild_shape_form = Some(stats.name.clone());
+            resources.wild_shape_hp = Some(stats.hit_points.current);
+            resources.wild_shape_stats = Some(stats.clone());
+
+            if let Some(ref mut combat) = world.combat {
+                let player_id = world.player_character.id;
+                combat.update_combatant_hp(player_id, stats.hit_points.current);
+            }
+        }
+        Effect::WildShapeHpChanged { new_current, .. } => {
+            let resources = &mut world.player_character.class_resources;
+            if let Some(ref mut stats) = resources.wild_shape_stats {
+                stats.hit_points.current = (*new_current).clamp(0, stats.hit_points.maximum);
+                resources.wild_shape_hp = Some(stats.hit_points.current);
+
+                if let Some(ref mut combat) = world.combat {
+                    let player_id = world.player_character.id;
+                    combat.update_combatant_hp(player_id, stats.hit_points.current);
+                }
+            }
+        }
+        Effect::WildShapeEnded { .. } => {
+            let resources = &mut world.player_character.class_resources;
+            resources.wild_shape_form = None;
+            resources.wild_shape_hp = None;
+            resources.wild_shape_stats = None;
+
+            if let Some(ref mut combat) = world.combat {
+                let player_id = world.player_character.id;
+                combat.update_combatant_hp(player_id, world.player_character.hit_points.current);
+            }
+        }
 
         // Quest effects
         Effect::QuestCreated {
@@ -626,7 +942,12 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                 } else {
                     // Location not found, clear location_id
                     npc.location_id = None;
+                    return EffectOutcome::clamped(format!(
+                        "unknown location {to_location}, so {npc_name} has no location"
+                    ));
                 }
+            } else {
+                return EffectOutcome::skipped(format!("no NPC named {npc_name}"));
             }
         }
 
@@ -640,6 +961,8 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
 
             if let Some(id) = npc_id {
                 world.npcs.remove(&id);
+            } else {
+                return EffectOutcome::skipped(format!("no NPC named {npc_name}"));
             }
         }
 
@@ -716,19 +1039,94 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                 Ability::Wisdom => &mut world.player_character.ability_scores.wisdom,
                 Ability::Charisma => &mut world.player_character.ability_scores.charisma,
             };
-            *score = (*score as i16 + *modifier as i16).clamp(1, 30) as u8;
+            let target = *score as i16 + *modifier as i16;
+            *score = target.clamp(1, 30) as u8;
+            if target != *score as i16 {
+                return EffectOutcome::clamped(format!(
+                    "{} score held at {}",
+                    ability.name(),
+                    score
+                ));
+            }
         }
 
+        Effect::ReputationChanged {
+            faction,
+            new_reputation,
+            ..
+        } => {
+            world.factions.insert(faction.clone(), *new_reputation);
+        }
         Effect::SpellSlotRestored { level, .. } => {
             if let Some(ref mut spellcasting) = world.player_character.spellcasting {
                 if *level >= 1 && *level <= 9 {
                     let slot_idx = (*level - 1) as usize;
                     if spellcasting.spell_slots.slots[slot_idx].used > 0 {
                         spellcasting.spell_slots.slots[slot_idx].used -= 1;
+                    } else {
+                        return EffectOutcome::skipped(format!(
+                            "no used level {level} spell slot to restore"
+                        ));
                     }
                 }
             }
         }
+        Effect::SpellPrepared { spell_name } => {
+            let Some(spellcasting) = world.player_character.spellcasting.as_mut() else {
+                return EffectOutcome::skipped("no spellcasting");
+            };
+            if spellcasting.spells_prepared.contains(spell_name) {
+                return EffectOutcome::skipped(format!("{spell_name} is already prepared"));
+            }
+            spellcasting.spells_prepared.push(spell_name.clone());
+        }
+        Effect::SpellUnprepared { spell_name } => {
+            let Some(spellcasting) = world.player_character.spellcasting.as_mut() else {
+                return EffectOutcome::skipped("no spellcasting");
+            };
+            let before = spellcasting.spells_prepared.len();
+            spellcasting.spells_prepared.retain(|s| s != spell_name);
+            if spellcasting.spells_prepared.len() == before {
+                return EffectOutcome::skipped(format!("{spell_name} is not prepared"));
+            }
+        }
+        Effect::SpellCopied { spell_name, free } => {
+            let character = &mut world.player_character;
+            let Some(spellcasting) = character.spellcasting.as_mut() else {
+                return EffectOutcome::skipped("no spellcasting");
+            };
+            if spellcasting.spellbook.contains(spell_name) {
+                return EffectOutcome::skipped(format!("{spell_name} is already in the spellbook"));
+            }
+            if *free {
+                if character.free_spellbook_spells == 0 {
+                    return EffectOutcome::skipped("no free spellbook spells left");
+                }
+                character.free_spellbook_spells -= 1;
+            }
+            spellcasting.spellbook.push(spell_name.clone());
+        }
+        Effect::SpellLearned {
+            spell_name,
+            replaced,
+        } => {
+            let character = &mut world.player_character;
+            let Some(spellcasting) = character.spellcasting.as_mut() else {
+                return EffectOutcome::skipped("no spellcasting");
+            };
+            if let Some(replaced) = replaced {
+                let before = spellcasting.spells_known.len();
+                spellcasting.spells_known.retain(|s| s != replaced);
+                if spellcasting.spells_known.len() == before {
+                    return EffectOutcome::skipped(format!("{replaced} is not known"));
+                }
+                spellcasting.spells_prepared.retain(|s| s != replaced);
+                character.spell_swaps = character.spell_swaps.saturating_sub(1);
+            }
+            if !spellcasting.spells_known.contains(spell_name) {
+                spellcasting.spells_known.push(spell_name.clone());
+            }
+        }
         Effect::StateAsserted {
             entity_name,
             state_type,
@@ -750,7 +1148,11 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
                             "neutral" => crate::world::Disposition::Neutral,
                             "friendly" => crate::world::Disposition::Friendly,
                             "helpful" => crate::world::Disposition::Helpful,
-                            _ => return, // Invalid disposition, skip
+                            _ => {
+                                return EffectOutcome::skipped(format!(
+                                    "unknown disposition {new_value}"
+                                ))
+                            }
                         };
                         npc.disposition = new_disp;
                     }
@@ -813,6 +1215,26 @@ pub fn apply_effect(world: &mut GameWorld, effect: &Effect) {
             // No GameWorld state changes needed - StoryMemory handles these
         }
     }
+    EffectOutcome::Applied
+}
+
+/// The ability named by a Resilient feat, written as "Resilient (Constitution)".
+fn resilient_ability(feat_name: &str) -> Option<Ability> {
+    let rest = feat_name.trim().strip_prefix("Resilient")?.trim();
+    let ability = rest.strip_prefix('(')?.strip_suffix(')')?;
+    Ability::from_name(ability)
+}
+
+/// Limited uses a feat grants: Lucky's three luck points per long rest.
+fn feat_uses(feat_name: &str) -> Option<FeatureUses> {
+    feat_name
+        .trim()
+        .eq_ignore_ascii_case("Lucky")
+        .then_some(FeatureUses {
+            current: 3,
+            maximum: 3,
+            recharge: RechargeType::LongRest,
+        })
 }
 
 #[cfg(test)]
@@ -820,7 +1242,8 @@ mod tests {
     use super::*;
     use crate::world::{
         create_sample_barbarian, create_sample_bard, create_sample_cleric, create_sample_fighter,
-        create_sample_monk, create_sample_paladin, create_sample_sorcerer, GameWorld,
+        create_sample_monk, create_sample_paladin, create_sample_sorcerer, Character,
+        CharacterClass, ClassLevel, DurationTiming, GameWorld,
     };
 
     // Helper function to create a level up effect
@@ -894,6 +1317,63 @@ mod tests {
         assert_eq!(world.player_character.classes[0].level, 5);
     }
 
+    /// A level 2 Champion fighter, one level short of their subclass features.
+    fn level_two_champion() -> GameWorld {
+        let mut character = create_sample_fighter("Roland");
+        character.level = 2;
+        character.classes[0].level = 2;
+        GameWorld::new("Test", character)
+    }
+
+    #[test]
+    fn test_champion_level_three_grants_improved_critical() {
+        let mut world = level_two_champion();
+        assert!(!world
+            .player_character
+            .features
+            .iter()
+            .any(|f| f.name == "Improved Critical"));
+
+        apply_effect(&mut world, &level_up_effect(3));
+
+        let feature = world
+            .player_character
+            .features
+            .iter()
+            .find(|f| f.name == "Improved Critical")
+            .expect("Champion gains Improved Critical at level 3");
+        assert_eq!(feature.source, "Champion");
+        assert!(feature.uses.is_none());
+    }
+
+    #[test]
+    fn test_subclass_features_granted_for_every_level_gained() {
+        let mut world = level_two_champion();
+
+        apply_effect(&mut world, &level_up_effect(10));
+
+        let names: Vec<&str> = world
+            .player_character
+            .features
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert!(names.contains(&"Improved Critical"));
+        assert!(names.contains(&"Remarkable Athlete"));
+        assert!(names.contains(&"Additional Fighting Style"));
+        assert!(!names.contains(&"Superior Critical"));
+
+        // Leveling again doesn't duplicate features
+        apply_effect(&mut world, &level_up_effect(11));
+        let improved = world
+            .player_character
+            .features
+            .iter()
+            .filter(|f| f.name == "Improved Critical")
+            .count();
+        assert_eq!(improved, 1);
+    }
+
     // ========== Barbarian Level Up Tests ==========
 
     #[test]
@@ -969,6 +1449,89 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_barbarian_rage_count_progression() {
+        let character = create_sample_barbarian("Conan");
+        let mut world = GameWorld::new("Test", character);
+        let rage_max = |world: &GameWorld| {
+            world
+                .player_character
+                .features
+                .iter()
+                .find(|f| f.name == "Rage")
+                .and_then(|f| f.uses.as_ref())
+                .map(|u| (u.current, u.maximum))
+                .unwrap()
+        };
+
+        let mut expected = Vec::new();
+        for level in 4..=17 {
+            apply_effect(&mut world, &level_up_effect(level));
+            expected.push((level, rage_max(&world).1));
+        }
+
+        assert!(expected.contains(&(5, 3)));
+        assert!(expected.contains(&(6, 4)));
+        assert!(expected.contains(&(12, 5)));
+        assert!(expected.contains(&(17, 6)));
+        assert_eq!(
+            rage_max(&world).0,
+            6,
+            "New rage uses are granted on level up"
+        );
+    }
+
+    // ========== Cleric Level Up Tests ==========
+
+    #[test]
+    fn test_cleric_channel_divinity_second_use_at_6() {
+        let character = create_sample_cleric("Brother Marcus");
+        let mut world = GameWorld::new("Test", character);
+        let channel_divinity = |world: &GameWorld| {
+            world
+                .player_character
+                .features
+                .iter()
+                .find(|f| f.name == "Channel Divinity")
+                .and_then(|f| f.uses.as_ref())
+                .map(|u| (u.current, u.maximum))
+                .unwrap()
+        };
+
+        for level in 4..=5 {
+            apply_effect(&mut world, &level_up_effect(level));
+        }
+        assert_eq!(channel_divinity(&world), (1, 1));
+
+        apply_effect(&mut world, &level_up_effect(6));
+        assert_eq!(channel_divinity(&world), (2, 2));
+    }
+
+    #[test]
+    fn test_channel_divinity_spends_one_of_several_uses() {
+        let character = create_sample_cleric("Brother Marcus");
+        let mut world = GameWorld::new("Test", character);
+        for level in 4..=6 {
+            apply_effect(&mut world, &level_up_effect(level));
+        }
+
+        let engine = crate::rules::RulesEngine::new();
+        let resolution = engine.resolve_use_channel_divinity(
+            &world,
+            world.player_character.id,
+            "Turn Undead",
+            &[],
+        );
+
+        assert!(resolution.effects.iter().any(|e| matches!(
+            e,
+            Effect::FeatureUsed {
+                uses_remaining: 1,
+                ..
+            }
+        )));
+    }
+
     // ========== Monk Level Up Tests ==========
 
     #[test]
@@ -1044,6 +1607,59 @@ mod tests {
         );
     }
 
+    // ========== Half-Caster Spell Slot Tests ==========
+
+    fn level_one(class: CharacterClass) -> GameWorld {
+        let mut character = Character::new("Halfcaster");
+        character.classes.push(ClassLevel {
+            class,
+            level: 1,
+            subclass: None,
+        });
+        GameWorld::new("Test", character)
+    }
+
+    #[test]
+    fn test_half_casters_have_no_slots_at_level_1() {
+        for class in [CharacterClass::Paladin, CharacterClass::Ranger] {
+            assert_eq!(class.spell_slots_at_level(1), [0; 9], "{class:?}");
+            assert_eq!(class.max_spell_level(1), 0, "{class:?}");
+            assert!(level_one(class).player_character.spellcasting.is_none());
+        }
+    }
+
+    #[test]
+    fn test_half_casters_gain_two_slots_at_level_2() {
+        for class in [CharacterClass::Paladin, CharacterClass::Ranger] {
+            assert_eq!(
+                class.spell_slots_at_level(2),
+                [2, 0, 0, 0, 0, 0, 0, 0, 0],
+                "{class:?}"
+            );
+
+            let mut world = level_one(class);
+            apply_effect(&mut world, &level_up_effect(2));
+
+            let spellcasting = world
+                .player_character
+                .spellcasting
+                .as_ref()
+                .unwrap_or_else(|| panic!("{class:?} should gain spellcasting at level 2"));
+            assert_eq!(spellcasting.spell_slots.slots[0].total, 2, "{class:?}");
+            assert_eq!(
+                spellcasting.spell_slots.slots[0].available(),
+                2,
+                "{class:?}"
+            );
+            assert!(
+                spellcasting.spell_slots.slots[1..]
+                    .iter()
+                    .all(|slot| slot.total == 0),
+                "{class:?}"
+            );
+        }
+    }
+
     // ========== Sorcerer Level Up Tests ==========
 
     #[test]
@@ -1181,4 +1797,60 @@ mod tests {
         }
         assert_eq!(world.player_character.proficiency_bonus(), 4);
     }
+
+    #[test]
+    fn test_immune_condition_effect_is_reported_skipped() {
+        let mut character = create_sample_fighter("Roland");
+        character.condition_immunities.insert(Condition::Poisoned);
+        let mut world = GameWorld::new("Test", character);
+        let target_id = world.player_character.id;
+
+        let outcomes = apply_effects(
+            &mut world,
+            &[
+                Effect::ConditionApplied {
+                    target_id,
+                    condition: Condition::Poisoned,
+                    source: "poison dart".to_string(),
+                    duration_rounds: None,
+                    timing: DurationTiming::EndOfTurn,
+                },
+                Effect::ConditionApplied {
+                    target_id,
+                    condition: Condition::Frightened,
+                    source: "dragon".to_string(),
+                    duration_rounds: None,
+                    timing: DurationTiming::EndOfTurn,
+                },
+            ],
+        );
+
+        assert_eq!(
+            outcomes[0],
+            EffectOutcome::Skipped {
+                reason: "Roland is immune to being Poisoned".to_string()
+            }
+        );
+        assert!(outcomes[1].is_applied());
+        assert!(!world.player_character.has_condition(Condition::Poisoned));
+        assert!(world.player_character.has_condition(Condition::Frightened));
+    }
+
+    #[test]
+    fn test_narrate_outcomes_notes_what_did_not_apply() {
+        let outcomes = [
+            EffectOutcome::Applied,
+            EffectOutcome::skipped("Roland is immune to being Poisoned"),
+            EffectOutcome::clamped("only 3 gold to give"),
+        ];
+
+        assert_eq!(
+            narrate_outcomes("The dart strikes.", &outcomes),
+            "The dart strikes.\n(Not applied: Roland is immune to being Poisoned)\n(Only partly applied: only 3 gold to give)"
+        );
+        assert_eq!(
+            narrate_outcomes("The dart strikes.", &outcomes[..1]),
+            "The dart strikes."
+        );
+    }
 }
@@ -1,14 +1,171 @@
 //! The RulesEngine struct and main resolve() dispatch method.
 
-use crate::rules::types::{Intent, Resolution};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::dice::{DiceError, DiceRoller, RollResult};
+use crate::rules::types::{Intent, RerollFeature, Resolution};
+use crate::rules::Locale;
 use crate::world::GameWorld;
 
 /// The rules engine resolves intents into effects using D&D 5e rules.
-pub struct RulesEngine;
+///
+/// Rolls made for the player character come from the player roller, and
+/// rolls made for monsters and NPCs (their saves, initiative, and the damage
+/// of hazards they unleash) come from the DM roller. Both start out as the
+/// same randomly seeded roller; give either its own seed to replay or audit
+/// that side of the table independently.
+pub struct RulesEngine {
+    player_roller: Arc<Mutex<DiceRoller>>,
+    dm_roller: Arc<Mutex<DiceRoller>>,
+    locale: Locale,
+    /// The player's most recent d20 test, kept so a luck feature can reroll it.
+    last_d20: Mutex<Option<LastD20>>,
+    /// A d20 result the next player d20 test takes instead of rolling, set
+    /// while a rerolled test is re-resolved.
+    pinned_d20: Mutex<Option<RollResult>>,
+}
+
+/// A d20 test that may still be rerolled.
+#[derive(Debug, Clone)]
+pub(crate) struct LastD20 {
+    pub roll: RollResult,
+    /// Features that have already rerolled this test; each gets one go.
+    pub rerolled_by: Vec<RerollFeature>,
+    /// What a failed test decided, so a reroll that turns it around can
+    /// correct the effects already applied.
+    pub stakes: Option<D20Stakes>,
+}
+
+/// The outcome riding on a failed d20 test.
+#[derive(Debug, Clone)]
+pub(crate) enum D20Stakes {
+    /// A check or save reported by `CheckFailed`.
+    Check { check_type: String, dc: i32 },
+    /// A save against area damage; succeeding would have spared `refund` HP.
+    AreaSave { dc: i32, refund: i32 },
+    /// A missed attack, re-resolved against the current world without
+    /// charging its costs again.
+    Attack { intent: Intent },
+    /// A death save, re-resolved from the tallies before it.
+    DeathSave { successes: u8, failures: u8 },
+}
 
 impl RulesEngine {
     pub fn new() -> Self {
-        Self
+        let roller = Arc::new(Mutex::new(DiceRoller::new(rand::random())));
+        Self {
+            player_roller: Arc::clone(&roller),
+            dm_roller: roller,
+            locale: Locale::english(),
+            last_d20: Mutex::new(None),
+            pinned_d20: Mutex::new(None),
+        }
+    }
+
+    /// Use `roller` for the player character's rolls.
+    pub fn with_player_roller(mut self, roller: DiceRoller) -> Self {
+        self.player_roller = Arc::new(Mutex::new(roller));
+        self
+    }
+
+    /// Use `roller` for monster and NPC rolls.
+    pub fn with_dm_roller(mut self, roller: DiceRoller) -> Self {
+        self.dm_roller = Arc::new(Mutex::new(roller));
+        self
+    }
+
+    /// Narrate mechanics with the templates in `locale`.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// The catalog mechanical narration is written from.
+    pub fn locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    /// The player roller as it stands, e.g. to save its position.
+    pub fn player_roller(&self) -> DiceRoller {
+        self.player_dice().clone()
+    }
+
+    /// The DM roller as it stands, e.g. to save its position.
+    pub fn dm_roller(&self) -> DiceRoller {
+        self.dm_dice().clone()
+    }
+
+    /// Whether the DM rolls on the player's roller, as a new engine does.
+    pub fn shares_rollers(&self) -> bool {
+        Arc::ptr_eq(&self.player_roller, &self.dm_roller)
+    }
+
+    /// Put back rollers saved mid-sequence. A `dm` of `None` has the DM
+    /// roll on the player's roller.
+    pub fn restore_rollers(&mut self, player: DiceRoller, dm: Option<DiceRoller>) {
+        self.player_roller = Arc::new(Mutex::new(player));
+        self.dm_roller = match dm {
+            Some(dm) => Arc::new(Mutex::new(dm)),
+            None => Arc::clone(&self.player_roller),
+        };
+    }
+
+    /// The player's most recent d20 test, after any rerolls.
+    pub fn last_d20(&self) -> Option<RollResult> {
+        self.last_d20
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|last| last.roll.clone())
+    }
+
+    pub(crate) fn last_d20_state(&self) -> MutexGuard<'_, Option<LastD20>> {
+        self.last_d20.lock().unwrap()
+    }
+
+    /// Record what the player's last d20 test decided.
+    pub(crate) fn stake_last_d20(&self, stakes: D20Stakes) {
+        if let Some(last) = self.last_d20_state().as_mut() {
+            last.stakes = Some(stakes);
+        }
+    }
+
+    pub(crate) fn pinned_d20_state(&self) -> MutexGuard<'_, Option<RollResult>> {
+        self.pinned_d20.lock().unwrap()
+    }
+
+    /// Whether a rerolled test is being re-resolved. Its costs (the action,
+    /// ammunition, spell slot, or component) were paid when it was first
+    /// made, so they are neither checked nor charged again.
+    pub(crate) fn replaying(&self) -> bool {
+        self.pinned_d20_state().is_some()
+    }
+
+    /// Roll `notation` on the player roller.
+    pub(crate) fn roll_player(&self, notation: &str) -> Result<RollResult, DiceError> {
+        self.player_dice().roll(notation)
+    }
+
+    /// Roll `notation` on the DM roller.
+    pub(crate) fn roll_dm(&self, notation: &str) -> Result<RollResult, DiceError> {
+        self.dm_dice().roll(notation)
+    }
+
+    pub(crate) fn player_dice(&self) -> MutexGuard<'_, DiceRoller> {
+        self.player_roller.lock().unwrap()
+    }
+
+    pub(crate) fn dm_dice(&self) -> MutexGuard<'_, DiceRoller> {
+        self.dm_roller.lock().unwrap()
+    }
+
+    /// The roller for a creature's rolls: the player's or the DM's.
+    pub(crate) fn dice_for(&self, is_player: bool) -> MutexGuard<'_, DiceRoller> {
+        if is_player {
+            self.player_dice()
+        } else {
+            self.dm_dice()
+        }
     }
 
     /// Resolve an intent and produce effects.
@@ -20,6 +177,11 @@ impl RulesEngine {
                 weapon_name,
                 advantage,
             } => self.resolve_attack(world, attacker_id, target_id, &weapon_name, advantage),
+            Intent::OffhandAttack {
+                attacker_id,
+                target_id,
+                advantage,
+            } => self.resolve_offhand_attack(world, attacker_id, target_id, advantage),
             Intent::CastSpell {
                 caster_id,
                 spell_name,
@@ -55,12 +217,43 @@ impl RulesEngine {
                 advantage,
                 source,
             } => self.resolve_saving_throw(world, character_id, ability, dc, advantage, &source),
+            Intent::RerollD20 {
+                character_id,
+                feature,
+            } => self.resolve_reroll_d20(world, character_id, feature),
+            Intent::Help {
+                helper,
+                target_id,
+                task,
+            } => self.resolve_help(world, &helper, target_id, &task),
+            Intent::GroupCheck {
+                skill,
+                dc,
+                companions,
+                description,
+            } => self.resolve_group_check(world, skill, dc, &companions, &description),
             Intent::Damage {
                 target_id,
                 amount,
                 damage_type,
                 source,
             } => self.resolve_damage(world, target_id, amount, damage_type, &source),
+            Intent::AreaDamage {
+                targets,
+                damage_dice,
+                damage_type,
+                save_ability,
+                dc,
+                source,
+            } => self.resolve_area_damage(
+                world,
+                &targets,
+                &damage_dice,
+                damage_type,
+                save_ability,
+                dc,
+                &source,
+            ),
             Intent::Heal {
                 target_id,
                 amount,
@@ -71,9 +264,15 @@ impl RulesEngine {
                 condition,
                 source,
                 duration_rounds,
-            } => {
-                self.resolve_apply_condition(world, target_id, condition, &source, duration_rounds)
-            }
+                timing,
+            } => self.resolve_apply_condition(
+                world,
+                target_id,
+                condition,
+                &source,
+                duration_rounds,
+                timing,
+            ),
             Intent::RemoveCondition {
                 target_id,
                 condition,
@@ -92,6 +291,14 @@ impl RulesEngine {
             Intent::RollDice { notation, purpose } => self.resolve_roll_dice(&notation, &purpose),
             Intent::AdvanceTime { minutes } => self.resolve_advance_time(minutes),
             Intent::GainExperience { amount } => self.resolve_gain_experience(world, amount),
+            Intent::ApplyAsi { ability, amount } => self.resolve_apply_asi(world, ability, amount),
+            Intent::ChooseFeat {
+                feat_name,
+                description,
+            } => self.resolve_choose_feat(world, &feat_name, &description),
+            Intent::ChooseFightingStyle { style } => {
+                self.resolve_choose_fighting_style(world, style)
+            }
             Intent::UseFeature {
                 character_id,
                 feature_name,
@@ -375,6 +582,9 @@ impl RulesEngine {
                 &add_npcs,
                 &remove_npcs,
             ),
+            Intent::SetLightLevel { light_level } => {
+                self.resolve_set_light_level(world, light_level)
+            }
             Intent::ModifyAbilityScore {
                 ability,
                 modifier,
@@ -384,6 +594,22 @@ impl RulesEngine {
             Intent::RestoreSpellSlot { slot_level, source } => {
                 self.resolve_restore_spell_slot(world, slot_level, &source)
             }
+            Intent::PrepareSpell { spell_name } => self.resolve_prepare_spell(world, &spell_name),
+            Intent::UnprepareSpell { spell_name } => {
+                self.resolve_unprepare_spell(world, &spell_name)
+            }
+            Intent::CopySpellToBook { spell_name, free } => {
+                self.resolve_copy_spell_to_book(world, &spell_name, free)
+            }
+            Intent::LearnSpell {
+                spell_name,
+                replace,
+            } => self.resolve_learn_spell(world, &spell_name, replace.as_deref()),
+            Intent::AdjustReputation {
+                faction,
+                amount,
+                reason,
+            } => self.resolve_adjust_reputation(world, &faction, amount, &reason),
 
             // State assertion
             Intent::AssertState {
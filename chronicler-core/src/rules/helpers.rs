@@ -1,13 +1,17 @@
 //! Helper functions for the rules engine.
 
-use crate::dice::{self, ComponentResult, DiceExpression, DieType, RollResult};
+use crate::dice::{ComponentResult, DiceExpression, DieType, RollResult};
+use crate::rules::types::Effect;
+use crate::world::{Character, RechargeType};
+use rand::Rng;
 
 /// Roll dice with a fallback expression. If both fail, returns a minimal result.
 ///
 /// This avoids nested unwraps which could panic in edge cases.
-pub fn roll_with_fallback(notation: &str, fallback: &str) -> RollResult {
-    dice::roll(notation)
-        .or_else(|_| dice::roll(fallback))
+pub fn roll_with_fallback<R: Rng>(notation: &str, fallback: &str, rng: &mut R) -> RollResult {
+    DiceExpression::parse(notation)
+        .or_else(|_| DiceExpression::parse(fallback))
+        .map(|expr| expr.roll_with_rng(rng))
         .unwrap_or_else(|_| {
             // Create a minimal fallback result (1d4 = 1)
             let expr = DiceExpression {
@@ -22,11 +26,13 @@ pub fn roll_with_fallback(notation: &str, fallback: &str) -> RollResult {
                     rolls: vec![1],
                     kept: vec![1],
                     subtotal: 1,
+                    rerolled: Vec::new(),
                 }],
                 modifier: 0,
                 total: 1,
                 natural_20: false,
                 natural_1: false,
+                advantage: Default::default(),
             }
         })
 }
@@ -38,6 +44,63 @@ pub fn sneak_attack_dice(rogue_level: u8) -> u8 {
     rogue_level.div_ceil(2)
 }
 
+/// Reroll each damage die showing a 1 or 2 once, keeping the new result
+/// (Great Weapon Fighting).
+pub fn reroll_low_damage_dice<R: Rng>(roll: &mut RollResult, rng: &mut R) {
+    for component in &mut roll.component_results {
+        let sides = component.die_type.sides();
+        for (index, die) in component.rolls.iter_mut().enumerate() {
+            if *die <= 2 {
+                component.rerolled.push((index, *die));
+                *die = rng.gen_range(1..=sides);
+            }
+        }
+        component.kept = component.rolls.clone();
+        component.subtotal = component.rolls.iter().sum();
+    }
+    roll.total = roll
+        .component_results
+        .iter()
+        .map(|c| c.subtotal as i32)
+        .sum::<i32>()
+        + roll.modifier;
+}
+
+/// Roll recharge dice for every expended feature with a `RechargeType::Die`
+/// recharge, returning the dice rolled and a `FeatureRecharged` effect for
+/// each one that comes back.
+pub fn roll_feature_recharges<R: Rng>(character: &Character, rng: &mut R) -> Vec<Effect> {
+    let d6 = DiceExpression::parse("1d6").unwrap();
+    let mut effects = Vec::new();
+
+    for feature in &character.features {
+        let Some(uses) = &feature.uses else {
+            continue;
+        };
+        let RechargeType::Die { min } = uses.recharge else {
+            continue;
+        };
+        if uses.current >= uses.maximum {
+            continue;
+        }
+
+        let roll = d6.roll_with_rng(rng);
+        let recharged = roll.total >= min as i32;
+        effects.push(Effect::DiceRolled {
+            roll,
+            purpose: format!("{} recharge", feature.name),
+        });
+        if recharged {
+            effects.push(Effect::FeatureRecharged {
+                feature_name: feature.name.clone(),
+                uses_remaining: uses.maximum,
+            });
+        }
+    }
+
+    effects
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,7 +109,7 @@ mod tests {
 
     #[test]
     fn test_roll_with_fallback_valid_notation() {
-        let result = roll_with_fallback("2d6", "1d4");
+        let result = roll_with_fallback("2d6", "1d4", &mut rand::thread_rng());
 
         // Total should be between 2 and 12 for 2d6
         assert!(result.total >= 2 && result.total <= 12);
@@ -55,7 +118,7 @@ mod tests {
 
     #[test]
     fn test_roll_with_fallback_invalid_uses_fallback() {
-        let result = roll_with_fallback("invalid", "1d6");
+        let result = roll_with_fallback("invalid", "1d6", &mut rand::thread_rng());
 
         // Should have used the fallback (1d6)
         assert!(result.total >= 1 && result.total <= 6);
@@ -64,7 +127,7 @@ mod tests {
 
     #[test]
     fn test_roll_with_fallback_both_invalid_returns_minimal() {
-        let result = roll_with_fallback("invalid", "also_invalid");
+        let result = roll_with_fallback("invalid", "also_invalid", &mut rand::thread_rng());
 
         // Should return the minimal fallback (total of 1)
         assert_eq!(result.total, 1);
@@ -72,7 +135,7 @@ mod tests {
 
     #[test]
     fn test_roll_with_fallback_with_modifier() {
-        let result = roll_with_fallback("1d6+3", "1d4");
+        let result = roll_with_fallback("1d6+3", "1d4", &mut rand::thread_rng());
 
         // Total should be between 4 and 9 for 1d6+3
         assert!(result.total >= 4 && result.total <= 9);
@@ -80,7 +143,7 @@ mod tests {
 
     #[test]
     fn test_roll_with_fallback_complex_notation() {
-        let result = roll_with_fallback("4d6kh3", "3d6");
+        let result = roll_with_fallback("4d6kh3", "3d6", &mut rand::thread_rng());
 
         // Keep highest 3 of 4d6 should be between 3 and 18
         assert!(result.total >= 3 && result.total <= 18);
@@ -136,4 +199,88 @@ mod tests {
             );
         }
     }
+
+    // ========== reroll_low_damage_dice Tests ==========
+
+    #[test]
+    fn test_reroll_low_damage_dice_keeps_totals_consistent() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let mut roll = crate::dice::DiceExpression::parse("2d6+3")
+                .unwrap()
+                .roll_with_rng(&mut rng);
+            reroll_low_damage_dice(&mut roll, &mut rng);
+            let dice: u32 = roll.component_results[0].rolls.iter().sum();
+            assert_eq!(roll.total, dice as i32 + 3);
+            assert_eq!(roll.component_results[0].subtotal, dice);
+
+            // The breakdown records which faces were rerolled
+            for die in roll.breakdown().dice {
+                if let Some(original) = die.rerolled_from {
+                    assert!(original <= 2);
+                }
+            }
+        }
+    }
+
+    // ========== roll_feature_recharges Tests ==========
+
+    #[test]
+    fn test_recharge_5_6_follows_the_die() {
+        use crate::world::{create_sample_fighter, Feature, FeatureUses};
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut character = create_sample_fighter("Roland");
+        character.features.push(Feature {
+            name: "Fire Breath".to_string(),
+            description: "Exhale fire in a 15-foot cone".to_string(),
+            source: "Test".to_string(),
+            uses: Some(FeatureUses {
+                current: 0,
+                maximum: 1,
+                recharge: RechargeType::Die { min: 5 },
+            }),
+        });
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut recharges = 0;
+        for _ in 0..60 {
+            let effects = roll_feature_recharges(&character, &mut rng);
+            let die = effects
+                .iter()
+                .find_map(|e| match e {
+                    Effect::DiceRolled { roll, .. } => Some(roll.total),
+                    _ => None,
+                })
+                .expect("an expended Die feature should roll");
+            let recharged = effects.iter().any(|e| {
+                matches!(
+                    e,
+                    Effect::FeatureRecharged {
+                        uses_remaining: 1,
+                        ..
+                    }
+                )
+            });
+            assert_eq!(recharged, die >= 5, "rolled {die}");
+            if recharged {
+                recharges += 1;
+            }
+        }
+        // Expect about a third of turns to recharge
+        assert!((10..=30).contains(&recharges), "{recharges} recharges");
+
+        // Nothing to roll once the feature is back at full uses
+        character
+            .features
+            .last_mut()
+            .unwrap()
+            .uses
+            .as_mut()
+            .unwrap()
+            .current = 1;
+        assert!(roll_feature_recharges(&character, &mut rng).is_empty());
+    }
 }
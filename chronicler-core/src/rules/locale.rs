@@ -0,0 +1,172 @@
+//! Message catalogs for the rules engine's mechanical narration.
+//!
+//! Resolutions describe what happened in plain text ("Roland takes 7
+//! slashing damage from Goblin"). A [`Locale`] supplies the templates those
+//! lines are built from, so the mechanics read in the same language the DM
+//! narrates in. Templates name their arguments in braces, e.g.
+//! `"{target} takes {amount} {damage_type} damage from {source}"`, and any
+//! message a catalog leaves out falls back to English.
+//!
+//! Catalogs serialize as JSON, so a game can ship them as data files.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use super::types::DamageType;
+
+/// A line of mechanical narration that can be translated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Message {
+    /// `{target}`, `{amount}`, `{damage_type}`, `{source}`
+    TakesDamage,
+    /// `{target}`, `{amount}`, `{source}`
+    Heals,
+    /// `{current}`, `{max}`
+    HpStatus,
+    /// `{current}`, `{max}`
+    Bloodied,
+    /// `{current}`, `{max}`
+    CriticallyWounded,
+    /// `{max}`
+    DroppedToZero,
+    /// `{overflow}`, `{max}`
+    InstantDeath,
+    /// `{current}`, `{max}`
+    FullyHealed,
+    /// `{current}`, `{max}`
+    RegainsConsciousness,
+    /// `{character}`, `{damage_bonus}`
+    EntersRage,
+}
+
+impl Message {
+    /// The English template for this message.
+    pub fn english(&self) -> &'static str {
+        match self {
+            Message::TakesDamage => "{target} takes {amount} {damage_type} damage from {source}",
+            Message::Heals => "{target} heals {amount} hit points from {source}",
+            Message::HpStatus => " (HP: {current}/{max})",
+            Message::Bloodied => " (HP: {current}/{max} - bloodied)",
+            Message::CriticallyWounded => " (HP: {current}/{max} - critically wounded)",
+            Message::DroppedToZero => {
+                " (HP: 0/{max} - UNCONSCIOUS! Character falls and begins making death saving throws)"
+            }
+            Message::InstantDeath => {
+                " - INSTANT DEATH! The {overflow} damage left after dropping to 0 HP meets their max HP of {max}, so there are no death saves"
+            }
+            Message::FullyHealed => " (HP: {current}/{max} - fully healed)",
+            Message::RegainsConsciousness => " (HP: {current}/{max} - regains consciousness!)",
+            Message::EntersRage => {
+                "{character} enters a RAGE! Gains: advantage on STR checks/saves, +{damage_bonus} rage damage to melee attacks, resistance to bludgeoning/piercing/slashing damage. Cannot cast spells or concentrate while raging."
+            }
+        }
+    }
+}
+
+/// A catalog of message templates and damage type names.
+///
+/// The default catalog is English.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Locale {
+    #[serde(default)]
+    messages: HashMap<Message, String>,
+    #[serde(default)]
+    damage_types: HashMap<DamageType, String>,
+}
+
+impl Locale {
+    pub fn english() -> Self {
+        Self::default()
+    }
+
+    /// Use `template` for `message`.
+    pub fn with_message(mut self, message: Message, template: impl Into<String>) -> Self {
+        self.messages.insert(message, template.into());
+        self
+    }
+
+    /// Call `damage_type` by `name`.
+    pub fn with_damage_type(mut self, damage_type: DamageType, name: impl Into<String>) -> Self {
+        self.damage_types.insert(damage_type, name.into());
+        self
+    }
+
+    /// The name of `damage_type` in this locale.
+    pub fn damage_type(&self, damage_type: DamageType) -> &str {
+        self.damage_types
+            .get(&damage_type)
+            .map_or(damage_type.name(), String::as_str)
+    }
+
+    /// Fill in `message`'s template with `args`. Placeholders without a
+    /// matching argument are left as written.
+    pub fn format(&self, message: Message, args: &[(&str, &dyn Display)]) -> String {
+        let template = self
+            .messages
+            .get(&message)
+            .map_or(message.english(), String::as_str);
+
+        let mut text = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            text.push_str(&rest[..open]);
+            let after = &rest[open + 1..];
+            let arg = after.find('}').and_then(|close| {
+                let (_, value) = args.iter().find(|(name, _)| *name == &after[..close])?;
+                Some((close, value))
+            });
+            match arg {
+                Some((close, value)) => {
+                    text.push_str(&value.to_string());
+                    rest = &after[close + 1..];
+                }
+                None => {
+                    text.push('{');
+                    rest = after;
+                }
+            }
+        }
+        text.push_str(rest);
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_messages_fall_back_to_english() {
+        let locale = Locale::english().with_message(Message::Heals, "{target} sana {amount}");
+
+        assert_eq!(
+            locale.format(Message::Heals, &[("target", &"Sera"), ("amount", &4)]),
+            "Sera sana 4"
+        );
+        assert_eq!(
+            locale.format(Message::HpStatus, &[("current", &4), ("max", &9)]),
+            " (HP: 4/9)"
+        );
+        assert_eq!(
+            locale.format(Message::HpStatus, &[("current", &4)]),
+            " (HP: 4/{max})"
+        );
+    }
+
+    #[test]
+    fn test_locale_round_trips_through_json() {
+        let locale = Locale::english()
+            .with_message(Message::TakesDamage, "{target} recibe {amount} de daño")
+            .with_damage_type(DamageType::Fire, "fuego");
+
+        let json = serde_json::to_string(&locale).unwrap();
+        let restored: Locale = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, locale);
+        assert_eq!(restored.damage_type(DamageType::Fire), "fuego");
+        assert_eq!(restored.damage_type(DamageType::Cold), "cold");
+    }
+}
@@ -9,15 +9,24 @@
 //! This separation ensures deterministic, testable game mechanics
 //! independent of AI decision-making.
 
+mod diff;
 mod effects;
 mod engine;
 mod helpers;
+mod locale;
 mod resolve;
+mod schema;
 #[cfg(test)]
 mod tests;
 mod types;
 
 // Re-export public API
-pub use effects::{apply_effect, apply_effects};
+pub use crate::world::{HpDelta, InventoryDelta, WorldDiff};
+pub use effects::{apply_effect, apply_effects, narrate_outcomes, EffectOutcome};
 pub use engine::RulesEngine;
-pub use types::{CombatantInit, DamageType, Effect, Intent, Resolution, RestType, StateType};
+pub use locale::{Locale, Message};
+pub use schema::{schema, EffectLog, EFFECT_SCHEMA_VERSION};
+pub use types::{
+    AreaTarget, CheckOutcome, CombatantInit, DamageType, Effect, GroupCheckMember, Intent,
+    RerollFeature, Resistance, Resolution, RestType, StateType,
+};
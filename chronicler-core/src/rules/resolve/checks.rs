@@ -1,11 +1,58 @@
 //! Skill checks, ability checks, saving throws, and dice rolls.
 
-use crate::dice::{self, Advantage, DiceExpression};
-use crate::rules::types::{Effect, Resolution};
+use crate::dice::{Advantage, DiceExpression, RollResult};
+use crate::rules::engine::{D20Stakes, LastD20};
+use crate::rules::types::{Effect, GroupCheckMember, Intent, RerollFeature, Resolution};
 use crate::rules::RulesEngine;
-use crate::world::{Ability, CharacterId, Condition, GameWorld, Skill};
+use crate::world::{
+    Ability, CharacterId, Condition, DeathSaves, GameWorld, LightLevel, RaceType, Skill,
+};
 
 impl RulesEngine {
+    /// Roll a d20 test: skill and ability checks, saves, attacks, and
+    /// initiative all go through here so advantage is applied the same way.
+    pub(crate) fn roll_d20_check(&self, modifier: i32, advantage: Advantage) -> RollResult {
+        self.roll_d20_check_for(true, modifier, advantage)
+    }
+
+    /// A d20 test made by a monster or NPC, on the DM roller.
+    pub(crate) fn roll_dm_d20_check(&self, modifier: i32, advantage: Advantage) -> RollResult {
+        self.roll_d20_check_for(false, modifier, advantage)
+    }
+
+    /// A d20 test on the player roller or the DM roller.
+    pub(crate) fn roll_d20_check_for(
+        &self,
+        is_player: bool,
+        modifier: i32,
+        advantage: Advantage,
+    ) -> RollResult {
+        // A rerolled test being re-resolved takes the kept roll
+        if is_player {
+            if let Some(roll) = self.pinned_d20_state().take() {
+                return roll;
+            }
+        }
+
+        let notation = if modifier < 0 {
+            format!("1d20{modifier}")
+        } else {
+            format!("1d20+{modifier}")
+        };
+        let roll = DiceExpression::parse(&notation)
+            .unwrap()
+            .roll_with_advantage_rng(advantage, &mut *self.dice_for(is_player));
+
+        if is_player {
+            *self.last_d20_state() = Some(LastD20 {
+                roll: roll.clone(),
+                rerolled_by: Vec::new(),
+                stakes: None,
+            });
+        }
+        roll
+    }
+
     pub(crate) fn resolve_skill_check(
         &self,
         world: &GameWorld,
@@ -36,52 +83,64 @@ impl RulesEngine {
 
         let modifier = character.skill_modifier(skill);
 
-        // Check for armor-imposed stealth disadvantage
-        let effective_advantage = if skill == Skill::Stealth {
-            if let Some(ref armor) = character.equipment.armor {
-                if armor.stealth_disadvantage {
-                    // Armor imposes disadvantage on Stealth
-                    advantage.combine(Advantage::Disadvantage)
-                } else {
-                    advantage
-                }
-            } else {
-                advantage
-            }
-        } else {
-            advantage
-        };
+        // Armor can impose disadvantage on Stealth
+        let armor_disadvantage = skill == Skill::Stealth
+            && character
+                .equipment
+                .armor
+                .as_ref()
+                .is_some_and(|armor| armor.stealth_disadvantage);
+        // Dim light or darkness imposes disadvantage on Perception, unless
+        // darkvision lets the character see clearly
+        let light = character.perceived_light(world.light_level());
+        let darkness_disadvantage = skill == Skill::Perception && light != LightLevel::Bright;
+
+        let mut sources = vec![advantage];
+        if armor_disadvantage || darkness_disadvantage {
+            sources.push(Advantage::Disadvantage);
+        }
+        // A pending Help grants advantage and is spent on this check
+        if character.helped_by.is_some() {
+            sources.push(Advantage::Advantage);
+        }
+        let effective_advantage = Advantage::from_sources(sources);
 
-        let expr = DiceExpression::parse(&format!("1d20+{modifier}")).unwrap();
-        let roll = expr.roll_with_advantage(effective_advantage);
+        let roll = self.roll_d20_check(modifier.into(), effective_advantage);
 
         let success = roll.total >= dc;
         let result_str = if success { "succeeds" } else { "fails" };
 
-        // Note if stealth disadvantage was applied
-        let disadvantage_note = if skill == Skill::Stealth
-            && effective_advantage != advantage
-            && matches!(effective_advantage, Advantage::Disadvantage)
+        // Note where an imposed disadvantage came from
+        let disadvantage_note = if !matches!(effective_advantage, Advantage::Disadvantage)
+            || effective_advantage == advantage
         {
-            " [armor disadvantage]"
+            String::new()
+        } else if armor_disadvantage {
+            " [armor disadvantage]".to_string()
         } else {
-            ""
+            format!(" [{light} disadvantage]")
         };
 
         let mut resolution = Resolution::new(format!(
-            "{} {} ({} check: {} vs DC {}){}",
+            "{} {} ({} check: {} vs DC {}){}{}",
             character.name,
             result_str,
             skill.name(),
             roll.total,
             dc,
-            disadvantage_note
+            disadvantage_note,
+            help_note(character.helped_by.as_deref())
         ));
 
         resolution = resolution.with_effect(Effect::DiceRolled {
             roll: roll.clone(),
             purpose: format!("{} check - {}", skill.name(), description),
         });
+        if let Some(helper) = &character.helped_by {
+            resolution = resolution.with_effect(Effect::HelpUsed {
+                helper: helper.clone(),
+            });
+        }
 
         if success {
             resolution = resolution.with_effect(Effect::CheckSucceeded {
@@ -90,6 +149,10 @@ impl RulesEngine {
                 dc,
             });
         } else {
+            self.stake_last_d20(D20Stakes::Check {
+                check_type: skill.name().to_string(),
+                dc,
+            });
             resolution = resolution.with_effect(Effect::CheckFailed {
                 check_type: skill.name().to_string(),
                 roll: roll.total,
@@ -129,25 +192,37 @@ impl RulesEngine {
 
         let modifier = character.ability_scores.modifier(ability);
 
-        let expr = DiceExpression::parse(&format!("1d20+{modifier}")).unwrap();
-        let roll = expr.roll_with_advantage(advantage);
+        // A pending Help grants advantage and is spent on this check
+        let advantage = if character.helped_by.is_some() {
+            advantage.combine(Advantage::Advantage)
+        } else {
+            advantage
+        };
+
+        let roll = self.roll_d20_check(modifier.into(), advantage);
 
         let success = roll.total >= dc;
         let result_str = if success { "succeeds" } else { "fails" };
 
         let mut resolution = Resolution::new(format!(
-            "{} {} ({} check: {} vs DC {})",
+            "{} {} ({} check: {} vs DC {}){}",
             character.name,
             result_str,
             ability.abbreviation(),
             roll.total,
-            dc
+            dc,
+            help_note(character.helped_by.as_deref())
         ));
 
         resolution = resolution.with_effect(Effect::DiceRolled {
             roll: roll.clone(),
             purpose: format!("{} check - {}", ability.abbreviation(), description),
         });
+        if let Some(helper) = &character.helped_by {
+            resolution = resolution.with_effect(Effect::HelpUsed {
+                helper: helper.clone(),
+            });
+        }
 
         if success {
             resolution.with_effect(Effect::CheckSucceeded {
@@ -156,6 +231,10 @@ impl RulesEngine {
                 dc,
             })
         } else {
+            self.stake_last_d20(D20Stakes::Check {
+                check_type: ability.abbreviation().to_string(),
+                dc,
+            });
             resolution.with_effect(Effect::CheckFailed {
                 check_type: ability.abbreviation().to_string(),
                 roll: roll.total,
@@ -164,6 +243,324 @@ impl RulesEngine {
         }
     }
 
+    pub(crate) fn resolve_help(
+        &self,
+        world: &GameWorld,
+        helper: &str,
+        _target_id: CharacterId,
+        task: &str,
+    ) -> Resolution {
+        let target_name = world.player_character.name.clone();
+        let task_note = if task.is_empty() {
+            String::new()
+        } else {
+            format!(" to {task}")
+        };
+
+        Resolution::new(format!(
+            "{helper} helps {target_name}{task_note}, granting advantage on the next ability check."
+        ))
+        .with_effect(Effect::HelpGiven {
+            helper: helper.to_string(),
+            target_name,
+        })
+    }
+
+    /// Reroll the player's most recent d20 test with Halfling Luck or the
+    /// Lucky feat. Each feature can reroll a given test once; Halfling Luck
+    /// only rerolls a natural 1 and must keep the new die, while Lucky
+    /// spends a luck point and keeps the better die.
+    pub(crate) fn resolve_reroll_d20(
+        &self,
+        world: &GameWorld,
+        _character_id: CharacterId,
+        feature: Option<RerollFeature>,
+    ) -> Resolution {
+        let character = &world.player_character;
+        let Some(last) = self.last_d20_state().clone() else {
+            return Resolution::new(format!("{} has no d20 roll to reroll.", character.name));
+        };
+        let Some(natural) = last.roll.natural_d20() else {
+            return Resolution::new(format!("{} has no d20 roll to reroll.", character.name));
+        };
+
+        let is_halfling = character.race_type == RaceType::Halfling;
+        let lucky = character
+            .features
+            .iter()
+            .find(|f| f.name.trim().eq_ignore_ascii_case("Lucky") && f.source == "Feat");
+        let lucky_uses = lucky.and_then(|f| f.uses.as_ref()).map(|uses| uses.current);
+        let halfling_luck_applies =
+            is_halfling && natural == 1 && !last.rerolled_by.contains(&RerollFeature::HalflingLuck);
+        let feature = match feature {
+            Some(feature) => feature,
+            None if halfling_luck_applies => RerollFeature::HalflingLuck,
+            None => RerollFeature::Lucky,
+        };
+
+        if last.rerolled_by.contains(&feature) {
+            return Resolution::new(format!(
+                "{} has already rerolled this d20 with {}.",
+                character.name,
+                feature.name()
+            ));
+        }
+        let lucky_remaining = match feature {
+            RerollFeature::HalflingLuck if !is_halfling => {
+                return Resolution::new(format!(
+                    "{} is not a halfling and can't use Halfling Luck.",
+                    character.name
+                ));
+            }
+            RerollFeature::HalflingLuck if natural != 1 => {
+                return Resolution::new(format!(
+                    "Halfling Luck only rerolls a natural 1, and {} rolled a {natural}.",
+                    character.name
+                ));
+            }
+            RerollFeature::HalflingLuck => None,
+            RerollFeature::Lucky => match lucky_uses {
+                None => {
+                    return Resolution::new(format!(
+                        "{} does not have the Lucky feat.",
+                        character.name
+                    ));
+                }
+                Some(0) => {
+                    return Resolution::new(format!(
+                        "{} has no luck points remaining (recovers on long rest).",
+                        character.name
+                    ));
+                }
+                Some(uses) => Some(uses - 1),
+            },
+        };
+
+        // Everything but the die itself (modifiers and bonus dice) carries over
+        let bonus = last.roll.total - natural as i32;
+        let reroll = self.roll_d20_check(bonus, Advantage::Normal);
+        let new_natural = reroll.natural_d20().unwrap_or_default();
+        let keep_new = feature == RerollFeature::HalflingLuck || new_natural >= natural;
+        let kept = if keep_new {
+            reroll.clone()
+        } else {
+            last.roll.clone()
+        };
+
+        // A better roll can turn a failure around; re-resolve what rode on it
+        let correction = last
+            .stakes
+            .as_ref()
+            .filter(|_| keep_new)
+            .and_then(|stakes| self.correct_d20_outcome(world, stakes, &kept));
+
+        // Once a test has been turned around there is nothing left to correct,
+        // but a death save is always replayed from the saves made before it
+        let stakes = match (&correction, last.stakes) {
+            (Some(_), Some(stakes @ D20Stakes::DeathSave { .. })) => Some(stakes),
+            (Some(_), _) => None,
+            (None, stakes) => stakes,
+        };
+        let mut rerolled_by = last.rerolled_by;
+        rerolled_by.push(feature);
+        *self.last_d20_state() = Some(LastD20 {
+            roll: kept.clone(),
+            rerolled_by,
+            stakes,
+        });
+
+        let narrative = match lucky_remaining {
+            None => format!(
+                "{} rerolls the natural 1 with Halfling Luck and rolls a {new_natural}: the total is now {}.",
+                character.name, kept.total
+            ),
+            Some(remaining) => format!(
+                "{} spends a luck point ({remaining} left) and rolls a {new_natural} against the original {natural}, keeping the {}: the total is now {}.",
+                character.name,
+                if keep_new { new_natural } else { natural },
+                kept.total
+            ),
+        };
+        let mut resolution = Resolution::new(narrative).with_effect(Effect::DiceRolled {
+            roll: reroll,
+            purpose: format!("{} reroll", feature.name()),
+        });
+        if let Some(correction) = correction {
+            resolution.narrative.push(' ');
+            resolution.narrative.push_str(&correction.narrative);
+            resolution = resolution.with_effects(correction.effects);
+        }
+        match lucky_remaining {
+            Some(remaining) => {
+                resolution.use_feature(lucky.map_or("Lucky", |f| &f.name), remaining)
+            }
+            None => resolution,
+        }
+    }
+
+    /// Effects that bring the world in line with a rerolled d20 test whose
+    /// outcome changed, or `None` if the kept roll changes nothing.
+    fn correct_d20_outcome(
+        &self,
+        world: &GameWorld,
+        stakes: &D20Stakes,
+        kept: &RollResult,
+    ) -> Option<Resolution> {
+        let character = &world.player_character;
+        match stakes {
+            D20Stakes::Check { check_type, dc } => (kept.total >= *dc).then(|| {
+                Resolution::new(format!("The {check_type} now succeeds!")).with_effect(
+                    Effect::CheckSucceeded {
+                        check_type: check_type.clone(),
+                        roll: kept.total,
+                        dc: *dc,
+                    },
+                )
+            }),
+            D20Stakes::AreaSave { dc, refund } => (kept.total >= *dc).then(|| {
+                let hp = &character.hit_points;
+                Resolution::new(format!(
+                    "The save now succeeds, and {} takes {refund} less damage.",
+                    character.name
+                ))
+                .with_effect(Effect::HpChanged {
+                    target_id: character.id,
+                    amount: *refund,
+                    new_current: (hp.current + refund).min(hp.maximum),
+                    new_max: hp.maximum,
+                    dropped_to_zero: false,
+                })
+            }),
+            D20Stakes::Attack { intent } => {
+                let replay = self.replay_d20(world, intent.clone(), kept);
+                if !replay
+                    .effects
+                    .iter()
+                    .any(|e| matches!(e, Effect::AttackHit { .. }))
+                {
+                    return None;
+                }
+                // Only what the hit adds: the attack's costs were already paid
+                let hit_effects: Vec<Effect> = replay
+                    .effects
+                    .into_iter()
+                    .filter(|e| match e {
+                        Effect::DiceRolled { roll, .. } => roll.natural_d20().is_none(),
+                        Effect::AttackHit { .. }
+                        | Effect::CombatantHpChanged { .. }
+                        | Effect::SneakAttackUsed { .. } => true,
+                        _ => false,
+                    })
+                    .collect();
+                let damage: Vec<String> = hit_effects
+                    .iter()
+                    .filter_map(|e| match e {
+                        Effect::DiceRolled { roll, purpose } => {
+                            Some(format!("{purpose}: {}", roll.total))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                let narrative = if damage.is_empty() {
+                    "The attack now hits!".to_string()
+                } else {
+                    format!("The attack now hits! ({})", damage.join(", "))
+                };
+                Some(Resolution::new(narrative).with_effects(hit_effects))
+            }
+            D20Stakes::DeathSave {
+                successes,
+                failures,
+            } => {
+                let saves = DeathSaves {
+                    successes: *successes,
+                    failures: *failures,
+                };
+                let replay = self.death_save_outcome(character, kept, &saves);
+                Some(
+                    Resolution::new(format!("Replaying the death save: {}", replay.narrative))
+                        .with_effect(Effect::DeathSavesSet {
+                            target_id: character.id,
+                            successes: saves.successes,
+                            failures: saves.failures,
+                        })
+                        .with_effects(replay.effects),
+                )
+            }
+        }
+    }
+
+    /// Re-resolve `intent` against `world` with the player's d20 test
+    /// landing on `roll`, without charging the costs it already paid.
+    fn replay_d20(&self, world: &GameWorld, intent: Intent, roll: &RollResult) -> Resolution {
+        *self.pinned_d20_state() = Some(roll.clone());
+        let resolution = self.resolve(world, intent);
+        self.pinned_d20_state().take();
+        resolution
+    }
+
+    pub(crate) fn resolve_group_check(
+        &self,
+        world: &GameWorld,
+        skill: Skill,
+        dc: i32,
+        companions: &[GroupCheckMember],
+        description: &str,
+    ) -> Resolution {
+        let character = &world.player_character;
+        let members = std::iter::once((
+            character.name.as_str(),
+            character.skill_modifier(skill) as i32,
+            true,
+        ))
+        .chain(
+            companions
+                .iter()
+                .map(|c| (c.name.as_str(), c.modifier, false)),
+        );
+
+        let mut rolls = Vec::new();
+        let mut results = Vec::new();
+        let mut successes = 0;
+        for (name, modifier, is_player) in members {
+            let roll = self.roll_d20_check_for(is_player, modifier, Advantage::Normal);
+            let passed = roll.total >= dc;
+            if passed {
+                successes += 1;
+            }
+            results.push(format!(
+                "{name} {} ({})",
+                roll.total,
+                if passed { "pass" } else { "fail" }
+            ));
+            rolls.push((name.to_string(), roll));
+        }
+
+        // The group succeeds if at least half its members succeed
+        let participants = rolls.len();
+        let success = successes * 2 >= participants;
+        let mut resolution = Resolution::new(format!(
+            "Group {} check {} ({successes}/{participants} vs DC {dc}): {}",
+            skill.name(),
+            if success { "succeeds" } else { "fails" },
+            results.join(", ")
+        ));
+
+        for (name, roll) in rolls {
+            resolution = resolution.with_effect(Effect::DiceRolled {
+                roll,
+                purpose: format!("{name}'s {} check - {description}", skill.name()),
+            });
+        }
+        resolution.with_effect(Effect::GroupCheckResolved {
+            check_type: skill.name().to_string(),
+            successes,
+            participants,
+            dc,
+            success,
+        })
+    }
+
     pub(crate) fn resolve_saving_throw(
         &self,
         world: &GameWorld,
@@ -193,19 +590,32 @@ impl RulesEngine {
 
         let modifier = character.saving_throw_modifier(ability);
 
-        let expr = DiceExpression::parse(&format!("1d20+{modifier}")).unwrap();
-        let roll = expr.roll_with_advantage(advantage);
+        // Racial traits, class features, and conditions can tip the save
+        let save_sources = character.save_advantage_sources(ability, source);
+        let advantage = Advantage::from_sources(
+            std::iter::once(advantage).chain(save_sources.iter().map(|(kind, _)| *kind)),
+        );
+        let source_notes: String = save_sources
+            .into_iter()
+            .map(|(kind, label)| match kind {
+                Advantage::Disadvantage => format!(" [{label} disadvantage]"),
+                _ => format!(" [{label} advantage]"),
+            })
+            .collect();
+
+        let roll = self.roll_d20_check(modifier.into(), advantage);
 
         let success = roll.total >= dc;
         let result_str = if success { "succeeds" } else { "fails" };
 
         let mut resolution = Resolution::new(format!(
-            "{} {} on {} saving throw ({} vs DC {})",
+            "{} {} on {} saving throw ({} vs DC {}){}",
             character.name,
             result_str,
             ability.abbreviation(),
             roll.total,
-            dc
+            dc,
+            source_notes
         ));
 
         resolution = resolution.with_effect(Effect::DiceRolled {
@@ -220,6 +630,10 @@ impl RulesEngine {
                 dc,
             })
         } else {
+            self.stake_last_d20(D20Stakes::Check {
+                check_type: format!("{} save", ability.abbreviation()),
+                dc,
+            });
             resolution.with_effect(Effect::CheckFailed {
                 check_type: format!("{} save", ability.abbreviation()),
                 roll: roll.total,
@@ -229,7 +643,7 @@ impl RulesEngine {
     }
 
     pub(crate) fn resolve_roll_dice(&self, notation: &str, purpose: &str) -> Resolution {
-        match dice::roll(notation) {
+        match self.roll_dm(notation) {
             Ok(roll) => Resolution::new(format!("Rolling {notation} for {purpose}: {roll}"))
                 .with_effect(Effect::DiceRolled {
                     roll,
@@ -240,6 +654,13 @@ impl RulesEngine {
     }
 }
 
+/// Note appended to a check that used a pending Help.
+fn help_note(helped_by: Option<&str>) -> String {
+    helped_by
+        .map(|helper| format!(" [helped by {helper}]"))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,6 +781,57 @@ mod tests {
             .any(|e| matches!(e, Effect::DiceRolled { .. })));
     }
 
+    fn perception_check(
+        race: crate::world::RaceType,
+        world_setup: impl FnOnce(&mut GameWorld),
+    ) -> Resolution {
+        let mut character = create_sample_fighter("Roland");
+        character.race_type = race;
+        character.darkvision_range = race.darkvision_range();
+        let mut world = GameWorld::new("Test", character);
+        world_setup(&mut world);
+        RulesEngine::new().resolve_skill_check(
+            &world,
+            world.player_character.id,
+            Skill::Perception,
+            10,
+            Advantage::Normal,
+            "spotting the ambush",
+        )
+    }
+
+    #[test]
+    fn test_perception_at_night_without_darkvision_has_disadvantage() {
+        use crate::world::RaceType;
+
+        let resolution = perception_check(RaceType::Human, |w| w.game_time.hour = 23);
+        assert!(resolution.narrative.contains("[dim light disadvantage]"));
+
+        let resolution = perception_check(RaceType::Human, |w| w.game_time.hour = 12);
+        assert!(!resolution.narrative.contains("disadvantage"));
+
+        let resolution = perception_check(RaceType::Dwarf, |w| w.game_time.hour = 23);
+        assert!(!resolution.narrative.contains("disadvantage"));
+    }
+
+    #[test]
+    fn test_dwarf_sees_in_dim_light_human_struggles_in_darkness() {
+        use crate::world::RaceType;
+
+        let dim = |w: &mut GameWorld| w.current_location.light_level = Some(LightLevel::Dim);
+        let dark = |w: &mut GameWorld| w.current_location.light_level = Some(LightLevel::Darkness);
+
+        let resolution = perception_check(RaceType::Dwarf, dim);
+        assert!(!resolution.narrative.contains("disadvantage"));
+
+        let resolution = perception_check(RaceType::Human, dark);
+        assert!(resolution.narrative.contains("[darkness disadvantage]"));
+
+        // Darkvision only turns darkness into dim light
+        let resolution = perception_check(RaceType::Dwarf, dark);
+        assert!(resolution.narrative.contains("[dim light disadvantage]"));
+    }
+
     // ========== Ability Check Tests ==========
 
     #[test]
@@ -528,6 +1000,72 @@ mod tests {
             .any(|e| matches!(e, Effect::CheckFailed { roll: 0, .. })));
     }
 
+    fn save_against(world: &GameWorld, ability: Ability, source: &str) -> Resolution {
+        RulesEngine::new().resolve_saving_throw(
+            world,
+            world.player_character.id,
+            ability,
+            10,
+            Advantage::Normal,
+            source,
+        )
+    }
+
+    #[test]
+    fn test_saving_throw_advantage_against_poison() {
+        let mut character = create_sample_fighter("Roland");
+        character.features.push(crate::world::Feature {
+            name: "Dwarven Resilience".to_string(),
+            description: String::new(),
+            source: "Dwarf".to_string(),
+            uses: None,
+        });
+        let world = GameWorld::new("Test", character);
+
+        let resolution = save_against(&world, Ability::Constitution, "spider poison");
+        assert_eq!(d20_rolls(&resolution), 2);
+        assert!(resolution
+            .narrative
+            .contains("[Dwarven Resilience advantage]"));
+
+        let resolution = save_against(&world, Ability::Constitution, "disease");
+        assert_eq!(d20_rolls(&resolution), 1);
+        assert!(!resolution.narrative.contains("advantage"));
+    }
+
+    #[test]
+    fn test_saving_throw_condition_disadvantage_cancels_advantage() {
+        let mut character = create_sample_fighter("Roland");
+        character.add_condition(Condition::Restrained, "net");
+        let world = GameWorld::new("Test", character);
+
+        let resolution = save_against(&world, Ability::Dexterity, "fireball");
+        assert_eq!(d20_rolls(&resolution), 2);
+        assert!(resolution.narrative.contains("[Restrained disadvantage]"));
+        assert_eq!(
+            world
+                .player_character
+                .save_advantage(Ability::Dexterity, "fireball"),
+            Advantage::Disadvantage
+        );
+
+        // Only Dexterity saves suffer from being restrained
+        let resolution = save_against(&world, Ability::Wisdom, "fear");
+        assert_eq!(d20_rolls(&resolution), 1);
+
+        let mut character = world.player_character.clone();
+        character.features.push(crate::world::Feature {
+            name: "Danger Sense".to_string(),
+            description: String::new(),
+            source: "Barbarian".to_string(),
+            uses: None,
+        });
+        assert_eq!(
+            character.save_advantage(Ability::Dexterity, "fireball"),
+            Advantage::Normal
+        );
+    }
+
     // ========== Dice Roll Tests ==========
 
     #[test]
@@ -566,4 +1104,392 @@ mod tests {
             .iter()
             .any(|e| matches!(e, Effect::DiceRolled { .. })));
     }
+
+    // ========== Help and Group Check Tests ==========
+
+    fn d20_rolls(resolution: &Resolution) -> usize {
+        resolution
+            .effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::DiceRolled { roll, .. } => Some(roll.component_results[0].rolls.len()),
+                _ => None,
+            })
+            .expect("check should roll a d20")
+    }
+
+    #[test]
+    fn test_help_grants_advantage_exactly_once() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let engine = RulesEngine::new();
+        let id = world.player_character.id;
+
+        let help = engine.resolve_help(&world, "Kira", id, "climb the wall");
+        assert!(help
+            .narrative
+            .contains("Kira helps Roland to climb the wall"));
+        crate::rules::apply_effects(&mut world, &help.effects);
+        assert_eq!(world.player_character.helped_by.as_deref(), Some("Kira"));
+
+        let helped = engine.resolve_skill_check(
+            &world,
+            id,
+            Skill::Athletics,
+            10,
+            Advantage::Normal,
+            "climbing",
+        );
+        assert_eq!(d20_rolls(&helped), 2);
+        assert!(helped.narrative.contains("[helped by Kira]"));
+        crate::rules::apply_effects(&mut world, &helped.effects);
+        assert!(world.player_character.helped_by.is_none());
+
+        let unhelped =
+            engine.resolve_ability_check(&world, id, Ability::Strength, 10, Advantage::Normal, "");
+        assert_eq!(d20_rolls(&unhelped), 1);
+        assert!(!unhelped.narrative.contains("helped"));
+    }
+
+    #[test]
+    fn test_help_is_spent_by_ability_check() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        world.player_character.helped_by = Some("Kira".to_string());
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_ability_check(
+            &world,
+            world.player_character.id,
+            Ability::Strength,
+            10,
+            Advantage::Normal,
+            "forcing the door",
+        );
+
+        assert_eq!(d20_rolls(&resolution), 2);
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::HelpUsed { helper } if helper == "Kira")));
+    }
+
+    #[test]
+    fn test_group_check_needs_half_the_party() {
+        let world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let engine = RulesEngine::new();
+        let members = |modifier| {
+            vec![
+                GroupCheckMember {
+                    name: "Kira".to_string(),
+                    modifier,
+                },
+                GroupCheckMember {
+                    name: "Bram".to_string(),
+                    modifier,
+                },
+            ]
+        };
+
+        // Both companions pass, so the group passes whatever Roland rolls
+        let resolution =
+            engine.resolve_group_check(&world, Skill::Stealth, 25, &members(100), "sneaking");
+        assert!(resolution.effects.iter().any(|e| matches!(
+            e,
+            Effect::GroupCheckResolved {
+                success: true,
+                participants: 3,
+                ..
+            }
+        )));
+
+        // Both companions fail, so one success out of three is not enough
+        let resolution =
+            engine.resolve_group_check(&world, Skill::Stealth, 0, &members(-100), "sneaking");
+        assert!(resolution.effects.iter().any(|e| matches!(
+            e,
+            Effect::GroupCheckResolved {
+                success: false,
+                successes: 1,
+                ..
+            }
+        )));
+        assert_eq!(
+            resolution
+                .effects
+                .iter()
+                .filter(|e| matches!(e, Effect::DiceRolled { .. }))
+                .count(),
+            3
+        );
+    }
+
+    // ========== Reroll Tests ==========
+
+    /// Make skill checks until one lands on `natural`.
+    fn roll_until(engine: &RulesEngine, world: &GameWorld, natural: u32) -> RollResult {
+        loop {
+            engine.resolve_skill_check(
+                world,
+                world.player_character.id,
+                Skill::Stealth,
+                15,
+                Advantage::Normal,
+                "sneaking",
+            );
+            let roll = engine.last_d20().unwrap();
+            if roll.natural_d20() == Some(natural) {
+                return roll;
+            }
+        }
+    }
+
+    #[test]
+    fn test_halfling_luck_rerolls_a_natural_one_for_free() {
+        let mut character = create_sample_fighter("Pip");
+        character.race_type = RaceType::Halfling;
+        let world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let id = world.player_character.id;
+
+        let original = roll_until(&engine, &world, 1);
+        let resolution = engine.resolve_reroll_d20(&world, id, None);
+
+        assert!(resolution.narrative.contains("Halfling Luck"));
+        assert!(!resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::FeatureUsed { .. })));
+        let kept = engine.last_d20().unwrap();
+        assert_eq!(
+            kept.total - kept.natural_d20().unwrap() as i32,
+            original.total - 1
+        );
+
+        // Halfling Luck gets one reroll per roll, and only of a natural 1
+        let again = engine.resolve_reroll_d20(&world, id, Some(RerollFeature::HalflingLuck));
+        assert!(again.narrative.contains("already rerolled"));
+        roll_until(&engine, &world, 2);
+        let not_a_one = engine.resolve_reroll_d20(&world, id, Some(RerollFeature::HalflingLuck));
+        assert!(not_a_one.narrative.contains("only rerolls a natural 1"));
+        assert!(not_a_one.effects.is_empty());
+    }
+
+    #[test]
+    fn test_lucky_spends_a_luck_point_and_keeps_the_better_die() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        crate::rules::apply_effects(
+            &mut world,
+            &[Effect::FeatChosen {
+                feat_name: "Lucky".to_string(),
+                description: "Three luck points per long rest".to_string(),
+            }],
+        );
+        let engine = RulesEngine::new();
+        let id = world.player_character.id;
+
+        for remaining in (0..3).rev() {
+            let original = roll_until(&engine, &world, 5);
+            let resolution = engine.resolve_reroll_d20(&world, id, None);
+            assert!(resolution.effects.iter().any(|e| matches!(
+                e,
+                Effect::FeatureUsed { feature_name, uses_remaining }
+                    if feature_name == "Lucky" && *uses_remaining == remaining
+            )));
+            assert!(engine.last_d20().unwrap().total >= original.total);
+            crate::rules::apply_effects(&mut world, &resolution.effects);
+
+            // The same roll can't be rerolled with Lucky twice
+            let again = engine.resolve_reroll_d20(&world, id, None);
+            assert!(again.narrative.contains("already rerolled"));
+        }
+
+        roll_until(&engine, &world, 5);
+        let spent = engine.resolve_reroll_d20(&world, id, None);
+        assert!(spent.narrative.contains("no luck points remaining"));
+        assert!(spent.effects.is_empty());
+    }
+
+    fn halfling(name: &str) -> GameWorld {
+        let mut character = create_sample_fighter(name);
+        character.race_type = RaceType::Halfling;
+        GameWorld::new("Test", character)
+    }
+
+    #[test]
+    fn test_turn_advance_ends_the_chance_to_reroll() {
+        let mut world = halfling("Pip");
+        world.combat = Some(crate::world::CombatState::new());
+        let engine = RulesEngine::new();
+        let id = world.player_character.id;
+
+        roll_until(&engine, &world, 1);
+        engine.resolve_next_turn(&world);
+
+        assert!(engine.last_d20().is_none());
+        let reroll = engine.resolve_reroll_d20(&world, id, None);
+        assert!(reroll.narrative.contains("no d20 roll to reroll"));
+    }
+
+    #[test]
+    fn test_reroll_turns_a_failed_check_into_a_success() {
+        let world = halfling("Pip");
+        let engine = RulesEngine::new();
+        let id = world.player_character.id;
+        // Only a natural 1 fails
+        let dc = world.player_character.skill_modifier(Skill::Stealth) as i32 + 2;
+
+        let resolution = loop {
+            let check =
+                engine.resolve_skill_check(&world, id, Skill::Stealth, dc, Advantage::Normal, "");
+            if check
+                .effects
+                .iter()
+                .any(|e| matches!(e, Effect::CheckFailed { .. }))
+            {
+                let reroll = engine.resolve_reroll_d20(&world, id, None);
+                if engine.last_d20().unwrap().natural_d20() != Some(1) {
+                    break reroll;
+                }
+            }
+        };
+
+        assert!(resolution.narrative.contains("now succeeds"));
+        assert!(resolution.effects.iter().any(|e| matches!(
+            e,
+            Effect::CheckSucceeded { dc: check_dc, .. } if *check_dc == dc
+        )));
+    }
+
+    #[test]
+    fn test_reroll_turns_a_miss_into_a_hit() {
+        use crate::world::{CombatState, Combatant};
+
+        let engine = RulesEngine::new();
+        let goblin_id = CharacterId::new();
+        let (world, resolution) = loop {
+            let mut world = halfling("Pip");
+            // AC 1, so only a natural 1 misses
+            let mut combat = CombatState::new();
+            combat.add_combatant(Combatant {
+                id: goblin_id,
+                name: "Goblin".to_string(),
+                initiative: 10,
+                is_player: false,
+                is_ally: false,
+                current_hp: 30,
+                max_hp: 30,
+                armor_class: 1,
+                resistances: Vec::new(),
+            });
+            world.combat = Some(combat);
+            let id = world.player_character.id;
+
+            let attack =
+                engine.resolve_attack(&world, id, goblin_id, "Longsword", Advantage::Normal);
+            crate::rules::apply_effects(&mut world, &attack.effects);
+            if attack
+                .effects
+                .iter()
+                .any(|e| matches!(e, Effect::AttackMissed { .. }))
+            {
+                let reroll = engine.resolve_reroll_d20(&world, id, None);
+                if engine.last_d20().unwrap().natural_d20() != Some(1) {
+                    break (world, reroll);
+                }
+            }
+        };
+
+        assert!(resolution.narrative.contains("now hits"));
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::AttackHit { .. })));
+        // The attack's action was already spent and isn't spent again
+        assert!(!resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::ActionUsed { .. } | Effect::AttackMade { .. })));
+
+        let mut world = world;
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        let goblin = &world.combat.as_ref().unwrap().combatants[0];
+        assert!(goblin.current_hp < 30);
+    }
+
+    #[test]
+    fn test_reroll_replays_a_natural_one_death_save() {
+        let engine = RulesEngine::new();
+        let (world, resolution) = loop {
+            let mut world = halfling("Pip");
+            world.player_character.hit_points.current = 0;
+            world
+                .player_character
+                .add_condition(Condition::Unconscious, "Dropped to 0 HP");
+            let id = world.player_character.id;
+
+            let save = engine.resolve_death_save(&world, id);
+            crate::rules::apply_effects(&mut world, &save.effects);
+            if world.player_character.death_saves.failures == 2 {
+                let reroll = engine.resolve_reroll_d20(&world, id, None);
+                if engine.last_d20().unwrap().natural_d20() != Some(1) {
+                    break (world, reroll);
+                }
+            }
+        };
+
+        let mut world = world;
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        // The natural 1's two failures are gone, and the new roll counts once
+        let saves = &world.player_character.death_saves;
+        assert!(saves.failures <= 1);
+        assert!(saves.failures + saves.successes <= 1);
+    }
+
+    #[test]
+    fn test_rerolled_area_save_refunds_only_the_hp_lost() {
+        use crate::rules::types::{AreaTarget, DamageType};
+
+        let engine = RulesEngine::new();
+        for _ in 0..1000 {
+            let mut world = halfling("Pip");
+            let hp = &mut world.player_character.hit_points;
+            hp.current = hp.maximum - 5;
+            hp.temporary = 8;
+            let start = hp.current;
+            let id = world.player_character.id;
+            // Only a natural 1 fails
+            let dc = world
+                .player_character
+                .saving_throw_modifier(Ability::Dexterity) as i32
+                + 2;
+
+            let area = engine.resolve_area_damage(
+                &world,
+                &[AreaTarget {
+                    id,
+                    save_modifier: 0,
+                }],
+                "10",
+                DamageType::Fire,
+                Ability::Dexterity,
+                dc,
+                "Fire Trap",
+            );
+            crate::rules::apply_effects(&mut world, &area.effects);
+            if engine.last_d20().unwrap().natural_d20() != Some(1) {
+                continue;
+            }
+            // The temporary HP soaked 8 of the 10, so only 2 came off
+            assert_eq!(world.player_character.hit_points.current, start - 2);
+
+            let reroll = engine.resolve_reroll_d20(&world, id, None);
+            if engine.last_d20().unwrap().natural_d20() == Some(1) {
+                continue;
+            }
+            assert!(reroll.narrative.contains("2 less damage"));
+            crate::rules::apply_effects(&mut world, &reroll.effects);
+            assert_eq!(world.player_character.hit_points.current, start);
+            return;
+        }
+        panic!("never rolled a natural 1 and then something better");
+    }
 }
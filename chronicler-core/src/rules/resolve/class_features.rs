@@ -2,8 +2,8 @@
 
 use crate::rules::helpers::roll_with_fallback;
 use crate::rules::types::{Effect, Resolution};
-use crate::rules::RulesEngine;
-use crate::world::{CharacterClass, CharacterId, GameWorld};
+use crate::rules::{Message, RulesEngine};
+use crate::world::{CharacterClass, CharacterId, GameWorld, HitPoints, StatBlock};
 
 impl RulesEngine {
     pub(crate) fn resolve_use_rage(
@@ -45,23 +45,23 @@ impl RulesEngine {
             _ => 4,
         };
 
-        Resolution::new(format!(
-            "{} enters a RAGE! Gains: advantage on STR checks/saves, +{} rage damage to melee attacks, resistance to bludgeoning/piercing/slashing damage. Cannot cast spells or concentrate while raging.",
-            character.name, rage_damage
+        Resolution::new(self.locale().format(
+            Message::EntersRage,
+            &[
+                ("character", &character.name),
+                ("damage_bonus", &rage_damage),
+            ],
         ))
         .with_effect(Effect::RageStarted {
             character_id: world.player_character.id,
             damage_bonus: rage_damage,
         })
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Rage".to_string(),
-            description: format!("Entered rage (1 minute, +{rage_damage} damage)"),
-        })
-        .with_effect(Effect::FeatureUsed {
-            feature_name: "Rage".to_string(),
-            uses_remaining: 0,
-        })
+        .spend_resource(
+            &character.name,
+            "Rage",
+            format!("Entered rage (1 minute, +{rage_damage} damage)"),
+        )
+        .use_feature("Rage", 0)
     }
 
     pub(crate) fn resolve_end_rage(
@@ -89,11 +89,7 @@ impl RulesEngine {
                 character_id: world.player_character.id,
                 reason: reason_text.to_string(),
             })
-            .with_effect(Effect::ClassResourceUsed {
-                character_name: character.name.clone(),
-                resource_name: "Rage".to_string(),
-                description: reason_text.to_string(),
-            })
+            .spend_resource(&character.name, "Rage", reason_text)
     }
 
     pub(crate) fn resolve_use_ki(
@@ -130,11 +126,11 @@ impl RulesEngine {
             if points == 1 { "" } else { "s" },
             ability_description
         ))
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Ki Points".to_string(),
-            description: format!("Spent {points} ki for {ability}"),
-        })
+        .spend_resource(
+            &character.name,
+            "Ki Points",
+            format!("Spent {points} ki for {ability}"),
+        )
     }
 
     pub(crate) fn resolve_use_lay_on_hands(
@@ -177,11 +173,11 @@ impl RulesEngine {
             effects_text.join(", "),
             pool - total_cost
         ))
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Lay on Hands".to_string(),
-            description: format!("Used {total_cost} points on {target_name}"),
-        })
+        .spend_resource(
+            &character.name,
+            "Lay on Hands",
+            format!("Used {total_cost} points on {target_name}"),
+        )
     }
 
     pub(crate) fn resolve_use_divine_smite(
@@ -217,7 +213,8 @@ impl RulesEngine {
             base_dice.min(5)
         };
 
-        let damage_roll = roll_with_fallback(&format!("{total_dice}d8"), "2d8");
+        let damage_roll =
+            roll_with_fallback(&format!("{total_dice}d8"), "2d8", &mut *self.player_dice());
 
         let extra_text = if target_is_undead_or_fiend {
             " (extra damage vs undead/fiend)"
@@ -225,19 +222,17 @@ impl RulesEngine {
             ""
         };
 
-        Resolution::new(format!(
+        let narrative = format!(
             "{} channels divine power into their strike! Divine Smite deals {}d8 = {} radiant damage{}. (Level {} slot expended)",
             character.name, total_dice, damage_roll.total, extra_text, spell_slot_level
-        ))
-        .with_effect(Effect::DiceRolled {
-            roll: damage_roll,
-            purpose: "Divine Smite damage".to_string(),
-        })
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Divine Smite".to_string(),
-            description: format!("Used level {spell_slot_level} slot for smite"),
-        })
+        );
+        let spent = format!("Used level {spell_slot_level} slot for smite");
+        Resolution::new(narrative)
+            .with_effect(Effect::DiceRolled {
+                roll: damage_roll,
+                purpose: "Divine Smite damage".to_string(),
+            })
+            .spend_resource(&character.name, "Divine Smite", spent)
     }
 
     pub(crate) fn resolve_use_wild_shape(
@@ -246,7 +241,7 @@ impl RulesEngine {
         _character_id: CharacterId,
         beast_form: &str,
         beast_hp: i32,
-        _beast_ac: Option<u8>,
+        beast_ac: Option<u8>,
     ) -> Resolution {
         let character = &world.player_character;
 
@@ -282,20 +277,40 @@ impl RulesEngine {
             .unwrap_or(2);
         let duration_hours = druid_level / 2;
 
-        Resolution::new(format!(
-            "{} transforms into a {}! Beast form has {} HP. Duration: {} hour{}. Mental stats, proficiencies, and features retained. Cannot cast spells but can maintain concentration.",
-            character.name, beast_form, beast_hp, duration_hours,
+        // Known SRD beasts supply physical stats and attacks; the DM's HP/AC
+        // take precedence when given.
+        let mut stats = StatBlock::beast(beast_form).unwrap_or_else(|| {
+            let scores = &character.ability_scores;
+            StatBlock::new(beast_form, beast_hp, 10).with_abilities(
+                scores.strength,
+                scores.dexterity,
+                scores.constitution,
+            )
+        });
+        if beast_hp > 0 {
+            stats.hit_points = HitPoints::new(beast_hp);
+        }
+        if let Some(ac) = beast_ac {
+            stats.armor_class = ac;
+        }
+
+        let narrative = format!(
+            "{} transforms into a {}! Beast form has {} HP and AC {}. Attacks with {} ({}). Duration: {} hour{}. Mental stats, proficiencies, and features retained. Cannot cast spells but can maintain concentration.",
+            character.name, stats.name, stats.hit_points.maximum, stats.armor_class,
+            stats.attack_name, stats.attack_damage, duration_hours,
             if duration_hours == 1 { "" } else { "s" }
-        ))
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Wild Shape".to_string(),
-            description: format!("Transformed into {beast_form} ({beast_hp} HP)"),
-        })
-        .with_effect(Effect::FeatureUsed {
-            feature_name: "Wild Shape".to_string(),
-            uses_remaining: 0,
-        })
+        );
+        let spent = format!(
+            "Transformed into {} ({} HP)",
+            stats.name, stats.hit_points.maximum
+        );
+        Resolution::new(narrative)
+            .with_effect(Effect::WildShapeStarted {
+                character_id: world.player_character.id,
+                stats: stats.clone(),
+            })
+            .spend_resource(&character.name, "Wild Shape", spent)
+            .use_feature("Wild Shape", 0)
     }
 
     pub(crate) fn resolve_end_wild_shape(
@@ -340,11 +355,11 @@ impl RulesEngine {
             "{} reverts to their normal form. {}",
             character.name, reason_text
         ))
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Wild Shape".to_string(),
-            description: reason_text.to_string(),
-        });
+        .with_effect(Effect::WildShapeEnded {
+            character_id: world.player_character.id,
+            reason: reason_text.to_string(),
+        })
+        .spend_resource(&character.name, "Wild Shape", reason_text);
 
         // Apply excess damage if any
         if excess_damage > 0 {
@@ -374,6 +389,7 @@ impl RulesEngine {
             .features
             .iter()
             .find(|f| f.name == "Channel Divinity");
+        let mut uses_remaining = 0;
         if let Some(feature) = cd_feature {
             if let Some(ref uses) = feature.uses {
                 if uses.current == 0 {
@@ -382,6 +398,7 @@ impl RulesEngine {
                         character.name
                     ));
                 }
+                uses_remaining = uses.current - 1;
             }
         }
 
@@ -408,15 +425,8 @@ impl RulesEngine {
             "{} uses Channel Divinity: {}.{}",
             character.name, option_description, targets_text
         ))
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Channel Divinity".to_string(),
-            description: option.to_string(),
-        })
-        .with_effect(Effect::FeatureUsed {
-            feature_name: "Channel Divinity".to_string(),
-            uses_remaining: 0,
-        })
+        .spend_resource(&character.name, "Channel Divinity", option)
+        .use_feature("Channel Divinity", uses_remaining)
     }
 
     pub(crate) fn resolve_use_bardic_inspiration(
@@ -444,19 +454,14 @@ impl RulesEngine {
             }
         }
 
-        Resolution::new(format!(
+        let narrative = format!(
             "{} inspires {} with a rousing performance! {} gains a {} Bardic Inspiration die they can add to one ability check, attack roll, or saving throw within the next 10 minutes.",
             character.name, target_name, target_name, die_size
-        ))
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Bardic Inspiration".to_string(),
-            description: format!("Inspired {target_name} with a {die_size}"),
-        })
-        .with_effect(Effect::FeatureUsed {
-            feature_name: "Bardic Inspiration".to_string(),
-            uses_remaining: 0,
-        })
+        );
+        let spent = format!("Inspired {target_name} with a {die_size}");
+        Resolution::new(narrative)
+            .spend_resource(&character.name, "Bardic Inspiration", spent)
+            .use_feature("Bardic Inspiration", 0)
     }
 
     pub(crate) fn resolve_use_action_surge(
@@ -474,19 +479,19 @@ impl RulesEngine {
             ));
         }
 
-        Resolution::new(format!(
+        let resolution = Resolution::new(format!(
             "{} surges with renewed vigor! Takes an additional action this turn: {}",
             character.name, action_taken
         ))
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Action Surge".to_string(),
-            description: action_taken.to_string(),
-        })
-        .with_effect(Effect::FeatureUsed {
-            feature_name: "Action Surge".to_string(),
-            uses_remaining: 0,
-        })
+        .spend_resource(&character.name, "Action Surge", action_taken)
+        .use_feature("Action Surge", 0);
+        if world.combat.is_some() {
+            resolution.with_effect(Effect::ExtraActionGained {
+                character_id: character.id,
+            })
+        } else {
+            resolution
+        }
     }
 
     pub(crate) fn resolve_use_second_wind(
@@ -511,7 +516,11 @@ impl RulesEngine {
             .map(|c| c.level)
             .unwrap_or(1);
 
-        let healing_roll = roll_with_fallback(&format!("1d10+{fighter_level}"), "1d10+1");
+        let healing_roll = roll_with_fallback(
+            &format!("1d10+{fighter_level}"),
+            "1d10+1",
+            &mut *self.player_dice(),
+        );
         let healing = healing_roll.total;
 
         let new_hp = (character.hit_points.current + healing).min(character.hit_points.maximum);
@@ -531,15 +540,12 @@ impl RulesEngine {
             new_max: character.hit_points.maximum,
             dropped_to_zero: false,
         })
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Second Wind".to_string(),
-            description: format!("Healed {healing} HP"),
-        })
-        .with_effect(Effect::FeatureUsed {
-            feature_name: "Second Wind".to_string(),
-            uses_remaining: 0,
-        })
+        .spend_resource(
+            &character.name,
+            "Second Wind",
+            format!("Healed {healing} HP"),
+        )
+        .use_feature("Second Wind", 0)
     }
 
     pub(crate) fn resolve_use_sorcery_points(
@@ -568,11 +574,11 @@ impl RulesEngine {
                     "{} converts {} sorcery points into a level {} spell slot.",
                     character.name, cost, level
                 ))
-                .with_effect(Effect::ClassResourceUsed {
-                    character_name: character.name.clone(),
-                    resource_name: "Sorcery Points".to_string(),
-                    description: format!("Created level {level} spell slot"),
-                });
+                .spend_resource(
+                    &character.name,
+                    "Sorcery Points",
+                    format!("Created level {level} spell slot"),
+                );
             }
         }
 
@@ -582,11 +588,11 @@ impl RulesEngine {
                     "{} converts a level {} spell slot into {} sorcery points.",
                     character.name, level, level
                 ))
-                .with_effect(Effect::ClassResourceUsed {
-                    character_name: character.name.clone(),
-                    resource_name: "Sorcery Points".to_string(),
-                    description: format!("Gained {level} points from slot"),
-                });
+                .spend_resource(
+                    &character.name,
+                    "Sorcery Points",
+                    format!("Gained {level} points from slot"),
+                );
             }
         }
 
@@ -620,11 +626,11 @@ impl RulesEngine {
             points,
             if points == 1 { "" } else { "s" }
         ))
-        .with_effect(Effect::ClassResourceUsed {
-            character_name: character.name.clone(),
-            resource_name: "Sorcery Points".to_string(),
-            description: format!("Used {points} for {metamagic}"),
-        })
+        .spend_resource(
+            &character.name,
+            "Sorcery Points",
+            format!("Used {points} for {metamagic}"),
+        )
     }
 }
 
@@ -1052,6 +1058,85 @@ mod tests {
         assert!(resolution.effects.is_empty());
     }
 
+    #[test]
+    fn test_wild_shape_swaps_ac_and_hp() {
+        let character = create_sample_druid("Radagast");
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let druid_ac = world.player_character.current_ac();
+        let druid_hp = world.player_character.hit_points.current;
+
+        let resolution = engine.resolve_use_wild_shape(
+            &world,
+            world.player_character.id,
+            "Brown Bear",
+            34,
+            None,
+        );
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+
+        let pc = &world.player_character;
+        assert_eq!(
+            pc.class_resources.wild_shape_form.as_deref(),
+            Some("Brown Bear")
+        );
+        assert_eq!(pc.current_ac(), 11);
+        assert_ne!(pc.current_ac(), druid_ac);
+        assert_eq!(pc.class_resources.wild_shape_hp, Some(34));
+        assert_ne!(pc.class_resources.wild_shape_hp, Some(druid_hp));
+
+        // Physical scores come from the bear, mental scores stay the druid's
+        let scores = pc.effective_ability_scores();
+        assert_eq!(scores.strength, 19);
+        assert_eq!(scores.wisdom, pc.ability_scores.wisdom);
+
+        // Damage hits the beast form, not the druid
+        let resolution = engine.resolve_damage(
+            &world,
+            world.player_character.id,
+            10,
+            crate::rules::DamageType::Slashing,
+            "goblin",
+        );
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        assert_eq!(
+            world.player_character.class_resources.wild_shape_hp,
+            Some(24)
+        );
+        assert_eq!(world.player_character.hit_points.current, druid_hp);
+    }
+
+    #[test]
+    fn test_wild_shape_excess_damage_carries_over() {
+        let character = create_sample_druid("Radagast");
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let druid_hp = world.player_character.hit_points.current;
+
+        let resolution =
+            engine.resolve_use_wild_shape(&world, world.player_character.id, "Wolf", 11, None);
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.player_character.current_ac(), 13);
+
+        // 15 damage against an 11 HP wolf: 4 carries over to the druid
+        let resolution = engine.resolve_damage(
+            &world,
+            world.player_character.id,
+            15,
+            crate::rules::DamageType::Piercing,
+            "ogre",
+        );
+        assert!(resolution
+            .narrative
+            .contains("reverts to their normal form"));
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+
+        let pc = &world.player_character;
+        assert!(pc.class_resources.wild_shape_stats.is_none());
+        assert!(pc.class_resources.wild_shape_form.is_none());
+        assert_eq!(pc.hit_points.current, druid_hp - 4);
+    }
+
     // ========== Channel Divinity Tests (Cleric/Paladin) ==========
 
     #[test]
@@ -1166,6 +1251,33 @@ mod tests {
         assert!(resolution.narrative.contains("Attack action"));
     }
 
+    #[test]
+    fn test_action_surge_in_combat_grants_an_action() {
+        let character = create_sample_fighter("Roland");
+        let mut world = GameWorld::new("Test", character);
+        world.combat = Some(crate::world::CombatState::new());
+        let engine = RulesEngine::new();
+        let id = world.player_character.id;
+
+        let resolution = engine.resolve_use_action_surge(&world, id, "Attack action");
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::ExtraActionGained { .. })));
+
+        let used = Effect::ActionUsed {
+            character_id: id,
+            action: crate::world::ActionType::Action,
+            spell_level: None,
+        };
+        crate::rules::apply_effect(&mut world, &used);
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        let combat = world.combat.as_ref().unwrap();
+        assert!(!combat
+            .turn_actions(id)
+            .is_used(crate::world::ActionType::Action));
+    }
+
     #[test]
     fn test_use_action_surge_already_used() {
         let mut character = create_sample_fighter("Roland");
@@ -1,10 +1,16 @@
 //! Combat-related resolution methods.
 
-use crate::dice::{self, Advantage, DiceExpression};
-use crate::rules::helpers::{roll_with_fallback, sneak_attack_dice};
-use crate::rules::types::{CombatantInit, DamageType, Effect, Resolution};
-use crate::rules::RulesEngine;
-use crate::world::{Ability, CharacterClass, CharacterId, Condition, GameWorld};
+use crate::dice::{Advantage, RollResult};
+use crate::rules::engine::D20Stakes;
+use crate::rules::helpers::{
+    reroll_low_damage_dice, roll_feature_recharges, roll_with_fallback, sneak_attack_dice,
+};
+use crate::rules::types::{AreaTarget, CombatantInit, DamageType, Effect, Intent, Resolution};
+use crate::rules::{Message, RulesEngine};
+use crate::world::{
+    Ability, ActionType, Character, CharacterClass, CharacterId, Concentration, Condition,
+    DeathSaves, DurationTiming, FightingStyle, GameWorld, LightLevel, StatBlock, WeaponProperty,
+};
 
 impl RulesEngine {
     pub(crate) fn resolve_attack(
@@ -14,8 +20,63 @@ impl RulesEngine {
         target_id: CharacterId,
         weapon_name: &str,
         advantage: Advantage,
+    ) -> Resolution {
+        self.resolve_weapon_attack(world, target_id, weapon_name, advantage, false)
+    }
+
+    /// Resolve a bonus-action attack with the light weapon held in the off hand.
+    pub(crate) fn resolve_offhand_attack(
+        &self,
+        world: &GameWorld,
+        _attacker_id: CharacterId,
+        target_id: CharacterId,
+        advantage: Advantage,
+    ) -> Resolution {
+        let attacker = &world.player_character;
+
+        let Some(off_hand) = attacker.equipment.off_hand.as_ref() else {
+            return Resolution::new(format!(
+                "{} has no weapon in their off hand!",
+                attacker.name
+            ));
+        };
+        let is_light = crate::items::get_weapon(&off_hand.name)
+            .is_some_and(|w| w.properties.contains(&WeaponProperty::Light));
+        if !is_light {
+            return Resolution::new(format!(
+                "{} can only make an off-hand attack with a light weapon, not {}",
+                attacker.name, off_hand.name
+            ));
+        }
+
+        self.resolve_weapon_attack(world, target_id, &off_hand.name, advantage, true)
+    }
+
+    /// Shared attack resolution. Off-hand attacks add no positive ability
+    /// modifier to damage unless the attacker has the Two-Weapon Fighting style.
+    fn resolve_weapon_attack(
+        &self,
+        world: &GameWorld,
+        target_id: CharacterId,
+        weapon_name: &str,
+        advantage: Advantage,
+        off_hand: bool,
     ) -> Resolution {
         let attacker = &world.player_character;
+        let replay_intent = if off_hand {
+            Intent::OffhandAttack {
+                attacker_id: attacker.id,
+                target_id,
+                advantage,
+            }
+        } else {
+            Intent::Attack {
+                attacker_id: attacker.id,
+                target_id,
+                weapon_name: weapon_name.to_string(),
+                advantage,
+            }
+        };
 
         // Unconscious characters cannot attack
         if attacker.has_condition(Condition::Unconscious) {
@@ -25,6 +86,53 @@ impl RulesEngine {
             ));
         }
 
+        // In combat the attack spends part of the turn: an off-hand attack
+        // takes the bonus action, and the first attack of each Attack action
+        // takes an action (Extra Attack allows more attacks per action)
+        let mut turn_effects = Vec::new();
+        if let Some(ref combat) = world.combat {
+            let made = combat
+                .attacks_this_turn
+                .get(&attacker.id)
+                .copied()
+                .unwrap_or(0);
+            let action = if off_hand {
+                Some(ActionType::BonusAction)
+            } else if made % attacker.attacks_per_action() == 0 {
+                Some(ActionType::Action)
+            } else {
+                None
+            };
+            if let Some(action) = action {
+                if !self.replaying() && combat.turn_actions(attacker.id).is_used(action) {
+                    return Resolution::new(format!(
+                        "{} can't attack: {} already used their {} this turn.",
+                        attacker.name,
+                        attacker.name,
+                        action.name()
+                    ));
+                }
+                turn_effects.push(Effect::ActionUsed {
+                    character_id: attacker.id,
+                    action,
+                    spell_level: None,
+                });
+            }
+            if !off_hand {
+                turn_effects.push(Effect::AttackMade {
+                    character_id: attacker.id,
+                });
+            }
+        }
+
+        // Attacking in darkness the attacker can't see through imposes disadvantage
+        let in_darkness = attacker.perceived_light(world.light_level()) == LightLevel::Darkness;
+        let advantage = if in_darkness {
+            advantage.combine(Advantage::Disadvantage)
+        } else {
+            advantage
+        };
+
         // Get target AC from combat state, or use player AC if targeting self
         let target_ac = if target_id == world.player_character.id {
             world.player_character.current_ac()
@@ -41,24 +149,64 @@ impl RulesEngine {
 
         // Look up weapon from database or equipped weapon
         let weapon = crate::items::get_weapon(weapon_name);
-        let equipped_weapon = attacker.equipment.main_hand.as_ref();
-
-        // Determine the weapon properties
-        let (damage_dice, is_finesse, is_ranged) = if let Some(w) = &weapon {
-            (w.damage_dice.clone(), w.is_finesse(), w.is_ranged())
-        } else if let Some(w) = equipped_weapon {
+        let equipped_weapon = if off_hand {
+            None
+        } else {
+            attacker.equipment.main_hand.as_ref()
+        };
+        let wild_shape = attacker.class_resources.wild_shape_stats.as_ref();
+
+        // Prefer the equipped weapon when it is the one named, so its magical
+        // and silvered flags carry through to resistance checks.
+        let weapon_item = equipped_weapon
+            .filter(|w| w.base.name.eq_ignore_ascii_case(weapon_name))
+            .or(weapon.as_ref())
+            .or(equipped_weapon);
+
+        // Determine the weapon properties. A Wild Shaped druid attacks with the
+        // beast's natural weapons, using the better of its STR or DEX.
+        let (damage_dice, is_finesse, is_ranged) = if let Some(beast) = wild_shape {
+            (beast.attack_damage.clone(), true, false)
+        } else if let Some(w) = weapon_item {
             (w.damage_dice.clone(), w.is_finesse(), w.is_ranged())
         } else {
             // Default to unarmed strike
             ("1".to_string(), false, false)
         };
+        let (damage_type, mut magical, silvered) = match weapon_item {
+            Some(w) if wild_shape.is_none() => (w.damage_type.into(), w.base.magical, w.silvered),
+            _ => (DamageType::Bludgeoning, false, false),
+        };
+        if off_hand {
+            magical |= attacker
+                .equipment
+                .off_hand
+                .as_ref()
+                .is_some_and(|i| i.magical);
+        }
+
+        // Ranged weapons that fire ammunition need a unit to shoot
+        let ammunition = weapon_item
+            .filter(|_| wild_shape.is_none())
+            .and_then(|w| w.ammunition.as_deref())
+            .map(|ammo| (ammo, attacker.inventory.ammunition_count(ammo)));
+        if let (Some((ammo, 0)), false) = (ammunition, self.replaying()) {
+            return Resolution::new(format!(
+                "{} has no {} left to fire from the {}!",
+                attacker.name,
+                ammo.to_lowercase(),
+                weapon_name
+            ));
+        }
 
         // Determine which ability modifier to use
         // Ranged: DEX only
         // Finesse: higher of STR or DEX
         // Melee: STR only
-        let str_mod = attacker.ability_scores.modifier(Ability::Strength);
-        let dex_mod = attacker.ability_scores.modifier(Ability::Dexterity);
+        let scores = attacker.effective_ability_scores();
+        let str_mod = scores.modifier(Ability::Strength);
+        let dex_mod = scores.modifier(Ability::Dexterity);
+        let weapon_name = wild_shape.map_or(weapon_name, |beast| beast.attack_name.as_str());
 
         // Track if this is a strength-based melee attack (for rage bonus)
         let is_strength_melee = if is_ranged {
@@ -77,23 +225,50 @@ impl RulesEngine {
             str_mod
         };
 
-        let attack_mod = ability_mod + attacker.proficiency_bonus();
-        let attack_expr = DiceExpression::parse(&format!("1d20+{attack_mod}")).unwrap();
-        let attack_roll = attack_expr.roll_with_advantage(advantage);
+        // Fighting styles that depend on how the weapon is wielded
+        let styles = &attacker.fighting_styles;
+        let wielded = weapon_item.filter(|_| wild_shape.is_none());
+        let archery = is_ranged && wielded.is_some() && styles.contains(&FightingStyle::Archery);
+        let dueling = !off_hand
+            && !is_ranged
+            && styles.contains(&FightingStyle::Dueling)
+            && wielded.is_some_and(|w| !w.is_two_handed())
+            && attacker.equipment.off_hand.is_none();
+        let great_weapon_fighting = !off_hand
+            && !is_ranged
+            && styles.contains(&FightingStyle::GreatWeaponFighting)
+            && wielded.is_some_and(|w| {
+                w.is_two_handed()
+                    || (w.versatile_damage().is_some()
+                        && attacker.equipment.shield.is_none()
+                        && attacker.equipment.off_hand.is_none())
+            });
+
+        let attack_mod = ability_mod + attacker.proficiency_bonus() + if archery { 2 } else { 0 };
+        let attack_roll = self.roll_d20_check(attack_mod.into(), advantage);
 
+        let hand = if off_hand { " (off hand)" } else { "" };
+        let darkness = if in_darkness {
+            " [darkness disadvantage]"
+        } else {
+            ""
+        };
         let mut resolution = Resolution::new(format!(
-            "{} attacks with {} (roll: {} vs AC {})",
-            attacker.name, weapon_name, attack_roll.total, target_ac
-        ));
+            "{} attacks with {}{} (roll: {} vs AC {}){}",
+            attacker.name, weapon_name, hand, attack_roll.total, target_ac, darkness
+        ))
+        .with_effects(turn_effects);
 
         resolution = resolution.with_effect(Effect::DiceRolled {
             roll: attack_roll.clone(),
             purpose: format!("Attack with {weapon_name}"),
         });
 
-        // Natural 1 always misses, natural 20 always hits (and crits)
-        let hits = !attack_roll.is_fumble()
-            && (attack_roll.total >= target_ac as i32 || attack_roll.is_critical());
+        // Natural 1 always misses; a critical (natural 20, or lower with
+        // Improved Critical) always hits
+        let is_critical = attack_roll.is_critical_on(attacker.crit_range());
+        let hits =
+            !attack_roll.is_fumble() && (attack_roll.total >= target_ac as i32 || is_critical);
 
         if hits {
             resolution = resolution.with_effect(Effect::AttackHit {
@@ -101,7 +276,7 @@ impl RulesEngine {
                 target_name: "target".to_string(),
                 attack_roll: attack_roll.total,
                 target_ac,
-                is_critical: attack_roll.is_critical(),
+                is_critical,
             });
 
             // Roll damage with ability modifier and rage bonus (if applicable)
@@ -110,80 +285,103 @@ impl RulesEngine {
             } else {
                 0
             };
-            let total_mod = ability_mod as i32 + rage_bonus;
-
-            let damage_expr = if attack_roll.is_critical() {
-                // Critical hit: double the number of dice
-                // Parse "XdY" and produce "2XdY"
-                let doubled_dice = if let Some(d_pos) = damage_dice.find('d') {
-                    let num_dice: i32 = damage_dice[..d_pos].parse().unwrap_or(1);
-                    let die_type = &damage_dice[d_pos..];
-                    format!("{}{}", num_dice * 2, die_type)
-                } else {
-                    // Not a dice expression, just double the flat value
-                    let flat: i32 = damage_dice.parse().unwrap_or(1);
-                    format!("{}", flat * 2)
-                };
-                format!("{doubled_dice}+{total_mod}")
+            // Off-hand attacks only add a negative modifier, unless the
+            // attacker has the Two-Weapon Fighting style
+            let damage_mod = if off_hand && !styles.contains(&FightingStyle::TwoWeaponFighting) {
+                ability_mod.min(0)
+            } else {
+                ability_mod
+            };
+            let dueling_bonus = if dueling { 2 } else { 0 };
+            let total_mod = damage_mod as i32 + rage_bonus + dueling_bonus;
+
+            let damage_expr = if is_critical {
+                let crit_dice = world.house_rules.crit_rule.critical_dice(&damage_dice);
+                format!("{crit_dice}+{total_mod}")
             } else {
                 format!("{damage_dice}+{total_mod}")
             };
-            let damage_roll = roll_with_fallback(&damage_expr, "1d4");
+            let mut damage_roll = roll_with_fallback(&damage_expr, "1d4", &mut *self.player_dice());
+            if great_weapon_fighting {
+                reroll_low_damage_dice(&mut damage_roll, &mut *self.player_dice());
+            }
             resolution = resolution.with_effect(Effect::DiceRolled {
                 roll: damage_roll.clone(),
                 purpose: "Damage".to_string(),
             });
 
-            // Check for Sneak Attack (Rogue feature)
-            let rogue_level = attacker
-                .classes
-                .iter()
-                .find(|c| c.class == CharacterClass::Rogue)
-                .map(|c| c.level)
-                .unwrap_or(0);
-
-            if rogue_level > 0 && (is_finesse || is_ranged) {
-                // Check if sneak attack conditions are met:
-                // 1. Has advantage, OR
-                // 2. An ally is engaged with the target (in melee range)
-                let has_advantage = matches!(advantage, Advantage::Advantage);
-
-                // Check for ally adjacent to target (any non-player ally in combat)
-                let has_ally_adjacent = if let Some(ref combat) = world.combat {
-                    combat
-                        .combatants
-                        .iter()
-                        .any(|c| c.is_ally && !c.is_player && c.current_hp > 0 && c.id != target_id)
-                } else {
-                    false
-                };
+            // Sneak Attack dice are added on top of the weapon damage
+            let sneak_attack = self.roll_sneak_attack(
+                world,
+                target_id,
+                advantage,
+                is_finesse || is_ranged,
+                is_critical,
+            );
+
+            let sneak_total = sneak_attack.as_ref().map_or(0, |(roll, _)| roll.total);
+            let raw_damage = damage_roll.total + sneak_total;
+            let target = world
+                .combat
+                .as_ref()
+                .and_then(|combat| combat.combatants.iter().find(|c| c.id == target_id));
+            let resisted = target.is_some_and(|target| {
+                target
+                    .resistances
+                    .iter()
+                    .any(|r| r.applies_to(damage_type, magical, silvered))
+            });
+            let dealt = if resisted { raw_damage / 2 } else { raw_damage };
+
+            let mut details = Vec::new();
+            if sneak_attack.is_some() {
+                details.push(format!(
+                    "{} + {} Sneak Attack",
+                    damage_roll.total, sneak_total
+                ));
+            } else if resisted {
+                details.push(raw_damage.to_string());
+            }
+            if resisted {
+                details.push(format!("halved by {} resistance", damage_type.name()));
+            }
+            if details.is_empty() {
+                resolution
+                    .narrative
+                    .push_str(&format!(" Hit for {dealt} damage."));
+            } else {
+                resolution.narrative.push_str(&format!(
+                    " Hit for {dealt} damage ({}).",
+                    details.join(", ")
+                ));
+            }
 
-                // Check if sneak attack hasn't been used this turn
-                let sneak_attack_available = if let Some(ref combat) = world.combat {
-                    !combat.sneak_attack_used.contains(&attacker.id)
-                } else {
-                    true // Outside combat, allow it
-                };
+            // Track the hit against the target's HP in the initiative order
+            if let Some(target) = target.filter(|t| !t.is_player) {
+                resolution = resolution.with_effect(Effect::CombatantHpChanged {
+                    id: target.id,
+                    name: target.name.clone(),
+                    amount: -dealt,
+                    new_current: (target.current_hp - dealt).max(0),
+                });
+            }
 
-                if sneak_attack_available && (has_advantage || has_ally_adjacent) {
-                    let sneak_dice = sneak_attack_dice(rogue_level);
-                    let sneak_expr = if attack_roll.is_critical() {
-                        format!("{}d6", sneak_dice * 2) // Double dice on crit
-                    } else {
-                        format!("{}d6", sneak_dice)
-                    };
-                    let sneak_roll = roll_with_fallback(&sneak_expr, "1d6");
-                    resolution = resolution.with_effect(Effect::DiceRolled {
-                        roll: sneak_roll.clone(),
+            if let Some((sneak_roll, sneak_dice)) = sneak_attack {
+                resolution = resolution
+                    .with_effect(Effect::DiceRolled {
+                        roll: sneak_roll,
                         purpose: "Sneak Attack".to_string(),
-                    });
-                    resolution = resolution.with_effect(Effect::SneakAttackUsed {
+                    })
+                    .with_effect(Effect::SneakAttackUsed {
                         character_id: attacker.id,
                         damage_dice: sneak_dice,
                     });
-                }
             }
         } else {
+            // A reroll that turns the miss into a hit replays the attack
+            self.stake_last_d20(D20Stakes::Attack {
+                intent: replay_intent,
+            });
             resolution = resolution.with_effect(Effect::AttackMissed {
                 attacker_name: attacker.name.clone(),
                 target_name: "target".to_string(),
@@ -192,9 +390,76 @@ impl RulesEngine {
             });
         }
 
+        if let Some((ammo, count)) = ammunition {
+            resolution = resolution.with_effect(Effect::AmmunitionSpent {
+                ammunition: ammo.to_string(),
+                remaining: count.saturating_sub(1),
+                recoverable: !hits,
+            });
+        }
+
         resolution
     }
 
+    /// Roll Sneak Attack dice if the attacker is a Rogue who qualifies: a
+    /// finesse or ranged weapon, advantage or an ally engaged with the target,
+    /// and Sneak Attack not yet used this turn. Returns the roll and the
+    /// number of d6s (before critical doubling).
+    fn roll_sneak_attack(
+        &self,
+        world: &GameWorld,
+        target_id: CharacterId,
+        advantage: Advantage,
+        finesse_or_ranged: bool,
+        is_critical: bool,
+    ) -> Option<(RollResult, u8)> {
+        let attacker = &world.player_character;
+        let rogue_level = attacker
+            .classes
+            .iter()
+            .find(|c| c.class == CharacterClass::Rogue)
+            .map(|c| c.level)
+            .unwrap_or(0);
+
+        if rogue_level == 0 || !finesse_or_ranged {
+            return None;
+        }
+
+        let has_advantage = matches!(advantage, Advantage::Advantage);
+
+        // Check for ally adjacent to target (any non-player ally in combat)
+        let has_ally_adjacent = world.combat.as_ref().is_some_and(|combat| {
+            combat
+                .combatants
+                .iter()
+                .any(|c| c.is_ally && !c.is_player && c.current_hp > 0 && c.id != target_id)
+        });
+
+        // Outside combat there is no turn structure, so always allow it
+        let sneak_attack_available = world
+            .combat
+            .as_ref()
+            .is_none_or(|combat| !combat.sneak_attack_used.contains(&attacker.id));
+
+        if !sneak_attack_available || !(has_advantage || has_ally_adjacent) {
+            return None;
+        }
+
+        let sneak_dice = sneak_attack_dice(rogue_level);
+        let sneak_expr = if is_critical {
+            world
+                .house_rules
+                .crit_rule
+                .critical_dice(&format!("{sneak_dice}d6"))
+        } else {
+            format!("{}d6", sneak_dice)
+        };
+        Some((
+            roll_with_fallback(&sneak_expr, "1d6", &mut *self.player_dice()),
+            sneak_dice,
+        ))
+    }
+
     pub(crate) fn resolve_damage(
         &self,
         world: &GameWorld,
@@ -202,6 +467,258 @@ impl RulesEngine {
         amount: i32,
         damage_type: DamageType,
         source: &str,
+    ) -> Resolution {
+        // Resistances (racial or otherwise) halve the damage before it lands
+        let resisted = world
+            .player_character
+            .resistances
+            .iter()
+            .any(|r| r.applies_to(damage_type, false, false));
+        let amount = if resisted { amount / 2 } else { amount };
+
+        // Damage to a Wild Shaped druid hits the beast form first
+        let mut resolution =
+            if let Some(ref stats) = world.player_character.class_resources.wild_shape_stats {
+                self.resolve_wild_shape_damage(world, stats, target_id, amount, damage_type, source)
+            } else {
+                self.resolve_damage_to_normal_form(world, target_id, amount, damage_type, source)
+            };
+
+        if resisted {
+            resolution
+                .narrative
+                .push_str(&format!(" (halved by {} resistance)", damage_type.name()));
+        }
+        resolution
+    }
+
+    /// Resolve damage to every creature in an area. The damage is rolled once;
+    /// each target makes its own save and takes half on a success. A player
+    /// with Evasion takes nothing on a successful DEX save and half on a failure.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn resolve_area_damage(
+        &self,
+        world: &GameWorld,
+        targets: &[AreaTarget],
+        damage_dice: &str,
+        damage_type: DamageType,
+        save_ability: Ability,
+        dc: i32,
+        source: &str,
+    ) -> Resolution {
+        if targets.is_empty() {
+            return Resolution::new(format!("{source} catches no one in its area."));
+        }
+        let Ok(damage_roll) = self.roll_dm(damage_dice) else {
+            return Resolution::new(format!("Invalid damage dice: {damage_dice}"));
+        };
+        let total = damage_roll.total.max(0);
+
+        let mut resolution = Resolution::new(format!(
+            "{} deals {} {} damage (DC {} {} save for half).",
+            source,
+            total,
+            damage_type.name(),
+            dc,
+            save_ability.abbreviation()
+        ))
+        .with_effect(Effect::DiceRolled {
+            roll: damage_roll,
+            purpose: format!("{source} damage"),
+        });
+
+        for target in targets {
+            if target.id == world.player_character.id {
+                let player = &world.player_character;
+                let evasion = save_ability == Ability::Dexterity
+                    && player.features.iter().any(|f| f.name == "Evasion");
+
+                // Unconscious characters automatically fail STR and DEX saves
+                let auto_fail = player.has_condition(Condition::Unconscious)
+                    && matches!(save_ability, Ability::Strength | Ability::Dexterity);
+                let saved = if auto_fail {
+                    resolution
+                        .narrative
+                        .push_str(&format!(" {} is unconscious and fails.", player.name));
+                    false
+                } else {
+                    let modifier = player.saving_throw_modifier(save_ability);
+                    let advantage = player.save_advantage(save_ability, source);
+                    let roll = self.roll_d20_check(modifier.into(), advantage);
+                    let saved = roll.total >= dc;
+                    resolution.narrative.push_str(&format!(
+                        " {} {} ({} vs DC {}).",
+                        player.name,
+                        if saved { "saves" } else { "fails" },
+                        roll.total,
+                        dc
+                    ));
+                    resolution.effects.push(Effect::DiceRolled {
+                        roll,
+                        purpose: format!("{} save", save_ability.abbreviation()),
+                    });
+                    saved
+                };
+
+                let amount = area_damage_taken(total, saved, evasion);
+                if amount == 0 {
+                    let reason = if evasion { " thanks to Evasion" } else { "" };
+                    resolution
+                        .narrative
+                        .push_str(&format!(" {} takes no damage{}.", player.name, reason));
+                    continue;
+                }
+                let damage = self.resolve_damage(world, target.id, amount, damage_type, source);
+
+                // A reroll that turns the save around gives back the HP a
+                // success would have kept, unless the failure was fatal
+                if !saved && !auto_fail && !damage.effects.iter().any(is_death) {
+                    let spared = area_damage_taken(total, true, evasion);
+                    let lost_on_save = if spared == 0 {
+                        0
+                    } else {
+                        let save =
+                            self.resolve_damage(world, target.id, spared, damage_type, source);
+                        hp_lost(player, &save)
+                    };
+                    let refund = hp_lost(player, &damage) - lost_on_save;
+                    if refund > 0 {
+                        self.stake_last_d20(D20Stakes::AreaSave { dc, refund });
+                    }
+                }
+
+                resolution.narrative.push(' ');
+                resolution.narrative.push_str(&damage.narrative);
+                resolution.effects.extend(damage.effects);
+            } else {
+                let Some(combatant) = world
+                    .combat
+                    .as_ref()
+                    .and_then(|combat| combat.combatants.iter().find(|c| c.id == target.id))
+                else {
+                    continue;
+                };
+
+                let roll = self.roll_dm_d20_check(target.save_modifier.into(), Advantage::Normal);
+                let saved = roll.total >= dc;
+                let resisted = combatant
+                    .resistances
+                    .iter()
+                    .any(|r| r.applies_to(damage_type, false, false));
+                let mut amount = area_damage_taken(total, saved, false);
+                if resisted {
+                    amount /= 2;
+                }
+                let new_current = (combatant.current_hp - amount).max(0);
+
+                resolution.narrative.push_str(&format!(
+                    " {} {} ({} vs DC {}) and takes {} {} damage{} (HP: {}/{}).",
+                    combatant.name,
+                    if saved { "saves" } else { "fails" },
+                    roll.total,
+                    dc,
+                    amount,
+                    damage_type.name(),
+                    if resisted {
+                        ", halved by resistance"
+                    } else {
+                        ""
+                    },
+                    new_current,
+                    combatant.max_hp
+                ));
+                resolution.effects.push(Effect::DiceRolled {
+                    roll,
+                    purpose: format!("{} {} save", combatant.name, save_ability.abbreviation()),
+                });
+                resolution.effects.push(Effect::CombatantHpChanged {
+                    id: combatant.id,
+                    name: combatant.name.clone(),
+                    amount: -amount,
+                    new_current,
+                });
+            }
+        }
+
+        resolution
+    }
+
+    /// Apply damage to the beast form. If the beast drops to 0 HP the druid
+    /// reverts and any excess damage carries over to their normal form.
+    fn resolve_wild_shape_damage(
+        &self,
+        world: &GameWorld,
+        stats: &StatBlock,
+        target_id: CharacterId,
+        amount: i32,
+        damage_type: DamageType,
+        source: &str,
+    ) -> Resolution {
+        let target = &world.player_character;
+
+        let mut hp = stats.hit_points.clone();
+        let result = hp.take_damage(amount);
+
+        if !result.dropped_to_zero {
+            return Resolution::new(format!(
+                "{} takes {} {} damage from {} in {} form (Beast HP: {}/{})",
+                target.name,
+                amount,
+                damage_type.name(),
+                source,
+                stats.name,
+                hp.current,
+                hp.maximum
+            ))
+            .with_effect(Effect::WildShapeHpChanged {
+                character_id: target_id,
+                amount: -amount,
+                new_current: hp.current,
+            });
+        }
+
+        let excess = -hp.current;
+        let mut resolution = Resolution::new(format!(
+            "{} takes {} {} damage from {}. The {} form drops to 0 HP and {} reverts to their normal form!",
+            target.name,
+            amount,
+            damage_type.name(),
+            source,
+            stats.name,
+            target.name
+        ))
+        .with_effect(Effect::WildShapeHpChanged {
+            character_id: target_id,
+            amount: -amount,
+            new_current: 0,
+        })
+        .with_effect(Effect::WildShapeEnded {
+            character_id: target_id,
+            reason: "Beast form dropped to 0 HP".to_string(),
+        });
+
+        if excess > 0 {
+            let carried = self.resolve_damage_to_normal_form(
+                world,
+                target_id,
+                excess,
+                damage_type,
+                &format!("{source} (excess from Wild Shape)"),
+            );
+            resolution.narrative = format!("{} {}", resolution.narrative, carried.narrative);
+            resolution = resolution.with_effects(carried.effects);
+        }
+
+        resolution
+    }
+
+    fn resolve_damage_to_normal_form(
+        &self,
+        world: &GameWorld,
+        target_id: CharacterId,
+        amount: i32,
+        damage_type: DamageType,
+        source: &str,
     ) -> Resolution {
         let target = &world.player_character;
 
@@ -266,39 +783,47 @@ impl RulesEngine {
         let instant_death = result.dropped_to_zero && overflow_damage >= hp.maximum;
 
         // Build narrative with HP status so DM knows the character's state
-        let hp_status = if instant_death {
-            format!(
-                " (INSTANT DEATH! Massive damage ({} overflow) exceeds max HP of {})",
-                overflow_damage, hp.maximum
+        let locale = self.locale();
+        let status = |message| {
+            locale.format(
+                message,
+                &[
+                    ("current", &hp.current),
+                    ("max", &hp.maximum),
+                    ("overflow", &overflow_damage),
+                ],
             )
+        };
+        let hp_status = if instant_death {
+            status(Message::InstantDeath)
         } else if result.dropped_to_zero {
-            format!(
-                " (HP: 0/{} - UNCONSCIOUS! Character falls and begins making death saving throws)",
-                hp.maximum
-            )
+            status(Message::DroppedToZero)
         } else if hp.current <= hp.maximum / 4 {
-            format!(" (HP: {}/{} - critically wounded)", hp.current, hp.maximum)
+            status(Message::CriticallyWounded)
         } else if hp.current <= hp.maximum / 2 {
-            format!(" (HP: {}/{} - bloodied)", hp.current, hp.maximum)
+            status(Message::Bloodied)
         } else {
-            format!(" (HP: {}/{})", hp.current, hp.maximum)
+            status(Message::HpStatus)
         };
 
-        let mut resolution = Resolution::new(format!(
-            "{} takes {} {} damage from {}{}",
-            target.name,
-            amount,
-            damage_type.name(),
-            source,
-            hp_status
-        ));
+        let takes_damage = locale.format(
+            Message::TakesDamage,
+            &[
+                ("target", &target.name),
+                ("amount", &amount),
+                ("damage_type", &locale.damage_type(damage_type)),
+                ("source", &source),
+            ],
+        );
+        let mut resolution = Resolution::new(format!("{takes_damage}{hp_status}"));
 
         resolution = resolution.with_effect(Effect::HpChanged {
             target_id,
             amount: -amount,
             new_current: hp.current,
             new_max: hp.maximum,
-            dropped_to_zero: result.dropped_to_zero,
+            // Killed outright rather than knocked unconscious
+            dropped_to_zero: result.dropped_to_zero && !instant_death,
         });
 
         if instant_death {
@@ -319,26 +844,46 @@ impl RulesEngine {
         source: &str,
     ) -> Resolution {
         let target = &world.player_character;
+
+        // Healing a Wild Shaped druid restores the beast form's HP
+        if let Some(ref stats) = target.class_resources.wild_shape_stats {
+            let mut hp = stats.hit_points.clone();
+            let healed = hp.heal(amount);
+            return Resolution::new(format!(
+                "{} heals {} hit points from {} in {} form (Beast HP: {}/{})",
+                target.name, healed, source, stats.name, hp.current, hp.maximum
+            ))
+            .with_effect(Effect::WildShapeHpChanged {
+                character_id: target_id,
+                amount: healed,
+                new_current: hp.current,
+            });
+        }
+
         let mut hp = target.hit_points.clone();
         let was_unconscious = hp.current <= 0;
         let healed = hp.heal(amount);
 
         // Build narrative with HP status
-        let hp_status = if was_unconscious && hp.current > 0 {
-            format!(
-                " (HP: {}/{} - regains consciousness!)",
-                hp.current, hp.maximum
-            )
+        let locale = self.locale();
+        let status = if was_unconscious && hp.current > 0 {
+            Message::RegainsConsciousness
         } else if hp.current == hp.maximum {
-            format!(" (HP: {}/{} - fully healed)", hp.current, hp.maximum)
+            Message::FullyHealed
         } else {
-            format!(" (HP: {}/{})", hp.current, hp.maximum)
+            Message::HpStatus
         };
+        let hp_status = locale.format(status, &[("current", &hp.current), ("max", &hp.maximum)]);
+        let heals = locale.format(
+            Message::Heals,
+            &[
+                ("target", &target.name),
+                ("amount", &healed),
+                ("source", &source),
+            ],
+        );
 
-        let resolution = Resolution::new(format!(
-            "{} heals {} hit points from {}{}",
-            target.name, healed, source, hp_status
-        ));
+        let resolution = Resolution::new(format!("{heals}{hp_status}"));
 
         resolution.with_effect(Effect::HpChanged {
             target_id,
@@ -356,11 +901,24 @@ impl RulesEngine {
         condition: Condition,
         source: &str,
         duration_rounds: Option<u32>,
+        timing: DurationTiming,
     ) -> Resolution {
         let target = &world.player_character;
 
+        if let Some(reason) = target.condition_immunity(condition, source) {
+            return Resolution::new(format!(
+                "{} is unaffected by {} ({}).",
+                target.name, source, reason
+            ));
+        }
+
         let duration_text = duration_rounds
-            .map(|d| format!(" for {} rounds", d))
+            .map(|d| match timing {
+                DurationTiming::StartOfTurn => {
+                    format!(" for {d} rounds (ending at the start of their turn)")
+                }
+                DurationTiming::EndOfTurn => format!(" for {d} rounds"),
+            })
             .unwrap_or_default();
 
         let resolution = Resolution::new(format!(
@@ -376,6 +934,7 @@ impl RulesEngine {
             condition,
             source: source.to_string(),
             duration_rounds,
+            timing,
         })
     }
 
@@ -386,14 +945,19 @@ impl RulesEngine {
         condition: Condition,
     ) -> Resolution {
         let target = &world.player_character;
+        let condition_name = condition.name().to_lowercase();
 
-        let resolution =
-            Resolution::new(format!("{} is no longer {}", target.name, condition.name()));
+        // Nothing to remove, so say so rather than narrate a recovery
+        if !target.conditions.iter().any(|c| c.condition == condition) {
+            return Resolution::new(format!("{} is not {condition_name}", target.name));
+        }
 
-        resolution.with_effect(Effect::ConditionRemoved {
-            target_id,
-            condition,
-        })
+        Resolution::new(format!("{} is no longer {condition_name}", target.name)).with_effect(
+            Effect::ConditionRemoved {
+                target_id,
+                condition,
+            },
+        )
     }
 
     pub(crate) fn resolve_start_combat(
@@ -412,13 +976,13 @@ impl RulesEngine {
                 init.initiative_modifier
             };
 
-            let roll = dice::roll("1d20").unwrap();
-            let total = roll.total + modifier as i32;
+            let roll = self.roll_d20_check_for(init.is_player, modifier.into(), Advantage::Normal);
+            let total = roll.total;
 
             resolution = resolution.with_effect(Effect::InitiativeRolled {
                 character_id: init.id,
                 name: init.name.clone(),
-                roll: roll.total,
+                roll: total - roll.modifier,
                 total,
             });
 
@@ -430,18 +994,53 @@ impl RulesEngine {
                 current_hp: init.current_hp,
                 max_hp: init.max_hp,
                 armor_class: init.armor_class,
+                resistances: init.resistances,
             });
         }
 
         resolution
     }
 
-    pub(crate) fn resolve_end_combat(&self, _world: &GameWorld) -> Resolution {
-        Resolution::new("Combat ends.").with_effect(Effect::CombatEnded)
+    pub(crate) fn resolve_end_combat(&self, world: &GameWorld) -> Resolution {
+        let summary = world.combat.as_ref().map(|combat| combat.summary());
+        let mut resolution = Resolution::new("Combat ends.");
+        if let Some(ref summary) = summary {
+            resolution.narrative.push(' ');
+            resolution.narrative.push_str(&summary.describe());
+        }
+        resolution = resolution.with_effect(Effect::CombatEnded { summary });
+
+        // Half of the ammunition that missed can be recovered after the fight
+        if let Some(ref combat) = world.combat {
+            let mut recovered: Vec<_> = combat
+                .recoverable_ammunition
+                .iter()
+                .map(|(ammo, missed)| (ammo, missed / 2))
+                .filter(|(_, count)| *count > 0)
+                .collect();
+            recovered.sort();
+            for (ammo, count) in recovered {
+                let new_total = world.player_character.inventory.ammunition_count(ammo) + count;
+                resolution
+                    .narrative
+                    .push_str(&format!(" Recovered {count} {}.", ammo.to_lowercase()));
+                resolution = resolution.with_effect(Effect::ItemAdded {
+                    item_name: ammo.clone(),
+                    quantity: count,
+                    new_total,
+                    item: None,
+                });
+            }
+        }
+
+        resolution
     }
 
     pub(crate) fn resolve_next_turn(&self, world: &GameWorld) -> Resolution {
         if let Some(ref combat) = world.combat {
+            // A luck feature can only reroll a d20 from the turn it was rolled
+            *self.last_d20_state() = None;
+
             let mut combat_clone = combat.clone();
             combat_clone.next_turn();
 
@@ -450,32 +1049,89 @@ impl RulesEngine {
                 .map(|c| c.name.clone())
                 .unwrap_or_else(|| "Unknown".to_string());
 
-            Resolution::new(format!(
-                "Next turn: {} (Round {})",
-                current, combat_clone.round
-            ))
-            .with_effect(Effect::TurnAdvanced {
-                round: combat_clone.round,
-                current_combatant: current,
-            })
-        } else {
-            Resolution::new("No combat in progress")
-        }
-    }
-
+            // Features with a die-based recharge roll at the start of the
+            // player's turn
+            let moments = combat.player_turn_moments();
+            let recharges = if moments.contains(&DurationTiming::StartOfTurn) {
+                roll_feature_recharges(&world.player_character, &mut *self.player_dice())
+            } else {
+                Vec::new()
+            };
+            let mut narrative = format!("Next turn: {} (Round {})", current, combat_clone.round);
+            for effect in &recharges {
+                if let Effect::FeatureRecharged { feature_name, .. } = effect {
+                    narrative.push_str(&format!(" {feature_name} recharges!"));
+                }
+            }
+
+            // Timed conditions that lapse at this point in the player's turn
+            let player = &world.player_character;
+            let expired: Vec<Effect> = player
+                .conditions
+                .iter()
+                .filter(|c| {
+                    let mut condition = (*c).clone();
+                    !moments.iter().all(|&moment| condition.tick(moment))
+                })
+                .map(|c| Effect::ConditionRemoved {
+                    target_id: player.id,
+                    condition: c.condition,
+                })
+                .collect();
+            for effect in &expired {
+                if let Effect::ConditionRemoved { condition, .. } = effect {
+                    narrative.push_str(&format!(
+                        " {} is no longer {}.",
+                        player.name,
+                        condition.name()
+                    ));
+                }
+            }
+            let lapsed: Vec<Effect> = player
+                .ac_bonuses
+                .iter()
+                .filter(|b| {
+                    let mut bonus = (*b).clone();
+                    !moments.iter().all(|&moment| bonus.tick(moment))
+                })
+                .map(|b| Effect::AcBonusEnded {
+                    target_id: player.id,
+                    source: b.source.clone(),
+                })
+                .collect();
+            for effect in &lapsed {
+                if let Effect::AcBonusEnded { source, .. } = effect {
+                    narrative.push_str(&format!(" {source} no longer protects {}.", player.name));
+                }
+            }
+
+            Resolution::new(narrative)
+                .with_effect(Effect::TurnAdvanced {
+                    round: combat_clone.round,
+                    current_combatant: current,
+                })
+                .with_effects(recharges)
+                .with_effects(expired)
+                .with_effects(lapsed)
+        } else {
+            Resolution::new("No combat in progress")
+        }
+    }
+
     pub(crate) fn resolve_roll_initiative(
         &self,
         character_id: CharacterId,
         name: &str,
         modifier: i8,
-        _is_player: bool,
+        is_player: bool,
     ) -> Resolution {
-        let roll = dice::roll("1d20").unwrap();
-        let total = roll.total + modifier as i32;
+        let roll = self.roll_d20_check_for(is_player, modifier.into(), Advantage::Normal);
+        let total = roll.total;
+        let natural = total - roll.modifier;
 
         Resolution::new(format!(
             "{} rolls initiative: {} + {} = {}",
-            name, roll.total, modifier, total
+            name, natural, modifier, total
         ))
         .with_effect(Effect::DiceRolled {
             roll: roll.clone(),
@@ -484,7 +1140,7 @@ impl RulesEngine {
         .with_effect(Effect::InitiativeRolled {
             character_id,
             name: name.to_string(),
-            roll: roll.total,
+            roll: natural,
             total,
         })
     }
@@ -492,7 +1148,7 @@ impl RulesEngine {
     pub(crate) fn resolve_death_save(
         &self,
         world: &GameWorld,
-        character_id: CharacterId,
+        _character_id: CharacterId,
     ) -> Resolution {
         let character = &world.player_character;
 
@@ -504,8 +1160,24 @@ impl RulesEngine {
             ));
         }
 
-        // Roll d20
-        let roll = dice::roll("1d20").unwrap();
+        // Roll d20; a reroll replays the save from the tallies before it
+        let roll = self.roll_d20_check(0, Advantage::Normal);
+        let saves = &character.death_saves;
+        self.stake_last_d20(D20Stakes::DeathSave {
+            successes: saves.successes,
+            failures: saves.failures,
+        });
+        self.death_save_outcome(character, &roll, saves)
+    }
+
+    /// What a death save landing on `roll` does, counted on top of `saves`.
+    pub(crate) fn death_save_outcome(
+        &self,
+        character: &Character,
+        roll: &RollResult,
+        saves: &DeathSaves,
+    ) -> Resolution {
+        let character_id = character.id;
         let roll_value = roll.total;
 
         // Check for natural 20 - regain 1 HP
@@ -532,7 +1204,7 @@ impl RulesEngine {
 
         // Check for natural 1 - counts as 2 failures
         if roll.is_fumble() {
-            let new_failures = character.death_saves.failures + 2;
+            let new_failures = saves.failures + 2;
             if new_failures >= 3 {
                 return Resolution::new(format!(
                     "{} rolls a NATURAL 1 on their death save! Two failures! {} has died!",
@@ -564,7 +1236,7 @@ impl RulesEngine {
 
         // Normal roll - 10+ is success, <10 is failure
         if roll_value >= 10 {
-            let new_successes = character.death_saves.successes + 1;
+            let new_successes = saves.successes + 1;
             if new_successes >= 3 {
                 Resolution::new(format!(
                     "{} rolls {} on their death save - SUCCESS! With 3 successes, {} is now STABLE!",
@@ -590,7 +1262,7 @@ impl RulesEngine {
                 })
             }
         } else {
-            let new_failures = character.death_saves.failures + 1;
+            let new_failures = saves.failures + 1;
             if new_failures >= 3 {
                 Resolution::new(format!(
                     "{} rolls {} on their death save - FAILURE! With 3 failures, {} has DIED!",
@@ -630,8 +1302,7 @@ impl RulesEngine {
     ) -> Resolution {
         let character = &world.player_character;
 
-        // Calculate DC: max(10, damage / 2)
-        let dc = (damage_taken / 2).max(10);
+        let dc = Concentration::save_dc(damage_taken);
 
         // Get CON modifier
         let con_mod = character.ability_scores.modifier(Ability::Constitution);
@@ -649,7 +1320,7 @@ impl RulesEngine {
         };
 
         // Roll the save
-        let roll = dice::roll(&format!("1d20+{save_mod}")).unwrap();
+        let roll = self.roll_d20_check(save_mod.into(), Advantage::Normal);
         let roll_total = roll.total;
 
         if roll_total >= dc {
@@ -679,11 +1350,56 @@ impl RulesEngine {
     }
 }
 
+/// Damage an area effect deals to one target after its save: half on a
+/// success, or with Evasion none on a success and half on a failure.
+fn area_damage_taken(total: i32, saved: bool, evasion: bool) -> i32 {
+    match (saved, evasion) {
+        (true, true) => 0,
+        (true, false) | (false, true) => total / 2,
+        (false, false) => total,
+    }
+}
+
+/// The current HP `damage` takes off `player`, after temporary HP.
+fn hp_lost(player: &Character, damage: &Resolution) -> i32 {
+    damage
+        .effects
+        .iter()
+        .rev()
+        .find_map(|effect| match effect {
+            Effect::HpChanged {
+                target_id,
+                new_current,
+                ..
+            } if *target_id == player.id => Some(player.hit_points.current - new_current),
+            _ => None,
+        })
+        .unwrap_or(0)
+}
+
+fn is_death(effect: &Effect) -> bool {
+    matches!(effect, Effect::CharacterDied { .. })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dice::DiceRoller;
     use crate::rules::types::Effect;
-    use crate::world::{create_sample_fighter, CharacterId};
+    use crate::world::{
+        create_sample_fighter, CharacterId, CombatState, Combatant, CritRule, Item, ItemType,
+    };
+
+    /// Attacks a test makes while waiting for the roll it needs before it
+    /// fails instead.
+    const MAX_ATTEMPTS: usize = 1000;
+
+    /// An engine that rolls the same dice on every run.
+    fn seeded_engine() -> RulesEngine {
+        RulesEngine::new()
+            .with_player_roller(DiceRoller::new(7))
+            .with_dm_roller(DiceRoller::new(8))
+    }
 
     // ========== Attack Tests ==========
 
@@ -737,6 +1453,734 @@ mod tests {
         assert!(resolution.effects.is_empty());
     }
 
+    fn rogue_world() -> (GameWorld, CharacterId) {
+        use crate::world::{AbilityScores, ClassLevel, CombatState, Combatant};
+
+        let mut character = crate::world::Character::new("Vex");
+        character.ability_scores = AbilityScores::new(10, 16, 12, 10, 12, 10);
+        character.level = 3;
+        character.classes.push(ClassLevel {
+            class: CharacterClass::Rogue,
+            level: 3,
+            subclass: None,
+        });
+        let mut world = GameWorld::new("Test", character);
+
+        // AC 1 so every roll except a natural 1 hits
+        let goblin_id = CharacterId::new();
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant {
+            id: goblin_id,
+            name: "Goblin".to_string(),
+            initiative: 10,
+            is_player: false,
+            is_ally: false,
+            current_hp: 7,
+            max_hp: 7,
+            armor_class: 1,
+            resistances: Vec::new(),
+        });
+        world.combat = Some(combat);
+        (world, goblin_id)
+    }
+
+    fn hitting_attack(
+        engine: &RulesEngine,
+        world: &GameWorld,
+        target_id: CharacterId,
+        advantage: Advantage,
+    ) -> Resolution {
+        (0..MAX_ATTEMPTS)
+            .find_map(|_| {
+                let resolution = engine.resolve_attack(
+                    world,
+                    world.player_character.id,
+                    target_id,
+                    "Rapier",
+                    advantage,
+                );
+                assert!(
+                    !resolution.effects.is_empty(),
+                    "attack refused: {}",
+                    resolution.narrative
+                );
+                let is_normal_hit = resolution.effects.iter().any(|e| {
+                    matches!(
+                        e,
+                        Effect::AttackHit {
+                            is_critical: false,
+                            ..
+                        }
+                    )
+                });
+                is_normal_hit.then_some(resolution)
+            })
+            .expect("no attack landed a normal hit")
+    }
+
+    fn werewolf_world(main_hand: Option<crate::world::WeaponItem>) -> (GameWorld, CharacterId) {
+        use crate::rules::Resistance;
+        use crate::world::{CombatState, Combatant};
+
+        let mut character = create_sample_fighter("Roland");
+        character.equipment.main_hand = main_hand;
+        let mut world = GameWorld::new("Test", character);
+
+        // AC 1 so every roll except a natural 1 hits
+        let werewolf_id = CharacterId::new();
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant {
+            id: werewolf_id,
+            name: "Werewolf".to_string(),
+            initiative: 10,
+            is_player: false,
+            is_ally: false,
+            current_hp: 58,
+            max_hp: 58,
+            armor_class: 1,
+            resistances: vec![Resistance::NonmagicalUnsilveredPhysical],
+        });
+        world.combat = Some(combat);
+        (world, werewolf_id)
+    }
+
+    fn hit_narrative(world: &GameWorld, target_id: CharacterId, weapon: &str) -> String {
+        let engine = seeded_engine();
+        (0..MAX_ATTEMPTS)
+            .find_map(|_| {
+                let resolution = engine.resolve_attack(
+                    world,
+                    world.player_character.id,
+                    target_id,
+                    weapon,
+                    Advantage::Normal,
+                );
+                resolution
+                    .effects
+                    .iter()
+                    .any(|e| matches!(e, Effect::AttackHit { .. }))
+                    .then_some(resolution.narrative)
+            })
+            .expect("no attack hit")
+    }
+
+    #[test]
+    fn test_plain_weapon_resisted_by_werewolf() {
+        let (world, werewolf_id) = werewolf_world(None);
+
+        let narrative = hit_narrative(&world, werewolf_id, "Longsword");
+
+        assert!(narrative.contains("halved by slashing resistance"));
+    }
+
+    #[test]
+    fn test_silvered_weapon_bypasses_werewolf_resistance() {
+        use crate::world::{WeaponDamageType, WeaponItem};
+
+        let sword = WeaponItem::new("Longsword", "1d8", WeaponDamageType::Slashing).silvered();
+        let (world, werewolf_id) = werewolf_world(Some(sword));
+
+        let narrative = hit_narrative(&world, werewolf_id, "Longsword");
+
+        assert!(!narrative.contains("resistance"));
+    }
+
+    #[test]
+    fn test_magic_weapon_bypasses_werewolf_resistance() {
+        use crate::world::{WeaponDamageType, WeaponItem};
+
+        let sword = WeaponItem::new("+1 Longsword", "1d8", WeaponDamageType::Slashing).magical();
+        let (world, werewolf_id) = werewolf_world(Some(sword));
+
+        let narrative = hit_narrative(&world, werewolf_id, "+1 Longsword");
+
+        assert!(!narrative.contains("resistance"));
+    }
+
+    #[test]
+    fn test_resistance_applies_to() {
+        use crate::rules::Resistance;
+
+        let unsilvered = Resistance::NonmagicalUnsilveredPhysical;
+        assert!(unsilvered.applies_to(DamageType::Slashing, false, false));
+        assert!(!unsilvered.applies_to(DamageType::Slashing, false, true));
+        assert!(!unsilvered.applies_to(DamageType::Slashing, true, false));
+        assert!(!unsilvered.applies_to(DamageType::Fire, false, false));
+
+        let nonmagical = Resistance::NonmagicalPhysical;
+        assert!(nonmagical.applies_to(DamageType::Piercing, false, true));
+        assert!(!nonmagical.applies_to(DamageType::Piercing, true, false));
+
+        assert!(Resistance::Type(DamageType::Fire).applies_to(DamageType::Fire, true, true));
+    }
+
+    fn archer_world(arrows: Option<Item>, target_ac: u8) -> (GameWorld, CharacterId) {
+        use crate::world::{CombatState, Combatant};
+
+        let mut character = create_sample_fighter("Robin");
+        character
+            .inventory
+            .items
+            .retain(|i| !i.name.starts_with("Arrows"));
+        if let Some(arrows) = arrows {
+            character.inventory.add_item(arrows);
+        }
+        let mut world = GameWorld::new("Test", character);
+
+        let target_id = CharacterId::new();
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant {
+            id: target_id,
+            name: "Bandit".to_string(),
+            initiative: 10,
+            is_player: false,
+            is_ally: false,
+            current_hp: 11,
+            max_hp: 11,
+            armor_class: target_ac,
+            resistances: Vec::new(),
+        });
+        world.combat = Some(combat);
+        (world, target_id)
+    }
+
+    fn arrows(name: &str, quantity: u32) -> Item {
+        Item {
+            name: name.to_string(),
+            quantity,
+            weight: 1.0,
+            value_gp: 1.0,
+            description: None,
+            item_type: ItemType::Adventuring,
+            magical: false,
+        }
+    }
+
+    #[test]
+    fn test_firing_bow_spends_an_arrow() {
+        let (mut world, target_id) = archer_world(Some(arrows("Arrows (20)", 1)), 10);
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_attack(
+            &world,
+            world.player_character.id,
+            target_id,
+            "Longbow",
+            Advantage::Normal,
+        );
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::AmmunitionSpent { remaining: 19, .. })));
+
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        let inventory = &world.player_character.inventory;
+        assert_eq!(inventory.ammunition_count("Arrows"), 19);
+        assert!(!inventory.has_item("Arrows (20)"));
+    }
+
+    #[test]
+    fn test_firing_bow_without_arrows_is_refused() {
+        let (world, target_id) = archer_world(None, 10);
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_attack(
+            &world,
+            world.player_character.id,
+            target_id,
+            "Longbow",
+            Advantage::Normal,
+        );
+
+        assert!(resolution.narrative.contains("no arrows left"));
+        assert!(resolution.effects.is_empty());
+    }
+
+    #[test]
+    fn test_half_of_missed_arrows_recovered_after_combat() {
+        let (mut world, target_id) = archer_world(Some(arrows("Arrows", 20)), 40);
+        let engine = RulesEngine::new();
+
+        let mut misses = 0;
+        while misses < 4 {
+            if let Some(combat) = world.combat.as_mut() {
+                combat.actions_this_turn.clear();
+                combat.attacks_this_turn.clear();
+            }
+            let resolution = engine.resolve_attack(
+                &world,
+                world.player_character.id,
+                target_id,
+                "Longbow",
+                Advantage::Normal,
+            );
+            if resolution
+                .effects
+                .iter()
+                .any(|e| matches!(e, Effect::AttackMissed { .. }))
+            {
+                misses += 1;
+            }
+            crate::rules::apply_effects(&mut world, &resolution.effects);
+        }
+        let after_firing = world.player_character.inventory.ammunition_count("Arrows");
+
+        let resolution = engine.resolve_end_combat(&world);
+        assert!(resolution.narrative.contains("Recovered 2 arrows"));
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+
+        assert_eq!(
+            world.player_character.inventory.ammunition_count("Arrows"),
+            after_firing + 2
+        );
+    }
+
+    fn dual_dagger_rogue(two_weapon_fighting: bool) -> (GameWorld, CharacterId) {
+        let (mut world, goblin_id) = rogue_world();
+        let dagger = crate::items::get_weapon("Dagger").unwrap();
+        let pc = &mut world.player_character;
+        pc.equipment.off_hand = Some(dagger.base.clone());
+        pc.equipment.main_hand = Some(dagger);
+        if two_weapon_fighting {
+            pc.fighting_styles.insert(FightingStyle::TwoWeaponFighting);
+        }
+        (world, goblin_id)
+    }
+
+    fn offhand_damage_modifier(world: &GameWorld, target_id: CharacterId) -> i32 {
+        let engine = seeded_engine();
+        (0..MAX_ATTEMPTS)
+            .find_map(|_| {
+                let resolution = engine.resolve_offhand_attack(
+                    world,
+                    world.player_character.id,
+                    target_id,
+                    Advantage::Normal,
+                );
+                assert!(resolution.narrative.contains("(off hand)"));
+                resolution.effects.iter().find_map(|e| match e {
+                    Effect::DiceRolled { roll, purpose } if purpose == "Damage" => {
+                        Some(roll.modifier)
+                    }
+                    _ => None,
+                })
+            })
+            .expect("no off-hand attack hit")
+    }
+
+    #[test]
+    fn test_offhand_attack_adds_no_modifier_without_style() {
+        let (world, goblin_id) = dual_dagger_rogue(false);
+        assert_eq!(offhand_damage_modifier(&world, goblin_id), 0);
+    }
+
+    #[test]
+    fn test_offhand_attack_adds_modifier_with_two_weapon_fighting() {
+        let (world, goblin_id) = dual_dagger_rogue(true);
+        // DEX 16 gives +3
+        assert_eq!(offhand_damage_modifier(&world, goblin_id), 3);
+    }
+
+    fn attack_roll_modifier(world: &GameWorld, target_id: CharacterId, weapon: &str) -> i32 {
+        let engine = RulesEngine::new();
+        let resolution = engine.resolve_attack(
+            world,
+            world.player_character.id,
+            target_id,
+            weapon,
+            Advantage::Normal,
+        );
+        resolution
+            .effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::DiceRolled { roll, purpose } if purpose.starts_with("Attack with") => {
+                    Some(roll.modifier)
+                }
+                _ => None,
+            })
+            .expect("attack roll should be made")
+    }
+
+    #[test]
+    fn test_archery_adds_two_to_ranged_attack_rolls() {
+        let (mut world, target_id) = archer_world(Some(arrows("Arrows", 20)), 10);
+        let without = attack_roll_modifier(&world, target_id, "Longbow");
+
+        world
+            .player_character
+            .fighting_styles
+            .insert(FightingStyle::Archery);
+        assert_eq!(
+            attack_roll_modifier(&world, target_id, "Longbow"),
+            without + 2
+        );
+    }
+
+    #[test]
+    fn test_archery_does_not_affect_melee_attack_rolls() {
+        let (mut world, target_id) = archer_world(None, 10);
+        let without = attack_roll_modifier(&world, target_id, "Longsword");
+
+        world
+            .player_character
+            .fighting_styles
+            .insert(FightingStyle::Archery);
+        assert_eq!(
+            attack_roll_modifier(&world, target_id, "Longsword"),
+            without
+        );
+    }
+
+    #[test]
+    fn test_dueling_adds_two_to_one_handed_damage() {
+        let (mut world, target_id) = archer_world(None, 0);
+        let pc = &mut world.player_character;
+        pc.equipment.main_hand = crate::items::get_weapon("Longsword");
+        pc.equipment.off_hand = None;
+        pc.fighting_styles.insert(FightingStyle::Dueling);
+        let engine = seeded_engine();
+
+        let damage_modifier = (0..MAX_ATTEMPTS)
+            .find_map(|_| {
+                let resolution = engine.resolve_attack(
+                    &world,
+                    world.player_character.id,
+                    target_id,
+                    "Longsword",
+                    Advantage::Normal,
+                );
+                resolution.effects.iter().find_map(|e| match e {
+                    Effect::DiceRolled { roll, purpose } if purpose == "Damage" => {
+                        Some(roll.modifier)
+                    }
+                    _ => None,
+                })
+            })
+            .expect("no attack hit");
+        // STR 16 gives +3, Dueling adds 2
+        assert_eq!(damage_modifier, 5);
+    }
+
+    #[test]
+    fn test_attacking_in_darkness_without_darkvision_has_disadvantage() {
+        let (mut world, target_id) = archer_world(None, 10);
+        let light = Some(LightLevel::Darkness);
+        world.combat.as_mut().unwrap().light_level = light;
+        let engine = RulesEngine::new();
+        let attack = |world: &GameWorld| {
+            engine.resolve_attack(
+                world,
+                world.player_character.id,
+                target_id,
+                "Longsword",
+                Advantage::Normal,
+            )
+        };
+
+        assert!(attack(&world).narrative.contains("[darkness disadvantage]"));
+
+        world.player_character.darkvision_range = 60;
+        assert!(!attack(&world).narrative.contains("darkness"));
+    }
+
+    /// A Champion leveled from 2 to `level`, facing a target only a critical can hit.
+    fn champion_vs_ac_40(level: u8) -> (GameWorld, CharacterId) {
+        let (mut world, target_id) = archer_world(None, 40);
+        world.player_character.level = 2;
+        world.player_character.classes[0].level = 2;
+        crate::rules::apply_effect(&mut world, &Effect::LevelUp { new_level: level });
+        (world, target_id)
+    }
+
+    /// Attack with a longsword until the d20 shows `natural`.
+    fn attack_rolling_natural(
+        world: &GameWorld,
+        target_id: CharacterId,
+        natural: u32,
+    ) -> Resolution {
+        let engine = seeded_engine();
+        (0..MAX_ATTEMPTS)
+            .find_map(|_| {
+                let resolution = engine.resolve_attack(
+                    world,
+                    world.player_character.id,
+                    target_id,
+                    "Longsword",
+                    Advantage::Normal,
+                );
+                let rolled = resolution.effects.iter().find_map(|e| match e {
+                    Effect::DiceRolled { roll, purpose } if purpose.starts_with("Attack with") => {
+                        roll.natural_d20()
+                    }
+                    _ => None,
+                });
+                (rolled == Some(natural)).then_some(resolution)
+            })
+            .unwrap_or_else(|| panic!("never rolled a natural {natural}"))
+    }
+
+    fn is_critical_hit(resolution: &Resolution) -> bool {
+        resolution.effects.iter().any(|e| {
+            matches!(
+                e,
+                Effect::AttackHit {
+                    is_critical: true,
+                    ..
+                }
+            )
+        })
+    }
+
+    #[test]
+    fn test_champion_with_improved_critical_crits_on_nineteen() {
+        let (world, target_id) = champion_vs_ac_40(3);
+        assert_eq!(world.player_character.crit_range(), 19);
+
+        assert!(is_critical_hit(&attack_rolling_natural(
+            &world, target_id, 19
+        )));
+        assert!(!is_critical_hit(&attack_rolling_natural(
+            &world, target_id, 18
+        )));
+    }
+
+    #[test]
+    fn test_champion_fifteen_crits_on_eighteen_and_doubles_dice() {
+        let (world, target_id) = champion_vs_ac_40(15);
+        assert_eq!(world.player_character.crit_range(), 18);
+
+        let resolution = attack_rolling_natural(&world, target_id, 18);
+        assert!(is_critical_hit(&resolution));
+        let damage = resolution
+            .effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::DiceRolled { roll, purpose } if purpose == "Damage" => Some(roll),
+                _ => None,
+            })
+            .expect("a critical hit rolls damage");
+        // Longsword 1d8 doubled to 2d8
+        assert_eq!(damage.component_results[0].rolls.len(), 2);
+
+        let resolution = attack_rolling_natural(&world, target_id, 17);
+        assert!(!is_critical_hit(&resolution));
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::AttackMissed { .. })));
+    }
+
+    #[test]
+    fn test_max_plus_roll_crit_maxes_base_dice_and_rolls_crit_dice() {
+        let (mut world, target_id) = champion_vs_ac_40(3);
+        world.house_rules.crit_rule = CritRule::MaxPlusRoll;
+        let strength = world
+            .player_character
+            .ability_scores
+            .modifier(Ability::Strength) as i32;
+
+        let resolution = attack_rolling_natural(&world, target_id, 20);
+        let damage = resolution
+            .effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::DiceRolled { roll, purpose } if purpose == "Damage" => Some(roll),
+                _ => None,
+            })
+            .expect("a critical hit rolls damage");
+        // Longsword: 8 for the maxed 1d8, plus one rolled 1d8
+        assert_eq!(damage.component_results.len(), 1);
+        assert_eq!(damage.component_results[0].rolls.len(), 1);
+        assert_eq!(damage.modifier, 8 + strength);
+        assert_eq!(
+            damage.total,
+            damage.component_results[0].subtotal as i32 + 8 + strength
+        );
+    }
+
+    #[test]
+    fn test_offhand_attack_requires_light_weapon() {
+        let (mut world, goblin_id) = rogue_world();
+        world.player_character.equipment.off_hand =
+            crate::items::get_weapon("Longsword").map(|w| w.base);
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_offhand_attack(
+            &world,
+            world.player_character.id,
+            goblin_id,
+            Advantage::Normal,
+        );
+
+        assert!(resolution.narrative.contains("light weapon"));
+        assert!(resolution.effects.is_empty());
+    }
+
+    fn rapier_attack(
+        engine: &RulesEngine,
+        world: &mut GameWorld,
+        target_id: CharacterId,
+    ) -> Resolution {
+        let resolution = engine.resolve_attack(
+            world,
+            world.player_character.id,
+            target_id,
+            "Rapier",
+            Advantage::Normal,
+        );
+        crate::rules::apply_effects(world, &resolution.effects);
+        resolution
+    }
+
+    #[test]
+    fn test_attack_in_combat_spends_the_action() {
+        let (mut world, goblin_id) = rogue_world();
+        let engine = RulesEngine::new();
+
+        let first = rapier_attack(&engine, &mut world, goblin_id);
+        assert!(first.effects.iter().any(|e| matches!(
+            e,
+            Effect::ActionUsed {
+                action: ActionType::Action,
+                ..
+            }
+        )));
+        assert!(first
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::AttackMade { .. })));
+
+        let second = rapier_attack(&engine, &mut world, goblin_id);
+        assert!(second.narrative.contains("already used their action"));
+        assert!(second.effects.is_empty());
+
+        // Action Surge grants another action to attack with
+        let extra_action = Effect::ExtraActionGained {
+            character_id: world.player_character.id,
+        };
+        crate::rules::apply_effect(&mut world, &extra_action);
+        let third = rapier_attack(&engine, &mut world, goblin_id);
+        assert!(third
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::AttackMade { .. })));
+    }
+
+    #[test]
+    fn test_extra_attack_shares_one_action() {
+        let (mut world, goblin_id) = rogue_world();
+        world.player_character.classes[0] = crate::world::ClassLevel {
+            class: CharacterClass::Fighter,
+            level: 5,
+            subclass: None,
+        };
+        let engine = RulesEngine::new();
+
+        let first = rapier_attack(&engine, &mut world, goblin_id);
+        assert!(first
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::ActionUsed { .. })));
+        let second = rapier_attack(&engine, &mut world, goblin_id);
+        assert!(second
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::AttackMade { .. })));
+        assert!(!second
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::ActionUsed { .. })));
+        let third = rapier_attack(&engine, &mut world, goblin_id);
+        assert!(third.effects.is_empty());
+    }
+
+    #[test]
+    fn test_offhand_attack_spends_the_bonus_action() {
+        let (mut world, goblin_id) = dual_dagger_rogue(false);
+        let engine = RulesEngine::new();
+
+        let attack = |world: &GameWorld| {
+            engine.resolve_offhand_attack(
+                world,
+                world.player_character.id,
+                goblin_id,
+                Advantage::Normal,
+            )
+        };
+        let first = attack(&world);
+        assert!(first.effects.iter().any(|e| matches!(
+            e,
+            Effect::ActionUsed {
+                action: ActionType::BonusAction,
+                ..
+            }
+        )));
+        crate::rules::apply_effects(&mut world, &first.effects);
+
+        let second = attack(&world);
+        assert!(second.narrative.contains("already used their bonus action"));
+    }
+
+    #[test]
+    fn test_sneak_attack_with_advantage() {
+        let (mut world, goblin_id) = rogue_world();
+        let engine = seeded_engine();
+
+        let resolution = hitting_attack(&engine, &world, goblin_id, Advantage::Advantage);
+
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::SneakAttackUsed { damage_dice: 2, .. })));
+        let sneak_roll = resolution
+            .effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::DiceRolled { roll, purpose } if purpose == "Sneak Attack" => Some(roll),
+                _ => None,
+            })
+            .expect("sneak attack dice should be rolled");
+        assert!((2..=12).contains(&sneak_roll.total));
+        assert!(resolution.narrative.contains("Sneak Attack"));
+
+        // Only once per turn, even with a second action
+        for effect in &resolution.effects {
+            crate::rules::apply_effect(&mut world, effect);
+        }
+        let extra_action = Effect::ExtraActionGained {
+            character_id: world.player_character.id,
+        };
+        crate::rules::apply_effect(&mut world, &extra_action);
+        let second = hitting_attack(&engine, &world, goblin_id, Advantage::Advantage);
+        assert!(!second
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::SneakAttackUsed { .. })));
+
+        // Available again on the next turn
+        world.combat.as_mut().unwrap().next_turn();
+        let third = hitting_attack(&engine, &world, goblin_id, Advantage::Advantage);
+        assert!(third
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::SneakAttackUsed { .. })));
+    }
+
+    #[test]
+    fn test_no_sneak_attack_without_advantage() {
+        let (world, goblin_id) = rogue_world();
+        let engine = seeded_engine();
+
+        let resolution = hitting_attack(&engine, &world, goblin_id, Advantage::Normal);
+
+        assert!(!resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::SneakAttackUsed { .. })));
+        assert!(!resolution.narrative.contains("Sneak Attack"));
+    }
+
     // ========== Damage Tests ==========
 
     #[test]
@@ -760,6 +2204,43 @@ mod tests {
             .any(|e| matches!(e, Effect::HpChanged { amount: -10, .. })));
     }
 
+    #[test]
+    fn test_racial_resistance_halves_damage() {
+        use crate::rules::Resistance;
+
+        let mut character = create_sample_fighter("Thorin");
+        character
+            .resistances
+            .push(Resistance::Type(DamageType::Poison));
+        let world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_damage(
+            &world,
+            world.player_character.id,
+            9,
+            DamageType::Poison,
+            "poisoned dart",
+        );
+        assert!(resolution.narrative.contains("halved by poison resistance"));
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::HpChanged { amount: -4, .. })));
+
+        let resolution = engine.resolve_damage(
+            &world,
+            world.player_character.id,
+            9,
+            DamageType::Fire,
+            "torch",
+        );
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::HpChanged { amount: -9, .. })));
+    }
+
     #[test]
     fn test_damage_drops_to_zero_unconscious() {
         let mut character = create_sample_fighter("Roland");
@@ -809,6 +2290,49 @@ mod tests {
             .any(|e| matches!(e, Effect::CharacterDied { .. })));
     }
 
+    #[test]
+    fn test_overkill_damage_kills_instead_of_knocking_out() {
+        let mut character = create_sample_fighter("Roland");
+        character.hit_points.current = 10;
+        character.hit_points.maximum = 28;
+        let world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let id = world.player_character.id;
+        let dropped_to_zero = |resolution: &Resolution| {
+            resolution.effects.iter().find_map(|e| match e {
+                Effect::HpChanged {
+                    dropped_to_zero, ..
+                } => Some(*dropped_to_zero),
+                _ => None,
+            })
+        };
+        let died = |resolution: &Resolution| {
+            resolution
+                .effects
+                .iter()
+                .any(|e| matches!(e, Effect::CharacterDied { .. }))
+        };
+
+        // 37 damage leaves 27 past 0 HP, one short of the maximum
+        let knocked_out = engine.resolve_damage(&world, id, 37, DamageType::Force, "ogre");
+        assert_eq!(dropped_to_zero(&knocked_out), Some(true));
+        assert!(!died(&knocked_out));
+        let mut fallen = world.clone();
+        crate::rules::apply_effects(&mut fallen, &knocked_out.effects);
+        assert!(fallen
+            .player_character
+            .has_condition(Condition::Unconscious));
+
+        // 38 leaves 28, which meets it
+        let killed = engine.resolve_damage(&world, id, 38, DamageType::Force, "ogre");
+        assert_eq!(dropped_to_zero(&killed), Some(false));
+        assert!(died(&killed));
+        assert!(killed.narrative.contains("no death saves"));
+        let mut dead = world.clone();
+        crate::rules::apply_effects(&mut dead, &killed.effects);
+        assert!(!dead.player_character.has_condition(Condition::Unconscious));
+    }
+
     #[test]
     fn test_damage_while_unconscious_causes_death_save_failure() {
         let mut character = create_sample_fighter("Roland");
@@ -876,6 +2400,7 @@ mod tests {
             Condition::Poisoned,
             "poison dart",
             Some(10),
+            DurationTiming::EndOfTurn,
         );
 
         assert!(resolution.narrative.contains("Poisoned"));
@@ -890,9 +2415,90 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn test_apply_condition_refused_when_immune() {
+        let mut character = create_sample_fighter("Roland");
+        character
+            .condition_immunities
+            .insert(Condition::Exhaustion(1));
+        character.condition_immunities.insert(Condition::Poisoned);
+        let world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+
+        for condition in [Condition::Poisoned, Condition::Exhaustion(2)] {
+            let resolution = engine.resolve_apply_condition(
+                &world,
+                world.player_character.id,
+                condition,
+                "ghoul claws",
+                None,
+                DurationTiming::EndOfTurn,
+            );
+
+            assert!(resolution.narrative.contains("unaffected"));
+            assert!(resolution.narrative.contains("immune"));
+            assert!(resolution.effects.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_fey_ancestry_blocks_magical_sleep() {
+        use crate::world::{Feature, RaceType};
+
+        let mut character = create_sample_fighter("Aelar");
+        character.race_type = RaceType::Elf;
+        character.features.push(Feature {
+            name: "Fey Ancestry".to_string(),
+            description: String::new(),
+            source: "Elf".to_string(),
+            uses: None,
+        });
+        let world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let id = world.player_character.id;
+
+        let slept = engine.resolve_apply_condition(
+            &world,
+            id,
+            Condition::Unconscious,
+            "Sleep spell",
+            None,
+            DurationTiming::EndOfTurn,
+        );
+        assert!(slept.narrative.contains("Fey Ancestry"));
+        assert!(slept.effects.is_empty());
+
+        // Charm is only resisted with advantage on the save, not blocked
+        let charmed = engine.resolve_apply_condition(
+            &world,
+            id,
+            Condition::Charmed,
+            "Charm Person",
+            None,
+            DurationTiming::EndOfTurn,
+        );
+        assert!(!charmed.effects.is_empty());
+
+        let knocked_out = engine.resolve_apply_condition(
+            &world,
+            id,
+            Condition::Unconscious,
+            "club",
+            None,
+            DurationTiming::EndOfTurn,
+        );
+        assert!(!knocked_out.effects.is_empty());
+    }
+
     #[test]
     fn test_remove_condition() {
-        let character = create_sample_fighter("Roland");
+        let mut character = create_sample_fighter("Roland");
+        character
+            .conditions
+            .push(crate::world::ActiveCondition::new(
+                Condition::Frightened,
+                "Dragon's roar",
+            ));
         let world = GameWorld::new("Test", character);
         let engine = RulesEngine::new();
 
@@ -902,7 +2508,7 @@ mod tests {
             Condition::Frightened,
         );
 
-        assert!(resolution.narrative.contains("no longer"));
+        assert_eq!(resolution.narrative, "Roland is no longer frightened");
         assert!(resolution.effects.iter().any(|e| matches!(
             e,
             Effect::ConditionRemoved {
@@ -912,6 +2518,87 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn test_remove_absent_condition_changes_nothing() {
+        let world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let engine = RulesEngine::new();
+
+        let resolution =
+            engine.resolve_remove_condition(&world, world.player_character.id, Condition::Poisoned);
+
+        assert_eq!(resolution.narrative, "Roland is not poisoned");
+        assert!(resolution.effects.is_empty());
+    }
+
+    // ========== Area Damage Tests ==========
+
+    #[test]
+    fn test_area_damage_three_enemies_one_saves() {
+        let character = create_sample_fighter("Roland");
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+
+        let mut combat = CombatState::new();
+        let mut targets = Vec::new();
+        for (name, save_modifier) in [("Orc A", -30), ("Orc B", 30), ("Orc C", -30)] {
+            let id = CharacterId::new();
+            combat.add_combatant(Combatant {
+                id,
+                name: name.to_string(),
+                initiative: 10,
+                is_player: false,
+                is_ally: false,
+                current_hp: 100,
+                max_hp: 100,
+                armor_class: 13,
+                resistances: Vec::new(),
+            });
+            targets.push(AreaTarget { id, save_modifier });
+        }
+        world.combat = Some(combat);
+
+        let resolution = engine.resolve_area_damage(
+            &world,
+            &targets,
+            "8d6",
+            DamageType::Fire,
+            Ability::Dexterity,
+            15,
+            "Fireball",
+        );
+
+        let total = resolution
+            .effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::DiceRolled { roll, purpose } if purpose == "Fireball damage" => {
+                    Some(roll.total)
+                }
+                _ => None,
+            })
+            .unwrap();
+        let damage: Vec<(String, i32)> = resolution
+            .effects
+            .iter()
+            .filter_map(|e| match e {
+                Effect::CombatantHpChanged { name, amount, .. } => Some((name.clone(), -amount)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            damage,
+            vec![
+                ("Orc A".to_string(), total),
+                ("Orc B".to_string(), total / 2),
+                ("Orc C".to_string(), total),
+            ]
+        );
+
+        // Evasion turns a success into no damage and a failure into half
+        assert_eq!(area_damage_taken(28, true, true), 0);
+        assert_eq!(area_damage_taken(28, false, true), 14);
+    }
+
     // ========== Combat Management Tests ==========
 
     #[test]
@@ -929,6 +2616,7 @@ mod tests {
             max_hp: 28,
             armor_class: 18,
             initiative_modifier: 2,
+            resistances: Vec::new(),
         }];
 
         let resolution = engine.resolve_start_combat(&world, combatants);
@@ -956,7 +2644,96 @@ mod tests {
         assert!(resolution
             .effects
             .iter()
-            .any(|e| matches!(e, Effect::CombatEnded)));
+            .any(|e| matches!(e, Effect::CombatEnded { .. })));
+    }
+
+    #[test]
+    fn test_end_combat_summarizes_two_round_fight() {
+        let character = create_sample_fighter("Roland");
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let player_id = world.player_character.id;
+        let (goblin_id, wolf_id) = (CharacterId::new(), CharacterId::new());
+
+        let enemy = |id, name: &str, hp| CombatantInit {
+            id,
+            name: name.to_string(),
+            is_player: false,
+            is_ally: false,
+            current_hp: hp,
+            max_hp: hp,
+            armor_class: 12,
+            initiative_modifier: 2,
+            resistances: Vec::new(),
+        };
+        let start = engine.resolve_start_combat(
+            &world,
+            vec![
+                CombatantInit {
+                    id: player_id,
+                    name: "Roland".to_string(),
+                    is_player: true,
+                    is_ally: true,
+                    current_hp: 28,
+                    max_hp: 28,
+                    armor_class: 18,
+                    initiative_modifier: 2,
+                    resistances: Vec::new(),
+                },
+                enemy(goblin_id, "Goblin", 7),
+                enemy(wolf_id, "Wolf", 11),
+            ],
+        );
+        crate::rules::apply_effects(&mut world, &start.effects);
+
+        let hit = |id, name: &str, amount: i32| Effect::CombatantHpChanged {
+            id,
+            name: name.to_string(),
+            amount: -amount,
+            new_current: 0,
+        };
+        crate::rules::apply_effects(
+            &mut world,
+            &[
+                hit(goblin_id, "Goblin", 9),
+                Effect::HpChanged {
+                    target_id: player_id,
+                    amount: -5,
+                    new_current: 23,
+                    new_max: 28,
+                    dropped_to_zero: false,
+                },
+            ],
+        );
+        for _ in 0..3 {
+            let next = engine.resolve_next_turn(&world);
+            crate::rules::apply_effects(&mut world, &next.effects);
+        }
+        crate::rules::apply_effect(&mut world, &hit(wolf_id, "Wolf", 11));
+
+        let resolution = engine.resolve_end_combat(&world);
+        let summary = resolution
+            .effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::CombatEnded { summary } => summary.clone(),
+                _ => None,
+            })
+            .expect("an active combat is summarized");
+        assert_eq!(summary.rounds, 2);
+        assert_eq!(summary.defeated, vec!["Goblin", "Wolf"]);
+        // Only the HP the goblin had counts, not the overkill
+        assert_eq!(summary.damage_dealt, 18);
+        assert_eq!(summary.damage_taken, 5);
+        assert!(resolution.narrative.contains("Combat lasted 2 rounds"));
+
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        let entry = world.narrative_history.last().unwrap();
+        assert!(matches!(
+            entry.entry_type,
+            crate::world::NarrativeType::System
+        ));
+        assert!(entry.content.contains("Defeated: Goblin, Wolf."));
     }
 
     #[test]
@@ -970,6 +2747,142 @@ mod tests {
         assert!(resolution.narrative.contains("No combat"));
     }
 
+    /// A combat where the goblin is acting and the player goes next.
+    fn goblin_then_player(world: &mut GameWorld) {
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant {
+            id: CharacterId::new(),
+            name: "Goblin".to_string(),
+            initiative: 15,
+            is_player: false,
+            is_ally: false,
+            current_hp: 7,
+            max_hp: 7,
+            armor_class: 13,
+            resistances: Vec::new(),
+        });
+        combat.add_combatant(Combatant {
+            id: world.player_character.id,
+            name: world.player_character.name.clone(),
+            initiative: 10,
+            is_player: true,
+            is_ally: true,
+            current_hp: 20,
+            max_hp: 20,
+            armor_class: 16,
+            resistances: Vec::new(),
+        });
+        world.combat = Some(combat);
+    }
+
+    fn frighten_player(engine: &RulesEngine, world: &mut GameWorld, timing: DurationTiming) {
+        let resolution = engine.resolve_apply_condition(
+            world,
+            world.player_character.id,
+            Condition::Frightened,
+            "Dragon's roar",
+            Some(1),
+            timing,
+        );
+        crate::rules::apply_effects(world, &resolution.effects);
+    }
+
+    fn next_turn(engine: &RulesEngine, world: &mut GameWorld) -> Resolution {
+        let resolution = engine.resolve_next_turn(world);
+        crate::rules::apply_effects(world, &resolution.effects);
+        resolution
+    }
+
+    #[test]
+    fn test_feature_recharge_rolls_only_at_start_of_player_turn() {
+        use crate::world::{Feature, FeatureUses, RechargeType};
+
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        goblin_then_player(&mut world);
+        world.combat.as_mut().unwrap().add_combatant(Combatant {
+            id: CharacterId::new(),
+            name: "Wolf".to_string(),
+            initiative: 5,
+            is_player: false,
+            is_ally: false,
+            current_hp: 11,
+            max_hp: 11,
+            armor_class: 13,
+            resistances: Vec::new(),
+        });
+        // Recharge 1-6 always succeeds, so every roll shows up as a recharge
+        world.player_character.features.push(Feature {
+            name: "Fire Breath".to_string(),
+            description: String::new(),
+            source: "Test".to_string(),
+            uses: Some(FeatureUses {
+                current: 0,
+                maximum: 1,
+                recharge: RechargeType::Die { min: 1 },
+            }),
+        });
+        let engine = RulesEngine::new();
+
+        // Goblin -> Roland -> Wolf -> Goblin -> Roland
+        let mut recharged = Vec::new();
+        for _ in 0..4 {
+            let resolution = next_turn(&engine, &mut world);
+            recharged.push(
+                resolution
+                    .effects
+                    .iter()
+                    .any(|e| matches!(e, Effect::FeatureRecharged { .. })),
+            );
+            let feature = world.player_character.features.last_mut().unwrap();
+            feature.uses.as_mut().unwrap().current = 0;
+        }
+        assert_eq!(recharged, vec![true, false, false, true]);
+    }
+
+    #[test]
+    fn test_end_of_turn_condition_lasts_through_the_turn() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        goblin_then_player(&mut world);
+        let engine = RulesEngine::new();
+        frighten_player(&engine, &mut world, DurationTiming::EndOfTurn);
+
+        // The player's turn begins: still frightened for it.
+        let resolution = next_turn(&engine, &mut world);
+        assert!(world.player_character.has_condition(Condition::Frightened));
+        assert!(!resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::ConditionRemoved { .. })));
+
+        // The player's turn ends: the condition lapses.
+        let resolution = next_turn(&engine, &mut world);
+        assert!(!world.player_character.has_condition(Condition::Frightened));
+        assert!(resolution.effects.iter().any(|e| matches!(
+            e,
+            Effect::ConditionRemoved {
+                condition: Condition::Frightened,
+                ..
+            }
+        )));
+        assert!(resolution.narrative.contains("no longer"));
+    }
+
+    #[test]
+    fn test_start_of_turn_condition_lapses_as_the_turn_begins() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        goblin_then_player(&mut world);
+        let engine = RulesEngine::new();
+        frighten_player(&engine, &mut world, DurationTiming::StartOfTurn);
+
+        let resolution = next_turn(&engine, &mut world);
+
+        assert!(!world.player_character.has_condition(Condition::Frightened));
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::ConditionRemoved { .. })));
+    }
+
     // ========== Death Save Tests ==========
 
     #[test]
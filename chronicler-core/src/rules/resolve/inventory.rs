@@ -3,7 +3,7 @@
 use crate::rules::helpers::roll_with_fallback;
 use crate::rules::types::{Effect, Resolution};
 use crate::rules::RulesEngine;
-use crate::world::{CharacterId, Condition, GameWorld, ItemType};
+use crate::world::{CharacterId, Condition, GameWorld, Item, ItemType};
 
 impl RulesEngine {
     #[allow(clippy::too_many_arguments)]
@@ -12,11 +12,11 @@ impl RulesEngine {
         world: &GameWorld,
         item_name: &str,
         quantity: u32,
-        _item_type: Option<&str>,
-        _description: Option<&str>,
-        _magical: bool,
-        _weight: Option<f32>,
-        _value_gp: Option<f32>,
+        item_type: Option<&str>,
+        description: Option<&str>,
+        magical: bool,
+        weight: Option<f32>,
+        value_gp: Option<f32>,
     ) -> Resolution {
         let character = &world.player_character;
 
@@ -28,9 +28,34 @@ impl RulesEngine {
             .unwrap_or(0);
         let new_total = existing_qty + quantity;
 
-        // Note: item_type, description, magical, weight, value_gp are passed through
-        // but the actual item creation happens in apply_effect or could be enhanced
-        // to look up standard items from the items database.
+        // Start from the standard item's stats (or a generic item) and let
+        // anything the DM specified take precedence
+        let mut item = match crate::items::find_item(item_name) {
+            Some(standard_item) => standard_item.as_item(),
+            None => Item {
+                name: item_name.to_string(),
+                quantity,
+                weight: 0.0,
+                value_gp: 0.0,
+                description: None,
+                item_type: ItemType::Other,
+                magical: false,
+            },
+        };
+        item.quantity = quantity;
+        if let Some(item_type) = item_type.and_then(parse_item_type) {
+            item.item_type = item_type;
+        }
+        if let Some(description) = description {
+            item.description = Some(description.to_string());
+        }
+        item.magical |= magical;
+        if let Some(weight) = weight {
+            item.weight = weight;
+        }
+        if let Some(value_gp) = value_gp {
+            item.value_gp = value_gp;
+        }
 
         let qty_str = if quantity > 1 {
             format!("{quantity} x ")
@@ -46,6 +71,7 @@ impl RulesEngine {
             item_name: item_name.to_string(),
             quantity,
             new_total,
+            item: Some(item),
         })
     }
 
@@ -235,7 +261,7 @@ impl RulesEngine {
                     } else {
                         dice_expr
                     };
-                    let heal_roll = roll_with_fallback(&heal_expr, "1d4");
+                    let heal_roll = roll_with_fallback(&heal_expr, "1d4", &mut *self.player_dice());
 
                     Resolution::new(format!(
                         "{} drinks {} and heals for {} HP",
@@ -347,6 +373,24 @@ impl RulesEngine {
     }
 }
 
+/// Parse an item type as named in the `give_item` tool schema.
+fn parse_item_type(name: &str) -> Option<ItemType> {
+    Some(match name.to_lowercase().as_str() {
+        "weapon" => ItemType::Weapon,
+        "armor" => ItemType::Armor,
+        "shield" => ItemType::Shield,
+        "potion" => ItemType::Potion,
+        "scroll" => ItemType::Scroll,
+        "wand" => ItemType::Wand,
+        "ring" => ItemType::Ring,
+        "wondrous" => ItemType::Wondrous,
+        "adventuring" => ItemType::Adventuring,
+        "tool" => ItemType::Tool,
+        "other" => ItemType::Other,
+        _ => return None,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -371,7 +415,64 @@ mod tests {
         assert!(resolution
             .effects
             .iter()
-            .any(|e| matches!(e, Effect::ItemAdded { item_name, quantity: 1, new_total: 1 } if item_name == "Longsword")));
+            .any(|e| matches!(e, Effect::ItemAdded { item_name, quantity: 1, new_total: 1, .. } if item_name == "Longsword")));
+    }
+
+    #[test]
+    fn test_add_item_keeps_provided_metadata() {
+        let character = create_sample_fighter("Roland");
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_add_item(
+            &world,
+            "Moonsilver Circlet",
+            1,
+            Some("wondrous"),
+            Some("Glows faintly under starlight"),
+            true,
+            Some(0.5),
+            Some(750.0),
+        );
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+
+        let item = world
+            .player_character
+            .inventory
+            .find_item("Moonsilver Circlet")
+            .expect("item should be added");
+        assert!(item.magical);
+        assert_eq!(item.value_gp, 750.0);
+        assert_eq!(item.weight, 0.5);
+        assert_eq!(item.item_type, ItemType::Wondrous);
+        assert_eq!(
+            item.description.as_deref(),
+            Some("Glows faintly under starlight")
+        );
+    }
+
+    #[test]
+    fn test_add_item_falls_back_to_standard_stats() {
+        let character = create_sample_fighter("Roland");
+        let mut world = GameWorld::new("Test", character);
+        world.player_character.inventory.items.clear();
+        let engine = RulesEngine::new();
+        let standard = crate::items::find_item("Longsword").unwrap().as_item();
+
+        // Only the value is overridden; the rest comes from the database
+        let resolution =
+            engine.resolve_add_item(&world, "Longsword", 1, None, None, false, None, Some(40.0));
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+
+        let item = world
+            .player_character
+            .inventory
+            .find_item("Longsword")
+            .unwrap();
+        assert_eq!(item.value_gp, 40.0);
+        assert_eq!(item.weight, standard.weight);
+        assert_eq!(item.item_type, ItemType::Weapon);
+        assert!(!item.magical);
     }
 
     #[test]
@@ -1,8 +1,11 @@
-//! Miscellaneous resolution methods (experience, features, facts, consequences, ability scores).
+//! Miscellaneous resolution methods (experience, features, facts, consequences, ability scores,
+//! reputation).
 
 use crate::rules::types::{Effect, Resolution};
 use crate::rules::RulesEngine;
-use crate::world::{Ability, CharacterId, GameWorld};
+use crate::world::{
+    reputation_standing, Ability, CharacterId, FightingStyle, GameWorld, REPUTATION_RANGE,
+};
 
 impl RulesEngine {
     pub(crate) fn resolve_gain_experience(&self, world: &GameWorld, amount: u32) -> Resolution {
@@ -29,11 +32,165 @@ impl RulesEngine {
 
         if new_level > current_level {
             resolution = resolution.with_effect(Effect::LevelUp { new_level });
+
+            if let Some(class) = world.player_character.classes.first().map(|c| c.class) {
+                for level in (current_level + 1)..=new_level {
+                    if class.grants_asi_at(level) {
+                        resolution.narrative.push_str(&format!(
+                            " Level {level}: Ability Score Improvement available (+2 to one ability, +1 to two, or a feat)."
+                        ));
+                        resolution = resolution.with_effect(Effect::AsiAvailable { level });
+                    }
+                }
+
+                // The prepared-spell limit grows with the class level
+                let character = &world.player_character;
+                let class_level = character.classes[0].level + (new_level - current_level);
+                if let Some(limit) = class.spellcasting_ability().and_then(|ability| {
+                    class.max_prepared_spells(
+                        class_level,
+                        character.ability_scores.modifier(ability),
+                    )
+                }) {
+                    resolution
+                        .narrative
+                        .push_str(&format!(" Can now prepare {limit} spells."));
+                }
+            }
         }
 
         resolution
     }
 
+    pub(crate) fn resolve_apply_asi(
+        &self,
+        world: &GameWorld,
+        ability: Ability,
+        amount: u8,
+    ) -> Resolution {
+        let character = &world.player_character;
+
+        if amount == 0 || amount > 2 {
+            return Resolution::new(format!(
+                "An Ability Score Improvement raises a score by 1 or 2, not {amount}"
+            ));
+        }
+        if character.asi_points < amount {
+            return Resolution::new(format!(
+                "{} has {} Ability Score Improvement point(s) to spend, not {}",
+                character.name, character.asi_points, amount
+            ));
+        }
+
+        let current = character.ability_scores.get(ability);
+        if current >= 20 {
+            return Resolution::new(format!(
+                "{}'s {} is already 20 and cannot be raised further",
+                character.name,
+                ability.name()
+            ));
+        }
+
+        let new_score = (current + amount).min(20);
+        let applied = new_score - current;
+
+        Resolution::new(format!(
+            "{}'s {} increases from {} to {} ({} Ability Score Improvement point(s) remaining)",
+            character.name,
+            ability.name(),
+            current,
+            new_score,
+            character.asi_points - applied
+        ))
+        .with_effect(Effect::AbilityScoreImproved {
+            ability,
+            amount: applied,
+            new_score,
+        })
+    }
+
+    pub(crate) fn resolve_choose_feat(
+        &self,
+        world: &GameWorld,
+        feat_name: &str,
+        description: &str,
+    ) -> Resolution {
+        let character = &world.player_character;
+
+        if character.asi_points < 2 {
+            return Resolution::new(format!(
+                "{} needs an unspent Ability Score Improvement to take a feat",
+                character.name
+            ));
+        }
+        if character.features.iter().any(|f| f.name == feat_name) {
+            return Resolution::new(format!("{} already has {}", character.name, feat_name));
+        }
+
+        Resolution::new(format!("{} gains the {} feat", character.name, feat_name)).with_effect(
+            Effect::FeatChosen {
+                feat_name: feat_name.to_string(),
+                description: description.to_string(),
+            },
+        )
+    }
+
+    pub(crate) fn resolve_choose_fighting_style(
+        &self,
+        world: &GameWorld,
+        style: FightingStyle,
+    ) -> Resolution {
+        let character = &world.player_character;
+
+        // Each class with a Fighting Style feature at its current level grants one pick
+        let eligible: Vec<_> = character
+            .classes
+            .iter()
+            .filter(|c| {
+                c.class
+                    .fighting_style_level()
+                    .is_some_and(|level| c.level >= level)
+            })
+            .map(|c| c.class)
+            .collect();
+
+        if eligible.is_empty() {
+            return Resolution::new(format!(
+                "{} has no Fighting Style feature at this level",
+                character.name
+            ));
+        }
+        if character.fighting_styles.contains(&style) {
+            return Resolution::new(format!(
+                "{} already has the {} fighting style",
+                character.name, style
+            ));
+        }
+        if character.fighting_styles.len() >= eligible.len() {
+            return Resolution::new(format!(
+                "{} has already chosen a Fighting Style",
+                character.name
+            ));
+        }
+        if !eligible.iter().any(|class| style.available_to(*class)) {
+            return Resolution::new(format!(
+                "The {} fighting style is not available to {}",
+                style,
+                eligible
+                    .iter()
+                    .map(|c| c.name())
+                    .collect::<Vec<_>>()
+                    .join(" or ")
+            ));
+        }
+
+        Resolution::new(format!(
+            "{} adopts the {} fighting style",
+            character.name, style
+        ))
+        .with_effect(Effect::FightingStyleChosen { style })
+    }
+
     pub(crate) fn resolve_use_feature(
         &self,
         world: &GameWorld,
@@ -51,10 +208,7 @@ impl RulesEngine {
                         feature_name,
                         uses.current - 1
                     ))
-                    .with_effect(Effect::FeatureUsed {
-                        feature_name: feature_name.to_string(),
-                        uses_remaining: uses.current - 1,
-                    })
+                    .use_feature(feature_name, uses.current - 1)
                 } else {
                     Resolution::new(format!(
                         "{} has no uses of {} remaining",
@@ -130,7 +284,7 @@ impl RulesEngine {
         expires_in_turns: Option<u32>,
     ) -> Resolution {
         // Generate a unique ID for this consequence
-        let consequence_id = uuid::Uuid::new_v4().to_string();
+        let consequence_id = crate::world::new_uuid().to_string();
 
         let severity_display = match severity.to_lowercase().as_str() {
             "minor" => "minor",
@@ -185,6 +339,35 @@ impl RulesEngine {
             source: source.to_string(),
         })
     }
+
+    pub(crate) fn resolve_adjust_reputation(
+        &self,
+        world: &GameWorld,
+        faction: &str,
+        amount: i32,
+        reason: &str,
+    ) -> Resolution {
+        let faction = world.faction_name(faction).to_string();
+        let old = world.reputation(&faction);
+        let new_reputation =
+            (old + amount).clamp(*REPUTATION_RANGE.start(), *REPUTATION_RANGE.end());
+
+        let verb = if amount >= 0 { "rises" } else { "falls" };
+        Resolution::new(format!(
+            "Reputation with {} {} to {} ({}): {}",
+            faction,
+            verb,
+            new_reputation,
+            reputation_standing(new_reputation),
+            reason
+        ))
+        .with_effect(Effect::ReputationChanged {
+            faction,
+            amount: new_reputation - old,
+            new_reputation,
+            reason: reason.to_string(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -241,6 +424,122 @@ mod tests {
             .any(|e| matches!(e, Effect::LevelUp { new_level: 2 })));
     }
 
+    #[test]
+    fn test_level_four_fighter_gets_asi() {
+        let character = create_sample_fighter("Roland");
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let needed = 2700 - world.player_character.experience;
+
+        let resolution = engine.resolve_gain_experience(&world, needed);
+        assert!(resolution
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::AsiAvailable { level: 4 })));
+        assert!(resolution.narrative.contains("Ability Score Improvement"));
+
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.player_character.level, 4);
+        assert_eq!(world.player_character.asi_points, 2);
+    }
+
+    #[test]
+    fn test_apply_asi_raises_modifier() {
+        let mut character = create_sample_fighter("Roland");
+        character.asi_points = 2;
+        character.ability_scores.strength = 16;
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let old_modifier = world
+            .player_character
+            .ability_scores
+            .modifier(Ability::Strength);
+
+        let resolution = engine.resolve_apply_asi(&world, Ability::Strength, 2);
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+
+        let pc = &world.player_character;
+        assert_eq!(pc.ability_scores.strength, 18);
+        assert_eq!(
+            pc.ability_scores.modifier(Ability::Strength),
+            old_modifier + 1
+        );
+        assert_eq!(pc.asi_points, 0);
+    }
+
+    #[test]
+    fn test_apply_asi_clamps_at_twenty() {
+        let mut character = create_sample_fighter("Roland");
+        character.asi_points = 2;
+        character.ability_scores.strength = 19;
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_apply_asi(&world, Ability::Strength, 2);
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.player_character.ability_scores.strength, 20);
+        assert_eq!(world.player_character.asi_points, 1);
+
+        let resolution = engine.resolve_apply_asi(&world, Ability::Strength, 1);
+        assert!(resolution.effects.is_empty());
+    }
+
+    #[test]
+    fn test_apply_asi_requires_points() {
+        let world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_apply_asi(&world, Ability::Strength, 2);
+        assert!(resolution.effects.is_empty());
+    }
+
+    #[test]
+    fn test_choose_feat_spends_full_asi() {
+        let mut character = create_sample_fighter("Roland");
+        character.asi_points = 2;
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_choose_feat(&world, "Tough", "+2 HP per level");
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+
+        assert_eq!(world.player_character.asi_points, 0);
+        assert!(world
+            .player_character
+            .features
+            .iter()
+            .any(|f| f.name == "Tough" && f.source == "Feat"));
+    }
+
+    #[test]
+    fn test_resilient_feat_grants_save_proficiency() {
+        let mut character = create_sample_fighter("Roland");
+        character.asi_points = 2;
+        let wisdom = character.ability_scores.get(Ability::Wisdom);
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        assert!(!world
+            .player_character
+            .saving_throw_proficiencies
+            .contains(&Ability::Wisdom));
+        let before = world
+            .player_character
+            .saving_throw_modifier(Ability::Wisdom);
+
+        let resolution = engine.resolve_choose_feat(&world, "Resilient (Wisdom)", "");
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+
+        let character = &world.player_character;
+        assert!(character
+            .saving_throw_proficiencies
+            .contains(&Ability::Wisdom));
+        assert_eq!(character.ability_scores.get(Ability::Wisdom), wisdom + 1);
+        assert!(
+            character.saving_throw_modifier(Ability::Wisdom)
+                >= before + character.proficiency_bonus()
+        );
+    }
+
     #[test]
     fn test_gain_experience_multiple_levels() {
         let mut character = create_sample_fighter("Roland");
@@ -473,4 +772,94 @@ mod tests {
         assert!(resolution.narrative.contains("-2"));
         assert!(resolution.narrative.contains("for 1 hour"));
     }
+
+    #[test]
+    fn test_defense_style_raises_armored_ac() {
+        let mut character = create_sample_fighter("Roland");
+        character.equipment.armor = crate::items::get_armor("Chain Mail");
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let before = world.player_character.current_ac();
+
+        let resolution = engine.resolve_choose_fighting_style(&world, FightingStyle::Defense);
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+
+        assert_eq!(world.player_character.current_ac(), before + 1);
+        world.player_character.equipment.armor = None;
+        world.player_character.equipment.main_hand = crate::items::get_weapon("Longsword");
+        assert_eq!(world.player_character.current_ac(), 12); // 10 + DEX, no Defense bonus
+    }
+
+    #[test]
+    fn test_only_one_fighting_style_per_feature() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_choose_fighting_style(&world, FightingStyle::Archery);
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        assert!(world
+            .player_character
+            .fighting_styles
+            .contains(&FightingStyle::Archery));
+
+        let second = engine.resolve_choose_fighting_style(&world, FightingStyle::Dueling);
+        assert!(second.effects.is_empty());
+        assert!(second.narrative.contains("already chosen"));
+    }
+
+    #[test]
+    fn test_paladin_cannot_take_archery() {
+        let world = GameWorld::new("Test", crate::world::create_sample_paladin("Aria"));
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve_choose_fighting_style(&world, FightingStyle::Archery);
+        assert!(resolution.effects.is_empty());
+        assert!(resolution.narrative.contains("not available"));
+
+        let resolution = engine.resolve_choose_fighting_style(&world, FightingStyle::Defense);
+        assert_eq!(resolution.effects.len(), 1);
+    }
+
+    // ========== Reputation Tests ==========
+
+    #[test]
+    fn test_adjust_reputation() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let engine = RulesEngine::new();
+        assert_eq!(world.reputation("City Watch"), 0);
+
+        let resolution =
+            engine.resolve_adjust_reputation(&world, "City Watch", 20, "Caught a pickpocket");
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.reputation("City Watch"), 20);
+        assert!(resolution.narrative.contains("friendly"));
+
+        // Later changes find the faction regardless of case
+        let resolution =
+            engine.resolve_adjust_reputation(&world, "city watch", -5, "Brawled in the square");
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.reputation("CITY WATCH"), 15);
+        assert_eq!(world.factions.len(), 1);
+    }
+
+    #[test]
+    fn test_reputation_is_clamped() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let engine = RulesEngine::new();
+        world.factions.insert("Thieves' Guild".to_string(), -90);
+
+        let resolution =
+            engine.resolve_adjust_reputation(&world, "Thieves' Guild", -50, "Burned the hideout");
+        assert!(resolution.effects.iter().any(|e| matches!(
+            e,
+            Effect::ReputationChanged {
+                amount: -10,
+                new_reputation: -100,
+                ..
+            }
+        )));
+        crate::rules::apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.reputation("Thieves' Guild"), -100);
+        assert_eq!(crate::world::reputation_standing(-100), "hostile");
+    }
 }
@@ -1,10 +1,11 @@
 //! Spell casting resolution.
 
-use crate::dice;
-use crate::rules::helpers::roll_with_fallback;
-use crate::rules::types::{Effect, Resolution};
+use crate::dice::Advantage;
+use crate::rules::engine::D20Stakes;
+use crate::rules::types::{Effect, Intent, Resolution};
 use crate::rules::RulesEngine;
-use crate::world::{CharacterId, GameWorld};
+use crate::spells::{SpellClass, SpellData};
+use crate::world::{Character, CharacterClass, CharacterId, ClassLevel, GameWorld};
 
 impl RulesEngine {
     pub(crate) fn resolve_cast_spell(
@@ -31,7 +32,7 @@ impl RulesEngine {
         };
 
         // Determine the effective slot level
-        let effective_slot = if spell.level == 0 {
+        let mut effective_slot = if spell.level == 0 {
             0 // Cantrips don't use slots
         } else if slot_level == 0 {
             spell.level // Use base spell level if not specified
@@ -44,7 +45,9 @@ impl RulesEngine {
             slot_level
         };
 
-        // Check and consume spell slot (if not a cantrip)
+        // Check and consume spell slot (if not a cantrip). Without a regular
+        // slot, a Pact Magic slot is used, always cast at its own level.
+        let mut pact_remaining = None;
         if spell.level > 0 {
             if let Some(ref spellcasting) = caster.spellcasting {
                 let slot_idx = (effective_slot - 1) as usize;
@@ -52,11 +55,19 @@ impl RulesEngine {
                     return Resolution::new("Invalid spell slot level.");
                 }
                 let available = spellcasting.spell_slots.slots[slot_idx].available();
-                if available == 0 {
-                    return Resolution::new(format!(
-                        "{} has no level {} spell slots remaining!",
-                        caster.name, effective_slot
-                    ));
+                if available == 0 && !self.replaying() {
+                    match spellcasting.pact_slots {
+                        Some(pact) if pact.available() > 0 && pact.level >= effective_slot => {
+                            effective_slot = pact.level;
+                            pact_remaining = Some(pact.available() - 1);
+                        }
+                        _ => {
+                            return Resolution::new(format!(
+                                "{} has no level {} spell slots remaining!",
+                                caster.name, effective_slot
+                            ));
+                        }
+                    }
                 }
             } else {
                 return Resolution::new(format!(
@@ -66,15 +77,52 @@ impl RulesEngine {
             }
         }
 
-        // Get spellcasting ability modifier
+        // In combat, the spell spends part of the caster's turn
+        let action = spell.casting_time.action_type();
+        if let (Some(combat), false) = (&world.combat, self.replaying()) {
+            if let Some(reason) = combat
+                .turn_actions(caster.id)
+                .spell_blocked(action, spell.level)
+            {
+                return Resolution::new(format!(
+                    "{} can't cast {}: {} {}.",
+                    caster.name, spell.name, caster.name, reason
+                ));
+            }
+        }
+
+        // Costly material components can't be replaced by a focus, so the
+        // caster must actually carry them
+        let component = match &spell.components.cost {
+            Some(cost) => match caster.inventory.find_component(&cost.item, cost.value_gp) {
+                Some(item) => Some((item, cost.consumed)),
+                None if self.replaying() => None,
+                None => {
+                    return Resolution::new(format!(
+                        "{} lacks the material component for {}: {}.",
+                        caster.name,
+                        spell.name,
+                        spell.components.material.as_deref().unwrap_or(&cost.item)
+                    ));
+                }
+            },
+            None => None,
+        };
+
+        // Read from the caster's current scores, so ability changes apply
         let spell_mod = caster
             .spellcasting
             .as_ref()
-            .map(|sc| caster.ability_scores.modifier(sc.ability))
+            .map(|sc| caster.effective_ability_scores().modifier(sc.ability))
             .unwrap_or(0);
-        let spell_attack_bonus = spell_mod + caster.proficiency_bonus();
+        let spell_attack_bonus = caster
+            .spell_attack_bonus()
+            .unwrap_or_else(|| caster.proficiency_bonus());
         // Minimum DC of 8 as a sanity floor (though in practice, no valid build would go lower)
-        let spell_save_dc = (8 + spell_mod + caster.proficiency_bonus()).max(8);
+        let spell_save_dc = caster
+            .spell_save_dc()
+            .map_or(8 + caster.proficiency_bonus(), |dc| dc as i8)
+            .max(8);
 
         // Build the resolution
         let mut resolution = Resolution::new(String::new());
@@ -93,9 +141,64 @@ impl RulesEngine {
             caster.name, spell.name, slot_text
         ));
 
+        if world.combat.is_some() {
+            resolution = resolution.with_effect(Effect::ActionUsed {
+                character_id: caster.id,
+                action,
+                spell_level: Some(spell.level),
+            });
+        }
+
+        if let Some((item, true)) = component {
+            narrative_parts.push(format!("The {} is consumed.", item.name));
+            resolution = resolution.with_effect(Effect::ItemRemoved {
+                item_name: item.name.clone(),
+                quantity: 1,
+                remaining: item.quantity - 1,
+            });
+        }
+
         // Handle concentration
         if spell.concentration {
             narrative_parts.push("(Concentration)".to_string());
+            resolution = resolution.with_effect(Effect::ConcentrationStarted {
+                character_id: caster.id,
+                spell_name: spell.name.clone(),
+            });
+        }
+
+        // AC and speed bonuses are only tracked on the player, so one aimed
+        // at anyone else is left to the narration
+        let targets_caster = target_names.is_empty()
+            || target_names
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(&caster.name));
+        if let Some(ac_bonus) = spell.ac_bonus().filter(|_| targets_caster) {
+            narrative_parts.push(format!(
+                "{}'s AC rises by {} to {}.",
+                caster.name,
+                ac_bonus.bonus,
+                caster.current_ac() as i16 + ac_bonus.bonus as i16
+            ));
+            resolution = resolution.with_effect(Effect::AcBonusApplied {
+                target_id: caster.id,
+                source: ac_bonus.source,
+                bonus: ac_bonus.bonus,
+                duration_rounds: ac_bonus.duration_rounds,
+                timing: ac_bonus.timing,
+            });
+        }
+
+        if let Some(speed_bonus) = spell.speed_bonus().filter(|_| targets_caster) {
+            narrative_parts.push(format!(
+                "{}'s speed rises by {} feet.",
+                caster.name, speed_bonus.feet
+            ));
+            resolution = resolution.with_effect(Effect::SpeedBonusApplied {
+                target_id: caster.id,
+                source: speed_bonus.source,
+                feet: speed_bonus.feet,
+            });
         }
 
         // Determine damage dice (accounting for cantrip scaling and upcasting)
@@ -110,7 +213,7 @@ impl RulesEngine {
             };
 
             // Roll spell attack
-            let attack_roll = roll_with_fallback(&format!("1d20+{}", spell_attack_bonus), "1d20");
+            let attack_roll = self.roll_d20_check(spell_attack_bonus.into(), Advantage::Normal);
 
             resolution = resolution.with_effect(Effect::DiceRolled {
                 roll: attack_roll.clone(),
@@ -152,18 +255,12 @@ impl RulesEngine {
                 // Roll damage
                 if let Some(ref dice_str) = damage_dice {
                     let damage_formula = if attack_roll.is_critical() {
-                        // Double dice on crit
-                        if let Some(d_pos) = dice_str.find('d') {
-                            let num: i32 = dice_str[..d_pos].parse().unwrap_or(1);
-                            format!("{}d{}", num * 2, &dice_str[d_pos + 1..])
-                        } else {
-                            dice_str.clone()
-                        }
+                        world.house_rules.crit_rule.critical_dice(dice_str)
                     } else {
                         dice_str.clone()
                     };
 
-                    if let Ok(damage_roll) = dice::roll(&damage_formula) {
+                    if let Ok(damage_roll) = self.roll_player(&damage_formula) {
                         let damage_type_name =
                             spell.damage_type.map(|dt| dt.name()).unwrap_or("magical");
 
@@ -179,6 +276,16 @@ impl RulesEngine {
                     }
                 }
             } else {
+                // A reroll that turns the miss into a hit replays the cast
+                self.stake_last_d20(D20Stakes::Attack {
+                    intent: Intent::CastSpell {
+                        caster_id: caster.id,
+                        spell_name: spell_name.to_string(),
+                        targets: Vec::new(),
+                        spell_level: slot_level,
+                        target_names: target_names.to_vec(),
+                    },
+                });
                 narrative_parts.push("Miss!".to_string());
                 resolution = resolution.with_effect(Effect::AttackMissed {
                     attacker_name: caster.name.clone(),
@@ -201,7 +308,7 @@ impl RulesEngine {
 
             // Roll damage (before save resolution)
             if let Some(ref dice_str) = damage_dice {
-                if let Ok(damage_roll) = dice::roll(dice_str) {
+                if let Ok(damage_roll) = self.roll_player(dice_str) {
                     let damage_type_name =
                         spell.damage_type.map(|dt| dt.name()).unwrap_or("magical");
 
@@ -220,7 +327,7 @@ impl RulesEngine {
         // Handle healing spells
         else if let Some(ref healing_dice) = spell.healing_dice {
             let healing_formula = format!("{}+{}", healing_dice, spell_mod);
-            if let Ok(healing_roll) = dice::roll(&healing_formula) {
+            if let Ok(healing_roll) = self.roll_player(&healing_formula) {
                 let target_name = target_names.first().map(|s| s.as_str()).unwrap_or("target");
                 narrative_parts.push(format!(
                     "{} heals {} for {} HP.",
@@ -239,7 +346,12 @@ impl RulesEngine {
         }
 
         // Add spell slot consumption effect (for leveled spells)
-        if spell.level > 0 {
+        if let Some(remaining) = pact_remaining {
+            resolution = resolution.with_effect(Effect::PactSlotUsed {
+                level: effective_slot,
+                remaining,
+            });
+        } else if spell.level > 0 {
             resolution = resolution.with_effect(Effect::SpellSlotUsed {
                 level: effective_slot,
                 remaining: 0, // Will be calculated by effect application
@@ -282,13 +394,326 @@ impl RulesEngine {
             new_remaining,
         })
     }
+
+    pub(crate) fn resolve_prepare_spell(&self, world: &GameWorld, spell_name: &str) -> Resolution {
+        use crate::spells::find_spell;
+
+        let caster = &world.player_character;
+        let Some(spellcasting) = caster.spellcasting.as_ref() else {
+            return Resolution::new(format!("{} cannot cast spells", caster.name));
+        };
+        let Some(limit) = caster.max_prepared_spells() else {
+            return Resolution::new(format!(
+                "{} doesn't prepare spells; the spells they know are always ready",
+                caster.name
+            ));
+        };
+        let Some(spell) = find_spell(spell_name) else {
+            return Resolution::new(format!(
+                "Unknown spell: '{}'. The spell is not in the database.",
+                spell_name
+            ));
+        };
+        if spell.is_cantrip() {
+            return Resolution::new(format!(
+                "{} is a cantrip; cantrips are always ready and aren't prepared",
+                spell.name
+            ));
+        }
+        let prepared = &spellcasting.spells_prepared;
+        if prepared.iter().any(|s| s.eq_ignore_ascii_case(&spell.name)) {
+            return Resolution::new(format!(
+                "{} already has {} prepared",
+                caster.name, spell.name
+            ));
+        }
+
+        // Classes that prepare spells and have this one on their list
+        let preparing = match classes_for_spell(caster, spell, |c| {
+            c.class.max_prepared_spells(c.level, 0).is_some()
+        }) {
+            Ok(classes) => classes,
+            Err(reason) => return Resolution::new(reason),
+        };
+        let in_spellbook = spellcasting
+            .spellbook
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(&spell.name));
+        if preparing.iter().all(|c| c.class == CharacterClass::Wizard) && !in_spellbook {
+            return Resolution::new(format!(
+                "{} isn't in {}'s spellbook; it must be copied in first",
+                spell.name, caster.name
+            ));
+        }
+        if prepared.len() >= limit {
+            return Resolution::new(format!(
+                "{} already has {}/{} spells prepared; unprepare one first",
+                caster.name,
+                prepared.len(),
+                limit
+            ));
+        }
+
+        Resolution::new(format!(
+            "{} prepares {} ({}/{} prepared)",
+            caster.name,
+            spell.name,
+            prepared.len() + 1,
+            limit
+        ))
+        .with_effect(Effect::SpellPrepared {
+            spell_name: spell.name.clone(),
+        })
+    }
+
+    pub(crate) fn resolve_learn_spell(
+        &self,
+        world: &GameWorld,
+        spell_name: &str,
+        replace: Option<&str>,
+    ) -> Resolution {
+        use crate::spells::find_spell;
+
+        let caster = &world.player_character;
+        let Some(spellcasting) = caster.spellcasting.as_ref() else {
+            return Resolution::new(format!("{} cannot cast spells", caster.name));
+        };
+        let limit = caster
+            .classes
+            .iter()
+            .filter_map(|c| c.class.spells_known_at_level(c.level))
+            .reduce(|total, known| total + known);
+        let Some(limit) = limit else {
+            return Resolution::new(format!(
+                "{} doesn't learn a fixed list of spells",
+                caster.name
+            ));
+        };
+        let Some(spell) = find_spell(spell_name) else {
+            return Resolution::new(format!(
+                "Unknown spell: '{}'. The spell is not in the database.",
+                spell_name
+            ));
+        };
+        if spell.is_cantrip() {
+            return Resolution::new(format!(
+                "{} is a cantrip and doesn't count against known spells",
+                spell.name
+            ));
+        }
+        let known = &spellcasting.spells_known;
+        if known.iter().any(|s| s.eq_ignore_ascii_case(&spell.name)) {
+            return Resolution::new(format!("{} already knows {}", caster.name, spell.name));
+        }
+        if let Err(reason) = classes_for_spell(caster, spell, |c| {
+            c.class.spells_known_at_level(c.level).is_some()
+        }) {
+            return Resolution::new(reason);
+        }
+
+        let Some(replace) = replace else {
+            if known.len() >= limit {
+                return Resolution::new(format!(
+                    "{} already knows {}/{} spells; at a level-up one can be replaced instead",
+                    caster.name,
+                    known.len(),
+                    limit
+                ));
+            }
+            return Resolution::new(format!(
+                "{} learns {} ({}/{} known)",
+                caster.name,
+                spell.name,
+                known.len() + 1,
+                limit
+            ))
+            .with_effect(Effect::SpellLearned {
+                spell_name: spell.name.clone(),
+                replaced: None,
+            });
+        };
+
+        let Some(replaced) = known
+            .iter()
+            .find(|s| s.eq_ignore_ascii_case(replace.trim()))
+        else {
+            return Resolution::new(format!("{} doesn't know {}", caster.name, replace));
+        };
+        if caster.spell_swaps == 0 {
+            return Resolution::new(format!(
+                "{} can only replace a known spell on gaining a level",
+                caster.name
+            ));
+        }
+
+        Resolution::new(format!(
+            "{} forgets {} and learns {}",
+            caster.name, replaced, spell.name
+        ))
+        .with_effect(Effect::SpellLearned {
+            spell_name: spell.name.clone(),
+            replaced: Some(replaced.clone()),
+        })
+    }
+
+    pub(crate) fn resolve_copy_spell_to_book(
+        &self,
+        world: &GameWorld,
+        spell_name: &str,
+        free: bool,
+    ) -> Resolution {
+        use crate::spells::find_spell;
+
+        let caster = &world.player_character;
+        let spellbook = caster.spellcasting.as_ref().map(|sc| &sc.spellbook);
+        let is_wizard = caster
+            .classes
+            .iter()
+            .any(|c| c.class == CharacterClass::Wizard);
+        let Some(spellbook) = spellbook.filter(|_| is_wizard) else {
+            return Resolution::new(format!("{} has no spellbook", caster.name));
+        };
+        let Some(spell) = find_spell(spell_name) else {
+            return Resolution::new(format!(
+                "Unknown spell: '{}'. The spell is not in the database.",
+                spell_name
+            ));
+        };
+        if spell.is_cantrip() {
+            return Resolution::new(format!(
+                "{} is a cantrip; cantrips aren't kept in a spellbook",
+                spell.name
+            ));
+        }
+        if spellbook
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(&spell.name))
+        {
+            return Resolution::new(format!(
+                "{} is already in {}'s spellbook",
+                spell.name, caster.name
+            ));
+        }
+        if let Err(reason) = classes_for_spell(caster, spell, |c| c.class == CharacterClass::Wizard)
+        {
+            return Resolution::new(reason);
+        }
+
+        if free {
+            if caster.free_spellbook_spells == 0 {
+                return Resolution::new(format!(
+                    "{} has no free spellbook spells left from leveling up",
+                    caster.name
+                ));
+            }
+            return Resolution::new(format!(
+                "{} adds {} to their spellbook",
+                caster.name, spell.name
+            ))
+            .with_effect(Effect::SpellCopied {
+                spell_name: spell.name.clone(),
+                free: true,
+            });
+        }
+
+        // 2 hours and 50 gp per spell level
+        let cost = 50 * spell.level as i32;
+        let hours = 2 * spell.level as u32;
+        let gold = caster.inventory.gold;
+        if gold < cost {
+            return Resolution::new(format!(
+                "Copying {} takes {} gp of rare inks; {} has {} gp",
+                spell.name, cost, caster.name, gold
+            ));
+        }
+
+        Resolution::new(format!(
+            "{} spends {} hours and {} gp copying {} into their spellbook",
+            caster.name, hours, cost, spell.name
+        ))
+        .with_effect(Effect::GoldChanged {
+            amount: -cost,
+            new_total: gold - cost,
+            reason: format!("copying {} into spellbook", spell.name),
+        })
+        .with_effect(Effect::TimeAdvanced {
+            minutes: hours * 60,
+        })
+        .with_effect(Effect::SpellCopied {
+            spell_name: spell.name.clone(),
+            free: false,
+        })
+    }
+
+    pub(crate) fn resolve_unprepare_spell(
+        &self,
+        world: &GameWorld,
+        spell_name: &str,
+    ) -> Resolution {
+        let caster = &world.player_character;
+        let prepared = caster.spellcasting.as_ref().and_then(|sc| {
+            sc.spells_prepared
+                .iter()
+                .find(|s| s.eq_ignore_ascii_case(spell_name.trim()))
+        });
+        let Some(prepared) = prepared else {
+            return Resolution::new(format!(
+                "{} doesn't have {} prepared",
+                caster.name, spell_name
+            ));
+        };
+
+        Resolution::new(format!(
+            "{} no longer has {} prepared",
+            caster.name, prepared
+        ))
+        .with_effect(Effect::SpellUnprepared {
+            spell_name: prepared.clone(),
+        })
+    }
+}
+
+/// The caster's classes, among those picked by `uses`, that have `spell` on
+/// their list at a level they can cast, or why none do.
+fn classes_for_spell<'a>(
+    caster: &'a Character,
+    spell: &SpellData,
+    uses: impl Fn(&ClassLevel) -> bool,
+) -> Result<Vec<&'a ClassLevel>, String> {
+    let on_list: Vec<_> = caster
+        .classes
+        .iter()
+        .filter(|c| uses(c))
+        .filter(|c| SpellClass::from_class(c.class).is_some_and(|sc| spell.classes.contains(&sc)))
+        .collect();
+    if on_list.is_empty() {
+        return Err(format!(
+            "{} is not on {}'s spell list",
+            spell.name, caster.name
+        ));
+    }
+    let castable: Vec<_> = on_list
+        .into_iter()
+        .filter(|c| spell.level <= c.class.max_spell_level(c.level))
+        .collect();
+    if castable.is_empty() {
+        return Err(format!(
+            "{} can't take {} spells yet",
+            caster.name,
+            spell.level_and_school()
+        ));
+    }
+    Ok(castable)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::rules::types::Effect;
-    use crate::world::{create_sample_cleric, create_sample_fighter, GameWorld};
+    use crate::world::{
+        create_sample_bard, create_sample_cleric, create_sample_fighter, create_sample_sorcerer,
+        ActionType, GameWorld, TurnActions,
+    };
 
     // ========== Cast Spell Tests ==========
 
@@ -331,6 +756,69 @@ mod tests {
             .any(|e| matches!(e, Effect::SpellSlotUsed { .. })));
     }
 
+    #[test]
+    fn test_spell_attack_crit_follows_house_rule() {
+        use crate::world::CritRule;
+
+        let mut world = GameWorld::new("Test", create_sample_sorcerer("Mage"));
+        world.player_character.level = 1;
+        world.house_rules.crit_rule = CritRule::Brutal;
+        let engine = RulesEngine::new();
+
+        let resolution = loop {
+            let resolution = engine.resolve_cast_spell(
+                &world,
+                world.player_character.id,
+                "Fire Bolt",
+                0,
+                &["Goblin".to_string()],
+            );
+            let critical = resolution.effects.iter().any(|e| {
+                matches!(
+                    e,
+                    Effect::AttackHit {
+                        is_critical: true,
+                        ..
+                    }
+                )
+            });
+            if critical {
+                break resolution;
+            }
+        };
+
+        let damage = resolution
+            .effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::DiceRolled { roll, purpose } if purpose == "Fire Bolt damage" => Some(roll),
+                _ => None,
+            })
+            .expect("a critical hit rolls damage");
+        // Both sets of 1d10 deal maximum damage
+        assert_eq!(damage.total, 20);
+    }
+
+    #[test]
+    fn test_longstrider_raises_speed() {
+        use crate::rules::apply_effects;
+
+        let mut world = GameWorld::new("Test", create_sample_cleric("Sera"));
+        let engine = RulesEngine::new();
+        let speed = world.player_character.effective_speed();
+
+        let resolution =
+            engine.resolve_cast_spell(&world, world.player_character.id, "Longstrider", 1, &[]);
+        apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.player_character.effective_speed(), speed + 10);
+
+        // Recasting refreshes the bonus rather than stacking it
+        let resolution =
+            engine.resolve_cast_spell(&world, world.player_character.id, "Longstrider", 1, &[]);
+        apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.player_character.effective_speed(), speed + 10);
+    }
+
     #[test]
     fn test_cast_spell_slot_too_low() {
         let character = create_sample_cleric("Sera");
@@ -350,6 +838,124 @@ mod tests {
         assert!(resolution.narrative.contains("requires at least level"));
     }
 
+    #[test]
+    fn test_revivify_requires_diamond() {
+        use crate::rules::{apply_effects, Intent};
+
+        let mut character = create_sample_cleric("Sera");
+        character.spellcasting.as_mut().unwrap().spell_slots.slots[2].total = 2;
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let caster_id = world.player_character.id;
+
+        let resolution = engine.resolve_cast_spell(&world, caster_id, "Revivify", 3, &[]);
+        assert!(resolution
+            .narrative
+            .contains("lacks the material component"));
+        assert!(resolution.narrative.contains("300 gp"));
+        assert!(resolution.effects.is_empty());
+
+        let given = engine.resolve(
+            &world,
+            Intent::AddItem {
+                item_name: "Diamond".to_string(),
+                quantity: 1,
+                item_type: None,
+                description: None,
+                magical: false,
+                weight: None,
+                value_gp: Some(300.0),
+            },
+        );
+        apply_effects(&mut world, &given.effects);
+
+        let resolution = engine.resolve_cast_spell(&world, caster_id, "Revivify", 3, &[]);
+        assert!(resolution.narrative.contains("casts Revivify"));
+        assert!(resolution.narrative.contains("Diamond is consumed"));
+        apply_effects(&mut world, &resolution.effects);
+        assert!(!world.player_character.inventory.has_item("Diamond"));
+    }
+
+    #[test]
+    fn test_costly_component_below_value_is_refused() {
+        let mut character = create_sample_cleric("Sera");
+        character.spellcasting.as_mut().unwrap().spell_slots.slots[2].total = 2;
+        character.inventory.add_item(crate::world::Item {
+            name: "Small Diamond".to_string(),
+            quantity: 1,
+            weight: 0.0,
+            value_gp: 50.0,
+            description: None,
+            item_type: crate::world::ItemType::Other,
+            magical: false,
+        });
+        let world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+
+        let resolution =
+            engine.resolve_cast_spell(&world, world.player_character.id, "Revivify", 3, &[]);
+
+        assert!(resolution
+            .narrative
+            .contains("lacks the material component"));
+    }
+
+    #[test]
+    fn test_warlock_casts_with_pact_slots() {
+        use crate::rules::apply_effects;
+        use crate::world::{
+            mechanics::apply_short_rest, Ability, Character, CharacterClass, ClassLevel,
+            SpellSlots, SpellcastingData,
+        };
+
+        let mut character = Character::new("Vesper");
+        character.level = 3;
+        character.ability_scores.charisma = 16;
+        character.classes.push(ClassLevel {
+            class: CharacterClass::Warlock,
+            level: 3,
+            subclass: None,
+        });
+        character.spellcasting = Some(SpellcastingData {
+            ability: Ability::Charisma,
+            spells_known: vec!["Hellish Rebuke".to_string()],
+            spellbook: Vec::new(),
+            spells_prepared: Vec::new(),
+            cantrips_known: Vec::new(),
+            spell_slots: SpellSlots::new(),
+            pact_slots: CharacterClass::Warlock.pact_slots_at_level(3),
+        });
+        let mut world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+        let caster_id = world.player_character.id;
+        let target = ["Cultist".to_string()];
+
+        // A 1st-level spell is cast at the pact slot level of 2
+        for remaining in [1, 0] {
+            let resolution =
+                engine.resolve_cast_spell(&world, caster_id, "Hellish Rebuke", 1, &target);
+            assert!(resolution.narrative.contains("upcast at level 2"));
+            assert!(resolution.effects.iter().any(|e| matches!(
+                e,
+                Effect::PactSlotUsed { level: 2, remaining: r } if *r == remaining
+            )));
+            apply_effects(&mut world, &resolution.effects);
+        }
+
+        let resolution = engine.resolve_cast_spell(&world, caster_id, "Hellish Rebuke", 1, &target);
+        assert!(resolution.narrative.contains("no level 1 spell slots"));
+
+        apply_short_rest(&mut world.player_character);
+        let pact = world
+            .player_character
+            .spellcasting
+            .as_ref()
+            .unwrap()
+            .pact_slots
+            .unwrap();
+        assert_eq!(pact.available(), 2);
+    }
+
     #[test]
     fn test_cast_spell_no_spellcasting_ability() {
         let character = create_sample_fighter("Roland");
@@ -512,4 +1118,415 @@ mod tests {
         assert!(resolution.narrative.contains("Invalid spell slot level"));
         assert!(resolution.effects.is_empty());
     }
+
+    // ========== Spell Preparation Tests ==========
+
+    #[test]
+    fn test_prepare_spell_up_to_limit() {
+        // Level 3 cleric with WIS 16: 3 + 3 = 6 prepared, 3 already taken
+        let mut world = GameWorld::new("Test", create_sample_cleric("Sera"));
+        let engine = RulesEngine::new();
+        assert_eq!(world.player_character.max_prepared_spells(), Some(6));
+
+        for spell in ["Spiritual Weapon", "shield of faith", "Sanctuary"] {
+            let resolution = engine.resolve_prepare_spell(&world, spell);
+            assert_eq!(resolution.effects.len(), 1, "{}", resolution.narrative);
+            crate::rules::apply_effects(&mut world, &resolution.effects);
+        }
+
+        let prepared = &world
+            .player_character
+            .spellcasting
+            .as_ref()
+            .unwrap()
+            .spells_prepared;
+        assert_eq!(prepared.len(), 6);
+        assert!(prepared.contains(&"Shield of Faith".to_string()));
+    }
+
+    #[test]
+    fn test_prepare_spell_over_limit_is_rejected() {
+        let mut world = GameWorld::new("Test", create_sample_cleric("Sera"));
+        let engine = RulesEngine::new();
+        world
+            .player_character
+            .spellcasting
+            .as_mut()
+            .unwrap()
+            .spells_prepared
+            .extend(["Sanctuary", "Command", "Shield of Faith"].map(String::from));
+
+        let resolution = engine.resolve_prepare_spell(&world, "Healing Word");
+
+        assert!(resolution.effects.is_empty());
+        assert!(resolution.narrative.contains("6/6"));
+
+        // Dropping a spell makes room again
+        let unprepare = engine.resolve_unprepare_spell(&world, "command");
+        crate::rules::apply_effects(&mut world, &unprepare.effects);
+        let resolution = engine.resolve_prepare_spell(&world, "Healing Word");
+        assert_eq!(resolution.effects.len(), 1);
+    }
+
+    #[test]
+    fn test_prepare_spell_checks_class_list_and_level() {
+        let world = GameWorld::new("Test", create_sample_cleric("Sera"));
+        let engine = RulesEngine::new();
+
+        let off_list = engine.resolve_prepare_spell(&world, "Fireball");
+        assert!(off_list.effects.is_empty());
+        assert!(off_list.narrative.contains("not on"));
+
+        // A 3rd-level cleric casts up to 2nd-level spells
+        let too_high = engine.resolve_prepare_spell(&world, "Revivify");
+        assert!(too_high.effects.is_empty());
+        assert!(too_high.narrative.contains("can't take"));
+
+        let fighter = GameWorld::new("Test", create_sample_fighter("Roland"));
+        assert!(engine
+            .resolve_prepare_spell(&fighter, "Bless")
+            .effects
+            .is_empty());
+    }
+
+    #[test]
+    fn test_prepared_limit_grows_on_level_up() {
+        let mut world = GameWorld::new("Test", create_sample_cleric("Sera"));
+        crate::rules::apply_effect(&mut world, &Effect::LevelUp { new_level: 4 });
+        assert_eq!(world.player_character.max_prepared_spells(), Some(7));
+    }
+
+    // ========== Learn Spell Tests ==========
+
+    fn known_spells(world: &GameWorld) -> &[String] {
+        &world
+            .player_character
+            .spellcasting
+            .as_ref()
+            .unwrap()
+            .spells_known
+    }
+
+    #[test]
+    fn test_learn_spell_within_known_limit() {
+        // Level 3 bard: 6 spells known, 4 already learned
+        let mut world = GameWorld::new("Test", create_sample_bard("Lyra"));
+        let engine = RulesEngine::new();
+
+        for spell in ["Charm Person", "Thunderwave"] {
+            let resolution = engine.resolve_learn_spell(&world, spell, None);
+            assert_eq!(resolution.effects.len(), 1, "{}", resolution.narrative);
+            crate::rules::apply_effects(&mut world, &resolution.effects);
+        }
+        assert_eq!(known_spells(&world).len(), 6);
+
+        let full = engine.resolve_learn_spell(&world, "Sleep", None);
+        assert!(full.effects.is_empty());
+        assert!(full.narrative.contains("6/6"));
+
+        // Not on the bard list
+        let off_list = engine.resolve_learn_spell(&world, "Misty Step", None);
+        assert!(off_list.effects.is_empty());
+    }
+
+    #[test]
+    fn test_learn_spell_swaps_one_known_spell_at_level_up() {
+        let mut world = GameWorld::new("Test", create_sample_sorcerer("Vex"));
+        let engine = RulesEngine::new();
+
+        let too_early = engine.resolve_learn_spell(&world, "Misty Step", Some("Burning Hands"));
+        assert!(too_early.effects.is_empty());
+
+        // Swaps from earlier levels don't pile up
+        crate::rules::apply_effect(&mut world, &Effect::LevelUp { new_level: 3 });
+        crate::rules::apply_effect(&mut world, &Effect::LevelUp { new_level: 4 });
+        assert_eq!(world.player_character.spell_swaps, 1);
+
+        let swap = engine.resolve_learn_spell(&world, "Misty Step", Some("burning hands"));
+        crate::rules::apply_effects(&mut world, &swap.effects);
+
+        let known = known_spells(&world);
+        assert!(known.contains(&"Misty Step".to_string()));
+        assert!(!known.contains(&"Burning Hands".to_string()));
+        assert_eq!(known.len(), 4);
+        assert_eq!(world.player_character.spell_swaps, 0);
+
+        // The swap is used up, but the new level still allows one more spell
+        let again = engine.resolve_learn_spell(&world, "Sleep", Some("Shield"));
+        assert!(again.effects.is_empty());
+        let learned = engine.resolve_learn_spell(&world, "Sleep", None);
+        assert_eq!(learned.effects.len(), 1);
+    }
+
+    // ========== Spellbook Tests ==========
+
+    fn wizard_world() -> GameWorld {
+        use crate::world::{Ability, Background, RaceType, Skill};
+        let wizard = crate::CharacterBuilder::new()
+            .name("Elara")
+            .race(RaceType::Human)
+            .class(CharacterClass::Wizard)
+            .background(Background::Sage)
+            .standard_array([
+                (15, Ability::Intelligence),
+                (14, Ability::Constitution),
+                (13, Ability::Dexterity),
+                (12, Ability::Wisdom),
+                (10, Ability::Strength),
+                (8, Ability::Charisma),
+            ])
+            .skills(vec![Skill::Investigation, Skill::Insight])
+            .build()
+            .unwrap();
+        GameWorld::new("Test", wizard)
+    }
+
+    #[test]
+    fn test_wizard_prepares_only_from_spellbook() {
+        let world = wizard_world();
+        let engine = RulesEngine::new();
+
+        let in_book = engine.resolve_prepare_spell(&world, "Magic Missile");
+        assert_eq!(in_book.effects.len(), 1, "{}", in_book.narrative);
+
+        let uncopied = engine.resolve_prepare_spell(&world, "Thunderwave");
+        assert!(uncopied.effects.is_empty());
+        assert!(uncopied.narrative.contains("spellbook"));
+    }
+
+    #[test]
+    fn test_copy_spell_to_book_costs_gold_and_time() {
+        let mut world = wizard_world();
+        let engine = RulesEngine::new();
+
+        world.player_character.inventory.gold = 10;
+        let broke = engine.resolve_copy_spell_to_book(&world, "Thunderwave", false);
+        assert!(broke.effects.is_empty());
+
+        world.player_character.inventory.gold = 80;
+        let copy = engine.resolve_copy_spell_to_book(&world, "Thunderwave", false);
+        assert!(copy
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::TimeAdvanced { minutes: 120 })));
+        crate::rules::apply_effects(&mut world, &copy.effects);
+
+        assert_eq!(world.player_character.inventory.gold, 30);
+        let prepared = engine.resolve_prepare_spell(&world, "Thunderwave");
+        assert_eq!(prepared.effects.len(), 1, "{}", prepared.narrative);
+
+        // Only wizards keep a spellbook
+        let cleric = GameWorld::new("Test", create_sample_cleric("Sera"));
+        assert!(engine
+            .resolve_copy_spell_to_book(&cleric, "Bless", false)
+            .effects
+            .is_empty());
+    }
+
+    #[test]
+    fn test_wizard_copies_free_spells_gained_at_level_up() {
+        let mut world = wizard_world();
+        let engine = RulesEngine::new();
+        world.player_character.inventory.gold = 0;
+
+        let none_left = engine.resolve_copy_spell_to_book(&world, "Thunderwave", true);
+        assert!(none_left.effects.is_empty());
+
+        crate::rules::apply_effects(&mut world, &[Effect::LevelUp { new_level: 2 }]);
+        assert_eq!(world.player_character.free_spellbook_spells, 2);
+
+        let copy = engine.resolve_copy_spell_to_book(&world, "Thunderwave", true);
+        assert!(
+            !copy
+                .effects
+                .iter()
+                .any(|e| matches!(e, Effect::GoldChanged { .. } | Effect::TimeAdvanced { .. })),
+            "{:?}",
+            copy.effects
+        );
+        crate::rules::apply_effects(&mut world, &copy.effects);
+
+        let character = &world.player_character;
+        assert_eq!(character.free_spellbook_spells, 1);
+        assert!(character
+            .spellcasting
+            .as_ref()
+            .unwrap()
+            .spellbook
+            .contains(&"Thunderwave".to_string()));
+    }
+
+    // ========== Action Economy Tests ==========
+
+    fn in_combat(character: Character) -> GameWorld {
+        use crate::world::{CombatState, Combatant};
+
+        let mut world = GameWorld::new("Test", character);
+        let player = &world.player_character;
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant {
+            id: player.id,
+            name: player.name.clone(),
+            initiative: 15,
+            is_player: true,
+            is_ally: true,
+            current_hp: player.hit_points.current,
+            max_hp: player.hit_points.maximum,
+            armor_class: 16,
+            resistances: Vec::new(),
+        });
+        combat.add_combatant(Combatant {
+            id: CharacterId::new(),
+            name: "Goblin".to_string(),
+            initiative: 10,
+            is_player: false,
+            is_ally: false,
+            current_hp: 7,
+            max_hp: 7,
+            armor_class: 15,
+            resistances: Vec::new(),
+        });
+        world.combat = Some(combat);
+        world
+    }
+
+    fn cast(engine: &RulesEngine, world: &mut GameWorld, spell: &str) -> Resolution {
+        let resolution = engine.resolve_cast_spell(world, world.player_character.id, spell, 0, &[]);
+        crate::rules::apply_effects(world, &resolution.effects);
+        resolution
+    }
+
+    #[test]
+    fn test_bonus_action_spell_limits_action_to_a_cantrip() {
+        let mut world = in_combat(create_sample_cleric("Sera"));
+        let engine = RulesEngine::new();
+
+        let healing_word = cast(&engine, &mut world, "Healing Word");
+        assert!(healing_word.effects.iter().any(|e| matches!(
+            e,
+            Effect::ActionUsed {
+                action: ActionType::BonusAction,
+                spell_level: Some(1),
+                ..
+            }
+        )));
+
+        let cure_wounds = cast(&engine, &mut world, "Cure Wounds");
+        assert!(cure_wounds.effects.is_empty());
+        assert!(cure_wounds.narrative.contains("only cast a cantrip"));
+
+        let sacred_flame = cast(&engine, &mut world, "Sacred Flame");
+        assert!(
+            !sacred_flame.effects.is_empty(),
+            "{}",
+            sacred_flame.narrative
+        );
+    }
+
+    #[test]
+    fn test_leveled_action_spell_blocks_bonus_action_spell() {
+        let mut world = in_combat(create_sample_cleric("Sera"));
+        let engine = RulesEngine::new();
+
+        cast(&engine, &mut world, "Cure Wounds");
+        let healing_word = cast(&engine, &mut world, "Healing Word");
+        assert!(healing_word.effects.is_empty());
+        assert!(healing_word.narrative.contains("bonus-action spell"));
+    }
+
+    #[test]
+    fn test_spent_action_is_rejected_until_next_turn() {
+        let mut world = in_combat(create_sample_cleric("Sera"));
+        let engine = RulesEngine::new();
+
+        cast(&engine, &mut world, "Healing Word");
+        let again = cast(&engine, &mut world, "Healing Word");
+        assert!(again.effects.is_empty());
+        assert!(again.narrative.contains("already used their bonus action"));
+
+        // The goblin's turn, then the cleric's again
+        let combat = world.combat.as_mut().unwrap();
+        combat.next_turn();
+        assert!(combat
+            .turn_actions(world.player_character.id)
+            .is_used(ActionType::BonusAction));
+        combat.next_turn();
+        assert_eq!(
+            combat.turn_actions(world.player_character.id),
+            TurnActions::default()
+        );
+
+        let next_turn = cast(&engine, &mut world, "Healing Word");
+        assert!(!next_turn.effects.is_empty(), "{}", next_turn.narrative);
+    }
+
+    #[test]
+    fn test_spells_outside_combat_are_not_limited() {
+        let mut world = GameWorld::new("Test", create_sample_cleric("Sera"));
+        let engine = RulesEngine::new();
+
+        cast(&engine, &mut world, "Healing Word");
+        let cure_wounds = cast(&engine, &mut world, "Cure Wounds");
+        assert!(!cure_wounds.effects.is_empty(), "{}", cure_wounds.narrative);
+        assert!(!cure_wounds
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::ActionUsed { .. })));
+    }
+
+    #[test]
+    fn test_shield_raises_ac_until_the_start_of_the_next_turn() {
+        let mut world = in_combat(create_sample_sorcerer("Zara"));
+        let engine = RulesEngine::new();
+        let base_ac = world.player_character.current_ac();
+        let next_turn = |world: &mut GameWorld| {
+            let resolution = engine.resolve_next_turn(world);
+            crate::rules::apply_effects(world, &resolution.effects);
+            resolution
+        };
+
+        // Cast as a reaction on the goblin's turn
+        next_turn(&mut world);
+        let shield = cast(&engine, &mut world, "Shield");
+        assert!(shield
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::AcBonusApplied { bonus: 5, .. })));
+        assert_eq!(world.player_character.current_ac(), base_ac + 5);
+
+        let own_turn = next_turn(&mut world);
+        assert!(own_turn
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::AcBonusEnded { source, .. } if source == "Shield")));
+        assert_eq!(world.player_character.current_ac(), base_ac);
+    }
+
+    #[test]
+    fn test_shield_of_faith_lasts_as_long_as_concentration() {
+        let mut world = in_combat(create_sample_cleric("Sera"));
+        let engine = RulesEngine::new();
+        let base_ac = world.player_character.current_ac();
+
+        cast(&engine, &mut world, "Shield of Faith");
+        assert_eq!(world.player_character.current_ac(), base_ac + 2);
+        for _ in 0..4 {
+            let resolution = engine.resolve_next_turn(&world);
+            crate::rules::apply_effects(&mut world, &resolution.effects);
+        }
+        assert_eq!(world.player_character.current_ac(), base_ac + 2);
+
+        let character_id = world.player_character.id;
+        crate::rules::apply_effects(
+            &mut world,
+            &[Effect::ConcentrationBroken {
+                character_id,
+                spell_name: "Shield of Faith".to_string(),
+                damage_taken: 12,
+                roll: 4,
+                dc: 10,
+            }],
+        );
+        assert_eq!(world.player_character.current_ac(), base_ac);
+    }
 }
@@ -94,9 +94,16 @@ mod tests {
                 current_hp: 20,
                 max_hp: 20,
                 armor_class: 16,
+                resistances: Vec::new(),
             }],
             sneak_attack_used: std::collections::HashSet::new(),
             attacks_this_turn: std::collections::HashMap::new(),
+            recoverable_ammunition: std::collections::HashMap::new(),
+            light_level: None,
+            damage_dealt: 0,
+            damage_taken: 0,
+            defeated: Vec::new(),
+            actions_this_turn: std::collections::HashMap::new(),
         });
         let engine = RulesEngine::new();
 
@@ -148,9 +155,16 @@ mod tests {
                 current_hp: 20,
                 max_hp: 20,
                 armor_class: 16,
+                resistances: Vec::new(),
             }],
             sneak_attack_used: std::collections::HashSet::new(),
             attacks_this_turn: std::collections::HashMap::new(),
+            recoverable_ammunition: std::collections::HashMap::new(),
+            light_level: None,
+            damage_dealt: 0,
+            damage_taken: 0,
+            defeated: Vec::new(),
+            actions_this_turn: std::collections::HashMap::new(),
         });
         let engine = RulesEngine::new();
 
@@ -2,7 +2,7 @@
 
 use crate::rules::types::{Effect, Resolution, StateType};
 use crate::rules::RulesEngine;
-use crate::world::GameWorld;
+use crate::world::{GameWorld, LightLevel};
 
 impl RulesEngine {
     #[allow(clippy::too_many_arguments)]
@@ -209,6 +209,24 @@ impl RulesEngine {
         })
     }
 
+    pub(crate) fn resolve_set_light_level(
+        &self,
+        world: &GameWorld,
+        light_level: Option<LightLevel>,
+    ) -> Resolution {
+        let place = if world.combat.is_some() {
+            "the battlefield".to_string()
+        } else {
+            world.current_location.name.clone()
+        };
+        let narrative = match light_level {
+            Some(level) => format!("{place} is now in {level}."),
+            None => format!("Lighting in {place} returns to normal."),
+        };
+
+        Resolution::new(narrative).with_effect(Effect::LightLevelChanged { light_level })
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn resolve_update_location(
         &self,
@@ -520,6 +538,7 @@ mod tests {
                 disposition: Disposition::Friendly,
                 location_id: None,
                 known_information: vec![],
+                schedule: Vec::new(),
             },
         );
 
@@ -560,6 +579,7 @@ mod tests {
                 disposition: Disposition::Neutral,
                 location_id: None,
                 known_information: vec![],
+                schedule: Vec::new(),
             },
         );
 
@@ -616,6 +636,7 @@ mod tests {
                 disposition: Disposition::Neutral,
                 location_id: None,
                 known_information: vec![],
+                schedule: Vec::new(),
             },
         );
 
@@ -753,6 +774,7 @@ mod tests {
                 connections: vec![],
                 items: vec![],
                 npcs_present: vec![],
+                light_level: None,
             },
         );
 
@@ -833,6 +855,7 @@ mod tests {
                 disposition: Disposition::Neutral,
                 location_id: None,
                 known_information: vec![],
+                schedule: Vec::new(),
             },
         );
 
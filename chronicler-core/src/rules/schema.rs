@@ -0,0 +1,861 @@
+//! A stable JSON representation of effects for external tools.
+//!
+//! Replay viewers and other tools read a session's effects as an
+//! [`EffectLog`] (see [`GameSession::effect_log`](crate::GameSession::effect_log)):
+//! a list of [`Resolution`]s stamped with [`EFFECT_SCHEMA_VERSION`]. Each [`Effect`] is an object whose `"type"`
+//! names the variant in snake_case, with the variant's fields alongside it.
+//! [`schema()`] describes this as a JSON Schema, and a copy is committed at
+//! `chronicler-core/schema/effect-log.schema.json`.
+//!
+//! The version is bumped whenever a change could break a reader: a variant
+//! or field is renamed or removed, or a field changes type. Adding a variant
+//! or field does not bump it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::borrow::Cow;
+
+use super::types::{Effect, Resistance, Resolution, RestType, StateType};
+use crate::dice::DiceRoller;
+use crate::world::{
+    Ability, ActionType, CharacterId, CombatSummary, Condition, DurationTiming, FightingStyle,
+    Item, ItemType, LightLevel, StatBlock,
+};
+
+/// Version of the effect representation described by [`schema()`].
+pub const EFFECT_SCHEMA_VERSION: u32 = 1;
+
+/// Resolutions in the order they happened, tagged with the schema version.
+///
+/// A log can borrow its resolutions, so handing one out doesn't copy them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectLog<'a> {
+    pub schema_version: u32,
+    pub resolutions: Cow<'a, [Resolution]>,
+}
+
+impl<'a> EffectLog<'a> {
+    pub fn new(resolutions: impl Into<Cow<'a, [Resolution]>>) -> Self {
+        Self {
+            schema_version: EFFECT_SCHEMA_VERSION,
+            resolutions: resolutions.into(),
+        }
+    }
+}
+
+/// JSON Schema for an [`EffectLog`].
+///
+/// Each effect variant lists its fields with their JSON shapes, down through
+/// nested objects and arrays. Fields that are optional in Rust may also be
+/// `null` and are not required.
+pub fn schema() -> Value {
+    let filled = filled_examples();
+    let variants: Vec<Value> = examples()
+        .iter()
+        .map(|example| {
+            let filled = filled
+                .iter()
+                .find(|f| std::mem::discriminant(*f) == std::mem::discriminant(example));
+            variant_schema(example, filled)
+        })
+        .collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "EffectLog",
+        "type": "object",
+        "properties": {
+            "schema_version": {"const": EFFECT_SCHEMA_VERSION},
+            "resolutions": {"type": "array", "items": {"$ref": "#/$defs/Resolution"}}
+        },
+        "required": ["schema_version", "resolutions"],
+        "$defs": {
+            "Resolution": {
+                "type": "object",
+                "properties": {
+                    "effects": {"type": "array", "items": {"$ref": "#/$defs/Effect"}},
+                    "narrative": {"type": "string"}
+                },
+                "required": ["effects", "narrative"]
+            },
+            "Effect": {"oneOf": variants}
+        }
+    })
+}
+
+/// Schema for one variant, read off the JSON of an example of it. Optional
+/// fields left `None` in `example` take their shape from `filled`.
+fn variant_schema(example: &Effect, filled: Option<&Effect>) -> Value {
+    let to_value = |effect| serde_json::to_value(effect).expect("effects serialize");
+    let example = to_value(example);
+    let mut schema = value_schema(&example, filled.map(to_value).as_ref());
+    schema["properties"]["type"] = json!({"const": example["type"]});
+    schema
+}
+
+/// Schema for a value, read off its JSON. Where `value` holds `null` or an
+/// empty array, the shape comes from the same place in `filled`.
+fn value_schema(value: &Value, filled: Option<&Value>) -> Value {
+    match value {
+        Value::Null => match filled {
+            Some(filled) if !filled.is_null() => {
+                json!({"anyOf": [value_schema(filled, None), {"type": "null"}]})
+            }
+            _ => json!({}),
+        },
+        Value::Bool(_) => json!({"type": "boolean"}),
+        Value::Number(n) if n.is_f64() => json!({"type": "number"}),
+        Value::Number(_) => json!({"type": "integer"}),
+        Value::String(_) => json!({"type": "string"}),
+        Value::Array(items) => {
+            let filled = filled.and_then(Value::as_array);
+            let items: Vec<Value> = match filled {
+                Some(filled) if items.is_empty() => {
+                    filled.iter().map(|item| value_schema(item, None)).collect()
+                }
+                _ => items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| value_schema(item, filled.and_then(|f| f.get(i))))
+                    .collect(),
+            };
+            match items.first() {
+                None => json!({"type": "array"}),
+                Some(first) if items.iter().all(|item| item == first) => {
+                    json!({"type": "array", "items": first})
+                }
+                // Tuples serialize as arrays of mixed elements
+                Some(_) => json!({"type": "array", "prefixItems": items}),
+            }
+        }
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(name, value)| {
+                    let filled = filled.and_then(|f| f.get(name));
+                    (name.clone(), value_schema(value, filled))
+                })
+                .collect();
+            let required: Vec<&String> = fields
+                .iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(name, _)| name)
+                .collect();
+            json!({"type": "object", "properties": properties, "required": required})
+        }
+    }
+}
+
+/// One example of every effect variant, with `None` for optional fields.
+fn examples() -> Vec<Effect> {
+    let id = CharacterId::new();
+    let roll = DiceRoller::new(0)
+        .roll("1d20+2")
+        .expect("valid dice notation");
+    let text = || "text".to_string();
+
+    vec![
+        Effect::DiceRolled {
+            roll,
+            purpose: text(),
+        },
+        Effect::SneakAttackUsed {
+            character_id: id,
+            damage_dice: 1,
+        },
+        Effect::ActionUsed {
+            character_id: id,
+            action: ActionType::BonusAction,
+            spell_level: Some(1),
+        },
+        Effect::AttackMade { character_id: id },
+        Effect::ExtraActionGained { character_id: id },
+        Effect::HpChanged {
+            target_id: id,
+            amount: -3,
+            new_current: 7,
+            new_max: 10,
+            dropped_to_zero: false,
+        },
+        Effect::ConditionApplied {
+            target_id: id,
+            condition: Condition::Prone,
+            source: text(),
+            duration_rounds: None,
+            timing: DurationTiming::default(),
+        },
+        Effect::AcBonusApplied {
+            target_id: id,
+            source: text(),
+            bonus: 5,
+            duration_rounds: Some(1),
+            timing: DurationTiming::StartOfTurn,
+        },
+        Effect::AcBonusEnded {
+            target_id: id,
+            source: text(),
+        },
+        Effect::SpeedBonusApplied {
+            target_id: id,
+            source: text(),
+            feet: 10,
+        },
+        Effect::SpeedBonusEnded {
+            target_id: id,
+            source: text(),
+        },
+        Effect::ConditionRemoved {
+            target_id: id,
+            condition: Condition::Prone,
+        },
+        Effect::CombatStarted,
+        Effect::CombatEnded { summary: None },
+        Effect::TurnAdvanced {
+            round: 1,
+            current_combatant: text(),
+        },
+        Effect::InitiativeRolled {
+            character_id: id,
+            name: text(),
+            roll: 12,
+            total: 14,
+        },
+        Effect::CombatantAdded {
+            id,
+            name: text(),
+            initiative: 14,
+            is_ally: false,
+            current_hp: 7,
+            max_hp: 7,
+            armor_class: 15,
+            resistances: vec![Resistance::NonmagicalPhysical],
+        },
+        Effect::CombatantHpChanged {
+            id,
+            name: text(),
+            amount: -3,
+            new_current: 4,
+        },
+        Effect::TimeAdvanced { minutes: 10 },
+        Effect::ExperienceGained {
+            amount: 50,
+            new_total: 350,
+        },
+        Effect::LevelUp { new_level: 2 },
+        Effect::AsiAvailable { level: 4 },
+        Effect::AbilityScoreImproved {
+            ability: Ability::Strength,
+            amount: 2,
+            new_score: 18,
+        },
+        Effect::FeatChosen {
+            feat_name: text(),
+            description: text(),
+        },
+        Effect::FightingStyleChosen {
+            style: FightingStyle::Archery,
+        },
+        Effect::FeatureUsed {
+            feature_name: text(),
+            uses_remaining: 0,
+        },
+        Effect::FeatureRecharged {
+            feature_name: text(),
+            uses_remaining: 1,
+        },
+        Effect::SpellSlotUsed {
+            level: 1,
+            remaining: 1,
+        },
+        Effect::PactSlotUsed {
+            level: 1,
+            remaining: 0,
+        },
+        Effect::RestCompleted {
+            rest_type: RestType::Short,
+        },
+        Effect::CheckSucceeded {
+            check_type: text(),
+            roll: 15,
+            dc: 12,
+        },
+        Effect::CheckFailed {
+            check_type: text(),
+            roll: 8,
+            dc: 12,
+        },
+        Effect::HelpGiven {
+            helper: text(),
+            target_name: text(),
+        },
+        Effect::HelpUsed { helper: text() },
+        Effect::GroupCheckResolved {
+            check_type: text(),
+            successes: 2,
+            participants: 3,
+            dc: 12,
+            success: true,
+        },
+        Effect::AttackHit {
+            attacker_name: text(),
+            target_name: text(),
+            attack_roll: 17,
+            target_ac: 15,
+            is_critical: false,
+        },
+        Effect::AttackMissed {
+            attacker_name: text(),
+            target_name: text(),
+            attack_roll: 9,
+            target_ac: 15,
+        },
+        Effect::FactRemembered {
+            subject_name: text(),
+            subject_type: text(),
+            fact: text(),
+            category: text(),
+            related_entities: vec![text()],
+            importance: 0.5,
+        },
+        Effect::ItemAdded {
+            item_name: text(),
+            quantity: 1,
+            new_total: 1,
+            item: None,
+        },
+        Effect::AmmunitionSpent {
+            ammunition: text(),
+            remaining: 19,
+            recoverable: true,
+        },
+        Effect::ItemRemoved {
+            item_name: text(),
+            quantity: 1,
+            remaining: 0,
+        },
+        Effect::ItemEquipped {
+            item_name: text(),
+            slot: text(),
+        },
+        Effect::ItemUnequipped {
+            item_name: text(),
+            slot: text(),
+        },
+        Effect::ItemUsed {
+            item_name: text(),
+            result: text(),
+        },
+        Effect::GoldChanged {
+            amount: 5,
+            new_total: 20,
+            reason: text(),
+        },
+        Effect::SilverChanged {
+            amount: 5,
+            new_total: 20,
+            reason: text(),
+        },
+        Effect::AcChanged {
+            new_ac: 16,
+            source: text(),
+        },
+        Effect::DeathSaveFailure {
+            target_id: id,
+            failures: 1,
+            total_failures: 1,
+            source: text(),
+        },
+        Effect::DeathSavesReset { target_id: id },
+        Effect::DeathSavesSet {
+            target_id: id,
+            successes: 1,
+            failures: 1,
+        },
+        Effect::CharacterDied {
+            target_id: id,
+            cause: text(),
+        },
+        Effect::DeathSaveSuccess {
+            target_id: id,
+            roll: 12,
+            total_successes: 1,
+        },
+        Effect::Stabilized { target_id: id },
+        Effect::ConcentrationBroken {
+            character_id: id,
+            spell_name: text(),
+            damage_taken: 12,
+            roll: 7,
+            dc: 10,
+        },
+        Effect::ConcentrationStarted {
+            character_id: id,
+            spell_name: text(),
+        },
+        Effect::ConcentrationMaintained {
+            character_id: id,
+            spell_name: text(),
+            roll: 14,
+            dc: 10,
+        },
+        Effect::LocationChanged {
+            previous_location: text(),
+            new_location: text(),
+        },
+        Effect::ConsequenceRegistered {
+            consequence_id: text(),
+            trigger_description: text(),
+            consequence_description: text(),
+            severity: text(),
+        },
+        Effect::ConsequenceTriggered {
+            consequence_id: text(),
+            consequence_description: text(),
+        },
+        Effect::ClassResourceUsed {
+            character_name: text(),
+            resource_name: text(),
+            description: text(),
+        },
+        Effect::RageStarted {
+            character_id: id,
+            damage_bonus: 2,
+        },
+        Effect::RageEnded {
+            character_id: id,
+            reason: text(),
+        },
+        Effect::WildShapeStarted {
+            character_id: id,
+            stats: StatBlock::new("Wolf", 11, 13),
+        },
+        Effect::WildShapeHpChanged {
+            character_id: id,
+            amount: -4,
+            new_current: 7,
+        },
+        Effect::WildShapeEnded {
+            character_id: id,
+            reason: text(),
+        },
+        Effect::QuestCreated {
+            name: text(),
+            description: text(),
+            giver: None,
+            objectives: vec![(text(), false)],
+            rewards: vec![text()],
+        },
+        Effect::QuestObjectiveAdded {
+            quest_name: text(),
+            objective: text(),
+            optional: false,
+        },
+        Effect::QuestObjectiveCompleted {
+            quest_name: text(),
+            objective_description: text(),
+        },
+        Effect::QuestCompleted {
+            quest_name: text(),
+            completion_note: None,
+        },
+        Effect::QuestFailed {
+            quest_name: text(),
+            failure_reason: text(),
+        },
+        Effect::QuestUpdated {
+            quest_name: text(),
+            new_description: None,
+            add_rewards: vec![text()],
+        },
+        Effect::NpcCreated {
+            name: text(),
+            location: None,
+        },
+        Effect::NpcUpdated {
+            npc_name: text(),
+            changes: text(),
+        },
+        Effect::NpcMoved {
+            npc_name: text(),
+            from_location: None,
+            to_location: text(),
+        },
+        Effect::NpcRemoved {
+            npc_name: text(),
+            reason: text(),
+        },
+        Effect::LocationCreated {
+            name: text(),
+            location_type: text(),
+        },
+        Effect::LocationsConnected {
+            from: text(),
+            to: text(),
+            direction: None,
+        },
+        Effect::LocationUpdated {
+            location_name: text(),
+            changes: text(),
+        },
+        Effect::LightLevelChanged { light_level: None },
+        Effect::AbilityScoreModified {
+            ability: Ability::Strength,
+            modifier: 2,
+            source: text(),
+        },
+        Effect::SpellSlotRestored {
+            level: 1,
+            new_remaining: 2,
+        },
+        Effect::SpellPrepared { spell_name: text() },
+        Effect::SpellUnprepared { spell_name: text() },
+        Effect::SpellCopied {
+            spell_name: text(),
+            free: false,
+        },
+        Effect::SpellLearned {
+            spell_name: text(),
+            replaced: None,
+        },
+        Effect::ReputationChanged {
+            faction: text(),
+            amount: 5,
+            new_reputation: 15,
+            reason: text(),
+        },
+        Effect::StateAsserted {
+            entity_name: text(),
+            state_type: StateType::Disposition,
+            old_value: None,
+            new_value: text(),
+            reason: text(),
+            target_entity: None,
+        },
+        Effect::KnowledgeShared {
+            knowing_entity: text(),
+            content: text(),
+            source: text(),
+            verification: text(),
+            context: None,
+        },
+        Effect::EventScheduled {
+            description: text(),
+            trigger_description: text(),
+            location: None,
+            visibility: text(),
+        },
+        Effect::EventCancelled {
+            description: text(),
+            reason: text(),
+        },
+        Effect::EventTriggered {
+            description: text(),
+            location: None,
+        },
+    ]
+}
+
+/// Examples of the variants with optional fields or empty lists, nested ones
+/// included, with all of them filled in so the schema can describe what they
+/// hold.
+fn filled_examples() -> Vec<Effect> {
+    let id = CharacterId::new();
+    let text = || "text".to_string();
+    let mut roll = DiceRoller::new(0)
+        .roll("4d6kh3")
+        .expect("valid dice notation");
+    roll.expression.components[0].keep_lowest = Some(1);
+    roll.component_results[0].rerolled.push((0, 1));
+
+    vec![
+        Effect::DiceRolled {
+            roll,
+            purpose: text(),
+        },
+        Effect::ConditionApplied {
+            target_id: id,
+            condition: Condition::Prone,
+            source: text(),
+            duration_rounds: Some(1),
+            timing: DurationTiming::default(),
+        },
+        Effect::CombatEnded {
+            summary: Some(CombatSummary {
+                rounds: 2,
+                damage_dealt: 18,
+                damage_taken: 5,
+                defeated: vec![text()],
+            }),
+        },
+        Effect::ItemAdded {
+            item_name: text(),
+            quantity: 1,
+            new_total: 1,
+            item: Some(Item {
+                name: text(),
+                quantity: 1,
+                weight: 0.5,
+                value_gp: 0.5,
+                description: Some(text()),
+                item_type: ItemType::Adventuring,
+                magical: false,
+            }),
+        },
+        Effect::QuestCreated {
+            name: text(),
+            description: text(),
+            giver: Some(text()),
+            objectives: vec![(text(), false)],
+            rewards: vec![text()],
+        },
+        Effect::QuestCompleted {
+            quest_name: text(),
+            completion_note: Some(text()),
+        },
+        Effect::QuestUpdated {
+            quest_name: text(),
+            new_description: Some(text()),
+            add_rewards: vec![text()],
+        },
+        Effect::NpcCreated {
+            name: text(),
+            location: Some(text()),
+        },
+        Effect::NpcMoved {
+            npc_name: text(),
+            from_location: Some(text()),
+            to_location: text(),
+        },
+        Effect::LocationsConnected {
+            from: text(),
+            to: text(),
+            direction: Some(text()),
+        },
+        Effect::LightLevelChanged {
+            light_level: Some(LightLevel::Dim),
+        },
+        Effect::SpellLearned {
+            spell_name: text(),
+            replaced: Some(text()),
+        },
+        Effect::StateAsserted {
+            entity_name: text(),
+            state_type: StateType::Disposition,
+            old_value: Some(text()),
+            new_value: text(),
+            reason: text(),
+            target_entity: Some(text()),
+        },
+        Effect::KnowledgeShared {
+            knowing_entity: text(),
+            content: text(),
+            source: text(),
+            verification: text(),
+            context: Some(text()),
+        },
+        Effect::EventScheduled {
+            description: text(),
+            trigger_description: text(),
+            location: Some(text()),
+            visibility: text(),
+        },
+        Effect::EventTriggered {
+            description: text(),
+            location: Some(text()),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/schema/effect-log.schema.json");
+
+    /// Lists every variant. The match in `has_example` fails to compile when
+    /// a variant is added, as a reminder to list it here and give it an
+    /// example in `examples()`.
+    macro_rules! effect_variants {
+        ($($variant:ident),* $(,)?) => {
+            const VARIANT_COUNT: usize = [$(stringify!($variant)),*].len();
+
+            fn has_example(effect: &Effect) -> bool {
+                match effect {
+                    $(Effect::$variant { .. } => true,)*
+                }
+            }
+        };
+    }
+
+    effect_variants![
+        DiceRolled,
+        SneakAttackUsed,
+        ActionUsed,
+        AttackMade,
+        ExtraActionGained,
+        HpChanged,
+        ConditionApplied,
+        AcBonusApplied,
+        AcBonusEnded,
+        SpeedBonusApplied,
+        SpeedBonusEnded,
+        ConditionRemoved,
+        CombatStarted,
+        CombatEnded,
+        TurnAdvanced,
+        InitiativeRolled,
+        CombatantAdded,
+        CombatantHpChanged,
+        TimeAdvanced,
+        ExperienceGained,
+        LevelUp,
+        AsiAvailable,
+        AbilityScoreImproved,
+        FeatChosen,
+        FightingStyleChosen,
+        FeatureUsed,
+        FeatureRecharged,
+        SpellSlotUsed,
+        PactSlotUsed,
+        RestCompleted,
+        CheckSucceeded,
+        CheckFailed,
+        HelpGiven,
+        HelpUsed,
+        GroupCheckResolved,
+        AttackHit,
+        AttackMissed,
+        FactRemembered,
+        ItemAdded,
+        AmmunitionSpent,
+        ItemRemoved,
+        ItemEquipped,
+        ItemUnequipped,
+        ItemUsed,
+        GoldChanged,
+        SilverChanged,
+        AcChanged,
+        DeathSaveFailure,
+        DeathSavesReset,
+        DeathSavesSet,
+        CharacterDied,
+        DeathSaveSuccess,
+        Stabilized,
+        ConcentrationBroken,
+        ConcentrationStarted,
+        ConcentrationMaintained,
+        LocationChanged,
+        ConsequenceRegistered,
+        ConsequenceTriggered,
+        ClassResourceUsed,
+        RageStarted,
+        RageEnded,
+        WildShapeStarted,
+        WildShapeHpChanged,
+        WildShapeEnded,
+        QuestCreated,
+        QuestObjectiveAdded,
+        QuestObjectiveCompleted,
+        QuestCompleted,
+        QuestFailed,
+        QuestUpdated,
+        NpcCreated,
+        NpcUpdated,
+        NpcMoved,
+        NpcRemoved,
+        LocationCreated,
+        LocationsConnected,
+        LocationUpdated,
+        LightLevelChanged,
+        AbilityScoreModified,
+        SpellSlotRestored,
+        SpellPrepared,
+        SpellUnprepared,
+        SpellCopied,
+        SpellLearned,
+        ReputationChanged,
+        StateAsserted,
+        KnowledgeShared,
+        EventScheduled,
+        EventCancelled,
+        EventTriggered,
+    ];
+
+    #[test]
+    fn test_every_effect_round_trips() {
+        let examples = examples();
+        let mut tags = Vec::new();
+        for effect in &examples {
+            assert!(has_example(effect));
+            let json = serde_json::to_value(effect).unwrap();
+            let restored: Effect = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+            tags.push(json["type"].as_str().unwrap().to_string());
+        }
+        tags.sort();
+        tags.dedup();
+        assert_eq!(tags.len(), examples.len(), "one example per variant");
+        assert_eq!(examples.len(), VARIANT_COUNT, "an example of every variant");
+
+        let log =
+            EffectLog::new(vec![Resolution::new("The goblin falls.")
+                .with_effect(Effect::TimeAdvanced { minutes: 1 })]);
+        let json = serde_json::to_value(&log).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "schema_version": EFFECT_SCHEMA_VERSION,
+                "resolutions": [{
+                    "effects": [{"type": "time_advanced", "minutes": 1}],
+                    "narrative": "The goblin falls."
+                }]
+            })
+        );
+        let restored: EffectLog = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.resolutions[0].effects.len(), 1);
+    }
+
+    #[test]
+    fn test_every_field_has_a_shape() {
+        fn check(schema: &Value, path: &str) {
+            match schema {
+                Value::Object(map) => {
+                    if let Some(Value::Object(properties)) = map.get("properties") {
+                        for (name, property) in properties {
+                            assert!(
+                                property != &json!({}) && property != &json!({"type": "null"}),
+                                "{path}.{name} has no shape; fill it in filled_examples()"
+                            );
+                        }
+                    }
+                    if map.get("type") == Some(&json!("array")) {
+                        assert!(
+                            map.contains_key("items") || map.contains_key("prefixItems"),
+                            "{path} has no item shape"
+                        );
+                    }
+                    for (key, value) in map {
+                        check(value, &format!("{path}.{key}"));
+                    }
+                }
+                Value::Array(values) => values.iter().for_each(|value| check(value, path)),
+                _ => {}
+            }
+        }
+
+        check(&schema(), "");
+    }
+
+    #[test]
+    fn test_committed_schema_is_current() {
+        let generated = serde_json::to_string_pretty(&schema()).unwrap() + "\n";
+        if std::env::var_os("UPDATE_SCHEMA").is_some() {
+            std::fs::write(SCHEMA_PATH, &generated).unwrap();
+        }
+        let committed = std::fs::read_to_string(SCHEMA_PATH).unwrap_or_default();
+        assert!(
+            committed == generated,
+            "The effect schema changed. Bump EFFECT_SCHEMA_VERSION if readers \
+             could break, then rerun with UPDATE_SCHEMA=1 to update {SCHEMA_PATH}"
+        );
+    }
+}
@@ -4,7 +4,7 @@
 mod tests {
     use crate::dice::Advantage;
     use crate::rules::types::{CombatantInit, DamageType, Effect, Intent, RestType};
-    use crate::rules::{apply_effect, apply_effects, RulesEngine};
+    use crate::rules::{apply_effect, apply_effects, Resolution, RulesEngine};
     use crate::world::{create_sample_fighter, Ability, Condition, GameWorld, Skill};
 
     #[test]
@@ -29,6 +29,165 @@ mod tests {
             .any(|e| matches!(e, Effect::DiceRolled { .. })));
     }
 
+    #[test]
+    fn test_skill_check_exposes_outcome() {
+        let world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let engine = RulesEngine::new();
+
+        let resolution = engine.resolve(
+            &world,
+            Intent::SkillCheck {
+                character_id: world.player_character.id,
+                skill: Skill::Athletics,
+                dc: 15,
+                advantage: Advantage::Normal,
+                description: "Climbing a cliff".to_string(),
+            },
+        );
+
+        let outcome = resolution.check_outcome().unwrap();
+        let roll = resolution
+            .effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::DiceRolled { roll, .. } => Some(roll.total),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(outcome.check_type, "Athletics");
+        assert_eq!(outcome.dc, 15);
+        assert_eq!(outcome.roll, roll);
+        assert_eq!(outcome.success, roll >= 15);
+
+        let narration_only = Resolution::new("Nothing to check.");
+        assert_eq!(narration_only.check_outcome(), None);
+    }
+
+    #[test]
+    fn test_every_d20_path_honors_disadvantage() {
+        use crate::dice::{DieType, RollResult};
+
+        let character = create_sample_fighter("Roland");
+        let id = character.id;
+        let world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new();
+
+        let first_d20 = |resolution: crate::rules::types::Resolution| -> RollResult {
+            resolution
+                .effects
+                .into_iter()
+                .find_map(|e| match e {
+                    Effect::DiceRolled { roll, .. } => Some(roll),
+                    _ => None,
+                })
+                .expect("a d20 should have been rolled")
+        };
+
+        let intents = [
+            Intent::SkillCheck {
+                character_id: id,
+                skill: Skill::Athletics,
+                dc: 15,
+                advantage: Advantage::Disadvantage,
+                description: "Climbing".to_string(),
+            },
+            Intent::AbilityCheck {
+                character_id: id,
+                ability: Ability::Strength,
+                dc: 15,
+                advantage: Advantage::Disadvantage,
+                description: "Shoving a door".to_string(),
+            },
+            Intent::SavingThrow {
+                character_id: id,
+                ability: Ability::Constitution,
+                dc: 15,
+                advantage: Advantage::Disadvantage,
+                source: "Poison".to_string(),
+            },
+            Intent::Attack {
+                attacker_id: id,
+                target_id: crate::world::CharacterId::new(),
+                weapon_name: "Longsword".to_string(),
+                advantage: Advantage::Disadvantage,
+            },
+        ];
+
+        let mut rolls: Vec<RollResult> = intents
+            .into_iter()
+            .map(|intent| first_d20(engine.resolve(&world, intent)))
+            .collect();
+        rolls.push(engine.roll_d20_check(2, Advantage::Disadvantage));
+
+        for roll in rolls {
+            assert_eq!(roll.advantage, Advantage::Disadvantage);
+            let d20 = &roll.component_results[0];
+            assert_eq!(d20.die_type, DieType::D20);
+            assert_eq!(d20.rolls.len(), 2);
+            assert_eq!(d20.kept, vec![*d20.rolls.iter().min().unwrap()]);
+            assert_eq!(roll.total, d20.kept[0] as i32 + roll.modifier);
+        }
+    }
+
+    #[test]
+    fn test_monster_rolls_come_from_dm_roller() {
+        use crate::dice::DiceRoller;
+        use crate::world::CharacterId;
+
+        let world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        let engine = RulesEngine::new()
+            .with_player_roller(DiceRoller::new(1))
+            .with_dm_roller(DiceRoller::new(99));
+
+        let goblin_initiative = |engine: &RulesEngine| {
+            let resolution = engine.resolve(
+                &world,
+                Intent::RollInitiative {
+                    character_id: CharacterId::new(),
+                    name: "Goblin".to_string(),
+                    modifier: 2,
+                    is_player: false,
+                },
+            );
+            resolution
+                .effects
+                .iter()
+                .find_map(|e| match e {
+                    Effect::InitiativeRolled { total, .. } => Some(*total),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        let expected = DiceRoller::new(99)
+            .roll_with_advantage("1d20+2", Advantage::Normal)
+            .unwrap()
+            .total;
+        assert_eq!(goblin_initiative(&engine), expected);
+        assert_eq!(engine.player_roller().draws(), 0);
+        assert!(engine.dm_roller().draws() > 0);
+
+        // The player's own rolls leave the DM's sequence alone.
+        let dm_draws = engine.dm_roller().draws();
+        engine.roll_d20_check(0, Advantage::Normal);
+        assert_eq!(engine.dm_roller().draws(), dm_draws);
+        assert!(engine.player_roller().draws() > 0);
+    }
+
+    #[test]
+    fn test_roll_d20_check_keeps_highest_with_advantage() {
+        let engine = RulesEngine::new();
+
+        for _ in 0..20 {
+            let roll = engine.roll_d20_check(-1, Advantage::Advantage);
+            let d20 = &roll.component_results[0];
+            assert_eq!(d20.rolls.len(), 2);
+            assert_eq!(d20.kept, vec![*d20.rolls.iter().max().unwrap()]);
+            assert_eq!(roll.total, d20.kept[0] as i32 - 1);
+            assert_eq!(roll.natural_20, d20.kept[0] == 20);
+        }
+    }
+
     #[test]
     fn test_damage() {
         let character = create_sample_fighter("Roland");
@@ -49,6 +208,41 @@ mod tests {
             .any(|e| matches!(e, Effect::HpChanged { amount, .. } if *amount == -10)));
     }
 
+    #[test]
+    fn test_damage_narration_uses_the_locale() {
+        use crate::rules::{Locale, Message};
+
+        let character = create_sample_fighter("Roland");
+        let world = GameWorld::new("Test", character);
+        let engine = RulesEngine::new().with_locale(
+            Locale::english()
+                .with_message(
+                    Message::TakesDamage,
+                    "{target} recibe {amount} de daño {damage_type} de {source}",
+                )
+                .with_message(Message::HpStatus, " (PG: {current}/{max})")
+                .with_damage_type(DamageType::Slashing, "cortante"),
+        );
+
+        let intent = Intent::Damage {
+            target_id: world.player_character.id,
+            amount: 1,
+            damage_type: DamageType::Slashing,
+            source: "Goblin".to_string(),
+        };
+
+        let resolution = engine.resolve(&world, intent);
+        let hp = &world.player_character.hit_points;
+        assert_eq!(
+            resolution.narrative,
+            format!(
+                "Roland recibe 1 de daño cortante de Goblin (PG: {}/{})",
+                hp.current - 1,
+                hp.maximum
+            )
+        );
+    }
+
     #[test]
     fn test_heal() {
         let mut character = create_sample_fighter("Roland");
@@ -162,6 +356,7 @@ mod tests {
                 max_hp: character.hit_points.maximum,
                 armor_class: character.current_ac(),
                 initiative_modifier: character.initiative_modifier(),
+                resistances: Vec::new(),
             }],
         };
 
@@ -537,6 +732,64 @@ mod tests {
             .any(|c| c.condition == Condition::Unconscious));
     }
 
+    #[test]
+    fn test_healing_a_downed_character_wakes_them_in_combat() {
+        let mut character = create_sample_fighter("Roland");
+        character.hit_points.current = 5;
+        let mut world = GameWorld::new("Test", character);
+        let player_id = world.player_character.id;
+        let mut combat = crate::world::CombatState::new();
+        combat.add_combatant(crate::world::Combatant {
+            id: player_id,
+            name: "Roland".to_string(),
+            initiative: 12,
+            is_player: true,
+            is_ally: true,
+            current_hp: 5,
+            max_hp: world.player_character.hit_points.maximum,
+            armor_class: 16,
+            resistances: Vec::new(),
+        });
+        world.combat = Some(combat);
+        let engine = RulesEngine::new();
+        let combat_hp = |world: &GameWorld| world.combat.as_ref().unwrap().combatants[0].current_hp;
+
+        // Knocked below 0, then a failed death save
+        let down = engine.resolve(
+            &world,
+            Intent::Damage {
+                target_id: player_id,
+                amount: 12,
+                damage_type: DamageType::Slashing,
+                source: "Orc".to_string(),
+            },
+        );
+        apply_effects(&mut world, &down.effects);
+        world.player_character.death_saves.add_failure();
+        world.player_character.hit_points.temporary = 3;
+        assert!(world.player_character.has_condition(Condition::Unconscious));
+        assert_eq!(combat_hp(&world), world.player_character.hit_points.current);
+
+        // A single hit point of healing counts up from 0
+        let healed = engine.resolve(
+            &world,
+            Intent::Heal {
+                target_id: player_id,
+                amount: 1,
+                source: "Healing Word".to_string(),
+            },
+        );
+        assert!(healed.narrative.contains("regains consciousness"));
+        apply_effects(&mut world, &healed.effects);
+
+        let pc = &world.player_character;
+        assert_eq!(pc.hit_points.current, 1);
+        assert_eq!(pc.hit_points.temporary, 3);
+        assert!(!pc.has_condition(Condition::Unconscious));
+        assert_eq!(pc.death_saves.failures, 0);
+        assert_eq!(combat_hp(&world), 1);
+    }
+
     #[test]
     fn test_three_death_save_failures_causes_death() {
         let mut character = create_sample_fighter("Roland");
@@ -1108,6 +1361,47 @@ mod tests {
         assert_eq!(world.player_character.ability_scores.intelligence, 1);
     }
 
+    #[test]
+    fn test_ability_changes_flow_into_derived_stats() {
+        let mut character = crate::world::create_sample_sorcerer("Zara");
+        character.ability_scores.dexterity = 14;
+        character.ability_scores.charisma = 16;
+        let mut world = GameWorld::new("Test Campaign", character);
+        let engine = RulesEngine::new();
+
+        let ac = world.player_character.current_ac();
+        let dc = world.player_character.spell_save_dc().unwrap();
+        let attack = world.player_character.spell_attack_bonus().unwrap();
+        let stealth = world.player_character.skill_modifier(Skill::Stealth);
+
+        for ability in [Ability::Dexterity, Ability::Charisma] {
+            let resolution = engine.resolve(
+                &world,
+                Intent::ModifyAbilityScore {
+                    ability,
+                    modifier: 2,
+                    source: "Tome".to_string(),
+                    duration: None,
+                },
+            );
+            apply_effects(&mut world, &resolution.effects);
+        }
+
+        // Nothing is recomputed by hand; each getter reads the new scores
+        let pc = &world.player_character;
+        assert_eq!(pc.current_ac(), ac + 1);
+        assert_eq!(pc.skill_modifier(Skill::Stealth), stealth + 1);
+        assert_eq!(pc.spell_save_dc(), Some(dc + 1));
+        assert_eq!(pc.spell_attack_bonus(), Some(attack + 1));
+
+        let cast = engine.resolve_cast_spell(&world, pc.id, "Thunderwave", 1, &[]);
+        assert!(
+            cast.narrative.contains(&format!("DC {}", dc + 1)),
+            "{}",
+            cast.narrative
+        );
+    }
+
     #[test]
     fn test_restore_spell_slot() {
         use crate::world::{SlotInfo, SpellSlots, SpellcastingData};
@@ -1117,6 +1411,7 @@ mod tests {
         character.spellcasting = Some(SpellcastingData {
             ability: Ability::Intelligence,
             spells_known: vec!["Shield".to_string()],
+            spellbook: Vec::new(),
             spells_prepared: vec![],
             cantrips_known: vec!["Fire Bolt".to_string()],
             spell_slots: SpellSlots {
@@ -1132,6 +1427,7 @@ mod tests {
                     SlotInfo { total: 0, used: 0 }, // Level 9
                 ],
             },
+            pact_slots: None,
         });
 
         let mut world = GameWorld::new("Test Campaign", character);
@@ -1225,4 +1521,68 @@ mod tests {
         let npc = world.npcs.values().find(|n| n.name == "Durnan").unwrap();
         assert_eq!(npc.location_id, Some(tavern_id));
     }
+
+    #[test]
+    fn test_advancing_to_night_moves_scheduled_npc() {
+        use crate::world::{Location, LocationType, ScheduleEntry, TimeRange, NPC};
+
+        let character = create_sample_fighter("Roland");
+        let mut world = GameWorld::new("Test Campaign", character);
+        let engine = RulesEngine::new();
+
+        let forge = Location::new("The Forge", LocationType::Building);
+        let tavern = Location::new("The Rusty Tankard", LocationType::Building);
+        let (forge_id, tavern_id) = (forge.id, tavern.id);
+        world.known_locations.insert(forge_id, forge);
+        world.known_locations.insert(tavern_id, tavern);
+
+        let mut smith = NPC::new("Bram");
+        smith.location_id = Some(forge_id);
+        smith.schedule = vec![
+            ScheduleEntry {
+                time_range: TimeRange::new(6, 18),
+                location: forge_id,
+            },
+            ScheduleEntry {
+                time_range: TimeRange::new(18, 6),
+                location: tavern_id,
+            },
+        ];
+        let smith_id = smith.id;
+        world.npcs.insert(smith_id, smith);
+
+        // 10:00 -> 20:00
+        let resolution = engine.resolve(&world, Intent::AdvanceTime { minutes: 600 });
+        apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.game_time.hour, 20);
+        assert_eq!(world.npcs[&smith_id].location_id, Some(tavern_id));
+
+        // Past midnight and into the next morning, back at the forge
+        let resolution = engine.resolve(&world, Intent::AdvanceTime { minutes: 720 });
+        apply_effects(&mut world, &resolution.effects);
+        assert_eq!(world.game_time.hour, 8);
+        assert_eq!(world.npcs[&smith_id].location_id, Some(forge_id));
+    }
+
+    #[test]
+    fn test_resolution_helpers_match_manual_effects() {
+        let helpers = Resolution::new("Rage!")
+            .spend_resource("Grog", "Rage", "Entered rage")
+            .use_feature("Rage", 2);
+        let manual = Resolution::new("Rage!")
+            .with_effect(Effect::ClassResourceUsed {
+                character_name: "Grog".to_string(),
+                resource_name: "Rage".to_string(),
+                description: "Entered rage".to_string(),
+            })
+            .with_effect(Effect::FeatureUsed {
+                feature_name: "Rage".to_string(),
+                uses_remaining: 2,
+            });
+
+        assert_eq!(
+            format!("{:?}", helpers.effects),
+            format!("{:?}", manual.effects)
+        );
+    }
 }
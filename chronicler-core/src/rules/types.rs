@@ -1,9 +1,14 @@
 //! Core types for the Intent/Effect rules system.
 
 use crate::dice::RollResult;
-use crate::world::{Ability, CharacterId, Condition, Skill};
+use crate::world::{
+    Ability, ActionType, CharacterId, CombatSummary, Condition, DurationTiming, FightingStyle,
+    Item, LightLevel, Skill, StatBlock,
+};
 use serde::{Deserialize, Serialize};
 
+pub use crate::world::{DamageType, Resistance};
+
 /// An intent represents what a character wants to do.
 /// The AI generates intents, the RulesEngine resolves them.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +21,13 @@ pub enum Intent {
         advantage: crate::dice::Advantage,
     },
 
+    /// Bonus-action attack with the light weapon held in the off hand
+    OffhandAttack {
+        attacker_id: CharacterId,
+        target_id: CharacterId,
+        advantage: crate::dice::Advantage,
+    },
+
     /// Cast a spell
     CastSpell {
         caster_id: CharacterId,
@@ -44,6 +56,28 @@ pub enum Intent {
         description: String,
     },
 
+    /// Help a character with their next ability check (the Help action)
+    Help {
+        helper: String,
+        target_id: CharacterId,
+        task: String,
+    },
+
+    /// The party attempts a check together; it succeeds if at least half do
+    GroupCheck {
+        skill: Skill,
+        dc: i32,
+        companions: Vec<GroupCheckMember>,
+        description: String,
+    },
+
+    /// Reroll the player's most recent d20 with a luck feature. With no
+    /// feature given, whichever one applies is used.
+    RerollD20 {
+        character_id: CharacterId,
+        feature: Option<RerollFeature>,
+    },
+
     /// Make a saving throw
     SavingThrow {
         character_id: CharacterId,
@@ -61,6 +95,17 @@ pub enum Intent {
         source: String,
     },
 
+    /// Damage several combatants at once, each saving for half (e.g. Fireball)
+    AreaDamage {
+        targets: Vec<AreaTarget>,
+        /// Damage dice rolled once for every target (e.g. "8d6")
+        damage_dice: String,
+        damage_type: DamageType,
+        save_ability: Ability,
+        dc: i32,
+        source: String,
+    },
+
     /// Heal a target
     Heal {
         target_id: CharacterId,
@@ -74,6 +119,7 @@ pub enum Intent {
         condition: Condition,
         source: String,
         duration_rounds: Option<u32>,
+        timing: DurationTiming,
     },
 
     /// Remove a condition from a target
@@ -121,6 +167,18 @@ pub enum Intent {
     /// Add experience points
     GainExperience { amount: u32 },
 
+    /// Spend Ability Score Improvement points to raise an ability score
+    ApplyAsi { ability: Ability, amount: u8 },
+
+    /// Spend a full Ability Score Improvement on a feat instead
+    ChooseFeat {
+        feat_name: String,
+        description: String,
+    },
+
+    /// Pick a Fighting Style granted by a class feature
+    ChooseFightingStyle { style: FightingStyle },
+
     /// Use a class feature
     UseFeature {
         character_id: CharacterId,
@@ -397,6 +455,9 @@ pub enum Intent {
         remove_npcs: Vec<String>,
     },
 
+    /// Set the light level of the current area (None restores the default)
+    SetLightLevel { light_level: Option<LightLevel> },
+
     /// Modify an ability score temporarily or permanently
     ModifyAbilityScore {
         ability: Ability,
@@ -408,6 +469,33 @@ pub enum Intent {
     /// Restore a spell slot
     RestoreSpellSlot { slot_level: u8, source: String },
 
+    /// Add a spell to the caster's prepared list
+    PrepareSpell { spell_name: String },
+
+    /// Remove a spell from the caster's prepared list
+    UnprepareSpell { spell_name: String },
+
+    /// Copy a spell into a Wizard's spellbook, as one of the spells gained
+    /// at level-up when `free` is set
+    CopySpellToBook {
+        spell_name: String,
+        #[serde(default)]
+        free: bool,
+    },
+
+    /// Learn a spell, optionally in place of a known one
+    LearnSpell {
+        spell_name: String,
+        replace: Option<String>,
+    },
+
+    /// Change the party's reputation with a faction
+    AdjustReputation {
+        faction: String,
+        amount: i32,
+        reason: String,
+    },
+
     // ========================================================================
     // State Assertion Intents (declarative state changes)
     // ========================================================================
@@ -511,6 +599,43 @@ impl StateType {
     }
 }
 
+/// A companion taking part in a group check alongside the player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupCheckMember {
+    pub name: String,
+    /// The companion's bonus for the skill being checked.
+    pub modifier: i32,
+}
+
+/// A feature that lets a character reroll a d20.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RerollFeature {
+    /// The Lucky feat: spend a luck point, roll again, and use either die.
+    Lucky,
+    /// The halfling trait: reroll a natural 1 and use the new roll.
+    HalflingLuck,
+}
+
+impl RerollFeature {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RerollFeature::Lucky => "Lucky",
+            RerollFeature::HalflingLuck => "Halfling Luck",
+        }
+    }
+}
+
+/// One creature caught in an area effect.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AreaTarget {
+    pub id: CharacterId,
+    /// Saving throw modifier for non-player targets (the player's own
+    /// modifier is always used for the player)
+    #[serde(default)]
+    pub save_modifier: i8,
+}
+
 /// Initial combatant data for starting combat.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CombatantInit {
@@ -523,48 +648,24 @@ pub struct CombatantInit {
     pub armor_class: u8,
     /// Initiative modifier (DEX mod for most creatures)
     pub initiative_modifier: i8,
+    /// Damage resistances (e.g. a werewolf's nonmagical, unsilvered weapons)
+    #[serde(default)]
+    pub resistances: Vec<Resistance>,
 }
 
-/// Common D&D damage types.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum DamageType {
-    Slashing,
-    Piercing,
-    Bludgeoning,
-    Fire,
-    Cold,
-    Lightning,
-    Thunder,
-    Acid,
-    Poison,
-    Necrotic,
-    Radiant,
-    Force,
-    Psychic,
-}
-
-impl DamageType {
-    pub fn name(&self) -> &'static str {
-        match self {
-            DamageType::Slashing => "slashing",
-            DamageType::Piercing => "piercing",
-            DamageType::Bludgeoning => "bludgeoning",
-            DamageType::Fire => "fire",
-            DamageType::Cold => "cold",
-            DamageType::Lightning => "lightning",
-            DamageType::Thunder => "thunder",
-            DamageType::Acid => "acid",
-            DamageType::Poison => "poison",
-            DamageType::Necrotic => "necrotic",
-            DamageType::Radiant => "radiant",
-            DamageType::Force => "force",
-            DamageType::Psychic => "psychic",
-        }
-    }
+/// How a check or saving throw came out, read from a [`Resolution`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckOutcome {
+    /// What was checked, e.g. "Athletics" or "DEX save".
+    pub check_type: String,
+    pub success: bool,
+    /// The roll's total, modifiers included.
+    pub roll: i32,
+    pub dc: i32,
 }
 
 /// The result of resolving an intent.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Resolution {
     pub effects: Vec<Effect>,
     pub narrative: String,
@@ -587,11 +688,64 @@ impl Resolution {
         self.effects.extend(effects);
         self
     }
+
+    /// Record `character_name` spending a class resource such as Ki Points.
+    pub fn spend_resource(
+        self,
+        character_name: impl Into<String>,
+        resource_name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.with_effect(Effect::ClassResourceUsed {
+            character_name: character_name.into(),
+            resource_name: resource_name.into(),
+            description: description.into(),
+        })
+    }
+
+    /// The outcome of the first check or save this resolution made, if any.
+    pub fn check_outcome(&self) -> Option<CheckOutcome> {
+        self.effects.iter().find_map(|effect| {
+            let (success, check_type, roll, dc) = match effect {
+                Effect::CheckSucceeded {
+                    check_type,
+                    roll,
+                    dc,
+                } => (true, check_type, roll, dc),
+                Effect::CheckFailed {
+                    check_type,
+                    roll,
+                    dc,
+                } => (false, check_type, roll, dc),
+                _ => return None,
+            };
+            Some(CheckOutcome {
+                check_type: check_type.clone(),
+                success,
+                roll: *roll,
+                dc: *dc,
+            })
+        })
+    }
+
+    /// Record a use of a limited-use feature, leaving `uses_remaining`.
+    pub fn use_feature(self, feature_name: impl Into<String>, uses_remaining: u8) -> Self {
+        self.with_effect(Effect::FeatureUsed {
+            feature_name: feature_name.into(),
+            uses_remaining,
+        })
+    }
 }
 
 /// Effects are the result of resolving an intent.
 /// They describe concrete state changes to apply to the GameWorld.
+///
+/// Effects serialize as JSON objects tagged with a snake_case `"type"`, e.g.
+/// `{"type": "time_advanced", "minutes": 10}`. This representation is
+/// stable; see [`schema`](super::schema()) and
+/// [`EFFECT_SCHEMA_VERSION`](super::EFFECT_SCHEMA_VERSION).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum Effect {
     /// A dice roll occurred
     DiceRolled { roll: RollResult, purpose: String },
@@ -602,12 +756,30 @@ pub enum Effect {
         damage_dice: u8,
     },
 
+    /// Part of a character's action economy was spent this turn
+    ActionUsed {
+        character_id: CharacterId,
+        action: ActionType,
+        /// Level of the spell cast with it, if any (0 for a cantrip)
+        #[serde(default)]
+        spell_level: Option<u8>,
+    },
+
+    /// A weapon attack was made, counted toward the attacks one Attack
+    /// action allows
+    AttackMade { character_id: CharacterId },
+
+    /// A character gained an extra action this turn (Action Surge)
+    ExtraActionGained { character_id: CharacterId },
+
     /// HP changed (damage or healing)
     HpChanged {
         target_id: CharacterId,
         amount: i32,
         new_current: i32,
         new_max: i32,
+        /// Fell unconscious at 0 HP. False when massive damage killed
+        /// outright (see [`Effect::CharacterDied`]).
         dropped_to_zero: bool,
     },
 
@@ -617,6 +789,37 @@ pub enum Effect {
         condition: Condition,
         source: String,
         duration_rounds: Option<u32>,
+        #[serde(default)]
+        timing: DurationTiming,
+    },
+
+    /// A temporary AC bonus took effect
+    AcBonusApplied {
+        target_id: CharacterId,
+        source: String,
+        bonus: i8,
+        duration_rounds: Option<u32>,
+        #[serde(default)]
+        timing: DurationTiming,
+    },
+
+    /// A temporary AC bonus ended
+    AcBonusEnded {
+        target_id: CharacterId,
+        source: String,
+    },
+
+    /// A temporary speed bonus took effect
+    SpeedBonusApplied {
+        target_id: CharacterId,
+        source: String,
+        feet: u32,
+    },
+
+    /// A temporary speed bonus ended
+    SpeedBonusEnded {
+        target_id: CharacterId,
+        source: String,
     },
 
     /// A condition was removed
@@ -628,8 +831,8 @@ pub enum Effect {
     /// Combat started
     CombatStarted,
 
-    /// Combat ended
-    CombatEnded,
+    /// Combat ended, with a recap of the encounter if one was in progress
+    CombatEnded { summary: Option<CombatSummary> },
 
     /// Turn advanced in combat
     TurnAdvanced {
@@ -654,6 +857,15 @@ pub enum Effect {
         current_hp: i32,
         max_hp: i32,
         armor_class: u8,
+        resistances: Vec<Resistance>,
+    },
+
+    /// A non-player combatant's hit points changed
+    CombatantHpChanged {
+        id: CharacterId,
+        name: String,
+        amount: i32,
+        new_current: i32,
     },
 
     /// Time advanced
@@ -665,15 +877,43 @@ pub enum Effect {
     /// Level up occurred
     LevelUp { new_level: u8 },
 
+    /// An Ability Score Improvement (or feat) became available
+    AsiAvailable { level: u8 },
+
+    /// Ability Score Improvement points were spent on an ability
+    AbilityScoreImproved {
+        ability: Ability,
+        amount: u8,
+        new_score: u8,
+    },
+
+    /// A feat was taken in place of an Ability Score Improvement
+    FeatChosen {
+        feat_name: String,
+        description: String,
+    },
+
+    /// A Fighting Style was chosen
+    FightingStyleChosen { style: FightingStyle },
+
     /// Feature use consumed
     FeatureUsed {
         feature_name: String,
         uses_remaining: u8,
     },
 
+    /// Feature use restored by a recharge roll
+    FeatureRecharged {
+        feature_name: String,
+        uses_remaining: u8,
+    },
+
     /// Spell slot consumed
     SpellSlotUsed { level: u8, remaining: u8 },
 
+    /// Warlock Pact Magic slot consumed
+    PactSlotUsed { level: u8, remaining: u8 },
+
     /// Rest completed
     RestCompleted { rest_type: RestType },
 
@@ -691,6 +931,21 @@ pub enum Effect {
         dc: i32,
     },
 
+    /// A character is helping with the next ability check
+    HelpGiven { helper: String, target_name: String },
+
+    /// A pending Help was spent on a check
+    HelpUsed { helper: String },
+
+    /// A group check was resolved
+    GroupCheckResolved {
+        check_type: String,
+        successes: usize,
+        participants: usize,
+        dc: i32,
+        success: bool,
+    },
+
     /// Attack hit
     AttackHit {
         attacker_name: String,
@@ -724,6 +979,18 @@ pub enum Effect {
         item_name: String,
         quantity: u32,
         new_total: u32,
+        /// Full stats of the item added; when absent they are looked up in
+        /// the standard item database by name
+        #[serde(default)]
+        item: Option<Item>,
+    },
+
+    /// A unit of ammunition was fired
+    AmmunitionSpent {
+        ammunition: String,
+        remaining: u32,
+        /// Whether the shot missed and can be recovered after combat
+        recoverable: bool,
     },
 
     /// An item was removed from inventory
@@ -770,6 +1037,13 @@ pub enum Effect {
     /// Death saves were reset (healed from 0 HP)
     DeathSavesReset { target_id: CharacterId },
 
+    /// Death save tallies set outright (replaying a rerolled death save)
+    DeathSavesSet {
+        target_id: CharacterId,
+        successes: u8,
+        failures: u8,
+    },
+
     /// Character died (3 death save failures or massive damage)
     CharacterDied {
         target_id: CharacterId,
@@ -795,6 +1069,12 @@ pub enum Effect {
         dc: i32,
     },
 
+    /// A character began concentrating on a spell
+    ConcentrationStarted {
+        character_id: CharacterId,
+        spell_name: String,
+    },
+
     /// Concentration was maintained
     ConcentrationMaintained {
         character_id: CharacterId,
@@ -844,6 +1124,25 @@ pub enum Effect {
         reason: String,
     },
 
+    /// Druid assumed a beast form via Wild Shape
+    WildShapeStarted {
+        character_id: CharacterId,
+        stats: StatBlock,
+    },
+
+    /// Damage or healing was applied to the Wild Shape beast form
+    WildShapeHpChanged {
+        character_id: CharacterId,
+        amount: i32,
+        new_current: i32,
+    },
+
+    /// Druid reverted from Wild Shape to their normal form
+    WildShapeEnded {
+        character_id: CharacterId,
+        reason: String,
+    },
+
     // ========================================================================
     // Quest Effects
     // ========================================================================
@@ -926,6 +1225,9 @@ pub enum Effect {
         changes: String,
     },
 
+    /// The light level of the current area (or battlefield) changed
+    LightLevelChanged { light_level: Option<LightLevel> },
+
     /// An ability score was modified
     AbilityScoreModified {
         ability: Ability,
@@ -936,6 +1238,34 @@ pub enum Effect {
     /// A spell slot was restored
     SpellSlotRestored { level: u8, new_remaining: u8 },
 
+    /// A spell was prepared
+    SpellPrepared { spell_name: String },
+
+    /// A spell is no longer prepared
+    SpellUnprepared { spell_name: String },
+
+    /// A spell was copied into a Wizard's spellbook, using up one of the
+    /// free level-up spells when `free` is set
+    SpellCopied {
+        spell_name: String,
+        #[serde(default)]
+        free: bool,
+    },
+
+    /// A spell was learned, replacing `replaced` if it is set
+    SpellLearned {
+        spell_name: String,
+        replaced: Option<String>,
+    },
+
+    /// The party's reputation with a faction changed
+    ReputationChanged {
+        faction: String,
+        amount: i32,
+        new_reputation: i32,
+        reason: String,
+    },
+
     // ========================================================================
     // State Assertion Effects
     // ========================================================================
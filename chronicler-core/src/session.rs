@@ -4,25 +4,56 @@
 //! game interactions. It wraps the DungeonMaster, GameWorld, and
 //! persistence logic into a single, easy-to-use API.
 
-use crate::dm::{DmConfig, DmError, DmResponse, DungeonMaster};
-use crate::rules::Effect;
-use crate::world::{create_sample_fighter, Character, GameWorld};
+use crate::dm::memory::DEFAULT_HISTORY_WINDOW;
+use crate::dm::{
+    DmBackend, DmConfig, DmError, DmResponse, DungeonMaster, ToolMetrics, TurnContext,
+    DEFAULT_MAX_CONTINUATIONS, DEFAULT_MAX_TOOL_ROUNDS,
+};
+use crate::narrative_log::NarrativeSpill;
+use crate::offline::OfflineTransport;
+use crate::persist::{
+    chrono_now, read_checked, write_checked, CampaignBundle, PersistError, SaveMetadata,
+};
+use crate::rules::{Effect, EffectLog, EffectOutcome, Locale, Resolution, WorldDiff};
+use crate::world::{create_sample_fighter, Character, GameWorld, HouseRules, NarrativeEntry};
 use claude::{Claude, Message, Request};
-use std::path::Path;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use tokio::fs;
 
 /// Errors from GameSession operations.
 #[derive(Debug, Error)]
 pub enum SessionError {
+    /// No Anthropic API key is available; front-ends should ask for one.
+    #[error(
+        "No Anthropic API key found. Set the ANTHROPIC_API_KEY environment variable \
+         (or add it to a .env file) and try again."
+    )]
+    MissingApiKey,
+
     #[error("DM error: {0}")]
-    Dm(#[from] DmError),
+    Dm(DmError),
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    #[error("Persistence error: {0}")]
+    Persist(#[from] PersistError),
+}
+
+impl From<DmError> for SessionError {
+    fn from(error: DmError) -> Self {
+        match error {
+            DmError::NoApiKey | DmError::ApiError(claude::Error::NoApiKey) => {
+                SessionError::MissingApiKey
+            }
+            other => SessionError::Dm(other),
+        }
+    }
 }
 
 /// Configuration for creating a new game session.
@@ -46,8 +77,44 @@ pub struct SessionConfig {
     /// Maximum tokens for DM responses.
     pub max_tokens: usize,
 
-    /// Temperature for DM generation.
-    pub temperature: Option<f32>,
+    /// Temperature for the DM's narration. Relevance checks and state
+    /// inference stay at 0.0 whatever this is set to.
+    pub dm_temperature: Option<f32>,
+
+    /// Number of recent conversation messages sent to the DM.
+    pub history_window: usize,
+
+    /// Whether the DM only receives the tools suited to the current game mode.
+    pub mode_aware_tools: bool,
+
+    /// How many times a DM reply cut off by `max_tokens` is continued.
+    pub max_continuations: usize,
+
+    /// How many rounds of tool calls the DM may make in one turn.
+    pub max_tool_rounds: usize,
+
+    /// Whether state inference runs at the start of the next turn instead
+    /// of delaying this one.
+    pub deferred_inference: bool,
+
+    /// Whether the DM's first request goes out before the relevance check
+    /// finishes, at the cost of a second DM call when the check triggers
+    /// something.
+    pub speculative_dm_request: bool,
+
+    /// Catalog for the rules engine's mechanical narration, in the language
+    /// the DM narrates in.
+    pub locale: Locale,
+
+    /// Table rules the new campaign is played with.
+    pub house_rules: HouseRules,
+
+    /// Scripted replies to play offline instead of calling the API.
+    pub offline: Option<OfflineTransport>,
+
+    /// Log that older narrative history is spilled to, keeping only the most
+    /// recent entries in memory and in saves.
+    pub narrative_log: Option<NarrativeSpill>,
 }
 
 impl SessionConfig {
@@ -61,7 +128,17 @@ impl SessionConfig {
             custom_dm_prompt: None,
             model: None,
             max_tokens: 4096,
-            temperature: Some(0.8),
+            dm_temperature: Some(0.8),
+            history_window: DEFAULT_HISTORY_WINDOW,
+            mode_aware_tools: true,
+            max_continuations: DEFAULT_MAX_CONTINUATIONS,
+            max_tool_rounds: DEFAULT_MAX_TOOL_ROUNDS,
+            deferred_inference: false,
+            speculative_dm_request: false,
+            locale: Locale::english(),
+            house_rules: HouseRules::default(),
+            offline: None,
+            narrative_log: None,
         }
     }
 
@@ -95,13 +172,148 @@ impl SessionConfig {
         self
     }
 
-    /// Set temperature for generation.
-    pub fn with_temperature(mut self, temp: f32) -> Self {
-        self.temperature = Some(temp);
+    /// Set how many times a reply cut off by the token limit is continued
+    /// (0 to leave truncated replies as they are).
+    pub fn with_max_continuations(mut self, continuations: usize) -> Self {
+        self.max_continuations = continuations;
+        self
+    }
+
+    /// Set how many rounds of tool calls the DM may make in one turn before
+    /// the turn ends with the narrative so far.
+    pub fn with_max_tool_rounds(mut self, rounds: usize) -> Self {
+        self.max_tool_rounds = rounds;
+        self
+    }
+
+    /// Set the temperature for the DM's narration.
+    pub fn with_dm_temperature(mut self, temp: f32) -> Self {
+        self.dm_temperature = Some(temp);
+        self
+    }
+
+    /// Set how many recent conversation messages are sent to the DM. At
+    /// least one is always sent, since a request can't be empty.
+    pub fn with_history_window(mut self, messages: usize) -> Self {
+        self.history_window = messages.max(1);
+        self
+    }
+
+    /// Set whether the DM only receives the tools suited to the current game mode.
+    pub fn with_mode_aware_tools(mut self, enabled: bool) -> Self {
+        self.mode_aware_tools = enabled;
+        self
+    }
+
+    /// Set whether state inference is deferred to the next turn; see
+    /// [`DmConfig::deferred_inference`].
+    pub fn with_deferred_inference(mut self, enabled: bool) -> Self {
+        self.deferred_inference = enabled;
+        self
+    }
+
+    /// Set whether the DM's first request is sent alongside the relevance
+    /// check; see [`DmConfig::speculative_dm_request`].
+    pub fn with_speculative_dm_request(mut self, enabled: bool) -> Self {
+        self.speculative_dm_request = enabled;
         self
     }
+
+    /// Write mechanical narration with the templates in `locale`.
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Play the new campaign with `house_rules`.
+    pub fn with_house_rules(mut self, house_rules: HouseRules) -> Self {
+        self.house_rules = house_rules;
+        self
+    }
+
+    /// Keep only the last `keep_in_memory` narrative entries in memory,
+    /// appending older ones to the log at `path`.
+    ///
+    /// A loaded save that already spills keeps its own log.
+    pub fn with_narrative_log(mut self, path: impl Into<PathBuf>, keep_in_memory: usize) -> Self {
+        self.narrative_log = Some(NarrativeSpill::new(path, keep_in_memory));
+        self
+    }
+
+    /// Run offline: no API key or network is needed, and the DM answers with
+    /// generic narration.
+    pub fn offline(self) -> Self {
+        self.with_offline_script(OfflineTransport::new())
+    }
+
+    /// Run offline, with the DM answering from `script`.
+    ///
+    /// Keep a clone of `script` to queue more replies while the session runs.
+    pub fn with_offline_script(mut self, script: OfflineTransport) -> Self {
+        self.offline = Some(script);
+        self
+    }
+
+    /// The DM settings for this configuration.
+    fn dm_config(&self) -> DmConfig {
+        DmConfig {
+            model: self.model.clone(),
+            max_tokens: self.max_tokens,
+            temperature: self.dm_temperature,
+            custom_system_prompt: self.custom_dm_prompt.clone(),
+            history_window: self.history_window,
+            mode_aware_tools: self.mode_aware_tools,
+            max_continuations: self.max_continuations,
+            max_tool_rounds: self.max_tool_rounds,
+            deferred_inference: self.deferred_inference,
+            speculative_dm_request: self.speculative_dm_request,
+            ..Default::default()
+        }
+    }
+
+    /// The DM for this configuration.
+    fn dungeon_master(&self) -> Result<DungeonMaster, SessionError> {
+        let mut dm_config = self.dm_config();
+
+        let client = match &self.offline {
+            Some(script) => {
+                // Only scripted DM replies; skip the side requests
+                dm_config.enable_state_inference = false;
+                dm_config.enable_relevance_check = false;
+                Claude::new("offline").with_transport(script.clone())
+            }
+            None => Claude::from_env().map_err(DmError::from)?,
+        };
+        Ok(DungeonMaster::with_client(client)
+            .with_config(dm_config)
+            .with_locale(self.locale.clone()))
+    }
+
+    /// The configured starting location, or one generated for `character`.
+    async fn starting_location(&self, character: &Character) -> String {
+        if !self.starting_location.is_empty() {
+            return self.starting_location.clone();
+        }
+        if self.offline.is_some() {
+            return FALLBACK_STARTING_LOCATION.to_string();
+        }
+        generate_starting_location(character)
+            .await
+            .unwrap_or_else(|e| {
+                eprintln!(
+                    "Warning: Failed to generate starting location: {e}. Using fallback location."
+                );
+                FALLBACK_STARTING_LOCATION.to_string()
+            })
+    }
 }
 
+/// Where a campaign starts when no location is given or generated.
+const FALLBACK_STARTING_LOCATION: &str = "a crossroads where several paths meet";
+
+/// Most resolutions [`GameSession::effect_log`] keeps; older ones are dropped.
+const EFFECT_LOG_LIMIT: usize = 1000;
+
 /// Response from a player action.
 #[derive(Debug, Clone)]
 pub struct Response {
@@ -111,20 +323,33 @@ pub struct Response {
     /// Effects that were applied to the game world.
     pub effects: Vec<Effect>,
 
+    /// What applying each of `effects` did, in the same order: whether it
+    /// took hold, was skipped, or was limited by the world.
+    pub outcomes: Vec<EffectOutcome>,
+
+    /// What the effects changed, for updating a UI incrementally.
+    pub diff: WorldDiff,
+
     /// Whether combat is currently active.
     pub in_combat: bool,
 
     /// The current player's turn in combat (if applicable).
     pub is_player_turn: bool,
+
+    /// Whether the DM hit the tool round limit and the turn ended early.
+    pub tool_round_limit_reached: bool,
 }
 
 impl From<DmResponse> for Response {
     fn from(dm: DmResponse) -> Self {
         Self {
             narrative: dm.narrative,
+            diff: WorldDiff::from_outcomes(&dm.effects, &dm.outcomes),
             effects: dm.effects,
+            outcomes: dm.outcomes,
             in_combat: false, // Will be set by GameSession
             is_player_turn: false,
+            tool_round_limit_reached: dm.tool_round_limit_reached,
         }
     }
 }
@@ -136,140 +361,221 @@ impl From<DmResponse> for Response {
 /// - The AI Dungeon Master
 /// - Session persistence
 pub struct GameSession {
-    dm: DungeonMaster,
+    dm: Box<dyn DmBackend>,
     world: GameWorld,
+    /// Play time from earlier sittings, restored from the save.
+    earlier_play_time: Duration,
+    /// When this sitting started.
+    resumed_at: Instant,
+    /// Where narrative history beyond what is kept in memory goes.
+    narrative_log: Option<NarrativeSpill>,
+    /// The resolutions of this sitting, in order, up to [`EFFECT_LOG_LIMIT`].
+    resolutions: Vec<Resolution>,
 }
 
 impl GameSession {
     /// Create a new game session with the given configuration.
     ///
-    /// Requires `ANTHROPIC_API_KEY` environment variable to be set.
+    /// Requires `ANTHROPIC_API_KEY` environment variable to be set, unless
+    /// the config is [`offline`](SessionConfig::offline).
     pub async fn new(config: SessionConfig) -> Result<Self, SessionError> {
-        let dm_config = DmConfig {
-            model: config.model,
-            max_tokens: config.max_tokens,
-            temperature: config.temperature,
-            custom_system_prompt: config.custom_dm_prompt,
-            ..Default::default()
-        };
-
-        let dm = DungeonMaster::from_env()?.with_config(dm_config);
-
         // Create a sample character
         let character = create_sample_fighter(&config.character_name);
-
-        let mut world = GameWorld::new(config.campaign_name, character.clone());
-
-        // Generate starting location if not specified
-        let starting_location = if config.starting_location.is_empty() {
-            generate_starting_location(&character)
-                .await
-                .unwrap_or_else(|e| {
-                    eprintln!(
-                    "Warning: Failed to generate starting location: {e}. Using fallback location."
-                );
-                    "a crossroads where several paths meet".to_string()
-                })
-        } else {
-            config.starting_location
-        };
-
-        world.current_location.name = starting_location;
-
-        Ok(Self { dm, world })
+        Self::new_with_character(config, character).await
     }
 
     /// Create a new game session with a custom character.
     ///
-    /// Requires `ANTHROPIC_API_KEY` environment variable to be set.
+    /// Requires `ANTHROPIC_API_KEY` environment variable to be set, unless
+    /// the config is [`offline`](SessionConfig::offline).
     /// If no starting location is specified, one will be generated by AI
     /// based on the character's background and class.
     pub async fn new_with_character(
         config: SessionConfig,
         character: crate::world::Character,
     ) -> Result<Self, SessionError> {
-        let dm_config = DmConfig {
-            model: config.model.clone(),
-            max_tokens: config.max_tokens,
-            temperature: config.temperature,
-            custom_system_prompt: config.custom_dm_prompt,
-            ..Default::default()
-        };
+        let dm = config.dungeon_master()?;
 
-        let dm = DungeonMaster::from_env()?.with_config(dm_config);
+        let mut world = GameWorld::new(config.campaign_name.clone(), character.clone());
+        world.current_location.name = config.starting_location(&character).await;
+        world.house_rules = config.house_rules.clone();
 
-        let mut world = GameWorld::new(config.campaign_name, character.clone());
-
-        // Generate starting location if not specified
-        let starting_location = if config.starting_location.is_empty() {
-            generate_starting_location(&character)
-                .await
-                .unwrap_or_else(|e| {
-                    eprintln!(
-                    "Warning: Failed to generate starting location: {e}. Using fallback location."
-                );
-                    "a crossroads where several paths meet".to_string()
-                })
-        } else {
-            config.starting_location
-        };
-
-        world.current_location.name = starting_location;
-
-        Ok(Self { dm, world })
+        let mut session = Self::from_parts(dm, world);
+        session
+            .set_narrative_log(config.narrative_log.clone())
+            .await;
+        Ok(session)
     }
 
     /// Create a session with a pre-configured world.
     ///
-    /// This allows for custom character creation and world setup.
-    pub fn with_world(dm: DungeonMaster, world: GameWorld) -> Self {
-        Self { dm, world }
+    /// This allows for custom character creation and world setup. The DM can
+    /// be a [`DungeonMaster`], a [`MockDm`](crate::MockDm), or any other
+    /// [`DmBackend`].
+    pub fn with_world(dm: impl DmBackend + 'static, world: GameWorld) -> Self {
+        Self::from_parts(dm, world)
+    }
+
+    fn from_parts(dm: impl DmBackend + 'static, world: GameWorld) -> Self {
+        Self {
+            dm: Box::new(dm),
+            world,
+            earlier_play_time: Duration::ZERO,
+            resumed_at: Instant::now(),
+            narrative_log: None,
+            resolutions: Vec::new(),
+        }
+    }
+
+    /// Real time spent playing this campaign, including earlier sittings.
+    pub fn play_time(&self) -> Duration {
+        self.earlier_play_time + self.resumed_at.elapsed()
     }
 
     /// Load a saved session from a file.
+    ///
+    /// State that older saves lack or got wrong (see [`GameWorld::repair`])
+    /// is derived or clamped on the way in.
     pub async fn load(path: impl AsRef<Path>) -> Result<Self, SessionError> {
-        let content = fs::read_to_string(path).await?;
-        let saved: SavedSession = serde_json::from_str(&content)?;
+        Self::load_with(path, DungeonMaster::from_env()?).await
+    }
+
+    /// Load a saved session and run its DM as `config` describes: the
+    /// model, locale, and other DM settings. Campaign settings such as the
+    /// name and house rules come from the save.
+    ///
+    /// Requires `ANTHROPIC_API_KEY` environment variable to be set, unless
+    /// the config is [`offline`](SessionConfig::offline).
+    pub async fn load_with_config(
+        path: impl AsRef<Path>,
+        config: &SessionConfig,
+    ) -> Result<Self, SessionError> {
+        let mut session = Self::load_with(path, config.dungeon_master()?).await?;
+        if session.narrative_log.is_none() {
+            session
+                .set_narrative_log(config.narrative_log.clone())
+                .await;
+        }
+        Ok(session)
+    }
 
-        let dm = DungeonMaster::from_env()?;
+    /// Load a saved session and run it with `dm`, e.g. a [`DungeonMaster`]
+    /// with custom tools registered. The save's memory replaces `dm`'s.
+    pub async fn load_with(
+        path: impl AsRef<Path>,
+        dm: impl DmBackend + 'static,
+    ) -> Result<Self, SessionError> {
+        let saved: SavedSession = read_checked(path).await?;
+        Ok(Self::from_saved(dm, saved))
+    }
 
-        // Restore memory from saved session
-        let mut session = Self {
-            dm,
-            world: saved.world,
-        };
+    fn from_saved(dm: impl DmBackend + 'static, saved: SavedSession) -> Self {
+        // A stand-in until the saved world replaces it
+        let world = GameWorld::new(String::new(), Character::new(String::new()));
+        let mut session = Self::from_parts(dm, world);
+        session.restore(saved);
+        session
+    }
 
-        // Restore memory context
-        if let Some(summary) = saved.conversation_summary {
-            session.dm.memory_mut().set_summary(summary);
-        }
+    /// Replace this session's campaign with `saved`, keeping the DM's client
+    /// and configuration. Every load and import goes through here.
+    fn restore(&mut self, mut saved: SavedSession) {
+        saved.world.repair();
 
-        for fact in saved.campaign_facts {
-            session
-                .dm
-                .memory_mut()
-                .add_fact(fact.category, &fact.content);
-        }
+        self.world = saved.world;
+        self.narrative_log = saved.narrative_log;
+        self.resolutions.clear();
+        self.earlier_play_time = saved.metadata.as_ref().map_or(Duration::ZERO, |metadata| {
+            Duration::from_secs(metadata.play_time_secs)
+        });
+        self.resumed_at = Instant::now();
 
-        // Restore story memory if present
-        if let Some(story_memory) = saved.story_memory {
-            *session.dm.story_memory_mut() = story_memory;
+        // Restore memory context
+        let memory = self.dm.memory_mut();
+        memory.clear_conversation();
+        memory.campaign_facts = saved.campaign_facts;
+        memory.conversation_summary = saved.conversation_summary;
+        *self.dm.story_memory_mut() = saved.story_memory.unwrap_or_default();
+
+        // Pick the dice up where they left off
+        if let (Some(player), Some(rules)) = (saved.player_roller, self.dm.rules_mut()) {
+            rules.restore_rollers(player, saved.dm_roller);
         }
 
-        Ok(session)
+        // Infer from the last narrative on the next turn, as if never saved
+        self.dm.set_pending_inference(saved.pending_inference);
     }
 
     /// Save the current session to a file.
     pub async fn save(&self, path: impl AsRef<Path>) -> Result<(), SessionError> {
+        let metadata = SaveMetadata {
+            play_time_secs: self.play_time().as_secs(),
+            ..SaveMetadata::for_world(&self.world, chrono_now())
+        };
         let saved = SavedSession {
+            metadata: Some(metadata),
             world: self.world.clone(),
             campaign_facts: self.dm.memory().campaign_facts.to_vec(),
             conversation_summary: Some(self.dm.memory().generate_summary()),
             story_memory: Some(self.dm.story_memory().clone()),
+            player_roller: self.dm.rules().map(|rules| rules.player_roller()),
+            dm_roller: self
+                .dm
+                .rules()
+                .filter(|rules| !rules.shares_rollers())
+                .map(|rules| rules.dm_roller()),
+            narrative_log: self.narrative_log.clone(),
+            pending_inference: self.dm.pending_inference().map(str::to_string),
         };
 
-        let content = serde_json::to_string_pretty(&saved)?;
-        fs::write(path, content).await?;
+        write_checked(path, &saved).await?;
+        Ok(())
+    }
+
+    /// Export the whole campaign (world, character, and story memory) as a
+    /// portable bundle.
+    pub async fn export_bundle(&self, path: impl AsRef<Path>) -> Result<(), SessionError> {
+        let mut bundle = CampaignBundle::new(
+            self.world.clone(),
+            self.dm.story_memory().clone(),
+            self.dm.memory().campaign_facts.to_vec(),
+            Some(self.dm.memory().generate_summary()),
+        );
+        bundle.metadata.play_time_secs = self.play_time().as_secs();
+        bundle.save_json(path).await?;
+        Ok(())
+    }
+
+    /// Replace this session's campaign with one from an exported bundle.
+    ///
+    /// The DM's client and configuration are kept; everything else comes
+    /// from the bundle, and nothing of the previous campaign's conversation
+    /// or play time carries over.
+    pub async fn import_bundle(&mut self, path: impl AsRef<Path>) -> Result<(), SessionError> {
+        let bundle = CampaignBundle::load_json(path).await?;
+
+        // The old campaign's log is overwritten from the start
+        let narrative_log = self
+            .narrative_log
+            .as_ref()
+            .map(|log| NarrativeSpill::new(log.path.clone(), log.keep_in_memory));
+        self.restore(SavedSession {
+            metadata: Some(bundle.metadata),
+            world: bundle.world,
+            campaign_facts: bundle.campaign_facts,
+            conversation_summary: bundle.conversation_summary,
+            story_memory: Some(bundle.story_memory),
+            player_roller: None,
+            dm_roller: None,
+            narrative_log,
+            pending_inference: None,
+        });
+        // Nor do the old campaign's dice carry over
+        if let Some(rules) = self.dm.rules_mut() {
+            rules.restore_rollers(crate::dice::DiceRoller::new(rand::random()), None);
+        }
+        self.spill_narrative().await;
+
         Ok(())
     }
 
@@ -277,23 +583,7 @@ impl GameSession {
     ///
     /// This is the main gameplay loop entry point.
     pub async fn player_action(&mut self, input: &str) -> Result<Response, SessionError> {
-        let dm_response = self.dm.process_input(input, &mut self.world).await?;
-
-        let in_combat = self.world.combat.is_some();
-        let is_player_turn = self
-            .world
-            .combat
-            .as_ref()
-            .and_then(|c| c.current_combatant())
-            .map(|c| c.is_player)
-            .unwrap_or(false);
-
-        Ok(Response {
-            narrative: dm_response.narrative,
-            effects: dm_response.effects,
-            in_combat,
-            is_player_turn,
-        })
+        self.run_turn(input, None, None).await
     }
 
     /// Process a player action with streaming text output.
@@ -321,17 +611,36 @@ impl GameSession {
     pub async fn player_action_streaming_with_effects<F, E>(
         &mut self,
         input: &str,
-        on_text: F,
-        on_effect: E,
+        mut on_text: F,
+        mut on_effect: E,
     ) -> Result<Response, SessionError>
     where
         F: FnMut(&str) + Send,
         E: FnMut(&Effect) + Send,
     {
-        let dm_response = self
-            .dm
-            .process_input_streaming_with_effects(input, &mut self.world, on_text, on_effect)
-            .await?;
+        self.run_turn(input, Some(&mut on_text), Some(&mut on_effect))
+            .await
+    }
+
+    /// Run one turn against the DM backend.
+    async fn run_turn(
+        &mut self,
+        input: &str,
+        on_text: Option<&mut (dyn FnMut(&str) + Send)>,
+        on_effect: Option<&mut (dyn FnMut(&Effect) + Send)>,
+    ) -> Result<Response, SessionError> {
+        let turn = TurnContext {
+            input,
+            world: &mut self.world,
+            on_text: on_text.map(|f| f as &mut (dyn FnMut(&str) + Send)),
+            on_effect: on_effect.map(|f| f as &mut (dyn FnMut(&Effect) + Send)),
+        };
+        let dm_response = self.dm.respond(turn).await?;
+        self.spill_narrative().await;
+        self.resolutions
+            .extend(dm_response.resolutions.iter().cloned());
+        let excess = self.resolutions.len().saturating_sub(EFFECT_LOG_LIMIT);
+        self.resolutions.drain(..excess);
 
         let in_combat = self.world.combat.is_some();
         let is_player_turn = self
@@ -344,12 +653,65 @@ impl GameSession {
 
         Ok(Response {
             narrative: dm_response.narrative,
+            diff: WorldDiff::from_outcomes(&dm_response.effects, &dm_response.outcomes),
             effects: dm_response.effects,
+            outcomes: dm_response.outcomes,
             in_combat,
             is_player_turn,
+            tool_round_limit_reached: dm_response.tool_round_limit_reached,
         })
     }
 
+    /// Spill narrative history to `log` from now on (or stop, with `None`).
+    async fn set_narrative_log(&mut self, log: Option<NarrativeSpill>) {
+        self.narrative_log = log;
+        self.spill_narrative().await;
+    }
+
+    /// Move narrative entries beyond the in-memory limit to the log.
+    async fn spill_narrative(&mut self) {
+        let Some(log) = self.narrative_log.as_mut() else {
+            return;
+        };
+        if let Err(e) = log.spill(&mut self.world.narrative_history).await {
+            eprintln!("Warning: Failed to spill narrative history: {e}");
+        }
+    }
+
+    /// Number of narrative entries over the whole campaign, including those
+    /// spilled to the narrative log.
+    pub fn narrative_len(&self) -> usize {
+        match &self.narrative_log {
+            Some(log) => log.len(&self.world.narrative_history),
+            None => self.world.narrative_history.len(),
+        }
+    }
+
+    /// The narrative entries at positions `range`, counted from the first
+    /// entry of the campaign and clamped to [`narrative_len`](Self::narrative_len).
+    ///
+    /// Entries spilled to the narrative log are read back from disk.
+    pub async fn narrative_page(
+        &self,
+        range: Range<usize>,
+    ) -> Result<Vec<NarrativeEntry>, SessionError> {
+        let history = &self.world.narrative_history;
+        match &self.narrative_log {
+            Some(log) => Ok(log.page(history, range).await?),
+            None => {
+                let end = range.end.min(history.len());
+                Ok(history[range.start.min(end)..end].to_vec())
+            }
+        }
+    }
+
+    /// The effects of the turns played since the session was created or
+    /// loaded, for replay viewers and other external tools. Only the most
+    /// recent 1000 resolutions are kept.
+    pub fn effect_log(&self) -> EffectLog<'_> {
+        EffectLog::new(self.resolutions.as_slice())
+    }
+
     /// Get a reference to the game world.
     pub fn world(&self) -> &GameWorld {
         &self.world
@@ -363,13 +725,26 @@ impl GameSession {
     }
 
     /// Get a reference to the DM.
-    pub fn dm(&self) -> &DungeonMaster {
-        &self.dm
+    pub fn dm(&self) -> &dyn DmBackend {
+        self.dm.as_ref()
     }
 
     /// Get a mutable reference to the DM.
-    pub fn dm_mut(&mut self) -> &mut DungeonMaster {
-        &mut self.dm
+    pub fn dm_mut(&mut self) -> &mut dyn DmBackend {
+        self.dm.as_mut()
+    }
+
+    /// Counts of the tools the DM has called this session.
+    pub fn tool_metrics(&self) -> &ToolMetrics {
+        self.dm.tool_metrics()
+    }
+
+    /// The request the next turn for `action` would send first: system
+    /// prompt, tools, and messages. Nothing is sent and no state changes.
+    ///
+    /// `None` when the session's backend doesn't send Claude requests.
+    pub fn build_turn_request(&self, action: &str) -> Option<Request> {
+        self.dm.build_turn_request(action, &self.world)
     }
 
     /// Get the player character's name.
@@ -416,7 +791,7 @@ impl GameSession {
 /// Uses Claude Haiku to create unique, character-appropriate starting scenarios
 /// that provide interesting hooks for adventure.
 async fn generate_starting_location(character: &Character) -> Result<String, SessionError> {
-    let client = Claude::from_env().map_err(|_| SessionError::Dm(DmError::NoApiKey))?;
+    let client = Claude::from_env().map_err(|_| SessionError::MissingApiKey)?;
 
     // Build character context
     let class_info = character
@@ -484,11 +859,25 @@ Respond with ONLY the location description, no preamble."#,
 /// Serializable session state for persistence.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct SavedSession {
+    /// Written before the world so save listings can read it cheaply.
+    #[serde(default)]
+    metadata: Option<SaveMetadata>,
     world: GameWorld,
     campaign_facts: Vec<crate::dm::memory::CampaignFact>,
     conversation_summary: Option<String>,
     #[serde(default)]
     story_memory: Option<crate::dm::StoryMemory>,
+    /// Dice saved mid-sequence; no DM roller means it shares the player's.
+    #[serde(default)]
+    player_roller: Option<crate::dice::DiceRoller>,
+    #[serde(default)]
+    dm_roller: Option<crate::dice::DiceRoller>,
+    /// How much of the narrative log belongs to this save.
+    #[serde(default)]
+    narrative_log: Option<NarrativeSpill>,
+    /// Last narrative, when its state inference was deferred to the next turn.
+    #[serde(default)]
+    pending_inference: Option<String>,
 }
 
 #[cfg(test)]
@@ -500,12 +889,71 @@ mod tests {
         let config = SessionConfig::new("Test Campaign")
             .with_character_name("Thorin")
             .with_starting_location("Mountain Hall")
-            .with_max_tokens(2048);
+            .with_max_tokens(2048)
+            .with_dm_temperature(1.2);
 
         assert_eq!(config.campaign_name, "Test Campaign");
         assert_eq!(config.character_name, "Thorin");
         assert_eq!(config.starting_location, "Mountain Hall");
         assert_eq!(config.max_tokens, 2048);
+        assert_eq!(config.dm_config().temperature, Some(1.2));
+        assert_eq!(config.with_history_window(0).history_window, 1);
+    }
+
+    #[tokio::test]
+    async fn test_house_rules_apply_to_new_campaign() {
+        use crate::world::CritRule;
+
+        let config = SessionConfig::new("House Rules")
+            .with_starting_location("Tavern")
+            .with_house_rules(HouseRules {
+                crit_rule: CritRule::Brutal,
+            })
+            .offline();
+        let session = GameSession::new(config).await.unwrap();
+
+        assert_eq!(session.world().house_rules.crit_rule, CritRule::Brutal);
+    }
+
+    #[tokio::test]
+    async fn test_dm_temperature_leaves_helper_calls_deterministic() {
+        fn reply(text: &str) -> String {
+            serde_json::json!({
+                "id": "msg",
+                "model": "m",
+                "content": [{"type": "text", "text": text}],
+                "stop_reason": "end_turn",
+                "usage": {"input_tokens": 1, "output_tokens": 1}
+            })
+            .to_string()
+        }
+
+        let transport = claude::MockTransport::new();
+        transport.push_response(200, reply("Bram grins and waves you over."));
+        transport.push_response(200, reply(r#"{"inferred_changes": []}"#));
+        let config = SessionConfig::new("Temperature").with_dm_temperature(1.0);
+        let dm = DungeonMaster::with_client(Claude::new("k").with_transport(transport.clone()))
+            .with_config(config.dm_config());
+
+        // State inference only runs when there is someone to infer about
+        let mut world = GameWorld::new("Temperature", create_sample_fighter("Roland"));
+        let bram = crate::world::NPC::new("Bram");
+        world.npcs.insert(bram.id, bram);
+        let mut session = GameSession::with_world(dm, world);
+        session.player_action("I greet Bram").await.unwrap();
+
+        let temperatures: Vec<serde_json::Value> = transport
+            .requests()
+            .iter()
+            .map(|request| {
+                let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+                body["temperature"].clone()
+            })
+            .collect();
+        assert_eq!(
+            temperatures,
+            vec![serde_json::json!(1.0), serde_json::json!(0.0)]
+        );
     }
 
     #[test]
@@ -514,12 +962,670 @@ mod tests {
             narrative: "You see a dragon!".to_string(),
             intents: vec![],
             effects: vec![],
+            outcomes: vec![],
             resolutions: vec![],
             inferred_state_changes: vec![],
+            tool_round_limit_reached: false,
         };
 
         let response: Response = dm_response.into();
         assert_eq!(response.narrative, "You see a dragon!");
         assert!(!response.in_combat);
     }
+
+    #[tokio::test]
+    async fn test_tool_metrics_count_calls_and_failures() {
+        let transport = claude::MockTransport::new();
+        transport.push_response(
+            200,
+            r#"{
+                "id": "msg_01",
+                "model": "m",
+                "content": [
+                    {"type": "tool_use", "id": "t1", "name": "skill_check",
+                     "input": {"skill": "athletics", "dc": 10}},
+                    {"type": "tool_use", "id": "t2", "name": "skill_check",
+                     "input": {"skill": "athletics"}},
+                    {"type": "tool_use", "id": "t3", "name": "roll_dice",
+                     "input": {"notation": "1d6"}}
+                ],
+                "stop_reason": "tool_use",
+                "usage": {"input_tokens": 1, "output_tokens": 1}
+            }"#,
+        );
+        transport.push_response(
+            200,
+            r#"{
+                "id": "msg_02",
+                "model": "m",
+                "content": [{"type": "text", "text": "You haul yourself up."}],
+                "stop_reason": "end_turn",
+                "usage": {"input_tokens": 1, "output_tokens": 1}
+            }"#,
+        );
+        let dm = DungeonMaster::with_client(Claude::new("test-key").with_transport(transport))
+            .with_config(DmConfig {
+                enable_state_inference: false,
+                ..Default::default()
+            });
+        let world = GameWorld::new("Metrics", create_sample_fighter("Roland"));
+        let mut session = GameSession::with_world(dm, world);
+
+        session.player_action("I climb the wall").await.unwrap();
+
+        let metrics = session.tool_metrics();
+        let skill_check = metrics.get("skill_check");
+        assert_eq!(skill_check.calls, 2);
+        assert_eq!(skill_check.failures, 1);
+        assert_eq!(skill_check.successes(), 1);
+        assert_eq!(metrics.get("roll_dice").calls, 1);
+        assert_eq!(metrics.get("roll_dice").failures, 0);
+        assert_eq!(metrics.get("attack").calls, 0);
+        assert_eq!(metrics.total_calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_session_save_is_listed_with_metadata() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dm = DungeonMaster::with_client(Claude::new("test-key"));
+        let world = GameWorld::new("Listed", create_sample_fighter("Roland"));
+        let mut session = GameSession::with_world(dm, world);
+        session.earlier_play_time = Duration::from_secs(3600);
+
+        session
+            .save(temp_dir.path().join("listed.json"))
+            .await
+            .unwrap();
+
+        let saves = crate::persist::list_saves(temp_dir.path()).await.unwrap();
+        assert_eq!(saves.len(), 1);
+        let metadata = &saves[0].metadata;
+        assert_eq!(metadata.campaign_name, "Listed");
+        assert_eq!(metadata.character_name, "Roland");
+        assert!(metadata.play_time_secs >= 3600);
+        assert!(!metadata.saved_at.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dice_resume_their_sequences_after_reload() {
+        use crate::dice::DiceRoller;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("dice.json");
+        let world = GameWorld::new("Dice", create_sample_fighter("Roland"));
+        let roll = |session: &GameSession| {
+            let rules = session.dm().rules().unwrap();
+            (
+                rules.roll_player("1d20").unwrap().total,
+                rules.roll_dm("1d20").unwrap().total,
+            )
+        };
+
+        // Separate rollers each pick up where they left off
+        let mut session = GameSession::with_world(
+            DungeonMaster::with_client(Claude::new("test-key")),
+            world.clone(),
+        );
+        session
+            .dm_mut()
+            .rules_mut()
+            .unwrap()
+            .restore_rollers(DiceRoller::new(7), Some(DiceRoller::new(8)));
+        roll(&session);
+        session.save(&path).await.unwrap();
+        let expected: Vec<_> = (0..5).map(|_| roll(&session)).collect();
+
+        let loaded =
+            GameSession::load_with(&path, DungeonMaster::with_client(Claude::new("test-key")))
+                .await
+                .unwrap();
+        assert!(!loaded.dm().rules().unwrap().shares_rollers());
+        let resumed: Vec<_> = (0..5).map(|_| roll(&loaded)).collect();
+        assert_eq!(resumed, expected);
+
+        // A shared roller stays shared
+        let session =
+            GameSession::with_world(DungeonMaster::with_client(Claude::new("test-key")), world);
+        session.save(&path).await.unwrap();
+        let loaded =
+            GameSession::load_with(&path, DungeonMaster::with_client(Claude::new("test-key")))
+                .await
+                .unwrap();
+        assert!(loaded.dm().rules().unwrap().shares_rollers());
+        assert_eq!(
+            loaded.dm().rules().unwrap().player_roller().seed(),
+            session.dm().rules().unwrap().player_roller().seed()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deferred_inference_survives_a_reload() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("deferred.json");
+        let world = GameWorld::new("Deferred", create_sample_fighter("Roland"));
+        let mut session =
+            GameSession::with_world(DungeonMaster::with_client(Claude::new("test-key")), world);
+        session
+            .dm_mut()
+            .set_pending_inference(Some("Mira smiles warmly.".to_string()));
+        session.save(&path).await.unwrap();
+
+        let loaded =
+            GameSession::load_with(&path, DungeonMaster::with_client(Claude::new("test-key")))
+                .await
+                .unwrap();
+        assert_eq!(loaded.dm().pending_inference(), Some("Mira smiles warmly."));
+    }
+
+    #[tokio::test]
+    async fn test_load_with_config_keeps_the_locale() {
+        use crate::rules::DamageType;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("espanol.json");
+        let locale = Locale::english().with_damage_type(DamageType::Fire, "fuego");
+        let config = SessionConfig::new("Fuego")
+            .with_starting_location("Plaza")
+            .with_locale(locale.clone())
+            .with_dm_temperature(0.3)
+            .offline();
+        GameSession::new(config.clone())
+            .await
+            .unwrap()
+            .save(&path)
+            .await
+            .unwrap();
+
+        let session = GameSession::load_with_config(&path, &config).await.unwrap();
+
+        assert_eq!(session.dm().rules().unwrap().locale(), &locale);
+        let request = session
+            .dm()
+            .build_turn_request("hola", session.world())
+            .unwrap();
+        assert_eq!(request.temperature, Some(0.3));
+        assert_eq!(session.world().campaign_name, "Fuego");
+    }
+
+    #[tokio::test]
+    async fn test_narrative_log_pages_back_and_follows_the_loaded_save() {
+        use crate::world::NarrativeType;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("long.json");
+        let config = SessionConfig::new("Long Campaign")
+            .with_starting_location("Inn")
+            .with_narrative_log(temp_dir.path().join("narrative.jsonl"), 3)
+            .offline();
+        async fn actions(session: &GameSession) -> Vec<String> {
+            session
+                .narrative_page(0..session.narrative_len())
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|entry| matches!(entry.entry_type, NarrativeType::PlayerAction))
+                .map(|entry| entry.content)
+                .collect()
+        }
+
+        let mut session = GameSession::new(config.clone()).await.unwrap();
+        for turn in 0..3 {
+            session
+                .player_action(&format!("Wait {turn}"))
+                .await
+                .unwrap();
+        }
+        assert_eq!(session.world().narrative_history.len(), 3);
+        assert!(session.narrative_len() > 3);
+        assert_eq!(actions(&session).await, ["Wait 0", "Wait 1", "Wait 2"]);
+        let first = session.narrative_page(0..1).await.unwrap();
+        assert_eq!(first[0].content, "Wait 0");
+        session.save(&path).await.unwrap();
+
+        // Entries spilled after the save don't belong to it
+        for turn in 3..6 {
+            session
+                .player_action(&format!("Wait {turn}"))
+                .await
+                .unwrap();
+        }
+        let mut reloaded = GameSession::load_with_config(&path, &config).await.unwrap();
+        assert_eq!(actions(&reloaded).await, ["Wait 0", "Wait 1", "Wait 2"]);
+
+        reloaded.player_action("Leave").await.unwrap();
+        assert_eq!(
+            actions(&reloaded).await,
+            ["Wait 0", "Wait 1", "Wait 2", "Leave"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_with_keeps_the_dms_custom_tools() {
+        use crate::dm::ToolRegistry;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("patron.json");
+        let world = GameWorld::new("Patron", create_sample_fighter("Roland"));
+        GameSession::with_world(DungeonMaster::with_client(Claude::new("test-key")), world)
+            .save(&path)
+            .await
+            .unwrap();
+
+        let mut tools = ToolRegistry::new();
+        tools.register(
+            claude::Tool {
+                name: "invoke_patron".to_string(),
+                description: "Call on the warlock's patron.".to_string(),
+                input_schema: serde_json::json!({"type": "object", "properties": {}}),
+            },
+            |_input, _world| None,
+        );
+        let dm = DungeonMaster::with_client(Claude::new("test-key")).with_tool_registry(tools);
+        let session = GameSession::load_with(&path, dm).await.unwrap();
+
+        let request = session
+            .dm()
+            .build_turn_request("I pray", session.world())
+            .unwrap();
+        assert!(request
+            .tools
+            .unwrap_or_default()
+            .iter()
+            .any(|tool| tool.name == "invoke_patron"));
+    }
+
+    #[test]
+    fn test_missing_api_key_surfaces_as_session_error() {
+        fn start() -> Result<(), SessionError> {
+            Err(DmError::from(claude::Error::NoApiKey))?
+        }
+        let error = start().unwrap_err();
+        assert!(matches!(error, SessionError::MissingApiKey));
+        assert!(error.to_string().contains("ANTHROPIC_API_KEY"));
+
+        let error = SessionError::from(DmError::NoApiKey);
+        assert!(matches!(error, SessionError::MissingApiKey));
+
+        let other = SessionError::from(DmError::ToolError("bad".to_string()));
+        assert!(matches!(other, SessionError::Dm(DmError::ToolError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_offline_session_runs_turns_through_the_rules() {
+        use crate::offline::{ScriptedReply, OFFLINE_NARRATION};
+        use serde_json::json;
+
+        let script = crate::OfflineTransport::new();
+        script.push_reply(ScriptedReply::with_tool_calls(
+            "The lid creaks open.",
+            vec![
+                (
+                    "give_item",
+                    json!({"item_name": "Silver Locket", "value_gp": 40}),
+                ),
+                ("adjust_gold", json!({"amount": 25, "reason": "chest"})),
+            ],
+        ));
+        script.push_narration("Inside you find a locket and a pouch of coins.");
+        script.push_reply(ScriptedReply::with_tool_calls(
+            "",
+            vec![(
+                "apply_damage",
+                json!({"amount": 4, "damage_type": "piercing", "source": "dart trap"}),
+            )],
+        ));
+        script.push_narration("A dart jabs your arm.");
+
+        let config = SessionConfig::new("Offline")
+            .with_offline_script(script.clone())
+            .with_mode_aware_tools(false);
+        let mut session = GameSession::new_with_character(config, create_sample_fighter("Roland"))
+            .await
+            .unwrap();
+        let gold = session.world().player_character.inventory.gold;
+        let hp = session.world().player_character.hit_points.current;
+        assert_eq!(
+            session.world().current_location.name,
+            FALLBACK_STARTING_LOCATION
+        );
+
+        let response = session.player_action("I pry open the chest").await.unwrap();
+        assert!(response.narrative.contains("a locket and a pouch of coins"));
+        let character = &session.world().player_character;
+        assert_eq!(character.inventory.gold, gold + 25);
+        assert!(character.inventory.find_item("Silver Locket").is_some());
+
+        let mut streamed = String::new();
+        session
+            .player_action_streaming("I check the chest for traps", |text| {
+                streamed.push_str(text)
+            })
+            .await
+            .unwrap();
+        assert!(streamed.contains("A dart jabs your arm."));
+        assert_eq!(session.world().player_character.hit_points.current, hp - 4);
+        assert_eq!(script.remaining(), 0);
+
+        // Past the end of the script the DM still answers
+        let response = session.player_action("I wait").await.unwrap();
+        assert_eq!(response.narrative, OFFLINE_NARRATION);
+        assert_eq!(session.tool_metrics().total_calls(), 3);
+    }
+
+    /// A backend that answers every turn by granting the same gold.
+    struct FixedToolBackend {
+        memory: crate::dm::DmMemory,
+        story_memory: crate::dm::StoryMemory,
+        tool_metrics: ToolMetrics,
+    }
+
+    impl DmBackend for FixedToolBackend {
+        fn respond<'a>(
+            &'a mut self,
+            turn: TurnContext<'a>,
+        ) -> futures::future::BoxFuture<'a, Result<DmResponse, DmError>> {
+            let input = serde_json::json!({"amount": 10, "reason": "tithe"});
+            let intent = crate::dm::parse_tool_call("adjust_gold", &input, turn.world)
+                .expect("adjust_gold input parses");
+            self.tool_metrics.record("adjust_gold", true);
+            let resolution = crate::rules::RulesEngine::new().resolve(turn.world, intent.clone());
+            let outcomes = crate::rules::apply_effects(turn.world, &resolution.effects);
+            if let Some(on_effect) = turn.on_effect {
+                resolution.effects.iter().for_each(on_effect);
+            }
+            let response = DmResponse {
+                narrative: "A grateful pilgrim presses coins into your hand.".to_string(),
+                intents: vec![intent],
+                effects: resolution.effects.clone(),
+                outcomes,
+                resolutions: vec![resolution],
+                inferred_state_changes: Vec::new(),
+                tool_round_limit_reached: false,
+            };
+            Box::pin(async move { Ok(response) })
+        }
+
+        fn memory(&self) -> &crate::dm::DmMemory {
+            &self.memory
+        }
+
+        fn memory_mut(&mut self) -> &mut crate::dm::DmMemory {
+            &mut self.memory
+        }
+
+        fn story_memory(&self) -> &crate::dm::StoryMemory {
+            &self.story_memory
+        }
+
+        fn story_memory_mut(&mut self) -> &mut crate::dm::StoryMemory {
+            &mut self.story_memory
+        }
+
+        fn tool_metrics(&self) -> &ToolMetrics {
+            &self.tool_metrics
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_stops_at_configured_rounds() {
+        use crate::offline::ScriptedReply;
+        use serde_json::json;
+
+        // A DM that never stops calling tools
+        let script = crate::OfflineTransport::new();
+        for _ in 0..10 {
+            script.push_reply(ScriptedReply::with_tool_calls(
+                "The dice keep tumbling.",
+                vec![("roll_dice", json!({"notation": "1d20", "purpose": "luck"}))],
+            ));
+        }
+        let config = SessionConfig::new("Runaway")
+            .with_offline_script(script.clone())
+            .with_max_tool_rounds(3);
+        let mut session = GameSession::new_with_character(config, create_sample_fighter("Roland"))
+            .await
+            .unwrap();
+
+        let response = session.player_action("I roll").await.unwrap();
+
+        // Three rounds run, then the fourth reply ends the turn
+        assert!(response.tool_round_limit_reached);
+        assert_eq!(script.remaining(), 6);
+        assert_eq!(
+            response
+                .narrative
+                .matches("The dice keep tumbling.")
+                .count(),
+            4
+        );
+        assert_eq!(session.tool_metrics().total_calls(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_build_turn_request_shows_the_next_prompt() {
+        use crate::dm::DmTools;
+        use crate::world::GameMode;
+        use claude::{ContentBlock, Role};
+
+        let script = crate::OfflineTransport::new();
+        script.push_narration("The door groans open.");
+        let config = SessionConfig::new("Inspect")
+            .with_offline_script(script.clone())
+            .with_model("test-model")
+            .with_dm_temperature(0.5);
+        let mut session = GameSession::new_with_character(config, create_sample_fighter("Roland"))
+            .await
+            .unwrap();
+        session.player_action("I knock").await.unwrap();
+
+        let request = session.build_turn_request("I open the door").unwrap();
+
+        assert_eq!(request.model.as_deref(), Some("test-model"));
+        assert_eq!(request.temperature, Some(0.5));
+        assert_eq!(request.max_tokens, 4096);
+        let system = request.system.as_deref().unwrap();
+        assert!(system.contains("Roland"));
+        let tool_names: Vec<_> = request.tools.unwrap().into_iter().map(|t| t.name).collect();
+        let expected: Vec<_> = DmTools::for_mode(GameMode::Exploration)
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(tool_names, expected);
+        let messages: Vec<_> = request
+            .messages
+            .iter()
+            .map(|m| match (&m.role, m.content.as_slice()) {
+                (Role::User, [ContentBlock::Text { text }]) => format!("user: {text}"),
+                (Role::Assistant, [ContentBlock::Text { text }]) => format!("dm: {text}"),
+                other => panic!("unexpected message {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "user: I knock",
+                "dm: The door groans open.",
+                "user: I open the door"
+            ]
+        );
+
+        // Nothing was sent or remembered
+        assert_eq!(script.remaining(), 0);
+        assert_eq!(session.dm.memory().get_recent_messages(usize::MAX).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_effect_log_keeps_only_the_most_recent_resolutions() {
+        let backend = FixedToolBackend {
+            memory: crate::dm::DmMemory::new(),
+            story_memory: crate::dm::StoryMemory::new(),
+            tool_metrics: ToolMetrics::new(),
+        };
+        let world = GameWorld::new("Custom", create_sample_fighter("Roland"));
+        let mut session = GameSession::with_world(backend, world);
+
+        for _ in 0..EFFECT_LOG_LIMIT + 5 {
+            session.player_action("I bless the pilgrim").await.unwrap();
+        }
+
+        let log = session.effect_log();
+        assert_eq!(log.resolutions.len(), EFFECT_LOG_LIMIT);
+        assert!(matches!(log.resolutions, std::borrow::Cow::Borrowed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_custom_backend_drives_session() {
+        let backend = FixedToolBackend {
+            memory: crate::dm::DmMemory::new(),
+            story_memory: crate::dm::StoryMemory::new(),
+            tool_metrics: ToolMetrics::new(),
+        };
+        let world = GameWorld::new("Custom", create_sample_fighter("Roland"));
+        let gold = world.player_character.inventory.gold;
+        let mut session = GameSession::with_world(backend, world);
+
+        let response = session.player_action("I bless the pilgrim").await.unwrap();
+        assert_eq!(
+            response.narrative,
+            "A grateful pilgrim presses coins into your hand."
+        );
+        assert_eq!(session.world().player_character.inventory.gold, gold + 10);
+
+        let mut effects = 0;
+        session
+            .player_action_streaming_with_effects("I bless another", |_| {}, |_| effects += 1)
+            .await
+            .unwrap();
+        assert_eq!(session.world().player_character.inventory.gold, gold + 20);
+        assert_eq!(session.tool_metrics().get("adjust_gold").calls, 2);
+        assert!(effects >= 1);
+
+        let log = session.effect_log();
+        assert_eq!(log.schema_version, crate::rules::EFFECT_SCHEMA_VERSION);
+        assert_eq!(log.resolutions.len(), 2);
+        assert!(log.resolutions[1]
+            .effects
+            .iter()
+            .any(|e| matches!(e, Effect::GoldChanged { amount: 10, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_mock_dm_backs_a_session() {
+        use crate::testing::{MockDm, MockResponse};
+        use serde_json::json;
+
+        let dm = MockDm::new(vec![MockResponse::with_tool_calls(
+            "Coins spill from the purse.",
+            vec![("adjust_gold", json!({"amount": 5}))],
+        )]);
+        let world = GameWorld::new("Mocked", create_sample_fighter("Roland"));
+        let gold = world.player_character.inventory.gold;
+        let mut session = GameSession::with_world(dm, world);
+
+        let mut streamed = String::new();
+        session
+            .player_action_streaming("I cut the purse", |text| streamed.push_str(text))
+            .await
+            .unwrap();
+
+        assert!(streamed.contains("Coins spill from the purse."));
+        assert_eq!(session.world().player_character.inventory.gold, gold + 5);
+        assert_eq!(session.tool_metrics().get("adjust_gold").calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_bundle_round_trip() {
+        use crate::dm::story_memory::{
+            ConsequenceSeverity, EntityType, FactCategory as StoryFactCategory, FactSource,
+        };
+        use crate::dm::FactCategory;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let bundle_path = temp_dir.path().join("campaign.bundle.json");
+
+        let mut world = GameWorld::new("Bundle Test", create_sample_fighter("Roland"));
+        // Out of range, as a hand-edited bundle might be
+        world.player_character.hit_points.current = world.player_character.hit_points.maximum + 50;
+        let mut session = GameSession::with_world(DungeonMaster::new("test-key"), world);
+        session
+            .dm_mut()
+            .memory_mut()
+            .add_fact(FactCategory::NPC, "The mayor is a vampire");
+        let story = session.dm_mut().story_memory_mut();
+        let mayor = story.create_entity(EntityType::Npc, "Mayor Harlow");
+        story.record_fact(
+            mayor,
+            "Harlow never appears in daylight",
+            StoryFactCategory::Secret,
+            FactSource::DmNarration,
+        );
+        let consequence = story.create_consequence(
+            "Player enters the crypt",
+            "Harlow's thralls attack",
+            ConsequenceSeverity::Major,
+        );
+
+        session
+            .export_bundle(&bundle_path)
+            .await
+            .expect("Export should succeed");
+
+        let world = GameWorld::new("Other Campaign", create_sample_fighter("Someone Else"));
+        let mut restored = GameSession::with_world(DungeonMaster::new("test-key"), world);
+        let memory = restored.dm_mut().memory_mut();
+        memory.add_player_message("I knock on the old campaign's door");
+        memory.set_summary("The old campaign's summary");
+        restored.earlier_play_time = Duration::from_secs(9_000);
+        restored
+            .resolutions
+            .push(Resolution::new("The old campaign's door opens."));
+        restored.dm_mut().rules_mut().unwrap().restore_rollers(
+            crate::dice::DiceRoller::new(7),
+            Some(crate::dice::DiceRoller::new(8)),
+        );
+        restored
+            .import_bundle(&bundle_path)
+            .await
+            .expect("Import should succeed");
+
+        // Nothing from the session's previous campaign leaks into the import
+        let memory = restored.dm().memory();
+        assert_eq!(memory.message_count(), 0);
+        assert_ne!(
+            memory.conversation_summary.as_deref(),
+            Some("The old campaign's summary")
+        );
+        assert!(restored.play_time() < Duration::from_secs(60));
+        assert!(restored.effect_log().resolutions.is_empty());
+        assert!(restored.dm().rules().unwrap().shares_rollers());
+
+        // The bundle is repaired on the way in, as a loaded save is
+        let hp = &restored.world().player_character.hit_points;
+        assert_eq!(hp.current, hp.maximum);
+
+        assert_eq!(restored.world().campaign_name, "Bundle Test");
+        assert_eq!(restored.player_name(), "Roland");
+        assert_eq!(
+            restored.dm().memory().campaign_facts[0].content,
+            "The mayor is a vampire"
+        );
+
+        let story = restored.dm().story_memory();
+        let mayor = story
+            .find_entity_id("Mayor Harlow")
+            .expect("Entity should survive the round trip");
+        assert_eq!(
+            story.facts_about(mayor)[0].content,
+            "Harlow never appears in daylight"
+        );
+        assert_eq!(story.pending_consequence_count(), 1);
+        assert_eq!(
+            story
+                .get_consequence(consequence)
+                .expect("Consequence should survive the round trip")
+                .consequence_description,
+            "Harlow's thralls attack"
+        );
+    }
 }
@@ -22,6 +22,42 @@ pub fn get_spell(name: &str) -> Option<&'static SpellData> {
     SPELL_DATABASE.get(&name.to_lowercase())
 }
 
+/// Look up a spell by the name a player or the DM wrote.
+///
+/// Tolerates case, stray punctuation and spacing, and the SRD 5.2 names that
+/// drop the wizard's name (e.g. "Acid Arrow" for "Melf's Acid Arrow").
+pub fn find_spell(name: &str) -> Option<&'static SpellData> {
+    if let Some(spell) = get_spell(name.trim()) {
+        return Some(spell);
+    }
+
+    let wanted = normalize_spell_name(name);
+    if wanted.is_empty() {
+        return None;
+    }
+    all_spells().find(|spell| {
+        normalize_spell_name(&spell.name) == wanted
+            || spell
+                .name
+                .split_once("'s ")
+                .is_some_and(|(_, rest)| normalize_spell_name(rest) == wanted)
+    })
+}
+
+/// Lowercase words with punctuation removed and single spaces between.
+fn normalize_spell_name(name: &str) -> String {
+    name.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Get all spells in the database.
 pub fn all_spells() -> impl Iterator<Item = &'static SpellData> {
     SPELL_DATABASE.values()
@@ -77,6 +113,35 @@ mod tests {
         assert!(get_spell("fireball").is_some());
     }
 
+    #[test]
+    fn test_find_spell_tolerates_written_names() {
+        let fireball = find_spell("  Fireball! ").expect("Fireball should be found");
+        assert_eq!(fireball.name, "Fireball");
+        assert_eq!(fireball.level_and_school(), "3rd-level Evocation");
+
+        let arrow = find_spell("acid arrow").expect("Melf's Acid Arrow should be found");
+        assert_eq!(arrow.name, "Melf's Acid Arrow");
+        assert_eq!(find_spell("hunters mark").unwrap().name, "Hunter's Mark");
+
+        assert!(find_spell("").is_none());
+        assert!(find_spell("Definitely Not A Spell").is_none());
+    }
+
+    #[test]
+    fn test_spell_detail_fields_for_known_spell() {
+        let hold_person = find_spell("Hold Person").expect("Hold Person should exist");
+        assert_eq!(hold_person.level_and_school(), "2nd-level Enchantment");
+        assert!(hold_person.concentration);
+        assert!(!hold_person.casting_time.description().is_empty());
+        assert!(!hold_person.range.description().is_empty());
+        assert!(!hold_person.components.description().is_empty());
+        assert!(!hold_person.duration.description().is_empty());
+        assert!(!hold_person.description.is_empty());
+
+        let fire_bolt = find_spell("fire bolt").unwrap();
+        assert_eq!(fire_bolt.level_and_school(), "Evocation cantrip");
+    }
+
     #[test]
     fn test_cantrip_scaling() {
         let fire_bolt = get_spell("fire bolt").expect("Fire Bolt should exist");
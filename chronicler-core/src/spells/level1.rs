@@ -387,7 +387,7 @@ pub fn register_level1(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Evocation,
         casting_time: CastingTime::Action,
         range: SpellRange::Feet(90),
-        components: Components::vsm("a diamond worth at least 50 gp"),
+        components: Components::vsm("a diamond worth at least 50 gp").with_cost("diamond", 50, false),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -673,7 +673,7 @@ pub fn register_level1(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Transmutation,
         casting_time: CastingTime::Reaction("which you take when you or a creature within 60 feet of you falls".to_string()),
         range: SpellRange::Feet(60),
-        components: Components { verbal: true, somatic: false, material: Some("a small feather or piece of down".to_string()) },
+        components: Components { verbal: true, somatic: false, material: Some("a small feather or piece of down".to_string()), cost: None },
         duration: SpellDuration::Minutes(1),
         concentration: false,
         ritual: false,
@@ -695,7 +695,7 @@ pub fn register_level1(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Conjuration,
         casting_time: CastingTime::Hours(1),
         range: SpellRange::Feet(10),
-        components: Components::vsm("10 gp worth of charcoal, incense, and herbs that must be consumed by fire in a brass brazier"),
+        components: Components::vsm("10 gp worth of charcoal, incense, and herbs that must be consumed by fire in a brass brazier").with_cost("incense", 10, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: true,
@@ -805,7 +805,7 @@ pub fn register_level1(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Divination,
         casting_time: CastingTime::Minutes(1),
         range: SpellRange::Touch,
-        components: Components::vsm("a pearl worth at least 100 gp and an owl feather"),
+        components: Components::vsm("a pearl worth at least 100 gp and an owl feather").with_cost("pearl", 100, false),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: true,
@@ -145,7 +145,7 @@ pub fn register_level2(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Abjuration,
         casting_time: CastingTime::Action,
         range: SpellRange::Touch,
-        components: Components::vsm("gold dust worth at least 25 gp, which the spell consumes"),
+        components: Components::vsm("gold dust worth at least 25 gp, which the spell consumes").with_cost("gold dust", 25, true),
         duration: SpellDuration::UntilDispelled,
         concentration: false,
         ritual: false,
@@ -629,7 +629,7 @@ pub fn register_level2(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Illusion,
         casting_time: CastingTime::Minutes(1),
         range: SpellRange::Feet(30),
-        components: Components::vsm("a small bit of honeycomb and jade dust worth at least 10 gp, which the spell consumes"),
+        components: Components::vsm("a small bit of honeycomb and jade dust worth at least 10 gp, which the spell consumes").with_cost("jade dust", 10, true),
         duration: SpellDuration::UntilDispelled,
         concentration: false,
         ritual: true,
@@ -959,7 +959,7 @@ pub fn register_level2(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Abjuration,
         casting_time: CastingTime::Action,
         range: SpellRange::Touch,
-        components: Components::vsm("a pair of platinum rings worth at least 50 gp each, which you and the target must wear for the duration"),
+        components: Components::vsm("a pair of platinum rings worth at least 50 gp each, which you and the target must wear for the duration").with_cost("platinum ring", 50, false),
         duration: SpellDuration::Hours(1),
         concentration: false,
         ritual: false,
@@ -788,7 +788,7 @@ pub fn register_level3(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Necromancy,
         casting_time: CastingTime::Action,
         range: SpellRange::Touch,
-        components: Components::vsm("diamonds worth 300 gp, which the spell consumes"),
+        components: Components::vsm("diamonds worth 300 gp, which the spell consumes").with_cost("diamond", 300, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -952,7 +952,11 @@ pub fn register_level3(db: &mut HashMap<String, SpellData>) {
             school: SpellSchool::Conjuration,
             casting_time: CastingTime::Action,
             range: SpellRange::Feet(90),
-            components: Components::vsm("a gilded flower worth at least 300 gp"),
+            components: Components::vsm("a gilded flower worth at least 300 gp").with_cost(
+                "gilded flower",
+                300,
+                false,
+            ),
             duration: SpellDuration::Hours(1),
             concentration: true,
             ritual: false,
@@ -189,7 +189,7 @@ pub fn register_level4(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Abjuration,
         casting_time: CastingTime::Action,
         range: SpellRange::Touch,
-        components: Components::vsm("diamond dust worth 100 gp, which the spell consumes"),
+        components: Components::vsm("diamond dust worth 100 gp, which the spell consumes").with_cost("diamond dust", 100, true),
         duration: SpellDuration::Hours(1),
         concentration: true,
         ritual: false,
@@ -431,7 +431,7 @@ pub fn register_level4(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Divination,
         casting_time: CastingTime::Action,
         range: SpellRange::Self_,
-        components: Components::vsm("incense and a sacrificial offering appropriate to your religion, together worth at least 25 gp, which the spell consumes"),
+        components: Components::vsm("incense and a sacrificial offering appropriate to your religion, together worth at least 25 gp, which the spell consumes").with_cost("incense", 25, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: true,
@@ -233,7 +233,7 @@ pub fn register_level5(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Transmutation,
         casting_time: CastingTime::Hours(8),
         range: SpellRange::Touch,
-        components: Components::vsm("an agate worth at least 1,000 gp, which the spell consumes"),
+        components: Components::vsm("an agate worth at least 1,000 gp, which the spell consumes").with_cost("agate", 1000, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -563,7 +563,7 @@ pub fn register_level5(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Abjuration,
         casting_time: CastingTime::Action,
         range: SpellRange::Touch,
-        components: Components::vsm("diamond dust worth at least 100 gp, which the spell consumes"),
+        components: Components::vsm("diamond dust worth at least 100 gp, which the spell consumes").with_cost("diamond dust", 100, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -585,7 +585,7 @@ pub fn register_level5(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Evocation,
         casting_time: CastingTime::Hours(24),
         range: SpellRange::Touch,
-        components: Components::vsm("herbs, oils, and incense worth at least 1,000 gp, which the spell consumes"),
+        components: Components::vsm("herbs, oils, and incense worth at least 1,000 gp, which the spell consumes").with_cost("incense", 1000, true),
         duration: SpellDuration::UntilDispelled,
         concentration: false,
         ritual: false,
@@ -629,7 +629,7 @@ pub fn register_level5(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Divination,
         casting_time: CastingTime::Minutes(10),
         range: SpellRange::Self_,
-        components: Components::vsm("incense worth at least 250 gp, which the spell consumes, and four ivory strips worth at least 50 gp each"),
+        components: Components::vsm("incense worth at least 250 gp, which the spell consumes, and four ivory strips worth at least 50 gp each").with_cost("incense", 250, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -717,7 +717,7 @@ pub fn register_level5(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Abjuration,
         casting_time: CastingTime::Hours(1),
         range: SpellRange::Feet(60),
-        components: Components::vsm("a jewel worth at least 1,000 gp, which the spell consumes"),
+        components: Components::vsm("a jewel worth at least 1,000 gp, which the spell consumes").with_cost("jewel", 1000, true),
         duration: SpellDuration::Hours(24),
         concentration: false,
         ritual: false,
@@ -739,7 +739,7 @@ pub fn register_level5(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Necromancy,
         casting_time: CastingTime::Hours(1),
         range: SpellRange::Touch,
-        components: Components::vsm("a diamond worth at least 500 gp, which the spell consumes"),
+        components: Components::vsm("a diamond worth at least 500 gp, which the spell consumes").with_cost("diamond", 500, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -827,7 +827,7 @@ pub fn register_level5(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Conjuration,
         casting_time: CastingTime::Minutes(1),
         range: SpellRange::Feet(10),
-        components: Components::vsm("rare chalks and inks infused with precious gems worth 50 gp, which the spell consumes"),
+        components: Components::vsm("rare chalks and inks infused with precious gems worth 50 gp, which the spell consumes").with_cost("chalk", 50, true),
         duration: SpellDuration::Rounds(1),
         concentration: false,
         ritual: false,
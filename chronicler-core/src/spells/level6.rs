@@ -211,7 +211,7 @@ pub fn register_level6(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Necromancy,
         casting_time: CastingTime::Action,
         range: SpellRange::Feet(150),
-        components: Components::vsm("the powder of a crushed black pearl worth at least 500 gp"),
+        components: Components::vsm("the powder of a crushed black pearl worth at least 500 gp").with_cost("black pearl", 500, false),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -255,7 +255,7 @@ pub fn register_level6(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Necromancy,
         casting_time: CastingTime::Minutes(1),
         range: SpellRange::Feet(10),
-        components: Components::vsm("one clay pot filled with grave dirt, one clay pot filled with brackish water, and one 150 gp black onyx stone for each corpse"),
+        components: Components::vsm("one clay pot filled with grave dirt, one clay pot filled with brackish water, and one 150 gp black onyx stone for each corpse").with_cost("onyx", 150, false),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -277,7 +277,7 @@ pub fn register_level6(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Conjuration,
         casting_time: CastingTime::Minutes(1),
         range: SpellRange::Touch,
-        components: Components::vsm("a sapphire worth 1,000 gp"),
+        components: Components::vsm("a sapphire worth 1,000 gp").with_cost("sapphire", 1000, false),
         duration: SpellDuration::UntilDispelled,
         concentration: false,
         ritual: true,
@@ -365,7 +365,7 @@ pub fn register_level6(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Abjuration,
         casting_time: CastingTime::Minutes(10),
         range: SpellRange::Touch,
-        components: Components::vsm("a sprinkling of holy water, rare incense, and powdered ruby worth at least 1,000 gp"),
+        components: Components::vsm("a sprinkling of holy water, rare incense, and powdered ruby worth at least 1,000 gp").with_cost("ruby", 1000, false),
         duration: SpellDuration::Days(1),
         concentration: false,
         ritual: true,
@@ -387,7 +387,7 @@ pub fn register_level6(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Abjuration,
         casting_time: CastingTime::Minutes(10),
         range: SpellRange::Touch,
-        components: Components::vsm("burning incense, a small measure of brimstone and oil, a knotted string, a small amount of umber hulk blood, and a small silver rod worth at least 10 gp"),
+        components: Components::vsm("burning incense, a small measure of brimstone and oil, a knotted string, a small amount of umber hulk blood, and a small silver rod worth at least 10 gp").with_cost("silver rod", 10, false),
         duration: SpellDuration::Hours(24),
         concentration: false,
         ritual: false,
@@ -409,7 +409,7 @@ pub fn register_level6(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Conjuration,
         casting_time: CastingTime::Minutes(10),
         range: SpellRange::Feet(30),
-        components: Components::vsm("a gem-encrusted bowl worth at least 1,000 gp, which the spell consumes"),
+        components: Components::vsm("a gem-encrusted bowl worth at least 1,000 gp, which the spell consumes").with_cost("bowl", 1000, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -541,7 +541,7 @@ pub fn register_level6(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Illusion,
         casting_time: CastingTime::Action,
         range: SpellRange::Feet(120),
-        components: Components::vsm("a bit of fleece and jade dust worth at least 25 gp"),
+        components: Components::vsm("a bit of fleece and jade dust worth at least 25 gp").with_cost("jade dust", 25, false),
         duration: SpellDuration::UntilDispelled,
         concentration: false,
         ritual: false,
@@ -585,7 +585,7 @@ pub fn register_level6(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Divination,
         casting_time: CastingTime::Action,
         range: SpellRange::Touch,
-        components: Components::vsm("an ointment for the eyes that costs 25 gp; is made from mushroom powder, saffron, and fat; and is consumed by the spell"),
+        components: Components::vsm("an ointment for the eyes that costs 25 gp; is made from mushroom powder, saffron, and fat; and is consumed by the spell").with_cost("ointment", 25, true),
         duration: SpellDuration::Hours(1),
         concentration: false,
         ritual: false,
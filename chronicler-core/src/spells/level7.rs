@@ -79,7 +79,7 @@ pub fn register_level7(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Conjuration,
         casting_time: CastingTime::Action,
         range: SpellRange::Touch,
-        components: Components::vsm("a forked, metal rod worth at least 250 gp, attuned to a particular plane of existence"),
+        components: Components::vsm("a forked, metal rod worth at least 250 gp, attuned to a particular plane of existence").with_cost("rod", 250, false),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -145,7 +145,7 @@ pub fn register_level7(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Necromancy,
         casting_time: CastingTime::Hours(1),
         range: SpellRange::Touch,
-        components: Components::vsm("a diamond worth at least 1,000 gp, which the spell consumes"),
+        components: Components::vsm("a diamond worth at least 1,000 gp, which the spell consumes").with_cost("diamond", 1000, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -233,7 +233,7 @@ pub fn register_level7(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Evocation,
         casting_time: CastingTime::Action,
         range: SpellRange::Feet(100),
-        components: Components::vsm("ruby dust worth 1,500 gp"),
+        components: Components::vsm("ruby dust worth 1,500 gp").with_cost("ruby dust", 1500, false),
         duration: SpellDuration::Hours(1),
         concentration: false,
         ritual: false,
@@ -299,7 +299,7 @@ pub fn register_level7(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Evocation,
         casting_time: CastingTime::Action,
         range: SpellRange::Feet(60),
-        components: Components::vsm("a miniature platinum sword with a grip and pommel of copper and zinc, worth 250 gp"),
+        components: Components::vsm("a miniature platinum sword with a grip and pommel of copper and zinc, worth 250 gp").with_cost("platinum sword", 250, false),
         duration: SpellDuration::Minutes(1),
         concentration: true,
         ritual: false,
@@ -387,7 +387,7 @@ pub fn register_level7(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Illusion,
         casting_time: CastingTime::Hours(12),
         range: SpellRange::Touch,
-        components: Components::vsm("snow or ice in quantities sufficient to make a life-size copy of the duplicated creature; some hair, fingernail clippings, or other piece of that creature's body placed inside the snow or ice; and powdered ruby worth 1,500 gp, sprinkled over the duplicate and consumed by the spell"),
+        components: Components::vsm("snow or ice in quantities sufficient to make a life-size copy of the duplicated creature; some hair, fingernail clippings, or other piece of that creature's body placed inside the snow or ice; and powdered ruby worth 1,500 gp, sprinkled over the duplicate and consumed by the spell").with_cost("ruby", 1500, true),
         duration: SpellDuration::UntilDispelled,
         concentration: false,
         ritual: false,
@@ -409,7 +409,7 @@ pub fn register_level7(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Abjuration,
         casting_time: CastingTime::Minutes(1),
         range: SpellRange::Touch,
-        components: Components::vsm("mercury, phosphorus, and powdered diamond and opal with a total value of at least 1,000 gp, which the spell consumes"),
+        components: Components::vsm("mercury, phosphorus, and powdered diamond and opal with a total value of at least 1,000 gp, which the spell consumes").with_cost("diamond", 1000, true),
         duration: SpellDuration::UntilDispelled,
         concentration: false,
         ritual: false,
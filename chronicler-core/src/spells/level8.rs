@@ -167,7 +167,7 @@ pub fn register_level8(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Necromancy,
         casting_time: CastingTime::Hours(1),
         range: SpellRange::Touch,
-        components: Components::vsm("a diamond worth at least 1,000 gp and at least 1 cubic inch of flesh of the creature that is to be cloned, which the spell consumes, and a vessel worth at least 2,000 gp that has a sealable lid and is large enough to hold the creature being cloned"),
+        components: Components::vsm("a diamond worth at least 1,000 gp and at least 1 cubic inch of flesh of the creature that is to be cloned, which the spell consumes, and a vessel worth at least 2,000 gp that has a sealable lid and is large enough to hold the creature being cloned").with_cost("diamond", 1000, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -277,7 +277,7 @@ pub fn register_level8(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Abjuration,
         casting_time: CastingTime::Action,
         range: SpellRange::Self_,
-        components: Components::vsm("a tiny reliquary worth at least 1,000 gp containing a sacred relic, such as a scrap of cloth from a saint's robe or a piece of parchment from a religious text"),
+        components: Components::vsm("a tiny reliquary worth at least 1,000 gp containing a sacred relic, such as a scrap of cloth from a saint's robe or a piece of parchment from a religious text").with_cost("reliquary", 1000, false),
         duration: SpellDuration::Minutes(1),
         concentration: true,
         ritual: false,
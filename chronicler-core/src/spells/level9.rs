@@ -35,7 +35,7 @@ pub fn register_level9(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Conjuration,
         casting_time: CastingTime::Action,
         range: SpellRange::Feet(60),
-        components: Components::vsm("a diamond worth at least 5,000 gp"),
+        components: Components::vsm("a diamond worth at least 5,000 gp").with_cost("diamond", 5000, false),
         duration: SpellDuration::Minutes(1),
         concentration: true,
         ritual: false,
@@ -167,7 +167,7 @@ pub fn register_level9(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Necromancy,
         casting_time: CastingTime::Hours(1),
         range: SpellRange::Touch,
-        components: Components::vsm("a sprinkle of holy water and diamonds worth at least 25,000 gp, which the spell consumes"),
+        components: Components::vsm("a sprinkle of holy water and diamonds worth at least 25,000 gp, which the spell consumes").with_cost("diamond", 25000, true),
         duration: SpellDuration::Instantaneous,
         concentration: false,
         ritual: false,
@@ -211,7 +211,7 @@ pub fn register_level9(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Necromancy,
         casting_time: CastingTime::Hours(1),
         range: SpellRange::Feet(10),
-        components: Components::vsm("for each creature you affect with this spell, you must provide one jacinth worth at least 1,000 gp and one ornately carved bar of silver worth at least 100 gp, all of which the spell consumes"),
+        components: Components::vsm("for each creature you affect with this spell, you must provide one jacinth worth at least 1,000 gp and one ornately carved bar of silver worth at least 100 gp, all of which the spell consumes").with_cost("jacinth", 1000, true),
         duration: SpellDuration::Special,
         concentration: false,
         ritual: false,
@@ -277,7 +277,7 @@ pub fn register_level9(db: &mut HashMap<String, SpellData>) {
         school: SpellSchool::Transmutation,
         casting_time: CastingTime::Action,
         range: SpellRange::Self_,
-        components: Components::vsm("a jade circlet worth at least 1,500 gp, which you must place on your head before you cast the spell"),
+        components: Components::vsm("a jade circlet worth at least 1,500 gp, which you must place on your head before you cast the spell").with_cost("jade circlet", 1500, false),
         duration: SpellDuration::Hours(1),
         concentration: true,
         ritual: false,
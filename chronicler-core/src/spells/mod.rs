@@ -17,9 +17,9 @@ mod types;
 
 // Re-export all public types
 pub use types::{
-    AreaOfEffect, CastingTime, Components, DamageScaling, SpellAttackType, SpellClass, SpellData,
-    SpellDuration, SpellRange, SpellSchool,
+    AreaOfEffect, CastingTime, Components, DamageScaling, MaterialCost, SpellAttackType,
+    SpellClass, SpellData, SpellDuration, SpellRange, SpellSchool,
 };
 
 // Re-export database functions
-pub use database::{all_spells, get_spell, spells_by_level, spells_for_class};
+pub use database::{all_spells, find_spell, get_spell, spells_by_level, spells_for_class};
@@ -1,7 +1,7 @@
 //! Type definitions for spells and spellcasting mechanics.
 
 use crate::rules::DamageType;
-use crate::world::Ability;
+use crate::world::{Ability, AcBonus, ActionType, CharacterClass, DurationTiming, SpeedBonus};
 use serde::{Deserialize, Serialize};
 
 /// Schools of magic in D&D.
@@ -52,6 +52,18 @@ impl CastingTime {
             CastingTime::Hours(h) => format!("{} hour{}", h, if *h == 1 { "" } else { "s" }),
         }
     }
+
+    /// The action spent casting the spell in combat. Spells taking minutes
+    /// or hours occupy the caster's action every turn.
+    pub fn action_type(&self) -> ActionType {
+        match self {
+            CastingTime::BonusAction => ActionType::BonusAction,
+            CastingTime::Reaction(_) => ActionType::Reaction,
+            CastingTime::Action | CastingTime::Minutes(_) | CastingTime::Hours(_) => {
+                ActionType::Action
+            }
+        }
+    }
 }
 
 /// Range of a spell.
@@ -90,6 +102,21 @@ pub struct Components {
     pub verbal: bool,
     pub somatic: bool,
     pub material: Option<String>,
+    /// A material with a gold cost, which a focus or component pouch can't
+    /// replace. Only costly materials are enforced when casting.
+    #[serde(default)]
+    pub cost: Option<MaterialCost>,
+}
+
+/// A costly material component.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaterialCost {
+    /// Word identifying the component in an inventory item name (e.g. "diamond").
+    pub item: String,
+    /// Minimum value in gold pieces.
+    pub value_gp: u32,
+    /// Whether casting the spell uses the component up.
+    pub consumed: bool,
 }
 
 impl Components {
@@ -98,6 +125,7 @@ impl Components {
             verbal: true,
             somatic: false,
             material: None,
+            cost: None,
         }
     }
 
@@ -106,6 +134,7 @@ impl Components {
             verbal: true,
             somatic: true,
             material: None,
+            cost: None,
         }
     }
 
@@ -114,6 +143,7 @@ impl Components {
             verbal: true,
             somatic: true,
             material: Some(material.to_string()),
+            cost: None,
         }
     }
 
@@ -122,6 +152,7 @@ impl Components {
             verbal: false,
             somatic: true,
             material: None,
+            cost: None,
         }
     }
 
@@ -130,9 +161,20 @@ impl Components {
             verbal: false,
             somatic: true,
             material: Some(material.to_string()),
+            cost: None,
         }
     }
 
+    /// Marks the material as costly, worth at least `value_gp`.
+    pub fn with_cost(mut self, item: &str, value_gp: u32, consumed: bool) -> Self {
+        self.cost = Some(MaterialCost {
+            item: item.to_string(),
+            value_gp,
+            consumed,
+        });
+        self
+    }
+
     pub fn description(&self) -> String {
         let mut parts = Vec::new();
         if self.verbal {
@@ -249,12 +291,70 @@ pub enum SpellClass {
     Wizard,
 }
 
+impl SpellClass {
+    /// The spell list a character class casts from, if it has one.
+    pub fn from_class(class: CharacterClass) -> Option<Self> {
+        match class {
+            CharacterClass::Bard => Some(SpellClass::Bard),
+            CharacterClass::Cleric => Some(SpellClass::Cleric),
+            CharacterClass::Druid => Some(SpellClass::Druid),
+            CharacterClass::Paladin => Some(SpellClass::Paladin),
+            CharacterClass::Ranger => Some(SpellClass::Ranger),
+            CharacterClass::Sorcerer => Some(SpellClass::Sorcerer),
+            CharacterClass::Warlock => Some(SpellClass::Warlock),
+            CharacterClass::Wizard => Some(SpellClass::Wizard),
+            CharacterClass::Barbarian
+            | CharacterClass::Fighter
+            | CharacterClass::Monk
+            | CharacterClass::Rogue => None,
+        }
+    }
+}
+
 impl SpellData {
     /// Check if this is a cantrip.
     pub fn is_cantrip(&self) -> bool {
         self.level == 0
     }
 
+    /// The bonus to AC the spell grants its target, if it grants one.
+    /// Bonuses from concentration spells last until concentration ends.
+    pub fn ac_bonus(&self) -> Option<AcBonus> {
+        match self.name.as_str() {
+            "Shield" => {
+                Some(AcBonus::new(&self.name, 5).with_duration(1, DurationTiming::StartOfTurn))
+            }
+            "Shield of Faith" => Some(AcBonus::new(&self.name, 2)),
+            _ => None,
+        }
+    }
+
+    /// The bonus to walking speed the spell grants its target, if it grants one.
+    pub fn speed_bonus(&self) -> Option<SpeedBonus> {
+        match self.name.as_str() {
+            "Longstrider" => Some(SpeedBonus {
+                source: self.name.clone(),
+                feet: 10,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Level and school as printed in a spell's header, e.g. "3rd-level
+    /// Evocation" or "Evocation cantrip".
+    pub fn level_and_school(&self) -> String {
+        if self.level == 0 {
+            return format!("{} cantrip", self.school.name());
+        }
+        let suffix = match self.level {
+            1 => "st",
+            2 => "nd",
+            3 => "rd",
+            _ => "th",
+        };
+        format!("{}{}-level {}", self.level, suffix, self.school.name())
+    }
+
     /// Get the number of damage dice based on caster level (for cantrips).
     pub fn cantrip_dice_count(&self, caster_level: u8) -> u8 {
         match caster_level {
@@ -3,11 +3,18 @@
 //! This module provides tools for integration testing:
 //! - `MockDm` for deterministic testing without API calls
 //! - `TestHarness` for scripted game scenarios
+//! - `with_deterministic_ids` for worlds with stable ids
 //! - Assertion helpers for verifying game state
 
-use crate::dm::{DmResponse, StoryMemory};
-use crate::rules::{Intent, RulesEngine};
+use crate::dm::{
+    invalid_tool_call_message, parse_tool_call, DmBackend, DmError, DmMemory, DmResponse,
+    StoryMemory, ToolMetrics, TurnContext, DEFAULT_MAX_TOOL_ROUNDS,
+};
+use crate::rules::{narrate_outcomes, EffectOutcome, Intent, Resolution, RulesEngine};
 use crate::world::{create_sample_fighter, Character, GameWorld, NarrativeType};
+use claude::ToolResult;
+use futures::future::BoxFuture;
+use serde_json::Value;
 
 /// A mock DM that returns scripted responses.
 ///
@@ -19,8 +26,16 @@ pub struct MockDm {
     response_index: usize,
     /// Story memory (shared with real DM API).
     story_memory: StoryMemory,
+    /// Conversation memory (shared with real DM API).
+    memory: DmMemory,
+    /// Counts of the scripted tool calls.
+    tool_metrics: ToolMetrics,
     /// Rules engine for resolving intents.
     rules: RulesEngine,
+    /// Tool results for the most recent turn's tool calls.
+    tool_results: Vec<ToolResult>,
+    /// Maximum rounds of tool results in one turn.
+    max_tool_rounds: usize,
 }
 
 /// A scripted response from the mock DM.
@@ -30,6 +45,8 @@ pub struct MockResponse {
     pub narrative: String,
     /// Intents to execute (will be resolved by rules engine).
     pub intents: Vec<Intent>,
+    /// Raw tool calls (name and JSON input) parsed as the real DM would.
+    pub tool_calls: Vec<(String, Value)>,
 }
 
 impl MockResponse {
@@ -38,6 +55,7 @@ impl MockResponse {
         Self {
             narrative: text.into(),
             intents: Vec::new(),
+            tool_calls: Vec::new(),
         }
     }
 
@@ -46,6 +64,22 @@ impl MockResponse {
         Self {
             narrative: text.into(),
             intents,
+            tool_calls: Vec::new(),
+        }
+    }
+
+    /// Create a response with raw tool calls.
+    ///
+    /// Calls that fail to parse produce error tool results instead of intents.
+    /// The turn continues with the next scripted response.
+    pub fn with_tool_calls(text: impl Into<String>, tool_calls: Vec<(&str, Value)>) -> Self {
+        Self {
+            narrative: text.into(),
+            intents: Vec::new(),
+            tool_calls: tool_calls
+                .into_iter()
+                .map(|(name, input)| (name.to_string(), input))
+                .collect(),
         }
     }
 }
@@ -57,49 +91,126 @@ impl MockDm {
             responses,
             response_index: 0,
             story_memory: StoryMemory::new(),
+            memory: DmMemory::new(),
+            tool_metrics: ToolMetrics::new(),
             rules: RulesEngine::new(),
+            tool_results: Vec::new(),
+            max_tool_rounds: DEFAULT_MAX_TOOL_ROUNDS,
         }
     }
 
-    /// Process input and return the next scripted response.
+    /// Limit how many rounds of tool results one turn may consume.
+    pub fn with_max_tool_rounds(mut self, rounds: usize) -> Self {
+        self.max_tool_rounds = rounds;
+        self
+    }
+
+    /// Process input and return the scripted responses for one turn.
     ///
-    /// Intents are resolved through the real rules engine.
+    /// Like the real DM, a response with tool calls continues the turn: its
+    /// tool results are produced and the next scripted response is consumed,
+    /// until a response makes no tool calls or the round limit is reached.
+    /// Intents and tool calls are resolved through the real rules engine.
     pub fn process_input(&mut self, _input: &str, world: &mut GameWorld) -> DmResponse {
         // Advance story memory turn
         self.story_memory.advance_turn();
 
-        // Get next response or return default
-        let response = if self.response_index < self.responses.len() {
-            let r = self.responses[self.response_index].clone();
-            self.response_index += 1;
-            r
-        } else {
-            MockResponse::narrative("The DM has no more scripted responses.")
-        };
-
-        // Resolve intents through rules engine
+        let mut narrative = String::new();
+        let mut intents = Vec::new();
         let mut all_effects = Vec::new();
+        let mut all_outcomes = Vec::new();
         let mut all_resolutions = Vec::new();
-
-        for intent in &response.intents {
-            let resolution = self.rules.resolve(world, intent.clone());
-            crate::rules::apply_effects(world, &resolution.effects);
-            all_effects.extend(resolution.effects.clone());
-            all_resolutions.push(resolution);
+        self.tool_results.clear();
+
+        let mut tool_rounds = 0;
+        let mut tool_round_limit_reached = false;
+        loop {
+            let response = self.next_response();
+
+            if !response.narrative.is_empty() {
+                if !narrative.is_empty() {
+                    narrative.push('\n');
+                }
+                narrative.push_str(&response.narrative);
+            }
+
+            // Resolve intents through rules engine
+            for intent in response.intents {
+                let (resolution, outcomes) = self.resolve(world, intent.clone());
+                all_effects.extend(resolution.effects.clone());
+                all_outcomes.extend(outcomes);
+                all_resolutions.push(resolution);
+                intents.push(intent);
+            }
+
+            // Only tool calls continue the turn
+            if response.tool_calls.is_empty() {
+                break;
+            }
+            tool_rounds += 1;
+            if tool_rounds > self.max_tool_rounds {
+                tool_round_limit_reached = true;
+                break;
+            }
+
+            // Parse tool calls as the real DM would, answering each with a tool result
+            for (name, input) in &response.tool_calls {
+                let result = match parse_tool_call(name, input, world) {
+                    Some(intent) => {
+                        let (resolution, outcomes) = self.resolve(world, intent.clone());
+                        all_effects.extend(resolution.effects.clone());
+                        intents.push(intent);
+                        let result =
+                            ToolResult::success(narrate_outcomes(&resolution.narrative, &outcomes));
+                        all_outcomes.extend(outcomes);
+                        all_resolutions.push(resolution);
+                        result
+                    }
+                    None => ToolResult::error(invalid_tool_call_message(name, input, world)),
+                };
+                self.tool_metrics.record(name, !result.is_error);
+                self.tool_results.push(result);
+            }
         }
 
         // Add narrative to world
-        world.add_narrative(response.narrative.clone(), NarrativeType::DmNarration);
+        world.add_narrative(narrative.clone(), NarrativeType::DmNarration);
 
         DmResponse {
-            narrative: response.narrative,
-            intents: response.intents,
+            narrative,
+            intents,
             effects: all_effects,
+            outcomes: all_outcomes,
             resolutions: all_resolutions,
             inferred_state_changes: Vec::new(),
+            tool_round_limit_reached,
+        }
+    }
+
+    /// Get the next scripted response, or a default once the script runs out.
+    fn next_response(&mut self) -> MockResponse {
+        if self.response_index < self.responses.len() {
+            let r = self.responses[self.response_index].clone();
+            self.response_index += 1;
+            r
+        } else {
+            MockResponse::narrative("The DM has no more scripted responses.")
         }
     }
 
+    /// Resolve an intent, apply its effects, and record the mechanics entry.
+    fn resolve(&self, world: &mut GameWorld, intent: Intent) -> (Resolution, Vec<EffectOutcome>) {
+        let resolution = self.rules.resolve(world, intent);
+        let outcomes = crate::rules::apply_effects(world, &resolution.effects);
+        world.add_narrative(resolution.narrative.clone(), NarrativeType::Mechanics);
+        (resolution, outcomes)
+    }
+
+    /// Tool results for the most recent turn's tool calls, in call order.
+    pub fn last_tool_results(&self) -> &[ToolResult] {
+        &self.tool_results
+    }
+
     /// Get the story memory.
     pub fn story_memory(&self) -> &StoryMemory {
         &self.story_memory
@@ -121,6 +232,56 @@ impl MockDm {
     }
 }
 
+impl DmBackend for MockDm {
+    fn respond<'a>(
+        &'a mut self,
+        turn: TurnContext<'a>,
+    ) -> BoxFuture<'a, Result<DmResponse, DmError>> {
+        let TurnContext {
+            input,
+            world,
+            on_text,
+            on_effect,
+        } = turn;
+        let response = self.process_input(input, world);
+        if let Some(on_effect) = on_effect {
+            response.effects.iter().for_each(on_effect);
+        }
+        if let Some(on_text) = on_text {
+            on_text(&response.narrative);
+        }
+        Box::pin(async move { Ok(response) })
+    }
+
+    fn memory(&self) -> &DmMemory {
+        &self.memory
+    }
+
+    fn memory_mut(&mut self) -> &mut DmMemory {
+        &mut self.memory
+    }
+
+    fn story_memory(&self) -> &StoryMemory {
+        &self.story_memory
+    }
+
+    fn story_memory_mut(&mut self) -> &mut StoryMemory {
+        &mut self.story_memory
+    }
+
+    fn tool_metrics(&self) -> &ToolMetrics {
+        &self.tool_metrics
+    }
+
+    fn rules(&self) -> Option<&RulesEngine> {
+        Some(&self.rules)
+    }
+
+    fn rules_mut(&mut self) -> Option<&mut RulesEngine> {
+        Some(&mut self.rules)
+    }
+}
+
 /// Test harness for running game scenarios.
 pub struct TestHarness {
     /// The mock DM.
@@ -219,6 +380,29 @@ impl Default for TestHarness {
     }
 }
 
+// ============================================================================
+// Deterministic Ids
+// ============================================================================
+
+/// Run `f` with ids handed out in sequence instead of at random, so worlds
+/// built inside it have the same ids every run.
+///
+/// The sequence restarts at each call and only covers the current thread;
+/// ids outside `f` stay random.
+pub fn with_deterministic_ids<R>(f: impl FnOnce() -> R) -> R {
+    /// Puts back the previous sequence even if `f` panics.
+    struct Restore(Option<u128>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            crate::world::swap_id_sequence(self.0);
+        }
+    }
+
+    let _restore = Restore(crate::world::swap_id_sequence(Some(1)));
+    f()
+}
+
 // ============================================================================
 // Assertion Helpers
 // ============================================================================
@@ -272,6 +456,35 @@ mod tests {
     use crate::rules::Intent;
     use crate::world::Skill;
 
+    #[test]
+    fn test_deterministic_ids_build_matching_worlds() {
+        let build = || {
+            with_deterministic_ids(|| {
+                let mut world = GameWorld::new("Snapshot", create_sample_fighter("Roland"));
+                let npc = crate::world::NPC::new("Bram");
+                world.npcs.insert(npc.id, npc);
+                world
+            })
+        };
+        let (first, second) = (build(), build());
+
+        assert_eq!(first.session_id, second.session_id);
+        assert_eq!(first.player_character.id, second.player_character.id);
+        assert_eq!(first.current_location.id, second.current_location.id);
+        let npc_ids = |world: &GameWorld| world.npcs.keys().copied().collect::<Vec<_>>();
+        assert_eq!(npc_ids(&first), npc_ids(&second));
+
+        // Outside the closure ids are random again
+        assert_ne!(
+            crate::world::CharacterId::new(),
+            crate::world::CharacterId::new()
+        );
+        assert_ne!(
+            build().session_id,
+            GameWorld::new("Random", first.player_character).session_id
+        );
+    }
+
     #[test]
     fn test_mock_dm_basic() {
         let mut harness = TestHarness::new();
@@ -304,6 +517,123 @@ mod tests {
         assert_eq!(harness.player_hp().0, initial_hp.0 - 5);
     }
 
+    #[test]
+    fn test_invalid_tool_call_returns_error_result_for_retry() {
+        let mut harness = TestHarness::new();
+        let initial_hp = harness.player_hp();
+
+        harness
+            .expect_response(MockResponse::with_tool_calls(
+                "The goblin swings...",
+                vec![(
+                    "apply_damage",
+                    serde_json::json!({"amount": 0, "damage_type": "slashing", "source": "Goblin"}),
+                )],
+            ))
+            // The DM reacts to the error by retrying with a corrected call
+            .expect_response(MockResponse::with_tool_calls(
+                "",
+                vec![(
+                    "apply_damage",
+                    serde_json::json!({"amount": 3, "damage_type": "slashing", "source": "Goblin"}),
+                )],
+            ))
+            .expect_narrative("...and nicks your arm.");
+
+        let response = harness.input("I dodge");
+
+        let results = harness.dm.last_tool_results();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_error);
+        assert!(results[0].text().contains("apply_damage"));
+        assert!(results[0].text().contains("amounts are positive"));
+        assert!(!results[1].is_error);
+        assert_eq!(response.intents.len(), 1);
+        assert_eq!(harness.player_hp().0, initial_hp.0 - 3);
+        assert_eq!(
+            response.narrative,
+            "The goblin swings...\n...and nicks your arm."
+        );
+    }
+
+    #[test]
+    fn test_turn_continues_after_tool_call_until_narration() {
+        let mut harness = TestHarness::new();
+
+        harness
+            .expect_response(MockResponse::with_tool_calls(
+                "You listen at the door.",
+                vec![(
+                    "skill_check",
+                    serde_json::json!({"skill": "perception", "dc": 12, "description": "Listening"}),
+                )],
+            ))
+            .expect_narrative("You hear muffled voices beyond.")
+            .expect_narrative("Next turn.");
+
+        let response = harness.input("I listen");
+
+        assert_eq!(response.resolutions.len(), 1);
+        assert_eq!(response.outcomes.len(), response.effects.len());
+        assert!(!harness.dm.last_tool_results()[0].is_error);
+        assert_eq!(
+            response.narrative,
+            "You listen at the door.\nYou hear muffled voices beyond."
+        );
+        assert_eq!(harness.input("Then?").narrative, "Next turn.");
+    }
+
+    #[test]
+    fn test_tool_rounds_are_bounded() {
+        let mut harness = TestHarness::new();
+        harness.dm = MockDm::new(Vec::new()).with_max_tool_rounds(2);
+        for _ in 0..5 {
+            harness.expect_response(MockResponse::with_tool_calls(
+                "Roll again.",
+                vec![("roll_dice", serde_json::json!({"notation": "1d20"}))],
+            ));
+        }
+
+        let response = harness.input("I keep rolling");
+
+        // Two rounds run; the third response's calls are never executed
+        assert_eq!(harness.dm.last_tool_results().len(), 2);
+        assert_eq!(response.narrative.matches("Roll again.").count(), 3);
+        assert!(response.tool_round_limit_reached);
+    }
+
+    #[test]
+    fn test_damage_turn_records_mechanics_entry() {
+        let mut harness = TestHarness::new();
+
+        harness.expect_response(MockResponse::with_intents(
+            "The goblin stabs you!",
+            vec![Intent::Damage {
+                target_id: harness.world.player_character.id,
+                amount: 5,
+                damage_type: DamageType::Piercing,
+                source: "Goblin dagger".to_string(),
+            }],
+        ));
+
+        let response = harness.input("I approach the goblin");
+
+        let history = &harness.world.narrative_history;
+        let mechanics: Vec<_> = history
+            .iter()
+            .filter(|e| matches!(e.entry_type, NarrativeType::Mechanics))
+            .collect();
+        assert_eq!(mechanics.len(), 1);
+        assert_eq!(mechanics[0].content, response.resolutions[0].narrative);
+
+        let narration = history
+            .iter()
+            .find(|e| matches!(e.entry_type, NarrativeType::DmNarration))
+            .expect("DM narration recorded");
+        assert_eq!(narration.content, "The goblin stabs you!");
+        assert_ne!(mechanics[0].content, narration.content);
+    }
+
     #[test]
     fn test_mock_dm_skill_check() {
         let mut harness = TestHarness::new();
@@ -346,6 +676,7 @@ mod tests {
                         max_hp: 10,
                         armor_class: 10,
                         initiative_modifier: 0,
+                        resistances: Vec::new(),
                     },
                     CombatantInit {
                         id: CharacterId::new(),
@@ -356,6 +687,7 @@ mod tests {
                         max_hp: 7,
                         armor_class: 13,        // Goblin AC from SRD
                         initiative_modifier: 2, // Goblin DEX +2 from SRD
+                        resistances: Vec::new(),
                     },
                 ],
             }],
@@ -503,7 +835,11 @@ mod tests {
         );
 
         // 7. Test the relevance context building (data flow verification)
-        let relevance_context = harness.dm.story_memory().build_consequences_for_relevance();
+        let relevance_context = harness
+            .dm
+            .story_memory()
+            .build_consequences_for_relevance()
+            .text;
         assert!(
             !relevance_context.is_empty(),
             "Relevance context should not be empty"
@@ -44,6 +44,14 @@ impl Ability {
         }
     }
 
+    /// Parse a full ability name or its abbreviation, ignoring case.
+    pub fn from_name(name: &str) -> Option<Ability> {
+        let name = name.trim();
+        Ability::all().into_iter().find(|a| {
+            a.name().eq_ignore_ascii_case(name) || a.abbreviation().eq_ignore_ascii_case(name)
+        })
+    }
+
     pub fn all() -> [Ability; 6] {
         [
             Ability::Strength,
@@ -102,6 +102,26 @@ impl Background {
         }
     }
 
+    /// Returns the equipment granted by this background. Items not in the
+    /// standard item database are carried as plain gear.
+    pub fn starting_equipment(&self) -> Vec<&'static str> {
+        match self {
+            Background::Acolyte => vec!["Holy Symbol", "Prayer Book", "Vestments"],
+            Background::Charlatan => vec!["Fine Clothes", "Disguise Kit"],
+            Background::Criminal => vec!["Crowbar", "Dark Common Clothes"],
+            Background::Entertainer => vec!["Musical Instrument", "Costume"],
+            Background::FolkHero => vec!["Artisan's Tools", "Shovel", "Common Clothes"],
+            Background::GuildArtisan => vec!["Artisan's Tools", "Letter of Introduction"],
+            Background::Hermit => vec!["Herbalism Kit", "Winter Blanket", "Common Clothes"],
+            Background::Noble => vec!["Fine Clothes", "Signet Ring", "Scroll of Pedigree"],
+            Background::Outlander => vec!["Hunting Trap", "Traveler's Clothes"],
+            Background::Sage => vec!["Ink and Quill", "Common Clothes"],
+            Background::Sailor => vec!["Rope (50 feet)", "Lucky Charm", "Common Clothes"],
+            Background::Soldier => vec!["Insignia of Rank", "Gaming Set", "Common Clothes"],
+            Background::Urchin => vec!["Map of Home City", "Common Clothes"],
+        }
+    }
+
     pub fn all() -> &'static [Background] {
         &[
             Background::Acolyte,
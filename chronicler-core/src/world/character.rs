@@ -4,10 +4,13 @@
 //! for non-player characters, along with supporting types like Race and Disposition.
 
 use super::{
-    Ability, AbilityScores, ActiveCondition, ArmorClass, ArmorType, Background, CharacterId,
-    ClassLevel, ClassResources, Condition, DeathSaves, Equipment, Feature, HitDice, HitPoints,
-    Inventory, LocationId, ProficiencyLevel, RaceType, Skill, Speed, SpellcastingData,
+    Ability, AbilityScores, AcBonus, ActiveCondition, ArmorClass, ArmorType, Background,
+    CharacterClass, CharacterId, ClassLevel, ClassResources, Concentration, Condition, DeathSaves,
+    EncumbranceLevel, Equipment, Feature, FightingStyle, GameTime, HitDice, HitPoints, Inventory,
+    LightLevel, LocationId, ProficiencyLevel, RaceType, Resistance, Skill, Speed, SpeedBonus,
+    SpellcastingData, TimeRange,
 };
+use crate::dice::Advantage;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
@@ -43,6 +46,11 @@ pub struct Character {
 
     // Combat
     pub armor_class: ArmorClass,
+    /// Armor class taken as final, e.g. from an imported character sheet.
+    /// Dropped when armor or a shield is put on or taken off, after which
+    /// AC is worked out from equipment again.
+    #[serde(default)]
+    pub ac_override: Option<u8>,
     pub speed: Speed,
     pub conditions: Vec<ActiveCondition>,
 
@@ -72,6 +80,53 @@ pub struct Character {
 
     // Player backstory
     pub backstory: Option<String>,
+
+    /// Unspent Ability Score Improvement points (2 per improvement earned).
+    #[serde(default)]
+    pub asi_points: u8,
+
+    /// Known spells that may still be swapped for another: one, on gaining
+    /// a level in a class that learns a fixed number of spells, until used
+    /// or the next level.
+    #[serde(default)]
+    pub spell_swaps: u8,
+
+    /// Spells a Wizard may still add to their spellbook without paying for
+    /// them: two for each Wizard level gained.
+    #[serde(default)]
+    pub free_spellbook_spells: u8,
+
+    /// Fighting Styles chosen through class features.
+    #[serde(default)]
+    pub fighting_styles: HashSet<FightingStyle>,
+
+    /// Darkvision range in feet (0 for none).
+    #[serde(default)]
+    pub darkvision_range: u8,
+
+    /// Damage resistances from race, features, or items.
+    #[serde(default)]
+    pub resistances: Vec<Resistance>,
+
+    /// Conditions that can't be applied to this character.
+    #[serde(default)]
+    pub condition_immunities: HashSet<Condition>,
+
+    /// Ally whose Help grants advantage on the next ability check.
+    #[serde(default)]
+    pub helped_by: Option<String>,
+
+    /// Spell currently being concentrated on.
+    #[serde(default)]
+    pub concentration: Option<Concentration>,
+
+    /// Temporary walking speed boosts from spells and items.
+    #[serde(default)]
+    pub speed_bonuses: Vec<SpeedBonus>,
+
+    /// Temporary AC boosts from spells.
+    #[serde(default)]
+    pub ac_bonuses: Vec<AcBonus>,
 }
 
 impl Character {
@@ -87,6 +142,7 @@ impl Character {
             hit_dice: HitDice::new(),
             death_saves: DeathSaves::default(),
             armor_class: ArmorClass::default(),
+            ac_override: None,
             speed: Speed::default(),
             conditions: Vec::new(),
             classes: Vec::new(),
@@ -112,6 +168,17 @@ impl Character {
             background: Background::Soldier,
             background_name: "Soldier".to_string(),
             backstory: None,
+            asi_points: 0,
+            spell_swaps: 0,
+            free_spellbook_spells: 0,
+            fighting_styles: HashSet::new(),
+            darkvision_range: 0,
+            resistances: Vec::new(),
+            condition_immunities: HashSet::new(),
+            helped_by: None,
+            concentration: None,
+            speed_bonuses: Vec::new(),
+            ac_bonuses: Vec::new(),
         }
     }
 
@@ -128,21 +195,40 @@ impl Character {
     }
 
     pub fn initiative_modifier(&self) -> i8 {
-        self.ability_scores.modifier(Ability::Dexterity)
+        self.effective_ability_scores().modifier(Ability::Dexterity)
+    }
+
+    /// Ability scores currently in effect.
+    ///
+    /// While in Wild Shape, physical scores come from the beast form and
+    /// mental scores stay the character's own.
+    pub fn effective_ability_scores(&self) -> AbilityScores {
+        match self.class_resources.wild_shape_stats {
+            Some(ref stats) => stats.apply_to(&self.ability_scores),
+            None => self.ability_scores.clone(),
+        }
     }
 
     pub fn skill_modifier(&self, skill: Skill) -> i8 {
-        let ability_mod = self.ability_scores.modifier(skill.ability());
-        let proficiency = self
-            .skill_proficiencies
-            .get(&skill)
-            .copied()
-            .unwrap_or(ProficiencyLevel::None);
+        let ability_mod = self.effective_ability_scores().modifier(skill.ability());
+        let proficiency = match self.skill_proficiencies.get(&skill).copied() {
+            Some(level) if level != ProficiencyLevel::None => level,
+            // Jack of All Trades adds half proficiency to untrained skills
+            _ if self.has_jack_of_all_trades() => ProficiencyLevel::Half,
+            _ => ProficiencyLevel::None,
+        };
         ability_mod + proficiency.bonus(self.proficiency_bonus())
     }
 
+    /// Bards gain Jack of All Trades at 2nd level.
+    pub fn has_jack_of_all_trades(&self) -> bool {
+        self.classes
+            .iter()
+            .any(|c| c.class == CharacterClass::Bard && c.level >= 2)
+    }
+
     pub fn saving_throw_modifier(&self, ability: Ability) -> i8 {
-        let ability_mod = self.ability_scores.modifier(ability);
+        let ability_mod = self.effective_ability_scores().modifier(ability);
         if self.saving_throw_proficiencies.contains(&ability) {
             ability_mod + self.proficiency_bonus()
         } else {
@@ -150,11 +236,87 @@ impl Character {
         }
     }
 
-    /// Calculate current AC from equipped armor and shield.
+    /// Traits and conditions that affect this character's saving throw,
+    /// each with the label it is reported under.
+    ///
+    /// `source` is what the save is against (e.g. "poison", "charm spell");
+    /// some traits only apply against particular sources.
+    pub fn save_advantage_sources(
+        &self,
+        ability: Ability,
+        source: &str,
+    ) -> Vec<(Advantage, String)> {
+        let source = source.to_lowercase();
+        let against = |words: &[&str]| words.iter().any(|w| source.contains(w));
+        let magical = against(&["magic", "spell"]);
+        let mental = matches!(
+            ability,
+            Ability::Intelligence | Ability::Wisdom | Ability::Charisma
+        );
+
+        let mut sources = Vec::new();
+        for feature in &self.features {
+            let applies = match feature.name.as_str() {
+                "Dwarven Resilience" => against(&["poison"]),
+                "Fey Ancestry" => against(&["charm"]),
+                "Brave" => against(&["fear", "fright"]),
+                "Gnome Cunning" => mental && magical,
+                "Magic Resistance" => magical,
+                "Danger Sense" => {
+                    ability == Ability::Dexterity
+                        && !self.has_condition(Condition::Blinded)
+                        && !self.has_condition(Condition::Deafened)
+                        && !self.has_condition(Condition::Incapacitated)
+                }
+                _ => false,
+            };
+            if applies {
+                sources.push((Advantage::Advantage, feature.name.clone()));
+            }
+        }
+
+        if ability == Ability::Dexterity && self.has_condition(Condition::Restrained) {
+            sources.push((Advantage::Disadvantage, "Restrained".to_string()));
+        }
+        if self
+            .conditions
+            .iter()
+            .any(|c| matches!(c.condition, Condition::Exhaustion(level) if level >= 3))
+        {
+            sources.push((Advantage::Disadvantage, "Exhaustion".to_string()));
+        }
+        sources
+    }
+
+    /// Net advantage on a saving throw from this character's own traits and
+    /// conditions. Any advantage and any disadvantage cancel out.
+    pub fn save_advantage(&self, ability: Ability, source: &str) -> Advantage {
+        Advantage::from_sources(
+            self.save_advantage_sources(ability, source)
+                .into_iter()
+                .map(|(advantage, _)| advantage),
+        )
+    }
+
+    /// Calculate current AC from equipped armor and shield, plus any
+    /// temporary bonuses from spells.
     ///
     /// If equipment is set, AC is calculated from equipped armor.
     /// Otherwise, falls back to the armor_class field for backwards compatibility.
+    /// While in Wild Shape, the beast form's AC is used instead.
     pub fn current_ac(&self) -> u8 {
+        let bonus: i16 = self.ac_bonuses.iter().map(|b| b.bonus as i16).sum();
+        (self.base_ac() as i16 + bonus).clamp(1, u8::MAX as i16) as u8
+    }
+
+    fn base_ac(&self) -> u8 {
+        if let Some(ref stats) = self.class_resources.wild_shape_stats {
+            return stats.armor_class;
+        }
+        if let Some(ac) = self.ac_override {
+            return ac;
+        }
+
         let dex_mod = self.ability_scores.modifier(Ability::Dexterity);
 
         // Calculate base AC from equipped armor or unarmored
@@ -184,13 +346,151 @@ impl Character {
             0
         };
 
-        (base_ac + shield_bonus).max(1) as u8
+        // Defense fighting style applies only while wearing armor
+        let defense_bonus: i8 = if self.equipment.armor.is_some()
+            && self.fighting_styles.contains(&FightingStyle::Defense)
+        {
+            1
+        } else {
+            0
+        };
+
+        (base_ac + shield_bonus + defense_bonus).max(1) as u8
+    }
+
+    /// Walking speed in feet after class features, temporary bonuses, armor,
+    /// load, and conditions.
+    ///
+    /// - Unarmored Movement (Monk) and Fast Movement (Barbarian) add speed
+    /// - Heavy armor without the Strength it requires costs 10 feet
+    /// - Being encumbered costs 10 feet, heavily encumbered 20, and carrying
+    ///   more than capacity leaves at most 5
+    /// - Exhaustion level 2 or more halves what is left, and level 5 means 0
+    /// - Grappled halves what is left
+    /// - Restrained, Paralyzed, Petrified, Stunned, or Unconscious means 0
+    pub fn effective_speed(&self) -> u32 {
+        let immobile = [
+            Condition::Restrained,
+            Condition::Paralyzed,
+            Condition::Petrified,
+            Condition::Stunned,
+            Condition::Unconscious,
+        ];
+        let exhaustion = self
+            .conditions
+            .iter()
+            .filter_map(|c| match c.condition {
+                Condition::Exhaustion(level) => Some(level),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+        if exhaustion >= 5 || immobile.into_iter().any(|c| self.has_condition(c)) {
+            return 0;
+        }
+
+        let class_level = |class: CharacterClass| {
+            self.classes
+                .iter()
+                .find(|c| c.class == class)
+                .map_or(0, |c| c.level)
+        };
+        let armor_type = self.equipment.armor.as_ref().map(|a| a.armor_type);
+        let heavy_armor = matches!(armor_type, Some(ArmorType::Heavy));
+
+        let mut bonus: u32 = self.speed_bonuses.iter().map(|b| b.feet).sum();
+        let monk = class_level(CharacterClass::Monk);
+        if monk >= 2 && armor_type.is_none() && self.equipment.shield.is_none() {
+            bonus += match monk {
+                2..=5 => 10,
+                6..=9 => 15,
+                10..=13 => 20,
+                14..=17 => 25,
+                _ => 30,
+            };
+        }
+        if class_level(CharacterClass::Barbarian) >= 5 && !heavy_armor {
+            bonus += 10;
+        }
+
+        let mut penalty: u32 = 0;
+        if let Some(ref armor) = self.equipment.armor {
+            if heavy_armor
+                && armor
+                    .strength_requirement
+                    .is_some_and(|req| self.ability_scores.strength < req)
+            {
+                penalty += 10;
+            }
+        }
+        let encumbrance = self.inventory.encumbrance(self.ability_scores.strength);
+        penalty += encumbrance.speed_penalty();
+
+        let mut speed = (self.speed.walk + bonus).saturating_sub(penalty);
+        if encumbrance == EncumbranceLevel::OverCapacity {
+            speed = speed.min(5);
+        }
+        if exhaustion >= 2 {
+            speed /= 2;
+        }
+        if self.has_condition(Condition::Grappled) {
+            speed / 2
+        } else {
+            speed
+        }
+    }
+
+    /// Attacks one Attack action allows, from the best Extra Attack among
+    /// the character's classes.
+    pub fn attacks_per_action(&self) -> u8 {
+        self.classes
+            .iter()
+            .map(|c| c.class.attacks_per_action(c.level))
+            .max()
+            .unwrap_or(1)
+            .max(1)
+    }
+
+    /// Lowest natural d20 roll that scores a critical hit with a weapon
+    /// attack: 20 normally, 19 with Improved Critical, 18 with Superior Critical.
+    pub fn crit_range(&self) -> u8 {
+        let has = |name: &str| self.features.iter().any(|f| f.name == name);
+        if has("Superior Critical") {
+            18
+        } else if has("Improved Critical") {
+            19
+        } else {
+            20
+        }
+    }
+
+    /// How the given light level looks to this character, accounting for darkvision.
+    pub fn perceived_light(&self, light: LightLevel) -> LightLevel {
+        light.seen_with_darkvision(self.darkvision_range)
     }
 
     pub fn is_conscious(&self) -> bool {
         self.hit_points.current > 0
     }
 
+    /// Why `condition` can't be applied to this character, if it can't.
+    ///
+    /// `source` is what is causing the condition; Fey Ancestry only blocks
+    /// sleep caused by magic.
+    pub fn condition_immunity(&self, condition: Condition, source: &str) -> Option<String> {
+        if condition.is_in(&self.condition_immunities) {
+            return Some(format!("immune to being {}", condition.name()));
+        }
+        let source = source.to_lowercase();
+        let magical_sleep = condition == Condition::Unconscious
+            && source.contains("sleep")
+            && ["magic", "spell"].iter().any(|w| source.contains(w));
+        if magical_sleep && self.features.iter().any(|f| f.name == "Fey Ancestry") {
+            return Some("Fey Ancestry: magic can't put them to sleep".to_string());
+        }
+        None
+    }
+
     /// Check if the character has a specific condition.
     pub fn has_condition(&self, condition: Condition) -> bool {
         self.conditions
@@ -210,18 +510,61 @@ impl Character {
         source: impl Into<String>,
         duration_rounds: Option<u32>,
     ) -> bool {
-        if self.has_condition(condition) {
+        let mut active = ActiveCondition::new(condition, source);
+        if let Some(duration) = duration_rounds {
+            active = active.with_duration(duration);
+        }
+        self.add_active_condition(active)
+    }
+
+    /// Add a fully specified condition if one of its kind isn't already
+    /// present. Returns true if the condition was added.
+    pub fn add_active_condition(&mut self, active: ActiveCondition) -> bool {
+        if self.has_condition(active.condition) {
             false
         } else {
-            let mut active = ActiveCondition::new(condition, source);
-            if let Some(duration) = duration_rounds {
-                active = active.with_duration(duration);
-            }
             self.conditions.push(active);
             true
         }
     }
 
+    /// Stop concentrating, ending the AC bonus the spell granted, if any.
+    pub fn end_concentration(&mut self) {
+        if let Some(concentration) = self.concentration.take() {
+            self.ac_bonuses
+                .retain(|b| b.source != concentration.spell_name);
+        }
+    }
+
+    /// Save DC for the character's spells, from their current scores.
+    pub fn spell_save_dc(&self) -> Option<u8> {
+        let spellcasting = self.spellcasting.as_ref()?;
+        Some(spellcasting.spell_save_dc(&self.effective_ability_scores(), self.proficiency_bonus()))
+    }
+
+    /// Attack bonus for the character's spell attacks, from their current
+    /// scores.
+    pub fn spell_attack_bonus(&self) -> Option<i8> {
+        let spellcasting = self.spellcasting.as_ref()?;
+        Some(
+            spellcasting
+                .spell_attack_bonus(&self.effective_ability_scores(), self.proficiency_bonus()),
+        )
+    }
+
+    /// How many spells the character can have prepared at their current
+    /// levels, or `None` if none of their classes prepares spells.
+    pub fn max_prepared_spells(&self) -> Option<usize> {
+        self.classes
+            .iter()
+            .filter_map(|c| {
+                let ability = c.class.spellcasting_ability()?;
+                c.class
+                    .max_prepared_spells(c.level, self.ability_scores.modifier(ability))
+            })
+            .reduce(|total, limit| total + limit)
+    }
+
     pub fn passive_perception(&self) -> i8 {
         10 + self.skill_modifier(Skill::Perception)
     }
@@ -242,6 +585,9 @@ pub struct NPC {
     pub location_id: Option<LocationId>,
     pub disposition: Disposition,
     pub known_information: Vec<String>,
+    /// Daily routine; the first entry covering the current hour wins.
+    #[serde(default)]
+    pub schedule: Vec<ScheduleEntry>,
 }
 
 impl NPC {
@@ -255,8 +601,24 @@ impl NPC {
             location_id: None,
             disposition: Disposition::Neutral,
             known_information: Vec::new(),
+            schedule: Vec::new(),
         }
     }
+
+    /// Where the NPC's routine puts them at the given time, if anywhere.
+    pub fn scheduled_location(&self, time: &GameTime) -> Option<LocationId> {
+        self.schedule
+            .iter()
+            .find(|entry| entry.time_range.contains(time.hour))
+            .map(|entry| entry.location)
+    }
+}
+
+/// Part of an NPC's daily routine: where they are during a span of hours.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    pub time_range: TimeRange,
+    pub location: LocationId,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -267,3 +629,10 @@ pub enum Disposition {
     Friendly,
     Helpful,
 }
+
+impl Disposition {
+    /// Whether an NPC with this disposition fights on the player's side.
+    pub fn is_ally(&self) -> bool {
+        matches!(self, Disposition::Friendly | Disposition::Helpful)
+    }
+}
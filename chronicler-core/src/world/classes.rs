@@ -7,12 +7,15 @@ use crate::dice::DieType;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-use super::Ability;
+use super::{Ability, AbilityScores, HitPoints, PactSlots};
 
 // ============================================================================
 // Classes and Features
 // ============================================================================
 
+/// Score each prerequisite ability needs to multiclass.
+pub const MULTICLASS_MINIMUM: u8 = 13;
+
 /// D&D character classes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CharacterClass {
@@ -105,6 +108,54 @@ impl CharacterClass {
         }
     }
 
+    /// Returns true if reaching `level` in this class grants an Ability Score
+    /// Improvement (or a feat in its place).
+    pub fn grants_asi_at(&self, level: u8) -> bool {
+        match level {
+            4 | 8 | 12 | 16 | 19 => true,
+            6 | 14 => matches!(self, CharacterClass::Fighter),
+            10 => matches!(self, CharacterClass::Rogue),
+            _ => false,
+        }
+    }
+
+    /// Abilities that must be at least [`MULTICLASS_MINIMUM`] to multiclass
+    /// into or out of this class. A Fighter needs only one of theirs; every
+    /// other class needs all of its listed abilities.
+    pub fn multiclass_abilities(&self) -> &'static [Ability] {
+        match self {
+            CharacterClass::Barbarian => &[Ability::Strength],
+            CharacterClass::Bard | CharacterClass::Sorcerer | CharacterClass::Warlock => {
+                &[Ability::Charisma]
+            }
+            CharacterClass::Cleric | CharacterClass::Druid => &[Ability::Wisdom],
+            CharacterClass::Fighter => &[Ability::Strength, Ability::Dexterity],
+            CharacterClass::Monk | CharacterClass::Ranger => &[Ability::Dexterity, Ability::Wisdom],
+            CharacterClass::Paladin => &[Ability::Strength, Ability::Charisma],
+            CharacterClass::Rogue => &[Ability::Dexterity],
+            CharacterClass::Wizard => &[Ability::Intelligence],
+        }
+    }
+
+    /// Returns true if `scores` meet this class's multiclassing prerequisites.
+    pub fn meets_multiclass_prerequisites(&self, scores: &AbilityScores) -> bool {
+        let meets = |ability: &Ability| scores.get(*ability) >= MULTICLASS_MINIMUM;
+        match self {
+            CharacterClass::Fighter => self.multiclass_abilities().iter().any(meets),
+            _ => self.multiclass_abilities().iter().all(meets),
+        }
+    }
+
+    /// Returns the class level at which this class chooses a Fighting Style,
+    /// if it gets one at all.
+    pub fn fighting_style_level(&self) -> Option<u8> {
+        match self {
+            CharacterClass::Fighter => Some(1),
+            CharacterClass::Paladin | CharacterClass::Ranger => Some(2),
+            _ => None,
+        }
+    }
+
     /// Returns the spellcasting ability for this class, if any.
     pub fn spellcasting_ability(&self) -> Option<Ability> {
         match self {
@@ -313,9 +364,10 @@ impl CharacterClass {
     /// Returns the maximum number of spells a prepared caster can prepare.
     /// Formula: spellcasting ability modifier + class level (minimum 1).
     /// For half-casters (Paladin, Ranger), it's ability mod + half class level.
+    /// Wizards prepare from their spellbook by the same formula.
     pub fn max_prepared_spells(&self, level: u8, ability_modifier: i8) -> Option<usize> {
         let base = match self {
-            CharacterClass::Cleric | CharacterClass::Druid => {
+            CharacterClass::Cleric | CharacterClass::Druid | CharacterClass::Wizard => {
                 (ability_modifier as i32 + level as i32).max(1) as usize
             }
             CharacterClass::Paladin => {
@@ -333,6 +385,9 @@ impl CharacterClass {
 
     /// Returns the highest spell level this class can cast at a given character level.
     pub fn max_spell_level(&self, level: u8) -> u8 {
+        if let Some(pact) = self.pact_slots_at_level(level) {
+            return pact.level;
+        }
         let slots = self.spell_slots_at_level(level);
         for (spell_level, &count) in slots.iter().enumerate().rev() {
             if count > 0 {
@@ -347,7 +402,6 @@ impl CharacterClass {
     pub fn spell_slots_at_level(&self, level: u8) -> [u8; 9] {
         // Full casters: Bard, Cleric, Druid, Sorcerer, Wizard
         // Half casters: Paladin, Ranger (start at level 2)
-        // Pact Magic: Warlock (different progression)
 
         match self {
             // Full casters (standard progression)
@@ -360,13 +414,31 @@ impl CharacterClass {
             // Half casters (Paladin, Ranger)
             CharacterClass::Paladin | CharacterClass::Ranger => half_caster_slots(level),
 
-            // Warlock uses Pact Magic
-            CharacterClass::Warlock => warlock_slots(level),
-
-            // Non-casters
+            // Warlock slots are Pact Magic, see `pact_slots_at_level`
             _ => [0; 9],
         }
     }
+
+    /// Returns Pact Magic slots for a given character level (Warlock only).
+    ///
+    /// Pact slots are all of one level and recharge on a short rest.
+    pub fn pact_slots_at_level(&self, level: u8) -> Option<PactSlots> {
+        if *self != CharacterClass::Warlock {
+            return None;
+        }
+        let (slot_level, count) = match level {
+            1 => (1, 1),
+            2 => (1, 2),
+            3..=4 => (2, 2),
+            5..=6 => (3, 2),
+            7..=8 => (4, 2),
+            9..=10 => (5, 2),
+            11..=16 => (5, 3),
+            17..=20 => (5, 4),
+            _ => return None,
+        };
+        Some(PactSlots::new(slot_level, count))
+    }
 }
 
 /// Standard full caster spell slot progression (D&D 5e SRD).
@@ -423,36 +495,6 @@ fn half_caster_slots(level: u8) -> [u8; 9] {
     }
 }
 
-/// Warlock Pact Magic slot progression.
-fn warlock_slots(level: u8) -> [u8; 9] {
-    // Warlocks have fewer slots but they're all at their highest available level
-    // and recharge on short rest. For simplicity, we track them in the slot array
-    // at the appropriate level.
-    match level {
-        1 => [1, 0, 0, 0, 0, 0, 0, 0, 0], // 1 slot, 1st level
-        2 => [2, 0, 0, 0, 0, 0, 0, 0, 0], // 2 slots, 1st level
-        3 => [0, 2, 0, 0, 0, 0, 0, 0, 0], // 2 slots, 2nd level
-        4 => [0, 2, 0, 0, 0, 0, 0, 0, 0],
-        5 => [0, 0, 2, 0, 0, 0, 0, 0, 0], // 2 slots, 3rd level
-        6 => [0, 0, 2, 0, 0, 0, 0, 0, 0],
-        7 => [0, 0, 0, 2, 0, 0, 0, 0, 0], // 2 slots, 4th level
-        8 => [0, 0, 0, 2, 0, 0, 0, 0, 0],
-        9 => [0, 0, 0, 0, 2, 0, 0, 0, 0], // 2 slots, 5th level
-        10 => [0, 0, 0, 0, 2, 0, 0, 0, 0],
-        11 => [0, 0, 0, 0, 3, 0, 0, 0, 0], // 3 slots, 5th level
-        12 => [0, 0, 0, 0, 3, 0, 0, 0, 0],
-        13 => [0, 0, 0, 0, 3, 0, 0, 0, 0],
-        14 => [0, 0, 0, 0, 3, 0, 0, 0, 0],
-        15 => [0, 0, 0, 0, 3, 0, 0, 0, 0],
-        16 => [0, 0, 0, 0, 3, 0, 0, 0, 0],
-        17 => [0, 0, 0, 0, 4, 0, 0, 0, 0], // 4 slots, 5th level
-        18 => [0, 0, 0, 0, 4, 0, 0, 0, 0],
-        19 => [0, 0, 0, 0, 4, 0, 0, 0, 0],
-        20 => [0, 0, 0, 0, 4, 0, 0, 0, 0],
-        _ => [0; 9],
-    }
-}
-
 impl fmt::Display for CharacterClass {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.name())
@@ -490,6 +532,160 @@ pub enum RechargeType {
     ShortRest,
     LongRest,
     Dawn,
+    /// Recharges at the start of each turn on a d6 roll of `min` or higher
+    /// (e.g. "Recharge 5-6" is `Die { min: 5 }`).
+    Die {
+        min: u8,
+    },
+}
+
+/// A Fighting Style chosen by a Fighter, Paladin, or Ranger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FightingStyle {
+    /// +2 to attack rolls with ranged weapons.
+    Archery,
+    /// +1 AC while wearing armor.
+    Defense,
+    /// +2 damage with a one-handed melee weapon and no other weapon.
+    Dueling,
+    /// Reroll 1s and 2s on damage dice of two-handed or versatile melee weapons.
+    GreatWeaponFighting,
+    /// Impose disadvantage on an attack against an adjacent ally (with a shield).
+    Protection,
+    /// Add the ability modifier to off-hand attack damage.
+    TwoWeaponFighting,
+}
+
+impl FightingStyle {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FightingStyle::Archery => "Archery",
+            FightingStyle::Defense => "Defense",
+            FightingStyle::Dueling => "Dueling",
+            FightingStyle::GreatWeaponFighting => "Great Weapon Fighting",
+            FightingStyle::Protection => "Protection",
+            FightingStyle::TwoWeaponFighting => "Two-Weapon Fighting",
+        }
+    }
+
+    /// Returns true if `class` can pick this style from its Fighting Style
+    /// feature. Fighters may take any style; Paladins and Rangers each have a
+    /// shorter list.
+    pub fn available_to(&self, class: CharacterClass) -> bool {
+        match class {
+            CharacterClass::Fighter => true,
+            CharacterClass::Paladin => !matches!(
+                self,
+                FightingStyle::Archery | FightingStyle::TwoWeaponFighting
+            ),
+            CharacterClass::Ranger => !matches!(
+                self,
+                FightingStyle::GreatWeaponFighting | FightingStyle::Protection
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for FightingStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// ============================================================================
+// Wild Shape
+// ============================================================================
+
+/// Physical stat block for a beast form assumed via Wild Shape.
+///
+/// While transformed, the druid's AC, hit points, attacks, and physical
+/// ability scores (STR/DEX/CON) come from this block. Mental ability scores
+/// (INT/WIS/CHA) stay the druid's own.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatBlock {
+    pub name: String,
+    pub armor_class: u8,
+    pub hit_points: HitPoints,
+    pub strength: u8,
+    pub dexterity: u8,
+    pub constitution: u8,
+    /// Name of the beast's natural attack (e.g., "Bite")
+    pub attack_name: String,
+    /// Damage dice for the natural attack, without ability modifier (e.g., "2d4")
+    pub attack_damage: String,
+}
+
+impl StatBlock {
+    /// Create a stat block with average physical scores and a generic 1d6 attack.
+    pub fn new(name: impl Into<String>, max_hp: i32, armor_class: u8) -> Self {
+        Self {
+            name: name.into(),
+            armor_class,
+            hit_points: HitPoints::new(max_hp.max(1)),
+            strength: 10,
+            dexterity: 10,
+            constitution: 10,
+            attack_name: "Natural weapons".to_string(),
+            attack_damage: "1d6".to_string(),
+        }
+    }
+
+    pub fn with_abilities(mut self, strength: u8, dexterity: u8, constitution: u8) -> Self {
+        self.strength = strength;
+        self.dexterity = dexterity;
+        self.constitution = constitution;
+        self
+    }
+
+    pub fn with_attack(mut self, name: impl Into<String>, damage: impl Into<String>) -> Self {
+        self.attack_name = name.into();
+        self.attack_damage = damage.into();
+        self
+    }
+
+    /// Look up a common SRD beast form by name (case-insensitive).
+    pub fn beast(name: &str) -> Option<Self> {
+        let block = match name.to_lowercase().as_str() {
+            "cat" => Self::new("Cat", 2, 12)
+                .with_abilities(3, 15, 10)
+                .with_attack("Claws", "1"),
+            "wolf" => Self::new("Wolf", 11, 13)
+                .with_abilities(12, 15, 12)
+                .with_attack("Bite", "2d4"),
+            "panther" => Self::new("Panther", 13, 12)
+                .with_abilities(14, 15, 10)
+                .with_attack("Bite", "1d6"),
+            "riding horse" => Self::new("Riding Horse", 13, 10)
+                .with_abilities(16, 10, 12)
+                .with_attack("Hooves", "2d4"),
+            "black bear" => Self::new("Black Bear", 19, 11)
+                .with_abilities(15, 10, 14)
+                .with_attack("Claws", "2d4"),
+            "giant spider" => Self::new("Giant Spider", 26, 14)
+                .with_abilities(14, 16, 12)
+                .with_attack("Bite", "1d8"),
+            "brown bear" => Self::new("Brown Bear", 34, 11)
+                .with_abilities(19, 10, 16)
+                .with_attack("Claws", "2d6"),
+            "dire wolf" => Self::new("Dire Wolf", 37, 14)
+                .with_abilities(17, 15, 15)
+                .with_attack("Bite", "2d6"),
+            _ => return None,
+        };
+        Some(block)
+    }
+
+    /// Ability scores while in this form: physical scores from the beast,
+    /// mental scores from the druid.
+    pub fn apply_to(&self, scores: &AbilityScores) -> AbilityScores {
+        AbilityScores {
+            strength: self.strength,
+            dexterity: self.dexterity,
+            constitution: self.constitution,
+            ..scores.clone()
+        }
+    }
 }
 
 // ============================================================================
@@ -519,6 +715,9 @@ pub struct ClassResources {
     pub wild_shape_form: Option<String>,
     /// Remaining HP in Wild Shape form
     pub wild_shape_hp: Option<i32>,
+    /// Active beast stat block while in Wild Shape (None if not transformed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wild_shape_stats: Option<StatBlock>,
 
     // Bard
     /// Current Bardic Inspiration uses remaining
@@ -609,6 +808,75 @@ impl ClassResources {
         }
     }
 
+    /// Recompute level-scaled resource pools after leveling from `old_level`
+    /// to `new_level` in `class`.
+    ///
+    /// Limited-use features tracked on [`Feature`] (Rage, Channel Divinity)
+    /// are sized by [`feature_uses_at`](Self::feature_uses_at) instead.
+    pub fn recompute_for(&mut self, class: CharacterClass, old_level: u8, new_level: u8) {
+        match class {
+            CharacterClass::Monk => {
+                // Ki points = Monk level
+                self.max_ki_points = new_level;
+                self.ki_points = new_level;
+            }
+            CharacterClass::Sorcerer => {
+                // Sorcery points = Sorcerer level (gained at level 2)
+                if new_level >= 2 {
+                    self.max_sorcery_points = new_level;
+                    let gained = new_level.saturating_sub(old_level);
+                    self.sorcery_points =
+                        (self.sorcery_points + gained).min(self.max_sorcery_points);
+                }
+            }
+            CharacterClass::Paladin => {
+                // Lay on Hands pool = 5 × Paladin level, restored to full
+                self.lay_on_hands_max = new_level as u32 * 5;
+                self.lay_on_hands_pool = self.lay_on_hands_max;
+            }
+            CharacterClass::Barbarian => {
+                // Rage damage bonus increases at levels 9 and 16
+                self.rage_damage_bonus = match new_level {
+                    1..=8 => 2,
+                    9..=15 => 3,
+                    16..=20 => 4,
+                    _ => 2,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the name and maximum uses of the class's level-scaled
+    /// limited-use feature, if it has one at this level.
+    pub fn feature_uses_at(class: CharacterClass, level: u8) -> Option<(&'static str, u8)> {
+        match class {
+            CharacterClass::Barbarian => {
+                let uses = match level {
+                    1..=2 => 2,
+                    3..=5 => 3,
+                    6..=11 => 4,
+                    12..=16 => 5,
+                    17..=19 => 6,
+                    20 => u8::MAX, // Unlimited at 20
+                    _ => 2,
+                };
+                Some(("Rage", uses))
+            }
+            CharacterClass::Cleric => {
+                let uses = match level {
+                    0..=1 => return None,
+                    2..=5 => 1,
+                    6..=17 => 2,
+                    _ => 3,
+                };
+                Some(("Channel Divinity", uses))
+            }
+            CharacterClass::Paladin if level >= 3 => Some(("Channel Divinity", 1)),
+            _ => None,
+        }
+    }
+
     /// Reset resources on a short rest
     pub fn short_rest_recovery(&mut self, class: CharacterClass, level: u8) {
         match class {
@@ -627,12 +895,11 @@ impl ClassResources {
                 self.channel_divinity_used = false;
             }
             CharacterClass::Monk => {
-                // Ki points don't recover on short rest in base rules
-                // (Uncanny Metabolism at level 2 lets them recover some)
+                // Ki comes back on a short or long rest
+                self.ki_points = self.max_ki_points;
             }
             _ => {}
         }
-        let _ = level; // Used for Bard Font of Inspiration check
     }
 
     /// Reset resources on a long rest
@@ -649,9 +916,6 @@ impl ClassResources {
                 // Full recovery on long rest
                 self.bardic_inspiration_uses = self.max_bardic_inspiration;
             }
-            CharacterClass::Monk => {
-                self.ki_points = self.max_ki_points;
-            }
             CharacterClass::Paladin => {
                 self.lay_on_hands_pool = self.lay_on_hands_max;
             }
@@ -4,9 +4,9 @@
 //! initiative tracking, combatant management, and turn order.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use super::CharacterId;
+use super::{sorted, CharacterId, DurationTiming, LightLevel, Resistance};
 
 /// Combat participant.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +19,9 @@ pub struct Combatant {
     pub current_hp: i32,
     pub max_hp: i32,
     pub armor_class: u8,
+    /// Damage resistances
+    #[serde(default)]
+    pub resistances: Vec<Resistance>,
 }
 
 /// Combat state tracking.
@@ -29,11 +32,144 @@ pub struct CombatState {
     pub turn_index: usize,
     pub combatants: Vec<Combatant>,
     /// Characters who have used their Sneak Attack this turn
-    #[serde(default)]
+    #[serde(default, with = "sorted::set")]
     pub sneak_attack_used: HashSet<CharacterId>,
     /// Number of attacks each character has made this turn
-    #[serde(default)]
+    #[serde(default, with = "sorted::pairs")]
     pub attacks_this_turn: std::collections::HashMap<CharacterId, u8>,
+    /// Ammunition fired on missed attacks, half of which is recovered when
+    /// combat ends
+    #[serde(default, with = "sorted::pairs")]
+    pub recoverable_ammunition: std::collections::HashMap<String, u32>,
+    /// Light level of the battlefield, overriding the location's when set
+    #[serde(default)]
+    pub light_level: Option<LightLevel>,
+    /// Damage dealt to enemies so far
+    #[serde(default)]
+    pub damage_dealt: i32,
+    /// Damage the player has taken so far
+    #[serde(default)]
+    pub damage_taken: i32,
+    /// Enemies dropped to 0 HP, in the order they fell
+    #[serde(default)]
+    pub defeated: Vec<String>,
+    /// What each character has spent since their turn began
+    #[serde(default, with = "sorted::pairs")]
+    pub actions_this_turn: HashMap<CharacterId, TurnActions>,
+}
+
+/// The kinds of action a creature gets each turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionType {
+    Action,
+    BonusAction,
+    Reaction,
+}
+
+impl ActionType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ActionType::Action => "action",
+            ActionType::BonusAction => "bonus action",
+            ActionType::Reaction => "reaction",
+        }
+    }
+}
+
+/// One character's action economy for the current turn.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TurnActions {
+    /// Actions taken this turn
+    #[serde(default)]
+    pub actions: u8,
+    /// Actions available beyond the usual one (e.g. from Action Surge)
+    #[serde(default)]
+    pub extra_actions: u8,
+    pub bonus_action: bool,
+    pub reaction: bool,
+    /// Spells cast this turn, with the action each took and its level
+    #[serde(default)]
+    pub spells: Vec<(ActionType, u8)>,
+}
+
+impl TurnActions {
+    pub fn is_used(&self, action: ActionType) -> bool {
+        match action {
+            ActionType::Action => self.actions > self.extra_actions,
+            ActionType::BonusAction => self.bonus_action,
+            ActionType::Reaction => self.reaction,
+        }
+    }
+
+    /// Spend `action`, noting the spell level if it cast a spell.
+    pub fn spend(&mut self, action: ActionType, spell_level: Option<u8>) {
+        match action {
+            ActionType::Action => self.actions = self.actions.saturating_add(1),
+            ActionType::BonusAction => self.bonus_action = true,
+            ActionType::Reaction => self.reaction = true,
+        }
+        if let Some(level) = spell_level {
+            self.spells.push((action, level));
+        }
+    }
+
+    /// Why a spell of `level` cast with `action` isn't allowed this turn,
+    /// if it isn't.
+    ///
+    /// Besides each action being usable once, a turn with a bonus-action
+    /// spell in it allows no other spell but a cantrip cast as an action.
+    pub fn spell_blocked(&self, action: ActionType, level: u8) -> Option<String> {
+        if self.is_used(action) {
+            return Some(format!("already used their {} this turn", action.name()));
+        }
+        let action_cantrip =
+            |(action, level): (ActionType, u8)| action == ActionType::Action && level == 0;
+        if action == ActionType::BonusAction
+            && self.spells.iter().any(|&spell| !action_cantrip(spell))
+        {
+            return Some(
+                "can't cast a bonus-action spell after casting a spell other than an action cantrip this turn"
+                    .to_string(),
+            );
+        }
+        if self
+            .spells
+            .iter()
+            .any(|(a, _)| *a == ActionType::BonusAction)
+            && !action_cantrip((action, level))
+        {
+            return Some(
+                "cast a spell as a bonus action this turn, so can only cast a cantrip with their action"
+                    .to_string(),
+            );
+        }
+        None
+    }
+}
+
+/// Recap of an encounter, produced when combat ends.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CombatSummary {
+    pub rounds: u32,
+    pub damage_dealt: i32,
+    pub damage_taken: i32,
+    pub defeated: Vec<String>,
+}
+
+impl CombatSummary {
+    /// One-line recap, e.g. "Combat lasted 2 rounds: dealt 18 damage and
+    /// took 5. Defeated: Goblin, Wolf."
+    pub fn describe(&self) -> String {
+        let rounds = if self.rounds == 1 { "round" } else { "rounds" };
+        let mut text = format!(
+            "Combat lasted {} {rounds}: dealt {} damage and took {}.",
+            self.rounds, self.damage_dealt, self.damage_taken
+        );
+        if !self.defeated.is_empty() {
+            text.push_str(&format!(" Defeated: {}.", self.defeated.join(", ")));
+        }
+        text
+    }
 }
 
 impl CombatState {
@@ -45,6 +181,12 @@ impl CombatState {
             combatants: Vec::new(),
             sneak_attack_used: HashSet::new(),
             attacks_this_turn: std::collections::HashMap::new(),
+            recoverable_ammunition: std::collections::HashMap::new(),
+            light_level: None,
+            damage_dealt: 0,
+            damage_taken: 0,
+            defeated: Vec::new(),
+            actions_this_turn: HashMap::new(),
         }
     }
 
@@ -58,6 +200,25 @@ impl CombatState {
         self.combatants.get(self.turn_index)
     }
 
+    /// The points in the player's turn cycle that advancing to the next
+    /// turn passes: the end of the player's turn, the start of their next,
+    /// or both when they fight alone.
+    pub fn player_turn_moments(&self) -> Vec<DurationTiming> {
+        let is_player = |index: usize| {
+            self.combatants
+                .get(index % self.combatants.len().max(1))
+                .is_some_and(|c| c.is_player)
+        };
+        let mut moments = Vec::new();
+        if is_player(self.turn_index) {
+            moments.push(DurationTiming::EndOfTurn);
+        }
+        if is_player(self.turn_index + 1) {
+            moments.push(DurationTiming::StartOfTurn);
+        }
+        moments
+    }
+
     pub fn next_turn(&mut self) {
         self.turn_index += 1;
         if self.turn_index >= self.combatants.len() {
@@ -67,6 +228,16 @@ impl CombatState {
         // Reset per-turn tracking for the new combatant
         self.sneak_attack_used.clear();
         self.attacks_this_turn.clear();
+        // A creature's actions, reaction included, refresh when its own
+        // turn starts
+        if let Some(id) = self.current_combatant().map(|c| c.id) {
+            self.actions_this_turn.remove(&id);
+        }
+    }
+
+    /// What `id` has spent since their turn began.
+    pub fn turn_actions(&self, id: CharacterId) -> TurnActions {
+        self.actions_this_turn.get(&id).cloned().unwrap_or_default()
     }
 
     pub fn end_combat(&mut self) {
@@ -80,6 +251,35 @@ impl CombatState {
         }
     }
 
+    /// Update a non-player combatant's HP, tallying damage dealt to enemies
+    /// and noting any enemy it drops to 0.
+    pub fn record_combatant_hp(&mut self, id: CharacterId, new_hp: i32) {
+        let Some(combatant) = self.combatants.iter_mut().find(|c| c.id == id) else {
+            return;
+        };
+        let old_hp = combatant.current_hp;
+        combatant.current_hp = new_hp;
+        if combatant.is_player || combatant.is_ally {
+            return;
+        }
+        if new_hp < old_hp {
+            self.damage_dealt += old_hp - new_hp.max(0);
+        }
+        if old_hp > 0 && new_hp <= 0 {
+            self.defeated.push(combatant.name.clone());
+        }
+    }
+
+    /// Recap of the fight so far.
+    pub fn summary(&self) -> CombatSummary {
+        CombatSummary {
+            rounds: self.round,
+            damage_dealt: self.damage_dealt,
+            damage_taken: self.damage_taken,
+            defeated: self.defeated.clone(),
+        }
+    }
+
     /// Get non-player combatants (enemies and allies)
     pub fn get_enemies(&self) -> Vec<&Combatant> {
         self.combatants.iter().filter(|c| !c.is_player).collect()
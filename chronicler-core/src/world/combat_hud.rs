@@ -0,0 +1,255 @@
+//! What a combat HUD shows for each combatant.
+//!
+//! A [`CombatHud`] is projected from the world once when combat starts and
+//! then kept current from each turn's [`WorldDiff`], so a UI doesn't have to
+//! re-read every character to redraw the initiative list.
+
+use super::{CharacterId, CombatState, Condition, GameWorld, WorldDiff};
+
+/// One combatant's row in the HUD.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombatantStatus {
+    pub id: CharacterId,
+    pub name: String,
+    pub is_player: bool,
+    pub is_ally: bool,
+    pub current_hp: i32,
+    pub max_hp: i32,
+    pub conditions: Vec<Condition>,
+    /// The spell being concentrated on, if any.
+    pub concentrating_on: Option<String>,
+}
+
+impl CombatantStatus {
+    /// Remaining hit points as a fraction of the maximum, for an HP bar.
+    pub fn hp_fraction(&self) -> f32 {
+        if self.max_hp <= 0 {
+            return 0.0;
+        }
+        (self.current_hp as f32 / self.max_hp as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// The combatants in initiative order, with whose turn it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CombatHud {
+    pub round: u32,
+    pub turn_index: usize,
+    pub combatants: Vec<CombatantStatus>,
+}
+
+impl CombatHud {
+    /// Project the HUD from `world`, or `None` outside combat.
+    pub fn from_world(world: &GameWorld) -> Option<Self> {
+        let combat = world.combat.as_ref()?;
+        let player = &world.player_character;
+        let combatants = combat
+            .combatants
+            .iter()
+            .map(|c| CombatantStatus {
+                id: c.id,
+                name: c.name.clone(),
+                is_player: c.is_player,
+                is_ally: c.is_ally,
+                current_hp: c.current_hp,
+                max_hp: c.max_hp,
+                conditions: if c.is_player {
+                    player.conditions.iter().map(|a| a.condition).collect()
+                } else {
+                    Vec::new()
+                },
+                concentrating_on: if c.is_player {
+                    player.concentration.as_ref().map(|c| c.spell_name.clone())
+                } else {
+                    None
+                },
+            })
+            .collect();
+
+        Some(Self {
+            round: combat.round,
+            turn_index: combat.turn_index,
+            combatants,
+        })
+    }
+
+    /// The combatant whose turn it is.
+    pub fn current(&self) -> Option<&CombatantStatus> {
+        self.combatants.get(self.turn_index)
+    }
+
+    /// Bring the HUD up to date after a turn: follow `combat`'s round, turn,
+    /// and roster, then apply the HP, condition, and concentration changes
+    /// in `diff`.
+    pub fn update(&mut self, combat: &CombatState, diff: &WorldDiff) {
+        self.round = combat.round;
+        self.turn_index = combat.turn_index;
+
+        let mut combatants = Vec::with_capacity(combat.combatants.len());
+        for c in &combat.combatants {
+            let status = match self.combatants.iter().position(|s| s.id == c.id) {
+                Some(i) => self.combatants.swap_remove(i),
+                None => CombatantStatus {
+                    id: c.id,
+                    name: c.name.clone(),
+                    is_player: c.is_player,
+                    is_ally: c.is_ally,
+                    current_hp: c.current_hp,
+                    max_hp: c.max_hp,
+                    conditions: Vec::new(),
+                    concentrating_on: None,
+                },
+            };
+            combatants.push(status);
+        }
+        self.combatants = combatants;
+
+        for delta in &diff.hp {
+            if let Some(status) = self.status_mut(delta.target_id) {
+                status.current_hp = delta.current;
+            }
+        }
+        for (id, condition) in &diff.conditions_removed {
+            if let Some(status) = self.status_mut(*id) {
+                status.conditions.retain(|c| c != condition);
+            }
+        }
+        for (id, condition) in &diff.conditions_added {
+            if let Some(status) = self.status_mut(*id) {
+                if !status.conditions.contains(condition) {
+                    status.conditions.push(*condition);
+                }
+            }
+        }
+        for id in &diff.concentration_ended {
+            if let Some(status) = self.status_mut(*id) {
+                status.concentrating_on = None;
+            }
+        }
+        for (id, spell) in &diff.concentration_started {
+            if let Some(status) = self.status_mut(*id) {
+                status.concentrating_on = Some(spell.clone());
+            }
+        }
+    }
+
+    fn status_mut(&mut self, id: CharacterId) -> Option<&mut CombatantStatus> {
+        self.combatants.iter_mut().find(|s| s.id == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{apply_effects, Effect};
+    use crate::world::{create_sample_cleric, Combatant, Concentration, HpDelta};
+
+    fn goblin() -> Combatant {
+        Combatant {
+            id: CharacterId::new(),
+            name: "Goblin".to_string(),
+            initiative: 12,
+            is_player: false,
+            is_ally: false,
+            current_hp: 7,
+            max_hp: 7,
+            armor_class: 15,
+            resistances: Vec::new(),
+        }
+    }
+
+    fn battle() -> (GameWorld, CharacterId) {
+        let mut world = GameWorld::new("Test", create_sample_cleric("Elara"));
+        let player = &world.player_character;
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant {
+            id: player.id,
+            name: player.name.clone(),
+            initiative: 15,
+            is_player: true,
+            is_ally: true,
+            current_hp: player.hit_points.current,
+            max_hp: player.hit_points.maximum,
+            armor_class: 12,
+            resistances: Vec::new(),
+        });
+        let goblin = goblin();
+        let goblin_id = goblin.id;
+        combat.add_combatant(goblin);
+        world.combat = Some(combat);
+        (world, goblin_id)
+    }
+
+    #[test]
+    fn test_hud_shows_player_conditions_and_concentration() {
+        let (mut world, goblin_id) = battle();
+        world.player_character.concentration = Some(Concentration {
+            spell_name: "Shield of Faith".to_string(),
+            pending_damage: Vec::new(),
+        });
+        let player_id = world.player_character.id;
+        apply_effects(
+            &mut world,
+            &[Effect::ConditionApplied {
+                target_id: player_id,
+                condition: Condition::Poisoned,
+                source: "dart".to_string(),
+                duration_rounds: None,
+                timing: Default::default(),
+            }],
+        );
+
+        let hud = CombatHud::from_world(&world).unwrap();
+
+        assert_eq!(hud.current().unwrap().id, player_id);
+        let player = &hud.combatants[0];
+        assert_eq!(player.conditions, vec![Condition::Poisoned]);
+        assert_eq!(player.concentrating_on.as_deref(), Some("Shield of Faith"));
+        let goblin = &hud.combatants[1];
+        assert_eq!(goblin.id, goblin_id);
+        assert_eq!((goblin.current_hp, goblin.max_hp), (7, 7));
+        assert!(goblin.conditions.is_empty());
+        assert_eq!(goblin.concentrating_on, None);
+    }
+
+    #[test]
+    fn test_hud_follows_diff_and_roster() {
+        let (mut world, goblin_id) = battle();
+        let mut hud = CombatHud::from_world(&world).unwrap();
+
+        let combat = world.combat.as_mut().unwrap();
+        combat.update_combatant_hp(goblin_id, 2);
+        let wolf = Combatant {
+            name: "Wolf".to_string(),
+            ..goblin()
+        };
+        let wolf_id = wolf.id;
+        combat.add_combatant(wolf);
+        let diff = WorldDiff {
+            hp: vec![HpDelta {
+                target_id: goblin_id,
+                amount: -5,
+                current: 2,
+            }],
+            conditions_added: vec![(goblin_id, Condition::Prone)],
+            concentration_started: vec![(world.player_character.id, "Bless".to_string())],
+            ..WorldDiff::default()
+        };
+
+        hud.update(world.combat.as_ref().unwrap(), &diff);
+
+        assert_eq!(hud.combatants.len(), 3);
+        let goblin = hud.combatants.iter().find(|s| s.id == goblin_id).unwrap();
+        assert_eq!(goblin.current_hp, 2);
+        assert_eq!(goblin.conditions, vec![Condition::Prone]);
+        assert!((goblin.hp_fraction() - 2.0 / 7.0).abs() < f32::EPSILON);
+        assert!(hud.combatants.iter().any(|s| s.id == wolf_id));
+        assert_eq!(
+            hud.combatants
+                .iter()
+                .find(|s| s.is_player)
+                .and_then(|s| s.concentrating_on.as_deref()),
+            Some("Bless")
+        );
+    }
+}
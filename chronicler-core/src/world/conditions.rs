@@ -3,6 +3,7 @@
 //! This module defines the standard conditions from the SRD 5.2,
 //! including effects like Blinded, Charmed, Frightened, etc.
 
+use std::collections::HashSet;
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
@@ -32,6 +33,13 @@ pub enum Condition {
 }
 
 impl Condition {
+    /// Whether a condition of the same kind is in `set`, ignoring the
+    /// exhaustion level.
+    pub fn is_in(&self, set: &HashSet<Condition>) -> bool {
+        set.iter()
+            .any(|c| std::mem::discriminant(c) == std::mem::discriminant(self))
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             Condition::Blinded => "Blinded",
@@ -73,12 +81,26 @@ impl fmt::Display for Condition {
     }
 }
 
+/// The point in the affected creature's turn at which a timed condition
+/// counts down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DurationTiming {
+    /// "Until the start of your next turn."
+    StartOfTurn,
+    /// "Until the end of your next turn."
+    #[default]
+    EndOfTurn,
+}
+
 /// A condition applied to a creature with tracking info.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActiveCondition {
     pub condition: Condition,
     pub source: String,
+    /// Turns of the affected creature the condition lasts, counted at `timing`.
     pub duration_rounds: Option<u32>,
+    #[serde(default)]
+    pub timing: DurationTiming,
 }
 
 impl ActiveCondition {
@@ -87,6 +109,7 @@ impl ActiveCondition {
             condition,
             source: source.into(),
             duration_rounds: None,
+            timing: DurationTiming::default(),
         }
     }
 
@@ -94,6 +117,23 @@ impl ActiveCondition {
         self.duration_rounds = Some(rounds);
         self
     }
+
+    pub fn with_timing(mut self, timing: DurationTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Counts down one turn if `moment` is when this condition ticks.
+    /// Returns false once the condition has lapsed.
+    pub fn tick(&mut self, moment: DurationTiming) -> bool {
+        match self.duration_rounds.as_mut() {
+            Some(duration) if self.timing == moment => {
+                *duration = duration.saturating_sub(1);
+                *duration > 0
+            }
+            _ => true,
+        }
+    }
 }
 
 #[cfg(test)]
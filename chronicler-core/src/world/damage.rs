@@ -0,0 +1,83 @@
+//! Damage types and resistances.
+//!
+//! These live with the world because characters, races, and combatants all
+//! record them; the rules engine re-exports them for resolving damage.
+
+use super::WeaponDamageType;
+use serde::{Deserialize, Serialize};
+
+/// Common D&D damage types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DamageType {
+    Slashing,
+    Piercing,
+    Bludgeoning,
+    Fire,
+    Cold,
+    Lightning,
+    Thunder,
+    Acid,
+    Poison,
+    Necrotic,
+    Radiant,
+    Force,
+    Psychic,
+}
+
+impl DamageType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageType::Slashing => "slashing",
+            DamageType::Piercing => "piercing",
+            DamageType::Bludgeoning => "bludgeoning",
+            DamageType::Fire => "fire",
+            DamageType::Cold => "cold",
+            DamageType::Lightning => "lightning",
+            DamageType::Thunder => "thunder",
+            DamageType::Acid => "acid",
+            DamageType::Poison => "poison",
+            DamageType::Necrotic => "necrotic",
+            DamageType::Radiant => "radiant",
+            DamageType::Force => "force",
+            DamageType::Psychic => "psychic",
+        }
+    }
+}
+
+impl From<WeaponDamageType> for DamageType {
+    fn from(damage_type: WeaponDamageType) -> Self {
+        match damage_type {
+            WeaponDamageType::Slashing => DamageType::Slashing,
+            WeaponDamageType::Piercing => DamageType::Piercing,
+            WeaponDamageType::Bludgeoning => DamageType::Bludgeoning,
+        }
+    }
+}
+
+/// A damage resistance held by a creature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Resistance {
+    /// Resistant to all damage of one type.
+    Type(DamageType),
+    /// Resistant to bludgeoning, piercing, and slashing from nonmagical attacks.
+    NonmagicalPhysical,
+    /// Resistant to bludgeoning, piercing, and slashing from nonmagical
+    /// attacks that aren't silvered (e.g. lycanthropes).
+    NonmagicalUnsilveredPhysical,
+}
+
+impl Resistance {
+    /// Whether this resistance applies to damage of `damage_type` from an
+    /// attack with the given weapon flags.
+    pub fn applies_to(&self, damage_type: DamageType, magical: bool, silvered: bool) -> bool {
+        let physical = matches!(
+            damage_type,
+            DamageType::Bludgeoning | DamageType::Piercing | DamageType::Slashing
+        );
+        match self {
+            Resistance::Type(resisted) => *resisted == damage_type,
+            Resistance::NonmagicalPhysical => physical && !magical,
+            Resistance::NonmagicalUnsilveredPhysical => physical && !magical && !silvered,
+        }
+    }
+}
@@ -4,6 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::DurationTiming;
+
 /// Armor class calculation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArmorClass {
@@ -74,3 +76,51 @@ impl Default for Speed {
         Self::new(30)
     }
 }
+
+/// A temporary boost to walking speed (e.g. Longstrider).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeedBonus {
+    pub source: String,
+    pub feet: u32,
+}
+
+/// A temporary bonus to AC (e.g. Shield, Shield of Faith).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AcBonus {
+    pub source: String,
+    pub bonus: i8,
+    /// Turns of the character the bonus lasts, counted at `timing`. `None`
+    /// lasts until removed, e.g. when concentration on the source ends.
+    pub duration_rounds: Option<u32>,
+    #[serde(default)]
+    pub timing: DurationTiming,
+}
+
+impl AcBonus {
+    pub fn new(source: impl Into<String>, bonus: i8) -> Self {
+        Self {
+            source: source.into(),
+            bonus,
+            duration_rounds: None,
+            timing: DurationTiming::default(),
+        }
+    }
+
+    pub fn with_duration(mut self, rounds: u32, timing: DurationTiming) -> Self {
+        self.duration_rounds = Some(rounds);
+        self.timing = timing;
+        self
+    }
+
+    /// Counts down one turn if `moment` is when this bonus ticks.
+    /// Returns false once the bonus has lapsed.
+    pub fn tick(&mut self, moment: DurationTiming) -> bool {
+        match self.duration_rounds.as_mut() {
+            Some(duration) if self.timing == moment => {
+                *duration = duration.saturating_sub(1);
+                *duration > 0
+            }
+            _ => true,
+        }
+    }
+}
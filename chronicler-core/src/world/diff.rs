@@ -0,0 +1,51 @@
+//! Per-turn summaries of what changed in the world.
+//!
+//! A [`WorldDiff`] is built from the effects a turn applied (see
+//! [`WorldDiff::from_effects`]), so a UI can update only the parts of the
+//! screen that changed instead of re-reading the whole [`GameWorld`].
+//!
+//! [`GameWorld`]: super::GameWorld
+
+use super::{CharacterId, Condition};
+
+/// The net hit point change for one creature over a turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HpDelta {
+    pub target_id: CharacterId,
+    /// Total change; negative for damage.
+    pub amount: i32,
+    /// Hit points after the last change.
+    pub current: i32,
+}
+
+/// The net change in how many of an item the player carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryDelta {
+    pub item_name: String,
+    /// Units gained; negative when items were used up or given away.
+    pub quantity: i32,
+}
+
+/// Everything a turn changed that the UI displays.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorldDiff {
+    pub hp: Vec<HpDelta>,
+    pub conditions_added: Vec<(CharacterId, Condition)>,
+    pub conditions_removed: Vec<(CharacterId, Condition)>,
+    /// Concentration begun this turn and still held, with the spell.
+    pub concentration_started: Vec<(CharacterId, String)>,
+    /// Creatures whose concentration ended this turn.
+    pub concentration_ended: Vec<CharacterId>,
+    pub inventory: Vec<InventoryDelta>,
+    /// Where the player ended up, if they moved.
+    pub location: Option<String>,
+    /// Names of quests started this turn.
+    pub new_quests: Vec<String>,
+}
+
+impl WorldDiff {
+    /// Whether the turn changed nothing the diff tracks.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
@@ -66,6 +66,52 @@ pub struct Inventory {
     pub silver: i32,
 }
 
+/// How burdened a character is by what they carry (the variant encumbrance
+/// rule: thresholds at 5, 10, and 15 times the Strength score, in pounds).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EncumbranceLevel {
+    Unencumbered,
+    /// Speed drops by 10 feet.
+    Encumbered,
+    /// Speed drops by 20 feet, with disadvantage on STR, DEX, and CON rolls.
+    HeavilyEncumbered,
+    /// More than the character can carry.
+    OverCapacity,
+}
+
+impl EncumbranceLevel {
+    pub fn for_load(weight: f32, strength: u8) -> Self {
+        let strength = strength as f32;
+        if weight > strength * 15.0 {
+            EncumbranceLevel::OverCapacity
+        } else if weight > strength * 10.0 {
+            EncumbranceLevel::HeavilyEncumbered
+        } else if weight > strength * 5.0 {
+            EncumbranceLevel::Encumbered
+        } else {
+            EncumbranceLevel::Unencumbered
+        }
+    }
+
+    /// Feet of walking speed lost to the load.
+    pub fn speed_penalty(&self) -> u32 {
+        match self {
+            EncumbranceLevel::Unencumbered => 0,
+            EncumbranceLevel::Encumbered => 10,
+            EncumbranceLevel::HeavilyEncumbered | EncumbranceLevel::OverCapacity => 20,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EncumbranceLevel::Unencumbered => "Unencumbered",
+            EncumbranceLevel::Encumbered => "Encumbered",
+            EncumbranceLevel::HeavilyEncumbered => "Heavily Encumbered",
+            EncumbranceLevel::OverCapacity => "Over Capacity",
+        }
+    }
+}
+
 // ============================================================================
 // Equipment System
 // ============================================================================
@@ -148,6 +194,12 @@ pub struct WeaponItem {
     pub damage_type: WeaponDamageType,
     pub properties: Vec<WeaponProperty>,
     pub range: Option<(u32, u32)>,
+    /// Coated in silver, bypassing resistances of creatures like lycanthropes
+    #[serde(default)]
+    pub silvered: bool,
+    /// Inventory item this weapon fires (e.g. "Arrows"), if it uses ammunition
+    #[serde(default)]
+    pub ammunition: Option<String>,
 }
 
 impl WeaponItem {
@@ -170,6 +222,8 @@ impl WeaponItem {
             damage_type,
             properties: Vec::new(),
             range: None,
+            silvered: false,
+            ammunition: None,
         }
     }
 
@@ -198,6 +252,16 @@ impl WeaponItem {
         self
     }
 
+    pub fn silvered(mut self) -> Self {
+        self.silvered = true;
+        self
+    }
+
+    pub fn with_ammunition(mut self, ammunition: impl Into<String>) -> Self {
+        self.ammunition = Some(ammunition.into());
+        self
+    }
+
     pub fn is_finesse(&self) -> bool {
         self.properties.contains(&WeaponProperty::Finesse)
     }
@@ -220,6 +284,19 @@ impl WeaponItem {
     }
 }
 
+/// Units of `ammunition` held by one item named `item_name`: 1 for a loose
+/// unit ("Arrows"), the bundle size for a bundle ("Arrows (20)").
+fn ammunition_units(item_name: &str, ammunition: &str) -> Option<u32> {
+    if item_name.eq_ignore_ascii_case(ammunition) {
+        return Some(1);
+    }
+    let (name, size) = item_name.rsplit_once(" (")?;
+    if !name.eq_ignore_ascii_case(ammunition) {
+        return None;
+    }
+    size.strip_suffix(')')?.parse().ok()
+}
+
 /// Weapon damage type (separate from spell/effect damage types).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WeaponDamageType {
@@ -339,6 +416,20 @@ impl Inventory {
             .sum()
     }
 
+    /// Combined value of the items carried, in gold pieces (coins not included).
+    pub fn total_value(&self) -> f32 {
+        self.items
+            .iter()
+            .map(|i| i.value_gp * i.quantity as f32)
+            .sum()
+    }
+
+    /// How burdened a character with the given Strength score is by this
+    /// inventory.
+    pub fn encumbrance(&self, strength: u8) -> EncumbranceLevel {
+        EncumbranceLevel::for_load(self.total_weight(), strength)
+    }
+
     /// Add an item to the inventory.
     /// Stackable items (potions, scrolls, adventuring gear, etc.) stack with existing items.
     /// Non-stackable items (weapons, armor, shields) are added as separate entries.
@@ -387,6 +478,55 @@ impl Inventory {
             .find(|i| i.name.to_lowercase() == name.to_lowercase())
     }
 
+    /// Count loose and bundled units of an ammunition type.
+    ///
+    /// Bundles are items named like "Arrows (20)" and count for their size.
+    pub fn ammunition_count(&self, ammunition: &str) -> u32 {
+        self.items
+            .iter()
+            .filter_map(|i| ammunition_units(&i.name, ammunition).map(|n| n * i.quantity))
+            .sum()
+    }
+
+    /// Spend one unit of ammunition, opening a bundle if no loose units remain.
+    /// Returns false if none is left.
+    pub fn spend_ammunition(&mut self, ammunition: &str) -> bool {
+        if self.remove_item(ammunition, 1) {
+            return true;
+        }
+
+        let Some((idx, size)) = self.items.iter().enumerate().find_map(|(idx, i)| {
+            ammunition_units(&i.name, ammunition)
+                .filter(|&n| n > 1)
+                .map(|n| (idx, n))
+        }) else {
+            return false;
+        };
+
+        let bundle = self.items[idx].clone();
+        self.remove_item(&bundle.name, 1);
+        self.add_item(Item {
+            name: ammunition.to_string(),
+            quantity: size - 1,
+            weight: bundle.weight / size as f32,
+            value_gp: bundle.value_gp / size as f32,
+            description: None,
+            ..bundle
+        });
+        true
+    }
+
+    /// Find an item usable as a costly spell component: its name contains
+    /// `keyword` and it is worth at least `min_value_gp`. Items without a
+    /// recorded value are assumed to be worth enough.
+    pub fn find_component(&self, keyword: &str, min_value_gp: u32) -> Option<&Item> {
+        let keyword = keyword.to_lowercase();
+        self.items.iter().find(|i| {
+            i.name.to_lowercase().contains(&keyword)
+                && (i.value_gp <= 0.0 || i.value_gp >= min_value_gp as f32)
+        })
+    }
+
     /// Check if the inventory contains an item.
     pub fn has_item(&self, name: &str) -> bool {
         self.find_item(name).is_some()
@@ -7,10 +7,10 @@
 //! [`mechanics`](super::mechanics) submodule and called from the methods here.
 
 use super::{
-    mechanics, Ability, ArmorType, Character, CharacterClass, CharacterId, ClassLevel, CombatState,
-    Feature, FeatureUses, GameTime, HitPoints, Location, LocationId, LocationType,
-    ProficiencyLevel, Quest, RechargeType, Skill, SlotInfo, SpellSlots, SpellcastingData, Subclass,
-    NPC,
+    mechanics, sorted, Ability, ArmorType, Character, CharacterClass, CharacterId, ClassLevel,
+    CombatState, Feature, FeatureUses, GameTime, HitPoints, HouseRules, LightLevel, Location,
+    LocationEdge, LocationGraph, LocationId, LocationNode, LocationType, ProficiencyLevel, Quest,
+    RechargeType, Skill, SlotInfo, SpellSlots, SpellcastingData, Subclass, NPC,
 };
 use crate::dice::DieType;
 use serde::{Deserialize, Serialize};
@@ -46,6 +46,8 @@ pub enum NarrativeType {
     NpcDialogue,
     Combat,
     System,
+    /// Mechanical outcome of a single resolved tool call.
+    Mechanics,
 }
 
 /// The complete game world state.
@@ -58,6 +60,7 @@ pub struct GameWorld {
     pub player_character: Character,
 
     // NPCs
+    #[serde(with = "sorted::by_key")]
     pub npcs: HashMap<CharacterId, NPC>,
 
     // Current state
@@ -67,11 +70,24 @@ pub struct GameWorld {
     pub game_time: GameTime,
 
     // Locations
+    #[serde(with = "sorted::by_key")]
     pub known_locations: HashMap<LocationId, Location>,
 
     // Campaign progress
     pub quests: Vec<Quest>,
+    /// Narrative entries in order. A session with a
+    /// [`NarrativeSpill`](crate::narrative_log::NarrativeSpill) keeps only the
+    /// most recent here.
     pub narrative_history: Vec<NarrativeEntry>,
+
+    /// Party reputation with each faction, from -100 (sworn enemies) to 100
+    /// (revered allies).
+    #[serde(default, with = "sorted::pairs")]
+    pub factions: HashMap<String, i32>,
+
+    /// Table-specific rule variations.
+    #[serde(default)]
+    pub house_rules: HouseRules,
 }
 
 impl GameWorld {
@@ -83,7 +99,7 @@ impl GameWorld {
         known_locations.insert(starting_location.id, starting_location.clone());
 
         Self {
-            session_id: Uuid::new_v4(),
+            session_id: super::new_uuid(),
             campaign_name: campaign_name.into(),
             player_character,
             npcs: HashMap::new(),
@@ -94,6 +110,8 @@ impl GameWorld {
             known_locations,
             quests: Vec::new(),
             narrative_history: Vec::new(),
+            factions: HashMap::new(),
+            house_rules: HouseRules::default(),
         }
     }
 
@@ -116,13 +134,23 @@ impl GameWorld {
         mechanics::next_turn(self)
     }
 
+    /// Let `minutes` pass. Features that recharge at dawn come back if dawn
+    /// breaks along the way.
+    pub fn advance_time(&mut self, minutes: u32) {
+        let before = self.game_time.clone();
+        self.game_time.advance_minutes(minutes);
+        if before.dawns_until(&self.game_time) > 0 {
+            mechanics::apply_dawn_recharge(&mut self.player_character);
+        }
+    }
+
     /// Take a short rest (1 hour).
     ///
-    /// - Warlocks recover all spell slots (Pact Magic)
+    /// - Warlocks recover all Pact Magic slots
     /// - Features that recharge on short rest are restored
     /// - Class-specific resources that recharge on short rest are restored
     pub fn short_rest(&mut self) {
-        self.game_time.advance_hours(1);
+        self.advance_time(60);
         mechanics::apply_short_rest(&mut self.player_character);
     }
 
@@ -136,10 +164,95 @@ impl GameWorld {
     /// - All features that recharge on short or long rest are restored
     /// - Class-specific resources that recharge on long rest are restored
     pub fn long_rest(&mut self) {
-        self.game_time.advance_hours(8);
+        self.advance_time(8 * 60);
         mechanics::apply_long_rest(&mut self.player_character);
     }
 
+    /// The light level where the party is: the combat override if set, then
+    /// the location's fixed lighting, then daylight from the time of day.
+    pub fn light_level(&self) -> LightLevel {
+        self.combat
+            .as_ref()
+            .and_then(|c| c.light_level)
+            .or(self.current_location.light_level)
+            .unwrap_or_else(|| self.game_time.time_of_day().light_level())
+    }
+
+    /// Project the known locations and their connections into a graph for
+    /// rendering a map. Nodes are sorted by name; connections leading to
+    /// places the party doesn't know yet are left out.
+    pub fn location_graph(&self) -> LocationGraph {
+        let mut nodes: Vec<LocationNode> = self
+            .known_locations
+            .values()
+            .map(|location| LocationNode {
+                id: location.id,
+                name: location.name.clone(),
+                location_type: location.location_type,
+                is_current: location.id == self.current_location.id,
+            })
+            .collect();
+        nodes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let edges = nodes
+            .iter()
+            .flat_map(|node| {
+                self.known_locations[&node.id]
+                    .connections
+                    .iter()
+                    .filter(|c| self.known_locations.contains_key(&c.destination_id))
+                    .map(|c| LocationEdge {
+                        from: node.id,
+                        to: c.destination_id,
+                        direction: c.direction.clone(),
+                        travel_time_minutes: c.travel_time_minutes,
+                    })
+            })
+            .collect();
+
+        LocationGraph { nodes, edges }
+    }
+
+    /// Move every NPC with a daily routine to where it puts them at the
+    /// current time. NPCs whose routine has a gap stay where they are.
+    pub fn follow_npc_schedules(&mut self) {
+        for npc in self.npcs.values_mut() {
+            if let Some(location) = npc.scheduled_location(&self.game_time) {
+                npc.location_id = Some(location);
+            }
+        }
+    }
+
+    /// NPCs at the party's current location, sorted by name.
+    pub fn npcs_here(&self) -> Vec<&NPC> {
+        let here = self.current_location.id;
+        let mut npcs: Vec<&NPC> = self
+            .npcs
+            .values()
+            .filter(|npc| npc.location_id == Some(here))
+            .collect();
+        npcs.sort_by(|a, b| a.name.cmp(&b.name));
+        npcs
+    }
+
+    /// The name a faction is tracked under, matching case-insensitively.
+    pub fn faction_name<'a>(&'a self, faction: &'a str) -> &'a str {
+        self.factions
+            .keys()
+            .find(|name| name.eq_ignore_ascii_case(faction))
+            .map(String::as_str)
+            .unwrap_or(faction)
+    }
+
+    /// The party's reputation with a faction (0 if they have never dealt
+    /// with it).
+    pub fn reputation(&self, faction: &str) -> i32 {
+        self.factions
+            .get(self.faction_name(faction))
+            .copied()
+            .unwrap_or(0)
+    }
+
     pub fn add_narrative(&mut self, content: String, entry_type: NarrativeType) {
         self.narrative_history.push(NarrativeEntry {
             content,
@@ -153,6 +266,20 @@ impl GameWorld {
     }
 }
 
+/// Lowest and highest reputation a faction can hold.
+pub const REPUTATION_RANGE: std::ops::RangeInclusive<i32> = -100..=100;
+
+/// Describe a reputation score in words.
+pub fn reputation_standing(reputation: i32) -> &'static str {
+    match reputation {
+        i32::MIN..=-50 => "hostile",
+        -49..=-11 => "unfriendly",
+        -10..=10 => "neutral",
+        11..=49 => "friendly",
+        _ => "allied",
+    }
+}
+
 /// Create a sample fighter character for testing.
 pub fn create_sample_fighter(name: &str) -> Character {
     use super::{AbilityScores, ArmorClass};
@@ -386,6 +513,7 @@ pub fn create_sample_paladin(name: &str) -> Character {
     character.spellcasting = Some(SpellcastingData {
         ability: Ability::Charisma,
         spells_known: vec![],
+        spellbook: Vec::new(),
         spells_prepared: vec!["Cure Wounds".to_string(), "Shield of Faith".to_string()],
         cantrips_known: vec![],
         spell_slots: {
@@ -393,6 +521,7 @@ pub fn create_sample_paladin(name: &str) -> Character {
             slots.slots[0] = SlotInfo { total: 3, used: 0 }; // 3 first-level slots at level 3
             slots
         },
+        pact_slots: None,
     });
 
     character.features.push(Feature {
@@ -471,6 +600,7 @@ pub fn create_sample_druid(name: &str) -> Character {
     character.spellcasting = Some(SpellcastingData {
         ability: Ability::Wisdom,
         spells_known: vec![],
+        spellbook: Vec::new(),
         spells_prepared: vec![
             "Cure Wounds".to_string(),
             "Entangle".to_string(),
@@ -483,6 +613,7 @@ pub fn create_sample_druid(name: &str) -> Character {
             slots.slots[1] = SlotInfo { total: 2, used: 0 }; // 2 second-level slots
             slots
         },
+        pact_slots: None,
     });
 
     character.features.push(Feature {
@@ -542,6 +673,7 @@ pub fn create_sample_cleric(name: &str) -> Character {
     character.spellcasting = Some(SpellcastingData {
         ability: Ability::Wisdom,
         spells_known: vec![],
+        spellbook: Vec::new(),
         spells_prepared: vec![
             "Cure Wounds".to_string(),
             "Bless".to_string(),
@@ -558,6 +690,7 @@ pub fn create_sample_cleric(name: &str) -> Character {
             slots.slots[1] = SlotInfo { total: 2, used: 0 };
             slots
         },
+        pact_slots: None,
     });
 
     character.features.push(Feature {
@@ -635,6 +768,7 @@ pub fn create_sample_bard(name: &str) -> Character {
             "Dissonant Whispers".to_string(),
             "Hideous Laughter".to_string(),
         ],
+        spellbook: Vec::new(),
         spells_prepared: vec![],
         cantrips_known: vec!["Vicious Mockery".to_string(), "Minor Illusion".to_string()],
         spell_slots: {
@@ -643,6 +777,7 @@ pub fn create_sample_bard(name: &str) -> Character {
             slots.slots[1] = SlotInfo { total: 2, used: 0 };
             slots
         },
+        pact_slots: None,
     });
 
     character.features.push(Feature {
@@ -717,6 +852,7 @@ pub fn create_sample_sorcerer(name: &str) -> Character {
             "Burning Hands".to_string(),
             "Scorching Ray".to_string(),
         ],
+        spellbook: Vec::new(),
         spells_prepared: vec![],
         cantrips_known: vec![
             "Fire Bolt".to_string(),
@@ -730,6 +866,7 @@ pub fn create_sample_sorcerer(name: &str) -> Character {
             slots.slots[1] = SlotInfo { total: 2, used: 0 };
             slots
         },
+        pact_slots: None,
     });
 
     character.features.push(Feature {
@@ -760,8 +897,8 @@ pub fn create_sample_sorcerer(name: &str) -> Character {
 mod tests {
     use super::*;
     use crate::world::{
-        AbilityScores, ArmorItem, ArmorType, Inventory, Item, ItemType, WeaponDamageType,
-        WeaponItem, WeaponProperty,
+        AbilityScores, ArmorItem, ArmorType, Condition, EncumbranceLevel, Inventory, Item,
+        ItemType, LocationConnection, SpeedBonus, WeaponDamageType, WeaponItem, WeaponProperty,
     };
 
     #[test]
@@ -990,6 +1127,171 @@ mod tests {
         assert_eq!(character.current_ac(), 18);
     }
 
+    #[test]
+    fn test_rogue_expertise_doubles_proficiency() {
+        let mut rogue = Character::new("Rogue");
+        rogue.level = 5;
+        rogue.ability_scores.dexterity = 16;
+        rogue.classes.push(ClassLevel {
+            class: CharacterClass::Rogue,
+            level: 5,
+            subclass: None,
+        });
+        rogue
+            .skill_proficiencies
+            .insert(Skill::Stealth, ProficiencyLevel::Expertise);
+        rogue
+            .skill_proficiencies
+            .insert(Skill::Acrobatics, ProficiencyLevel::Proficient);
+
+        // DEX +3, proficiency +3
+        assert_eq!(rogue.skill_modifier(Skill::Stealth), 3 + 6);
+        assert_eq!(rogue.skill_modifier(Skill::Acrobatics), 3 + 3);
+        // No Jack of All Trades for a Rogue
+        assert_eq!(rogue.skill_modifier(Skill::SleightOfHand), 3);
+    }
+
+    #[test]
+    fn test_bard_jack_of_all_trades_adds_half_proficiency() {
+        let mut bard = create_sample_bard("Lyra");
+        bard.level = 5;
+        bard.classes[0].level = 5;
+        assert!(bard.has_jack_of_all_trades());
+
+        // CHA +3, proficiency +3
+        assert_eq!(bard.skill_modifier(Skill::Performance), 3 + 3);
+        // Untrained: INT +1, half of +3 rounded down
+        assert_eq!(bard.skill_modifier(Skill::Arcana), 1 + 1);
+        bard.skill_proficiencies
+            .insert(Skill::Arcana, ProficiencyLevel::None);
+        assert_eq!(bard.skill_modifier(Skill::Arcana), 1 + 1);
+
+        // Not until 2nd level
+        bard.level = 1;
+        bard.classes[0].level = 1;
+        assert!(!bard.has_jack_of_all_trades());
+        assert_eq!(bard.skill_modifier(Skill::Arcana), 1);
+    }
+
+    #[test]
+    fn test_inventory_weight_value_and_encumbrance() {
+        let item = |name: &str, quantity, weight, value_gp, item_type| Item {
+            name: name.to_string(),
+            quantity,
+            weight,
+            value_gp,
+            description: None,
+            item_type,
+            magical: false,
+        };
+        let inventory = Inventory {
+            items: vec![
+                item("Longsword", 1, 3.0, 15.0, ItemType::Weapon),
+                item("Chain Mail", 1, 55.0, 75.0, ItemType::Armor),
+                item("Healing Potion", 3, 0.5, 50.0, ItemType::Potion),
+                item("Rations", 10, 2.0, 0.5, ItemType::Adventuring),
+            ],
+            gold: 100,
+            silver: 0,
+        };
+
+        // 3 + 55 + 1.5 + 20
+        assert_eq!(inventory.total_weight(), 79.5);
+        // 15 + 75 + 150 + 5; coins are not counted
+        assert_eq!(inventory.total_value(), 245.0);
+
+        assert_eq!(inventory.encumbrance(16), EncumbranceLevel::Unencumbered);
+        assert_eq!(inventory.encumbrance(10), EncumbranceLevel::Encumbered);
+        assert_eq!(
+            inventory.encumbrance(7),
+            EncumbranceLevel::HeavilyEncumbered
+        );
+        assert_eq!(inventory.encumbrance(5), EncumbranceLevel::OverCapacity);
+        assert_eq!(
+            Inventory::default().encumbrance(3),
+            EncumbranceLevel::Unencumbered
+        );
+    }
+
+    #[test]
+    fn test_effective_speed_with_heavy_armor_and_grappled() {
+        let mut character = Character::new("Test");
+        character.ability_scores.strength = 13;
+        assert_eq!(character.effective_speed(), 30);
+
+        // Plate needs STR 15: 30 - 10
+        character.equipment.armor =
+            Some(ArmorItem::new("Plate Armor", ArmorType::Heavy, 18).with_strength_requirement(15));
+        assert_eq!(character.effective_speed(), 20);
+
+        // Grappled halves what the armor leaves
+        character.add_condition(Condition::Grappled, "Ogre");
+        assert_eq!(character.effective_speed(), 10);
+
+        // Longstrider applies before halving: (30 + 10 - 10) / 2
+        character.speed_bonuses.push(SpeedBonus {
+            source: "Longstrider".to_string(),
+            feet: 10,
+        });
+        assert_eq!(character.effective_speed(), 15);
+
+        character.add_condition(Condition::Restrained, "Web");
+        assert_eq!(character.effective_speed(), 0);
+    }
+
+    #[test]
+    fn test_effective_speed_with_load_and_prone() {
+        let mut character = Character::new("Test");
+        character.ability_scores.strength = 10;
+
+        // Prone means crawling, which costs extra movement but leaves speed alone
+        character.add_condition(Condition::Prone, "Shove");
+        assert_eq!(character.effective_speed(), 30);
+
+        // STR 10: encumbered past 50 lb, heavily past 100, over capacity past 150
+        character.inventory.items.push(Item {
+            name: "Stone Blocks".to_string(),
+            quantity: 6,
+            weight: 10.0,
+            value_gp: 0.0,
+            description: None,
+            item_type: ItemType::Other,
+            magical: false,
+        });
+        assert_eq!(character.effective_speed(), 20);
+        character.inventory.items[0].quantity = 12;
+        assert_eq!(character.effective_speed(), 10);
+        character.inventory.items[0].quantity = 18;
+        assert_eq!(character.effective_speed(), 5);
+    }
+
+    #[test]
+    fn test_effective_speed_class_features() {
+        let mut monk = Character::new("Monk");
+        monk.classes.push(ClassLevel {
+            class: CharacterClass::Monk,
+            level: 6,
+            subclass: None,
+        });
+        assert_eq!(monk.effective_speed(), 45);
+        // Exhaustion halves speed from level 2 and stops it at level 5
+        for (level, speed) in [(1, 45), (2, 22), (4, 22), (5, 0)] {
+            monk.conditions.clear();
+            monk.add_condition(Condition::Exhaustion(level), "Forced march");
+            assert_eq!(monk.effective_speed(), speed);
+        }
+
+        let mut barbarian = Character::new("Barbarian");
+        barbarian.classes.push(ClassLevel {
+            class: CharacterClass::Barbarian,
+            level: 5,
+            subclass: None,
+        });
+        assert_eq!(barbarian.effective_speed(), 40);
+        barbarian.equipment.armor = Some(ArmorItem::new("Chain Mail", ArmorType::Heavy, 16));
+        assert_eq!(barbarian.effective_speed(), 30);
+    }
+
     #[test]
     fn test_weapon_item() {
         let sword = WeaponItem::new("Longsword", "1d8", WeaponDamageType::Slashing)
@@ -1076,4 +1378,173 @@ mod tests {
             "A wandering adventurer seeking glory."
         );
     }
+
+    #[test]
+    fn test_location_graph() {
+        let mut world = GameWorld::new("Test", Character::new("Test"));
+        let town_id = world.current_location.id;
+        let forest = Location::new("Darkwood", LocationType::Wilderness);
+        let cave = Location::new("Hollow Cave", LocationType::Cave);
+        let (forest_id, cave_id) = (forest.id, cave.id);
+
+        let connect = |to: &Location, direction: &str, minutes: u32| LocationConnection {
+            destination_id: to.id,
+            destination_name: to.name.clone(),
+            direction: Some(direction.to_string()),
+            travel_time_minutes: minutes,
+        };
+        let town = world.known_locations.get_mut(&town_id).unwrap();
+        town.connections.push(connect(&forest, "north", 60));
+        let mut forest = forest;
+        forest
+            .connections
+            .push(connect(&world.current_location, "south", 60));
+        forest.connections.push(connect(&cave, "east", 30));
+        // A path to somewhere not yet discovered is left off the map
+        forest.connections.push(LocationConnection {
+            destination_id: LocationId::new(),
+            destination_name: "Unknown Ruins".to_string(),
+            direction: None,
+            travel_time_minutes: 120,
+        });
+        world.known_locations.insert(forest_id, forest);
+        world.known_locations.insert(cave_id, cave);
+
+        let graph = world.location_graph();
+
+        assert_eq!(graph.nodes.len(), 3);
+        let current: Vec<_> = graph.nodes.iter().filter(|n| n.is_current).collect();
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].id, town_id);
+
+        let edge = |from, to, direction: &str, minutes| LocationEdge {
+            from,
+            to,
+            direction: Some(direction.to_string()),
+            travel_time_minutes: minutes,
+        };
+        assert_eq!(graph.edges.len(), 3);
+        assert!(graph.edges.contains(&edge(town_id, forest_id, "north", 60)));
+        assert!(graph.edges.contains(&edge(forest_id, town_id, "south", 60)));
+        assert!(graph.edges.contains(&edge(forest_id, cave_id, "east", 30)));
+    }
+
+    #[test]
+    fn test_serialization_is_deterministic() {
+        let mut world = GameWorld::new("Test", Character::new("Test"));
+        for name in ["Mira", "Bram", "Old Tess", "Corwin", "Ysolde"] {
+            let home = Location::new(format!("{name}'s House"), LocationType::Building);
+            world.known_locations.insert(home.id, home);
+            let npc = NPC::new(name);
+            world.npcs.insert(npc.id, npc);
+            world.factions.insert(format!("{name} Guild"), 5);
+        }
+
+        // Saved mid-combat, with every combatant's turn bookkeeping filled in
+        let mut combat = crate::world::CombatState::new();
+        for (i, npc) in world.npcs.values().enumerate() {
+            combat.attacks_this_turn.insert(npc.id, 1);
+            combat
+                .actions_this_turn
+                .insert(npc.id, crate::world::TurnActions::default());
+            combat.sneak_attack_used.insert(npc.id);
+            combat
+                .recoverable_ammunition
+                .insert(format!("{} Arrow", npc.name), i as u32);
+        }
+        world.combat = Some(combat);
+
+        // The same world with its maps rebuilt in reverse insertion order
+        fn reversed<K: std::hash::Hash + Eq + Clone, V: Clone>(
+            map: &HashMap<K, V>,
+        ) -> HashMap<K, V> {
+            let mut entries: Vec<_> = map.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+            entries.reverse();
+            entries.into_iter().collect()
+        }
+        let mut copy = world.clone();
+        copy.npcs = reversed(&world.npcs);
+        copy.known_locations = reversed(&world.known_locations);
+        copy.factions = reversed(&world.factions);
+        let combat = world.combat.as_ref().unwrap();
+        let copy_combat = copy.combat.as_mut().unwrap();
+        copy_combat.attacks_this_turn = reversed(&combat.attacks_this_turn);
+        copy_combat.actions_this_turn = reversed(&combat.actions_this_turn);
+        copy_combat.recoverable_ammunition = reversed(&combat.recoverable_ammunition);
+        let mut used: Vec<_> = combat.sneak_attack_used.iter().copied().collect();
+        used.reverse();
+        copy_combat.sneak_attack_used = used.into_iter().collect();
+
+        let json = serde_json::to_string_pretty(&world).unwrap();
+        assert_eq!(serde_json::to_string_pretty(&copy).unwrap(), json);
+
+        // Round-tripping through the sorted form keeps the maps intact
+        let loaded: GameWorld = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.npcs.len(), 5);
+        assert_eq!(loaded.known_locations.len(), 6);
+        assert_eq!(loaded.reputation("Bram Guild"), 5);
+        assert_eq!(loaded.combat.as_ref().unwrap().actions_this_turn.len(), 5);
+        assert_eq!(serde_json::to_string_pretty(&loaded).unwrap(), json);
+    }
+
+    #[test]
+    fn test_deserialize_map_backed_fields_from_legacy_maps() {
+        let mut world = GameWorld::new("Test", Character::new("Test"));
+        let npc = NPC::new("Bram");
+        world.npcs.insert(npc.id, npc.clone());
+        world.factions.insert("City Watch".to_string(), 10);
+
+        let mut json = serde_json::to_value(&world).unwrap();
+        let as_map = |list: &serde_json::Value| -> serde_json::Value {
+            list.as_array()
+                .unwrap()
+                .iter()
+                .map(|v| (v["id"].as_str().unwrap().to_string(), v.clone()))
+                .collect::<serde_json::Map<_, _>>()
+                .into()
+        };
+        json["npcs"] = as_map(&json["npcs"]);
+        json["known_locations"] = as_map(&json["known_locations"]);
+        json["factions"] = serde_json::json!({ "City Watch": 10 });
+
+        let loaded: GameWorld = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.npcs[&npc.id].name, "Bram");
+        assert!(loaded
+            .known_locations
+            .contains_key(&world.current_location.id));
+        assert_eq!(loaded.reputation("City Watch"), 10);
+    }
+
+    #[test]
+    fn test_dawn_restores_dawn_features_without_rest() {
+        let mut character = create_sample_fighter("Roland");
+        character.features.push(Feature {
+            name: "Stone's Endurance".to_string(),
+            description: "Reduce damage once per day".to_string(),
+            source: "Goliath".to_string(),
+            uses: Some(FeatureUses {
+                current: 0,
+                maximum: 1,
+                recharge: RechargeType::Dawn,
+            }),
+        });
+        let mut world = GameWorld::new("Test", character);
+        world.game_time = GameTime::new(1492, 3, 1, 22, 0);
+        let uses = |world: &GameWorld| {
+            world
+                .player_character
+                .features
+                .iter()
+                .find(|f| f.name == "Stone's Endurance")
+                .and_then(|f| f.uses.as_ref())
+                .unwrap()
+                .current
+        };
+
+        world.advance_time(6 * 60);
+        assert_eq!(uses(&world), 0);
+
+        world.advance_time(3 * 60);
+        assert_eq!(uses(&world), 1);
+    }
 }
@@ -50,9 +50,13 @@ impl HitPoints {
         }
     }
 
+    /// Restore up to `amount` hit points, returning how many were restored.
+    ///
+    /// Healing a character below 0 counts up from 0, so any healing wakes
+    /// them.
     pub fn heal(&mut self, amount: i32) -> i32 {
-        let old = self.current;
-        self.current = (self.current + amount).min(self.maximum);
+        let old = self.current.max(0);
+        self.current = (old + amount).min(self.maximum);
         self.current - old
     }
 
@@ -89,8 +93,10 @@ impl HitDice {
     }
 
     pub fn add(&mut self, die_type: DieType, count: u8) {
-        *self.total.entry(die_type).or_insert(0) += count;
-        *self.remaining.entry(die_type).or_insert(0) += count;
+        let total = self.total.entry(die_type).or_insert(0);
+        *total = total.saturating_add(count);
+        let remaining = self.remaining.entry(die_type).or_insert(0);
+        *remaining = remaining.saturating_add(count);
     }
 
     pub fn spend(&mut self, die_type: DieType) -> bool {
@@ -256,7 +262,7 @@ mod tests {
 
         let healed = hp.heal(10);
 
-        assert_eq!(hp.current, 5);
+        assert_eq!(hp.current, 10);
         assert_eq!(healed, 10);
     }
 
@@ -0,0 +1,91 @@
+//! Optional table rules that change how the core rules resolve.
+//!
+//! This module provides [`HouseRules`], stored on the game world, and the
+//! [`CritRule`] that decides how critical hits deal damage.
+
+use serde::{Deserialize, Serialize};
+
+use crate::dice::DiceExpression;
+
+/// Table-specific variations on the standard rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HouseRules {
+    /// How critical hits deal damage.
+    #[serde(default)]
+    pub crit_rule: CritRule,
+}
+
+/// How the damage dice of a critical hit are resolved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CritRule {
+    /// Roll all damage dice twice (the standard rule).
+    #[default]
+    DoubleDice,
+    /// The normal dice deal maximum damage, then the crit dice are rolled.
+    MaxPlusRoll,
+    /// Both sets of dice deal maximum damage.
+    Brutal,
+}
+
+impl CritRule {
+    /// The notation to roll for `dice` (e.g. "1d8") on a critical hit.
+    ///
+    /// A flat damage value has no dice to roll, so it is simply doubled.
+    pub fn critical_dice(&self, dice: &str) -> String {
+        let Ok(expr) = DiceExpression::parse(dice) else {
+            return dice.to_string();
+        };
+        if expr.components.is_empty() {
+            return (expr.modifier * 2).to_string();
+        }
+
+        let mut rolled = Vec::new();
+        let mut maxed = expr.modifier;
+        for component in &expr.components {
+            let count = component.count;
+            let sides = component.die_type.sides();
+            let max = (count * sides) as i32;
+            match self {
+                CritRule::DoubleDice => rolled.push(format!("{}d{sides}", count * 2)),
+                CritRule::MaxPlusRoll => {
+                    rolled.push(format!("{count}d{sides}"));
+                    maxed += max;
+                }
+                CritRule::Brutal => maxed += max * 2,
+            }
+        }
+
+        if maxed != 0 || rolled.is_empty() {
+            rolled.push(maxed.to_string());
+        }
+        rolled.join("+")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_critical_dice_by_rule() {
+        assert_eq!(CritRule::DoubleDice.critical_dice("1d8"), "2d8");
+        assert_eq!(CritRule::MaxPlusRoll.critical_dice("2d6"), "2d6+12");
+        assert_eq!(CritRule::Brutal.critical_dice("1d12"), "24");
+    }
+
+    #[test]
+    fn test_critical_flat_damage_doubles() {
+        for rule in [
+            CritRule::DoubleDice,
+            CritRule::MaxPlusRoll,
+            CritRule::Brutal,
+        ] {
+            assert_eq!(rule.critical_dice("1"), "2");
+        }
+    }
+
+    #[test]
+    fn test_default_crit_rule_doubles_dice() {
+        assert_eq!(HouseRules::default().crit_rule, CritRule::DoubleDice);
+    }
+}
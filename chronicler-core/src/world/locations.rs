@@ -5,7 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use super::{CharacterId, LocationId};
+use super::{CharacterId, LightLevel, LocationId};
 
 /// A location in the game world.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +17,9 @@ pub struct Location {
     pub connections: Vec<LocationConnection>,
     pub npcs_present: Vec<CharacterId>,
     pub items: Vec<String>,
+    /// Fixed light level (e.g. an unlit cave), or None to follow the time of day.
+    #[serde(default)]
+    pub light_level: Option<LightLevel>,
 }
 
 impl Location {
@@ -29,6 +32,7 @@ impl Location {
             connections: Vec::new(),
             npcs_present: Vec::new(),
             items: Vec::new(),
+            light_level: None,
         }
     }
 
@@ -58,3 +62,29 @@ pub struct LocationConnection {
     pub direction: Option<String>,
     pub travel_time_minutes: u32,
 }
+
+/// Known locations and the paths between them, flattened for drawing a map.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LocationGraph {
+    pub nodes: Vec<LocationNode>,
+    pub edges: Vec<LocationEdge>,
+}
+
+/// A known location on the map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationNode {
+    pub id: LocationId,
+    pub name: String,
+    pub location_type: LocationType,
+    /// Whether the party is here
+    pub is_current: bool,
+}
+
+/// A one-way path from one known location to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocationEdge {
+    pub from: LocationId,
+    pub to: LocationId,
+    pub direction: Option<String>,
+    pub travel_time_minutes: u32,
+}
@@ -8,11 +8,11 @@
 //!
 //! ## Submodules
 //!
-//! - [`rest`]: Short rest and long rest recovery mechanics
+//! - [`rest`]: Short rest, long rest, and dawn recovery mechanics
 //! - [`combat`]: Combat state transitions and turn management
 
 mod combat;
 mod rest;
 
 pub use combat::{end_combat, next_turn, start_combat};
-pub use rest::{apply_long_rest, apply_short_rest};
+pub use rest::{apply_dawn_recharge, apply_long_rest, apply_short_rest};
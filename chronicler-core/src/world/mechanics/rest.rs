@@ -8,30 +8,33 @@
 //! - Class-specific resource recovery
 //! - Condition removal (exhaustion, unconscious)
 
-use crate::world::{Character, CharacterClass, Condition, RechargeType};
+use crate::world::{Character, CharacterClass, Condition, Feature, RechargeType};
 
 /// Apply short rest recovery to a character.
 ///
 /// Short rest (1 hour):
-/// - Warlocks recover all spell slots (Pact Magic)
+/// - Warlocks recover all Pact Magic slots
 /// - Features that recharge on short rest are restored
 /// - Class-specific resources that recharge on short rest are restored
 pub fn apply_short_rest(character: &mut Character) {
-    // Warlocks recover all spell slots on short rest (Pact Magic)
-    let is_warlock = character
-        .classes
-        .iter()
-        .any(|c| c.class == CharacterClass::Warlock);
-    if is_warlock {
-        if let Some(ref mut spellcasting) = character.spellcasting {
-            spellcasting.spell_slots.recover_all();
-        }
+    // Pact Magic slots recharge on a short rest
+    if let Some(pact) = character
+        .spellcasting
+        .as_mut()
+        .and_then(|sc| sc.pact_slots.as_mut())
+    {
+        pact.recover_all();
     }
 
     // Reset feature uses that recharge on short rest
+    let bard_level = character
+        .classes
+        .iter()
+        .find(|c| c.class == CharacterClass::Bard)
+        .map_or(0, |c| c.level);
     for feature in &mut character.features {
-        if let Some(ref mut uses) = feature.uses {
-            if matches!(uses.recharge, RechargeType::ShortRest) {
+        if recharges_on_short_rest(feature, bard_level) {
+            if let Some(ref mut uses) = feature.uses {
                 uses.current = uses.maximum;
             }
         }
@@ -45,6 +48,30 @@ pub fn apply_short_rest(character: &mut Character) {
     }
 }
 
+/// Restore features that recharge at dawn, whether or not anyone rested.
+pub fn apply_dawn_recharge(character: &mut Character) {
+    for feature in &mut character.features {
+        if let Some(ref mut uses) = feature.uses {
+            if matches!(uses.recharge, RechargeType::Dawn) {
+                uses.current = uses.maximum;
+            }
+        }
+    }
+}
+
+/// Whether `feature` comes back on a short rest. Bardic Inspiration is
+/// stored as a long-rest feature but recharges on a short rest once Font of
+/// Inspiration arrives at bard level 5.
+fn recharges_on_short_rest(feature: &Feature, bard_level: u8) -> bool {
+    match &feature.uses {
+        Some(uses) => {
+            matches!(uses.recharge, RechargeType::ShortRest)
+                || (feature.name == "Bardic Inspiration" && bard_level >= 5)
+        }
+        None => false,
+    }
+}
+
 /// Apply long rest recovery to a character.
 ///
 /// Long rest (8 hours):
@@ -81,9 +108,15 @@ pub fn apply_long_rest(character: &mut Character) {
     // Recover half hit dice
     character.hit_dice.recover_half();
 
+    // Temporary speed bonuses (e.g. Longstrider's hour) have run out
+    character.speed_bonuses.clear();
+
     // Recover spell slots
     if let Some(ref mut spellcasting) = character.spellcasting {
         spellcasting.spell_slots.recover_all();
+        if let Some(pact) = &mut spellcasting.pact_slots {
+            pact.recover_all();
+        }
     }
 
     // Reset feature uses (both short rest and long rest features)
@@ -115,8 +148,8 @@ mod tests {
     use crate::dice::DieType;
     use crate::world::Ability;
     use crate::world::{
-        AbilityScores, ArmorClass, ArmorType, ClassLevel, Feature, FeatureUses, HitPoints,
-        ProficiencyLevel, Skill, Subclass,
+        AbilityScores, ArmorClass, ArmorType, CharacterClass, ClassLevel, Feature, FeatureUses,
+        HitPoints, ProficiencyLevel, Skill, SlotInfo, SpellSlots, SpellcastingData, Subclass,
     };
 
     fn create_test_fighter() -> Character {
@@ -190,6 +223,59 @@ mod tests {
         assert_eq!(character.features[1].uses.as_ref().unwrap().current, 1);
     }
 
+    fn create_test_warlock() -> Character {
+        let mut character = Character::new("Test Warlock");
+        character.level = 3;
+        character.classes.push(ClassLevel {
+            class: CharacterClass::Warlock,
+            level: 3,
+            subclass: None,
+        });
+        character.spellcasting = Some(SpellcastingData {
+            ability: Ability::Charisma,
+            spells_known: vec!["Hellish Rebuke".to_string()],
+            spellbook: Vec::new(),
+            spells_prepared: Vec::new(),
+            cantrips_known: vec!["Eldritch Blast".to_string()],
+            spell_slots: SpellSlots::new(),
+            pact_slots: CharacterClass::Warlock.pact_slots_at_level(3),
+        });
+        character
+    }
+
+    #[test]
+    fn test_short_rest_restores_pact_slots() {
+        let mut character = create_test_warlock();
+        let pact = character
+            .spellcasting
+            .as_mut()
+            .unwrap()
+            .pact_slots
+            .as_mut()
+            .unwrap();
+        assert_eq!((pact.level, pact.total), (2, 2));
+        assert!(pact.use_slot());
+        assert!(pact.use_slot());
+        assert!(!pact.use_slot());
+
+        apply_short_rest(&mut character);
+
+        let pact = character.spellcasting.unwrap().pact_slots.unwrap();
+        assert_eq!(pact.available(), 2);
+    }
+
+    #[test]
+    fn test_short_rest_leaves_regular_slots_spent() {
+        let mut character = create_test_warlock();
+        let spellcasting = character.spellcasting.as_mut().unwrap();
+        spellcasting.spell_slots.slots[0] = SlotInfo { total: 2, used: 2 };
+
+        apply_short_rest(&mut character);
+
+        let spellcasting = character.spellcasting.unwrap();
+        assert_eq!(spellcasting.spell_slots.slots[0].available(), 0);
+    }
+
     #[test]
     fn test_long_rest_recovers_hp() {
         let mut character = create_test_fighter();
@@ -223,4 +309,76 @@ mod tests {
         assert_eq!(character.features[1].uses.as_ref().unwrap().current, 1);
         assert_eq!(character.features[2].uses.as_ref().unwrap().current, 2);
     }
+
+    fn uses_of(character: &Character, name: &str) -> u8 {
+        character
+            .features
+            .iter()
+            .find(|f| f.name == name)
+            .and_then(|f| f.uses.as_ref())
+            .unwrap()
+            .current
+    }
+
+    #[test]
+    fn test_short_rest_restores_second_wind_but_not_rage() {
+        let mut character = create_test_fighter();
+        character.features.push(Feature {
+            name: "Rage".to_string(),
+            description: "Enter a rage as a bonus action".to_string(),
+            source: "Barbarian".to_string(),
+            uses: Some(FeatureUses {
+                current: 0,
+                maximum: 2,
+                recharge: RechargeType::LongRest,
+            }),
+        });
+
+        apply_short_rest(&mut character);
+        assert_eq!(uses_of(&character, "Second Wind"), 1);
+        assert_eq!(uses_of(&character, "Rage"), 0);
+
+        apply_long_rest(&mut character);
+        assert_eq!(uses_of(&character, "Rage"), 2);
+    }
+
+    #[test]
+    fn test_short_rest_restores_ki() {
+        let mut character = crate::world::create_sample_monk("Lee");
+        character.class_resources.ki_points = 0;
+
+        apply_short_rest(&mut character);
+
+        assert_eq!(
+            character.class_resources.ki_points,
+            character.class_resources.max_ki_points
+        );
+    }
+
+    #[test]
+    fn test_font_of_inspiration_restores_bardic_inspiration_on_short_rest() {
+        let mut character = Character::new("Test Bard");
+        character.classes.push(ClassLevel {
+            class: CharacterClass::Bard,
+            level: 4,
+            subclass: None,
+        });
+        character.features.push(Feature {
+            name: "Bardic Inspiration".to_string(),
+            description: "Inspire an ally".to_string(),
+            source: "Bard".to_string(),
+            uses: Some(FeatureUses {
+                current: 0,
+                maximum: 3,
+                recharge: RechargeType::LongRest,
+            }),
+        });
+
+        apply_short_rest(&mut character);
+        assert_eq!(uses_of(&character, "Bardic Inspiration"), 0);
+
+        character.classes[0].level = 5;
+        apply_short_rest(&mut character);
+        assert_eq!(uses_of(&character, "Bardic Inspiration"), 3);
+    }
 }
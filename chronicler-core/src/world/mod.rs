@@ -18,10 +18,18 @@
 //! - [`locations`]: Locations and connections
 //! - [`quests`]: Quests and objectives
 //! - [`combat`]: Combat state and combatants
+//! - [`damage`]: Damage types and resistances
+//! - [`diff`]: Per-turn summaries of what changed
+//! - [`scene`]: Compact summary of the current scene
 //! - [`time`]: In-game time tracking
+//! - [`vision`]: Light levels and darkvision
+//! - [`house_rules`]: Optional table rules (critical hit damage)
 //! - [`game_world`]: The complete game world state
+//! - [`validation`]: Consistency checks and repair for loaded state
+//! - [`sorted`]: Stable on-disk ordering for map-backed fields
 
 use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::fmt;
 use uuid::Uuid;
 
@@ -34,31 +42,63 @@ mod backgrounds;
 mod character;
 mod classes;
 mod combat;
+mod combat_hud;
 mod conditions;
+mod damage;
 mod defense;
+mod diff;
 mod equipment;
 mod game_world;
 mod health;
+mod house_rules;
 mod locations;
 pub mod mechanics;
 mod quests;
 mod races;
+mod scene;
 mod skills;
+mod sorted;
 mod spellcasting;
 mod subclasses;
 mod time;
+mod validation;
+mod vision;
 
 // ============================================================================
 // ID Types
 // ============================================================================
 
+thread_local! {
+    /// Next id to hand out on this thread, when ids are deterministic.
+    static ID_SEQUENCE: Cell<Option<u128>> = const { Cell::new(None) };
+}
+
+/// A fresh id for a character, location, quest, or story record.
+///
+/// Random in normal use; sequential while
+/// [`with_deterministic_ids`](crate::testing::with_deterministic_ids) runs.
+pub(crate) fn new_uuid() -> Uuid {
+    ID_SEQUENCE.with(|sequence| match sequence.get() {
+        Some(next) => {
+            sequence.set(Some(next + 1));
+            Uuid::from_u128(next)
+        }
+        None => Uuid::new_v4(),
+    })
+}
+
+/// Replace this thread's id sequence, returning the previous one.
+pub(crate) fn swap_id_sequence(sequence: Option<u128>) -> Option<u128> {
+    ID_SEQUENCE.with(|cell| cell.replace(sequence))
+}
+
 /// Unique identifier for characters.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct CharacterId(pub Uuid);
 
 impl CharacterId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(new_uuid())
     }
 }
 
@@ -80,7 +120,7 @@ pub struct LocationId(pub Uuid);
 
 impl LocationId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(new_uuid())
     }
 }
 
@@ -101,53 +141,77 @@ pub use abilities::{Ability, AbilityScores};
 pub use skills::{ProficiencyLevel, Skill};
 
 // Conditions
-pub use conditions::{ActiveCondition, Condition};
+pub use conditions::{ActiveCondition, Condition, DurationTiming};
 
 // Health
 pub use health::{DamageResult, DeathSaves, HitDice, HitPoints};
 
 // Defense
-pub use defense::{ArmorClass, ArmorType, Speed};
+pub use defense::{AcBonus, ArmorClass, ArmorType, Speed, SpeedBonus};
+
+// Damage
+pub use damage::{DamageType, Resistance};
 
 // Classes
-pub use classes::{CharacterClass, ClassLevel, ClassResources, Feature, FeatureUses, RechargeType};
+pub use classes::{
+    CharacterClass, ClassLevel, ClassResources, Feature, FeatureUses, FightingStyle, RechargeType,
+    StatBlock, MULTICLASS_MINIMUM,
+};
 
 // Subclasses
 pub use subclasses::{Subclass, SubclassFeature};
 
 // Spellcasting
-pub use spellcasting::{SlotInfo, SpellSlots, SpellcastingData};
+pub use spellcasting::{Concentration, PactSlots, SlotInfo, SpellSlots, SpellcastingData};
 
 // Equipment
 pub use equipment::{
-    ArmorItem, ConsumableEffect, ConsumableItem, Equipment, Inventory, Item, ItemType,
-    WeaponDamageType, WeaponItem, WeaponProperty,
+    ArmorItem, ConsumableEffect, ConsumableItem, EncumbranceLevel, Equipment, Inventory, Item,
+    ItemType, WeaponDamageType, WeaponItem, WeaponProperty,
 };
 
 // Races
-pub use races::RaceType;
+pub use races::{RaceTraits, RaceType, RacialFeature};
 
 // Backgrounds
 pub use backgrounds::Background;
 
 // Character
-pub use character::{Character, Disposition, Race, NPC};
+pub use character::{Character, Disposition, Race, ScheduleEntry, NPC};
 
 // Locations
-pub use locations::{Location, LocationConnection, LocationType};
+pub use locations::{
+    Location, LocationConnection, LocationEdge, LocationGraph, LocationNode, LocationType,
+};
 
 // Quests
 pub use quests::{Quest, QuestObjective, QuestStatus};
 
 // Combat
-pub use combat::{CombatState, Combatant};
+pub use combat::{ActionType, CombatState, CombatSummary, Combatant, TurnActions};
+pub use combat_hud::{CombatHud, CombatantStatus};
+
+// Turn diffs
+pub use diff::{HpDelta, InventoryDelta, WorldDiff};
+
+// Scene
+pub use scene::{PartyMemberStatus, SceneSummary};
 
 // Time
-pub use time::GameTime;
+pub use time::{GameTime, TimeOfDay, TimeRange};
+
+// Vision
+pub use vision::LightLevel;
+
+// House Rules
+pub use house_rules::{CritRule, HouseRules};
 
 // Game World
 pub use game_world::{
     create_sample_barbarian, create_sample_bard, create_sample_cleric, create_sample_druid,
     create_sample_fighter, create_sample_monk, create_sample_paladin, create_sample_sorcerer,
-    GameMode, GameWorld, NarrativeEntry, NarrativeType,
+    reputation_standing, GameMode, GameWorld, NarrativeEntry, NarrativeType, REPUTATION_RANGE,
 };
+
+// Validation
+pub use validation::ValidationIssue;
@@ -21,7 +21,7 @@ pub struct Quest {
 impl Quest {
     pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
         Self {
-            id: Uuid::new_v4(),
+            id: super::new_uuid(),
             name: name.into(),
             description: description.into(),
             status: QuestStatus::Active,
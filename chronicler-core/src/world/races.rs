@@ -1,9 +1,10 @@
 //! D&D 5e playable races.
 //!
 //! This module defines the playable races available in D&D 5e, including
-//! their ability score bonuses, base speeds, and descriptions.
+//! their descriptions and the [`RaceTraits`] table of ability score bonuses,
+//! speed, darkvision, resistances, proficiencies, and racial features.
 
-use super::AbilityScores;
+use super::{Ability, AbilityScores, DamageType, Feature, FeatureUses, RechargeType, Skill};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -67,45 +68,12 @@ impl RaceType {
     }
 
     /// Apply racial ability score bonuses to base scores.
+    ///
+    /// Half-elves also get +1 to two other abilities of their choice; that is
+    /// handled in the character builder.
     pub fn apply_ability_bonuses(&self, scores: &mut AbilityScores) {
-        match self {
-            RaceType::Human => {
-                scores.strength += 1;
-                scores.dexterity += 1;
-                scores.constitution += 1;
-                scores.intelligence += 1;
-                scores.wisdom += 1;
-                scores.charisma += 1;
-            }
-            RaceType::Elf => {
-                scores.dexterity += 2;
-            }
-            RaceType::Dwarf => {
-                scores.constitution += 2;
-            }
-            RaceType::Halfling => {
-                scores.dexterity += 2;
-            }
-            RaceType::HalfOrc => {
-                scores.strength += 2;
-                scores.constitution += 1;
-            }
-            RaceType::HalfElf => {
-                scores.charisma += 2;
-                // Note: Half-elves also get +1 to two other abilities of choice
-                // This is handled in character builder
-            }
-            RaceType::Tiefling => {
-                scores.charisma += 2;
-                scores.intelligence += 1;
-            }
-            RaceType::Gnome => {
-                scores.intelligence += 2;
-            }
-            RaceType::Dragonborn => {
-                scores.strength += 2;
-                scores.charisma += 1;
-            }
+        for &(ability, bonus) in self.traits().ability_bonuses {
+            scores.set(ability, scores.get(ability) + bonus);
         }
     }
 
@@ -125,9 +93,190 @@ impl RaceType {
     }
 
     pub fn base_speed(&self) -> u32 {
+        self.traits().speed
+    }
+
+    /// Darkvision range in feet (0 if the race has none).
+    pub fn darkvision_range(&self) -> u8 {
+        self.traits().darkvision_range
+    }
+
+    /// The mechanical traits every member of this race starts with.
+    pub fn traits(&self) -> RaceTraits {
+        use Ability::*;
+
         match self {
-            RaceType::Dwarf | RaceType::Halfling | RaceType::Gnome => 25,
-            _ => 30,
+            RaceType::Human => RaceTraits {
+                ability_bonuses: &[
+                    (Strength, 1),
+                    (Dexterity, 1),
+                    (Constitution, 1),
+                    (Intelligence, 1),
+                    (Wisdom, 1),
+                    (Charisma, 1),
+                ],
+                speed: 30,
+                darkvision_range: 0,
+                resistances: &[],
+                skill_proficiencies: &[],
+                languages: &["Common"],
+                features: &[],
+            },
+            RaceType::Elf => RaceTraits {
+                ability_bonuses: &[(Dexterity, 2)],
+                speed: 30,
+                darkvision_range: 60,
+                resistances: &[],
+                skill_proficiencies: &[Skill::Perception],
+                languages: &["Common", "Elvish"],
+                features: &[
+                    RacialFeature {
+                        name: "Fey Ancestry",
+                        description: "Advantage on saving throws against being charmed, and magic can't put you to sleep.",
+                        recharge: None,
+                    },
+                    RacialFeature {
+                        name: "Trance",
+                        description: "You meditate for 4 hours instead of sleeping 8 to gain the benefits of a long rest.",
+                        recharge: None,
+                    },
+                ],
+            },
+            RaceType::Dwarf => RaceTraits {
+                ability_bonuses: &[(Constitution, 2)],
+                speed: 25,
+                darkvision_range: 60,
+                resistances: &[DamageType::Poison],
+                skill_proficiencies: &[],
+                languages: &["Common", "Dwarvish"],
+                features: &[
+                    RacialFeature {
+                        name: "Dwarven Resilience",
+                        description: "Advantage on saving throws against poison, and resistance to poison damage.",
+                        recharge: None,
+                    },
+                    RacialFeature {
+                        name: "Dwarven Combat Training",
+                        description: "Proficiency with the battleaxe, handaxe, light hammer, and warhammer.",
+                        recharge: None,
+                    },
+                    RacialFeature {
+                        name: "Stonecunning",
+                        description: "Double proficiency bonus on History checks related to the origin of stonework.",
+                        recharge: None,
+                    },
+                ],
+            },
+            RaceType::Halfling => RaceTraits {
+                ability_bonuses: &[(Dexterity, 2)],
+                speed: 25,
+                darkvision_range: 0,
+                resistances: &[],
+                skill_proficiencies: &[],
+                languages: &["Common", "Halfling"],
+                features: &[
+                    RacialFeature {
+                        name: "Lucky",
+                        description: "When you roll a 1 on a d20 for an attack roll, ability check, or saving throw, you can reroll the die.",
+                        recharge: None,
+                    },
+                    RacialFeature {
+                        name: "Brave",
+                        description: "Advantage on saving throws against being frightened.",
+                        recharge: None,
+                    },
+                    RacialFeature {
+                        name: "Halfling Nimbleness",
+                        description: "You can move through the space of any creature larger than you.",
+                        recharge: None,
+                    },
+                ],
+            },
+            RaceType::HalfOrc => RaceTraits {
+                ability_bonuses: &[(Strength, 2), (Constitution, 1)],
+                speed: 30,
+                darkvision_range: 60,
+                resistances: &[],
+                skill_proficiencies: &[Skill::Intimidation],
+                languages: &["Common", "Orc"],
+                features: &[
+                    RacialFeature {
+                        name: "Relentless Endurance",
+                        description: "When reduced to 0 hit points but not killed outright, drop to 1 hit point instead.",
+                        recharge: Some(RechargeType::LongRest),
+                    },
+                    RacialFeature {
+                        name: "Savage Attacks",
+                        description: "On a melee weapon critical hit, roll one of the weapon's damage dice one additional time.",
+                        recharge: None,
+                    },
+                ],
+            },
+            RaceType::HalfElf => RaceTraits {
+                ability_bonuses: &[(Charisma, 2)],
+                speed: 30,
+                darkvision_range: 60,
+                resistances: &[],
+                skill_proficiencies: &[],
+                languages: &["Common", "Elvish"],
+                features: &[
+                    RacialFeature {
+                        name: "Fey Ancestry",
+                        description: "Advantage on saving throws against being charmed, and magic can't put you to sleep.",
+                        recharge: None,
+                    },
+                ],
+            },
+            RaceType::Tiefling => RaceTraits {
+                ability_bonuses: &[(Charisma, 2), (Intelligence, 1)],
+                speed: 30,
+                darkvision_range: 60,
+                resistances: &[DamageType::Fire],
+                skill_proficiencies: &[],
+                languages: &["Common", "Infernal"],
+                features: &[
+                    RacialFeature {
+                        name: "Hellish Resistance",
+                        description: "Resistance to fire damage.",
+                        recharge: None,
+                    },
+                    RacialFeature {
+                        name: "Infernal Legacy",
+                        description: "You know the Thaumaturgy cantrip.",
+                        recharge: None,
+                    },
+                ],
+            },
+            RaceType::Gnome => RaceTraits {
+                ability_bonuses: &[(Intelligence, 2)],
+                speed: 25,
+                darkvision_range: 60,
+                resistances: &[],
+                skill_proficiencies: &[],
+                languages: &["Common", "Gnomish"],
+                features: &[
+                    RacialFeature {
+                        name: "Gnome Cunning",
+                        description: "Advantage on Intelligence, Wisdom, and Charisma saving throws against magic.",
+                        recharge: None,
+                    },
+                ],
+            },
+            RaceType::Dragonborn => RaceTraits {
+                ability_bonuses: &[(Strength, 2), (Charisma, 1)],
+                speed: 30,
+                darkvision_range: 0,
+                resistances: &[],
+                skill_proficiencies: &[],
+                languages: &["Common", "Draconic"],
+                features: &[
+                    RacialFeature {
+                        name: "Breath Weapon",
+                        description: "Exhale destructive energy determined by your draconic ancestry (2d6, DC 8 + CON modifier + proficiency bonus).",
+                        recharge: Some(RechargeType::ShortRest),
+                    },
+                ],
+            },
         }
     }
 
@@ -146,6 +295,45 @@ impl RaceType {
     }
 }
 
+/// Mechanical traits granted by a race, applied when a character is built.
+#[derive(Debug, Clone, Copy)]
+pub struct RaceTraits {
+    pub ability_bonuses: &'static [(Ability, u8)],
+    /// Walking speed in feet.
+    pub speed: u32,
+    /// Darkvision range in feet (0 for none).
+    pub darkvision_range: u8,
+    pub resistances: &'static [DamageType],
+    pub skill_proficiencies: &'static [Skill],
+    pub languages: &'static [&'static str],
+    pub features: &'static [RacialFeature],
+}
+
+/// A racial feature recorded on the character sheet.
+#[derive(Debug, Clone, Copy)]
+pub struct RacialFeature {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Recharge for features with a single limited use.
+    pub recharge: Option<RechargeType>,
+}
+
+impl RacialFeature {
+    /// Build the character-sheet feature for this trait.
+    pub fn to_feature(&self, race: RaceType) -> Feature {
+        Feature {
+            name: self.name.to_string(),
+            description: self.description.to_string(),
+            source: race.name().to_string(),
+            uses: self.recharge.map(|recharge| FeatureUses {
+                current: 1,
+                maximum: 1,
+                recharge,
+            }),
+        }
+    }
+}
+
 impl fmt::Display for RaceType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.name())
@@ -0,0 +1,164 @@
+//! A compact summary of the current scene.
+//!
+//! [`GameWorld::scene_summary`] gathers where the party is, who is around,
+//! how the party is holding up, and the mode and time into one value, so the
+//! DM prompt describes the scene the same way every turn. Its `Display` form
+//! is the block the prompt uses.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::{Condition, GameMode, GameWorld, LightLevel, TimeOfDay};
+
+/// How one member of the party is doing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PartyMemberStatus {
+    pub name: String,
+    pub current_hp: i32,
+    pub max_hp: i32,
+    /// Conditions with their sources.
+    pub conditions: Vec<(Condition, String)>,
+    /// The spell being concentrated on, if any.
+    pub concentrating_on: Option<String>,
+}
+
+/// Where the party is and what is going on around them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SceneSummary {
+    pub location: String,
+    pub mode: GameMode,
+    pub time_of_day: TimeOfDay,
+    /// Finer-grained part of the day, e.g. "evening".
+    pub period: String,
+    pub hour: u8,
+    pub minute: u8,
+    pub light: LightLevel,
+    /// NPCs at the location, by name with their occupation if known.
+    pub npcs: Vec<String>,
+    /// The player character, then any allies fighting alongside them.
+    pub party: Vec<PartyMemberStatus>,
+}
+
+impl GameWorld {
+    /// Summarize the current scene.
+    pub fn scene_summary(&self) -> SceneSummary {
+        let pc = &self.player_character;
+        let mut party = vec![PartyMemberStatus {
+            name: pc.name.clone(),
+            current_hp: pc.hit_points.current,
+            max_hp: pc.hit_points.maximum,
+            conditions: pc
+                .conditions
+                .iter()
+                .map(|c| (c.condition, c.source.clone()))
+                .collect(),
+            concentrating_on: pc.concentration.as_ref().map(|c| c.spell_name.clone()),
+        }];
+        if let Some(ref combat) = self.combat {
+            party.extend(
+                combat
+                    .combatants
+                    .iter()
+                    .filter(|c| c.is_ally && !c.is_player)
+                    .map(|c| PartyMemberStatus {
+                        name: c.name.clone(),
+                        current_hp: c.current_hp,
+                        max_hp: c.max_hp,
+                        conditions: Vec::new(),
+                        concentrating_on: None,
+                    }),
+            );
+        }
+
+        SceneSummary {
+            location: self.current_location.name.clone(),
+            mode: self.mode,
+            time_of_day: self.game_time.time_of_day(),
+            period: self.game_time.period_name().to_string(),
+            hour: self.game_time.hour,
+            minute: self.game_time.minute,
+            light: self.light_level(),
+            npcs: self
+                .npcs_here()
+                .into_iter()
+                .map(|npc| match &npc.occupation {
+                    Some(occupation) => format!("{} ({})", npc.name, occupation),
+                    None => npc.name.clone(),
+                })
+                .collect(),
+            party,
+        }
+    }
+}
+
+impl fmt::Display for PartyMemberStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}/{} HP", self.name, self.current_hp, self.max_hp)?;
+        for (condition, source) in &self.conditions {
+            write!(f, ", {condition} (from {source})")?;
+        }
+        if let Some(ref spell) = self.concentrating_on {
+            write!(f, ", concentrating on {spell}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SceneSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Location: {}", self.location)?;
+        writeln!(
+            f,
+            "Time: {} ({}, {:02}:{:02}), {}",
+            self.period, self.time_of_day, self.hour, self.minute, self.light
+        )?;
+        writeln!(f, "Mode: {:?}", self.mode)?;
+        if !self.npcs.is_empty() {
+            writeln!(f, "NPCs here: {}", self.npcs.join(", "))?;
+        }
+        let party: Vec<String> = self.party.iter().map(ToString::to_string).collect();
+        writeln!(f, "Party: {}", party.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{create_sample_fighter, Concentration, GameTime, NPC};
+
+    #[test]
+    fn test_scene_summary_snapshot() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Roland"));
+        world.current_location.name = "The Rusty Anchor".to_string();
+        world.game_time = GameTime::new(1492, 3, 1, 19, 30);
+        let mut mira = NPC::new("Mira");
+        mira.occupation = Some("innkeeper".to_string());
+        mira.location_id = Some(world.current_location.id);
+        world.npcs.insert(mira.id, mira);
+        let mut stranger = NPC::new("Tomas");
+        stranger.location_id = Some(world.current_location.id);
+        world.npcs.insert(stranger.id, stranger);
+        world
+            .npcs
+            .insert(crate::world::CharacterId::new(), NPC::new("Elsewhere"));
+        let pc = &mut world.player_character;
+        pc.hit_points.current = 7;
+        pc.add_condition(Condition::Poisoned, "bad stew");
+        pc.concentration = Some(Concentration::new("Bless"));
+
+        let summary = world.scene_summary();
+
+        assert_eq!(
+            summary.to_string(),
+            format!(
+                "Location: The Rusty Anchor\n\
+                 Time: evening (dusk, 19:30), dim light\n\
+                 Mode: Exploration\n\
+                 NPCs here: Mira (innkeeper), Tomas\n\
+                 Party: Roland 7/{} HP, Poisoned (from bad stew), concentrating on Bless\n",
+                world.player_character.hit_points.maximum
+            )
+        );
+    }
+}
@@ -75,6 +75,29 @@ impl Skill {
         }
     }
 
+    pub fn all() -> [Skill; 18] {
+        [
+            Skill::Athletics,
+            Skill::Acrobatics,
+            Skill::SleightOfHand,
+            Skill::Stealth,
+            Skill::Arcana,
+            Skill::History,
+            Skill::Investigation,
+            Skill::Nature,
+            Skill::Religion,
+            Skill::AnimalHandling,
+            Skill::Insight,
+            Skill::Medicine,
+            Skill::Perception,
+            Skill::Survival,
+            Skill::Deception,
+            Skill::Intimidation,
+            Skill::Performance,
+            Skill::Persuasion,
+        ]
+    }
+
     pub fn description(&self) -> &'static str {
         match self {
             Skill::Athletics => "Covers climbing, jumping, or swimming. Used for scaling cliffs, avoiding hazards, or struggling against currents.",
@@ -0,0 +1,140 @@
+//! Serde helpers that write `HashMap` fields in a stable order.
+//!
+//! A `HashMap` iterates in a different order every run, so saving the same
+//! world twice could produce different JSON. These helpers write maps as
+//! lists sorted by name, so save files diff cleanly. They read either the
+//! sorted list or the plain map that older saves contain. Sets get the same
+//! treatment.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use uuid::Uuid;
+
+use super::{CharacterId, Location, LocationId, NPC};
+
+/// A value that carries the key it is stored under.
+pub(crate) trait Keyed {
+    type Key;
+
+    fn key(&self) -> Self::Key;
+
+    /// Order in which values are written: by name, with ties broken by id.
+    fn sort_key(&self) -> (&str, Uuid);
+}
+
+impl Keyed for NPC {
+    type Key = CharacterId;
+
+    fn key(&self) -> CharacterId {
+        self.id
+    }
+
+    fn sort_key(&self) -> (&str, Uuid) {
+        (&self.name, self.id.0)
+    }
+}
+
+impl Keyed for Location {
+    type Key = LocationId;
+
+    fn key(&self) -> LocationId {
+        self.id
+    }
+
+    fn sort_key(&self) -> (&str, Uuid) {
+        (&self.name, self.id.0)
+    }
+}
+
+/// Either layout a map field may have on disk.
+#[derive(Deserialize)]
+#[serde(untagged)]
+#[serde(bound(
+    deserialize = "K: Deserialize<'de> + Eq + Hash, V: Deserialize<'de>, T: Deserialize<'de>"
+))]
+enum Stored<K, V, T> {
+    List(Vec<T>),
+    Map(HashMap<K, V>),
+}
+
+/// For maps whose values carry their own key (NPCs, locations): written as a
+/// list of the values.
+pub(crate) mod by_key {
+    use super::*;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        V: Keyed + Serialize,
+        S: Serializer,
+    {
+        let mut values: Vec<&V> = map.values().collect();
+        values.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+        values.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Keyed<Key = K> + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(match Stored::<K, V, V>::deserialize(deserializer)? {
+            Stored::List(values) => values.into_iter().map(|v| (v.key(), v)).collect(),
+            Stored::Map(map) => map,
+        })
+    }
+}
+
+/// For maps with plain keys (faction reputation): written as a list of
+/// `[key, value]` pairs sorted by key.
+pub(crate) mod pairs {
+    use super::*;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Ord + Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        let mut entries: Vec<(&K, &V)> = map.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(match Stored::<K, V, (K, V)>::deserialize(deserializer)? {
+            Stored::List(entries) => entries.into_iter().collect(),
+            Stored::Map(map) => map,
+        })
+    }
+}
+
+/// For sets of plain values (who has used Sneak Attack this turn): written
+/// as a sorted list, which is also how an unsorted set was written.
+pub(crate) mod set {
+    use super::*;
+
+    pub fn serialize<T, S>(set: &HashSet<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Ord + Serialize,
+        S: Serializer,
+    {
+        let mut values: Vec<&T> = set.iter().collect();
+        values.sort();
+        values.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<HashSet<T>, D::Error>
+    where
+        T: Deserialize<'de> + Eq + Hash,
+        D: Deserializer<'de>,
+    {
+        HashSet::deserialize(deserializer)
+    }
+}
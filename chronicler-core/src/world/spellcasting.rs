@@ -12,9 +12,15 @@ use super::{Ability, AbilityScores};
 pub struct SpellcastingData {
     pub ability: Ability,
     pub spells_known: Vec<String>,
+    /// Spells a Wizard has copied into their spellbook and so can prepare.
+    #[serde(default)]
+    pub spellbook: Vec<String>,
     pub spells_prepared: Vec<String>,
     pub cantrips_known: Vec<String>,
     pub spell_slots: SpellSlots,
+    /// Warlock Pact Magic slots, tracked apart from `spell_slots`.
+    #[serde(default)]
+    pub pact_slots: Option<PactSlots>,
 }
 
 impl SpellcastingData {
@@ -65,6 +71,66 @@ impl Default for SpellSlots {
     }
 }
 
+/// Warlock Pact Magic: a few slots, all of the same level, that recharge on
+/// a short rest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PactSlots {
+    /// Spell level every pact slot is cast at.
+    pub level: u8,
+    pub total: u8,
+    pub used: u8,
+}
+
+impl PactSlots {
+    pub fn new(level: u8, total: u8) -> Self {
+        Self {
+            level,
+            total,
+            used: 0,
+        }
+    }
+
+    pub fn available(&self) -> u8 {
+        self.total.saturating_sub(self.used)
+    }
+
+    pub fn use_slot(&mut self) -> bool {
+        if self.available() > 0 {
+            self.used += 1;
+            return true;
+        }
+        false
+    }
+
+    pub fn recover_all(&mut self) {
+        self.used = 0;
+    }
+}
+
+/// A spell the character is concentrating on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Concentration {
+    pub spell_name: String,
+    /// Damage taken since casting, one entry per hit, each still owed a
+    /// concentration save
+    #[serde(default)]
+    pub pending_damage: Vec<i32>,
+}
+
+impl Concentration {
+    pub fn new(spell_name: impl Into<String>) -> Self {
+        Self {
+            spell_name: spell_name.into(),
+            pending_damage: Vec::new(),
+        }
+    }
+
+    /// DC of the CON save to keep concentrating after taking damage.
+    pub fn save_dc(damage_taken: i32) -> i32 {
+        (damage_taken / 2).max(10)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct SlotInfo {
     pub total: u8,
@@ -210,9 +276,11 @@ mod tests {
         let data = SpellcastingData {
             ability: Ability::Intelligence,
             spells_known: vec![],
+            spellbook: Vec::new(),
             spells_prepared: vec!["Fireball".to_string()],
             cantrips_known: vec!["Fire Bolt".to_string()],
             spell_slots: SpellSlots::new(),
+            pact_slots: None,
         };
 
         let scores = AbilityScores::new(10, 10, 10, 18, 10, 10); // INT 18 (+4)
@@ -227,9 +295,11 @@ mod tests {
         let data = SpellcastingData {
             ability: Ability::Wisdom,
             spells_known: vec![],
+            spellbook: Vec::new(),
             spells_prepared: vec!["Guiding Bolt".to_string()],
             cantrips_known: vec!["Sacred Flame".to_string()],
             spell_slots: SpellSlots::new(),
+            pact_slots: None,
         };
 
         let scores = AbilityScores::new(10, 10, 10, 10, 16, 10); // WIS 16 (+3)
@@ -244,9 +314,11 @@ mod tests {
         let data = SpellcastingData {
             ability: Ability::Charisma,
             spells_known: vec![],
+            spellbook: Vec::new(),
             spells_prepared: vec![],
             cantrips_known: vec![],
             spell_slots: SpellSlots::new(),
+            pact_slots: None,
         };
 
         // Extremely low stats (shouldn't happen in practice but test bounds)
@@ -262,9 +334,11 @@ mod tests {
         let data = SpellcastingData {
             ability: Ability::Intelligence,
             spells_known: vec![],
+            spellbook: Vec::new(),
             spells_prepared: vec![],
             cantrips_known: vec![],
             spell_slots: SpellSlots::new(),
+            pact_slots: None,
         };
 
         let scores = AbilityScores::new(10, 10, 10, 16, 10, 10); // INT 16 (+3)
@@ -279,9 +353,11 @@ mod tests {
         let data = SpellcastingData {
             ability: Ability::Wisdom,
             spells_known: vec![],
+            spellbook: Vec::new(),
             spells_prepared: vec![],
             cantrips_known: vec![],
             spell_slots: SpellSlots::new(),
+            pact_slots: None,
         };
 
         let scores = AbilityScores::new(10, 10, 10, 10, 20, 10); // WIS 20 (+5)
@@ -296,9 +372,11 @@ mod tests {
         let data = SpellcastingData {
             ability: Ability::Charisma,
             spells_known: vec![],
+            spellbook: Vec::new(),
             spells_prepared: vec![],
             cantrips_known: vec![],
             spell_slots: SpellSlots::new(),
+            pact_slots: None,
         };
 
         let scores = AbilityScores::new(10, 10, 10, 10, 10, 8); // CHA 8 (-1)
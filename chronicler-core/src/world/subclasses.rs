@@ -3,7 +3,7 @@
 //! This module defines the subclass enum and associated features
 //! for the one subclass per class included in the SRD 5.2.
 
-use super::CharacterClass;
+use super::{CharacterClass, Feature, FeatureUses, RechargeType};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -381,6 +381,16 @@ impl Subclass {
         }
     }
 
+    /// Limited uses for subclass features that have them, as (uses, recharge).
+    pub fn feature_uses(feature_name: &str) -> Option<(u8, RechargeType)> {
+        match feature_name {
+            "Natural Recovery" | "Wholeness of Body" | "Holy Nimbus" | "Hurl Through Hell"
+            | "Overchannel" => Some((1, RechargeType::LongRest)),
+            "Dark One's Own Luck" => Some((1, RechargeType::ShortRest)),
+            _ => None,
+        }
+    }
+
     /// Returns the available subclass for a given class.
     pub fn for_class(class: CharacterClass) -> Option<Subclass> {
         match class {
@@ -431,6 +441,23 @@ pub struct SubclassFeature {
     pub description: String,
 }
 
+impl SubclassFeature {
+    /// Build the character-sheet feature, with limited uses where the
+    /// subclass feature has them.
+    pub fn to_feature(&self, subclass: Subclass) -> Feature {
+        Feature {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            source: subclass.name().to_string(),
+            uses: Subclass::feature_uses(&self.name).map(|(maximum, recharge)| FeatureUses {
+                current: maximum,
+                maximum,
+                recharge,
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,12 +1,27 @@
 //! In-game time tracking for D&D campaigns.
 //!
 //! This module provides the [`GameTime`] struct for tracking the passage of time
-//! within a game session, including year, month, day, hour, and minute.
+//! within a game session, including year, month, day, hour, and minute, and
+//! the [`TimeOfDay`] bucket that drives day/night lighting.
 
+use super::LightLevel;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Minutes in a day.
+const MINUTES_PER_DAY: i64 = 24 * 60;
+/// Days in a month (the calendar uses twelve 30-day months).
+const DAYS_PER_MONTH: i64 = 30;
+/// Days in a year.
+const DAYS_PER_YEAR: i64 = 12 * DAYS_PER_MONTH;
+/// The hour at which dawn breaks, for abilities that recharge at dawn.
+const DAWN_HOUR: u8 = 6;
 
 /// In-game time tracking.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Times are ordered chronologically. The derived ordering compares fields in
+/// declaration order (year, month, day, hour, minute), so keep them in that order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct GameTime {
     pub year: i32,
     pub month: u8,
@@ -26,6 +41,36 @@ impl GameTime {
         }
     }
 
+    /// Build a time from an absolute minute count since midnight on the first
+    /// day of year 0. Inverse of [`GameTime::to_minutes`].
+    pub fn from_minutes(total: i64) -> Self {
+        let days = total.div_euclid(MINUTES_PER_DAY);
+        let minute_of_day = total.rem_euclid(MINUTES_PER_DAY);
+        let year = days.div_euclid(DAYS_PER_YEAR);
+        let day_of_year = days.rem_euclid(DAYS_PER_YEAR);
+
+        Self {
+            year: year as i32,
+            month: (day_of_year / DAYS_PER_MONTH + 1) as u8,
+            day: (day_of_year % DAYS_PER_MONTH + 1) as u8,
+            hour: (minute_of_day / 60) as u8,
+            minute: (minute_of_day % 60) as u8,
+        }
+    }
+
+    /// Absolute minute count since midnight on the first day of year 0.
+    pub fn to_minutes(&self) -> i64 {
+        let days = self.year as i64 * DAYS_PER_YEAR
+            + (self.month as i64 - 1) * DAYS_PER_MONTH
+            + (self.day as i64 - 1);
+        days * MINUTES_PER_DAY + self.hour as i64 * 60 + self.minute as i64
+    }
+
+    /// Signed number of minutes from `self` until `other`.
+    pub fn minutes_until(&self, other: &GameTime) -> i64 {
+        other.to_minutes() - self.to_minutes()
+    }
+
     pub fn advance_minutes(&mut self, minutes: u32) {
         let total_minutes = self.minute as u32 + minutes;
         self.minute = (total_minutes % 60) as u8;
@@ -54,11 +99,30 @@ impl GameTime {
         self.year += years_to_add as i32;
     }
 
+    /// How many dawns break after `self` and no later than `later`.
+    pub fn dawns_until(&self, later: &GameTime) -> i64 {
+        let dawn = DAWN_HOUR as i64 * 60;
+        let days_since_dawn =
+            |time: &GameTime| (time.to_minutes() - dawn).div_euclid(MINUTES_PER_DAY);
+        (days_since_dawn(later) - days_since_dawn(self)).max(0)
+    }
+
     pub fn is_daytime(&self) -> bool {
         self.hour >= 6 && self.hour < 18
     }
 
-    pub fn time_of_day(&self) -> &'static str {
+    /// Coarse time-of-day bucket used for lighting and scene context.
+    pub fn time_of_day(&self) -> TimeOfDay {
+        match self.hour {
+            5..=6 => TimeOfDay::Dawn,
+            7..=17 => TimeOfDay::Day,
+            18..=19 => TimeOfDay::Dusk,
+            _ => TimeOfDay::Night,
+        }
+    }
+
+    /// Finer-grained description of the hour for narration.
+    pub fn period_name(&self) -> &'static str {
         match self.hour {
             5..=7 => "dawn",
             8..=11 => "morning",
@@ -70,8 +134,174 @@ impl GameTime {
     }
 }
 
+/// A span of hours in the day, from `start_hour` up to but not including
+/// `end_hour`. A range whose end is before its start wraps past midnight
+/// (e.g. 20 to 6 covers the night).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeRange {
+    pub start_hour: u8,
+    pub end_hour: u8,
+}
+
+impl TimeRange {
+    pub fn new(start_hour: u8, end_hour: u8) -> Self {
+        Self {
+            start_hour,
+            end_hour,
+        }
+    }
+
+    pub fn contains(&self, hour: u8) -> bool {
+        if self.start_hour <= self.end_hour {
+            hour >= self.start_hour && hour < self.end_hour
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+/// Coarse time of day, which determines outdoor lighting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TimeOfDay {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+impl TimeOfDay {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeOfDay::Dawn => "dawn",
+            TimeOfDay::Day => "day",
+            TimeOfDay::Dusk => "dusk",
+            TimeOfDay::Night => "night",
+        }
+    }
+
+    /// True when it is dark outdoors, so creatures without darkvision
+    /// have trouble seeing.
+    pub fn is_dark(&self) -> bool {
+        matches!(self, TimeOfDay::Night)
+    }
+
+    /// Outdoor light level: bright by day, dim at twilight and under the
+    /// moon and stars at night.
+    pub fn light_level(&self) -> LightLevel {
+        match self {
+            TimeOfDay::Day => LightLevel::Bright,
+            TimeOfDay::Dawn | TimeOfDay::Dusk | TimeOfDay::Night => LightLevel::Dim,
+        }
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 impl Default for GameTime {
     fn default() -> Self {
         Self::new(1492, 3, 1, 10, 0) // Day 1 of the month
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eight_hours_changes_time_of_day() {
+        let mut time = GameTime::new(1492, 3, 1, 10, 0);
+        assert_eq!(time.time_of_day(), TimeOfDay::Day);
+
+        time.advance_hours(8);
+        assert_eq!(time.time_of_day(), TimeOfDay::Dusk);
+
+        time.advance_hours(8);
+        assert_eq!(time.time_of_day(), TimeOfDay::Night);
+        assert!(time.time_of_day().is_dark());
+
+        time.advance_hours(8);
+        assert_eq!(time.time_of_day(), TimeOfDay::Day);
+        assert_eq!(time.day, 2);
+    }
+
+    #[test]
+    fn test_time_of_day_boundaries() {
+        let at = |hour| GameTime::new(1492, 3, 1, hour, 0).time_of_day();
+        assert_eq!(at(4), TimeOfDay::Night);
+        assert_eq!(at(5), TimeOfDay::Dawn);
+        assert_eq!(at(7), TimeOfDay::Day);
+        assert_eq!(at(18), TimeOfDay::Dusk);
+        assert_eq!(at(20), TimeOfDay::Night);
+    }
+
+    #[test]
+    fn test_minutes_round_trip() {
+        let times = [
+            GameTime::default(),
+            GameTime::new(1492, 12, 30, 23, 59),
+            GameTime::new(0, 1, 1, 0, 0),
+            GameTime::new(-5, 6, 15, 12, 30),
+        ];
+        for time in times {
+            assert_eq!(GameTime::from_minutes(time.to_minutes()), time);
+        }
+    }
+
+    #[test]
+    fn test_to_minutes_matches_advance() {
+        let start = GameTime::new(1492, 3, 1, 10, 0);
+        let mut later = start.clone();
+        later.advance_minutes(3 * 24 * 60 + 95);
+
+        assert_eq!(start.minutes_until(&later), 3 * 24 * 60 + 95);
+        assert_eq!(
+            GameTime::from_minutes(start.to_minutes() + 3 * 24 * 60 + 95),
+            later
+        );
+    }
+
+    #[test]
+    fn test_ordering_across_day_boundary() {
+        let before_midnight = GameTime::new(1492, 3, 1, 23, 59);
+        let mut after_midnight = before_midnight.clone();
+        after_midnight.advance_minutes(2);
+
+        assert_eq!(after_midnight, GameTime::new(1492, 3, 2, 0, 1));
+        assert!(before_midnight < after_midnight);
+        assert_eq!(before_midnight.minutes_until(&after_midnight), 2);
+    }
+
+    #[test]
+    fn test_ordering_across_month_and_year_boundaries() {
+        let end_of_month = GameTime::new(1492, 3, 30, 22, 0);
+        let start_of_next = GameTime::new(1492, 4, 1, 1, 0);
+        let new_year = GameTime::new(1493, 1, 1, 0, 0);
+
+        assert!(end_of_month < start_of_next);
+        assert!(start_of_next < new_year);
+        assert_eq!(end_of_month.minutes_until(&start_of_next), 3 * 60);
+
+        let mut times = vec![
+            new_year.clone(),
+            end_of_month.clone(),
+            start_of_next.clone(),
+        ];
+        times.sort();
+        assert_eq!(times, vec![end_of_month, start_of_next, new_year]);
+    }
+
+    #[test]
+    fn test_dawns_until_counts_crossings() {
+        let night = GameTime::new(1492, 3, 1, 22, 0);
+        assert_eq!(night.dawns_until(&GameTime::new(1492, 3, 2, 5, 59)), 0);
+        assert_eq!(night.dawns_until(&GameTime::new(1492, 3, 2, 6, 0)), 1);
+        assert_eq!(night.dawns_until(&GameTime::new(1492, 3, 4, 7, 0)), 3);
+
+        let morning = GameTime::new(1492, 3, 1, 6, 0);
+        assert_eq!(morning.dawns_until(&GameTime::new(1492, 3, 1, 20, 0)), 0);
+    }
+}
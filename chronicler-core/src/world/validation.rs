@@ -0,0 +1,657 @@
+//! Consistency checks for loaded game state.
+//!
+//! Saves written by older builds (or edited by hand) can contain states the
+//! rules engine never produces: more spell slots used than exist, HP above
+//! maximum, a caster with no spellcasting data, a Warlock without Pact Magic
+//! slots, a race missing its darkvision, a Wizard with no spellbook. [`GameWorld::validate`]
+//! reports these and [`GameWorld::repair`] clamps them back into range or
+//! derives the missing values from the character's class and race.
+
+use super::{
+    Character, CharacterClass, GameWorld, RaceType, SlotInfo, SpellSlots, SpellcastingData,
+};
+use crate::dice::DieType;
+use std::fmt;
+
+/// An impossible or inconsistent piece of game state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// More spell slots of a level are marked used than the character has.
+    SlotOverused { level: u8, used: u8, total: u8 },
+    /// Current hit points exceed the maximum.
+    HpAboveMaximum {
+        name: String,
+        current: i32,
+        maximum: i32,
+    },
+    /// Current hit points are below zero.
+    NegativeHp { name: String, current: i32 },
+    /// Temporary hit points are below zero.
+    NegativeTemporaryHp { temporary: i32 },
+    /// A limited-use feature has more uses remaining than its maximum.
+    FeatureUsesAboveMaximum {
+        feature: String,
+        current: u8,
+        maximum: u8,
+    },
+    /// A class resource pool holds more than its maximum.
+    ResourceAboveMaximum {
+        resource: &'static str,
+        current: u32,
+        maximum: u32,
+    },
+    /// More hit dice remain than the character has in total.
+    HitDiceAboveTotal {
+        die: DieType,
+        remaining: u8,
+        total: u8,
+    },
+    /// A spellcasting class has no spellcasting data.
+    MissingSpellcasting { class: CharacterClass },
+    /// A Warlock has no Pact Magic slots (saves from before Pact Magic).
+    MissingPactSlots { warlock_level: u8 },
+    /// Darkvision is shorter than the character's race grants.
+    DarkvisionBelowRace {
+        race: RaceType,
+        range: u8,
+        expected: u8,
+    },
+    /// A Wizard's spellbook is empty (saves from before spellbooks kept a
+    /// Wizard's spells in `spells_known`).
+    EmptySpellbook,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::SlotOverused { level, used, total } => {
+                write!(f, "level {level} spell slots: {used} used of {total}")
+            }
+            ValidationIssue::HpAboveMaximum {
+                name,
+                current,
+                maximum,
+            } => write!(f, "{name} has {current} HP, above maximum {maximum}"),
+            ValidationIssue::NegativeHp { name, current } => {
+                write!(f, "{name} has negative HP ({current})")
+            }
+            ValidationIssue::NegativeTemporaryHp { temporary } => {
+                write!(f, "negative temporary HP ({temporary})")
+            }
+            ValidationIssue::FeatureUsesAboveMaximum {
+                feature,
+                current,
+                maximum,
+            } => write!(f, "{feature} has {current} uses, above maximum {maximum}"),
+            ValidationIssue::ResourceAboveMaximum {
+                resource,
+                current,
+                maximum,
+            } => write!(f, "{resource} is {current}, above maximum {maximum}"),
+            ValidationIssue::HitDiceAboveTotal {
+                die,
+                remaining,
+                total,
+            } => write!(f, "{remaining} {die:?} hit dice remaining of {total}"),
+            ValidationIssue::MissingSpellcasting { class } => {
+                write!(f, "{} has no spellcasting data", class.name())
+            }
+            ValidationIssue::MissingPactSlots { warlock_level } => {
+                write!(f, "level {warlock_level} Warlock has no pact slots")
+            }
+            ValidationIssue::DarkvisionBelowRace {
+                race,
+                range,
+                expected,
+            } => write!(
+                f,
+                "{race} has {range} ft darkvision, expected {expected} ft"
+            ),
+            ValidationIssue::EmptySpellbook => write!(f, "Wizard has an empty spellbook"),
+        }
+    }
+}
+
+impl GameWorld {
+    /// Check the world for impossible states.
+    ///
+    /// Returns an empty list when the world is consistent.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = validate_character(&self.player_character);
+
+        if let Some(combat) = &self.combat {
+            for combatant in &combat.combatants {
+                if combatant.current_hp > combatant.max_hp {
+                    issues.push(ValidationIssue::HpAboveMaximum {
+                        name: combatant.name.clone(),
+                        current: combatant.current_hp,
+                        maximum: combatant.max_hp,
+                    });
+                }
+                if combatant.current_hp < 0 {
+                    issues.push(ValidationIssue::NegativeHp {
+                        name: combatant.name.clone(),
+                        current: combatant.current_hp,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Clamp every value flagged by [`validate`](Self::validate) back into range.
+    ///
+    /// Returns the issues that were found and repaired.
+    pub fn repair(&mut self) -> Vec<ValidationIssue> {
+        let issues = self.validate();
+        if issues.is_empty() {
+            return issues;
+        }
+
+        repair_character(&mut self.player_character);
+
+        if let Some(combat) = &mut self.combat {
+            for combatant in &mut combat.combatants {
+                combatant.current_hp = combatant.current_hp.clamp(0, combatant.max_hp.max(0));
+            }
+        }
+
+        issues
+    }
+}
+
+fn validate_character(character: &Character) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let name = &character.name;
+
+    let hp = &character.hit_points;
+    if hp.current > hp.maximum {
+        issues.push(ValidationIssue::HpAboveMaximum {
+            name: name.clone(),
+            current: hp.current,
+            maximum: hp.maximum,
+        });
+    }
+    if hp.current < 0 {
+        issues.push(ValidationIssue::NegativeHp {
+            name: name.clone(),
+            current: hp.current,
+        });
+    }
+    if hp.temporary < 0 {
+        issues.push(ValidationIssue::NegativeTemporaryHp {
+            temporary: hp.temporary,
+        });
+    }
+
+    for (die, remaining) in &character.hit_dice.remaining {
+        let total = character.hit_dice.total.get(die).copied().unwrap_or(0);
+        if *remaining > total {
+            issues.push(ValidationIssue::HitDiceAboveTotal {
+                die: *die,
+                remaining: *remaining,
+                total,
+            });
+        }
+    }
+
+    match &character.spellcasting {
+        Some(spellcasting) => {
+            for (i, slot) in spellcasting.spell_slots.slots.iter().enumerate() {
+                if slot.used > slot.total {
+                    issues.push(ValidationIssue::SlotOverused {
+                        level: i as u8 + 1,
+                        used: slot.used,
+                        total: slot.total,
+                    });
+                }
+            }
+            match &spellcasting.pact_slots {
+                Some(pact) if pact.used > pact.total => {
+                    issues.push(ValidationIssue::SlotOverused {
+                        level: pact.level,
+                        used: pact.used,
+                        total: pact.total,
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    if let Some(warlock_level) = missing_pact_slots(character) {
+                        issues.push(ValidationIssue::MissingPactSlots { warlock_level });
+                    }
+                }
+            }
+        }
+        None => {
+            if let Some(class) = missing_spellcasting_class(character) {
+                issues.push(ValidationIssue::MissingSpellcasting { class });
+            }
+        }
+    }
+
+    if spellbook_to_seed(character).is_some() {
+        issues.push(ValidationIssue::EmptySpellbook);
+    }
+
+    let expected = character.race_type.darkvision_range();
+    if character.darkvision_range < expected {
+        issues.push(ValidationIssue::DarkvisionBelowRace {
+            race: character.race_type,
+            range: character.darkvision_range,
+            expected,
+        });
+    }
+
+    for feature in &character.features {
+        if let Some(uses) = &feature.uses {
+            if uses.current > uses.maximum {
+                issues.push(ValidationIssue::FeatureUsesAboveMaximum {
+                    feature: feature.name.clone(),
+                    current: uses.current,
+                    maximum: uses.maximum,
+                });
+            }
+        }
+    }
+
+    let resources = &character.class_resources;
+    let pools = [
+        (
+            "Ki points",
+            resources.ki_points as u32,
+            resources.max_ki_points as u32,
+        ),
+        (
+            "Sorcery points",
+            resources.sorcery_points as u32,
+            resources.max_sorcery_points as u32,
+        ),
+        (
+            "Bardic Inspiration",
+            resources.bardic_inspiration_uses as u32,
+            resources.max_bardic_inspiration as u32,
+        ),
+        (
+            "Lay on Hands",
+            resources.lay_on_hands_pool,
+            resources.lay_on_hands_max,
+        ),
+    ];
+    for (resource, current, maximum) in pools {
+        if current > maximum {
+            issues.push(ValidationIssue::ResourceAboveMaximum {
+                resource,
+                current,
+                maximum,
+            });
+        }
+    }
+
+    issues
+}
+
+fn repair_character(character: &mut Character) {
+    let hp = &mut character.hit_points;
+    hp.current = hp.current.clamp(0, hp.maximum.max(0));
+    hp.temporary = hp.temporary.max(0);
+
+    for (die, remaining) in character.hit_dice.remaining.iter_mut() {
+        let total = character.hit_dice.total.get(die).copied().unwrap_or(0);
+        *remaining = (*remaining).min(total);
+    }
+
+    if let Some(spellcasting) = &mut character.spellcasting {
+        for slot in &mut spellcasting.spell_slots.slots {
+            slot.used = slot.used.min(slot.total);
+        }
+        if let Some(pact) = &mut spellcasting.pact_slots {
+            pact.used = pact.used.min(pact.total);
+        }
+    }
+    if let Some(warlock_level) = missing_pact_slots(character) {
+        // Saves from before Pact Magic kept a pure Warlock's slots in the
+        // regular table
+        let pure_warlock = character
+            .classes
+            .iter()
+            .all(|c| c.class == CharacterClass::Warlock);
+        if let Some(spellcasting) = &mut character.spellcasting {
+            if pure_warlock {
+                spellcasting.spell_slots = SpellSlots::new();
+            }
+            spellcasting.pact_slots = CharacterClass::Warlock.pact_slots_at_level(warlock_level);
+        }
+    } else if let Some(class) = missing_spellcasting_class(character) {
+        let level = character
+            .classes
+            .iter()
+            .find(|c| c.class == class)
+            .map(|c| c.level)
+            .unwrap_or(character.level);
+        let mut spell_slots = SpellSlots::new();
+        for (slot, total) in spell_slots
+            .slots
+            .iter_mut()
+            .zip(class.spell_slots_at_level(level))
+        {
+            *slot = SlotInfo { total, used: 0 };
+        }
+        character.spellcasting = Some(SpellcastingData {
+            ability: class
+                .spellcasting_ability()
+                .expect("spellcasting class has an ability"),
+            spells_known: Vec::new(),
+            spellbook: Vec::new(),
+            spells_prepared: Vec::new(),
+            cantrips_known: Vec::new(),
+            spell_slots,
+            pact_slots: class.pact_slots_at_level(level),
+        });
+    }
+
+    if let Some(spells) = spellbook_to_seed(character) {
+        if let Some(spellcasting) = &mut character.spellcasting {
+            spellcasting.spellbook = spells;
+        }
+    }
+
+    character.darkvision_range = character
+        .darkvision_range
+        .max(character.race_type.darkvision_range());
+
+    for feature in &mut character.features {
+        if let Some(uses) = &mut feature.uses {
+            uses.current = uses.current.min(uses.maximum);
+        }
+    }
+
+    let resources = &mut character.class_resources;
+    resources.ki_points = resources.ki_points.min(resources.max_ki_points);
+    resources.sorcery_points = resources.sorcery_points.min(resources.max_sorcery_points);
+    resources.bardic_inspiration_uses = resources
+        .bardic_inspiration_uses
+        .min(resources.max_bardic_inspiration);
+    resources.lay_on_hands_pool = resources.lay_on_hands_pool.min(resources.lay_on_hands_max);
+}
+
+/// The Warlock level of a caster whose spellcasting data has no pact slots.
+fn missing_pact_slots(character: &Character) -> Option<u8> {
+    let spellcasting = character.spellcasting.as_ref()?;
+    if spellcasting.pact_slots.is_some() {
+        return None;
+    }
+    character
+        .classes
+        .iter()
+        .find(|c| c.class.pact_slots_at_level(c.level).is_some())
+        .map(|c| c.level)
+}
+
+/// The spells an empty Wizard spellbook should hold: those the Wizard
+/// already knows or has prepared, which older saves tracked in place of a
+/// spellbook.
+fn spellbook_to_seed(character: &Character) -> Option<Vec<String>> {
+    let spellcasting = character.spellcasting.as_ref()?;
+    let is_wizard = character
+        .classes
+        .iter()
+        .any(|c| c.class == CharacterClass::Wizard);
+    if !is_wizard || !spellcasting.spellbook.is_empty() {
+        return None;
+    }
+    let mut spells: Vec<String> = Vec::new();
+    for spell in spellcasting
+        .spells_known
+        .iter()
+        .chain(&spellcasting.spells_prepared)
+    {
+        if !spells.contains(spell) {
+            spells.push(spell.clone());
+        }
+    }
+    (!spells.is_empty()).then_some(spells)
+}
+
+/// The first class that should grant spell slots at its current level but
+/// has no spellcasting data backing it.
+fn missing_spellcasting_class(character: &Character) -> Option<CharacterClass> {
+    if character.spellcasting.is_some() {
+        return None;
+    }
+    character
+        .classes
+        .iter()
+        .find(|c| {
+            c.class.spellcasting_ability().is_some()
+                && (c.class.spell_slots_at_level(c.level).iter().any(|&n| n > 0)
+                    || c.class.pact_slots_at_level(c.level).is_some())
+        })
+        .map(|c| c.class)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::{create_sample_fighter, create_sample_sorcerer, CombatState, Combatant};
+    use crate::world::{CharacterId, Feature, FeatureUses, RechargeType};
+
+    #[test]
+    fn test_valid_world_has_no_issues() {
+        let world = GameWorld::new("Test", create_sample_sorcerer("Mage"));
+        assert!(world.validate().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_world_reports_and_repairs() {
+        let mut character = create_sample_sorcerer("Mage");
+        character.hit_points.current = character.hit_points.maximum + 5;
+        character.hit_points.temporary = -3;
+        let spellcasting = character.spellcasting.as_mut().unwrap();
+        let total = spellcasting.spell_slots.slots[0].total;
+        spellcasting.spell_slots.slots[0].used = total + 2;
+        character.class_resources.sorcery_points = 9;
+        character.class_resources.max_sorcery_points = 2;
+        character.features.push(Feature {
+            name: "Lucky".to_string(),
+            description: String::new(),
+            source: "Feat".to_string(),
+            uses: Some(FeatureUses {
+                current: 5,
+                maximum: 3,
+                recharge: RechargeType::LongRest,
+            }),
+        });
+        let maximum = character.hit_points.maximum;
+
+        let mut world = GameWorld::new("Test", character);
+        let issues = world.validate();
+
+        assert!(issues.contains(&ValidationIssue::HpAboveMaximum {
+            name: "Mage".to_string(),
+            current: maximum + 5,
+            maximum,
+        }));
+        assert!(issues.contains(&ValidationIssue::NegativeTemporaryHp { temporary: -3 }));
+        assert!(issues.contains(&ValidationIssue::SlotOverused {
+            level: 1,
+            used: total + 2,
+            total,
+        }));
+        assert!(issues.contains(&ValidationIssue::ResourceAboveMaximum {
+            resource: "Sorcery points",
+            current: 9,
+            maximum: 2,
+        }));
+        assert!(issues.contains(&ValidationIssue::FeatureUsesAboveMaximum {
+            feature: "Lucky".to_string(),
+            current: 5,
+            maximum: 3,
+        }));
+
+        let repaired = world.repair();
+        assert_eq!(repaired, issues);
+        assert!(world.validate().is_empty());
+
+        let pc = &world.player_character;
+        assert_eq!(pc.hit_points.current, maximum);
+        assert_eq!(pc.hit_points.temporary, 0);
+        assert_eq!(
+            pc.spellcasting.as_ref().unwrap().spell_slots.slots[0].used,
+            total
+        );
+        assert_eq!(pc.class_resources.sorcery_points, 2);
+        assert_eq!(
+            pc.features.last().unwrap().uses.as_ref().unwrap().current,
+            3
+        );
+    }
+
+    #[test]
+    fn test_missing_spellcasting_is_restored() {
+        let mut character = create_sample_sorcerer("Mage");
+        character.spellcasting = None;
+        let mut world = GameWorld::new("Test", character);
+
+        assert_eq!(
+            world.validate(),
+            vec![ValidationIssue::MissingSpellcasting {
+                class: CharacterClass::Sorcerer
+            }]
+        );
+
+        world.repair();
+        let spellcasting = world.player_character.spellcasting.as_ref().unwrap();
+        assert!(spellcasting.spell_slots.slots[0].total > 0);
+        assert!(world.validate().is_empty());
+    }
+
+    #[test]
+    fn test_non_caster_needs_no_spellcasting() {
+        let world = GameWorld::new("Test", create_sample_fighter("Hero"));
+        assert!(world.validate().is_empty());
+    }
+
+    #[test]
+    fn test_combatant_hp_is_clamped() {
+        let mut world = GameWorld::new("Test", create_sample_fighter("Hero"));
+        let mut combat = CombatState::new();
+        combat.add_combatant(Combatant {
+            id: CharacterId::new(),
+            name: "Goblin".to_string(),
+            initiative: 10,
+            is_player: false,
+            is_ally: false,
+            current_hp: -4,
+            max_hp: 7,
+            armor_class: 13,
+            resistances: Vec::new(),
+        });
+        world.combat = Some(combat);
+
+        assert_eq!(
+            world.validate(),
+            vec![ValidationIssue::NegativeHp {
+                name: "Goblin".to_string(),
+                current: -4,
+            }]
+        );
+        world.repair();
+        assert_eq!(world.combat.as_ref().unwrap().combatants[0].current_hp, 0);
+    }
+
+    #[test]
+    fn test_pre_pact_magic_warlock_gets_pact_slots() {
+        use crate::world::{Ability, ClassLevel};
+
+        let mut character = Character::new("Vesper");
+        character.level = 3;
+        character.classes.push(ClassLevel {
+            class: CharacterClass::Warlock,
+            level: 3,
+            subclass: None,
+        });
+        // Older builds kept Warlock slots in the regular table
+        let mut spell_slots = SpellSlots::new();
+        spell_slots.slots[1] = SlotInfo { total: 2, used: 1 };
+        character.spellcasting = Some(SpellcastingData {
+            ability: Ability::Charisma,
+            spells_known: Vec::new(),
+            spellbook: Vec::new(),
+            spells_prepared: Vec::new(),
+            cantrips_known: Vec::new(),
+            spell_slots,
+            pact_slots: None,
+        });
+        let mut world = GameWorld::new("Test", character);
+
+        assert_eq!(
+            world.validate(),
+            vec![ValidationIssue::MissingPactSlots { warlock_level: 3 }]
+        );
+
+        world.repair();
+        let spellcasting = world.player_character.spellcasting.as_ref().unwrap();
+        assert_eq!(
+            spellcasting.pact_slots,
+            CharacterClass::Warlock.pact_slots_at_level(3)
+        );
+        assert!(spellcasting.spell_slots.slots.iter().all(|s| s.total == 0));
+        assert!(world.validate().is_empty());
+    }
+
+    #[test]
+    fn test_missing_racial_darkvision_is_derived() {
+        let mut character = create_sample_fighter("Aelar");
+        character.race_type = RaceType::Elf;
+        character.darkvision_range = 0;
+        let mut world = GameWorld::new("Test", character);
+
+        assert_eq!(
+            world.validate(),
+            vec![ValidationIssue::DarkvisionBelowRace {
+                race: RaceType::Elf,
+                range: 0,
+                expected: 60,
+            }]
+        );
+
+        world.repair();
+        assert_eq!(world.player_character.darkvision_range, 60);
+        assert!(world.validate().is_empty());
+    }
+
+    #[test]
+    fn test_empty_wizard_spellbook_is_seeded_from_known_spells() {
+        use crate::world::{Ability, ClassLevel};
+
+        let mut character = Character::new("Elara");
+        character.classes.push(ClassLevel {
+            class: CharacterClass::Wizard,
+            level: 1,
+            subclass: None,
+        });
+        let mut spell_slots = SpellSlots::new();
+        spell_slots.slots[0] = SlotInfo { total: 2, used: 0 };
+        character.spellcasting = Some(SpellcastingData {
+            ability: Ability::Intelligence,
+            spells_known: vec!["Magic Missile".to_string(), "Shield".to_string()],
+            spellbook: Vec::new(),
+            spells_prepared: vec!["Shield".to_string(), "Sleep".to_string()],
+            cantrips_known: Vec::new(),
+            spell_slots,
+            pact_slots: None,
+        });
+        let mut world = GameWorld::new("Test", character);
+
+        assert_eq!(world.validate(), vec![ValidationIssue::EmptySpellbook]);
+
+        world.repair();
+        let spellcasting = world.player_character.spellcasting.as_ref().unwrap();
+        assert_eq!(
+            spellcasting.spellbook,
+            vec!["Magic Missile", "Shield", "Sleep"]
+        );
+        assert!(world.validate().is_empty());
+    }
+}
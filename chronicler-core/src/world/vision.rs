@@ -0,0 +1,46 @@
+//! Light levels and darkvision.
+//!
+//! This module provides the [`LightLevel`] of an area and the rules for how
+//! darkvision changes what a creature can see in it.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// How well lit an area is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LightLevel {
+    /// Normal vision works without penalty.
+    Bright,
+    /// Lightly obscured: disadvantage on sight-based Perception checks.
+    Dim,
+    /// Heavily obscured: effectively blinded, so attacks have disadvantage.
+    Darkness,
+}
+
+impl LightLevel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LightLevel::Bright => "bright light",
+            LightLevel::Dim => "dim light",
+            LightLevel::Darkness => "darkness",
+        }
+    }
+
+    /// The light level as seen by a creature with the given darkvision range.
+    /// Darkvision treats dim light as bright and darkness as dim.
+    pub fn seen_with_darkvision(self, darkvision_range: u8) -> LightLevel {
+        if darkvision_range == 0 {
+            return self;
+        }
+        match self {
+            LightLevel::Bright | LightLevel::Dim => LightLevel::Bright,
+            LightLevel::Darkness => LightLevel::Dim,
+        }
+    }
+}
+
+impl fmt::Display for LightLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -43,6 +43,7 @@ fn create_sample_wizard(name: &str) -> Character {
             "Thunderwave".to_string(),
             "Sleep".to_string(),
         ],
+        spellbook: Vec::new(),
         spells_prepared: vec![
             "Magic Missile".to_string(),
             "Shield".to_string(),
@@ -67,6 +68,7 @@ fn create_sample_wizard(name: &str) -> Character {
                 SlotInfo { total: 0, used: 0 },
             ],
         },
+        pact_slots: None,
     });
 
     // Set hit points
@@ -103,6 +105,7 @@ fn create_sample_cleric(name: &str) -> Character {
     character.spellcasting = Some(SpellcastingData {
         ability: Ability::Wisdom,
         spells_known: vec![],
+        spellbook: Vec::new(),
         spells_prepared: vec![
             "Cure Wounds".to_string(),
             "Healing Word".to_string(),
@@ -127,6 +130,7 @@ fn create_sample_cleric(name: &str) -> Character {
                 SlotInfo { total: 0, used: 0 },
             ],
         },
+        pact_slots: None,
     });
 
     // Set hit points
@@ -42,7 +42,7 @@ pub(crate) enum ApiContentBlock {
     },
     ToolResult {
         tool_use_id: String,
-        content: String,
+        content: Vec<ApiContentBlock>,
         is_error: bool,
     },
 }
@@ -69,7 +69,7 @@ impl From<&ContentBlock> for ApiContentBlock {
                 is_error,
             } => ApiContentBlock::ToolResult {
                 tool_use_id: tool_use_id.clone(),
-                content: content.clone(),
+                content: content.iter().map(Into::into).collect(),
                 is_error: *is_error,
             },
             ContentBlock::Thinking { thinking } => ApiContentBlock::Text {
@@ -0,0 +1,268 @@
+//! Message Batches API.
+//!
+//! Batches process many Messages requests asynchronously at reduced cost.
+//! Submit with [`Claude::create_batch`], poll with [`Claude::get_batch`] until
+//! it has [`BatchStatus::Ended`], then collect [`Claude::batch_results`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::api_types::{ApiRequest, ApiResponse};
+use crate::client::Claude;
+use crate::error::Error;
+use crate::transport::Method;
+use crate::types::{Request, Response};
+
+/// Processing state of a batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    InProgress,
+    Canceling,
+    Ended,
+}
+
+/// How many of a batch's requests are in each state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct BatchRequestCounts {
+    pub processing: usize,
+    pub succeeded: usize,
+    pub errored: usize,
+    pub canceled: usize,
+    pub expired: usize,
+}
+
+/// A submitted message batch.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Batch {
+    pub id: String,
+    pub processing_status: BatchStatus,
+    pub request_counts: BatchRequestCounts,
+    /// Where results can be downloaded once the batch has ended.
+    pub results_url: Option<String>,
+}
+
+// Internal API types
+
+#[derive(Debug, Serialize)]
+struct ApiBatchRequest {
+    requests: Vec<ApiBatchItem>,
+}
+
+#[derive(Debug, Serialize)]
+struct ApiBatchItem {
+    custom_id: String,
+    params: ApiRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiBatchResultLine {
+    custom_id: String,
+    result: ApiBatchResult,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ApiBatchResult {
+    Succeeded { message: ApiResponse },
+    Errored { error: ApiBatchError },
+    Canceled,
+    Expired,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiBatchError {
+    error: ApiBatchErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiBatchErrorDetail {
+    r#type: String,
+    message: String,
+}
+
+impl Claude {
+    /// Submits requests as a message batch, each tagged with a caller-chosen
+    /// `custom_id` used to match up its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any request fails [`Request::validate`], the
+    /// network request fails, or the API rejects the batch.
+    pub async fn create_batch(&self, requests: Vec<(String, Request)>) -> Result<Batch, Error> {
+        for (_, request) in &requests {
+            request.validate()?;
+        }
+        let body = ApiBatchRequest {
+            requests: requests
+                .iter()
+                .map(|(custom_id, request)| ApiBatchItem {
+                    custom_id: custom_id.clone(),
+                    params: self.build_api_request(request, false),
+                })
+                .collect(),
+        };
+        let body = serde_json::to_string(&body).map_err(|e| Error::Parse(e.to_string()))?;
+
+        let response = self
+            .send_http(Method::Post, "/messages/batches", body)
+            .await?;
+        parse_json(&self.response_text(response).await?)
+    }
+
+    /// Fetches the current state of a batch.
+    pub async fn get_batch(&self, id: &str) -> Result<Batch, Error> {
+        let response = self
+            .send_http(
+                Method::Get,
+                &format!("/messages/batches/{id}"),
+                String::new(),
+            )
+            .await?;
+        parse_json(&self.response_text(response).await?)
+    }
+
+    /// Downloads the results of an ended batch, pairing each `custom_id` with
+    /// its response or the error that request met.
+    ///
+    /// Results are not guaranteed to be in submission order.
+    pub async fn batch_results(
+        &self,
+        id: &str,
+    ) -> Result<Vec<(String, Result<Response, Error>)>, Error> {
+        let response = self
+            .send_http(
+                Method::Get,
+                &format!("/messages/batches/{id}/results"),
+                String::new(),
+            )
+            .await?;
+        let body = self.response_text(response).await?;
+
+        body.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let line: ApiBatchResultLine = parse_json(line)?;
+                let result = match line.result {
+                    ApiBatchResult::Succeeded { message } => Ok(self.parse_response(message)),
+                    ApiBatchResult::Errored { error } => Err(Error::Batch(format!(
+                        "{}: {}",
+                        error.error.r#type, error.error.message
+                    ))),
+                    ApiBatchResult::Canceled => Err(Error::Batch("canceled".to_string())),
+                    ApiBatchResult::Expired => Err(Error::Batch("expired".to_string())),
+                };
+                Ok((line.custom_id, result))
+            })
+            .collect()
+    }
+}
+
+fn parse_json<T: for<'de> Deserialize<'de>>(body: &str) -> Result<T, Error> {
+    serde_json::from_str(body).map_err(|e| Error::Parse(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::MockTransport;
+    use crate::types::Message;
+
+    const BATCH_IN_PROGRESS: &str = r#"{
+        "id": "msgbatch_01",
+        "type": "message_batch",
+        "processing_status": "in_progress",
+        "request_counts": {"processing": 2, "succeeded": 0, "errored": 0, "canceled": 0, "expired": 0},
+        "results_url": null
+    }"#;
+
+    fn client_with(transport: &MockTransport) -> Claude {
+        Claude::new("test-key").with_transport(transport.clone())
+    }
+
+    #[tokio::test]
+    async fn test_create_batch_submits_all_requests() {
+        let transport = MockTransport::new();
+        transport.push_response(200, BATCH_IN_PROGRESS);
+
+        let batch = client_with(&transport)
+            .create_batch(vec![
+                (
+                    "npc-1".to_string(),
+                    Request::new(vec![Message::user("A blacksmith")]),
+                ),
+                (
+                    "npc-2".to_string(),
+                    Request::new(vec![Message::user("A herbalist")]),
+                ),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(batch.id, "msgbatch_01");
+        assert_eq!(batch.processing_status, BatchStatus::InProgress);
+        assert_eq!(batch.request_counts.processing, 2);
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::Post);
+        assert!(request.url.ends_with("/messages/batches"));
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["requests"][1]["custom_id"], "npc-2");
+        assert_eq!(
+            body["requests"][1]["params"]["messages"][0]["content"][0]["text"],
+            "A herbalist"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_batch() {
+        let transport = MockTransport::new();
+        transport.push_response(
+            200,
+            BATCH_IN_PROGRESS
+                .replace("in_progress", "ended")
+                .replace("null", "\"https://example.com/results\""),
+        );
+
+        let batch = client_with(&transport)
+            .get_batch("msgbatch_01")
+            .await
+            .unwrap();
+
+        assert_eq!(batch.processing_status, BatchStatus::Ended);
+        assert!(batch.results_url.is_some());
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::Get);
+        assert!(request.url.ends_with("/messages/batches/msgbatch_01"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_results_map_custom_ids() {
+        let transport = MockTransport::new();
+        transport.push_response(
+            200,
+            [
+                r#"{"custom_id": "npc-1", "result": {"type": "succeeded", "message": {"id": "msg_1", "model": "m", "content": [{"type": "text", "text": "Gruff and kind."}], "stop_reason": "end_turn", "usage": {"input_tokens": 3, "output_tokens": 4}}}}"#,
+                r#"{"custom_id": "npc-2", "result": {"type": "errored", "error": {"type": "error", "error": {"type": "invalid_request_error", "message": "max_tokens too large"}}}}"#,
+                r#"{"custom_id": "npc-3", "result": {"type": "expired"}}"#,
+            ]
+            .join("\n"),
+        );
+
+        let results = client_with(&transport)
+            .batch_results("msgbatch_01")
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, "npc-1");
+        assert_eq!(results[0].1.as_ref().unwrap().text(), "Gruff and kind.");
+        assert!(matches!(
+            &results[1].1,
+            Err(Error::Batch(message)) if message.contains("max_tokens too large")
+        ));
+        assert!(matches!(&results[2].1, Err(Error::Batch(message)) if message == "expired"));
+        assert!(transport.requests()[0]
+            .url
+            .ends_with("/messages/batches/msgbatch_01/results"));
+    }
+}
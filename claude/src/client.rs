@@ -3,18 +3,21 @@
 use futures::StreamExt;
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use std::pin::Pin;
+use std::sync::Arc;
 use tokio_stream::Stream;
 
 use crate::api_types::{ApiContent, ApiMessage, ApiRequest, ApiResponse, ApiTool, ApiToolChoice};
 use crate::error::Error;
-use crate::streaming::parse_sse_events_buffered;
+use crate::log::{redact, LogEvent, Logger};
+use crate::streaming::{parse_sse_events_buffered, StreamAccumulator};
+use crate::transport::{HttpRequest, Method, RawResponse, ReqwestTransport, Transport};
 use crate::types::{
     ContentBlock, Message, Request, Response, Role, StopReason, StreamEvent, ToolChoice,
     ToolResult, ToolUse, Usage,
 };
 
 const API_BASE: &str = "https://api.anthropic.com/v1";
-const API_VERSION: &str = "2023-06-01";
+pub(crate) const DEFAULT_API_VERSION: &str = "2023-06-01";
 pub(crate) const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
 
 /// Claude API client for making requests to Anthropic's Claude models.
@@ -39,28 +42,31 @@ pub(crate) const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
 /// ```
 #[derive(Clone)]
 pub struct Claude {
-    client: reqwest::Client,
+    transport: Arc<dyn Transport>,
     api_key: String,
     pub(crate) model: String,
+    api_version: String,
+    betas: Vec<String>,
+    logger: Option<Logger>,
 }
 
 impl Claude {
     /// Creates a new Claude client with the provided API key.
     ///
-    /// Initializes with the default model and HTTP timeouts (120s total, 30s connect).
+    /// Initializes with the default model and a [`ReqwestTransport`] (HTTP
+    /// timeouts of 120s total, 30s connect).
     ///
     /// # Arguments
     ///
     /// * `api_key` - Your Anthropic API key from <https://console.anthropic.com/>
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
-            client: reqwest::Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(30))
-                .build()
-                .expect("Failed to build HTTP client"),
+            transport: Arc::new(ReqwestTransport::new()),
             api_key: api_key.into(),
             model: DEFAULT_MODEL.to_string(),
+            api_version: DEFAULT_API_VERSION.to_string(),
+            betas: Vec::new(),
+            logger: None,
         }
     }
 
@@ -70,10 +76,17 @@ impl Claude {
     ///
     /// # Errors
     ///
-    /// Returns [`Error::NoApiKey`] if `ANTHROPIC_API_KEY` is not set.
+    /// Returns [`Error::NoApiKey`] if `ANTHROPIC_API_KEY` is not set or is blank.
     pub fn from_env() -> Result<Self, Error> {
-        let api_key = std::env::var("ANTHROPIC_API_KEY").map_err(|_| Error::NoApiKey)?;
-        Ok(Self::new(api_key))
+        Self::from_api_key(std::env::var("ANTHROPIC_API_KEY").ok())
+    }
+
+    /// Creates a client from a key that may be missing, as read from the environment.
+    pub(crate) fn from_api_key(api_key: Option<String>) -> Result<Self, Error> {
+        match api_key {
+            Some(key) if !key.trim().is_empty() => Ok(Self::new(key.trim())),
+            _ => Err(Error::NoApiKey),
+        }
     }
 
     /// Sets the default model for this client.
@@ -84,6 +97,41 @@ impl Claude {
         self
     }
 
+    /// Sends requests through the given transport instead of `reqwest`.
+    ///
+    /// Use a [`MockTransport`](crate::MockTransport) to test without a network.
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// Sets the `anthropic-version` header sent with every request.
+    ///
+    /// Defaults to `2023-06-01`.
+    pub fn with_api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = version.into();
+        self
+    }
+
+    /// Opts into a beta feature (e.g. `prompt-caching-2024-07-31`).
+    ///
+    /// Features are sent comma-joined in the `anthropic-beta` header.
+    /// Adding the same feature twice has no further effect.
+    pub fn with_beta(mut self, feature: &str) -> Self {
+        if !self.betas.iter().any(|b| b == feature) {
+            self.betas.push(feature.to_string());
+        }
+        self
+    }
+
+    /// Installs a hook that sees every request, raw response body, streamed
+    /// event, and usage report, for debugging. The API key is redacted from
+    /// logged requests.
+    pub fn with_logger(mut self, logger: impl Fn(&LogEvent) + Send + Sync + 'static) -> Self {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
     /// Sends a completion request and returns the full response.
     ///
     /// This is the primary method for non-streaming interactions with Claude.
@@ -93,33 +141,15 @@ impl Claude {
     ///
     /// Returns an error if the network request fails or the API returns an error.
     pub async fn complete(&self, request: Request) -> Result<Response, Error> {
-        let api_request = self.build_api_request(&request, false);
-        let headers = self.build_headers()?;
-
-        let response = self
-            .client
-            .post(format!("{API_BASE}/messages"))
-            .headers(headers)
-            .json(&api_request)
-            .send()
-            .await
-            .map_err(|e| Error::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let body = response.text().await.unwrap_or_default();
-            return Err(Error::Api {
-                status,
-                message: body,
-            });
-        }
+        let response = self.send(&request, false).await?;
+        let body = self.response_text(response).await?;
 
-        let api_response: ApiResponse = response
-            .json()
-            .await
-            .map_err(|e| Error::Parse(e.to_string()))?;
+        let api_response: ApiResponse =
+            serde_json::from_str(&body).map_err(|e| Error::Parse(e.to_string()))?;
 
-        Ok(self.parse_response(api_response))
+        let response = self.parse_response(api_response);
+        self.log(|| LogEvent::Usage(response.usage.clone()));
+        Ok(response)
     }
 
     /// Sends a completion request and returns a stream of response events.
@@ -130,44 +160,139 @@ impl Claude {
         &self,
         request: Request,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamEvent, Error>> + Send>>, Error> {
-        let api_request = self.build_api_request(&request, true);
-        let headers = self.build_headers()?;
-
-        let response = self
-            .client
-            .post(format!("{API_BASE}/messages"))
-            .headers(headers)
-            .json(&api_request)
-            .send()
-            .await
-            .map_err(|e| Error::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            let status = response.status().as_u16();
-            let body = response.text().await.unwrap_or_default();
-            return Err(Error::Api {
-                status,
-                message: body,
-            });
-        }
+        let response = self.send(&request, true).await?;
 
         // Use scan to maintain a buffer for incomplete SSE events across chunks
         let stream = response
-            .bytes_stream()
+            .body
             .scan(String::new(), |buffer, result| {
                 let events = match result {
                     Ok(bytes) => {
                         buffer.push_str(&String::from_utf8_lossy(&bytes));
                         parse_sse_events_buffered(buffer)
                     }
-                    Err(e) => vec![Err(Error::Network(e.to_string()))],
+                    Err(e) => vec![Err(e)],
                 };
                 // Return Some to continue, the events vector
                 futures::future::ready(Some(events))
             })
             .flat_map(futures::stream::iter);
 
-        Ok(Box::pin(stream))
+        match self.logger.clone() {
+            Some(logger) => Ok(Box::pin(stream.inspect(move |event| {
+                if let Ok(event) = event {
+                    logger(&LogEvent::Stream(event.clone()));
+                }
+            }))),
+            None => Ok(Box::pin(stream)),
+        }
+    }
+
+    /// Streams a completion and collects it into a full [`Response`].
+    ///
+    /// If the stream breaks off after content has arrived, the partial
+    /// response is returned with `truncated` set instead of an error.
+    pub async fn stream_complete(&self, request: Request) -> Result<Response, Error> {
+        self.stream_complete_with_usage(request, |_| {}).await
+    }
+
+    /// Like [`stream_complete`](Self::stream_complete), calling `on_usage`
+    /// with the running output token count each time the server reports a
+    /// new one, e.g. to drive a live cost meter.
+    pub async fn stream_complete_with_usage(
+        &self,
+        request: Request,
+        mut on_usage: impl FnMut(usize),
+    ) -> Result<Response, Error> {
+        let mut stream = self.stream(request).await?;
+        let mut accumulator = StreamAccumulator::new();
+
+        while let Some(event) = stream.next().await {
+            match event {
+                Ok(StreamEvent::Error { message }) => return Err(Error::Stream(message)),
+                Ok(event) => {
+                    let before = accumulator.output_tokens();
+                    accumulator.push(&event);
+                    if accumulator.output_tokens() != before {
+                        on_usage(accumulator.output_tokens());
+                    }
+                }
+                Err(_) if accumulator.has_content() => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(accumulator.finish())
+    }
+
+    /// Posts a request to the Messages endpoint, returning the response if
+    /// its status is successful.
+    async fn send(&self, request: &Request, stream: bool) -> Result<RawResponse, Error> {
+        request.validate()?;
+        let api_request = self.build_api_request(request, stream);
+        let body = serde_json::to_string(&api_request).map_err(|e| Error::Parse(e.to_string()))?;
+        self.send_http(Method::Post, "/messages", body).await
+    }
+
+    /// Sends a request to an API path, returning the response if its status
+    /// is successful.
+    pub(crate) async fn send_http(
+        &self,
+        method: Method,
+        path: &str,
+        body: String,
+    ) -> Result<RawResponse, Error> {
+        let headers = self
+            .build_headers()?
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    value.to_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect();
+
+        let request = HttpRequest {
+            method,
+            url: format!("{API_BASE}{path}"),
+            headers,
+            body,
+        };
+        self.log(|| LogEvent::Request(redact(&request)));
+        let response = self.transport.send(request).await?;
+
+        if !response.is_success() {
+            let status = response.status;
+            let body = response.text().await.unwrap_or_default();
+            self.log(|| LogEvent::Response {
+                status,
+                body: body.clone(),
+            });
+            return Err(Error::Api {
+                status,
+                message: body,
+            });
+        }
+        Ok(response)
+    }
+
+    /// Collects a response body, logging it.
+    pub(crate) async fn response_text(&self, response: RawResponse) -> Result<String, Error> {
+        let status = response.status;
+        let body = response.text().await?;
+        self.log(|| LogEvent::Response {
+            status,
+            body: body.clone(),
+        });
+        Ok(body)
+    }
+
+    /// Passes an event to the logger, building it only if one is installed.
+    fn log(&self, event: impl FnOnce() -> LogEvent) {
+        if let Some(logger) = &self.logger {
+            logger(&event());
+        }
     }
 
     /// Run a tool use loop until completion.
@@ -238,7 +363,7 @@ impl Claude {
         }
     }
 
-    fn build_headers(&self) -> Result<HeaderMap, Error> {
+    pub(crate) fn build_headers(&self) -> Result<HeaderMap, Error> {
         let mut headers = HeaderMap::new();
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         headers.insert(
@@ -246,11 +371,22 @@ impl Claude {
             HeaderValue::from_str(&self.api_key)
                 .map_err(|e| Error::Config(format!("Invalid API key: {e}")))?,
         );
-        headers.insert("anthropic-version", HeaderValue::from_static(API_VERSION));
+        headers.insert(
+            "anthropic-version",
+            HeaderValue::from_str(&self.api_version)
+                .map_err(|e| Error::Config(format!("Invalid API version: {e}")))?,
+        );
+        if !self.betas.is_empty() {
+            headers.insert(
+                "anthropic-beta",
+                HeaderValue::from_str(&self.betas.join(","))
+                    .map_err(|e| Error::Config(format!("Invalid beta feature: {e}")))?,
+            );
+        }
         Ok(headers)
     }
 
-    fn build_api_request(&self, request: &Request, stream: bool) -> ApiRequest {
+    pub(crate) fn build_api_request(&self, request: &Request, stream: bool) -> ApiRequest {
         let messages: Vec<ApiMessage> = request
             .messages
             .iter()
@@ -299,7 +435,7 @@ impl Claude {
         }
     }
 
-    fn parse_response(&self, api_response: ApiResponse) -> Response {
+    pub(crate) fn parse_response(&self, api_response: ApiResponse) -> Response {
         let content: Vec<ContentBlock> = api_response
             .content
             .into_iter()
@@ -329,6 +465,7 @@ impl Claude {
                 input_tokens: api_response.usage.input_tokens,
                 output_tokens: api_response.usage.output_tokens,
             },
+            truncated: false,
         }
     }
 }
@@ -5,7 +5,7 @@ use thiserror::Error;
 /// Errors that can occur when using the Claude client.
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("API key not configured")]
+    #[error("API key not configured - set ANTHROPIC_API_KEY")]
     NoApiKey,
 
     #[error("Network error: {0}")]
@@ -19,4 +19,14 @@ pub enum Error {
 
     #[error("Invalid configuration: {0}")]
     Config(String),
+
+    /// The request would be rejected by the API, caught before sending.
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Stream error: {0}")]
+    Stream(String),
+
+    #[error("Batch request failed: {0}")]
+    Batch(String),
 }
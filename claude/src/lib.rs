@@ -3,16 +3,26 @@
 //! This crate provides a focused client for Claude's Messages API with:
 //! - Non-streaming and streaming completions
 //! - Tool use support
+//! - Message Batches for bulk, non-interactive generation
 //! - Proper SSE parsing for streaming responses
+//! - A pluggable HTTP [`Transport`], with a [`MockTransport`] for tests
+//! - An optional [`LogEvent`] hook for debugging raw exchanges
 
 mod api_types;
+mod batch;
 mod client;
 mod error;
+mod log;
 mod streaming;
+mod transport;
 mod types;
 
+pub use batch::{Batch, BatchRequestCounts, BatchStatus};
 pub use client::Claude;
 pub use error::Error;
+pub use log::{LogEvent, REDACTED};
+pub use streaming::StreamAccumulator;
+pub use transport::{HttpRequest, Method, MockTransport, RawResponse, ReqwestTransport, Transport};
 pub use types::{
     ContentBlock, Message, Request, Response, Role, StopReason, StreamEvent, Tool, ToolChoice,
     ToolResult, ToolUse, Usage,
@@ -29,12 +39,42 @@ mod tests {
         assert_eq!(client.model, DEFAULT_MODEL);
     }
 
+    #[test]
+    fn test_missing_or_blank_api_key_is_no_api_key() {
+        assert!(matches!(Claude::from_api_key(None), Err(Error::NoApiKey)));
+        assert!(matches!(
+            Claude::from_api_key(Some("   ".to_string())),
+            Err(Error::NoApiKey)
+        ));
+        assert!(Claude::from_api_key(Some("sk-test".to_string())).is_ok());
+    }
+
     #[test]
     fn test_client_with_model() {
         let client = Claude::new("test-key").with_model("claude-3-opus");
         assert_eq!(client.model, "claude-3-opus");
     }
 
+    #[test]
+    fn test_version_and_beta_headers() {
+        let headers = Claude::new("test-key").build_headers().unwrap();
+        assert_eq!(headers["anthropic-version"], "2023-06-01");
+        assert!(headers.get("anthropic-beta").is_none());
+
+        let headers = Claude::new("test-key")
+            .with_api_version("2024-01-01")
+            .with_beta("prompt-caching-2024-07-31")
+            .with_beta("message-batches-2024-09-24")
+            .with_beta("prompt-caching-2024-07-31")
+            .build_headers()
+            .unwrap();
+        assert_eq!(headers["anthropic-version"], "2024-01-01");
+        assert_eq!(
+            headers["anthropic-beta"],
+            "prompt-caching-2024-07-31,message-batches-2024-09-24"
+        );
+    }
+
     #[test]
     fn test_request_builder() {
         let request = Request::new(vec![Message::user("Hello")])
@@ -61,10 +101,303 @@ mod tests {
     fn test_tool_result() {
         let success = ToolResult::success("worked");
         assert!(!success.is_error);
-        assert_eq!(success.content, "worked");
+        assert_eq!(success.text(), "worked");
 
         let error = ToolResult::error("failed");
         assert!(error.is_error);
-        assert_eq!(error.content, "failed");
+        assert_eq!(error.text(), "failed");
+    }
+
+    #[test]
+    fn test_tool_result_with_image_serializes_as_blocks() {
+        let result = ToolResult::blocks(vec![
+            ContentBlock::Text {
+                text: "The ruined keep, north at top.".to_string(),
+            },
+            ContentBlock::Image {
+                media_type: "image/png".to_string(),
+                data: "iVBORw0KGgo=".to_string(),
+            },
+        ]);
+        let request = Request::new(vec![Message {
+            role: Role::User,
+            content: vec![ContentBlock::ToolResult {
+                tool_use_id: "toolu_01".to_string(),
+                content: result.content,
+                is_error: result.is_error,
+            }],
+        }]);
+
+        let body = serde_json::to_value(Claude::new("test-key").build_api_request(&request, false))
+            .unwrap();
+        let block = &body["messages"][0]["content"][0];
+        assert_eq!(block["type"], "tool_result");
+        assert_eq!(block["tool_use_id"], "toolu_01");
+        assert_eq!(block["content"][0]["type"], "text");
+        assert_eq!(
+            block["content"][0]["text"],
+            "The ruined keep, north at top."
+        );
+        assert_eq!(block["content"][1]["type"], "image");
+        assert_eq!(block["content"][1]["source"]["type"], "base64");
+        assert_eq!(block["content"][1]["source"]["media_type"], "image/png");
+    }
+
+    const CANNED_RESPONSE: &str = r#"{
+        "id": "msg_01",
+        "model": "claude-sonnet-4-20250514",
+        "content": [
+            {"type": "text", "text": "Paris."},
+            {"type": "tool_use", "id": "toolu_01", "name": "lookup", "input": {"q": "France"}}
+        ],
+        "stop_reason": "tool_use",
+        "usage": {"input_tokens": 12, "output_tokens": 5}
+    }"#;
+
+    #[tokio::test]
+    async fn test_complete_parses_canned_body() {
+        let transport = MockTransport::new();
+        transport.push_response(200, CANNED_RESPONSE);
+        let client = Claude::new("test-key")
+            .with_transport(transport.clone())
+            .with_beta("prompt-caching-2024-07-31");
+
+        let response = client
+            .complete(Request::new(vec![Message::user("Capital of France?")]))
+            .await
+            .unwrap();
+
+        assert_eq!(response.id, "msg_01");
+        assert_eq!(response.text(), "Paris.");
+        assert_eq!(response.stop_reason, StopReason::ToolUse);
+        assert_eq!(response.usage.output_tokens, 5);
+        assert!(matches!(
+            &response.content[1],
+            ContentBlock::ToolUse { name, .. } if name == "lookup"
+        ));
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].url.ends_with("/messages"));
+        assert_eq!(requests[0].header("x-api-key"), Some("test-key"));
+        assert_eq!(
+            requests[0].header("anthropic-beta"),
+            Some("prompt-caching-2024-07-31")
+        );
+        assert!(requests[0].body.contains("Capital of France?"));
+    }
+
+    fn tool(name: &str, input_schema: serde_json::Value) -> Tool {
+        Tool {
+            name: name.to_string(),
+            description: "A test tool".to_string(),
+            input_schema,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_tool_names_are_rejected_before_sending() {
+        let schema = serde_json::json!({"type": "object", "properties": {}});
+        let transport = MockTransport::new();
+        let client = Claude::new("test-key").with_transport(transport.clone());
+        let request = Request::new(vec![Message::user("Roll")]).with_tools(vec![
+            tool("roll_dice", schema.clone()),
+            tool("roll_dice", schema),
+        ]);
+
+        let result = client.complete(request).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::InvalidRequest(ref message)) if message.contains("roll_dice")
+        ));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn test_malformed_tool_schemas_are_rejected() {
+        let valid = serde_json::json!({
+            "type": "object",
+            "properties": {"notation": {"type": "string"}},
+            "required": ["notation"]
+        });
+        let request = |schema| Request::new(vec![]).with_tools(vec![tool("roll_dice", schema)]);
+        assert!(request(valid).validate().is_ok());
+
+        for schema in [
+            serde_json::json!("object"),
+            serde_json::json!({"type": "string"}),
+            serde_json::json!({"type": "object", "properties": ["notation"]}),
+            serde_json::json!({"type": "object", "properties": {}, "required": ["notation"]}),
+        ] {
+            let result = request(schema.clone()).validate();
+            assert!(
+                matches!(result, Err(Error::InvalidRequest(ref m)) if m.contains("roll_dice")),
+                "accepted {schema}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_complete_reports_api_errors() {
+        let transport = MockTransport::new();
+        transport.push_response(529, "overloaded");
+        let client = Claude::new("test-key").with_transport(transport);
+
+        let result = client
+            .complete(Request::new(vec![Message::user("Hi")]))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(Error::Api { status: 529, ref message }) if message == "overloaded"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_parses_canned_events() {
+        use futures::StreamExt;
+
+        let transport = MockTransport::new();
+        transport.push_response(
+            200,
+            "event: content_block_delta\n\
+             data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hello\"}}\n\n\
+             event: message_stop\n\
+             data: {\"type\":\"message_stop\"}\n\n",
+        );
+        let client = Claude::new("test-key").with_transport(transport.clone());
+
+        let events: Vec<_> = client
+            .stream(Request::new(vec![Message::user("Hi")]))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert!(matches!(
+            &events[0],
+            Ok(StreamEvent::TextDelta { text, .. }) if text == "Hello"
+        ));
+        assert!(transport.requests()[0].body.contains("\"stream\":true"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_complete_reports_running_usage() {
+        let transport = MockTransport::new();
+        transport.push_response(
+            200,
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hello\"}}\n\n\
+             data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":null},\"usage\":{\"output_tokens\":2}}\n\n\
+             data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\"},\"usage\":{\"output_tokens\":7}}\n\n\
+             data: {\"type\":\"message_stop\"}\n\n",
+        );
+        let client = Claude::new("test-key").with_transport(transport);
+
+        let mut counts = Vec::new();
+        let response = client
+            .stream_complete_with_usage(Request::new(vec![Message::user("Hi")]), |tokens| {
+                counts.push(tokens)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(counts, vec![2, 7]);
+        assert_eq!(response.usage.output_tokens, 7);
+        assert_eq!(response.text(), "Hello");
+    }
+
+    #[tokio::test]
+    async fn test_logger_sees_exchange_with_redacted_key() {
+        use std::sync::{Arc, Mutex};
+
+        let transport = MockTransport::new();
+        transport.push_response(200, CANNED_RESPONSE);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let client = Claude::new("sk-secret")
+            .with_transport(transport.clone())
+            .with_logger(move |event| sink.lock().unwrap().push(event.clone()));
+
+        client
+            .complete(Request::new(vec![Message::user("Capital of France?")]))
+            .await
+            .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        let LogEvent::Request(request) = &events[0] else {
+            panic!("expected request first, got {:?}", events[0]);
+        };
+        assert_eq!(request.header("x-api-key"), Some(REDACTED));
+        assert!(request.body.contains("Capital of France?"));
+        assert!(!format!("{events:?}").contains("sk-secret"));
+        assert!(matches!(
+            &events[1],
+            LogEvent::Response { status: 200, body } if body.contains("Paris.")
+        ));
+        assert!(matches!(&events[2], LogEvent::Usage(usage) if usage.input_tokens == 12));
+
+        // The transport itself still receives the real key.
+        assert_eq!(
+            transport.requests()[0].header("x-api-key"),
+            Some("sk-secret")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logger_sees_each_stream_event() {
+        use futures::StreamExt;
+        use std::sync::{Arc, Mutex};
+
+        let transport = MockTransport::new();
+        transport.push_response(
+            200,
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hello\"}}\n\n\
+             data: {\"type\":\"message_stop\"}\n\n",
+        );
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let client = Claude::new("test-key")
+            .with_transport(transport)
+            .with_logger(move |event| sink.lock().unwrap().push(event.clone()));
+
+        let _: Vec<_> = client
+            .stream(Request::new(vec![Message::user("Hi")]))
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        let events = events.lock().unwrap();
+        assert!(matches!(events[0], LogEvent::Request(_)));
+        assert!(matches!(
+            &events[1],
+            LogEvent::Stream(StreamEvent::TextDelta { text, .. }) if text == "Hello"
+        ));
+        assert!(matches!(
+            events[2],
+            LogEvent::Stream(StreamEvent::MessageStop)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_complete_tolerates_missing_message_stop() {
+        let transport = MockTransport::new();
+        transport.push_response(
+            200,
+            "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_01\",\"model\":\"m\"}}\n\n\
+             data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"The bridge sways\"}}\n\n",
+        );
+        let client = Claude::new("test-key").with_transport(transport);
+
+        let response = client
+            .stream_complete(Request::new(vec![Message::user("Hi")]))
+            .await
+            .unwrap();
+
+        assert!(response.truncated);
+        assert_eq!(response.stop_reason, StopReason::MaxTokens);
+        assert_eq!(response.text(), "The bridge sways");
     }
 }
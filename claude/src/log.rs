@@ -0,0 +1,39 @@
+//! Request/response logging hook.
+//!
+//! A logger installed with [`Claude::with_logger`](crate::Claude::with_logger)
+//! sees every exchange with the API as a series of [`LogEvent`]s, so callers
+//! can forward them to `tracing`, a file, or anywhere else.
+
+use std::sync::Arc;
+
+use crate::transport::HttpRequest;
+use crate::types::{StreamEvent, Usage};
+
+/// Placeholder substituted for the API key in logged requests.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Something that happened while talking to the API.
+#[derive(Debug, Clone)]
+pub enum LogEvent {
+    /// A request about to be sent, with the API key redacted.
+    Request(HttpRequest),
+    /// A raw response body as received.
+    Response { status: u16, body: String },
+    /// One parsed event of a streaming response.
+    Stream(StreamEvent),
+    /// Token usage reported for a completed request.
+    Usage(Usage),
+}
+
+pub(crate) type Logger = Arc<dyn Fn(&LogEvent) + Send + Sync>;
+
+/// A copy of the request whose `x-api-key` header is replaced by [`REDACTED`].
+pub(crate) fn redact(request: &HttpRequest) -> HttpRequest {
+    let mut request = request.clone();
+    for (name, value) in &mut request.headers {
+        if name.eq_ignore_ascii_case("x-api-key") {
+            *value = REDACTED.to_string();
+        }
+    }
+    request
+}
@@ -1,9 +1,10 @@
 //! SSE parsing and streaming types for the Claude API.
 
 use serde::Deserialize;
+use std::collections::BTreeMap;
 
 use crate::error::Error;
-use crate::types::{StopReason, StreamEvent};
+use crate::types::{ContentBlock, Response, StopReason, StreamEvent, Usage};
 
 // Internal streaming API types
 
@@ -26,6 +27,8 @@ pub(crate) enum ApiStreamEvent {
     },
     MessageDelta {
         delta: ApiMessageDelta,
+        #[serde(default)]
+        usage: Option<ApiDeltaUsage>,
     },
     MessageStop,
     Ping,
@@ -65,6 +68,12 @@ pub(crate) struct ApiMessageDelta {
     pub stop_reason: Option<String>,
 }
 
+/// Usage carried by `message_delta`; the count is cumulative for the message.
+#[derive(Debug, Deserialize)]
+pub(crate) struct ApiDeltaUsage {
+    pub output_tokens: usize,
+}
+
 #[derive(Debug, Deserialize)]
 pub(crate) struct ApiError {
     pub message: String,
@@ -110,6 +119,146 @@ pub(crate) fn parse_sse_events_buffered(buffer: &mut String) -> Vec<Result<Strea
     events
 }
 
+/// Builds a [`Response`] from a sequence of [`StreamEvent`]s.
+///
+/// A stream can end early (the connection drops, or the server never sends
+/// `message_stop`). [`finish`](Self::finish) still returns everything received,
+/// marked `truncated`, with a `MaxTokens` stop reason unless the server
+/// reported one.
+///
+/// The running output token count from `message_delta` events is available
+/// mid-stream through [`output_tokens`](Self::output_tokens).
+#[derive(Debug, Default)]
+pub struct StreamAccumulator {
+    id: String,
+    model: String,
+    blocks: BTreeMap<usize, PartialBlock>,
+    stop_reason: Option<StopReason>,
+    output_tokens: usize,
+    complete: bool,
+}
+
+#[derive(Debug)]
+enum PartialBlock {
+    Text(String),
+    ToolUse {
+        id: String,
+        name: String,
+        json: String,
+    },
+}
+
+impl StreamAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold one event into the response being built.
+    pub fn push(&mut self, event: &StreamEvent) {
+        match event {
+            StreamEvent::MessageStart { id, model } => {
+                self.id = id.clone();
+                self.model = model.clone();
+            }
+            StreamEvent::ContentBlockStart {
+                index,
+                content_type,
+                tool_use_id,
+                tool_name,
+            } => {
+                let block = if content_type == "tool_use" {
+                    PartialBlock::ToolUse {
+                        id: tool_use_id.clone().unwrap_or_default(),
+                        name: tool_name.clone().unwrap_or_default(),
+                        json: String::new(),
+                    }
+                } else {
+                    PartialBlock::Text(String::new())
+                };
+                self.blocks.insert(*index, block);
+            }
+            StreamEvent::TextDelta { index, text } => {
+                if let PartialBlock::Text(buffer) = self
+                    .blocks
+                    .entry(*index)
+                    .or_insert_with(|| PartialBlock::Text(String::new()))
+                {
+                    buffer.push_str(text);
+                }
+            }
+            StreamEvent::InputJsonDelta {
+                index,
+                partial_json,
+            } => {
+                if let Some(PartialBlock::ToolUse { json, .. }) = self.blocks.get_mut(index) {
+                    json.push_str(partial_json);
+                }
+            }
+            StreamEvent::MessageDelta {
+                stop_reason,
+                output_tokens,
+            } => {
+                if let Some(stop_reason) = stop_reason {
+                    self.stop_reason = Some(*stop_reason);
+                }
+                // Counts are cumulative, so a late or repeated event can't lower them
+                if let Some(output_tokens) = output_tokens {
+                    self.output_tokens = self.output_tokens.max(*output_tokens);
+                }
+            }
+            StreamEvent::MessageStop => self.complete = true,
+            _ => {}
+        }
+    }
+
+    /// Output tokens generated so far, as last reported by the server.
+    pub fn output_tokens(&self) -> usize {
+        self.output_tokens
+    }
+
+    /// Whether any content has been received.
+    pub fn has_content(&self) -> bool {
+        !self.blocks.is_empty()
+    }
+
+    /// Finalize the response from the events received so far.
+    pub fn finish(self) -> Response {
+        let truncated = !self.complete;
+        let content = self
+            .blocks
+            .into_values()
+            .filter_map(|block| match block {
+                PartialBlock::Text(text) => Some(ContentBlock::Text { text }),
+                // A tool call cut off mid-input can't be parsed, so it is dropped
+                PartialBlock::ToolUse { id, name, json } => {
+                    let input = if json.is_empty() {
+                        serde_json::json!({})
+                    } else {
+                        serde_json::from_str(&json).ok()?
+                    };
+                    Some(ContentBlock::ToolUse { id, name, input })
+                }
+            })
+            .collect();
+
+        Response {
+            id: self.id,
+            model: self.model,
+            content,
+            stop_reason: self.stop_reason.unwrap_or(if truncated {
+                StopReason::MaxTokens
+            } else {
+                StopReason::EndTurn
+            }),
+            usage: Usage {
+                input_tokens: 0,
+                output_tokens: self.output_tokens,
+            },
+            truncated,
+        }
+    }
+}
+
 pub(crate) fn convert_stream_event(event: ApiStreamEvent) -> StreamEvent {
     match event {
         ApiStreamEvent::MessageStart { message } => StreamEvent::MessageStart {
@@ -137,7 +286,7 @@ pub(crate) fn convert_stream_event(event: ApiStreamEvent) -> StreamEvent {
             },
         },
         ApiStreamEvent::ContentBlockStop { index } => StreamEvent::ContentBlockStop { index },
-        ApiStreamEvent::MessageDelta { delta } => StreamEvent::MessageDelta {
+        ApiStreamEvent::MessageDelta { delta, usage } => StreamEvent::MessageDelta {
             stop_reason: delta.stop_reason.map(|s| match s.as_str() {
                 "end_turn" => StopReason::EndTurn,
                 "max_tokens" => StopReason::MaxTokens,
@@ -145,6 +294,7 @@ pub(crate) fn convert_stream_event(event: ApiStreamEvent) -> StreamEvent {
                 "tool_use" => StopReason::ToolUse,
                 _ => StopReason::EndTurn,
             }),
+            output_tokens: usage.map(|usage| usage.output_tokens),
         },
         ApiStreamEvent::MessageStop => StreamEvent::MessageStop,
         ApiStreamEvent::Ping => StreamEvent::Ping,
@@ -153,3 +303,124 @@ pub(crate) fn convert_stream_event(event: ApiStreamEvent) -> StreamEvent {
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_delta(text: &str) -> StreamEvent {
+        StreamEvent::TextDelta {
+            index: 0,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_accumulator_finalizes_without_message_stop() {
+        let mut accumulator = StreamAccumulator::new();
+        for event in [
+            StreamEvent::MessageStart {
+                id: "msg_01".to_string(),
+                model: "m".to_string(),
+            },
+            StreamEvent::ContentBlockStart {
+                index: 0,
+                content_type: "text".to_string(),
+                tool_use_id: None,
+                tool_name: None,
+            },
+            text_delta("The dragon "),
+            text_delta("rears back"),
+        ] {
+            accumulator.push(&event);
+        }
+
+        let response = accumulator.finish();
+        assert!(response.truncated);
+        assert_eq!(response.stop_reason, StopReason::MaxTokens);
+        assert_eq!(response.text(), "The dragon rears back");
+        assert_eq!(response.id, "msg_01");
+    }
+
+    #[test]
+    fn test_accumulator_complete_stream() {
+        let mut accumulator = StreamAccumulator::new();
+        for event in [
+            text_delta("Done."),
+            StreamEvent::ContentBlockStart {
+                index: 1,
+                content_type: "tool_use".to_string(),
+                tool_use_id: Some("toolu_01".to_string()),
+                tool_name: Some("roll_dice".to_string()),
+            },
+            StreamEvent::InputJsonDelta {
+                index: 1,
+                partial_json: r#"{"notation": "1d20"}"#.to_string(),
+            },
+            StreamEvent::MessageDelta {
+                stop_reason: Some(StopReason::ToolUse),
+                output_tokens: None,
+            },
+            StreamEvent::MessageStop,
+        ] {
+            accumulator.push(&event);
+        }
+
+        let response = accumulator.finish();
+        assert!(!response.truncated);
+        assert_eq!(response.stop_reason, StopReason::ToolUse);
+        assert!(matches!(
+            &response.content[1],
+            ContentBlock::ToolUse { name, input, .. }
+                if name == "roll_dice" && input["notation"] == "1d20"
+        ));
+    }
+
+    #[test]
+    fn test_accumulator_drops_cut_off_tool_input() {
+        let mut accumulator = StreamAccumulator::new();
+        for event in [
+            text_delta("Rolling"),
+            StreamEvent::ContentBlockStart {
+                index: 1,
+                content_type: "tool_use".to_string(),
+                tool_use_id: Some("toolu_01".to_string()),
+                tool_name: Some("roll_dice".to_string()),
+            },
+            StreamEvent::InputJsonDelta {
+                index: 1,
+                partial_json: r#"{"nota"#.to_string(),
+            },
+        ] {
+            accumulator.push(&event);
+        }
+
+        let response = accumulator.finish();
+        assert!(response.truncated);
+        assert_eq!(response.content.len(), 1);
+    }
+
+    #[test]
+    fn test_output_tokens_rise_with_usage_deltas() {
+        let mut buffer = String::new();
+        for (stop_reason, tokens) in [("null", 3), ("null", 8), ("\"end_turn\"", 15)] {
+            buffer.push_str(&format!(
+                "event: message_delta\n\
+                 data: {{\"type\":\"message_delta\",\"delta\":{{\"stop_reason\":{stop_reason}}},\"usage\":{{\"output_tokens\":{tokens}}}}}\n\n"
+            ));
+        }
+        let events = parse_sse_events_buffered(&mut buffer);
+
+        let mut accumulator = StreamAccumulator::new();
+        let mut counts = Vec::new();
+        for event in events {
+            accumulator.push(&event.unwrap());
+            counts.push(accumulator.output_tokens());
+        }
+
+        assert_eq!(counts, vec![3, 8, 15]);
+        let response = accumulator.finish();
+        assert_eq!(response.stop_reason, StopReason::EndTurn);
+        assert_eq!(response.usage.output_tokens, 15);
+    }
+}
@@ -0,0 +1,175 @@
+//! HTTP transport abstraction.
+//!
+//! [`Claude`](crate::Claude) builds requests and parses responses, but hands
+//! the actual HTTP exchange to a [`Transport`]. The default is
+//! [`ReqwestTransport`]; [`MockTransport`] returns canned bodies so the client
+//! can be exercised without a network.
+
+use futures::future::BoxFuture;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use crate::error::Error;
+
+/// HTTP method of a [`HttpRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+/// An outgoing request to the API.
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// JSON request body (empty for GET requests).
+    pub body: String,
+}
+
+impl HttpRequest {
+    /// Value of the first header with the given name, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// A raw HTTP response: status code and a stream of body chunks.
+pub struct RawResponse {
+    pub status: u16,
+    pub body: BoxStream<'static, Result<Vec<u8>, Error>>,
+}
+
+impl RawResponse {
+    /// A response whose whole body arrives in one chunk.
+    pub fn from_body(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        let chunk: Result<Vec<u8>, Error> = Ok(body.into());
+        Self {
+            status,
+            body: futures::stream::iter([chunk]).boxed(),
+        }
+    }
+
+    /// Whether the status code is 2xx.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Collect the body into a string.
+    pub async fn text(mut self) -> Result<String, Error> {
+        let mut bytes = Vec::new();
+        while let Some(chunk) = self.body.next().await {
+            bytes.extend(chunk?);
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+/// Sends HTTP requests on behalf of the client.
+pub trait Transport: Send + Sync {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<RawResponse, Error>>;
+}
+
+/// The default transport, backed by `reqwest`.
+#[derive(Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport with HTTP timeouts of 120s total and 30s to connect.
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(120))
+                .connect_timeout(std::time::Duration::from_secs(30))
+                .build()
+                .expect("Failed to build HTTP client"),
+        }
+    }
+}
+
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<RawResponse, Error>> {
+        Box::pin(async move {
+            let mut builder = match request.method {
+                Method::Get => self.client.get(&request.url),
+                Method::Post => self.client.post(&request.url).body(request.body),
+            };
+            for (name, value) in &request.headers {
+                builder = builder.header(name, value);
+            }
+
+            let response = builder
+                .send()
+                .await
+                .map_err(|e| Error::Network(e.to_string()))?;
+
+            Ok(RawResponse {
+                status: response.status().as_u16(),
+                body: response
+                    .bytes_stream()
+                    .map(|chunk| {
+                        chunk
+                            .map(|bytes| bytes.to_vec())
+                            .map_err(|e| Error::Network(e.to_string()))
+                    })
+                    .boxed(),
+            })
+        })
+    }
+}
+
+/// A transport that replies with queued canned responses and records every
+/// request it receives.
+///
+/// Clones share the same queue and request log, so a test can keep one
+/// handle while the client owns another.
+#[derive(Clone, Default)]
+pub struct MockTransport {
+    responses: Arc<Mutex<VecDeque<(u16, String)>>>,
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response body to return with the given status.
+    pub fn push_response(&self, status: u16, body: impl Into<String>) {
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back((status, body.into()));
+    }
+
+    /// Requests sent so far, oldest first.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<RawResponse, Error>> {
+        self.requests.lock().unwrap().push(request);
+        let next = self.responses.lock().unwrap().pop_front();
+        Box::pin(async move {
+            let (status, body) =
+                next.ok_or_else(|| Error::Network("No canned response queued".to_string()))?;
+            Ok(RawResponse::from_body(status, body))
+        })
+    }
+}
@@ -1,5 +1,9 @@
 //! Public types for the Claude API client.
 
+use std::collections::HashSet;
+
+use crate::error::Error;
+
 /// A completion request to send to Claude.
 ///
 /// Use builder methods to configure the request. At minimum, provide messages via [`Request::new`].
@@ -73,6 +77,32 @@ impl Request {
         self.tool_choice = Some(tool_choice);
         self
     }
+
+    /// Checks for mistakes the API would reject the whole request for:
+    /// two tools sharing a name, or a tool whose input schema is not an
+    /// object schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidRequest`] naming the offending tool.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut names = HashSet::new();
+        for tool in self.tools.iter().flatten() {
+            if !names.insert(tool.name.as_str()) {
+                return Err(Error::InvalidRequest(format!(
+                    "tool '{}' is defined more than once",
+                    tool.name
+                )));
+            }
+            tool.validate_schema().map_err(|problem| {
+                Error::InvalidRequest(format!(
+                    "tool '{}' has an invalid input schema: {problem}",
+                    tool.name
+                ))
+            })?;
+        }
+        Ok(())
+    }
 }
 
 /// A message in the conversation.
@@ -127,7 +157,7 @@ pub enum ContentBlock {
     },
     ToolResult {
         tool_use_id: String,
-        content: String,
+        content: Vec<ContentBlock>,
         is_error: bool,
     },
     Thinking {
@@ -154,6 +184,40 @@ pub struct Tool {
     pub input_schema: serde_json::Value,
 }
 
+impl Tool {
+    /// The API accepts only object schemas: `"type": "object"`, with
+    /// `properties` an object and `required` a list of property names.
+    fn validate_schema(&self) -> Result<(), String> {
+        let schema = self
+            .input_schema
+            .as_object()
+            .ok_or("schema must be a JSON object")?;
+        if schema.get("type").and_then(|t| t.as_str()) != Some("object") {
+            return Err("schema type must be \"object\"".to_string());
+        }
+        let properties = match schema.get("properties") {
+            None => None,
+            Some(properties) => Some(
+                properties
+                    .as_object()
+                    .ok_or("\"properties\" must be an object")?,
+            ),
+        };
+        if let Some(required) = schema.get("required") {
+            let required = required.as_array().ok_or("\"required\" must be a list")?;
+            for name in required {
+                let name = name
+                    .as_str()
+                    .ok_or("\"required\" must list property names")?;
+                if !properties.is_some_and(|p| p.contains_key(name)) {
+                    return Err(format!("required property '{name}' is not defined"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Tool choice configuration.
 #[derive(Debug, Clone)]
 pub enum ToolChoice {
@@ -172,6 +236,9 @@ pub struct Response {
     pub content: Vec<ContentBlock>,
     pub stop_reason: StopReason,
     pub usage: Usage,
+    /// Whether a streamed response ended before `message_stop` arrived.
+    /// Content holds whatever was received.
+    pub truncated: bool,
 }
 
 impl Response {
@@ -212,26 +279,45 @@ pub struct ToolUse {
 }
 
 /// Result of executing a tool.
+///
+/// Content is usually a single text block, but may mix text and images.
 #[derive(Debug, Clone)]
 pub struct ToolResult {
-    pub content: String,
+    pub content: Vec<ContentBlock>,
     pub is_error: bool,
 }
 
 impl ToolResult {
     pub fn success(content: impl Into<String>) -> Self {
-        Self {
-            content: content.into(),
-            is_error: false,
-        }
+        Self::blocks(vec![ContentBlock::Text {
+            text: content.into(),
+        }])
     }
 
     pub fn error(content: impl Into<String>) -> Self {
         Self {
-            content: content.into(),
             is_error: true,
+            ..Self::success(content)
+        }
+    }
+
+    /// A successful result made of arbitrary content blocks, e.g. a caption
+    /// followed by an [`ContentBlock::Image`].
+    pub fn blocks(content: Vec<ContentBlock>) -> Self {
+        Self {
+            content,
+            is_error: false,
         }
     }
+
+    /// All text content concatenated, ignoring images.
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|block| block.as_text())
+            .collect::<Vec<_>>()
+            .join("")
+    }
 }
 
 /// Events from a streaming response.
@@ -262,6 +348,8 @@ pub enum StreamEvent {
     },
     MessageDelta {
         stop_reason: Option<StopReason>,
+        /// Output tokens generated so far, when the event reports usage.
+        output_tokens: Option<usize>,
     },
     MessageStop,
     Ping,